@@ -1,86 +1,258 @@
 //! Benchmarks for TA-Rust functions
+//!
+//! Each group runs one indicator over input sizes from 1k to 10M points, with one
+//! benchmark per implementation variant (allocating, `*_into` with a reused buffer,
+//! running-sum rolling, `f32`), so variants can be compared at equal size and
+//! regressions show up against a saved baseline:
+//!
+//! ```bash
+//! cargo bench -- --save-baseline main      # on the base branch
+//! cargo bench -- --baseline main           # on the change
+//! TA_BENCH_MAX_SIZE=100000 cargo bench sma # quick run on small inputs
+//! ```
+//!
+//! A SIMD variant of an indicator is added to its group as another benchmark.
+//!
+//! The `cross_section` group runs SMA, standard deviation and correlation to a market
+//! series over 1k and 10k symbols of one session each, through `batch::run` (and
+//! `run_parallel` with the `parallel` feature): the per-bar screening workload. With the
+//! `gpu` feature, the same kernels also run through `gpu::GpuContext`, timed from the
+//! host series to the results read back, when the machine has an adapter:
+//!
+//! ```bash
+//! cargo bench --features parallel,gpu cross_section
+//! ```
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use criterion::measurement::WallTime;
+use std::collections::HashMap;
+use ta_rust::batch::run;
+#[cfg(feature = "parallel")]
+use ta_rust::batch::run_parallel;
+use ta_rust::common::MAType;
+#[cfg(feature = "gpu")]
+use ta_rust::gpu::GpuContext;
+use ta_rust::momentum::{rsi, rsi_into, stoch};
+use ta_rust::overlap::{bbands, ema, ema_into, sma, sma_into, sma_rolling};
+use ta_rust::statistic::{correl, stddev_into};
+use ta_rust::volatility::{atr, atr_into};
 
-// Benchmark data generators
-fn generate_test_data(size: usize) -> Vec<f64> {
-    (0..size).map(|i| (i as f64 * 0.1).sin() * 100.0 + 100.0).collect()
+const SIZES: [usize; 5] = [1_000, 10_000, 100_000, 1_000_000, 10_000_000];
+const PERIOD: usize = 14;
+
+/// Sizes up to `TA_BENCH_MAX_SIZE`, if set
+fn sizes() -> Vec<usize> {
+    let max = std::env::var("TA_BENCH_MAX_SIZE").ok().and_then(|v| v.parse().ok()).unwrap_or(usize::MAX);
+    SIZES.into_iter().filter(|&size| size <= max).collect()
 }
 
-#[allow(dead_code)]
-fn generate_ohlc_data(size: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
-    let base_prices = generate_test_data(size);
-    let open: Vec<f64> = base_prices.iter().map(|&p| p * 0.99).collect();
-    let high: Vec<f64> = base_prices.iter().map(|&p| p * 1.02).collect();
-    let low: Vec<f64> = base_prices.iter().map(|&p| p * 0.98).collect();
-    let close = base_prices;
-    (open, high, low, close)
+/// Deterministic random-walk bars
+struct Bars {
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
 }
 
-// Placeholder benchmark functions (will be implemented as functions are added)
-fn bench_placeholder(c: &mut Criterion) {
-    let data = generate_test_data(1000);
-    
-    c.bench_function("placeholder", |b| {
-        b.iter(|| {
-            // Placeholder for actual function benchmarks
-            black_box(&data);
-        })
-    });
+impl Bars {
+    fn new(size: usize) -> Self {
+        // xorshift keeps the data reproducible without an RNG dependency
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        let mut price = 100.0;
+        let mut bars = Bars { high: Vec::with_capacity(size), low: Vec::with_capacity(size), close: Vec::with_capacity(size) };
+        for _ in 0..size {
+            price *= 1.0 + (next() - 0.5) * 0.02;
+            bars.high.push(price * (1.0 + next() * 0.01));
+            bars.low.push(price * (1.0 - next() * 0.01));
+            bars.close.push(price);
+        }
+        bars
+    }
 }
 
-// Benchmark different data sizes
-fn bench_data_sizes(c: &mut Criterion) {
-    let sizes = vec![100, 500, 1000, 5000, 10000];
-    
-    for size in sizes {
-        let data = generate_test_data(size);
-        
-        c.bench_with_input(
-            BenchmarkId::new("data_generation", size),
-            &size,
-            |b, &_size| {
-                b.iter(|| {
-                    black_box(&data);
-                })
-            },
-        );
+/// Builds the bars of each size once and lets `variants` register its benchmarks on them
+fn bench_sizes<F>(c: &mut Criterion, name: &str, mut variants: F)
+where
+    F: FnMut(&mut BenchmarkGroup<'_, WallTime>, usize, &Bars),
+{
+    let mut group = c.benchmark_group(name);
+    for size in sizes() {
+        if size >= 1_000_000 {
+            group.sample_size(10);
+        }
+        group.throughput(Throughput::Elements(size as u64));
+        variants(&mut group, size, &Bars::new(size));
     }
+    group.finish();
 }
 
-// Memory allocation benchmarks
-fn bench_memory_allocation(c: &mut Criterion) {
-    c.bench_function("vec_allocation_1000", |b| {
-        b.iter(|| {
-            let v: Vec<f64> = vec![0.0; 1000];
-            black_box(v);
-        })
+fn bench_sma(c: &mut Criterion) {
+    bench_sizes(c, "sma", |group, size, bars| {
+        let mut out = vec![0.0; size];
+        let close32: Vec<f32> = bars.close.iter().map(|&v| v as f32).collect();
+        let mut out32 = vec![0.0f32; size];
+
+        group.bench_with_input(BenchmarkId::new("scalar", size), &bars.close, |b, data| {
+            b.iter(|| sma(black_box(data), PERIOD).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("rolling", size), &bars.close, |b, data| {
+            b.iter(|| sma_rolling(black_box(data), PERIOD).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("into", size), &bars.close, |b, data| {
+            b.iter(|| sma_into(black_box(data), PERIOD, &mut out).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("f32", size), &close32, |b, data| {
+            b.iter(|| sma_into(black_box(data), PERIOD, &mut out32).unwrap())
+        });
     });
-    
-    c.bench_function("vec_with_capacity_1000", |b| {
-        b.iter(|| {
-            let mut v = Vec::with_capacity(1000);
-            for i in 0..1000 {
-                v.push(i as f64);
-            }
-            black_box(v);
-        })
+}
+
+fn bench_ema(c: &mut Criterion) {
+    bench_sizes(c, "ema", |group, size, bars| {
+        let mut out = vec![0.0; size];
+        let close32: Vec<f32> = bars.close.iter().map(|&v| v as f32).collect();
+        let mut out32 = vec![0.0f32; size];
+
+        group.bench_with_input(BenchmarkId::new("scalar", size), &bars.close, |b, data| {
+            b.iter(|| ema(black_box(data), PERIOD).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("into", size), &bars.close, |b, data| {
+            b.iter(|| ema_into(black_box(data), PERIOD, &mut out).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("f32", size), &close32, |b, data| {
+            b.iter(|| ema_into(black_box(data), PERIOD, &mut out32).unwrap())
+        });
     });
 }
 
-// Future benchmark groups will be added here as functions are implemented:
-// - bench_overlap_studies
-// - bench_momentum_indicators  
-// - bench_volume_indicators
-// - bench_volatility_indicators
-// - bench_pattern_recognition
-// - bench_math_functions
+fn bench_rsi(c: &mut Criterion) {
+    bench_sizes(c, "rsi", |group, size, bars| {
+        let mut out = vec![0.0; size];
+
+        group.bench_with_input(BenchmarkId::new("scalar", size), &bars.close, |b, data| {
+            b.iter(|| rsi(black_box(data), PERIOD).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("into", size), &bars.close, |b, data| {
+            b.iter(|| rsi_into(black_box(data), PERIOD, &mut out).unwrap())
+        });
+    });
+}
+
+fn bench_atr(c: &mut Criterion) {
+    bench_sizes(c, "atr", |group, size, bars| {
+        let mut out = vec![0.0; size];
+
+        group.bench_with_input(BenchmarkId::new("scalar", size), bars, |b, bars| {
+            b.iter(|| atr(black_box(&bars.high), &bars.low, &bars.close, PERIOD).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("into", size), bars, |b, bars| {
+            b.iter(|| atr_into(black_box(&bars.high), &bars.low, &bars.close, PERIOD, &mut out).unwrap())
+        });
+    });
+}
+
+fn bench_bbands(c: &mut Criterion) {
+    bench_sizes(c, "bbands", |group, size, bars| {
+        group.bench_with_input(BenchmarkId::new("scalar", size), &bars.close, |b, data| {
+            b.iter(|| bbands(black_box(data), 20, 2.0).unwrap())
+        });
+    });
+}
+
+fn bench_stoch(c: &mut Criterion) {
+    bench_sizes(c, "stoch", |group, size, bars| {
+        group.bench_with_input(BenchmarkId::new("scalar", size), bars, |b, bars| {
+            b.iter(|| stoch(black_box(&bars.high), &bars.low, &bars.close, 5, 3, MAType::SMA, 3, MAType::SMA).unwrap())
+        });
+    });
+}
+
+fn bench_correl(c: &mut Criterion) {
+    bench_sizes(c, "correl", |group, size, bars| {
+        group.bench_with_input(BenchmarkId::new("scalar", size), bars, |b, bars| {
+            b.iter(|| correl(black_box(&bars.high), &bars.low, 30).unwrap())
+        });
+    });
+}
+
+const SYMBOLS: [usize; 2] = [1_000, 10_000];
+/// One session of minute bars per symbol
+const SESSION: usize = 390;
+
+fn bench_cross_section(c: &mut Criterion) {
+    let max = std::env::var("TA_BENCH_MAX_SYMBOLS").ok().and_then(|v| v.parse().ok()).unwrap_or(usize::MAX);
+    let market = Bars::new(SESSION).close;
+    #[cfg(feature = "gpu")]
+    let gpu = GpuContext::new().map_err(|err| eprintln!("skipping GPU benchmarks: {}", err)).ok();
+    let mut group = c.benchmark_group("cross_section");
+    group.sample_size(10);
+    for symbols in SYMBOLS.into_iter().filter(|&symbols| symbols <= max) {
+        // Each symbol follows the market with its own noise
+        let noise = Bars::new(symbols * SESSION).close;
+        let series: HashMap<usize, Vec<f64>> = (0..symbols)
+            .map(|id| {
+                let own = &noise[id * SESSION..(id + 1) * SESSION];
+                (id, market.iter().zip(own).map(|(m, o)| m + o * 0.1).collect())
+            })
+            .collect();
+        let correl_into = |data: &[f64], out: &mut [f64]| {
+            out.copy_from_slice(&correl(data, &market, 30)?);
+            Ok(data.len() - 29)
+        };
+        group.throughput(Throughput::Elements((symbols * SESSION) as u64));
+
+        group.bench_with_input(BenchmarkId::new("sma", symbols), &series, |b, series| {
+            b.iter(|| run(|data, out| sma_into(data, 20, out), black_box(series)))
+        });
+        group.bench_with_input(BenchmarkId::new("stddev", symbols), &series, |b, series| {
+            b.iter(|| run(|data, out| stddev_into(data, 20, 1.0, out), black_box(series)))
+        });
+        group.bench_with_input(BenchmarkId::new("correl", symbols), &series, |b, series| {
+            b.iter(|| run(correl_into, black_box(series)))
+        });
+        #[cfg(feature = "parallel")]
+        {
+            group.bench_with_input(BenchmarkId::new("sma_parallel", symbols), &series, |b, series| {
+                b.iter(|| run_parallel(|data, out| sma_into(data, 20, out), black_box(series)))
+            });
+            group.bench_with_input(BenchmarkId::new("correl_parallel", symbols), &series, |b, series| {
+                b.iter(|| run_parallel(correl_into, black_box(series)))
+            });
+        }
+        #[cfg(feature = "gpu")]
+        if let Some(gpu) = &gpu {
+            let rows: Vec<&[f64]> = series.values().map(Vec::as_slice).collect();
+            let markets = vec![market.as_slice(); rows.len()];
+            group.bench_with_input(BenchmarkId::new("sma_gpu", symbols), &rows, |b, rows| {
+                b.iter(|| gpu.sma(black_box(rows), 20).unwrap())
+            });
+            group.bench_with_input(BenchmarkId::new("stddev_gpu", symbols), &rows, |b, rows| {
+                b.iter(|| gpu.stddev(black_box(rows), 20, 1.0).unwrap())
+            });
+            group.bench_with_input(BenchmarkId::new("correl_gpu", symbols), &rows, |b, rows| {
+                b.iter(|| gpu.correl(black_box(rows), &markets, 30).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
 
 criterion_group!(
     benches,
-    bench_placeholder,
-    bench_data_sizes,
-    bench_memory_allocation
+    bench_sma,
+    bench_ema,
+    bench_rsi,
+    bench_atr,
+    bench_bbands,
+    bench_stoch,
+    bench_correl,
+    bench_cross_section
 );
 
-criterion_main!(benches);
\ No newline at end of file
+criterion_main!(benches);
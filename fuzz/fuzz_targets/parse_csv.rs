@@ -0,0 +1,18 @@
+//! CSV loader on arbitrary text: must return an error, never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ta_rust::data::{parse_ohlcv_csv, OhlcvSchema, TimestampFormat};
+
+fuzz_target!(|input: (&str, bool, bool)| {
+    let (text, semicolon, iso) = input;
+    let schema = OhlcvSchema {
+        delimiter: if semicolon { ';' } else { ',' },
+        timestamp_format: if iso { TimestampFormat::Iso8601 } else { TimestampFormat::Auto },
+        ..Default::default()
+    };
+    if let Ok(series) = parse_ohlcv_csv(text, &schema) {
+        assert!(series.validate().is_ok());
+    }
+});
@@ -0,0 +1,12 @@
+//! JSON loader on arbitrary text: must return an error, never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ta_rust::data::{parse_ohlcv_json, OhlcvSchema};
+
+fuzz_target!(|text: &str| {
+    if let Ok(series) = parse_ohlcv_json(text, &OhlcvSchema::default()) {
+        assert!(series.validate().is_ok());
+    }
+});
@@ -0,0 +1,19 @@
+//! Every registered function on arbitrary data and parameters: errors are fine, panics
+//! and misaligned outputs are not
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ta_rust::common::registry::Registry;
+
+fuzz_target!(|input: (u8, Vec<f64>, Vec<f64>)| {
+    let (index, data, params) = input;
+    let functions = Registry::functions();
+    let info = &functions[index as usize % functions.len()];
+    let inputs: Vec<&[f64]> = info.inputs.iter().map(|_| data.as_slice()).collect();
+    let params = &params[..params.len().min(info.params.len())];
+
+    if let Ok(outputs) = info.call(&inputs, params) {
+        assert_eq!(outputs.len(), info.outputs.len(), "{}", info.name);
+    }
+});
@@ -0,0 +1,23 @@
+//! Resampling of arbitrary timestamped bars
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ta_rust::common::{resample, OhlcvSeries, PartialBar};
+
+fuzz_target!(|input: (Vec<(i64, f64, f64, f64, f64, f64)>, i64, bool)| {
+    let (bars, interval, drop) = input;
+    let series = OhlcvSeries {
+        timestamp: bars.iter().map(|b| b.0).collect(),
+        open: bars.iter().map(|b| b.1).collect(),
+        high: bars.iter().map(|b| b.2).collect(),
+        low: bars.iter().map(|b| b.3).collect(),
+        close: bars.iter().map(|b| b.4).collect(),
+        volume: bars.iter().map(|b| b.5).collect(),
+    };
+    let partial = if drop { PartialBar::Drop } else { PartialBar::Keep };
+    if let Ok(result) = resample(&series, interval, partial) {
+        assert!(result.validate().is_ok());
+        assert!(result.len() <= series.len());
+    }
+});
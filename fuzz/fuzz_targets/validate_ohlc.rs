@@ -0,0 +1,17 @@
+//! OHLC validation on arbitrary bars, including NaN, infinities and mismatched lengths
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ta_rust::common::{validate_ohlc, validate_prices};
+
+fuzz_target!(|input: (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>)| {
+    let (open, high, low, close) = input;
+    let ohlc_ok = validate_ohlc(&open, &high, &low, &close).is_ok();
+    if ohlc_ok {
+        for i in 0..close.len() {
+            assert!(low[i] <= open[i].min(close[i]) && high[i] >= open[i].max(close[i]));
+        }
+    }
+    let _ = validate_prices(&close, "close");
+});
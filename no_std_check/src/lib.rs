@@ -0,0 +1,46 @@
+//! Build check for the fixed-capacity streaming path without `std`
+//!
+//! CI builds this for `thumbv7em-none-eabihf`. The global allocator always fails, as on a
+//! target without a heap: [`SmaWindow`] keeps its window in an array, so updating it never
+//! allocates.
+
+#![no_std]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::panic::PanicInfo;
+use ta_rust::overlap::SmaWindow;
+
+/// Smooths `len` samples with a 20-sample SMA, writing NaN during warm-up
+///
+/// # Safety
+/// `input` and `output` must each point to `len` valid values.
+#[no_mangle]
+pub unsafe extern "C" fn smooth_samples(input: *const f64, output: *mut f64, len: usize) {
+    let input = core::slice::from_raw_parts(input, len);
+    let output = core::slice::from_raw_parts_mut(output, len);
+    let mut sma = SmaWindow::<20>::new();
+    for (smoothed, &sample) in output.iter_mut().zip(input) {
+        *smoothed = sma.update(sample).unwrap_or(f64::NAN);
+    }
+}
+
+/// An allocator without memory; the library links `alloc` but the streaming path never uses it
+struct NoHeap;
+
+unsafe impl GlobalAlloc for NoHeap {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NoHeap = NoHeap;
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -0,0 +1,254 @@
+//! Long-only backtest loop
+
+use crate::backtest::BacktestStats;
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_same_length};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Backtest settings
+///
+/// Construct with struct update syntax over the defaults, e.g.
+/// `BacktestConfig { fee_rate: 0.001, ..Default::default() }`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BacktestConfig {
+    /// Starting equity (default 10,000)
+    pub initial_capital: f64,
+    /// Fee per fill as a fraction of traded value (default 0)
+    pub fee_rate: f64,
+    /// Adverse price move per fill as a fraction of price (default 0)
+    pub slippage: f64,
+    /// Bars per year, used to annualize CAGR, Sharpe and Sortino (default 252)
+    pub periods_per_year: f64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            initial_capital: 10_000.0,
+            fee_rate: 0.0,
+            slippage: 0.0,
+            periods_per_year: 252.0,
+        }
+    }
+}
+
+/// A closed trade
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trade {
+    /// Bar at whose open the position was bought
+    pub entry_index: usize,
+    /// Fill price of the entry, including slippage
+    pub entry_price: f64,
+    /// Bar at whose open the position was sold
+    pub exit_index: usize,
+    /// Fill price of the exit, including slippage
+    pub exit_price: f64,
+    /// Profit in currency, after fees on both fills
+    pub profit: f64,
+    /// Profit as a percentage of the capital committed at entry
+    pub return_pct: f64,
+}
+
+/// Backtest result
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BacktestResult {
+    /// Equity marked at each bar's close
+    pub equity: Vec<f64>,
+    /// Closed trades in order; a position still open at the end is only reflected in `equity`
+    pub trades: Vec<Trade>,
+    /// Summary statistics
+    pub stats: BacktestStats,
+}
+
+/// Backtest entry and exit signals on a long-only, fully invested position
+///
+/// Signals are read at a bar's close and filled at the next bar's open, so a strategy never
+/// trades on the bar that generated its signal. While flat, an entry signal buys with all
+/// available equity; while long, an exit signal sells the whole position. A bar signalling
+/// both entry and exit is ignored when flat.
+///
+/// Each fill is moved against the trader by `slippage` and pays `fee_rate` of its value.
+///
+/// # Arguments
+/// * `open` - Open prices, used for fills
+/// * `close` - Close prices, used to mark equity
+/// * `entries` - Entry signal per bar
+/// * `exits` - Exit signal per bar
+/// * `config` - Capital, costs and annualization
+///
+/// # Returns
+/// * `Ok(BacktestResult)` - Equity curve, closed trades and statistics
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::backtest::{backtest, BacktestConfig};
+///
+/// let open = vec![10.0, 10.0, 11.0, 12.0, 12.0];
+/// let close = vec![10.0, 11.0, 12.0, 12.0, 13.0];
+/// let entries = vec![true, false, false, false, false];
+/// let exits = vec![false, false, true, false, false];
+///
+/// let result = backtest(&open, &close, &entries, &exits, &BacktestConfig::default()).unwrap();
+/// // Bought at 10 on bar 1, sold at 12 on bar 3
+/// assert_eq!(result.trades.len(), 1);
+/// assert!((result.trades[0].return_pct - 20.0).abs() < 1e-9);
+/// assert_eq!(result.equity[4], 12_000.0);
+/// ```
+pub fn backtest(
+    open: &[f64],
+    close: &[f64],
+    entries: &[bool],
+    exits: &[bool],
+    config: &BacktestConfig,
+) -> TAResult<BacktestResult> {
+    with_function("BACKTEST", || {
+        validate_not_empty(close, "close")?;
+        validate_same_length(open, close, "open", "close")?;
+        validate_same_length(entries, close, "entries", "close")?;
+        validate_same_length(exits, close, "exits", "close")?;
+
+        if open.iter().chain(close).any(|&p| !(p.is_finite() && p > 0.0)) {
+            return Err(TAError::invalid_input("Prices must be positive finite numbers"));
+        }
+
+        if !(config.initial_capital.is_finite() && config.initial_capital > 0.0) {
+            return Err(TAError::invalid_parameter("initial_capital", "must be a positive finite number"));
+        }
+
+        if !(0.0..1.0).contains(&config.fee_rate) {
+            return Err(TAError::invalid_parameter("fee_rate", "must be in [0, 1)"));
+        }
+
+        if !(0.0..1.0).contains(&config.slippage) {
+            return Err(TAError::invalid_parameter("slippage", "must be in [0, 1)"));
+        }
+
+        if !(config.periods_per_year.is_finite() && config.periods_per_year > 0.0) {
+            return Err(TAError::invalid_parameter("periods_per_year", "must be a positive finite number"));
+        }
+
+        let mut cash = config.initial_capital;
+        let mut equity = Vec::with_capacity(close.len());
+        let mut trades = Vec::new();
+        // Units held, entry bar, entry fill and capital committed at entry
+        let mut position: Option<(f64, usize, f64, f64)> = None;
+
+        for i in 0..close.len() {
+            if i > 0 {
+                match position {
+                    None if entries[i - 1] && !exits[i - 1] => {
+                        let fill = open[i] * (1.0 + config.slippage);
+                        let units = cash / (fill * (1.0 + config.fee_rate));
+                        position = Some((units, i, fill, cash));
+                        cash = 0.0;
+                    }
+                    Some((units, entry_index, entry_price, committed)) if exits[i - 1] => {
+                        let fill = open[i] * (1.0 - config.slippage);
+                        cash = units * fill * (1.0 - config.fee_rate);
+                        trades.push(Trade {
+                            entry_index,
+                            entry_price,
+                            exit_index: i,
+                            exit_price: fill,
+                            profit: cash - committed,
+                            return_pct: 100.0 * (cash / committed - 1.0),
+                        });
+                        position = None;
+                    }
+                    _ => {}
+                }
+            }
+
+            let held = position.map_or(0.0, |(units, ..)| units * close[i]);
+            equity.push(cash + held);
+        }
+
+        let stats = BacktestStats::compute(&equity, &trades, config.periods_per_year);
+        Ok(BacktestResult { equity, trades, stats })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::overlap::sma;
+    use crate::signal::{cross_over, cross_under};
+
+    #[test]
+    fn test_backtest_fills_next_open() {
+        let open = vec![10.0, 10.0, 11.0, 12.0, 12.0, 11.0];
+        let close = vec![10.0, 11.0, 12.0, 12.0, 11.5, 11.0];
+        let entries = vec![true, false, false, true, false, false];
+        let exits = vec![false, false, true, false, false, false];
+        let result = backtest(&open, &close, &entries, &exits, &BacktestConfig::default()).unwrap();
+
+        assert_eq!(result.trades.len(), 1);
+        let trade = result.trades[0];
+        assert_eq!((trade.entry_index, trade.exit_index), (1, 3));
+        assert_float_eq!(trade.profit, 2_000.0, 1e-9);
+        assert_float_eq!(result.equity[0], 10_000.0, 1e-9);
+        assert_float_eq!(result.equity[2], 12_000.0, 1e-9);
+
+        // Re-entered at bar 4's open (12) and still open at the end
+        assert_float_eq!(result.equity[5], 12_000.0 * 11.0 / 12.0, 1e-9);
+        assert_float_eq!(result.stats.total_return, 10.0, 1e-9);
+        assert_float_eq!(result.stats.win_rate, 100.0, 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_costs() {
+        let open = vec![100.0, 100.0, 100.0];
+        let close = vec![100.0, 100.0, 100.0];
+        let config = BacktestConfig { fee_rate: 0.001, slippage: 0.002, ..Default::default() };
+        let result = backtest(&open, &close, &[true, true, false], &[false, true, false], &config).unwrap();
+
+        let trade = result.trades[0];
+        assert_float_eq!(trade.entry_price, 100.2, 1e-12);
+        assert_float_eq!(trade.exit_price, 99.8, 1e-12);
+        let units = 10_000.0 / (100.2 * 1.001);
+        assert_float_eq!(result.equity[2], units * 99.8 * 0.999, 1e-9);
+        assert!(trade.profit < 0.0);
+        assert_float_eq!(result.stats.profit_factor, 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_moving_average_cross() {
+        let close: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.1).sin() * 10.0 + i as f64 * 0.05).collect();
+        let open: Vec<f64> = core::iter::once(close[0]).chain(close[..119].iter().copied()).collect();
+        let fast = sma(&close, 5).unwrap();
+        let slow = sma(&close, 20).unwrap();
+        let entries = cross_over(&fast, &slow).unwrap();
+        let exits = cross_under(&fast, &slow).unwrap();
+
+        let result = backtest(&open, &close, &entries, &exits, &BacktestConfig::default()).unwrap();
+        assert_eq!(result.equity.len(), 120);
+        assert!(!result.trades.is_empty());
+        for pair in result.trades.windows(2) {
+            assert!(pair[0].exit_index <= pair[1].entry_index);
+        }
+        // Flat until the first entry
+        let first_entry = result.trades[0].entry_index;
+        assert!(result.equity[..first_entry].iter().all(|&e| e == 10_000.0));
+    }
+
+    #[test]
+    fn test_backtest_invalid_input() {
+        let prices = vec![1.0, 2.0, 3.0];
+        let signals = vec![false; 3];
+        let config = BacktestConfig::default();
+        assert_eq!(backtest(&[], &[], &[], &[], &config).unwrap_err().function(), Some("BACKTEST"));
+        assert!(backtest(&prices, &prices, &signals[..2], &signals, &config).is_err());
+        assert!(backtest(&prices, &[1.0, f64::NAN, 3.0], &signals, &signals, &config).is_err());
+        let err = backtest(&prices, &prices, &signals, &signals, &BacktestConfig { fee_rate: -0.1, ..config }).unwrap_err();
+        assert_eq!(err.function(), Some("BACKTEST"));
+        assert!(backtest(&prices, &prices, &signals, &signals, &BacktestConfig { initial_capital: 0.0, ..config }).is_err());
+        assert!(backtest(&prices, &prices, &signals, &signals, &BacktestConfig { periods_per_year: 0.0, ..config }).is_err());
+    }
+}
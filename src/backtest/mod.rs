@@ -0,0 +1,11 @@
+//! Backtesting
+//!
+//! A minimal loop for evaluating entry/exit signals built from this crate's indicators:
+//! it produces an equity curve, the list of closed trades and summary statistics, with
+//! configurable fees and slippage.
+
+pub mod engine;
+pub mod stats;
+
+pub use engine::*;
+pub use stats::*;
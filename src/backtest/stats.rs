@@ -0,0 +1,121 @@
+//! Backtest summary statistics
+
+use crate::backtest::Trade;
+use crate::performance::{annualized_return_of, sharpe_of, sortino_of};
+use crate::volatility::max_drawdown;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Summary statistics of a backtest
+///
+/// Returns, drawdown and win rate are percentages. Ratios that are undefined for the run
+/// (no trades, a flat equity curve, a single bar) are NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BacktestStats {
+    /// Change of equity over the whole run, in percent
+    pub total_return: f64,
+    /// Compound annual growth rate, in percent
+    pub cagr: f64,
+    /// Deepest peak-to-trough decline of the equity curve, in percent
+    pub max_drawdown: f64,
+    /// Number of closed trades
+    pub trades: usize,
+    /// Share of closed trades with a positive profit, in percent
+    pub win_rate: f64,
+    /// Gross profit of winning trades over gross loss of losing trades
+    pub profit_factor: f64,
+    /// Annualized Sharpe ratio of bar returns (zero risk-free rate)
+    pub sharpe: f64,
+    /// Annualized Sortino ratio of bar returns (zero target return)
+    pub sortino: f64,
+}
+
+impl BacktestStats {
+    /// Computes the statistics of an equity curve and its closed trades
+    pub(crate) fn compute(equity: &[f64], trades: &[Trade], periods_per_year: f64) -> Self {
+        let first = equity[0];
+        let last = equity[equity.len() - 1];
+        let returns: Vec<f64> = equity.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+
+        let wins = trades.iter().filter(|t| t.profit > 0.0).count();
+        let gross_profit: f64 = trades.iter().map(|t| t.profit.max(0.0)).sum();
+        let gross_loss: f64 = trades.iter().map(|t| (-t.profit).max(0.0)).sum();
+
+        let (win_rate, profit_factor) = if trades.is_empty() {
+            (f64::NAN, f64::NAN)
+        } else if gross_loss > 0.0 {
+            (100.0 * wins as f64 / trades.len() as f64, gross_profit / gross_loss)
+        } else if gross_profit > 0.0 {
+            (100.0 * wins as f64 / trades.len() as f64, f64::INFINITY)
+        } else {
+            (100.0 * wins as f64 / trades.len() as f64, f64::NAN)
+        };
+
+        Self {
+            total_return: 100.0 * (last / first - 1.0),
+            cagr: annualized_return_of(&returns, periods_per_year),
+            max_drawdown: max_drawdown(equity).unwrap_or(f64::NAN),
+            trades: trades.len(),
+            win_rate,
+            profit_factor,
+            sharpe: sharpe_of(&returns, 0.0, periods_per_year),
+            sortino: sortino_of(&returns, 0.0, periods_per_year),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::performance::{sharpe_ratio, sortino_ratio};
+
+    fn trade(profit: f64) -> Trade {
+        Trade {
+            entry_index: 0,
+            entry_price: 1.0,
+            exit_index: 1,
+            exit_price: 1.0,
+            profit,
+            return_pct: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_stats_trades() {
+        let equity = vec![100.0, 110.0, 99.0, 121.0];
+        let stats = BacktestStats::compute(&equity, &[trade(30.0), trade(-10.0), trade(5.0)], 252.0);
+
+        assert_float_eq!(stats.total_return, 21.0, 1e-9);
+        assert_float_eq!(stats.max_drawdown, 10.0, 1e-9);
+        assert_eq!(stats.trades, 3);
+        assert_float_eq!(stats.win_rate, 200.0 / 3.0, 1e-9);
+        assert_float_eq!(stats.profit_factor, 3.5, 1e-12);
+        // Three bars of returns at 3 bars per year is one year
+        let stats = BacktestStats::compute(&equity, &[], 3.0);
+        assert_float_eq!(stats.cagr, 21.0, 1e-9);
+        assert!(stats.win_rate.is_nan() && stats.profit_factor.is_nan());
+    }
+
+    #[test]
+    fn test_stats_risk_ratios() {
+        let equity = vec![100.0, 101.0, 98.98, 101.9494, 101.9494];
+        let stats = BacktestStats::compute(&equity, &[], 4.0);
+
+        // Returns of 1%, -2%, 3% and 0%
+        let mean = 0.005;
+        let sd = ((0.005f64.powi(2) + 0.025f64.powi(2) + 0.025f64.powi(2) + 0.005f64.powi(2)) / 3.0).sqrt();
+        assert_float_eq!(stats.sharpe, mean / sd * 2.0, 1e-9);
+        assert_float_eq!(stats.sortino, mean / (0.0004f64 / 4.0).sqrt() * 2.0, 1e-9);
+
+        let returns = [0.01, -0.02, 0.03, 0.0];
+        assert_float_eq!(stats.sharpe, sharpe_ratio(&returns, 0.0, 4.0).unwrap(), 1e-9);
+        assert_float_eq!(stats.sortino, sortino_ratio(&returns, 0.0, 4.0).unwrap(), 1e-9);
+
+        let stats = BacktestStats::compute(&[100.0, 100.0, 100.0], &[], 252.0);
+        assert!(stats.sharpe.is_nan() && stats.sortino.is_nan());
+        assert!(BacktestStats::compute(&[100.0], &[trade(1.0)], 252.0).cagr.is_nan());
+        assert!(BacktestStats::compute(&[100.0, 101.0], &[trade(1.0)], 252.0).profit_factor.is_infinite());
+    }
+}
@@ -0,0 +1,17 @@
+//! Batch computation
+//!
+//! Helpers for computing many indicators at once. A [`ComputePlan`] evaluates several
+//! indicators on one series, computing the intermediate series they share (moving
+//! averages, true range, rolling extremes) only once. [`run`] evaluates one indicator
+//! over the series of many symbols. The [`sweep`] functions compute one indicator for
+//! many periods in a single pass.
+
+pub mod plan;
+pub mod sweep;
+#[cfg(feature = "std")]
+pub mod runner;
+
+pub use plan::*;
+pub use sweep::*;
+#[cfg(feature = "std")]
+pub use runner::*;
@@ -0,0 +1,711 @@
+//! Multi-indicator computation plans
+//!
+//! Indicators computed on the same series often start from the same intermediate results:
+//! MACD, PPO and APO on periods 12 and 26 all need the 12 and 26 period EMAs, ATR and NATR
+//! the true range, Williams' %R and Donchian Channels the rolling highest high and lowest
+//! low. A [`ComputePlan`] collects the requested indicators, computes each distinct
+//! intermediate once and returns every output in a [`PlanResult`] keyed by name.
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+use core::ops::Index;
+
+use crate::common::registry::{FunctionInfo, Registry};
+use crate::common::{ema_multiplier, validate_hlc_with, validate_not_empty, HlcValidation, MAType, TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::math_operators::{max, min};
+use crate::momentum::macd::{aligned_fast_ema, macd_from_emas};
+use crate::momentum::price_oscillator::oscillator_from_mas;
+use crate::momentum::rsi;
+use crate::momentum::willr::willr_from_extremes;
+use crate::overlap::bbands::bands_from;
+use crate::overlap::{ma, wilder_smooth};
+use crate::statistic::stddev;
+use crate::volatility::natr::natr_from_atr;
+use crate::volatility::trange;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// An indicator that can be added to a [`ComputePlan`]
+///
+/// Each indicator is computed exactly as by the function of the same name. Its outputs
+/// are keyed by its [`Display`](fmt::Display) form, e.g. `EMA(12)`, followed by the
+/// output name for indicators with several outputs, e.g. `MACD(12,26,9).signal`; see
+/// [`Indicator::output_keys`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Indicator {
+    /// Simple Moving Average of the close
+    Sma(usize),
+    /// Exponential Moving Average of the close
+    Ema(usize),
+    /// Moving average of the close of any type
+    Ma {
+        /// Period of the average
+        period: usize,
+        /// Type of the average
+        ma_type: MAType,
+    },
+    /// Rolling population standard deviation of the close
+    StdDev(usize),
+    /// Highest close over the period
+    Max(usize),
+    /// Lowest close over the period
+    Min(usize),
+    /// Relative Strength Index
+    Rsi(usize),
+    /// MACD line, signal and histogram
+    Macd {
+        /// Fast EMA period
+        fast: usize,
+        /// Slow EMA period
+        slow: usize,
+        /// Signal EMA period
+        signal: usize,
+    },
+    /// Absolute Price Oscillator
+    Apo {
+        /// Fast moving average period
+        fast: usize,
+        /// Slow moving average period
+        slow: usize,
+        /// Type of both moving averages
+        ma_type: MAType,
+    },
+    /// Percentage Price Oscillator
+    Ppo {
+        /// Fast moving average period
+        fast: usize,
+        /// Slow moving average period
+        slow: usize,
+        /// Type of both moving averages
+        ma_type: MAType,
+    },
+    /// Bollinger Bands, as computed by [`bbands_ma`](crate::overlap::bbands_ma)
+    Bbands {
+        /// Period of the middle band and the standard deviation
+        period: usize,
+        /// Standard deviations above the middle band
+        nb_dev_up: f64,
+        /// Standard deviations below the middle band
+        nb_dev_dn: f64,
+        /// Type of the middle band moving average
+        ma_type: MAType,
+    },
+    /// True Range (needs high, low and close)
+    Trange,
+    /// Average True Range (needs high, low and close)
+    Atr(usize),
+    /// Normalized Average True Range (needs high, low and close)
+    Natr(usize),
+    /// Williams' %R (needs high, low and close)
+    Willr(usize),
+    /// Donchian Channels (needs high and low)
+    Donchian(usize),
+}
+
+impl Indicator {
+    /// Names of the outputs, empty for indicators with a single output
+    pub fn output_names(&self) -> &'static [&'static str] {
+        match self {
+            Indicator::Macd { .. } => &["macd", "signal", "histogram"],
+            Indicator::Bbands { .. } | Indicator::Donchian(_) => &["upper", "middle", "lower"],
+            _ => &[],
+        }
+    }
+
+    /// Keys of the outputs in a [`PlanResult`]
+    ///
+    /// # Examples
+    /// ```
+    /// use ta_rust::batch::Indicator;
+    ///
+    /// assert_eq!(Indicator::Ema(12).output_keys(), vec!["EMA(12)"]);
+    /// let macd = Indicator::Macd { fast: 12, slow: 26, signal: 9 };
+    /// assert_eq!(macd.output_keys()[2], "MACD(12,26,9).histogram");
+    /// ```
+    pub fn output_keys(&self) -> Vec<String> {
+        match self.output_names() {
+            [] => vec![self.to_string()],
+            names => names.iter().map(|name| format!("{}.{}", self, name)).collect(),
+        }
+    }
+
+    /// Whether the indicator needs high and low prices besides the close
+    pub fn needs_hlc(&self) -> bool {
+        matches!(
+            self,
+            Indicator::Trange | Indicator::Atr(_) | Indicator::Natr(_) | Indicator::Willr(_) | Indicator::Donchian(_)
+        )
+    }
+
+    /// Name of the indicator, recorded in the errors of a plan computing it
+    pub fn name(&self) -> &'static str {
+        match self {
+            Indicator::Sma(_) => "SMA",
+            Indicator::Ema(_) => "EMA",
+            Indicator::Ma { .. } => "MA",
+            Indicator::StdDev(_) => "STDDEV",
+            Indicator::Max(_) => "MAX",
+            Indicator::Min(_) => "MIN",
+            Indicator::Rsi(_) => "RSI",
+            Indicator::Macd { .. } => "MACD",
+            Indicator::Apo { .. } => "APO",
+            Indicator::Ppo { .. } => "PPO",
+            Indicator::Bbands { .. } => "BBANDS",
+            Indicator::Trange => "TRANGE",
+            Indicator::Atr(_) => "ATR",
+            Indicator::Natr(_) => "NATR",
+            Indicator::Willr(_) => "WILLR",
+            Indicator::Donchian(_) => "DONCHIAN",
+        }
+    }
+
+    /// Intermediate series the indicator is computed from, dependencies first
+    fn intermediates(&self) -> Vec<Intermediate> {
+        use Intermediate::*;
+        match *self {
+            Indicator::Sma(period) => vec![Ma(period, MAType::SMA)],
+            Indicator::Ema(period) => vec![Ma(period, MAType::EMA)],
+            Indicator::Ma { period, ma_type } => vec![Ma(period, ma_type)],
+            Indicator::StdDev(period) => vec![StdDev(period)],
+            Indicator::Max(period) => vec![Highest(Source::Close, period)],
+            Indicator::Min(period) => vec![Lowest(Source::Close, period)],
+            Indicator::Rsi(_) => vec![],
+            Indicator::Macd { slow, .. } => vec![Ma(slow, MAType::EMA)],
+            Indicator::Apo { fast, slow, ma_type } | Indicator::Ppo { fast, slow, ma_type } => {
+                vec![Ma(fast, ma_type), Ma(slow, ma_type)]
+            }
+            Indicator::Bbands { period, ma_type, .. } => vec![Ma(period, ma_type), StdDev(period)],
+            Indicator::Trange => vec![TrueRange],
+            Indicator::Atr(period) | Indicator::Natr(period) => vec![TrueRange, Atr(period)],
+            Indicator::Willr(period) | Indicator::Donchian(period) => {
+                vec![Highest(Source::High, period), Lowest(Source::Low, period)]
+            }
+        }
+    }
+
+    /// Outputs of the indicator, from intermediates already in `cache`
+    fn compute(&self, cache: &Intermediates<'_>) -> TAResult<Vec<Vec<f64>>> {
+        Ok(match *self {
+            Indicator::Sma(period) => vec![cache.get(Intermediate::Ma(period, MAType::SMA)).to_vec()],
+            Indicator::Ema(period) => vec![cache.get(Intermediate::Ma(period, MAType::EMA)).to_vec()],
+            Indicator::Ma { period, ma_type } => vec![cache.get(Intermediate::Ma(period, ma_type)).to_vec()],
+            Indicator::StdDev(period) => vec![cache.get(Intermediate::StdDev(period)).to_vec()],
+            Indicator::Max(period) => vec![cache.get(Intermediate::Highest(Source::Close, period)).to_vec()],
+            Indicator::Min(period) => vec![cache.get(Intermediate::Lowest(Source::Close, period)).to_vec()],
+            Indicator::Rsi(period) => vec![rsi(cache.close, period)?],
+            Indicator::Macd { fast, slow, signal } => {
+                // The fast EMA is seeded to end with the slow one, unlike a plain EMA
+                let (line, signal, histogram) = macd_from_emas(
+                    &aligned_fast_ema(cache.close, ema_multiplier(fast), fast, slow),
+                    cache.get(Intermediate::Ma(slow, MAType::EMA)),
+                    slow,
+                    signal,
+                )?;
+                vec![line, signal, histogram]
+            }
+            Indicator::Apo { fast, slow, ma_type } => vec![oscillator_from_mas(
+                cache.get(Intermediate::Ma(fast, ma_type)),
+                cache.get(Intermediate::Ma(slow, ma_type)),
+                slow,
+                false,
+            )],
+            Indicator::Ppo { fast, slow, ma_type } => vec![oscillator_from_mas(
+                cache.get(Intermediate::Ma(fast, ma_type)),
+                cache.get(Intermediate::Ma(slow, ma_type)),
+                slow,
+                true,
+            )],
+            Indicator::Bbands { period, nb_dev_up, nb_dev_dn, ma_type } => {
+                if nb_dev_up < 0.0 || nb_dev_dn < 0.0 {
+                    return Err(TAError::invalid_parameter("std_dev_multiplier", "must be non-negative"));
+                }
+                let bands = bands_from(
+                    cache.get(Intermediate::Ma(period, ma_type)).to_vec(),
+                    cache.get(Intermediate::StdDev(period)),
+                    nb_dev_up,
+                    nb_dev_dn,
+                );
+                vec![bands.upper, bands.middle, bands.lower]
+            }
+            Indicator::Trange => vec![cache.get(Intermediate::TrueRange).to_vec()],
+            Indicator::Atr(period) => vec![cache.get(Intermediate::Atr(period)).to_vec()],
+            Indicator::Natr(period) => vec![natr_from_atr(cache.get(Intermediate::Atr(period)), cache.close)],
+            Indicator::Willr(period) => vec![willr_from_extremes(
+                cache.get(Intermediate::Highest(Source::High, period)),
+                cache.get(Intermediate::Lowest(Source::Low, period)),
+                cache.close,
+            )],
+            Indicator::Donchian(period) => {
+                let upper = cache.get(Intermediate::Highest(Source::High, period));
+                let lower = cache.get(Intermediate::Lowest(Source::Low, period));
+                let middle = upper.iter().zip(lower).map(|(&up, &lo)| (up + lo) / 2.0).collect();
+                vec![upper.to_vec(), middle, lower.to_vec()]
+            }
+        })
+    }
+}
+
+impl fmt::Display for Indicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Indicator::Sma(period) => write!(f, "SMA({})", period),
+            Indicator::Ema(period) => write!(f, "EMA({})", period),
+            Indicator::Ma { period, ma_type } => write!(f, "{}({})", ma_type, period),
+            Indicator::StdDev(period) => write!(f, "STDDEV({})", period),
+            Indicator::Max(period) => write!(f, "MAX({})", period),
+            Indicator::Min(period) => write!(f, "MIN({})", period),
+            Indicator::Rsi(period) => write!(f, "RSI({})", period),
+            Indicator::Macd { fast, slow, signal } => write!(f, "MACD({},{},{})", fast, slow, signal),
+            Indicator::Apo { fast, slow, ma_type } => write!(f, "APO({},{},{})", fast, slow, ma_type),
+            Indicator::Ppo { fast, slow, ma_type } => write!(f, "PPO({},{},{})", fast, slow, ma_type),
+            Indicator::Bbands { period, nb_dev_up, nb_dev_dn, ma_type } => {
+                write!(f, "BBANDS({},{},{},{})", period, nb_dev_up, nb_dev_dn, ma_type)
+            }
+            Indicator::Trange => write!(f, "TRANGE"),
+            Indicator::Atr(period) => write!(f, "ATR({})", period),
+            Indicator::Natr(period) => write!(f, "NATR({})", period),
+            Indicator::Willr(period) => write!(f, "WILLR({})", period),
+            Indicator::Donchian(period) => write!(f, "DONCHIAN({})", period),
+        }
+    }
+}
+
+/// Input series of an intermediate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    High,
+    Low,
+    Close,
+}
+
+/// A series shared between indicators of a plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Intermediate {
+    Ma(usize, MAType),
+    StdDev(usize),
+    TrueRange,
+    /// Wilder's average of the true range, computed after `TrueRange`
+    Atr(usize),
+    Highest(Source, usize),
+    Lowest(Source, usize),
+}
+
+/// Intermediate series computed so far, in dependency order
+struct Intermediates<'a> {
+    high: &'a [f64],
+    low: &'a [f64],
+    close: &'a [f64],
+    values: Vec<(Intermediate, Vec<f64>)>,
+}
+
+impl<'a> Intermediates<'a> {
+    fn source(&self, source: Source) -> &'a [f64] {
+        match source {
+            Source::High => self.high,
+            Source::Low => self.low,
+            Source::Close => self.close,
+        }
+    }
+
+    /// Computes `intermediate` unless it already has been
+    fn ensure(&mut self, intermediate: Intermediate) -> TAResult<()> {
+        if self.values.iter().any(|(computed, _)| *computed == intermediate) {
+            return Ok(());
+        }
+        let values = match intermediate {
+            Intermediate::Ma(period, ma_type) => ma(self.close, period, ma_type)?,
+            Intermediate::StdDev(period) => stddev(self.close, period, 1.0)?,
+            Intermediate::TrueRange => trange(self.high, self.low, self.close)?,
+            Intermediate::Atr(period) => wilder_smooth(self.get(Intermediate::TrueRange), period)?,
+            Intermediate::Highest(source, period) => max(self.source(source), period)?,
+            Intermediate::Lowest(source, period) => min(self.source(source), period)?,
+        };
+        self.values.push((intermediate, values));
+        Ok(())
+    }
+
+    fn get(&self, intermediate: Intermediate) -> &[f64] {
+        self.values
+            .iter()
+            .find(|(computed, _)| *computed == intermediate)
+            .map(|(_, values)| values.as_slice())
+            .expect("intermediates are computed before the indicators using them")
+    }
+}
+
+/// Function recorded in errors of a plan's own checks; errors of an indicator name it
+const PLAN: &str = "COMPUTE_PLAN";
+
+/// A set of indicators to compute together on one series
+///
+/// An error of one of the indicators names that indicator, e.g. `RSI`; an error of the
+/// plan's own checks, such as empty inputs, names `COMPUTE_PLAN`.
+///
+/// # Examples
+/// ```
+/// use ta_rust::batch::{ComputePlan, Indicator};
+/// use ta_rust::common::MAType;
+/// use ta_rust::momentum::macd;
+///
+/// let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+/// let plan = ComputePlan::new()
+///     .add(Indicator::Ema(12))
+///     .add(Indicator::Ema(26))
+///     .add(Indicator::Macd { fast: 12, slow: 26, signal: 9 })
+///     .add(Indicator::Ppo { fast: 12, slow: 26, ma_type: MAType::EMA });
+/// // The four indicators only need the two EMAs
+/// assert_eq!(plan.intermediates(), 2);
+///
+/// let result = plan.run(&close).unwrap();
+/// let (line, _, _) = macd(&close, 12, 26, 9).unwrap();
+/// assert_eq!(result["MACD(12,26,9).macd"][40], line[40]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComputePlan {
+    indicators: Vec<Indicator>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    functions: Vec<FunctionCall>,
+}
+
+/// A registry function added to a plan by name
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct FunctionCall {
+    name: String,
+    params: Vec<f64>,
+}
+
+impl FunctionCall {
+    /// Registry entry of the function
+    fn info(&self) -> TAResult<&'static FunctionInfo> {
+        Registry::get(&self.name)
+            .ok_or_else(|| {
+                TAError::invalid_input(format!("{} is not a registered function", self.name)).in_function(PLAN)
+            })
+    }
+
+    /// Whether the function takes high or low prices
+    fn needs_hlc(info: &FunctionInfo) -> bool {
+        info.inputs.iter().any(|input| matches!(*input, "high" | "low"))
+    }
+
+    /// Output keys: the name and parameters, e.g. `RSI(14)`, then the output name if several
+    fn output_keys(&self, info: &FunctionInfo) -> Vec<String> {
+        let params: Vec<String> = self.params.iter().map(f64::to_string).collect();
+        let key = if params.is_empty() {
+            String::from(info.name)
+        } else {
+            format!("{}({})", info.name, params.join(","))
+        };
+        match info.outputs {
+            [_] => vec![key],
+            names => names.iter().map(|name| format!("{}.{}", key, name)).collect(),
+        }
+    }
+}
+
+impl ComputePlan {
+    /// Creates an empty plan
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an indicator to the plan, ignoring duplicates
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, indicator: Indicator) -> Self {
+        if !self.indicators.contains(&indicator) {
+            self.indicators.push(indicator);
+        }
+        self
+    }
+
+    /// Adds a function of the [`Registry`] by name, built-in or custom, ignoring duplicates
+    ///
+    /// The function is looked up when the plan runs and called with positional parameters,
+    /// missing ones taking their defaults. Inputs named `real` or `close` receive the
+    /// closes, `high` and `low` the highs and lows. Outputs are keyed by the function name
+    /// with the parameters as given, e.g. `RSI(14)` or `TRANGE`, followed by the output
+    /// name for functions with several outputs, e.g. `STOCH(5,3,0,3,0).slowk`.
+    ///
+    /// Unlike [`Indicator`]s, these functions share no intermediate results.
+    ///
+    /// # Example
+    /// ```
+    /// use ta_rust::batch::ComputePlan;
+    /// use ta_rust::momentum::rsi;
+    ///
+    /// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0).collect();
+    /// let result = ComputePlan::new().add_function("rsi", &[14.0]).run(&close).unwrap();
+    /// assert_eq!(result["RSI(14)"][20], rsi(&close, 14).unwrap()[20]);
+    /// ```
+    pub fn add_function(mut self, name: &str, params: &[f64]) -> Self {
+        let call = FunctionCall { name: name.to_ascii_uppercase(), params: params.to_vec() };
+        if !self.functions.contains(&call) {
+            self.functions.push(call);
+        }
+        self
+    }
+
+    /// Indicators of the plan, in the order they were added
+    pub fn indicators(&self) -> &[Indicator] {
+        &self.indicators
+    }
+
+    /// Number of distinct intermediate series the plan computes
+    pub fn intermediates(&self) -> usize {
+        let mut distinct = Vec::new();
+        for intermediate in self.indicators.iter().flat_map(Indicator::intermediates) {
+            if !distinct.contains(&intermediate) {
+                distinct.push(intermediate);
+            }
+        }
+        distinct.len()
+    }
+
+    /// Computes the plan on a series of closes
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `close` is empty, an indicator needs high and low prices or a
+    ///   function is not registered
+    /// - Any error of the individual indicators
+    pub fn run(&self, close: &[f64]) -> TAResult<PlanResult> {
+        with_function(PLAN, || {
+            if let Some(indicator) = self.indicators.iter().find(|indicator| indicator.needs_hlc()) {
+                return Err(TAError::invalid_input(format!(
+                    "{} needs high, low and close prices, use run_hlc",
+                    indicator
+                )));
+            }
+            for call in &self.functions {
+                let info = call.info()?;
+                if FunctionCall::needs_hlc(info) {
+                    return Err(TAError::invalid_input(format!(
+                        "{} needs high, low and close prices, use run_hlc",
+                        info.name
+                    )));
+                }
+            }
+            Ok(())
+        })?;
+        self.execute(close, close, close)
+    }
+
+    /// Computes the plan on high, low and close prices
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the inputs are empty, a close lies outside its bar or a function is
+    ///   not registered
+    /// - `MismatchedInputs` if the inputs differ in length
+    /// - Any error of the individual indicators
+    pub fn run_hlc(&self, high: &[f64], low: &[f64], close: &[f64]) -> TAResult<PlanResult> {
+        with_function(PLAN, || validate_hlc_with(high, low, close, HlcValidation::CONSISTENT))?;
+        self.execute(high, low, close)
+    }
+
+    fn execute(&self, high: &[f64], low: &[f64], close: &[f64]) -> TAResult<PlanResult> {
+        with_function(PLAN, || validate_not_empty(close, "close"))?;
+
+        let mut cache = Intermediates { high, low, close, values: Vec::new() };
+        for indicator in &self.indicators {
+            for intermediate in indicator.intermediates() {
+                cache.ensure(intermediate).map_err(|err| err.in_function(indicator.name()))?;
+            }
+        }
+
+        let mut outputs = BTreeMap::new();
+        for indicator in &self.indicators {
+            let values = indicator.compute(&cache).map_err(|err| err.in_function(indicator.name()))?;
+            outputs.extend(indicator.output_keys().into_iter().zip(values));
+        }
+
+        for call in &self.functions {
+            let info = call.info()?;
+            let inputs = info
+                .inputs
+                .iter()
+                .map(|input| match *input {
+                    "real" | "close" => Ok(close),
+                    "high" => Ok(high),
+                    "low" => Ok(low),
+                    other => Err(TAError::invalid_input(format!(
+                        "{} needs {} input, which a plan does not provide",
+                        info.name, other
+                    ))
+                    .in_function(PLAN)),
+                })
+                .collect::<TAResult<Vec<&[f64]>>>()?;
+            let values = info.call(&inputs, &call.params)?;
+            outputs.extend(call.output_keys(info).into_iter().zip(values));
+        }
+
+        Ok(PlanResult { outputs })
+    }
+}
+
+/// Outputs of a [`ComputePlan`], keyed by [`Indicator::output_keys`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanResult {
+    outputs: BTreeMap<String, Vec<f64>>,
+}
+
+impl PlanResult {
+    /// Output with the given key
+    pub fn get(&self, key: &str) -> Option<&[f64]> {
+        self.outputs.get(key).map(Vec::as_slice)
+    }
+
+    /// Outputs of an indicator, in the order of [`Indicator::output_keys`]
+    pub fn indicator(&self, indicator: &Indicator) -> Option<Vec<&[f64]>> {
+        indicator.output_keys().iter().map(|key| self.get(key)).collect()
+    }
+
+    /// Keys of all outputs, in sorted order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.outputs.keys().map(String::as_str)
+    }
+
+    /// Number of outputs
+    pub fn len(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Whether there are no outputs
+    pub fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+
+    /// Consumes the result, returning the map of outputs
+    pub fn into_map(self) -> BTreeMap<String, Vec<f64>> {
+        self.outputs
+    }
+}
+
+impl Index<&str> for PlanResult {
+    type Output = [f64];
+
+    fn index(&self, key: &str) -> &[f64] {
+        self.get(key).unwrap_or_else(|| panic!("no output with key {}", key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::momentum::{apo, macd, ppo, willr};
+    use crate::overlap::{bbands_ma, donchian, ema, sma};
+    use crate::volatility::{atr, natr};
+
+    fn bars() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let close: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.17).sin() * 6.0 + i as f64 * 0.05).collect();
+        let high = close.iter().enumerate().map(|(i, c)| c + 0.5 + (i % 4) as f64 * 0.25).collect();
+        let low = close.iter().enumerate().map(|(i, c)| c - 0.4 - (i % 3) as f64 * 0.3).collect();
+        (high, low, close)
+    }
+
+    #[test]
+    fn test_plan_shares_intermediates() {
+        let plan = ComputePlan::new()
+            .add(Indicator::Ema(12))
+            .add(Indicator::Ema(26))
+            .add(Indicator::Macd { fast: 12, slow: 26, signal: 9 })
+            .add(Indicator::Apo { fast: 12, slow: 26, ma_type: MAType::EMA })
+            .add(Indicator::Ppo { fast: 12, slow: 26, ma_type: MAType::EMA })
+            .add(Indicator::Ema(12));
+        assert_eq!(plan.indicators().len(), 5);
+        assert_eq!(plan.intermediates(), 2);
+
+        let hlc = ComputePlan::new()
+            .add(Indicator::Trange)
+            .add(Indicator::Atr(14))
+            .add(Indicator::Natr(14))
+            .add(Indicator::Willr(14))
+            .add(Indicator::Donchian(14))
+            .add(Indicator::Bbands { period: 20, nb_dev_up: 2.0, nb_dev_dn: 2.0, ma_type: MAType::SMA })
+            .add(Indicator::Sma(20));
+        // TR, ATR(14), highest high, lowest low, SMA(20) and STDDEV(20)
+        assert_eq!(hlc.intermediates(), 6);
+    }
+
+    #[test]
+    fn test_plan_matches_individual_functions() {
+        let (high, low, close) = bars();
+        let bbands = Indicator::Bbands { period: 20, nb_dev_up: 2.0, nb_dev_dn: 1.5, ma_type: MAType::EMA };
+        let result = ComputePlan::new()
+            .add(Indicator::Sma(20))
+            .add(Indicator::Ema(12))
+            .add(Indicator::Macd { fast: 12, slow: 26, signal: 9 })
+            .add(Indicator::Apo { fast: 12, slow: 26, ma_type: MAType::SMA })
+            .add(Indicator::Ppo { fast: 12, slow: 26, ma_type: MAType::EMA })
+            .add(bbands)
+            .add(Indicator::Atr(14))
+            .add(Indicator::Natr(14))
+            .add(Indicator::Willr(14))
+            .add(Indicator::Donchian(10))
+            .run_hlc(&high, &low, &close)
+            .unwrap();
+
+        assert_arrays_approx_equal(&result["SMA(20)"], &sma(&close, 20).unwrap(), 0.0);
+        assert_arrays_approx_equal(&result["EMA(12)"], &ema(&close, 12).unwrap(), 0.0);
+        let (line, signal, histogram) = macd(&close, 12, 26, 9).unwrap();
+        assert_arrays_approx_equal(&result["MACD(12,26,9).macd"], &line, 0.0);
+        assert_arrays_approx_equal(&result["MACD(12,26,9).signal"], &signal, 0.0);
+        assert_arrays_approx_equal(&result["MACD(12,26,9).histogram"], &histogram, 0.0);
+        assert_arrays_approx_equal(&result["APO(12,26,SMA)"], &apo(&close, 12, 26, MAType::SMA).unwrap(), 0.0);
+        assert_arrays_approx_equal(&result["PPO(12,26,EMA)"], &ppo(&close, 12, 26, MAType::EMA).unwrap(), 0.0);
+
+        let bands = bbands_ma(&close, 20, 2.0, 1.5, MAType::EMA).unwrap();
+        let outputs = result.indicator(&bbands).unwrap();
+        assert_arrays_approx_equal(outputs[0], &bands.upper, 0.0);
+        assert_arrays_approx_equal(outputs[2], &bands.lower, 0.0);
+
+        assert_arrays_approx_equal(&result["ATR(14)"], &atr(&high, &low, &close, 14).unwrap(), 0.0);
+        assert_arrays_approx_equal(&result["NATR(14)"], &natr(&high, &low, &close, 14).unwrap(), 0.0);
+        assert_arrays_approx_equal(&result["WILLR(14)"], &willr(&high, &low, &close, 14).unwrap(), 0.0);
+        let channels = donchian(&high, &low, 10).unwrap();
+        assert_arrays_approx_equal(&result["DONCHIAN(10).middle"], &channels.middle, 0.0);
+        assert_eq!(result.len(), 16);
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let (high, low, close) = bars();
+        let plan = ComputePlan::new().add(Indicator::Ema(12)).add(Indicator::Atr(14));
+        assert!(plan.run(&close).is_err());
+        assert!(plan.run_hlc(&high, &low, &close[1..]).is_err());
+        let err = ComputePlan::new().add(Indicator::Ema(5)).add(Indicator::Sma(200)).run(&close).unwrap_err();
+        assert_eq!(err.function(), Some("SMA"));
+        let err = ComputePlan::new().add(Indicator::Sma(10)).run(&[]).unwrap_err();
+        assert_eq!(err.function(), Some("COMPUTE_PLAN"));
+        let negative = Indicator::Bbands { period: 20, nb_dev_up: -1.0, nb_dev_dn: 2.0, ma_type: MAType::SMA };
+        assert_eq!(ComputePlan::new().add(negative).run(&close).unwrap_err().function(), Some("BBANDS"));
+        assert!(ComputePlan::new().run(&close).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_functions() {
+        let (high, low, close) = bars();
+        let plan = ComputePlan::new()
+            .add(Indicator::Ema(12))
+            .add_function("rsi", &[14.0])
+            .add_function("RSI", &[14.0])
+            .add_function("STOCH", &[])
+            .add_function("TRANGE", &[]);
+        let result = plan.run_hlc(&high, &low, &close).unwrap();
+        assert_arrays_approx_equal(&result["RSI(14)"], &rsi(&close, 14).unwrap(), 0.0);
+        // Registry functions follow TA-Lib, which gives no true range on the first bar
+        assert!(result["TRANGE"][0].is_nan());
+        assert_arrays_approx_equal(&result["TRANGE"][1..], &trange(&high, &low, &close).unwrap()[1..], 0.0);
+        assert!(result.get("STOCH.slowk").is_some() && result.get("STOCH.slowd").is_some());
+        assert_eq!(result.len(), 5);
+
+        assert!(plan.run(&close).is_err());
+        assert!(ComputePlan::new().add_function("NOPE", &[]).run(&close).is_err());
+        assert!(ComputePlan::new().add_function("OBV", &[]).run(&close).is_err());
+        let err = ComputePlan::new().add_function("RSI", &[0.0]).run(&close).unwrap_err();
+        assert_eq!(err.function(), Some("RSI"));
+    }
+}
@@ -0,0 +1,248 @@
+//! Multi-symbol batch runs
+//!
+//! Screeners compute the same indicator over hundreds or thousands of symbols. [`run`]
+//! takes the price series of each symbol and an `*_into` style indicator, allocates the
+//! output of every symbol in one contiguous buffer (one column per symbol) and fills the
+//! columns in turn, or concurrently with [`run_parallel`] when the `parallel` feature is
+//! enabled.
+
+use core::hash::Hash;
+use core::ops::Range;
+use std::collections::HashMap;
+
+use crate::common::{TAError, TAResult};
+
+/// Outputs of a multi-symbol run, keyed by symbol
+///
+/// The columns of all symbols share one buffer. A symbol whose computation failed, for
+/// example because its series is shorter than the indicator period, has an error instead.
+#[derive(Debug, Clone)]
+pub struct SymbolResults<K> {
+    values: Vec<f64>,
+    columns: HashMap<K, Range<usize>>,
+    errors: HashMap<K, TAError>,
+}
+
+impl<K: Eq + Hash> SymbolResults<K> {
+    /// Output of `symbol`, or `None` if it was not computed or failed
+    pub fn get(&self, symbol: &K) -> Option<&[f64]> {
+        self.columns.get(symbol).map(|range| &self.values[range.clone()])
+    }
+
+    /// Error of `symbol`, if its computation failed
+    pub fn error(&self, symbol: &K) -> Option<&TAError> {
+        self.errors.get(symbol)
+    }
+
+    /// Symbols with an output and their outputs, in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &[f64])> {
+        self.columns.iter().map(|(symbol, range)| (symbol, &self.values[range.clone()]))
+    }
+
+    /// Symbols whose computation failed and their errors
+    pub fn errors(&self) -> &HashMap<K, TAError> {
+        &self.errors
+    }
+
+    /// Number of symbols with an output
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Whether no symbol has an output
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Copies the outputs into a map of vectors, dropping the errors
+    pub fn to_map(&self) -> HashMap<K, Vec<f64>>
+    where
+        K: Clone,
+    {
+        self.iter().map(|(symbol, values)| (symbol.clone(), values.to_vec())).collect()
+    }
+}
+
+/// Input and output column of every symbol, in the order of the output buffer
+type Columns<'a, K> = Vec<(&'a K, &'a [f64], &'a mut [f64])>;
+
+/// Allocates the output buffer and splits it into one column per symbol
+fn allocate<K>(series: &HashMap<K, Vec<f64>>) -> (Vec<f64>, Vec<(&K, Range<usize>)>) {
+    let mut ranges = Vec::with_capacity(series.len());
+    let mut offset = 0;
+    for (symbol, data) in series {
+        ranges.push((symbol, offset..offset + data.len()));
+        offset += data.len();
+    }
+    (vec![f64::NAN; offset], ranges)
+}
+
+fn columns<'a, K: Eq + Hash>(
+    series: &'a HashMap<K, Vec<f64>>,
+    ranges: &[(&'a K, Range<usize>)],
+    mut buffer: &'a mut [f64],
+) -> Columns<'a, K> {
+    let mut columns = Vec::with_capacity(ranges.len());
+    for &(symbol, ref range) in ranges {
+        let (column, rest) = buffer.split_at_mut(range.len());
+        columns.push((symbol, series[symbol].as_slice(), column));
+        buffer = rest;
+    }
+    columns
+}
+
+fn collect<K: Eq + Hash + Clone>(
+    values: Vec<f64>,
+    ranges: Vec<(&K, Range<usize>)>,
+    outcomes: Vec<TAResult<usize>>,
+) -> SymbolResults<K> {
+    let mut columns = HashMap::with_capacity(ranges.len());
+    let mut errors = HashMap::new();
+    for ((symbol, range), outcome) in ranges.into_iter().zip(outcomes) {
+        match outcome {
+            Ok(_) => {
+                columns.insert(symbol.clone(), range);
+            }
+            Err(error) => {
+                errors.insert(symbol.clone(), error);
+            }
+        }
+    }
+    SymbolResults { values, columns, errors }
+}
+
+/// Computes one indicator over the series of many symbols
+///
+/// `indicator` is called once per symbol with its series and an output column of the same
+/// length, like the `*_into` functions of this crate. Errors are recorded per symbol, as
+/// returned by `indicator` and so naming the indicator, and do not stop the run.
+///
+/// # Arguments
+/// * `indicator` - Function filling the output column of one series
+/// * `series` - Price series keyed by symbol
+///
+/// # Returns
+/// The output column of every symbol, or its error
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use ta_rust::batch::run;
+/// use ta_rust::overlap::sma_into;
+///
+/// let mut series = HashMap::new();
+/// series.insert("AAPL", vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+/// series.insert("MSFT", vec![10.0, 20.0, 30.0]);
+/// series.insert("NEW", vec![7.0]);
+///
+/// let results = run(|data, out| sma_into(data, 3, out), &series);
+/// assert_eq!(results.get(&"AAPL").unwrap()[4], 4.0);
+/// assert_eq!(results.get(&"MSFT").unwrap()[2], 20.0);
+/// assert!(results.error(&"NEW").is_some());
+/// ```
+pub fn run<K, F>(indicator: F, series: &HashMap<K, Vec<f64>>) -> SymbolResults<K>
+where
+    K: Eq + Hash + Clone,
+    F: Fn(&[f64], &mut [f64]) -> TAResult<usize>,
+{
+    let (mut values, ranges) = allocate(series);
+    let outcomes = columns(series, &ranges, &mut values)
+        .into_iter()
+        .map(|(_, data, out)| indicator(data, out))
+        .collect();
+    collect(values, ranges, outcomes)
+}
+
+/// Computes one indicator over the series of many symbols on the rayon thread pool
+///
+/// Same as [`run`], with the symbols distributed over the threads of the global rayon
+/// pool. Requires the `parallel` feature.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use ta_rust::batch::{run, run_parallel};
+/// use ta_rust::momentum::rsi_into;
+///
+/// let series: HashMap<u32, Vec<f64>> = (0..100)
+///     .map(|id| (id, (0..250).map(|i| 100.0 + ((i + id) as f64 * 0.1).sin()).collect()))
+///     .collect();
+///
+/// let parallel = run_parallel(|data, out| rsi_into(data, 14, out), &series);
+/// let sequential = run(|data, out| rsi_into(data, 14, out), &series);
+/// assert_eq!(parallel.get(&7).unwrap()[200], sequential.get(&7).unwrap()[200]);
+/// ```
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub fn run_parallel<K, F>(indicator: F, series: &HashMap<K, Vec<f64>>) -> SymbolResults<K>
+where
+    K: Eq + Hash + Clone + Sync,
+    F: Fn(&[f64], &mut [f64]) -> TAResult<usize> + Sync,
+{
+    use rayon::prelude::*;
+
+    let (mut values, ranges) = allocate(series);
+    let outcomes = columns(series, &ranges, &mut values)
+        .into_par_iter()
+        .map(|(_, data, out)| indicator(data, out))
+        .collect();
+    collect(values, ranges, outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::overlap::{ema, ema_into};
+
+    fn universe() -> HashMap<String, Vec<f64>> {
+        (0..20)
+            .map(|id| {
+                let len = 30 + id * 7;
+                let prices = (0..len).map(|i| 50.0 + id as f64 + ((i * (id + 1)) as f64 * 0.05).sin()).collect();
+                (format!("SYM{}", id), prices)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_run_matches_per_symbol() {
+        let series = universe();
+        let results = run(|data, out| ema_into(data, 10, out), &series);
+        assert_eq!(results.len(), series.len());
+        assert!(results.errors().is_empty());
+        for (symbol, prices) in &series {
+            assert_arrays_approx_equal(results.get(symbol).unwrap(), &ema(prices, 10).unwrap(), 0.0);
+        }
+        assert_eq!(results.to_map().len(), series.len());
+        assert!(results.get(&"MISSING".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_run_records_errors_per_symbol() {
+        let series = universe();
+        // Only the symbols with at least 100 bars can be computed
+        let results = run(|data, out| ema_into(data, 100, out), &series);
+        let long = series.values().filter(|prices| prices.len() >= 100).count();
+        assert_eq!(results.len(), long);
+        assert_eq!(results.errors().len(), series.len() - long);
+        assert!(matches!(results.error(&"SYM0".to_string()), Some(TAError::InsufficientData { .. })));
+        assert!(results.iter().all(|(symbol, values)| values.len() == series[symbol].len()));
+
+        let empty: HashMap<u8, Vec<f64>> = HashMap::new();
+        assert!(run(|data, out| ema_into(data, 3, out), &empty).is_empty());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_run_parallel_matches_run() {
+        let series = universe();
+        let sequential = run(|data, out| ema_into(data, 40, out), &series);
+        let parallel = run_parallel(|data, out| ema_into(data, 40, out), &series);
+        assert_eq!(parallel.to_map().len(), sequential.len());
+        for (symbol, values) in sequential.iter() {
+            assert_arrays_approx_equal(parallel.get(symbol).unwrap(), values, 0.0);
+        }
+        assert_eq!(parallel.errors().len(), sequential.errors().len());
+    }
+}
@@ -0,0 +1,290 @@
+//! Parameter sweeps
+//!
+//! Grid searches evaluate one indicator for many periods. The `*_multi` functions compute
+//! all of them in a single pass over the data: what the periods share, such as the price
+//! changes of RSI or the true range of ATR, is computed once per bar, and each period
+//! keeps its own incremental state. The results are the same, bit for bit, as calling
+//! the single-period function for each period.
+
+use crate::common::{TAError, TAResult, NeumaierSum, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_prices, ema_multiplier};
+use crate::common::{validate_hlc_with, HlcValidation};
+use crate::volatility::trange::true_range;
+use crate::momentum::rsi::rsi_from_averages;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the SMA for several periods in one pass
+///
+/// # Returns
+/// One vector per period, in the order of `periods`, equal to
+/// [`sma`](crate::overlap::sma) for that period.
+///
+/// # Errors
+/// - `InvalidInput` if data is empty
+/// - `InvalidParameter` if a period is 0
+/// - `InsufficientData` if data is shorter than the longest period
+///
+/// # Example
+/// ```rust
+/// use ta_rust::batch::sma_multi;
+/// use ta_rust::overlap::sma;
+///
+/// let prices: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+/// let results = sma_multi(&prices, &[5, 10, 20]).unwrap();
+/// assert_eq!(results[2][40], sma(&prices, 20).unwrap()[40]);
+/// ```
+pub fn sma_multi(data: &[f64], periods: &[usize]) -> TAResult<Vec<Vec<f64>>> {
+    with_function("SMA_MULTI", || {
+        validate_not_empty(data, "data")?;
+        let periods = validate_periods(periods, data.len(), 0)?;
+
+        let mut outputs = vec![vec![f64::NAN; data.len()]; periods.len()];
+        // Per period, like `sma_into`: compensated sum of the finite values, count of the others
+        let mut sums = vec![(NeumaierSum::new(), 0usize); periods.len()];
+        for (i, &value) in data.iter().enumerate() {
+            for ((&period, (sum, invalid)), output) in periods.iter().zip(&mut sums).zip(&mut outputs) {
+                if value.is_finite() {
+                    sum.add(value);
+                } else {
+                    *invalid += 1;
+                }
+                if i + 1 < period {
+                    continue;
+                }
+
+                let start = i + 1 - period;
+                output[i] = if *invalid == 0 {
+                    sum.value() / period as f64
+                } else {
+                    data[start..=i].iter().sum::<f64>() / period as f64
+                };
+                if data[start].is_finite() {
+                    sum.sub(data[start]);
+                } else {
+                    *invalid -= 1;
+                }
+            }
+        }
+        Ok(outputs)
+    })
+}
+
+/// Calculates the EMA for several periods in one pass
+///
+/// # Returns
+/// One vector per period, in the order of `periods`, equal to
+/// [`ema`](crate::overlap::ema) for that period.
+///
+/// # Errors
+/// - `InvalidInput` if data is empty
+/// - `InvalidParameter` if a period is 0
+/// - `InsufficientData` if data is shorter than the longest period
+///
+/// # Example
+/// ```rust
+/// use ta_rust::batch::ema_multi;
+/// use ta_rust::overlap::ema;
+///
+/// let prices: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+/// let results = ema_multi(&prices, &[5, 12, 26]).unwrap();
+/// assert_eq!(results[1][50], ema(&prices, 12).unwrap()[50]);
+/// ```
+pub fn ema_multi(data: &[f64], periods: &[usize]) -> TAResult<Vec<Vec<f64>>> {
+    with_function("EMA_MULTI", || {
+        validate_not_empty(data, "data")?;
+        let periods = validate_periods(periods, data.len(), 0)?;
+
+        let mut smoothers: Vec<Smoother> = periods.iter().map(|&p| Smoother::new(p, ema_multiplier(p))).collect();
+        Ok(smooth_multi(data.iter().copied(), data.len(), &mut smoothers))
+    })
+}
+
+/// Calculates the RSI for several periods in one pass
+///
+/// Each price change and its gain and loss are computed once for all periods.
+///
+/// # Returns
+/// One vector per period, in the order of `periods`, equal to
+/// [`rsi`](crate::momentum::rsi) for that period.
+///
+/// # Errors
+/// - `InvalidInput` if prices are empty or not all finite
+/// - `InvalidParameter` if a period is 0
+/// - `InsufficientData` if there are no more prices than the longest period
+///
+/// # Example
+/// ```rust
+/// use ta_rust::batch::rsi_multi;
+/// use ta_rust::momentum::rsi;
+///
+/// let prices: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+/// let results = rsi_multi(&prices, &[5, 10, 14, 21, 30]).unwrap();
+/// assert_eq!(results.len(), 5);
+/// assert_eq!(results[2][60], rsi(&prices, 14).unwrap()[60]);
+/// ```
+pub fn rsi_multi(prices: &[f64], periods: &[usize]) -> TAResult<Vec<Vec<f64>>> {
+    with_function("RSI_MULTI", || {
+        validate_not_empty(prices, "prices")?;
+        validate_prices(prices, "prices")?;
+        let periods = validate_periods(periods, prices.len(), 1)?;
+
+        let mut outputs = vec![vec![f64::NAN; prices.len()]; periods.len()];
+        let mut states: Vec<(Smoother, Smoother)> = periods
+            .iter()
+            .map(|&p| (Smoother::new(p, 1.0 / p as f64), Smoother::new(p, 1.0 / p as f64)))
+            .collect();
+        for (i, pair) in prices.windows(2).enumerate() {
+            let change = pair[1] - pair[0];
+            let (gain, loss) = (if change > 0.0 { change } else { 0.0 }, if change < 0.0 { -change } else { 0.0 });
+            for ((gains, losses), output) in states.iter_mut().zip(&mut outputs) {
+                if let (Some(avg_gain), Some(avg_loss)) = (gains.update(gain), losses.update(loss)) {
+                    output[i + 1] = rsi_from_averages(avg_gain, avg_loss);
+                }
+            }
+        }
+        Ok(outputs)
+    })
+}
+
+/// Calculates the ATR for several periods in one pass
+///
+/// The true range of each bar is computed once for all periods.
+///
+/// # Returns
+/// One vector per period, in the order of `periods`, equal to
+/// [`atr`](crate::volatility::atr) for that period.
+///
+/// # Errors
+/// - `InvalidInput` if the inputs are empty or inconsistent
+/// - `InvalidParameter` if a period is 0
+/// - `InsufficientData` if the inputs are shorter than the longest period
+///
+/// # Example
+/// ```rust
+/// use ta_rust::batch::atr_multi;
+/// use ta_rust::volatility::atr;
+///
+/// let close: Vec<f64> = (0..50).map(|i| 20.0 + (i as f64 * 0.5).cos()).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.4).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 0.3).collect();
+/// let results = atr_multi(&high, &low, &close, &[7, 14]).unwrap();
+/// assert_eq!(results[1][30], atr(&high, &low, &close, 14).unwrap()[30]);
+/// ```
+pub fn atr_multi(high: &[f64], low: &[f64], close: &[f64], periods: &[usize]) -> TAResult<Vec<Vec<f64>>> {
+    with_function("ATR_MULTI", || {
+        validate_not_empty(high, "high")?;
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let periods = validate_periods(periods, high.len(), 0)?;
+
+        let true_ranges = (0..high.len()).map(|i| match i {
+            0 => high[0] - low[0],
+            _ => true_range(high[i], low[i], close[i - 1]),
+        });
+        let mut smoothers: Vec<Smoother> = periods.iter().map(|&p| Smoother::new(p, 1.0 / p as f64)).collect();
+        Ok(smooth_multi(true_ranges, high.len(), &mut smoothers))
+    })
+}
+
+/// Checks the periods, returning them as plain counts; the data must hold at least
+/// `extra` more values than the longest period
+fn validate_periods(periods: &[usize], len: usize, extra: usize) -> TAResult<Vec<usize>> {
+    let periods = periods
+        .iter()
+        .map(|&p| p.into_param("periods").map(Period::get))
+        .collect::<TAResult<Vec<usize>>>()?;
+    let longest = periods.iter().copied().max().unwrap_or(0);
+    if len < longest + extra {
+        return Err(TAError::insufficient_data(longest + extra, len));
+    }
+    Ok(periods)
+}
+
+/// Feeds every value to every smoother, one output per smoother
+fn smooth_multi(values: impl Iterator<Item = f64>, len: usize, smoothers: &mut [Smoother]) -> Vec<Vec<f64>> {
+    let mut outputs = vec![vec![f64::NAN; len]; smoothers.len()];
+    for (i, value) in values.enumerate() {
+        for (smoother, output) in smoothers.iter_mut().zip(&mut outputs) {
+            if let Some(average) = smoother.update(value) {
+                output[i] = average;
+            }
+        }
+    }
+    outputs
+}
+
+/// Exponential average seeded with the mean of the first `period` values, in the same
+/// operations as the smoothing of `ema`, `rsi` and `atr`
+#[derive(Debug, Clone)]
+struct Smoother {
+    period: usize,
+    alpha: f64,
+    count: usize,
+    seed_sum: f64,
+    value: Option<f64>,
+}
+
+impl Smoother {
+    fn new(period: usize, alpha: f64) -> Self {
+        Self { period, alpha, count: 0, seed_sum: 0.0, value: None }
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(average) => Some(self.alpha * value + (1.0 - self.alpha) * average),
+            None => {
+                self.count += 1;
+                self.seed_sum += value;
+                (self.count == self.period).then(|| self.seed_sum / self.period as f64)
+            }
+        };
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::rsi;
+    use crate::overlap::{ema, sma};
+    use crate::volatility::atr;
+
+    fn same(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.to_bits() == y.to_bits())
+    }
+
+    #[test]
+    fn test_sweeps_match_single_period() {
+        let close: Vec<f64> = (0..400).map(|i| 50.0 + (i as f64 * 0.11).sin() * 6.0 + ((i * 7) % 5) as f64 * 0.13).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.2 + (i % 3) as f64 * 0.1).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.25 - (i % 4) as f64 * 0.05).collect();
+        let periods = [1, 2, 5, 14, 30, 14];
+
+        let smas = sma_multi(&close, &periods).unwrap();
+        let emas = ema_multi(&close, &periods).unwrap();
+        let rsis = rsi_multi(&close, &periods).unwrap();
+        let atrs = atr_multi(&high, &low, &close, &periods).unwrap();
+        for (k, &period) in periods.iter().enumerate() {
+            assert!(same(&smas[k], &sma(&close, period).unwrap()));
+            assert!(same(&emas[k], &ema(&close, period).unwrap()));
+            assert!(same(&rsis[k], &rsi(&close, period).unwrap()));
+            assert!(same(&atrs[k], &atr(&high, &low, &close, period).unwrap()));
+        }
+
+        let mut gappy = close.clone();
+        gappy[100] = f64::NAN;
+        assert!(same(&sma_multi(&gappy, &[3, 8]).unwrap()[1], &sma(&gappy, 8).unwrap()));
+        assert!(rsi_multi(&gappy, &[3]).is_err());
+    }
+
+    #[test]
+    fn test_sweep_errors() {
+        let prices = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(sma_multi(&prices, &[2, 0]).unwrap_err().function(), Some("SMA_MULTI"));
+        assert!(sma_multi(&prices, &[5]).is_err());
+        assert!(rsi_multi(&prices, &[4]).is_err());
+        assert!(ema_multi(&[], &[2]).is_err());
+        assert!(sma_multi(&prices, &[]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,494 @@
+//! Resumable Streaming Indicators
+//!
+//! Every streaming state (`MaState`, `RsiState`, `StochState`, ...) holds all it needs to
+//! continue and, with the `serde` feature, serializes. [`Checkpointed`] wraps any
+//! [`StreamingIndicator`] with the last inputs it saw, prices or bars such as
+//! `(high, low, close)`, so a pipeline can snapshot it, restart from the snapshot and carry
+//! on with outputs bit-identical to an uninterrupted run. The count of inputs seen, or the
+//! recorded inputs, tell where a replayed feed picks up after the snapshot; see
+//! [`Checkpointed::unseen_from`] and [`Checkpointed::unseen`].
+//!
+//! Serialize with a format that round-trips `f64` exactly, such as `serde_json` with its
+//! `float_roundtrip` feature or a binary format.
+
+use crate::common::{IntoParam, Period, TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::iter::StreamingIndicator;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// One bar of input a [`Checkpointed`] indicator records
+///
+/// Implemented for `f64`, `f32`, `i64` timestamps and tuples of them, such as the
+/// `(high, low, close)` of [`AtrState`](crate::volatility::AtrState). Recorded inputs are
+/// compared bit for bit and, with the `serde` feature, written with NaN as `None`.
+pub trait RecordedInput: Copy + core::fmt::Debug + PartialEq {
+    /// The input as serialized
+    #[cfg(feature = "serde")]
+    type Encoded: serde::Serialize + serde::de::DeserializeOwned;
+
+    /// Whether two inputs are the same, bit for bit
+    fn same_bits(&self, other: &Self) -> bool;
+
+    /// The input to serialize
+    #[cfg(feature = "serde")]
+    fn encode(self) -> Self::Encoded;
+
+    /// The input read back from [`encode`](RecordedInput::encode)
+    #[cfg(feature = "serde")]
+    fn decode(encoded: Self::Encoded) -> Self;
+}
+
+macro_rules! recorded_float {
+    ($($t:ty),+) => {$(
+        impl RecordedInput for $t {
+            #[cfg(feature = "serde")]
+            type Encoded = Option<$t>;
+
+            fn same_bits(&self, other: &Self) -> bool {
+                self.to_bits() == other.to_bits()
+            }
+
+            #[cfg(feature = "serde")]
+            fn encode(self) -> Option<$t> {
+                if self.is_nan() { None } else { Some(self) }
+            }
+
+            #[cfg(feature = "serde")]
+            fn decode(encoded: Option<$t>) -> Self {
+                encoded.unwrap_or(<$t>::NAN)
+            }
+        }
+    )+};
+}
+
+recorded_float!(f64, f32);
+
+impl RecordedInput for i64 {
+    #[cfg(feature = "serde")]
+    type Encoded = i64;
+
+    fn same_bits(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    #[cfg(feature = "serde")]
+    fn encode(self) -> i64 {
+        self
+    }
+
+    #[cfg(feature = "serde")]
+    fn decode(encoded: i64) -> Self {
+        encoded
+    }
+}
+
+macro_rules! recorded_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: RecordedInput),+> RecordedInput for ($($name,)+) {
+            #[cfg(feature = "serde")]
+            type Encoded = ($($name::Encoded,)+);
+
+            fn same_bits(&self, other: &Self) -> bool {
+                $(self.$index.same_bits(&other.$index))&&+
+            }
+
+            #[cfg(feature = "serde")]
+            fn encode(self) -> Self::Encoded {
+                ($(self.$index.encode(),)+)
+            }
+
+            #[cfg(feature = "serde")]
+            fn decode(encoded: Self::Encoded) -> Self {
+                ($($name::decode(encoded.$index),)+)
+            }
+        }
+    };
+}
+
+recorded_tuple!(A 0, B 1);
+recorded_tuple!(A 0, B 1, C 2);
+recorded_tuple!(A 0, B 1, C 2, D 3);
+
+/// Serde encoding of recorded inputs, used through `#[serde(with = "recorded")]`
+#[cfg(feature = "serde")]
+mod recorded {
+    use super::RecordedInput;
+    use serde::{Deserialize, Deserializer, Serializer};
+    #[cfg(not(feature = "std"))]
+    use crate::common::compat::*;
+
+    pub fn serialize<I: RecordedInput, S: Serializer>(inputs: &[I], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(inputs.iter().map(|&input| input.encode()))
+    }
+
+    pub fn deserialize<'de, I: RecordedInput, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<I>, D::Error> {
+        let encoded = Vec::<I::Encoded>::deserialize(deserializer)?;
+        Ok(encoded.into_iter().map(I::decode).collect())
+    }
+}
+
+/// A streaming indicator recording its latest inputs, to be snapshot and resumed
+///
+/// A snapshot is a clone, or a serialized copy with the `serde` feature; resuming is
+/// calling [`update`](Checkpointed::update) on it with the bars that followed.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::checkpoint::Checkpointed;
+/// use ta_rust::momentum::RsiState;
+///
+/// let prices: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+///
+/// let mut live = Checkpointed::new(RsiState::new(14).unwrap(), 5).unwrap();
+/// for &price in &prices[..40] {
+///     live.update(price);
+/// }
+/// let snapshot = live.clone();
+/// let expected: Vec<Option<f64>> = prices[40..].iter().map(|&p| live.update(p)).collect();
+///
+/// // After a restart the feed is replayed from an earlier point
+/// let mut resumed = snapshot;
+/// let unseen = resumed.unseen(&prices[30..]).unwrap();
+/// assert_eq!(unseen.len(), 20);
+/// let outputs: Vec<Option<f64>> = unseen.iter().map(|&p| resumed.update(p)).collect();
+/// assert_eq!(outputs, expected);
+/// ```
+///
+/// Deserializing checks the snapshot: a `window` of 0, more recorded inputs than twice
+/// `window`, or more recorded inputs than `bars`, is rejected.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Snapshot<S>"))]
+pub struct Checkpointed<S: StreamingIndicator>
+where
+    S::Input: RecordedInput,
+{
+    state: S,
+    /// Number of inputs to keep, at least 1
+    window: usize,
+    /// Latest inputs, oldest first; up to twice `window` long, trimmed in batches
+    #[cfg_attr(feature = "serde", serde(with = "recorded"))]
+    recent: Vec<S::Input>,
+    /// Number of inputs seen
+    bars: usize,
+}
+
+/// Fields of a serialized [`Checkpointed`], checked before it is rebuilt
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Snapshot<S: StreamingIndicator>
+where
+    S::Input: RecordedInput,
+{
+    state: S,
+    window: usize,
+    #[serde(with = "recorded")]
+    recent: Vec<S::Input>,
+    bars: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<S: StreamingIndicator> TryFrom<Snapshot<S>> for Checkpointed<S>
+where
+    S::Input: RecordedInput,
+{
+    type Error = TAError;
+
+    fn try_from(snapshot: Snapshot<S>) -> TAResult<Self> {
+        let window = snapshot.window.into_param("window")?.get();
+        if snapshot.recent.len() > 2 * window {
+            return Err(TAError::invalid_input(format!(
+                "checkpoint records {} inputs, more than twice its window of {}",
+                snapshot.recent.len(),
+                window
+            )));
+        }
+        if snapshot.recent.len() > snapshot.bars {
+            return Err(TAError::invalid_parameter(
+                "bars".to_string(),
+                format!("counts {} inputs, fewer than the {} recorded", snapshot.bars, snapshot.recent.len()),
+            ));
+        }
+        Ok(Self { state: snapshot.state, window, recent: snapshot.recent, bars: snapshot.bars })
+    }
+}
+
+impl<S: StreamingIndicator> Checkpointed<S>
+where
+    S::Input: RecordedInput,
+{
+    /// Wraps a state, keeping its last `window` inputs
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if `window` is 0
+    pub fn new(state: S, window: impl IntoParam<Period>) -> TAResult<Self> {
+        with_function("CHECKPOINTED", || {
+            let window = window.into_param("window")?.get();
+            Ok(Self { state, window, recent: Vec::with_capacity(2 * window), bars: 0 })
+        })
+    }
+
+    /// Feeds the next bar and returns the indicator, or `None` during warm-up
+    pub fn update(&mut self, input: S::Input) -> Option<S::Output> {
+        if self.recent.len() >= 2 * self.window {
+            self.recent.drain(..self.recent.len() - self.window);
+        }
+        self.recent.push(input);
+        self.bars += 1;
+        self.state.next_value(input)
+    }
+
+    /// The wrapped streaming state
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Latest inputs, oldest first, at most `window` of them
+    pub fn recent(&self) -> &[S::Input] {
+        &self.recent[self.recent.len().saturating_sub(self.window)..]
+    }
+
+    /// Number of inputs seen
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+
+    /// The part of `inputs` after the latest inputs seen
+    ///
+    /// For a feed replayed from an unknown point before the snapshot: the recorded inputs
+    /// are looked up in `inputs`, bit for bit, and the values after them are returned. On
+    /// flat or repeating prices the recorded inputs can appear more than once, and only
+    /// [`unseen_from`](Checkpointed::unseen_from) can tell where the feed resumes. Before
+    /// the first input, all of `inputs` is unseen.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `inputs` does not contain the recorded inputs, or contains them
+    ///   more than once
+    pub fn unseen<'a>(&self, inputs: &'a [S::Input]) -> TAResult<&'a [S::Input]> {
+        let recent = self.recent();
+        if recent.is_empty() {
+            return Ok(inputs);
+        }
+        let mut starts = inputs
+            .windows(recent.len())
+            .enumerate()
+            .filter(|(_, window)| same_bits(window, recent))
+            .map(|(start, _)| start);
+        match (starts.next(), starts.next()) {
+            (Some(start), None) => Ok(&inputs[start + recent.len()..]),
+            (Some(_), Some(_)) => Err(TAError::invalid_input(
+                "the latest inputs of the checkpoint appear more than once in inputs; use unseen_from",
+            )),
+            (None, _) => Err(TAError::invalid_input("inputs do not contain the latest inputs of the checkpoint")),
+        }
+    }
+
+    /// The part of `inputs` after the inputs seen, for a feed replayed from bar `first_bar`
+    ///
+    /// Bars count from the first input the checkpoint saw, so `inputs` is split after its
+    /// first `bars() - first_bar` values. The recorded inputs that `inputs` overlaps are
+    /// checked against it, bit for bit.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if `inputs` starts after the snapshot, ends before it, or differs
+    ///   from the recorded inputs
+    ///
+    /// # Example
+    /// ```rust
+    /// use ta_rust::checkpoint::Checkpointed;
+    /// use ta_rust::common::MAType;
+    /// use ta_rust::overlap::MaState;
+    ///
+    /// let prices = [10.0, 11.0, 12.0, 12.0, 12.0, 12.0, 12.0, 13.0, 14.0];
+    /// let mut sma = Checkpointed::new(MaState::new(3, MAType::SMA).unwrap(), 3).unwrap();
+    /// for &price in &prices[..5] {
+    ///     sma.update(price);
+    /// }
+    /// // [12, 12, 12] appears three times in the feed: only the bar count locates it
+    /// assert!(sma.unseen(&prices).is_err());
+    /// assert_eq!(sma.unseen_from(&prices, 0).unwrap(), &prices[5..]);
+    /// assert_eq!(sma.unseen_from(&prices[2..], 2).unwrap(), &prices[5..]);
+    /// ```
+    pub fn unseen_from<'a>(&self, inputs: &'a [S::Input], first_bar: usize) -> TAResult<&'a [S::Input]> {
+        let seen = self
+            .bars
+            .checked_sub(first_bar)
+            .filter(|&seen| seen <= inputs.len())
+            .ok_or_else(|| TAError::invalid_input("inputs must start at or before the checkpoint and reach it"))?;
+        let recent = self.recent();
+        let overlap = recent.len().min(seen);
+        if !same_bits(&inputs[seen - overlap..seen], &recent[recent.len() - overlap..]) {
+            return Err(TAError::invalid_input("inputs differ from the latest inputs of the checkpoint"));
+        }
+        Ok(&inputs[seen..])
+    }
+}
+
+/// Whether two series hold the same inputs, bit for bit
+fn same_bits<I: RecordedInput>(a: &[I], b: &[I]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x.same_bits(y))
+}
+
+impl<S: StreamingIndicator> StreamingIndicator for Checkpointed<S>
+where
+    S::Input: RecordedInput,
+{
+    type Input = S::Input;
+    type Output = S::Output;
+
+    fn next_value(&mut self, input: S::Input) -> Option<S::Output> {
+        self.update(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::MAType;
+    use crate::overlap::MaState;
+
+    #[test]
+    fn test_resume_matches_uninterrupted() {
+        let prices: Vec<f64> = (0..300).map(|i| 40.0 + (i as f64 * 0.07).sin() * 5.0 + (i % 7) as f64 * 0.1).collect();
+        for ma_type in [MAType::SMA, MAType::EMA, MAType::TEMA] {
+            let mut uninterrupted = Checkpointed::new(MaState::new(20, ma_type).unwrap(), 3).unwrap();
+            let expected: Vec<Option<f64>> = prices.iter().map(|&p| uninterrupted.update(p)).collect();
+
+            let mut first = Checkpointed::new(MaState::new(20, ma_type).unwrap(), 3).unwrap();
+            prices[..137].iter().for_each(|&p| { first.update(p); });
+            assert_eq!(first.recent(), &prices[134..137]);
+            assert_eq!(first.bars(), 137);
+
+            let mut resumed = first.clone();
+            let unseen = resumed.unseen(&prices[100..]).unwrap();
+            let outputs: Vec<Option<f64>> = unseen.iter().map(|&p| resumed.update(p)).collect();
+            assert_eq!(outputs, expected[137..]);
+            assert_eq!(resumed, uninterrupted);
+
+            assert!(first.unseen(&prices[200..]).is_err());
+        }
+        let fresh = Checkpointed::new(MaState::new(5, MAType::SMA).unwrap(), 2).unwrap();
+        assert_eq!(fresh.unseen(&prices).unwrap().len(), prices.len());
+        let err = Checkpointed::new(MaState::new(5, MAType::SMA).unwrap(), 0).unwrap_err();
+        assert_eq!(err.function(), Some("CHECKPOINTED"));
+    }
+
+    #[test]
+    fn test_resume_on_repeated_prices() {
+        let prices = [10.0, 11.0, 12.0, 12.0, 12.0, 12.0, 12.0, 13.0, 14.0];
+        let mut uninterrupted = Checkpointed::new(MaState::new(3, MAType::SMA).unwrap(), 3).unwrap();
+        let expected: Vec<Option<f64>> = prices.iter().map(|&p| uninterrupted.update(p)).collect();
+
+        let mut first = Checkpointed::new(MaState::new(3, MAType::SMA).unwrap(), 3).unwrap();
+        prices[..5].iter().for_each(|&p| { first.update(p); });
+        // The recorded [12, 12, 12] matches at bars 2, 3 and 4 of the replay
+        assert!(first.unseen(&prices).is_err());
+
+        let mut resumed = first.clone();
+        let unseen = resumed.unseen_from(&prices, 0).unwrap();
+        assert_eq!(unseen.len(), 4);
+        let outputs: Vec<Option<f64>> = unseen.iter().map(|&p| resumed.update(p)).collect();
+        assert_eq!(outputs, expected[5..]);
+        assert_eq!(resumed, uninterrupted);
+
+        assert_eq!(first.unseen_from(&prices[3..], 3).unwrap(), &prices[5..]);
+        assert!(first.unseen_from(&prices[6..], 6).is_err());
+        assert!(first.unseen_from(&prices[..4], 0).is_err());
+        assert!(first.unseen_from(&prices, 1).is_err());
+    }
+
+    fn bars(len: usize) -> Vec<(f64, f64, f64)> {
+        (0..len)
+            .map(|i| {
+                let close = 60.0 + (i as f64 * 0.19).sin() * 6.0 + (i % 5) as f64 * 0.2;
+                (close + 0.8 + (i % 3) as f64 * 0.3, close - 0.7 - (i % 4) as f64 * 0.2, close)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resume_hlc_states() {
+        use crate::momentum::StochState;
+        use crate::volatility::AtrState;
+
+        let bars = bars(200);
+
+        let mut uninterrupted = Checkpointed::new(AtrState::new(14).unwrap(), 4).unwrap();
+        let expected: Vec<Option<f64>> = bars.iter().map(|&bar| uninterrupted.update(bar)).collect();
+        let mut first = Checkpointed::new(AtrState::new(14).unwrap(), 4).unwrap();
+        bars[..83].iter().for_each(|&bar| { first.update(bar); });
+        assert_eq!(first.recent(), &bars[79..83]);
+
+        let mut resumed = first.clone();
+        let unseen = resumed.unseen(&bars[50..]).unwrap();
+        let outputs: Vec<Option<f64>> = unseen.iter().map(|&bar| resumed.update(bar)).collect();
+        assert_eq!(outputs, expected[83..]);
+        assert_eq!(resumed, uninterrupted);
+
+        let stoch = || StochState::new(5, 3, MAType::SMA, 3, MAType::SMA).unwrap();
+        let mut uninterrupted = Checkpointed::new(stoch(), 3).unwrap();
+        let expected: Vec<Option<(f64, f64)>> = bars.iter().map(|&bar| uninterrupted.update(bar)).collect();
+        let mut first = Checkpointed::new(stoch(), 3).unwrap();
+        bars[..120].iter().for_each(|&bar| { first.update(bar); });
+
+        let mut resumed = first.clone();
+        let unseen = resumed.unseen_from(&bars[100..], 100).unwrap();
+        let outputs: Vec<Option<(f64, f64)>> = unseen.iter().map(|&bar| resumed.update(bar)).collect();
+        assert_eq!(outputs, expected[120..]);
+        assert_eq!(resumed, uninterrupted);
+
+        // A bar differing only in its low is not the recorded one
+        let mut edited = bars[100..].to_vec();
+        edited[19].1 += 0.01;
+        assert!(first.unseen_from(&edited, 100).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialized_resume_hlc() {
+        use crate::momentum::StochState;
+
+        let bars = bars(150);
+        let mut live = Checkpointed::new(StochState::new(14, 3, MAType::SMA, 3, MAType::EMA).unwrap(), 5).unwrap();
+        bars[..62].iter().for_each(|&bar| { live.update(bar); });
+        let json = serde_json::to_string(&live).unwrap();
+
+        let expected: Vec<Option<(f64, f64)>> = bars[62..].iter().map(|&bar| live.update(bar)).collect();
+        let mut resumed: Checkpointed<StochState> = serde_json::from_str(&json).unwrap();
+        let unseen = resumed.unseen(&bars[40..]).unwrap();
+        let outputs: Vec<Option<(f64, f64)>> = unseen.iter().map(|&bar| resumed.update(bar)).collect();
+        assert_eq!(outputs, expected);
+
+        // Recorded bars write NaN fields as `None`
+        let bar = (1.5, f64::NAN, 1.0).encode();
+        assert_eq!(bar, (Some(1.5), None, Some(1.0)));
+        assert!(<(f64, f64, f64)>::decode(bar).same_bits(&(1.5, f64::NAN, 1.0)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialized_resume() {
+        use crate::momentum::RsiState;
+
+        let prices: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.31).cos() * 4.0).collect();
+        let mut live = Checkpointed::new(RsiState::new(14).unwrap(), 4).unwrap();
+        prices[..70].iter().for_each(|&p| { live.update(p); });
+        let json = serde_json::to_string(&live).unwrap();
+
+        let expected: Vec<Option<f64>> = prices[70..].iter().map(|&p| live.update(p)).collect();
+        let mut resumed: Checkpointed<RsiState> = serde_json::from_str(&json).unwrap();
+        let outputs: Vec<Option<f64>> = prices[70..].iter().map(|&p| resumed.update(p)).collect();
+        assert_eq!(outputs, expected);
+
+        // Snapshots edited to a smaller or zero window are rejected
+        let edited = json.replace("\"window\":4", "\"window\":1");
+        assert!(serde_json::from_str::<Checkpointed<RsiState>>(&edited).is_err());
+        let edited = json.replace("\"window\":4", "\"window\":0");
+        assert!(serde_json::from_str::<Checkpointed<RsiState>>(&edited).is_err());
+        // So are snapshots recording more inputs than they counted
+        assert!(json.contains("\"bars\":70"));
+        let edited = json.replace("\"bars\":70", "\"bars\":2");
+        assert!(serde_json::from_str::<Checkpointed<RsiState>>(&edited).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! `alloc` re-exports for `no_std` builds
+//!
+//! Modules glob-import this under `#[cfg(not(feature = "std"))]` so that `Vec`, `vec!`,
+//! `String` and `format!` resolve as they do with the std prelude, and so that the
+//! math methods of [`Float`] (backed by libm) stand in for the inherent `f64` ones.
+
+pub use alloc::format;
+pub use alloc::string::{String, ToString};
+pub use alloc::vec;
+pub use alloc::vec::Vec;
+
+pub use crate::common::float::Float;
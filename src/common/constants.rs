@@ -14,22 +14,21 @@ pub mod pattern {
 
 /// Default parameters for various indicators
 pub mod defaults {
-    use crate::common::Period;
 
     /// Default period for most indicators
-    pub const PERIOD: Period = 14;
+    pub const PERIOD: usize = 14;
     
     /// Default fast period for MACD
-    pub const MACD_FAST: Period = 12;
+    pub const MACD_FAST: usize = 12;
     
     /// Default slow period for MACD
-    pub const MACD_SLOW: Period = 26;
+    pub const MACD_SLOW: usize = 26;
     
     /// Default signal period for MACD
-    pub const MACD_SIGNAL: Period = 9;
+    pub const MACD_SIGNAL: usize = 9;
     
     /// Default period for Bollinger Bands
-    pub const BBANDS_PERIOD: Period = 20;
+    pub const BBANDS_PERIOD: usize = 20;
     
     /// Default standard deviation multiplier for Bollinger Bands
     pub const BBANDS_STDDEV: f64 = 2.0;
@@ -40,6 +39,12 @@ pub mod defaults {
     /// Default maximum acceleration factor for Parabolic SAR
     pub const SAR_MAX_AF: f64 = 0.20;
     
+    /// Default fast period of the KAMA smoothing constant
+    pub const KAMA_FAST_PERIOD: usize = 2;
+    
+    /// Default slow period of the KAMA smoothing constant
+    pub const KAMA_SLOW_PERIOD: usize = 30;
+    
     /// Default fast limit for MAMA
     pub const MAMA_FAST_LIMIT: f64 = 0.5;
     
@@ -50,22 +55,22 @@ pub mod defaults {
     pub const T3_VOLUME_FACTOR: f64 = 0.7;
     
     /// Default period 1 for Ultimate Oscillator
-    pub const ULTOSC_PERIOD1: Period = 7;
+    pub const ULTOSC_PERIOD1: usize = 7;
     
     /// Default period 2 for Ultimate Oscillator
-    pub const ULTOSC_PERIOD2: Period = 14;
+    pub const ULTOSC_PERIOD2: usize = 14;
     
     /// Default period 3 for Ultimate Oscillator
-    pub const ULTOSC_PERIOD3: Period = 28;
+    pub const ULTOSC_PERIOD3: usize = 28;
     
     /// Default fast period for Stochastic
-    pub const STOCH_FASTK: Period = 5;
+    pub const STOCH_FASTK: usize = 5;
     
     /// Default slow K period for Stochastic
-    pub const STOCH_SLOWK: Period = 3;
+    pub const STOCH_SLOWK: usize = 3;
     
     /// Default slow D period for Stochastic
-    pub const STOCH_SLOWD: Period = 3;
+    pub const STOCH_SLOWD: usize = 3;
 }
 
 /// Mathematical constants
@@ -135,69 +140,68 @@ pub mod thresholds {
 /// Unstable periods for various indicators
 /// These represent the number of periods needed for an indicator to stabilize
 pub mod unstable_periods {
-    use crate::common::Period;
 
     /// EMA unstable period calculation: 2 * period - 1
-    pub fn ema(period: Period) -> Period {
+    pub fn ema(period: usize) -> usize {
         2 * period - 1
     }
     
     /// RSI unstable period (using Wilder's smoothing)
-    pub fn rsi(period: Period) -> Period {
+    pub fn rsi(period: usize) -> usize {
         period + 100 // Conservative estimate for Wilder's smoothing
     }
     
     /// ATR unstable period (using Wilder's smoothing)
-    pub fn atr(period: Period) -> Period {
+    pub fn atr(period: usize) -> usize {
         period + 100 // Conservative estimate for Wilder's smoothing
     }
     
     /// ADX unstable period
-    pub fn adx(period: Period) -> Period {
+    pub fn adx(period: usize) -> usize {
         2 * period + 100 // DX smoothing + ADX smoothing
     }
     
     /// MACD unstable period
-    pub fn macd(slow_period: Period, signal_period: Period) -> Period {
+    pub fn macd(slow_period: usize, signal_period: usize) -> usize {
         slow_period + signal_period - 1
     }
     
     /// Stochastic unstable period
-    pub fn stochastic(fastk_period: Period, slowk_period: Period, slowd_period: Period) -> Period {
+    pub fn stochastic(fastk_period: usize, slowk_period: usize, slowd_period: usize) -> usize {
         fastk_period + slowk_period + slowd_period - 2
     }
     
     /// Bollinger Bands unstable period
-    pub fn bbands(period: Period) -> Period {
+    pub fn bbands(period: usize) -> usize {
         period - 1
     }
     
     /// KAMA unstable period
-    pub fn kama(period: Period) -> Period {
+    pub fn kama(period: usize) -> usize {
         period + 32 // Conservative estimate for adaptive period
     }
     
     /// Hilbert Transform unstable period
-    pub const HILBERT_TRANSFORM: Period = 63;
+    pub const HILBERT_TRANSFORM: usize = 63;
     
     /// MAMA unstable period
-    pub const MAMA: Period = 32;
+    pub const MAMA: usize = 32;
     
     /// T3 unstable period
-    pub fn t3(period: Period) -> Period {
+    pub fn t3(period: usize) -> usize {
         6 * period // Six EMA calculations
     }
 }
 
 /// Validation limits
 pub mod limits {
-    use crate::common::{Price, Period};
+    use crate::common::{Price};
 
     /// Maximum reasonable period for most indicators
-    pub const MAX_PERIOD: Period = 100000;
+    pub const MAX_PERIOD: usize = 100000;
     
     /// Minimum period for most indicators
-    pub const MIN_PERIOD: Period = 1;
+    pub const MIN_PERIOD: usize = 1;
     
     /// Maximum price value (to prevent overflow)
     pub const MAX_PRICE: Price = 1e15;
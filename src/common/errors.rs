@@ -1,75 +1,105 @@
 //! Error handling for TA-Rust
 
+use core::fmt;
+
 use thiserror::Error;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Result type used throughout the library
 pub type TAResult<T> = Result<T, TAError>;
 
 /// Errors that can occur during technical analysis calculations
 #[derive(Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TAError {
     /// Insufficient data provided for calculation
-    #[error("Insufficient data: need at least {required} data points, got {provided}")]
+    #[error("{}Insufficient data: need at least {required} data points, got {provided}", Context(.function))]
     InsufficientData {
         /// Required number of data points
         required: usize,
         /// Provided number of data points
         provided: usize,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 
     /// Invalid parameter value
-    #[error("Invalid parameter '{parameter}': {reason}")]
+    #[error("{}Invalid parameter '{parameter}': {reason}", Context(.function))]
     InvalidParameter {
         /// Parameter name
         parameter: String,
         /// Reason why the parameter is invalid
         reason: String,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 
     /// Empty input data
-    #[error("Input data is empty")]
-    EmptyInput,
+    #[error("{}Input data is empty", Context(.function))]
+    EmptyInput {
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
+    },
 
     /// Mismatched input lengths
-    #[error("Input arrays have different lengths: {details}")]
+    #[error("{}Input arrays have different lengths: {details}", Context(.function))]
     MismatchedInputs {
         /// Details about the mismatch
         details: String,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 
     /// Invalid input values (NaN, infinite, etc.)
-    #[error("Invalid input values detected: {details}")]
+    #[error("{}Invalid input values detected: {details}", Context(.function))]
     InvalidInput {
         /// Details about the invalid input
         details: String,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 
     /// Calculation overflow or underflow
-    #[error("Numerical error during calculation: {details}")]
+    #[error("{}Numerical error during calculation: {details}", Context(.function))]
     NumericalError {
         /// Details about the numerical error
         details: String,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 
     /// Unsupported operation or feature
-    #[error("Unsupported operation: {operation}")]
+    #[error("{}Unsupported operation: {operation}", Context(.function))]
     UnsupportedOperation {
         /// Description of the unsupported operation
         operation: String,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 
     /// Internal library error
-    #[error("Internal error: {details}")]
+    #[error("{}Internal error: {details}", Context(.function))]
     InternalError {
         /// Details about the internal error
         details: String,
+        /// Function that failed
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        function: Option<&'static str>,
     },
 }
 
 impl TAError {
     /// Creates an insufficient data error
     pub fn insufficient_data(required: usize, provided: usize) -> Self {
-        Self::InsufficientData { required, provided }
+        Self::InsufficientData { required, provided, function: None }
     }
 
     /// Creates an invalid parameter error
@@ -77,6 +107,7 @@ impl TAError {
         Self::InvalidParameter {
             parameter: parameter.into(),
             reason: reason.into(),
+            function: None,
         }
     }
 
@@ -84,6 +115,7 @@ impl TAError {
     pub fn mismatched_inputs<S: Into<String>>(details: S) -> Self {
         Self::MismatchedInputs {
             details: details.into(),
+            function: None,
         }
     }
 
@@ -91,6 +123,7 @@ impl TAError {
     pub fn invalid_input<S: Into<String>>(details: S) -> Self {
         Self::InvalidInput {
             details: details.into(),
+            function: None,
         }
     }
 
@@ -98,6 +131,7 @@ impl TAError {
     pub fn numerical_error<S: Into<String>>(details: S) -> Self {
         Self::NumericalError {
             details: details.into(),
+            function: None,
         }
     }
 
@@ -105,6 +139,7 @@ impl TAError {
     pub fn unsupported_operation<S: Into<String>>(operation: S) -> Self {
         Self::UnsupportedOperation {
             operation: operation.into(),
+            function: None,
         }
     }
 
@@ -112,6 +147,122 @@ impl TAError {
     pub fn internal_error<S: Into<String>>(details: S) -> Self {
         Self::InternalError {
             details: details.into(),
+            function: None,
+        }
+    }
+
+    /// Creates an empty input error
+    pub fn empty_input() -> Self {
+        Self::EmptyInput { function: None }
+    }
+
+    /// Records the function the error came from
+    ///
+    /// Replaces any function already recorded, so an error names the outermost function
+    /// that added context to it: the one the caller called rather than those it is built on.
+    ///
+    /// # Examples
+    /// ```
+    /// use ta_rust::common::{ErrorKind, TAError};
+    ///
+    /// let err = TAError::insufficient_data(14, 5).in_function("RSI").in_function("PIPELINE");
+    /// assert_eq!(err.function(), Some("PIPELINE"));
+    /// assert_eq!(err.kind(), ErrorKind::InsufficientData);
+    /// assert_eq!(err.to_string(), "PIPELINE: Insufficient data: need at least 14 data points, got 5");
+    /// ```
+    pub fn in_function(mut self, name: &'static str) -> Self {
+        *self.function_mut() = Some(name);
+        self
+    }
+
+    fn function_mut(&mut self) -> &mut Option<&'static str> {
+        match self {
+            Self::InsufficientData { function, .. }
+            | Self::InvalidParameter { function, .. }
+            | Self::EmptyInput { function }
+            | Self::MismatchedInputs { function, .. }
+            | Self::InvalidInput { function, .. }
+            | Self::NumericalError { function, .. }
+            | Self::UnsupportedOperation { function, .. }
+            | Self::InternalError { function, .. } => function,
+        }
+    }
+
+    /// Function the error came from, if recorded
+    pub fn function(&self) -> Option<&'static str> {
+        match *self {
+            Self::InsufficientData { function, .. }
+            | Self::InvalidParameter { function, .. }
+            | Self::EmptyInput { function }
+            | Self::MismatchedInputs { function, .. }
+            | Self::InvalidInput { function, .. }
+            | Self::NumericalError { function, .. }
+            | Self::UnsupportedOperation { function, .. }
+            | Self::InternalError { function, .. } => function,
+        }
+    }
+
+    /// Name of the invalid parameter, for `InvalidParameter` errors
+    pub fn parameter(&self) -> Option<&str> {
+        match self {
+            Self::InvalidParameter { parameter, .. } => Some(parameter),
+            _ => None,
+        }
+    }
+
+    /// Category of the error, without its details
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InsufficientData { .. } => ErrorKind::InsufficientData,
+            Self::InvalidParameter { .. } => ErrorKind::InvalidParameter,
+            Self::EmptyInput { .. } => ErrorKind::EmptyInput,
+            Self::MismatchedInputs { .. } => ErrorKind::MismatchedInputs,
+            Self::InvalidInput { .. } => ErrorKind::InvalidInput,
+            Self::NumericalError { .. } => ErrorKind::NumericalError,
+            Self::UnsupportedOperation { .. } => ErrorKind::UnsupportedOperation,
+            Self::InternalError { .. } => ErrorKind::InternalError,
+        }
+    }
+}
+
+/// Runs `f`, recording `function` in its error
+///
+/// Wraps the bodies of public functions so an error names the function the caller
+/// called rather than the ones it is built on, e.g. `BBANDS` rather than `SMA`.
+pub(crate) fn with_function<T>(function: &'static str, f: impl FnOnce() -> TAResult<T>) -> TAResult<T> {
+    f().map_err(|err| err.in_function(function))
+}
+
+/// Category of a [`TAError`], one per variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorKind {
+    /// Insufficient data provided for calculation
+    InsufficientData,
+    /// Invalid parameter value
+    InvalidParameter,
+    /// Empty input data
+    EmptyInput,
+    /// Mismatched input lengths
+    MismatchedInputs,
+    /// Invalid input values
+    InvalidInput,
+    /// Calculation overflow or underflow
+    NumericalError,
+    /// Unsupported operation or feature
+    UnsupportedOperation,
+    /// Internal library error
+    InternalError,
+}
+
+/// Displays the function of an error as a message prefix
+struct Context<'a>(&'a Option<&'static str>);
+
+impl fmt::Display for Context<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(function) => write!(f, "{}: ", function),
+            None => Ok(()),
         }
     }
 }
@@ -123,7 +274,7 @@ mod tests {
     #[test]
     fn test_error_creation() {
         let err = TAError::insufficient_data(10, 5);
-        assert!(matches!(err, TAError::InsufficientData { required: 10, provided: 5 }));
+        assert!(matches!(err, TAError::InsufficientData { required: 10, provided: 5, .. }));
 
         let err = TAError::invalid_parameter("period", "must be positive");
         assert!(matches!(err, TAError::InvalidParameter { .. }));
@@ -150,4 +301,29 @@ mod tests {
         assert_eq!(err1, err2);
         assert_ne!(err1, err3);
     }
+
+    #[test]
+    fn test_error_context() {
+        let err = TAError::invalid_parameter("period", "period must be greater than 0");
+        assert_eq!(err.function(), None);
+        assert_eq!(err.parameter(), Some("period"));
+        assert_eq!(err.to_string(), "Invalid parameter 'period': period must be greater than 0");
+
+        let err = err.in_function("MACD");
+        assert_eq!(err.kind(), ErrorKind::InvalidParameter);
+        assert_eq!(err.to_string(), "MACD: Invalid parameter 'period': period must be greater than 0");
+        assert_ne!(err, TAError::invalid_parameter("period", "period must be greater than 0"));
+
+        assert_eq!(TAError::empty_input().in_function("SMA").to_string(), "SMA: Input data is empty");
+        assert_eq!(TAError::mismatched_inputs("high, low").kind(), ErrorKind::MismatchedInputs);
+        assert_eq!(TAError::numerical_error("overflow").parameter(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_is_std_error() {
+        let err: Box<dyn std::error::Error + Send + Sync> = Box::new(TAError::insufficient_data(3, 1).in_function("ATR"));
+        assert_eq!(err.to_string(), "ATR: Insufficient data: need at least 3 data points, got 1");
+        assert!(err.downcast_ref::<TAError>().is_some());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,239 @@
+//! Floating point abstraction for TA-Rust
+//!
+//! The core indicator functions are generic over [`Float`] so that `f32`
+//! pipelines can use the library without converting their arrays.
+//! [`Price`](crate::common::Price) remains `f64` and is the default everywhere else.
+//!
+//! The transcendental methods forward to the inherent `std` implementations, or to
+//! `libm` when built with the `no_std` feature.
+
+use core::fmt::{Debug, Display};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Floating point type usable as indicator input and output
+///
+/// Implemented for `f32` and `f64`.
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Debug
+    + Display
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + for<'a> Sum<&'a Self>
+    + Send
+    + Sync
+    + 'static
+{
+    /// Not-a-number, used for warm-up slots
+    const NAN: Self;
+    /// Additive identity
+    const ZERO: Self;
+    /// Multiplicative identity
+    const ONE: Self;
+    /// Machine epsilon
+    const EPSILON: Self;
+    /// Positive infinity
+    const INFINITY: Self;
+    /// Negative infinity
+    const NEG_INFINITY: Self;
+
+    /// Converts from an `f64`, rounding if necessary
+    fn from_f64(value: f64) -> Self;
+    /// Converts from a count or period
+    fn from_usize(value: usize) -> Self;
+    /// Widens (or passes through) to `f64`
+    fn to_f64(self) -> f64;
+
+    /// Returns true if the value is NaN
+    fn is_nan(self) -> bool;
+    /// Returns true if the value is neither infinite nor NaN
+    fn is_finite(self) -> bool;
+    /// Absolute value
+    fn abs(self) -> Self;
+    /// Square root
+    fn sqrt(self) -> Self;
+    /// Maximum of two values, ignoring NaN
+    fn max(self, other: Self) -> Self;
+    /// Minimum of two values, ignoring NaN
+    fn min(self, other: Self) -> Self;
+    /// Raises to an integer power
+    fn powi(self, n: i32) -> Self;
+    /// Raises to a floating point power
+    fn powf(self, n: Self) -> Self;
+    /// Natural logarithm
+    fn ln(self) -> Self;
+    /// Base 10 logarithm
+    fn log10(self) -> Self;
+    /// Exponential function (e^x)
+    fn exp(self) -> Self;
+    /// Sine (radians)
+    fn sin(self) -> Self;
+    /// Cosine (radians)
+    fn cos(self) -> Self;
+    /// Tangent (radians)
+    fn tan(self) -> Self;
+    /// Arcsine in radians
+    fn asin(self) -> Self;
+    /// Arccosine in radians
+    fn acos(self) -> Self;
+    /// Arctangent in radians
+    fn atan(self) -> Self;
+    /// Four quadrant arctangent of `self` (y) and `other` (x)
+    fn atan2(self, other: Self) -> Self;
+    /// Hyperbolic sine
+    fn sinh(self) -> Self;
+    /// Hyperbolic cosine
+    fn cosh(self) -> Self;
+    /// Hyperbolic tangent
+    fn tanh(self) -> Self;
+    /// Largest integer less than or equal to the value
+    fn floor(self) -> Self;
+    /// Smallest integer greater than or equal to the value
+    fn ceil(self) -> Self;
+    /// Nearest integer, rounding half away from zero
+    fn round(self) -> Self;
+}
+
+// Generates a method forwarding to the inherent std implementation, or to
+// the given libm function when building without std
+macro_rules! forward {
+    ($t:ident, $name:ident, $libm:ident) => {
+        #[inline]
+        fn $name(self) -> Self {
+            #[cfg(feature = "std")]
+            {
+                $t::$name(self)
+            }
+            #[cfg(not(feature = "std"))]
+            {
+                libm::$libm(self)
+            }
+        }
+    };
+    ($t:ident, $name:ident, $libm:ident, $arg:ident: $arg_t:ty => $conv:expr) => {
+        #[inline]
+        fn $name(self, $arg: $arg_t) -> Self {
+            #[cfg(feature = "std")]
+            {
+                $t::$name(self, $arg)
+            }
+            #[cfg(not(feature = "std"))]
+            {
+                libm::$libm(self, $conv)
+            }
+        }
+    };
+}
+
+macro_rules! impl_float {
+    ($t:ident, $($libm:ident),*) => {
+        impl_float!(@impl $t, [$($libm),*]);
+    };
+    (@impl $t:ident, [$sqrt:ident, $pow:ident, $log:ident, $log10:ident, $exp:ident,
+        $sin:ident, $cos:ident, $tan:ident, $asin:ident, $acos:ident, $atan:ident,
+        $atan2:ident, $sinh:ident, $cosh:ident, $tanh:ident, $floor:ident, $ceil:ident,
+        $round:ident, $fabs:ident]) => {
+        impl Float for $t {
+            const NAN: Self = $t::NAN;
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const EPSILON: Self = $t::EPSILON;
+            const INFINITY: Self = $t::INFINITY;
+            const NEG_INFINITY: Self = $t::NEG_INFINITY;
+
+            #[inline]
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            #[inline]
+            fn from_usize(value: usize) -> Self {
+                value as $t
+            }
+
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            #[inline]
+            fn is_nan(self) -> bool {
+                $t::is_nan(self)
+            }
+
+            #[inline]
+            fn is_finite(self) -> bool {
+                $t::is_finite(self)
+            }
+
+            #[inline]
+            fn max(self, other: Self) -> Self {
+                $t::max(self, other)
+            }
+
+            #[inline]
+            fn min(self, other: Self) -> Self {
+                $t::min(self, other)
+            }
+
+            forward!($t, abs, $fabs);
+            forward!($t, sqrt, $sqrt);
+            forward!($t, powi, $pow, n: i32 => n as $t);
+            forward!($t, powf, $pow, n: Self => n);
+            forward!($t, ln, $log);
+            forward!($t, log10, $log10);
+            forward!($t, exp, $exp);
+            forward!($t, sin, $sin);
+            forward!($t, cos, $cos);
+            forward!($t, tan, $tan);
+            forward!($t, asin, $asin);
+            forward!($t, acos, $acos);
+            forward!($t, atan, $atan);
+            forward!($t, atan2, $atan2, other: Self => other);
+            forward!($t, sinh, $sinh);
+            forward!($t, cosh, $cosh);
+            forward!($t, tanh, $tanh);
+            forward!($t, floor, $floor);
+            forward!($t, ceil, $ceil);
+            forward!($t, round, $round);
+        }
+    };
+}
+
+impl_float!(f32, sqrtf, powf, logf, log10f, expf, sinf, cosf, tanf, asinf, acosf, atanf,
+    atan2f, sinhf, coshf, tanhf, floorf, ceilf, roundf, fabsf);
+impl_float!(f64, sqrt, pow, log, log10, exp, sin, cos, tan, asin, acos, atan,
+    atan2, sinh, cosh, tanh, floor, ceil, round, fabs);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean<T: Float>(data: &[T]) -> T {
+        data.iter().copied().sum::<T>() / T::from_usize(data.len())
+    }
+
+    #[test]
+    fn test_float_f32_and_f64() {
+        assert_eq!(mean(&[1.0f32, 2.0, 3.0]), 2.0f32);
+        assert_eq!(mean(&[1.0f64, 2.0, 3.0]), 2.0f64);
+    }
+
+    #[test]
+    fn test_float_constants() {
+        assert!(<f32 as Float>::NAN.is_nan());
+        assert!(!<f64 as Float>::INFINITY.is_finite());
+        assert_eq!(<f32 as Float>::from_f64(0.5), 0.5f32);
+        assert_eq!(Float::sqrt(4.0f32), 2.0);
+    }
+}
@@ -0,0 +1,436 @@
+//! Lookback periods
+//!
+//! The lookback of an indicator is the number of leading bars for which it produces no
+//! value: the NaNs at the start of its output, or the zero placeholders of index outputs
+//! such as `MAXINDEX`. Like TA-Lib's `TA_*_Lookback` functions, it depends on the parameters
+//! only, so output buffers can be pre-sized and warm-up bars skipped without computing the
+//! indicator.
+//!
+//! Window indicators without a dedicated function below (`WILLR`, `CCI`, `ATR`, `LINEARREG`,
+//! ...) have a lookback of `period - 1`. Every registered function, including those with
+//! several outputs, can be queried with [`FunctionInfo::lookback`](crate::common::registry::FunctionInfo::lookback)
+//! and [`FunctionInfo::output_lookbacks`](crate::common::registry::FunctionInfo::output_lookbacks).
+//!
+//! # Example
+//! ```
+//! use ta_rust::common::lookback::{macd_lookback, rsi_lookback};
+//! use ta_rust::momentum::rsi;
+//!
+//! let close: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+//! let values = rsi(&close, 14).unwrap();
+//! assert_eq!(values.iter().take_while(|v| v.is_nan()).count(), rsi_lookback(14));
+//! assert_eq!(macd_lookback(12, 26, 9), 33);
+//! ```
+
+use crate::common::{MAType};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Lookback of the Simple Moving Average
+pub fn sma_lookback(period: usize) -> usize {
+    period.saturating_sub(1)
+}
+
+/// Lookback of the Exponential Moving Average, seeded with the SMA of the first `period` values
+pub fn ema_lookback(period: usize) -> usize {
+    period.saturating_sub(1)
+}
+
+/// Lookback of the Weighted Moving Average
+pub fn wma_lookback(period: usize) -> usize {
+    period.saturating_sub(1)
+}
+
+/// Lookback of the Double Exponential Moving Average
+pub fn dema_lookback(period: usize) -> usize {
+    2 * ema_lookback(period)
+}
+
+/// Lookback of the Triple Exponential Moving Average
+pub fn tema_lookback(period: usize) -> usize {
+    3 * ema_lookback(period)
+}
+
+/// Lookback of the Triangular Moving Average
+pub fn trima_lookback(period: usize) -> usize {
+    period.saturating_sub(1)
+}
+
+/// Lookback of the Kaufman Adaptive Moving Average
+pub fn kama_lookback(period: usize) -> usize {
+    period
+}
+
+/// Lookback of the MESA Adaptive Moving Average, independent of its limits
+pub fn mama_lookback() -> usize {
+    32
+}
+
+/// Lookback of T3, built from six chained EMAs
+pub fn t3_lookback(period: usize) -> usize {
+    6 * ema_lookback(period)
+}
+
+/// Lookback of the Zero-Lag EMA, an EMA of the price de-lagged by `(period - 1) / 2` bars
+pub fn zlema_lookback(period: usize) -> usize {
+    period.saturating_sub(1) / 2 + ema_lookback(period)
+}
+
+/// Lookback of a moving average of the given type
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::MAType;
+/// use ta_rust::common::lookback::ma_lookback;
+///
+/// assert_eq!(ma_lookback(10, MAType::SMA), 9);
+/// assert_eq!(ma_lookback(10, MAType::DEMA), 18);
+/// ```
+pub fn ma_lookback(period: usize, ma_type: MAType) -> usize {
+    match ma_type {
+        MAType::SMA => sma_lookback(period),
+        MAType::EMA => ema_lookback(period),
+        MAType::WMA => wma_lookback(period),
+        MAType::DEMA => dema_lookback(period),
+        MAType::TEMA => tema_lookback(period),
+        MAType::TRIMA => trima_lookback(period),
+        MAType::KAMA => kama_lookback(period),
+        MAType::MAMA => mama_lookback(),
+        MAType::T3 => t3_lookback(period),
+    }
+}
+
+/// Lookback of Bollinger Bands, set by the middle band's moving average or, for MAMA,
+/// which ignores the period, by the standard deviation window
+pub fn bbands_lookback(period: usize, ma_type: MAType) -> usize {
+    ma_lookback(period, ma_type).max(period.saturating_sub(1))
+}
+
+/// Lookback of the Relative Strength Index, which needs `period` price changes
+pub fn rsi_lookback(period: usize) -> usize {
+    period
+}
+
+/// Lookback of MACD's signal line and histogram
+///
+/// The MACD line itself starts `signal_period - 1` bars earlier, at
+/// `ema_lookback(fast_period.max(slow_period))`.
+pub fn macd_lookback(fast_period: usize, slow_period: usize, signal_period: usize) -> usize {
+    ema_lookback(fast_period.max(slow_period)) + ema_lookback(signal_period)
+}
+
+/// Lookback of MACDEXT's signal line and histogram
+///
+/// With a MAMA signal line, which ignores its period, the signal can start before the
+/// histogram, which is only defined from `slow_period + signal_period - 2` on.
+pub fn macdext_lookback(
+    fast_period: usize,
+    fast_ma: MAType,
+    slow_period: usize,
+    slow_ma: MAType,
+    signal_period: usize,
+    signal_ma: MAType,
+) -> usize {
+    let line = macdext_line_lookback(fast_period, fast_ma, slow_period, slow_ma);
+    (line + ma_lookback(signal_period, signal_ma)).max((slow_period + signal_period).saturating_sub(2))
+}
+
+/// Lookback of MACDEXT's MACD line, which starts no earlier than the slow period
+fn macdext_line_lookback(fast_period: usize, fast_ma: MAType, slow_period: usize, slow_ma: MAType) -> usize {
+    ma_lookback(fast_period, fast_ma)
+        .max(ma_lookback(slow_period, slow_ma))
+        .max(slow_period.saturating_sub(1))
+}
+
+/// Lookback of the Absolute and Percentage Price Oscillators
+///
+/// The oscillators start no earlier than the slow period, even for MAMA, which ignores it.
+pub fn apo_lookback(fast_period: usize, slow_period: usize, ma_type: MAType) -> usize {
+    ma_lookback(fast_period, ma_type)
+        .max(ma_lookback(slow_period, ma_type))
+        .max(slow_period.saturating_sub(1))
+}
+
+/// Lookback of the slow %D line of the Stochastic; slow %K starts `slowd` lookback bars earlier
+pub fn stoch_lookback(
+    fastk_period: usize,
+    slowk_period: usize,
+    slowk_ma: MAType,
+    slowd_period: usize,
+    slowd_ma: MAType,
+) -> usize {
+    fastk_period.saturating_sub(1) + ma_lookback(slowk_period, slowk_ma) + ma_lookback(slowd_period, slowd_ma)
+}
+
+/// Lookback of the fast %D line of the Fast Stochastic
+pub fn stochf_lookback(fastk_period: usize, fastd_period: usize, fastd_ma: MAType) -> usize {
+    fastk_period.saturating_sub(1) + ma_lookback(fastd_period, fastd_ma)
+}
+
+/// Lookback of the fast %D line of the Stochastic RSI
+pub fn stochrsi_lookback(period: usize, fastk_period: usize, fastd_period: usize, fastd_ma: MAType) -> usize {
+    rsi_lookback(period) + fastk_period.saturating_sub(1) + ma_lookback(fastd_period, fastd_ma)
+}
+
+/// Lookback of the smoothed directional movements, PLUS_DM and MINUS_DM
+pub fn dm_lookback(period: usize) -> usize {
+    period.saturating_sub(1).max(1)
+}
+
+/// Lookback of the Directional Movement Index and the directional indicators
+pub fn dx_lookback(period: usize) -> usize {
+    period
+}
+
+/// Lookback of the Average Directional Movement Index, Wilder's average of `period` DX values
+pub fn adx_lookback(period: usize) -> usize {
+    dx_lookback(period) + period.saturating_sub(1)
+}
+
+/// Lookback of the ADX Rating, the average of ADX and ADX `period - 1` bars earlier
+pub fn adxr_lookback(period: usize) -> usize {
+    adx_lookback(period) + period.saturating_sub(1)
+}
+
+/// Lookback of TRIX, the one-bar rate of change of a triple EMA
+pub fn trix_lookback(period: usize) -> usize {
+    3 * ema_lookback(period) + 1
+}
+
+/// Lookback of the Relative Vigor Index signal line; the RVI line starts 3 bars earlier
+pub fn rvi_lookback(period: usize) -> usize {
+    period + 5
+}
+
+/// Lookback of the Mass Index
+pub fn mass_index_lookback(ema_period: usize, sum_period: usize) -> usize {
+    2 * ema_lookback(ema_period) + sum_period.saturating_sub(1)
+}
+
+/// Lookback of the Ulcer Index, a window of drawdowns from a rolling high
+pub fn ulcer_index_lookback(period: usize) -> usize {
+    2 * period.saturating_sub(1)
+}
+
+/// Lookback of each output of a registered function, keyed by its registry name
+///
+/// `p` holds every parameter, validated. Returns `None` for unknown names.
+pub(crate) fn registry_lookbacks(name: &str, p: &[f64], outputs: usize) -> Option<Vec<usize>> {
+    let n = |i: usize| p[i] as usize;
+    let ma = |i: usize| MAType::all()[p[i] as usize];
+    let all = |lookback: usize| Some(vec![lookback; outputs]);
+
+    match name {
+        // Functions of the current bar only
+        "ZIGZAG" | "BOP" | "AD" | "DELTA" | "OBV" | "PVT" | "AVGPRICE" | "MEDPRICE"
+        | "TYPPRICE" | "WCLPRICE" | "ADD" | "DIV" | "MULT" | "SUB" | "ACOS" | "ASIN" | "ATAN" | "CEIL" | "COS"
+        | "COSH" | "EXP" | "FLOOR" | "LN" | "LOG10" | "SIN" | "SINH" | "SQRT" | "TAN" | "TANH" => all(0),
+        // The previous bar and the current one, as TA-Lib reports them
+        "SAR" | "SAREXT" | "TRANGE" => all(1),
+        // Recursive filters defined from the first bar
+        "BANDPASS" | "DECYCLER" | "HIGHPASS" | "KALMAN" | "KALMANTREND" | "ROOFING" | "SUPERSMOOTHER" => all(0),
+        // Windows of `period` bars
+        "DONCHIAN" | "FRAMA" | "MCGINLEY" | "MIDPOINT" | "MIDPRICE" | "CCI"
+        | "ELDERRAY" | "IMI" | "WILLR" | "CHOP" | "GKVOL" | "PKVOL"
+        | "RSVOL" | "AUTOCORR" | "AVGDEV" | "CAPM" | "CORREL" | "COVAR" | "HEDGERATIO"
+        | "LINEARREG" | "LINEARREG_ANGLE" | "LINEARREG_INTERCEPT" | "LINEARREG_SLOPE" | "LINREGCHANNEL" | "MAD" | "MEDIAN"
+        | "QUANTILE" | "STDDEV" | "TSF" | "VAR" | "MAX" | "MAXINDEX" | "MIN" | "MININDEX" | "MINMAX"
+        | "MINMAXINDEX" | "SUM" | "VPROFILE" => all(n(0) - 1),
+        // Windows of `period` changes
+        "AROON" | "AROONOSC" | "BETA" | "CMO" | "MFI" | "MOM" | "ROC" | "ROCP" | "ROCR" | "ROCR100" | "RSI"
+        | "HV" | "YZVOL" | "EOM" | "FORCE" | "TWIGGSMF" | "ADF" | "HURST" | "PERCENTRANK" | "ATR" | "NATR" => all(n(0)),
+        "MINUS_DM" | "PLUS_DM" => all(dm_lookback(n(0))),
+        "DX" | "MINUS_DI" | "PLUS_DI" => all(dx_lookback(n(0))),
+        "SMA" => all(sma_lookback(n(0))),
+        "EMA" => all(ema_lookback(n(0))),
+        "WMA" => all(wma_lookback(n(0))),
+        "TRIMA" => all(trima_lookback(n(0))),
+        "DEMA" => all(dema_lookback(n(0))),
+        "TEMA" => all(tema_lookback(n(0))),
+        "KAMA" => all(kama_lookback(n(0))),
+        "MAMA" => all(mama_lookback()),
+        // Cycle-adaptive windows, at least a measured cycle in; %D averages %K
+        "RSICYCLE" => all(mama_lookback()),
+        "STOCHCYCLE" => Some(vec![mama_lookback(), mama_lookback() + sma_lookback(n(0))]),
+        "T3" => all(t3_lookback(n(0))),
+        "ZLEMA" => all(zlema_lookback(n(0))),
+        "MA" => all(ma_lookback(n(0), ma(1))),
+        // The middle band is the average alone, the outer ones also need a full window
+        "BBANDS" => {
+            let bands = bbands_lookback(n(0), ma(3));
+            Some(vec![bands, ma_lookback(n(0), ma(3)), bands])
+        }
+        "KELTNER" => all(ema_lookback(n(0)).max(n(1) - 1)),
+        "ICHIMOKU" => Some(vec![n(0) - 1, n(1) - 1, n(0).max(n(1)) - 1, n(2) - 1, 0]),
+        // Each bar's average has warmed up by the maximum period's lookback
+        "MAVP" => all(ma_lookback(n(1), ma(2))),
+        "ADX" => all(adx_lookback(n(0))),
+        "ADXR" => all(adxr_lookback(n(0))),
+        "APO" | "PPO" => all(apo_lookback(n(0), n(1), ma(2))),
+        "MACD" => {
+            let line = ema_lookback(n(0).max(n(1)));
+            Some(vec![line, macd_lookback(n(0), n(1), n(2)), macd_lookback(n(0), n(1), n(2))])
+        }
+        "MACDEXT" => {
+            let line = macdext_line_lookback(n(0), ma(1), n(2), ma(3));
+            let signal = line + ma_lookback(n(4), ma(5));
+            Some(vec![line, signal, macdext_lookback(n(0), ma(1), n(2), ma(3), n(4), ma(5))])
+        }
+        "MACDFIX" => Some(vec![ema_lookback(26), macd_lookback(12, 26, n(0)), macd_lookback(12, 26, n(0))]),
+        "VWMACD" => {
+            let line = n(1) - 1;
+            Some(vec![line, line + n(2) - 1, line + n(2) - 1])
+        }
+        "RVI" => Some(vec![rvi_lookback(n(0)) - 3, rvi_lookback(n(0))]),
+        "STOCH" => {
+            let slowk = n(0) - 1 + ma_lookback(n(1), ma(2));
+            Some(vec![slowk, stoch_lookback(n(0), n(1), ma(2), n(3), ma(4))])
+        }
+        "STOCHF" => Some(vec![n(0) - 1, stochf_lookback(n(0), n(1), ma(2))]),
+        "STOCHRSI" => Some(vec![rsi_lookback(n(0)) + n(1) - 1, stochrsi_lookback(n(0), n(1), n(2), ma(3))]),
+        "TRIX" => all(trix_lookback(n(0))),
+        "ULTOSC" => all(n(0).max(n(1)).max(n(2))),
+        "MASSINDEX" => all(mass_index_lookback(n(0), n(1))),
+        "ULCER" => all(ulcer_index_lookback(n(0))),
+        "ADOSC" => all(n(0).max(n(1)) - 1),
+        // Windows without mean reversion extend the warm-up
+        "HALFLIFE" => all(n(0)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::registry::{ParamKind, Registry};
+    use crate::momentum::{macd, rsi, stoch};
+    use crate::overlap::{ma, t3};
+
+    /// Functions whose warm-up the data can extend beyond the lookback
+    ///
+    /// The cycle-adaptive RSICYCLE and STOCHCYCLE are not listed: their first cycle is
+    /// short enough on these bars to start at the lookback.
+    const DATA_DEPENDENT: &[&str] = &["HALFLIFE", "MAVP", "ZIGZAG"];
+
+    fn leading_nans(values: &[f64]) -> usize {
+        values.iter().take_while(|v| v.is_nan()).count()
+    }
+
+    fn bars(len: usize) -> [Vec<f64>; 6] {
+        let close: Vec<f64> = (0..len)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 5.0 + (i as f64 * 0.11).cos() * 3.0 + i as f64 * 0.01)
+            .collect();
+        let open: Vec<f64> = (0..len).map(|i| close[i.saturating_sub(1)]).collect();
+        let high = (0..len).map(|i| open[i].max(close[i]) + 0.5 + (i as f64 * 0.7).sin().abs()).collect();
+        let low = (0..len).map(|i| open[i].min(close[i]) - 0.5 - (i as f64 * 0.3).cos().abs()).collect();
+        let volume = (0..len).map(|i| 1000.0 + (i as f64 * 0.5).sin() * 300.0).collect();
+        let periods = (0..len).map(|i| (2 + i % 20) as f64).collect();
+        [open, high, low, close, volume, periods]
+    }
+
+    #[test]
+    fn test_ma_lookbacks_match_output() {
+        let [_, _, _, close, _, _] = bars(200);
+        for ma_type in [MAType::SMA, MAType::EMA, MAType::WMA, MAType::DEMA, MAType::TEMA, MAType::TRIMA] {
+            for period in [1, 2, 5, 14] {
+                let values = ma(&close, period, ma_type).unwrap();
+                assert_eq!(leading_nans(&values), ma_lookback(period, ma_type), "{:?} {}", ma_type, period);
+            }
+        }
+        assert_eq!(leading_nans(&t3(&close, 5, 0.7).unwrap()), t3_lookback(5));
+    }
+
+    #[test]
+    fn test_multi_stage_lookbacks() {
+        let [_, high, low, close, _, _] = bars(200);
+        let (line, signal, _) = macd(&close, 12, 26, 9).unwrap();
+        assert_eq!(leading_nans(&line), ema_lookback(26));
+        assert_eq!(leading_nans(&signal), macd_lookback(12, 26, 9));
+
+        let (_, slowd) = stoch(&high, &low, &close, 5, 3, MAType::EMA, 4, MAType::DEMA).unwrap();
+        assert_eq!(leading_nans(&slowd), stoch_lookback(5, 3, MAType::EMA, 4, MAType::DEMA));
+        assert_eq!(leading_nans(&rsi(&close, 9).unwrap()), rsi_lookback(9));
+
+        // Outputs of one function can start at different bars
+        let mama = MAType::all().iter().position(|&t| t == MAType::MAMA).unwrap() as f64;
+        let bbands = Registry::get("BBANDS").unwrap();
+        assert_eq!(bbands.output_lookbacks(&[40.0, 2.0, 2.0, mama]).unwrap(), vec![39, 32, 39]);
+        let stochcycle = Registry::get("STOCHCYCLE").unwrap();
+        assert_eq!(stochcycle.output_lookbacks(&[18.0]).unwrap(), vec![32, 49]);
+    }
+
+    #[test]
+    fn test_registry_lookbacks_match_output() {
+        let [open, high, low, close, volume, periods] = bars(400);
+        let unit: Vec<f64> = close.iter().map(|c| (c - 100.0) / 20.0).collect();
+
+        for info in Registry::functions() {
+            let inputs: Vec<&[f64]> = info
+                .inputs
+                .iter()
+                .map(|&name| match name {
+                    "open" => open.as_slice(),
+                    "high" | "real0" => high.as_slice(),
+                    "low" | "real1" => low.as_slice(),
+                    "volume" => volume.as_slice(),
+                    "periods" => periods.as_slice(),
+                    _ if matches!(info.name, "ACOS" | "ASIN") => unit.as_slice(),
+                    _ => close.as_slice(),
+                })
+                .collect();
+
+            // Defaults, then each integer parameter at a few other values
+            let defaults: Vec<f64> = info.params.iter().map(|p| p.default).collect();
+            let mut cases = vec![defaults.clone()];
+            for (k, param) in info.params.iter().enumerate() {
+                if let ParamKind::Integer { .. } = param.kind {
+                    for value in [3.0, 7.0, 18.0] {
+                        let mut case = defaults.clone();
+                        case[k] = value;
+                        cases.push(case);
+                    }
+                }
+            }
+
+            // Every combination of MA types, with each integer parameter at its default or
+            // long enough for the window rather than the average to set the warm-up
+            if info.params.iter().any(|p| p.kind == ParamKind::MAType) {
+                let mut combined = vec![defaults.clone()];
+                for (k, param) in info.params.iter().enumerate() {
+                    let values: Vec<f64> = match param.kind {
+                        ParamKind::Integer { .. } => vec![param.default, 18.0],
+                        ParamKind::MAType => (0..MAType::all().len()).map(|t| t as f64).collect(),
+                        ParamKind::Real { .. } => continue,
+                    };
+                    combined = combined
+                        .iter()
+                        .flat_map(|case| {
+                            values.iter().map(move |&value| {
+                                let mut case = case.clone();
+                                case[k] = value;
+                                case
+                            })
+                        })
+                        .collect();
+                }
+                cases.extend(combined);
+            }
+
+            for params in cases {
+                // Invalid combinations, e.g. a fast period above the slow one, are skipped
+                let Ok(outputs) = info.call(&inputs, &params) else { continue };
+                let lookbacks = info.output_lookbacks(&params).unwrap();
+                assert_eq!(lookbacks.len(), outputs.len(), "{}", info.name);
+                for (k, (values, &lookback)) in outputs.iter().zip(&lookbacks).enumerate() {
+                    if matches!(info.name, "MAXINDEX" | "MININDEX" | "MINMAXINDEX") {
+                        assert!(values[..lookback].iter().all(|&v| v == 0.0), "{} {:?}", info.name, params);
+                    } else if DATA_DEPENDENT.contains(&info.name) {
+                        assert!(leading_nans(values) >= lookback, "{} {:?}", info.name, params);
+                    } else {
+                        assert_eq!(leading_nans(values), lookback, "{} output {} {:?}", info.name, k, params);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,25 +1,62 @@
 //! Common types, utilities, and error handling for TA-Rust
 //!
 //! This module provides the foundational components used throughout the library:
-//! - Type definitions for prices, volumes, and periods
+//! - Type definitions for prices, volumes, and timestamps
+//! - Validated parameter types: periods, smoothing factors and percent levels
+//! - The `Float` trait for `f32`/`f64` generic functions
 //! - Error handling types
 //! - Moving average type enumeration
 //! - Utility functions for validation and calculations
 //! - Constants used in pattern recognition
+//! - A runtime registry for calling indicators by name
+//! - Lookback periods, the length of each indicator's warm-up
+//! - Indicator outputs carrying where their values start, for aligning several of them
+//! - A policy for NaN values in inputs with missing bars
+//! - Compensated summation for long running sums
+//! - Resampling of timestamped bars to higher timeframes and aligning results back
+//! - Trading sessions: gaps between them and indicators restarting with each one
 
 pub mod types;
+pub mod float;
 pub mod errors;
 pub mod utils;
 pub mod constants;
+pub mod registry;
+pub mod lookback;
+pub mod output;
+pub mod nan_policy;
+pub mod summation;
+pub mod window;
+pub mod resample;
+pub mod timeframe;
+pub mod sessions;
+pub mod params;
+
+#[cfg(not(feature = "std"))]
+pub(crate) mod compat;
+
+#[cfg(feature = "serde")]
+pub(crate) mod serde_nan;
 
 #[cfg(test)]
 pub mod test_helpers;
 
 // Re-export commonly used items
-pub use types::{Price, Volume, Period, MAType, OHLC, OHLCV};
-pub use errors::{TAError, TAResult};
+pub use types::{Price, Volume, Timestamp, MAType, OHLC, OHLCV, OhlcvSeries};
+pub use params::{Alpha, IntoParam, Percent, Period};
+pub use float::Float;
+pub use errors::{ErrorKind, TAError, TAResult};
 pub use utils::*;
 pub use constants::*;
+pub use registry::Registry;
+pub use lookback::*;
+pub use output::TAOutput;
+pub use nan_policy::{with_nan_policy, NanPolicy, PolicyOutput};
+pub use summation::{neumaier_sum, NeumaierSum};
+pub use window::{RingBuffer, RunningMax, RunningMin, RunningSum, WindowHook};
+pub use resample::{resample, PartialBar};
+pub use timeframe::{align_to_base, higher_timeframe};
+pub use sessions::{per_session, session_gaps, session_high_low, session_starts, session_vwap, sessions, SessionBoundary};
 
 #[cfg(test)]
 pub use test_helpers::*;
\ No newline at end of file
@@ -0,0 +1,256 @@
+//! Handling of missing values in inputs
+//!
+//! Indicators assume gap-free inputs. Some reject NaN (`rsi`, `willr`), while in others a NaN
+//! inside a window yields NaN for that window and a NaN reaching a recursive state (EMA,
+//! Wilder smoothing) makes every later value NaN.
+//! Real feeds have missing bars, so [`with_nan_policy`] decides what the indicator sees
+//! instead:
+//!
+//! - [`NanPolicy::Error`] rejects inputs containing NaN,
+//! - [`NanPolicy::Skip`] drops bars where any input is NaN, as if they never happened,
+//! - [`NanPolicy::ForwardFill`] repeats the last valid value of each input.
+//!
+//! Outputs always have one value per input bar. Bars that were not passed to the indicator
+//! (skipped bars, or leading bars before every input has a value) are NaN.
+//!
+//! The major indicators take a policy directly: [`sma_with_policy`](crate::overlap::sma_with_policy),
+//! [`ema_with_policy`](crate::overlap::ema_with_policy),
+//! [`bbands_with_policy`](crate::overlap::bbands_with_policy),
+//! [`rsi_with_policy`](crate::momentum::rsi_with_policy),
+//! [`macd_with_policy`](crate::momentum::macd_with_policy) and
+//! [`atr_with_policy`](crate::volatility::atr_with_policy). Any other indicator goes through
+//! [`with_nan_policy`], or [`FunctionInfo::call_with_nan_policy`](crate::common::registry::FunctionInfo::call_with_nan_policy)
+//! by name.
+
+use crate::common::{TAError, TAResult};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// What to do with NaN values in indicator inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NanPolicy {
+    /// Return an error naming the first NaN
+    #[default]
+    Error,
+    /// Compute on the bars where every input has a value; the others are NaN in the output
+    Skip,
+    /// Replace each NaN with the last valid value of the same input
+    ForwardFill,
+}
+
+/// Outputs that can be spread back over the bars of the original inputs
+pub trait PolicyOutput: Sized {
+    /// Places output value `k` at bar `bars[k]` of a series of `len` bars, NaN elsewhere
+    fn scatter(self, bars: &[usize], len: usize) -> Self;
+}
+
+impl PolicyOutput for Vec<f64> {
+    fn scatter(self, bars: &[usize], len: usize) -> Self {
+        let mut result = vec![f64::NAN; len];
+        for (&bar, value) in bars.iter().zip(self) {
+            result[bar] = value;
+        }
+        result
+    }
+}
+
+impl PolicyOutput for Vec<Vec<f64>> {
+    fn scatter(self, bars: &[usize], len: usize) -> Self {
+        self.into_iter().map(|output| output.scatter(bars, len)).collect()
+    }
+}
+
+impl PolicyOutput for (Vec<f64>, Vec<f64>) {
+    fn scatter(self, bars: &[usize], len: usize) -> Self {
+        (self.0.scatter(bars, len), self.1.scatter(bars, len))
+    }
+}
+
+impl PolicyOutput for (Vec<f64>, Vec<f64>, Vec<f64>) {
+    fn scatter(self, bars: &[usize], len: usize) -> Self {
+        (self.0.scatter(bars, len), self.1.scatter(bars, len), self.2.scatter(bars, len))
+    }
+}
+
+/// Applies an indicator to inputs that may contain NaN
+///
+/// # Arguments
+/// * `inputs` - Input series of equal length, e.g. `[high, low, close]`
+/// * `policy` - How NaN values are handled
+/// * `indicator` - Computes the indicator from gap-free inputs, in the same order
+///
+/// # Returns
+/// * `Ok(O)` - The indicator output, with one value per input bar
+/// * `Err(TAError)` - Error if inputs have different lengths, contain NaN under
+///   [`NanPolicy::Error`], or the indicator fails
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{with_nan_policy, NanPolicy};
+/// use ta_rust::overlap::ema;
+///
+/// let mut close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.5).sin() * 3.0).collect();
+/// close[20] = f64::NAN;
+///
+/// // Without a policy the NaN reaches the EMA state and every later value is NaN
+/// assert!(ema(&close, 10).unwrap()[39].is_nan());
+///
+/// let skipped = with_nan_policy(&[&close], NanPolicy::Skip, |i| ema(i[0], 10)).unwrap();
+/// assert!(skipped[20].is_nan());
+/// assert!(!skipped[39].is_nan());
+///
+/// assert!(with_nan_policy(&[&close], NanPolicy::Error, |i| ema(i[0], 10)).is_err());
+/// ```
+pub fn with_nan_policy<O, F>(inputs: &[&[f64]], policy: NanPolicy, indicator: F) -> TAResult<O>
+where
+    O: PolicyOutput,
+    F: FnOnce(&[&[f64]]) -> TAResult<O>,
+{
+    let len = inputs.first().map_or(0, |input| input.len());
+    if inputs.iter().any(|input| input.len() != len) {
+        return Err(TAError::mismatched_inputs("All input series must have the same length"));
+    }
+
+    let complete = |bar: usize| inputs.iter().all(|input| !input[bar].is_nan());
+    let bars: Vec<usize> = match policy {
+        NanPolicy::Error => {
+            for (k, input) in inputs.iter().enumerate() {
+                if let Some(bar) = input.iter().position(|v| v.is_nan()) {
+                    return Err(TAError::invalid_input(format!("Input {} is NaN at index {}", k, bar)));
+                }
+            }
+            return indicator(inputs);
+        }
+        NanPolicy::Skip => (0..len).filter(|&bar| complete(bar)).collect(),
+        // Bars before every input has a value have nothing to fill from
+        NanPolicy::ForwardFill => (0..len).skip_while(|&bar| !complete(bar)).collect(),
+    };
+    if bars.len() == len && policy == NanPolicy::Skip {
+        return indicator(inputs);
+    }
+
+    let columns: Vec<Vec<f64>> = inputs
+        .iter()
+        .map(|input| {
+            let mut last = f64::NAN;
+            bars.iter()
+                .map(|&bar| {
+                    if !input[bar].is_nan() {
+                        last = input[bar];
+                    }
+                    last
+                })
+                .collect()
+        })
+        .collect();
+    let columns: Vec<&[f64]> = columns.iter().map(|column| column.as_slice()).collect();
+    Ok(indicator(&columns)?.scatter(&bars, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
+    use crate::momentum::{macd, rsi};
+    use crate::volatility::atr;
+
+    fn prices(len: usize) -> Vec<f64> {
+        (0..len).map(|i| 100.0 + (i as f64 * 0.4).sin() * 4.0 + i as f64 * 0.05).collect()
+    }
+
+    #[test]
+    fn test_error_policy() {
+        let mut close = prices(30);
+        let clean = with_nan_policy(&[&close], NanPolicy::Error, |i| rsi(i[0], 5)).unwrap();
+        assert_arrays_approx_equal(&clean, &rsi(&close, 5).unwrap(), DEFAULT_TOLERANCE);
+
+        close[12] = f64::NAN;
+        let err = with_nan_policy(&[&close], NanPolicy::Error, |i| rsi(i[0], 5)).unwrap_err();
+        assert!(err.to_string().contains("index 12"));
+    }
+
+    #[test]
+    fn test_skip_policy() {
+        let close = prices(40);
+        let mut gapped = close.clone();
+        gapped.insert(15, f64::NAN);
+        gapped.insert(25, f64::NAN);
+
+        let result = with_nan_policy(&[&gapped], NanPolicy::Skip, |i| rsi(i[0], 7)).unwrap();
+        let mut expected = rsi(&close, 7).unwrap();
+        expected.insert(15, f64::NAN);
+        expected.insert(25, f64::NAN);
+        assert_eq!(result.len(), gapped.len());
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_forward_fill_policy() {
+        let close = prices(40);
+        let mut high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let mut low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        high[0] = f64::NAN;
+        low[20] = f64::NAN;
+
+        let result = with_nan_policy(&[&high, &low, &close], NanPolicy::ForwardFill, |i| atr(i[0], i[1], i[2], 5))
+            .unwrap();
+        let mut filled = low.clone();
+        filled[20] = filled[19];
+        let mut expected = atr(&high[1..], &filled[1..], &close[1..], 5).unwrap();
+        expected.insert(0, f64::NAN);
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_multiple_outputs() {
+        let mut close = prices(60);
+        close[40] = f64::NAN;
+        let (line, signal, hist) =
+            with_nan_policy(&[&close], NanPolicy::Skip, |i| macd(i[0], 5, 10, 4)).unwrap();
+        for output in [&line, &signal, &hist] {
+            assert_eq!(output.len(), 60);
+            assert!(output[40].is_nan());
+            assert!(!output[59].is_nan());
+        }
+    }
+
+    /// An indicator called on the NaN-free inputs, as passed to [`with_nan_policy`]
+    type Indicator<'a> = &'a dyn Fn(&[&[f64]]) -> TAResult<Vec<f64>>;
+
+    #[test]
+    fn test_indicator_policy_variants() {
+        use crate::momentum::{macd_with_policy, rsi_with_policy};
+        use crate::overlap::{bbands, bbands_with_policy, ema, ema_with_policy, sma, sma_with_policy};
+        use crate::volatility::atr_with_policy;
+
+        let mut close = prices(60);
+        close[30] = f64::NAN;
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let skip = NanPolicy::Skip;
+        // Each variant matches the indicator under the generic wrapper
+        let check = |actual: Vec<f64>, indicator: Indicator| {
+            assert_arrays_approx_equal(&actual, &with_nan_policy(&[&close], skip, indicator).unwrap(), DEFAULT_TOLERANCE);
+        };
+
+        check(sma_with_policy(&close, 5, skip).unwrap(), &|i| sma(i[0], 5));
+        check(ema_with_policy(&close, 5, skip).unwrap(), &|i| ema(i[0], 5));
+        check(rsi_with_policy(&close, 5, skip).unwrap(), &|i| rsi(i[0], 5));
+        check(macd_with_policy(&close, 5, 10, 4, skip).unwrap().2, &|i| Ok(macd(i[0], 5, 10, 4)?.2));
+        check(bbands_with_policy(&close, 5, 2.0, skip).unwrap().lower, &|i| Ok(bbands(i[0], 5, 2.0)?.lower));
+        let atr_filled = atr_with_policy(&high, &low, &close, 5, NanPolicy::ForwardFill).unwrap();
+        assert!(atr_filled[30..].iter().all(|v| !v.is_nan()));
+
+        // Errors name the indicator, not the wrapper
+        let err = atr_with_policy(&high, &low, &close, 5, NanPolicy::Error).unwrap_err();
+        assert_eq!(err.function(), Some("ATR"));
+    }
+
+    #[test]
+    fn test_mismatched_inputs() {
+        let close = prices(10);
+        let result = with_nan_policy(&[&close, &close[1..]], NanPolicy::Skip, |i| Ok(i[0].to_vec()));
+        assert!(result.is_err());
+    }
+}
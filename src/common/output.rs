@@ -0,0 +1,116 @@
+//! Indicator outputs with their warm-up
+//!
+//! Indicators return one value per input bar with NaN padding in front, so joining several
+//! of them means counting NaNs to find where each one starts. [`TAOutput`] carries that
+//! position along with the values: `lookback` is the warm-up the parameters imply (see
+//! [`lookback`](crate::common::lookback)), `first_valid` is where the values actually start,
+//! later than the lookback when the input itself starts with NaNs.
+//!
+//! The `*_ex` variants of the indicators (`sma_ex`, `rsi_ex`, ...) and
+//! [`FunctionInfo::call_ex`](crate::common::registry::FunctionInfo::call_ex) return it.
+//!
+//! # Example
+//! ```
+//! use ta_rust::common::TAOutput;
+//! use ta_rust::momentum::rsi_ex;
+//! use ta_rust::overlap::sma_ex;
+//!
+//! let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+//! let fast = sma_ex(&close, 10).unwrap();
+//! let strength = rsi_ex(&close, 14).unwrap();
+//! assert_eq!((fast.first_valid, strength.first_valid), (9, 14));
+//!
+//! // Both indicators from the first bar where each has a value
+//! let (start, columns) = TAOutput::align(&[&fast, &strength]);
+//! assert_eq!(start, 14);
+//! assert_eq!(columns[0].len(), 46);
+//! assert!(columns.iter().all(|column| column.iter().all(|v| !v.is_nan())));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Output series of an indicator, with where its values start
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TAOutput {
+    /// One value per input bar, NaN before `first_valid`
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub values: Vec<f64>,
+    /// Index of the first value that is not NaN, `values.len()` if there is none
+    pub first_valid: usize,
+    /// Warm-up of the indicator for its parameters, the bars it needs before a first value
+    pub lookback: usize,
+}
+
+impl TAOutput {
+    /// Wraps indicator values, finding the first valid one
+    pub fn new(values: Vec<f64>, lookback: usize) -> Self {
+        let first_valid = values.iter().position(|v| !v.is_nan()).unwrap_or(values.len());
+        Self { values, first_valid, lookback }
+    }
+
+    /// Values from the first valid one on
+    pub fn valid(&self) -> &[f64] {
+        &self.values[self.first_valid..]
+    }
+
+    /// Number of bars, valid or not
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether there are no bars
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Values, dropping the metadata
+    pub fn into_values(self) -> Vec<f64> {
+        self.values
+    }
+
+    /// Aligns outputs computed on the same bars
+    ///
+    /// # Returns
+    /// The first bar where every output has started, and each output from that bar on.
+    /// Outputs of different lengths are cut to the shortest.
+    pub fn align<'a>(outputs: &[&'a TAOutput]) -> (usize, Vec<&'a [f64]>) {
+        let len = outputs.iter().map(|output| output.len()).min().unwrap_or(0);
+        let start = outputs.iter().map(|output| output.first_valid).max().unwrap_or(0).min(len);
+        (start, outputs.iter().map(|output| &output.values[start..len]).collect())
+    }
+}
+
+impl From<TAOutput> for Vec<f64> {
+    fn from(output: TAOutput) -> Vec<f64> {
+        output.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_first_valid_and_align() {
+        let warmup = TAOutput::new(vec![f64::NAN, f64::NAN, 1.0, 2.0, f64::NAN, 3.0], 2);
+        assert_eq!(warmup.first_valid, 2);
+        assert_eq!(warmup.valid().len(), 4);
+
+        // Leading NaN inputs push the first value past the lookback
+        let late = TAOutput::new(vec![f64::NAN, f64::NAN, f64::NAN, 4.0, 5.0], 1);
+        assert_eq!((late.first_valid, late.lookback), (3, 1));
+
+        let (start, columns) = TAOutput::align(&[&warmup, &late]);
+        assert_eq!(start, 3);
+        assert_eq!(columns[0].len(), 2);
+        assert_eq!(columns[1], &[4.0, 5.0]);
+
+        let none = TAOutput::new(vec![f64::NAN; 3], 5);
+        assert_eq!(none.first_valid, 3);
+        assert!(none.valid().is_empty());
+        assert_eq!(TAOutput::align(&[&none, &late]).0, 3);
+        assert_eq!(TAOutput::align(&[]), (0, vec![]));
+    }
+}
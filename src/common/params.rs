@@ -0,0 +1,276 @@
+//! Validated parameter types
+//!
+//! Periods, smoothing factors and oscillator levels are all plain numbers, which makes it
+//! easy to pass one where another was meant or to build an invalid one far from where it
+//! is used. The types below can only hold valid values, so mistakes surface where the
+//! parameter is constructed. Functions taking them also accept the plain number through
+//! [`IntoParam`], validating it on the call as before, so `rsi(&close, 14)` and
+//! `rsi(&close, Period::new(14)?)` both work.
+
+use core::fmt;
+use core::num::NonZeroUsize;
+
+use crate::common::{TAError, TAResult};
+
+/// Number of bars of an indicator window, at least 1
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::Period;
+/// use ta_rust::momentum::rsi;
+///
+/// let period = Period::new(14).unwrap();
+/// assert_eq!(period.get(), 14);
+/// assert!(Period::new(0).is_err());
+///
+/// let close: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+/// assert_eq!(rsi(&close, period).unwrap()[20], rsi(&close, 14).unwrap()[20]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "usize", into = "usize"))]
+pub struct Period(NonZeroUsize);
+
+impl Period {
+    /// Creates a period of `bars` bars
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if bars is 0
+    pub fn new(bars: usize) -> TAResult<Self> {
+        bars.into_param("period")
+    }
+
+    /// Number of bars
+    pub fn get(self) -> usize {
+        self.0.get()
+    }
+}
+
+impl TryFrom<usize> for Period {
+    type Error = TAError;
+
+    fn try_from(bars: usize) -> TAResult<Self> {
+        Period::new(bars)
+    }
+}
+
+impl From<Period> for usize {
+    fn from(period: Period) -> usize {
+        period.get()
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Smoothing factor of an exponential average, in (0, 1]
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::Alpha;
+///
+/// assert_eq!(Alpha::new(0.2).unwrap().get(), 0.2);
+/// assert_eq!(Alpha::ema(9).unwrap().get(), 0.2);
+/// assert_eq!(Alpha::wilder(14).unwrap().get(), 1.0 / 14.0);
+/// assert!(Alpha::new(0.0).is_err());
+/// assert!(Alpha::new(1.5).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f64", into = "f64"))]
+pub struct Alpha(f64);
+
+impl Alpha {
+    /// Creates a smoothing factor
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if alpha is not in (0, 1]
+    pub fn new(alpha: f64) -> TAResult<Self> {
+        alpha.into_param("alpha")
+    }
+
+    /// Smoothing factor of an EMA of `period` bars, `2 / (period + 1)`
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn ema(period: usize) -> TAResult<Self> {
+        let period = Period::new(period)?;
+        Ok(Alpha(2.0 / (period.get() as f64 + 1.0)))
+    }
+
+    /// Smoothing factor of Wilder's average of `period` bars, `1 / period`
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn wilder(period: usize) -> TAResult<Self> {
+        let period = Period::new(period)?;
+        Ok(Alpha(1.0 / period.get() as f64))
+    }
+
+    /// Value of the smoothing factor
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Alpha {
+    type Error = TAError;
+
+    fn try_from(alpha: f64) -> TAResult<Self> {
+        Alpha::new(alpha)
+    }
+}
+
+impl From<Alpha> for f64 {
+    fn from(alpha: Alpha) -> f64 {
+        alpha.get()
+    }
+}
+
+impl fmt::Display for Alpha {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Level on a 0 to 100 scale, such as an RSI overbought level
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::Percent;
+///
+/// let overbought = Percent::new(70.0).unwrap();
+/// assert_eq!(overbought.fraction(), 0.7);
+/// assert!(Percent::new(120.0).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f64", into = "f64"))]
+pub struct Percent(f64);
+
+impl Percent {
+    /// Creates a level
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if percent is not in [0, 100]
+    pub fn new(percent: f64) -> TAResult<Self> {
+        percent.into_param("percent")
+    }
+
+    /// Value of the level, from 0 to 100
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    /// Value of the level as a fraction, from 0 to 1
+    pub fn fraction(self) -> f64 {
+        self.0 / 100.0
+    }
+}
+
+impl TryFrom<f64> for Percent {
+    type Error = TAError;
+
+    fn try_from(percent: f64) -> TAResult<Self> {
+        Percent::new(percent)
+    }
+}
+
+impl From<Percent> for f64 {
+    fn from(percent: Percent) -> f64 {
+        percent.get()
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// Argument convertible into a validated parameter type `P`
+///
+/// Implemented for each parameter type itself and for the plain number it wraps, so
+/// functions taking `impl IntoParam<Period>` accept both `14` and `Period::new(14)?`.
+pub trait IntoParam<P>: Copy {
+    /// Converts the argument, naming it `name` in the error if it is invalid
+    fn into_param(self, name: &str) -> TAResult<P>;
+}
+
+impl IntoParam<Period> for Period {
+    fn into_param(self, _name: &str) -> TAResult<Period> {
+        Ok(self)
+    }
+}
+
+impl IntoParam<Period> for usize {
+    fn into_param(self, name: &str) -> TAResult<Period> {
+        NonZeroUsize::new(self)
+            .map(Period)
+            .ok_or_else(|| TAError::invalid_parameter(name, "period must be greater than 0"))
+    }
+}
+
+impl IntoParam<Alpha> for Alpha {
+    fn into_param(self, _name: &str) -> TAResult<Alpha> {
+        Ok(self)
+    }
+}
+
+impl IntoParam<Alpha> for f64 {
+    fn into_param(self, name: &str) -> TAResult<Alpha> {
+        if self > 0.0 && self <= 1.0 {
+            Ok(Alpha(self))
+        } else {
+            Err(TAError::invalid_parameter(name, "must be between 0 (exclusive) and 1 (inclusive)"))
+        }
+    }
+}
+
+impl IntoParam<Percent> for Percent {
+    fn into_param(self, _name: &str) -> TAResult<Percent> {
+        Ok(self)
+    }
+}
+
+impl IntoParam<Percent> for f64 {
+    fn into_param(self, name: &str) -> TAResult<Percent> {
+        if (0.0..=100.0).contains(&self) {
+            Ok(Percent(self))
+        } else {
+            Err(TAError::invalid_parameter(name, "must be between 0 and 100"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_validate_on_construction() {
+        assert_eq!(Period::try_from(20).unwrap().get(), 20);
+        assert_eq!(usize::from(Period::new(5).unwrap()), 5);
+        assert!(matches!(
+            Period::try_from(0),
+            Err(TAError::InvalidParameter { ref parameter, .. }) if parameter == "period"
+        ));
+        assert!(matches!(
+            IntoParam::<Period>::into_param(0usize, "slow_period"),
+            Err(TAError::InvalidParameter { ref parameter, .. }) if parameter == "slow_period"
+        ));
+
+        assert_eq!(Alpha::try_from(1.0).unwrap().get(), 1.0);
+        assert!(Alpha::try_from(f64::NAN).is_err());
+        assert!(Alpha::ema(0).is_err());
+        assert_eq!(f64::from(Alpha::wilder(4).unwrap()), 0.25);
+
+        assert_eq!(Percent::try_from(0.0).unwrap().fraction(), 0.0);
+        assert!(Percent::try_from(-1.0).is_err());
+        assert!(Percent::try_from(f64::NAN).is_err());
+        assert_eq!(Percent::new(30.0).unwrap().to_string(), "30%");
+    }
+}
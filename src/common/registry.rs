@@ -0,0 +1,1157 @@
+//! Runtime indicator registry
+//!
+//! The registry mirrors TA-Lib's abstract interface (`TA_GetFuncHandle` / `TA_CallFunc`):
+//! indicators can be looked up by name, inspected for their inputs, parameters and
+//! outputs, and called with parameters supplied at runtime. This is the entry point for
+//! config-file driven pipelines and scripting-engine bindings.
+//!
+//! Names and parameter names follow TA-Lib (`"RSI"`, `"timeperiod"`, `"nbdevup"`, ...).
+//! All parameters are passed as `f64`; integer and [`MAType`] parameters must hold whole
+//! numbers (MA types by their TA-Lib index). Index outputs such as `MININDEX` are returned
+//! as `f64`.
+//!
+//! # Example
+//! ```
+//! use ta_rust::common::registry::Registry;
+//!
+//! let close: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+//!
+//! let rsi = Registry::get("rsi").unwrap();
+//! assert_eq!(rsi.inputs, &["real"]);
+//! assert_eq!(rsi.params[0].name, "timeperiod");
+//!
+//! let outputs = rsi.call(&[&close], &[10.0]).unwrap();
+//! assert_eq!(outputs.len(), 1);
+//! assert_eq!(outputs[0].len(), close.len());
+//! ```
+
+use crate::common::{lookback, with_nan_policy, MAType, NanPolicy, TAError, TAOutput, TAResult};
+use crate::{math_operators, math_transform, momentum, overlap, price_transform, statistic, volatility, volume};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Largest period accepted by integer parameters, as in TA-Lib
+const MAX_PERIOD: f64 = 100_000.0;
+
+/// Type and valid range of a parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamKind {
+    /// Whole number within `min..=max`
+    Integer {
+        /// Smallest accepted value
+        min: f64,
+        /// Largest accepted value
+        max: f64,
+    },
+    /// Real number within `min..=max`
+    Real {
+        /// Smallest accepted value
+        min: f64,
+        /// Largest accepted value
+        max: f64,
+    },
+    /// Moving average type, given as its index in [`MAType::all`]
+    MAType,
+}
+
+/// Description of a function parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamInfo {
+    /// Parameter name (TA-Lib naming)
+    pub name: &'static str,
+    /// Parameter type and range
+    pub kind: ParamKind,
+    /// Value used when the parameter is not supplied
+    pub default: f64,
+}
+
+impl ParamInfo {
+    /// Whole-number parameter from `min` up to the largest period TA-Lib accepts
+    pub const fn int(name: &'static str, default: f64, min: f64) -> Self {
+        Self { name, kind: ParamKind::Integer { min, max: MAX_PERIOD }, default }
+    }
+
+    /// Real parameter within `min..=max`
+    pub const fn real(name: &'static str, default: f64, min: f64, max: f64) -> Self {
+        Self { name, kind: ParamKind::Real { min, max }, default }
+    }
+
+    /// Moving average type parameter, defaulting to SMA
+    pub const fn ma(name: &'static str) -> Self {
+        Self { name, kind: ParamKind::MAType, default: 0.0 }
+    }
+
+    /// Checks that `value` is valid for this parameter
+    pub fn validate(&self, value: f64) -> TAResult<()> {
+        let (min, max, integer) = match self.kind {
+            ParamKind::Integer { min, max } => (min, max, true),
+            ParamKind::Real { min, max } => (min, max, false),
+            ParamKind::MAType => (0.0, (MAType::all().len() - 1) as f64, true),
+        };
+
+        if !value.is_finite() || value < min || value > max {
+            return Err(TAError::invalid_parameter(
+                self.name.to_string(),
+                format!("must be between {} and {}, got {}", min, max, value),
+            ));
+        }
+        if integer && value != (value as i64) as f64 {
+            return Err(TAError::invalid_parameter(
+                self.name.to_string(),
+                format!("must be a whole number, got {}", value),
+            ));
+        }
+        Ok(())
+    }
+}
+
+type Handler = fn(&[&[f64]], &[f64]) -> TAResult<Vec<Vec<f64>>>;
+type Lookbacks = fn(&[f64]) -> Vec<usize>;
+
+/// A custom indicator, described like the built-in functions of the registry
+///
+/// Implementing the trait gives the indicator a [`FunctionInfo`] through
+/// [`FunctionInfo::of`], with the same parameter checking, named and NaN-policy calls and
+/// lookbacks as the built-ins. With the `std` feature, [`Registry::register`] makes it
+/// available by name, including to [`ComputePlan::add_function`](crate::batch::ComputePlan::add_function).
+/// For streaming, implement [`StreamingIndicator`](crate::iter::StreamingIndicator) on its
+/// state.
+///
+/// # Example
+/// ```
+/// use ta_rust::common::registry::{FunctionInfo, IndicatorDef, ParamInfo};
+/// use ta_rust::common::TAResult;
+/// use ta_rust::overlap::sma;
+///
+/// /// Distance of the price from its moving average, in percent
+/// struct Stretch;
+///
+/// impl IndicatorDef for Stretch {
+///     const NAME: &'static str = "STRETCH";
+///     const DESCRIPTION: &'static str = "Percent distance from the SMA";
+///     const INPUTS: &'static [&'static str] = &["real"];
+///     const PARAMS: &'static [ParamInfo] = &[ParamInfo::int("timeperiod", 20.0, 2.0)];
+///     const OUTPUTS: &'static [&'static str] = &["real"];
+///
+///     fn compute(inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+///         let average = sma(inputs[0], params[0] as usize)?;
+///         Ok(vec![inputs[0].iter().zip(&average).map(|(p, a)| 100.0 * (p / a - 1.0)).collect()])
+///     }
+///
+///     fn lookbacks(params: &[f64]) -> Vec<usize> {
+///         vec![params[0] as usize - 1]
+///     }
+/// }
+///
+/// let close: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+/// let stretch = FunctionInfo::of::<Stretch>();
+/// let outputs = stretch.call(&[&close], &[10.0]).unwrap();
+/// assert!(outputs[0][8].is_nan() && outputs[0][9] > 0.0);
+/// assert_eq!(stretch.lookback(&[]).unwrap(), 19);
+/// assert!(stretch.call(&[&close], &[1.0]).is_err());
+/// ```
+pub trait IndicatorDef {
+    /// Name the indicator is looked up by, upper case by convention
+    const NAME: &'static str;
+    /// Group of the indicator
+    const GROUP: &'static str = "custom";
+    /// Short human readable description
+    const DESCRIPTION: &'static str;
+    /// Names of the input series, in call order
+    const INPUTS: &'static [&'static str];
+    /// Parameters, in call order
+    const PARAMS: &'static [ParamInfo];
+    /// Names of the output series, in return order
+    const OUTPUTS: &'static [&'static str];
+
+    /// Computes one vector per output, as long as the inputs
+    ///
+    /// Called with as many inputs as [`IndicatorDef::INPUTS`] and one valid value per
+    /// parameter, missing ones set to their default.
+    fn compute(inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<Vec<f64>>>;
+
+    /// Number of leading bars without a value, for each output
+    fn lookbacks(params: &[f64]) -> Vec<usize>;
+}
+
+/// Description of a registered function, callable with runtime parameters
+#[derive(Clone, Copy)]
+pub struct FunctionInfo {
+    /// Function name (TA-Lib naming, upper case)
+    pub name: &'static str,
+    /// Module the function lives in, e.g. `"momentum"`
+    pub group: &'static str,
+    /// Short human readable description
+    pub description: &'static str,
+    /// Names of the input series, in call order
+    pub inputs: &'static [&'static str],
+    /// Parameters, in call order
+    pub params: &'static [ParamInfo],
+    /// Names of the output series, in return order
+    pub outputs: &'static [&'static str],
+    handler: Handler,
+    /// Lookbacks of a custom indicator; built-ins are looked up by name
+    lookbacks: Option<Lookbacks>,
+}
+
+impl core::fmt::Debug for FunctionInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FunctionInfo")
+            .field("name", &self.name)
+            .field("group", &self.group)
+            .field("inputs", &self.inputs)
+            .field("params", &self.params)
+            .field("outputs", &self.outputs)
+            .finish()
+    }
+}
+
+impl FunctionInfo {
+    /// Description of a custom indicator
+    pub const fn of<T: IndicatorDef>() -> Self {
+        Self {
+            name: T::NAME,
+            group: T::GROUP,
+            description: T::DESCRIPTION,
+            inputs: T::INPUTS,
+            params: T::PARAMS,
+            outputs: T::OUTPUTS,
+            handler: T::compute,
+            lookbacks: Some(T::lookbacks),
+        }
+    }
+
+    /// Calls the function with positional parameters
+    ///
+    /// `params` may be shorter than [`FunctionInfo::params`]; missing trailing parameters
+    /// take their default value. Errors record the function name, see
+    /// [`TAError::function`].
+    ///
+    /// # Returns
+    /// One vector per entry in [`FunctionInfo::outputs`]
+    pub fn call(&self, inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+        let resolved = self.resolve(params)?;
+        self.call_resolved(inputs, &resolved)
+    }
+
+    /// Calls the function with positional parameters, returning each output with where its
+    /// values start
+    ///
+    /// Each output has its own lookback, from [`FunctionInfo::output_lookbacks`]. On inputs
+    /// without NaN and longer than the lookback, `first_valid` equals it, except where the
+    /// data extends the warm-up (`HALFLIFE`, `MAVP`, `ZIGZAG`) and for the index functions,
+    /// which give 0 during the warm-up.
+    ///
+    /// # Example
+    /// ```
+    /// use ta_rust::common::registry::Registry;
+    ///
+    /// let close: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+    /// let outputs = Registry::get("MACD").unwrap().call_ex(&[&close], &[]).unwrap();
+    /// assert_eq!(outputs[0].lookback, 25);
+    /// assert_eq!(outputs[1].first_valid, 33);
+    /// ```
+    pub fn call_ex(&self, inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<TAOutput>> {
+        let lookbacks = self.output_lookbacks(params)?;
+        let outputs = self.call(inputs, params)?;
+        Ok(outputs.into_iter().zip(lookbacks).map(|(values, lookback)| TAOutput::new(values, lookback)).collect())
+    }
+
+    /// Calls the function with named parameters
+    ///
+    /// Parameters that are not named take their default value.
+    ///
+    /// # Example
+    /// ```
+    /// use ta_rust::common::registry::Registry;
+    ///
+    /// let close: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+    /// let macd = Registry::get("MACD").unwrap();
+    /// let outputs = macd.call_named(&[&close], &[("signalperiod", 5.0)]).unwrap();
+    /// assert_eq!(outputs.len(), 3);
+    /// ```
+    pub fn call_named(&self, inputs: &[&[f64]], params: &[(&str, f64)]) -> TAResult<Vec<Vec<f64>>> {
+        let mut resolved: Vec<f64> = self.params.iter().map(|info| info.default).collect();
+
+        for &(name, value) in params {
+            let index = self
+                .params
+                .iter()
+                .position(|info| info.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    TAError::invalid_parameter(name.to_string(), format!("not a parameter of {}", self.name))
+                        .in_function(self.name)
+                })?;
+            resolved[index] = value;
+        }
+        self.call_resolved(inputs, &resolved)
+    }
+
+    /// Calls the function with every parameter at its default value
+    pub fn call_default(&self, inputs: &[&[f64]]) -> TAResult<Vec<Vec<f64>>> {
+        self.call(inputs, &[])
+    }
+
+    /// Calls the function on inputs that may contain NaN, handled according to `policy`
+    ///
+    /// Parameters are given as for [`FunctionInfo::call`]. See
+    /// [`with_nan_policy`](crate::common::with_nan_policy) for how each policy treats gaps.
+    ///
+    /// # Example
+    /// ```
+    /// use ta_rust::common::{registry::Registry, NanPolicy};
+    ///
+    /// let mut close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+    /// close[30] = f64::NAN;
+    ///
+    /// let ema = Registry::get("EMA").unwrap();
+    /// let outputs = ema.call_with_nan_policy(&[&close], &[10.0], NanPolicy::ForwardFill).unwrap();
+    /// assert!(!outputs[0][59].is_nan());
+    /// ```
+    pub fn call_with_nan_policy(
+        &self,
+        inputs: &[&[f64]],
+        params: &[f64],
+        policy: NanPolicy,
+    ) -> TAResult<Vec<Vec<f64>>> {
+        let resolved = self.resolve(params)?;
+        with_nan_policy(inputs, policy, |inputs| self.call_resolved(inputs, &resolved))
+    }
+
+    /// Number of leading bars without a value, for the output that starts last
+    ///
+    /// Parameters are given as for [`FunctionInfo::call`]. Every output has a value from
+    /// this bar on; see [`lookback`](crate::common::lookback) for the definition.
+    ///
+    /// # Example
+    /// ```
+    /// use ta_rust::common::registry::Registry;
+    ///
+    /// let rsi = Registry::get("RSI").unwrap();
+    /// assert_eq!(rsi.lookback(&[]).unwrap(), 14);
+    /// assert_eq!(Registry::get("MACD").unwrap().lookback(&[12.0, 26.0, 5.0]).unwrap(), 29);
+    /// ```
+    pub fn lookback(&self, params: &[f64]) -> TAResult<usize> {
+        Ok(self.output_lookbacks(params)?.into_iter().max().unwrap_or(0))
+    }
+
+    /// Number of leading bars without a value, for each entry in [`FunctionInfo::outputs`]
+    ///
+    /// Parameters are given as for [`FunctionInfo::call`]. For `HALFLIFE`, `MAVP` and
+    /// `ZIGZAG` the data can extend the warm-up, and the lookback is its minimum.
+    pub fn output_lookbacks(&self, params: &[f64]) -> TAResult<Vec<usize>> {
+        let resolved = self.resolve(params)?;
+        for (info, &value) in self.params.iter().zip(&resolved) {
+            info.validate(value).map_err(|err| err.in_function(self.name))?;
+        }
+        if let Some(lookbacks) = self.lookbacks {
+            return Ok(lookbacks(&resolved));
+        }
+        lookback::registry_lookbacks(self.name, &resolved, self.outputs.len())
+            .ok_or_else(|| TAError::unsupported_operation(format!("{} has no lookback", self.name)).in_function(self.name))
+    }
+
+    /// Positional parameters with missing trailing ones set to their default
+    fn resolve(&self, params: &[f64]) -> TAResult<Vec<f64>> {
+        if params.len() > self.params.len() {
+            return Err(TAError::invalid_input(format!(
+                "{} takes {} parameters, got {}",
+                self.name,
+                self.params.len(),
+                params.len()
+            ))
+            .in_function(self.name));
+        }
+
+        Ok(self
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, info)| params.get(i).copied().unwrap_or(info.default))
+            .collect())
+    }
+
+    fn call_resolved(&self, inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+        self.check_and_call(inputs, params).map_err(|err| err.in_function(self.name))
+    }
+
+    fn check_and_call(&self, inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+        if inputs.len() != self.inputs.len() {
+            return Err(TAError::invalid_input(format!(
+                "{} takes {} input series ({}), got {}",
+                self.name,
+                self.inputs.len(),
+                self.inputs.join(", "),
+                inputs.len()
+            )));
+        }
+        for (info, &value) in self.params.iter().zip(params) {
+            info.validate(value)?;
+        }
+        (self.handler)(inputs, params)
+    }
+}
+
+/// Lookup of registered functions by name
+///
+/// See the [module documentation](self) for an example.
+pub struct Registry;
+
+impl Registry {
+    /// Returns the function registered under `name` (case-insensitive), built-in or custom
+    pub fn get(name: &str) -> Option<&'static FunctionInfo> {
+        FUNCTIONS
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+            .or_else(|| Self::custom().into_iter().find(|f| f.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// Returns every built-in function
+    pub fn functions() -> &'static [FunctionInfo] {
+        FUNCTIONS
+    }
+
+    /// Returns the custom indicators registered so far, in registration order
+    ///
+    /// Always empty without the `std` feature, which registration needs.
+    pub fn custom() -> Vec<&'static FunctionInfo> {
+        #[cfg(feature = "std")]
+        {
+            CUSTOM.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Returns the functions of one group, e.g. `"momentum"`, built-in then custom
+    pub fn group(group: &str) -> impl Iterator<Item = &'static FunctionInfo> + '_ {
+        FUNCTIONS.iter().chain(Self::custom()).filter(move |f| f.group == group)
+    }
+
+    /// Registers a custom indicator, making it available by name for the rest of the program
+    ///
+    /// # Errors
+    /// - `InvalidInput` if a function of the same name (case-insensitive) is already registered
+    ///
+    /// # Example
+    /// ```
+    /// use ta_rust::common::registry::{IndicatorDef, ParamInfo, Registry};
+    /// use ta_rust::common::TAResult;
+    ///
+    /// struct Spread;
+    ///
+    /// impl IndicatorDef for Spread {
+    ///     const NAME: &'static str = "SPREAD";
+    ///     const DESCRIPTION: &'static str = "High minus low";
+    ///     const INPUTS: &'static [&'static str] = &["high", "low"];
+    ///     const PARAMS: &'static [ParamInfo] = &[];
+    ///     const OUTPUTS: &'static [&'static str] = &["real"];
+    ///
+    ///     fn compute(inputs: &[&[f64]], _: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+    ///         Ok(vec![inputs[0].iter().zip(inputs[1]).map(|(h, l)| h - l).collect()])
+    ///     }
+    ///
+    ///     fn lookbacks(_: &[f64]) -> Vec<usize> {
+    ///         vec![0]
+    ///     }
+    /// }
+    ///
+    /// Registry::register::<Spread>().unwrap();
+    /// let spread = Registry::get("spread").unwrap();
+    /// assert_eq!(spread.call(&[&[3.0, 4.0], &[1.0, 1.5]], &[]).unwrap()[0], vec![2.0, 2.5]);
+    /// assert!(Registry::group("custom").any(|f| f.name == "SPREAD"));
+    /// assert!(Registry::register::<Spread>().is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn register<T: IndicatorDef>() -> TAResult<&'static FunctionInfo> {
+        let mut custom = CUSTOM.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let taken = FUNCTIONS.iter().chain(custom.iter().copied()).any(|f| f.name.eq_ignore_ascii_case(T::NAME));
+        if taken {
+            return Err(TAError::invalid_input(format!("{} is already registered", T::NAME)));
+        }
+        let info: &'static FunctionInfo = Box::leak(Box::new(FunctionInfo::of::<T>()));
+        custom.push(info);
+        Ok(info)
+    }
+}
+
+/// Custom indicators, leaked so they live as long as the built-ins
+#[cfg(feature = "std")]
+static CUSTOM: std::sync::RwLock<Vec<&'static FunctionInfo>> = std::sync::RwLock::new(Vec::new());
+
+// Parameter value as a period or count (validated as a whole number beforehand)
+fn n(value: f64) -> usize {
+    value as usize
+}
+
+// Parameter value as a moving average type (validated as an index beforehand)
+fn ma(value: f64) -> MAType {
+    MAType::all()[value as usize]
+}
+
+fn one(values: Vec<f64>) -> Vec<Vec<f64>> {
+    vec![values]
+}
+
+fn indices(values: Vec<usize>) -> Vec<f64> {
+    values.into_iter().map(|i| i as f64).collect()
+}
+
+macro_rules! function {
+    ($name:literal, $group:literal, $description:literal,
+     $inputs:expr, [$($param:expr),*], [$($output:literal),*],
+     |$i:pat_param, $p:pat_param| $body:expr) => {
+        FunctionInfo {
+            name: $name,
+            group: $group,
+            description: $description,
+            inputs: &$inputs,
+            params: &[$($param),*],
+            outputs: &[$($output),*],
+            handler: |$i, $p| $body,
+            lookbacks: None,
+        }
+    };
+}
+
+// Shorthand for single-input math functions without parameters
+macro_rules! unary {
+    ($name:literal, $group:literal, $description:literal, $f:path) => {
+        function!($name, $group, $description, ["real"], [], ["real"], |i, _| $f(i[0]).map(one))
+    };
+}
+
+const PERIOD_30: ParamInfo = ParamInfo::int("timeperiod", 30.0, 2.0);
+const PERIOD_14: ParamInfo = ParamInfo::int("timeperiod", 14.0, 2.0);
+const PERIOD_10: ParamInfo = ParamInfo::int("timeperiod", 10.0, 1.0);
+const PERIOD_5: ParamInfo = ParamInfo::int("timeperiod", 5.0, 1.0);
+const HLC: [&str; 3] = ["high", "low", "close"];
+
+static FUNCTIONS: &[FunctionInfo] = &[
+    // Overlap studies
+    function!("BANDPASS", "overlap", "Ehlers Band-Pass Filter", ["real"],
+        [ParamInfo::int("timeperiod", 20.0, 2.0), ParamInfo::real("bandwidth", 0.3, 0.0, 1.0)],
+        ["real"],
+        |i, p| overlap::bandpass(i[0], n(p[0]), p[1]).map(one)),
+    function!("BBANDS", "overlap", "Bollinger Bands", ["real"],
+        [
+            ParamInfo::int("timeperiod", 5.0, 2.0),
+            ParamInfo::real("nbdevup", 2.0, 0.0, 1e5),
+            ParamInfo::real("nbdevdn", 2.0, 0.0, 1e5),
+            ParamInfo::ma("matype")
+        ],
+        ["upperband", "middleband", "lowerband"],
+        |i, p| overlap::bbands_ma(i[0], n(p[0]), p[1], p[2], ma(p[3])).map(|b| vec![b.upper, b.middle, b.lower])),
+    function!("DECYCLER", "overlap", "Ehlers Decycler", ["real"], [ParamInfo::int("timeperiod", 60.0, 5.0)], ["real"],
+        |i, p| overlap::decycler(i[0], n(p[0])).map(one)),
+    function!("DEMA", "overlap", "Double Exponential Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::dema(i[0], n(p[0])).map(one)),
+    function!("DONCHIAN", "overlap", "Donchian Channels", ["high", "low"], [ParamInfo::int("timeperiod", 20.0, 1.0)],
+        ["upperband", "middleband", "lowerband"],
+        |i, p| overlap::donchian(i[0], i[1], n(p[0])).map(|d| vec![d.upper, d.middle, d.lower])),
+    function!("EMA", "overlap", "Exponential Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::ema(i[0], n(p[0])).map(one)),
+    function!("FRAMA", "overlap", "Fractal Adaptive Moving Average", HLC,
+        [ParamInfo::int("timeperiod", 16.0, 2.0)],
+        ["real"],
+        |i, p| overlap::frama(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("HIGHPASS", "overlap", "Ehlers High-Pass Filter", ["real"], [ParamInfo::int("timeperiod", 48.0, 2.0)],
+        ["real"],
+        |i, p| overlap::high_pass(i[0], n(p[0])).map(one)),
+    function!("ICHIMOKU", "overlap", "Ichimoku Cloud", HLC,
+        [
+            ParamInfo::int("tenkanperiod", 9.0, 1.0),
+            ParamInfo::int("kijunperiod", 26.0, 1.0),
+            ParamInfo::int("senkoubperiod", 52.0, 1.0)
+        ],
+        ["tenkan", "kijun", "senkoua", "senkoub", "chikou"],
+        |i, p| overlap::ichimoku(i[0], i[1], i[2], n(p[0]), n(p[1]), n(p[2])).map(|c| {
+            // Outputs must have the input length, so the leading spans are reported at the
+            // bar they are computed at rather than `displacement` bars ahead
+            let len = c.tenkan_sen.len();
+            let shift = c.displacement;
+            vec![
+                c.tenkan_sen,
+                c.kijun_sen,
+                c.senkou_span_a[shift..shift + len].to_vec(),
+                c.senkou_span_b[shift..shift + len].to_vec(),
+                c.chikou_span,
+            ]
+        })),
+    function!("KALMAN", "overlap", "Kalman Filter", ["real"],
+        [ParamInfo::real("processvar", 0.01, 0.0, 1e10), ParamInfo::real("measurementvar", 1.0, 0.0, 1e10)],
+        ["real"],
+        |i, p| overlap::kalman(i[0], p[0], p[1]).map(one)),
+    function!("KALMANTREND", "overlap", "Kalman Filter with Velocity", ["real"],
+        [ParamInfo::real("processvar", 0.01, 0.0, 1e10), ParamInfo::real("measurementvar", 1.0, 0.0, 1e10)],
+        ["level", "velocity"],
+        |i, p| overlap::kalman_trend(i[0], p[0], p[1]).map(|k| vec![k.level, k.velocity])),
+    function!("KAMA", "overlap", "Kaufman Adaptive Moving Average", ["real"],
+        [PERIOD_30, ParamInfo::int("fastperiod", 2.0, 1.0), ParamInfo::int("slowperiod", 30.0, 1.0)],
+        ["real"],
+        |i, p| overlap::kama(i[0], n(p[0]), n(p[1]), n(p[2])).map(one)),
+    function!("KELTNER", "overlap", "Keltner Channels", HLC,
+        [
+            ParamInfo::int("emaperiod", 20.0, 1.0),
+            ParamInfo::int("atrperiod", 10.0, 1.0),
+            ParamInfo::real("multiplier", 2.0, 0.0, 1e5)
+        ],
+        ["upperband", "middleband", "lowerband"],
+        |i, p| overlap::keltner(i[0], i[1], i[2], n(p[0]), n(p[1]), p[2]).map(|k| vec![k.upper, k.middle, k.lower])),
+    function!("MA", "overlap", "Moving Average", ["real"], [PERIOD_30, ParamInfo::ma("matype")], ["real"],
+        |i, p| overlap::ma(i[0], n(p[0]), ma(p[1])).map(one)),
+    function!("MAMA", "overlap", "MESA Adaptive Moving Average", ["real"],
+        [ParamInfo::real("fastlimit", 0.5, 0.01, 0.99), ParamInfo::real("slowlimit", 0.05, 0.01, 0.99)],
+        ["mama", "fama"],
+        |i, p| overlap::mama(i[0], p[0], p[1]).map(|r| vec![r.mama, r.fama])),
+    function!("MAVP", "overlap", "Moving Average with Variable Period", ["real", "periods"],
+        [ParamInfo::int("minperiod", 2.0, 2.0), ParamInfo::int("maxperiod", 30.0, 2.0), ParamInfo::ma("matype")],
+        ["real"],
+        |i, p| overlap::mavp_ma(i[0], i[1], n(p[0]), n(p[1]), ma(p[2])).map(one)),
+    function!("MCGINLEY", "overlap", "McGinley Dynamic", ["real"], [ParamInfo::int("timeperiod", 10.0, 1.0)], ["real"],
+        |i, p| overlap::mcginley_dynamic(i[0], n(p[0])).map(one)),
+    function!("MIDPOINT", "overlap", "MidPoint over period", ["real"], [PERIOD_14], ["real"],
+        |i, p| overlap::midpoint(i[0], n(p[0])).map(one)),
+    function!("MIDPRICE", "overlap", "Midpoint Price over period", ["high", "low"], [PERIOD_14], ["real"],
+        |i, p| overlap::midprice(i[0], i[1], n(p[0])).map(one)),
+    function!("ROOFING", "overlap", "Ehlers Roofing Filter", ["real"],
+        [ParamInfo::int("hpperiod", 48.0, 3.0), ParamInfo::int("ssperiod", 10.0, 2.0)],
+        ["real"],
+        |i, p| overlap::roofing_filter(i[0], n(p[0]), n(p[1])).map(one)),
+    // As TA-Lib: no value on the first bar
+    function!("SAR", "overlap", "Parabolic SAR", ["high", "low"],
+        [ParamInfo::real("acceleration", 0.02, 0.0, 1e5), ParamInfo::real("maximum", 0.2, 0.0, 1e5)],
+        ["real"],
+        |i, p| overlap::sar::sar_talib(i[0], i[1], p[0], p[1]).map(one)),
+    // As TA-Lib: negative while short, and no value on the first bar
+    function!("SAREXT", "overlap", "Parabolic SAR - Extended", ["high", "low"],
+        [
+            ParamInfo::real("startvalue", 0.0, -1e5, 1e5),
+            ParamInfo::real("offsetonreverse", 0.0, 0.0, 1e5),
+            ParamInfo::real("accelerationinitlong", 0.02, 0.0, 1e5),
+            ParamInfo::real("accelerationlong", 0.02, 0.0, 1e5),
+            ParamInfo::real("accelerationmaxlong", 0.2, 0.0, 1e5),
+            ParamInfo::real("accelerationinitshort", 0.02, 0.0, 1e5),
+            ParamInfo::real("accelerationshort", 0.02, 0.0, 1e5),
+            ParamInfo::real("accelerationmaxshort", 0.2, 0.0, 1e5)
+        ],
+        ["real"],
+        |i, p| overlap::sarext::sarext_talib(i[0], i[1], p[0], p[1], p[2], p[3], p[4], p[5], p[6], p[7]).map(one)),
+    function!("SMA", "overlap", "Simple Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::sma(i[0], n(p[0])).map(one)),
+    function!("SUPERSMOOTHER", "overlap", "Ehlers Super Smoother", ["real"], [ParamInfo::int("timeperiod", 10.0, 2.0)],
+        ["real"],
+        |i, p| overlap::super_smoother(i[0], n(p[0])).map(one)),
+    function!("T3", "overlap", "Triple Exponential Moving Average (T3)", ["real"],
+        [PERIOD_5, ParamInfo::real("vfactor", 0.7, 0.0, 1.0)],
+        ["real"],
+        |i, p| overlap::t3(i[0], n(p[0]), p[1]).map(one)),
+    function!("TEMA", "overlap", "Triple Exponential Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::tema(i[0], n(p[0])).map(one)),
+    function!("TRIMA", "overlap", "Triangular Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::trima(i[0], n(p[0])).map(one)),
+    function!("WMA", "overlap", "Weighted Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::wma(i[0], n(p[0])).map(one)),
+    function!("ZLEMA", "overlap", "Zero-Lag Exponential Moving Average", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::zlema(i[0], n(p[0])).map(one)),
+    function!("ZIGZAG", "overlap", "ZigZag", ["high", "low"], [ParamInfo::real("percent", 5.0, 0.0, 1e5)], ["real"],
+        |i, p| overlap::zigzag(i[0], i[1], p[0]).map(|z| vec![z.line])),
+    // Momentum indicators
+    function!("ADX", "momentum", "Average Directional Movement Index", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::adx(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("ADXR", "momentum", "Average Directional Movement Index Rating", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::adxr(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("APO", "momentum", "Absolute Price Oscillator", ["real"],
+        [ParamInfo::int("fastperiod", 12.0, 2.0), ParamInfo::int("slowperiod", 26.0, 2.0), ParamInfo::ma("matype")],
+        ["real"],
+        |i, p| momentum::apo(i[0], n(p[0]), n(p[1]), ma(p[2])).map(one)),
+    // TA-Lib's output order, Down before Up
+    function!("AROON", "momentum", "Aroon", ["high", "low"], [PERIOD_14], ["aroondown", "aroonup"],
+        |i, p| momentum::aroon(i[0], i[1], n(p[0])).map(|(up, down)| vec![down, up])),
+    function!("AROONOSC", "momentum", "Aroon Oscillator", ["high", "low"], [PERIOD_14], ["real"],
+        |i, p| momentum::aroonosc(i[0], i[1], n(p[0])).map(one)),
+    function!("BOP", "momentum", "Balance Of Power", ["open", "high", "low", "close"], [], ["real"],
+        |i, _| momentum::bop(i[0], i[1], i[2], i[3]).map(one)),
+    function!("CCI", "momentum", "Commodity Channel Index", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::cci(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("CMO", "momentum", "Chande Momentum Oscillator", ["real"], [PERIOD_14], ["real"],
+        |i, p| momentum::cmo(i[0], n(p[0])).map(one)),
+    function!("DX", "momentum", "Directional Movement Index", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::dx(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("ELDERRAY", "momentum", "Elder Ray Bull/Bear Power", HLC, [ParamInfo::int("timeperiod", 13.0, 1.0)],
+        ["bullpower", "bearpower"],
+        |i, p| momentum::elder_ray(i[0], i[1], i[2], n(p[0])).map(|r| vec![r.bull_power, r.bear_power])),
+    function!("IMI", "momentum", "Intraday Momentum Index", ["open", "close"], [PERIOD_14], ["real"],
+        |i, p| momentum::imi(i[0], i[1], n(p[0])).map(one)),
+    function!("MACD", "momentum", "Moving Average Convergence/Divergence", ["real"],
+        [
+            ParamInfo::int("fastperiod", 12.0, 2.0),
+            ParamInfo::int("slowperiod", 26.0, 2.0),
+            ParamInfo::int("signalperiod", 9.0, 1.0)
+        ],
+        ["macd", "macdsignal", "macdhist"],
+        |i, p| momentum::macd(i[0], n(p[0]), n(p[1]), n(p[2])).map(|(m, s, h)| vec![m, s, h])),
+    function!("MACDEXT", "momentum", "MACD with controllable MA type", ["real"],
+        [
+            ParamInfo::int("fastperiod", 12.0, 2.0),
+            ParamInfo::ma("fastmatype"),
+            ParamInfo::int("slowperiod", 26.0, 2.0),
+            ParamInfo::ma("slowmatype"),
+            ParamInfo::int("signalperiod", 9.0, 1.0),
+            ParamInfo::ma("signalmatype")
+        ],
+        ["macd", "macdsignal", "macdhist"],
+        |i, p| momentum::macdext(i[0], n(p[0]), ma(p[1]), n(p[2]), ma(p[3]), n(p[4]), ma(p[5]))
+            .map(|(m, s, h)| vec![m, s, h])),
+    function!("MACDFIX", "momentum", "Moving Average Convergence/Divergence Fix 12/26", ["real"],
+        [ParamInfo::int("signalperiod", 9.0, 1.0)],
+        ["macd", "macdsignal", "macdhist"],
+        |i, p| momentum::macdfix(i[0], n(p[0])).map(|(m, s, h)| vec![m, s, h])),
+    function!("MFI", "momentum", "Money Flow Index", ["high", "low", "close", "volume"], [PERIOD_14], ["real"],
+        |i, p| momentum::mfi(i[0], i[1], i[2], i[3], n(p[0])).map(one)),
+    function!("MINUS_DI", "momentum", "Minus Directional Indicator", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::minus_di(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("MINUS_DM", "momentum", "Minus Directional Movement", ["high", "low"],
+        [ParamInfo::int("timeperiod", 14.0, 1.0)],
+        ["real"],
+        |i, p| momentum::minus_dm(i[0], i[1], n(p[0])).map(one)),
+    function!("MOM", "momentum", "Momentum", ["real"], [PERIOD_10], ["real"],
+        |i, p| momentum::mom(i[0], n(p[0])).map(one)),
+    function!("PLUS_DI", "momentum", "Plus Directional Indicator", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::plus_di(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("PLUS_DM", "momentum", "Plus Directional Movement", ["high", "low"],
+        [ParamInfo::int("timeperiod", 14.0, 1.0)],
+        ["real"],
+        |i, p| momentum::plus_dm(i[0], i[1], n(p[0])).map(one)),
+    function!("PPO", "momentum", "Percentage Price Oscillator", ["real"],
+        [ParamInfo::int("fastperiod", 12.0, 2.0), ParamInfo::int("slowperiod", 26.0, 2.0), ParamInfo::ma("matype")],
+        ["real"],
+        |i, p| momentum::ppo(i[0], n(p[0]), n(p[1]), ma(p[2])).map(one)),
+    function!("ROC", "momentum", "Rate of change: ((price/prevPrice)-1)*100", ["real"], [PERIOD_10], ["real"],
+        |i, p| momentum::roc(i[0], n(p[0])).map(one)),
+    function!("ROCP", "momentum", "Rate of change Percentage: (price-prevPrice)/prevPrice", ["real"], [PERIOD_10], ["real"],
+        |i, p| momentum::rocp(i[0], n(p[0])).map(one)),
+    function!("ROCR", "momentum", "Rate of change ratio: (price/prevPrice)", ["real"], [PERIOD_10], ["real"],
+        |i, p| momentum::rocr(i[0], n(p[0])).map(one)),
+    function!("ROCR100", "momentum", "Rate of change ratio 100 scale: (price/prevPrice)*100", ["real"], [PERIOD_10], ["real"],
+        |i, p| momentum::rocr100(i[0], n(p[0])).map(one)),
+    function!("RSI", "momentum", "Relative Strength Index", ["real"], [PERIOD_14], ["real"],
+        |i, p| momentum::rsi(i[0], n(p[0])).map(one)),
+    function!("RSICYCLE", "momentum", "Relative Strength Index over half the dominant cycle", ["real"], [], ["real"],
+        |i, _| momentum::rsi_adaptive_cycle(i[0]).map(one)),
+    function!("RVI", "momentum", "Relative Vigor Index", ["open", "high", "low", "close"], [PERIOD_10], ["rvi", "rvisignal"],
+        |i, p| momentum::rvi(i[0], i[1], i[2], i[3], n(p[0])).map(|r| vec![r.rvi, r.signal])),
+    function!("STOCH", "momentum", "Stochastic", HLC,
+        [
+            ParamInfo::int("fastk_period", 5.0, 1.0),
+            ParamInfo::int("slowk_period", 3.0, 1.0),
+            ParamInfo::ma("slowk_matype"),
+            ParamInfo::int("slowd_period", 3.0, 1.0),
+            ParamInfo::ma("slowd_matype")
+        ],
+        ["slowk", "slowd"],
+        |i, p| momentum::stoch(i[0], i[1], i[2], n(p[0]), n(p[1]), ma(p[2]), n(p[3]), ma(p[4]))
+            .map(|(k, d)| vec![k, d])),
+    function!("STOCHCYCLE", "momentum", "Stochastic over the dominant cycle", HLC,
+        [ParamInfo::int("fastd_period", 3.0, 1.0)], ["fastk", "fastd"],
+        |i, p| momentum::stoch_adaptive_cycle(i[0], i[1], i[2], n(p[0])).map(|r| vec![r.k, r.d])),
+    function!("STOCHF", "momentum", "Stochastic Fast", HLC,
+        [ParamInfo::int("fastk_period", 5.0, 1.0), ParamInfo::int("fastd_period", 3.0, 1.0), ParamInfo::ma("fastd_matype")],
+        ["fastk", "fastd"],
+        |i, p| momentum::stochf(i[0], i[1], i[2], n(p[0]), n(p[1]), ma(p[2])).map(|(k, d)| vec![k, d])),
+    // As TA-Lib: its arithmetic, which decides %K where the RSI only moves by rounding
+    function!("STOCHRSI", "momentum", "Stochastic Relative Strength Index", ["real"],
+        [
+            PERIOD_14,
+            ParamInfo::int("fastk_period", 5.0, 1.0),
+            ParamInfo::int("fastd_period", 3.0, 1.0),
+            ParamInfo::ma("fastd_matype")
+        ],
+        ["fastk", "fastd"],
+        |i, p| momentum::stochrsi::stochrsi_talib(i[0], n(p[0]), n(p[1]), n(p[2]), ma(p[3])).map(|(k, d)| vec![k, d])),
+    function!("TRIX", "overlap", "1-day Rate-Of-Change (ROC) of a Triple Smooth EMA", ["real"], [PERIOD_30], ["real"],
+        |i, p| overlap::trix(i[0], n(p[0])).map(one)),
+    function!("ULTOSC", "momentum", "Ultimate Oscillator", HLC,
+        [
+            ParamInfo::int("timeperiod1", 7.0, 1.0),
+            ParamInfo::int("timeperiod2", 14.0, 1.0),
+            ParamInfo::int("timeperiod3", 28.0, 1.0)
+        ],
+        ["real"],
+        |i, p| momentum::ultosc(i[0], i[1], i[2], n(p[0]), n(p[1]), n(p[2])).map(one)),
+    function!("WILLR", "momentum", "Williams' %R", HLC, [PERIOD_14], ["real"],
+        |i, p| momentum::willr(i[0], i[1], i[2], n(p[0])).map(one)),
+    // Volatility indicators
+    // As TA-Lib: seeded from the second bar, the first with a true range
+    function!("ATR", "volatility", "Average True Range", HLC, [PERIOD_14], ["real"],
+        |i, p| volatility::atr::atr_talib(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("CHOP", "volatility", "Choppiness Index", HLC, [ParamInfo::int("timeperiod", 14.0, 2.0)], ["real"],
+        |i, p| volatility::choppiness(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("GKVOL", "volatility", "Garman-Klass Volatility", ["open", "high", "low", "close"],
+        [ParamInfo::int("timeperiod", 20.0, 1.0), ParamInfo::real("tradingdays", 252.0, 1.0, 1e5)],
+        ["real"],
+        |i, p| volatility::estimators::garman_klass(i[0], i[1], i[2], i[3], n(p[0]), p[1]).map(one)),
+    function!("HV", "volatility", "Historical Volatility (close-to-close)", ["real"],
+        [ParamInfo::int("timeperiod", 20.0, 2.0), ParamInfo::real("tradingdays", 252.0, 1.0, 1e5)],
+        ["real"],
+        |i, p| volatility::estimators::close_to_close(i[0], n(p[0]), p[1]).map(one)),
+    function!("MASSINDEX", "volatility", "Mass Index", ["high", "low"],
+        [ParamInfo::int("emaperiod", 9.0, 1.0), ParamInfo::int("sumperiod", 25.0, 1.0)],
+        ["real"],
+        |i, p| volatility::mass_index(i[0], i[1], n(p[0]), n(p[1])).map(one)),
+    // As TA-Lib: of the ATR seeded from the second bar
+    function!("NATR", "volatility", "Normalized Average True Range", HLC, [PERIOD_14], ["real"],
+        |i, p| volatility::natr::natr_talib(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("PKVOL", "volatility", "Parkinson Volatility", ["high", "low"],
+        [ParamInfo::int("timeperiod", 20.0, 1.0), ParamInfo::real("tradingdays", 252.0, 1.0, 1e5)],
+        ["real"],
+        |i, p| volatility::estimators::parkinson(i[0], i[1], n(p[0]), p[1]).map(one)),
+    function!("RSVOL", "volatility", "Rogers-Satchell Volatility", ["open", "high", "low", "close"],
+        [ParamInfo::int("timeperiod", 20.0, 1.0), ParamInfo::real("tradingdays", 252.0, 1.0, 1e5)],
+        ["real"],
+        |i, p| volatility::estimators::rogers_satchell(i[0], i[1], i[2], i[3], n(p[0]), p[1]).map(one)),
+    // As TA-Lib: no value on the first bar, which has no previous close
+    function!("TRANGE", "volatility", "True Range", HLC, [], ["real"],
+        |i, _| volatility::trange::trange_talib(i[0], i[1], i[2]).map(one)),
+    function!("ULCER", "volatility", "Ulcer Index", ["real"], [PERIOD_14], ["real"],
+        |i, p| volatility::ulcer_index(i[0], n(p[0])).map(one)),
+    function!("YZVOL", "volatility", "Yang-Zhang Volatility", ["open", "high", "low", "close"],
+        [ParamInfo::int("timeperiod", 20.0, 2.0), ParamInfo::real("tradingdays", 252.0, 1.0, 1e5)],
+        ["real"],
+        |i, p| volatility::estimators::yang_zhang(i[0], i[1], i[2], i[3], n(p[0]), p[1]).map(one)),
+    // Volume indicators
+    function!("AD", "volume", "Chaikin A/D Line", ["high", "low", "close", "volume"], [], ["real"],
+        |i, _| volume::ad(i[0], i[1], i[2], i[3]).map(one)),
+    function!("ADOSC", "volume", "Chaikin A/D Oscillator", ["high", "low", "close", "volume"],
+        [ParamInfo::int("fastperiod", 3.0, 2.0), ParamInfo::int("slowperiod", 10.0, 2.0)],
+        ["real"],
+        |i, p| volume::adosc(i[0], i[1], i[2], i[3], n(p[0]), n(p[1])).map(one)),
+    function!("DELTA", "volume", "Approximate Volume Delta", ["open", "high", "low", "close", "volume"], [],
+        ["delta", "cumdelta"],
+        |i, _| volume::delta_approx(i[0], i[1], i[2], i[3], i[4]).map(|r| vec![r.delta, r.cumulative_delta])),
+    function!("EOM", "volume", "Ease of Movement", ["high", "low", "volume"], [PERIOD_14], ["real"],
+        |i, p| volume::eom(i[0], i[1], i[2], n(p[0])).map(one)),
+    function!("FORCE", "volume", "Elder Force Index", ["close", "volume"], [ParamInfo::int("timeperiod", 13.0, 1.0)],
+        ["real"],
+        |i, p| volume::force_index(i[0], i[1], n(p[0])).map(one)),
+    function!("OBV", "volume", "On Balance Volume", ["close", "volume"], [], ["real"],
+        |i, _| volume::obv(i[0], i[1]).map(one)),
+    function!("PVT", "volume", "Volume Price Trend", ["close", "volume"], [], ["real"],
+        |i, _| volume::pvt(i[0], i[1]).map(one)),
+    function!("TWIGGSMF", "volume", "Twiggs Money Flow", ["high", "low", "close", "volume"],
+        [ParamInfo::int("timeperiod", 21.0, 1.0)],
+        ["real"],
+        |i, p| volume::twiggs_mf(i[0], i[1], i[2], i[3], n(p[0])).map(one)),
+    function!("VPROFILE", "volume", "Rolling Volume Profile", ["high", "low", "close", "volume"],
+        [ParamInfo::int("timeperiod", 20.0, 1.0), ParamInfo::int("nbins", 24.0, 1.0)],
+        ["poc", "vahigh", "valow"],
+        |i, p| volume::profile_rolling(i[0], i[1], i[2], i[3], n(p[0]), volume::ProfileBins::Count(n(p[1])))
+            .map(|r| vec![r.poc, r.value_area_high, r.value_area_low])),
+    function!("VWMACD", "volume", "Volume-Weighted MACD", ["close", "volume"],
+        [
+            ParamInfo::int("fastperiod", 12.0, 1.0),
+            ParamInfo::int("slowperiod", 26.0, 2.0),
+            ParamInfo::int("signalperiod", 9.0, 1.0)
+        ],
+        ["macd", "macdsignal", "macdhist"],
+        |i, p| volume::vw_macd(i[0], i[1], n(p[0]), n(p[1]), n(p[2])).map(|m| vec![m.macd, m.signal, m.histogram])),
+    // Statistic functions
+    function!("ADF", "statistic", "Rolling Dickey-Fuller Statistic", ["real"],
+        [ParamInfo::int("timeperiod", 30.0, 3.0)],
+        ["real"],
+        |i, p| statistic::adf_stat(i[0], n(p[0])).map(one)),
+    function!("AUTOCORR", "statistic", "Rolling Autocorrelation", ["real"],
+        [PERIOD_30, ParamInfo::int("lag", 1.0, 1.0)],
+        ["real"],
+        |i, p| statistic::autocorr(i[0], n(p[0]), n(p[1])).map(one)),
+    function!("AVGDEV", "statistic", "Average Deviation", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::avgdev(i[0], n(p[0])).map(one)),
+    // As TA-Lib: the beta of the returns of `real1` against those of `real0`
+    function!("BETA", "statistic", "Beta", ["real0", "real1"], [PERIOD_5], ["real"],
+        |i, p| statistic::beta::beta_talib(i[1], i[0], n(p[0])).map(one)),
+    function!("CAPM", "statistic", "Rolling CAPM Regression", ["real0", "real1"], [PERIOD_30],
+        ["alpha", "beta", "rsquared", "trackingerror", "informationratio"],
+        |i, p| statistic::capm(i[0], i[1], n(p[0])).map(|c| vec![c.alpha, c.beta, c.r_squared, c.tracking_error, c.information_ratio])),
+    function!("CORREL", "statistic", "Pearson's Correlation Coefficient (r)", ["real0", "real1"], [PERIOD_30], ["real"],
+        |i, p| statistic::correl::correl_talib(i[0], i[1], n(p[0])).map(one)),
+    function!("COVAR", "statistic", "Rolling Sample Covariance", ["real0", "real1"], [PERIOD_30], ["real"],
+        |i, p| statistic::covariance(i[0], i[1], n(p[0])).map(one)),
+    function!("HALFLIFE", "statistic", "Rolling Half-Life of Mean Reversion", ["real"],
+        [ParamInfo::int("timeperiod", 30.0, 3.0)],
+        ["real"],
+        |i, p| statistic::half_life(i[0], n(p[0])).map(one)),
+    function!("HEDGERATIO", "statistic", "Rolling Hedge Ratio", ["real0", "real1"], [PERIOD_30], ["real"],
+        |i, p| statistic::hedge_ratio(i[0], i[1], n(p[0])).map(one)),
+    function!("HURST", "statistic", "Rolling Hurst Exponent (R/S)", ["real"],
+        [ParamInfo::int("timeperiod", 100.0, 16.0)],
+        ["real"],
+        |i, p| statistic::hurst(i[0], n(p[0])).map(one)),
+    function!("LINEARREG", "statistic", "Linear Regression", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::linearreg(i[0], n(p[0])).map(one)),
+    function!("LINEARREG_ANGLE", "statistic", "Linear Regression Angle", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::linearreg_angle(i[0], n(p[0])).map(one)),
+    function!("LINEARREG_INTERCEPT", "statistic", "Linear Regression Intercept", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::linearreg_intercept(i[0], n(p[0])).map(one)),
+    function!("LINEARREG_SLOPE", "statistic", "Linear Regression Slope", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::linearreg_slope(i[0], n(p[0])).map(one)),
+    function!("LINREGCHANNEL", "statistic", "Linear Regression Channel", ["real"],
+        [PERIOD_14, ParamInfo::real("nbdev", 2.0, 0.0, 1e5)],
+        ["upperband", "middleband", "lowerband", "rsquared"],
+        |i, p| statistic::linreg_channel(i[0], n(p[0]), p[1]).map(|c| vec![c.upper, c.middle, c.lower, c.r_squared])),
+    function!("MAD", "statistic", "Rolling Median Absolute Deviation", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::rolling_mad(i[0], n(p[0])).map(one)),
+    function!("MEDIAN", "statistic", "Rolling Median", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::rolling_median(i[0], n(p[0])).map(one)),
+    function!("PERCENTRANK", "statistic", "Rolling Percent Rank", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::percent_rank(i[0], n(p[0])).map(one)),
+    function!("QUANTILE", "statistic", "Rolling Quantile", ["real"],
+        [PERIOD_14, ParamInfo::real("quantile", 0.5, 0.0, 1.0)],
+        ["real"],
+        |i, p| statistic::rolling_quantile(i[0], n(p[0]), p[1]).map(one)),
+    function!("STDDEV", "statistic", "Standard Deviation", ["real"],
+        [PERIOD_5, ParamInfo::real("nbdev", 1.0, -1e5, 1e5)],
+        ["real"],
+        |i, p| statistic::stddev(i[0], n(p[0]), p[1]).map(one)),
+    function!("TSF", "statistic", "Time Series Forecast", ["real"], [PERIOD_14], ["real"],
+        |i, p| statistic::tsf(i[0], n(p[0])).map(one)),
+    // TA-Lib takes `nbdev` for VAR but does not apply it
+    function!("VAR", "statistic", "Variance", ["real"],
+        [PERIOD_5, ParamInfo::real("nbdev", 1.0, -1e5, 1e5)],
+        ["real"],
+        |i, p| statistic::var(i[0], n(p[0])).map(one)),
+    // Math transforms
+    unary!("ACOS", "math_transform", "Vector Trigonometric ACos", math_transform::trigonometric::acos_talib),
+    unary!("ASIN", "math_transform", "Vector Trigonometric ASin", math_transform::trigonometric::asin_talib),
+    unary!("ATAN", "math_transform", "Vector Trigonometric ATan", math_transform::atan),
+    unary!("CEIL", "math_transform", "Vector Ceil", math_transform::ceil),
+    unary!("COS", "math_transform", "Vector Trigonometric Cos", math_transform::cos),
+    unary!("COSH", "math_transform", "Vector Trigonometric Cosh", math_transform::cosh),
+    unary!("EXP", "math_transform", "Vector Arithmetic Exp", math_transform::exp),
+    unary!("FLOOR", "math_transform", "Vector Floor", math_transform::floor),
+    unary!("LN", "math_transform", "Vector Log Natural", math_transform::ln),
+    unary!("LOG10", "math_transform", "Vector Log10", math_transform::log10),
+    unary!("SIN", "math_transform", "Vector Trigonometric Sin", math_transform::sin),
+    unary!("SINH", "math_transform", "Vector Trigonometric Sinh", math_transform::sinh),
+    unary!("SQRT", "math_transform", "Vector Square Root", math_transform::sqrt),
+    unary!("TAN", "math_transform", "Vector Trigonometric Tan", math_transform::tan),
+    unary!("TANH", "math_transform", "Vector Trigonometric Tanh", math_transform::tanh),
+    // Math operators
+    function!("ADD", "math_operators", "Vector Arithmetic Add", ["real0", "real1"], [], ["real"],
+        |i, _| math_operators::add(i[0], i[1]).map(one)),
+    function!("DIV", "math_operators", "Vector Arithmetic Div", ["real0", "real1"], [], ["real"],
+        |i, _| math_operators::div(i[0], i[1]).map(one)),
+    function!("MAX", "math_operators", "Highest value over a specified period", ["real"], [PERIOD_30], ["real"],
+        |i, p| math_operators::max(i[0], n(p[0])).map(one)),
+    function!("MAXINDEX", "math_operators", "Index of highest value over a specified period", ["real"], [PERIOD_30], ["integer"],
+        |i, p| math_operators::maxindex(i[0], n(p[0])).map(|v| vec![indices(v)])),
+    function!("MIN", "math_operators", "Lowest value over a specified period", ["real"], [PERIOD_30], ["real"],
+        |i, p| math_operators::min(i[0], n(p[0])).map(one)),
+    function!("MININDEX", "math_operators", "Index of lowest value over a specified period", ["real"], [PERIOD_30], ["integer"],
+        |i, p| math_operators::minindex(i[0], n(p[0])).map(|v| vec![indices(v)])),
+    function!("MINMAX", "math_operators", "Lowest and highest values over a specified period", ["real"], [PERIOD_30],
+        ["min", "max"],
+        |i, p| math_operators::minmax(i[0], n(p[0])).map(|(lo, hi)| vec![lo, hi])),
+    function!("MINMAXINDEX", "math_operators", "Indexes of lowest and highest values over a specified period", ["real"],
+        [PERIOD_30],
+        ["minidx", "maxidx"],
+        |i, p| math_operators::minmaxindex(i[0], n(p[0])).map(|(lo, hi)| vec![indices(lo), indices(hi)])),
+    function!("MULT", "math_operators", "Vector Arithmetic Mult", ["real0", "real1"], [], ["real"],
+        |i, _| math_operators::mult(i[0], i[1]).map(one)),
+    function!("SUB", "math_operators", "Vector Arithmetic Subtraction", ["real0", "real1"], [], ["real"],
+        |i, _| math_operators::sub(i[0], i[1]).map(one)),
+    function!("SUM", "math_operators", "Summation", ["real"], [PERIOD_30], ["real"],
+        |i, p| math_operators::sum(i[0], n(p[0])).map(one)),
+    // Price transforms
+    function!("AVGPRICE", "price_transform", "Average Price", ["open", "high", "low", "close"], [], ["real"],
+        |i, _| price_transform::avgprice(i[0], i[1], i[2], i[3]).map(one)),
+    function!("MEDPRICE", "price_transform", "Median Price", ["high", "low"], [], ["real"],
+        |i, _| price_transform::medprice(i[0], i[1]).map(one)),
+    function!("TYPPRICE", "price_transform", "Typical Price", HLC, [], ["real"],
+        |i, _| price_transform::typprice(i[0], i[1], i[2]).map(one)),
+    function!("WCLPRICE", "price_transform", "Weighted Close Price", HLC, [], ["real"],
+        |i, _| price_transform::wclprice(i[0], i[1], i[2]).map(one)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_arrays_approx_equal, ErrorKind, DEFAULT_TOLERANCE};
+
+    fn sample(name: &str, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| {
+                let x = i as f64;
+                let base = 100.0 + (x * 0.3).sin() * 5.0 + x * 0.1;
+                match name {
+                    "open" => base - 0.5,
+                    "high" => base + 1.0 + (x * 0.7).cos().abs(),
+                    "low" => base - 1.0 - (x * 0.5).sin().abs(),
+                    "volume" => 1000.0 + (x * 0.2).cos() * 300.0,
+                    "periods" => 10.0,
+                    // Kept within (0, 1) so that ACOS/ASIN/LN/SQRT are all defined
+                    "real" | "real0" | "real1" => 0.5 + (x * 0.3).sin() * 0.4 + (x * 0.05).cos() * 0.05,
+                    _ => base,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_function_runs_with_defaults() {
+        for f in Registry::functions() {
+            let data: Vec<Vec<f64>> = f.inputs.iter().map(|name| sample(name, 200)).collect();
+            let inputs: Vec<&[f64]> = data.iter().map(|d| d.as_slice()).collect();
+
+            let outputs = f.call_default(&inputs).unwrap_or_else(|e| panic!("{} failed: {}", f.name, e));
+            assert_eq!(outputs.len(), f.outputs.len(), "{}", f.name);
+            for output in &outputs {
+                assert_eq!(output.len(), 200, "{}", f.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_call_ex_lookback_is_first_valid() {
+        for f in Registry::functions() {
+            // real1 a bar behind real0, so that a paired regression does not fit exactly
+            let data: Vec<Vec<f64>> = f
+                .inputs
+                .iter()
+                .map(|&name| if name == "real1" { sample(name, 201)[1..].to_vec() } else { sample(name, 200) })
+                .collect();
+            let inputs: Vec<&[f64]> = data.iter().map(|d| d.as_slice()).collect();
+
+            for (k, output) in f.call_ex(&inputs, &[]).unwrap().iter().enumerate() {
+                match f.name {
+                    // Index functions give 0 during the warm-up
+                    "MAXINDEX" | "MININDEX" | "MINMAXINDEX" => {
+                        assert!(output.values[..output.lookback].iter().all(|&v| v == 0.0), "{}", f.name)
+                    }
+                    // The data can extend the warm-up beyond the lookback
+                    "HALFLIFE" | "MAVP" | "ZIGZAG" => assert!(output.first_valid >= output.lookback, "{}", f.name),
+                    _ => assert_eq!(output.first_valid, output.lookback, "{} output {}", f.name, k),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_and_call_matches_direct() {
+        let close = sample("close", 100);
+        let rsi = Registry::get("Rsi").unwrap();
+        assert_eq!(rsi.name, "RSI");
+        assert_eq!(rsi.group, "momentum");
+
+        let outputs = rsi.call(&[&close], &[10.0]).unwrap();
+        let expected = momentum::rsi(&close, 10).unwrap();
+        assert_arrays_approx_equal(&outputs[0], &expected, DEFAULT_TOLERANCE);
+
+        let defaults = rsi.call_default(&[&close]).unwrap();
+        let expected = momentum::rsi(&close, 14).unwrap();
+        assert_arrays_approx_equal(&defaults[0], &expected, DEFAULT_TOLERANCE);
+
+        assert!(Registry::get("NOT_A_FUNCTION").is_none());
+    }
+
+    #[test]
+    fn test_named_params_and_ma_type() {
+        let close = sample("close", 100);
+        let f = Registry::get("MA").unwrap();
+        let outputs = f.call_named(&[&close], &[("matype", 1.0), ("timeperiod", 20.0)]).unwrap();
+        let expected = overlap::ema(&close, 20).unwrap();
+        assert_arrays_approx_equal(&outputs[0], &expected, DEFAULT_TOLERANCE);
+
+        assert!(f.call_named(&[&close], &[("bogus", 1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_calls() {
+        let close = sample("close", 100);
+        let rsi = Registry::get("RSI").unwrap();
+
+        // Wrong number of inputs or parameters
+        assert!(rsi.call(&[&close, &close], &[]).is_err());
+        assert!(rsi.call(&[&close], &[14.0, 1.0]).is_err());
+        // Out of range, fractional and non-finite parameters
+        assert!(rsi.call(&[&close], &[1.0]).is_err());
+        assert!(rsi.call(&[&close], &[14.5]).is_err());
+        assert!(rsi.call(&[&close], &[f64::NAN]).is_err());
+        // MA type index out of range
+        assert!(Registry::get("MA").unwrap().call(&[&close], &[10.0, 9.0]).is_err());
+
+        // Errors name the function that failed
+        let err = Registry::get("ATR").unwrap().call(&[&close, &close, &close[1..]], &[]).unwrap_err();
+        assert_eq!(err.function(), Some("ATR"));
+        assert_eq!(rsi.call(&[&close[..5]], &[]).unwrap_err().kind(), ErrorKind::InsufficientData);
+        assert_eq!(rsi.call(&[&close], &[14.0, 1.0]).unwrap_err().function(), Some("RSI"));
+    }
+
+    #[test]
+    fn test_group_lookup() {
+        assert!(Registry::group("price_transform").all(|f| f.group == "price_transform"));
+        assert_eq!(Registry::group("price_transform").count(), 4);
+
+        let mut names: Vec<&str> = Registry::functions().iter().map(|f| f.name).collect();
+        let total = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), total, "duplicate function names");
+    }
+
+    struct Range;
+
+    impl IndicatorDef for Range {
+        const NAME: &'static str = "TEST_RANGE";
+        const GROUP: &'static str = "test";
+        const DESCRIPTION: &'static str = "Rolling high-low range";
+        const INPUTS: &'static [&'static str] = &["high", "low"];
+        const PARAMS: &'static [ParamInfo] = &[ParamInfo::int("timeperiod", 5.0, 1.0)];
+        const OUTPUTS: &'static [&'static str] = &["real"];
+
+        fn compute(inputs: &[&[f64]], params: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+            let period = params[0] as usize;
+            let highest = crate::math_operators::max(inputs[0], period)?;
+            let lowest = crate::math_operators::min(inputs[1], period)?;
+            Ok(vec![highest.iter().zip(&lowest).map(|(h, l)| h - l).collect()])
+        }
+
+        fn lookbacks(params: &[f64]) -> Vec<usize> {
+            vec![params[0] as usize - 1]
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_register_custom() {
+        let high = sample("high", 30);
+        let low = sample("low", 30);
+        let info = Registry::register::<Range>().unwrap();
+        assert!(core::ptr::eq(Registry::get("test_range").unwrap(), info));
+        assert_eq!(Registry::group("test").count(), 1);
+        assert!(Registry::custom().iter().any(|f| f.name == "TEST_RANGE"));
+
+        let outputs = info.call(&[&high, &low], &[]).unwrap();
+        assert!(outputs[0][3].is_nan() && outputs[0][4] > 0.0);
+        assert_eq!(info.lookback(&[10.0]).unwrap(), 9);
+        assert_eq!(info.call(&[&high, &low], &[0.0]).unwrap_err().function(), Some("TEST_RANGE"));
+
+        assert!(Registry::register::<Range>().is_err());
+        struct Shadow;
+        impl IndicatorDef for Shadow {
+            const NAME: &'static str = "sma";
+            const DESCRIPTION: &'static str = "";
+            const INPUTS: &'static [&'static str] = &["real"];
+            const PARAMS: &'static [ParamInfo] = &[];
+            const OUTPUTS: &'static [&'static str] = &["real"];
+            fn compute(inputs: &[&[f64]], _: &[f64]) -> TAResult<Vec<Vec<f64>>> {
+                Ok(vec![inputs[0].to_vec()])
+            }
+            fn lookbacks(_: &[f64]) -> Vec<usize> {
+                vec![0]
+            }
+        }
+        assert!(Registry::register::<Shadow>().is_err());
+    }
+}
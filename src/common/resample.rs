@@ -0,0 +1,221 @@
+//! Resampling of bar data to a higher timeframe
+//!
+//! Bars are grouped into fixed-length buckets aligned to multiples of the interval from
+//! timestamp 0, so with Unix timestamps in seconds an interval of 3,600 produces hourly bars
+//! starting on the hour and 86,400 daily bars starting at midnight UTC. Buckets without any
+//! source bar (weekends, trading halts) are skipped rather than filled.
+
+use core::ops::Range;
+
+use crate::common::{OhlcvSeries, TAError, TAResult, Timestamp};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Handling of a final bucket that the source data does not fully cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartialBar {
+    /// Keep the incomplete bar, e.g. to show the current day while it is still trading
+    #[default]
+    Keep,
+    /// Drop the incomplete bar, e.g. so that indicators only see final values
+    Drop,
+}
+
+/// Aggregates bars into bars of a higher timeframe
+///
+/// Each output bar opens at the open of its first source bar, closes at the close of its
+/// last one, and takes the highest high, the lowest low and the summed volume.
+///
+/// The last bucket is complete when its final source bar ends at or after the bucket end.
+/// Source bars are assumed to last the smallest gap between consecutive timestamps, so a
+/// series of a single bar always ends with an incomplete bucket.
+///
+/// # Arguments
+/// * `series` - Source bars, labelled by their start time
+/// * `interval` - Length of an output bar, in the unit of the timestamps
+/// * `partial` - Whether to keep or drop an incomplete final bar
+///
+/// # Returns
+/// * `Ok(OhlcvSeries)` - Aggregated bars labelled by the start of their bucket
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{OhlcvSeries, resample, PartialBar};
+///
+/// // Seven 1-minute bars into 5-minute bars; the second one is still forming
+/// let minutes = OhlcvSeries::new(
+///     (0..7).map(|i| i * 60).collect(),
+///     vec![10.0, 11.0, 12.0, 11.0, 10.0, 9.0, 9.5],
+///     vec![11.0, 12.5, 12.0, 11.5, 10.5, 9.5, 10.0],
+///     vec![9.5, 10.5, 11.0, 10.0, 9.5, 8.5, 9.0],
+///     vec![11.0, 12.0, 11.0, 10.0, 9.0, 9.5, 9.8],
+///     vec![100.0; 7],
+/// ).unwrap();
+///
+/// let bars = resample(&minutes, 300, PartialBar::Keep).unwrap();
+/// assert_eq!(bars.timestamp, vec![0, 300]);
+/// assert_eq!((bars.open[0], bars.high[0], bars.low[0], bars.close[0]), (10.0, 12.5, 9.5, 9.0));
+/// assert_eq!(bars.volume[0], 500.0);
+///
+/// let bars = resample(&minutes, 300, PartialBar::Drop).unwrap();
+/// assert_eq!(bars.len(), 1);
+/// ```
+pub fn resample(series: &OhlcvSeries, interval: Timestamp, partial: PartialBar) -> TAResult<OhlcvSeries> {
+    with_function("RESAMPLE", || {
+        validate_interval(interval)?;
+        series.validate()?;
+        if series.is_empty() {
+            return Err(TAError::invalid_input("Input series cannot be empty"));
+        }
+
+        let mut buckets = buckets(&series.timestamp, interval);
+        if partial == PartialBar::Drop && !last_bucket_complete(&series.timestamp, interval) {
+            buckets.pop();
+        }
+
+        let mut result = OhlcvSeries {
+            timestamp: Vec::with_capacity(buckets.len()),
+            open: Vec::with_capacity(buckets.len()),
+            high: Vec::with_capacity(buckets.len()),
+            low: Vec::with_capacity(buckets.len()),
+            close: Vec::with_capacity(buckets.len()),
+            volume: Vec::with_capacity(buckets.len()),
+        };
+
+        for bucket in buckets {
+            result.timestamp.push(bucket_start(series.timestamp[bucket.start], interval));
+            result.open.push(series.open[bucket.start]);
+            result.high.push(series.high[bucket.clone()].iter().copied().fold(f64::NAN, f64::max));
+            result.low.push(series.low[bucket.clone()].iter().copied().fold(f64::NAN, f64::min));
+            result.close.push(series.close[bucket.end - 1]);
+            result.volume.push(series.volume[bucket].iter().sum());
+        }
+        Ok(result)
+    })
+}
+
+pub(crate) fn validate_interval(interval: Timestamp) -> TAResult<()> {
+    if interval <= 0 {
+        return Err(TAError::invalid_parameter("interval", "must be positive"));
+    }
+    Ok(())
+}
+
+/// Start of the bucket containing `timestamp`
+///
+/// Arithmetic saturates so that timestamps near the ends of the range cannot overflow.
+pub(crate) fn bucket_start(timestamp: Timestamp, interval: Timestamp) -> Timestamp {
+    timestamp.saturating_sub(timestamp.rem_euclid(interval))
+}
+
+/// Index ranges of the source bars falling into each non-empty bucket
+pub(crate) fn buckets(timestamps: &[Timestamp], interval: Timestamp) -> Vec<Range<usize>> {
+    let mut buckets = Vec::new();
+    let mut start = 0;
+    for i in 1..=timestamps.len() {
+        if i == timestamps.len() || bucket_start(timestamps[i], interval) != bucket_start(timestamps[start], interval) {
+            buckets.push(start..i);
+            start = i;
+        }
+    }
+    buckets
+}
+
+/// Whether the source bars cover the last bucket to its end
+pub(crate) fn last_bucket_complete(timestamps: &[Timestamp], interval: Timestamp) -> bool {
+    let step = timestamps.windows(2).map(|w| w[1].saturating_sub(w[0])).min();
+    match (step, timestamps.last()) {
+        (Some(step), Some(&last)) => {
+            last.saturating_add(step) >= bucket_start(last, interval).saturating_add(interval)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(count: i64) -> OhlcvSeries {
+        let close: Vec<f64> = (0..count).map(|i| 100.0 + (i as f64 * 0.9).sin() * 5.0).collect();
+        OhlcvSeries::new(
+            (0..count).map(|i| i * 60).collect(),
+            close.iter().map(|c| c - 0.5).collect(),
+            close.iter().map(|c| c + 1.0).collect(),
+            close.iter().map(|c| c - 1.0).collect(),
+            close,
+            (0..count).map(|i| i as f64).collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resample_ohlcv() {
+        let source = minutes(120);
+        let hours = resample(&source, 3_600, PartialBar::Drop).unwrap();
+        assert_eq!(hours.timestamp, vec![0, 3_600]);
+        for (h, range) in [(0, 0..60), (1, 60..120)] {
+            assert_eq!(hours.open[h], source.open[range.start]);
+            assert_eq!(hours.close[h], source.close[range.end - 1]);
+            assert_eq!(hours.high[h], source.high[range.clone()].iter().copied().fold(f64::MIN, f64::max));
+            assert_eq!(hours.low[h], source.low[range.clone()].iter().copied().fold(f64::MAX, f64::min));
+            assert_eq!(hours.volume[h], source.volume[range].iter().sum::<f64>());
+        }
+    }
+
+    #[test]
+    fn test_resample_partial_final_bar() {
+        let source = minutes(13);
+        assert_eq!(resample(&source, 300, PartialBar::Keep).unwrap().timestamp, vec![0, 300, 600]);
+        assert_eq!(resample(&source, 300, PartialBar::Drop).unwrap().timestamp, vec![0, 300]);
+
+        // Fifteen minutes fill the last bucket exactly
+        let source = minutes(15);
+        assert_eq!(resample(&source, 300, PartialBar::Drop).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_resample_gaps_and_alignment() {
+        // Starts mid-bucket and skips a whole bucket
+        let series = OhlcvSeries::new(
+            vec![-60, 120, 240, 900],
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![1.0, 1.0, 1.0, 1.0],
+        )
+        .unwrap();
+        let bars = resample(&series, 300, PartialBar::Keep).unwrap();
+        assert_eq!(bars.timestamp, vec![-300, 0, 900]);
+        assert_eq!(bars.close, vec![1.0, 3.0, 4.0]);
+        assert_eq!(bars.volume, vec![1.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_resample_extreme_timestamps() {
+        let series = OhlcvSeries::new(
+            vec![i64::MIN, 0, i64::MAX],
+            vec![1.0; 3],
+            vec![1.0; 3],
+            vec![1.0; 3],
+            vec![1.0; 3],
+            vec![1.0; 3],
+        )
+        .unwrap();
+        assert_eq!(resample(&series, 7, PartialBar::Drop).unwrap().len(), 3);
+        assert_eq!(resample(&series, i64::MAX, PartialBar::Keep).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_resample_invalid_input() {
+        let source = minutes(10);
+        assert!(resample(&source, 0, PartialBar::Keep).is_err());
+        assert!(resample(&minutes(0), 300, PartialBar::Keep).is_err());
+        let unordered = OhlcvSeries { timestamp: vec![0; 10], ..source };
+        assert!(resample(&unordered, 300, PartialBar::Keep).is_err());
+    }
+}
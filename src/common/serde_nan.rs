@@ -0,0 +1,88 @@
+//! Serde helpers for series that use NaN padding
+//!
+//! Text formats such as JSON have no NaN, and `serde_json` writes it as `null`, which
+//! then fails to deserialize back into `f64`. Series fields are therefore written as
+//! sequences of optional values (NaN as `None`) and NaN is restored when reading.
+//!
+//! Used through `#[serde(with = "crate::common::serde_nan")]`.
+
+use serde::{Deserialize, Deserializer, Serializer};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Writes a series, with NaN values as `None`
+pub fn serialize<S: Serializer>(values: &[f64], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(values.iter().map(|&v| if v.is_nan() { None } else { Some(v) }))
+}
+
+/// Reads a series written by [`serialize`], with `None` as NaN
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f64>, D::Error> {
+    let values = Vec::<Option<f64>>::deserialize(deserializer)?;
+    Ok(values.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+}
+
+/// The same encoding for a list of series, such as one coefficient series per regressor
+///
+/// Used through `#[serde(with = "crate::common::serde_nan::nested")]`.
+pub mod nested {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    #[cfg(not(feature = "std"))]
+    use crate::common::compat::*;
+
+    struct Series<'a>(&'a [f64]);
+
+    impl Serialize for Series<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(self.0, serializer)
+        }
+    }
+
+    /// Writes each series with NaN values as `None`
+    pub fn serialize<S: Serializer>(series: &[Vec<f64>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(series.iter().map(|values| Series(values)))
+    }
+
+    /// Reads series written by [`serialize`], with `None` as NaN
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<f64>>, D::Error> {
+        let series = Vec::<Vec<Option<f64>>>::deserialize(deserializer)?;
+        Ok(series
+            .into_iter()
+            .map(|values| values.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::overlap::{bbands, mama, BollingerBands, MamaResult};
+
+    #[test]
+    fn test_nan_padded_series_round_trip() {
+        let data: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+
+        let bands = bbands(&data, 20, 2.0).unwrap();
+        let json = serde_json::to_string(&bands).unwrap();
+        let restored: BollingerBands = serde_json::from_str(&json).unwrap();
+        crate::common::assert_arrays_approx_equal(&restored.upper, &bands.upper, 0.0);
+        crate::common::assert_arrays_approx_equal(&restored.lower, &bands.lower, 0.0);
+
+        let result = mama(&data, 0.5, 0.05).unwrap();
+        let json = serde_json::to_string(&result).unwrap();
+        let restored: MamaResult = serde_json::from_str(&json).unwrap();
+        crate::common::assert_arrays_approx_equal(&restored.mama, &result.mama, 0.0);
+        crate::common::assert_arrays_approx_equal(&restored.fama, &result.fama, 0.0);
+    }
+
+    #[test]
+    fn test_nested_series_round_trip() {
+        use crate::statistic::{rolling_ols, RollingOls};
+
+        let x: Vec<f64> = (0..20).map(|i| (i as f64 * 0.4).sin()).collect();
+        let y: Vec<f64> = (0..20).map(|i| 1.0 + 2.0 * x[i] + (i % 3) as f64 * 0.1).collect();
+        let result = rolling_ols(&y, &[&x], 5).unwrap();
+        let json = serde_json::to_string(&result).unwrap();
+        let restored: RollingOls = serde_json::from_str(&json).unwrap();
+        crate::common::assert_arrays_approx_equal(&restored.coefficients[0], &result.coefficients[0], 0.0);
+        crate::common::assert_arrays_approx_equal(&restored.r_squared, &result.r_squared, 0.0);
+    }
+}
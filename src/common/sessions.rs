@@ -0,0 +1,415 @@
+//! Trading sessions
+//!
+//! Intraday bars come in sessions separated by closed hours, and many intraday tools
+//! restart with each session: VWAP, the session high and low, opening-range levels.
+//! Sessions are found either from gaps between bars, which suits exchange hours without
+//! any calendar, or from fixed-length periods such as days, which suits markets that trade
+//! around the clock. Indicators are then applied to each session separately.
+
+use core::ops::Range;
+
+use crate::common::resample::{bucket_start, validate_interval};
+use crate::common::{OhlcvSeries, TAError, TAResult, Timestamp};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rule starting a new trading session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SessionBoundary {
+    /// A new session starts after a pause between bar starts longer than the given
+    /// duration, e.g. 3,600 seconds to split an exchange's days at the overnight close
+    Gap(Timestamp),
+    /// A new session starts at every multiple of `length` after timestamp `offset`, e.g.
+    /// `length` 86,400 and `offset` 79,200 for days starting at 22:00 UTC
+    Period {
+        /// Length of a session
+        length: Timestamp,
+        /// Start of one session, aligning all others
+        offset: Timestamp,
+    },
+}
+
+impl SessionBoundary {
+    fn validate(self) -> TAResult<()> {
+        match self {
+            SessionBoundary::Gap(gap) if gap <= 0 => Err(TAError::invalid_parameter("gap", "must be positive")),
+            SessionBoundary::Period { length, .. } => validate_interval(length),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a bar starting at `current` opens a new session after one starting at `previous`
+    fn starts_session(self, previous: Timestamp, current: Timestamp) -> bool {
+        match self {
+            SessionBoundary::Gap(gap) => current.saturating_sub(previous) > gap,
+            SessionBoundary::Period { length, offset } => {
+                bucket_start(previous.wrapping_sub(offset), length) != bucket_start(current.wrapping_sub(offset), length)
+            }
+        }
+    }
+}
+
+/// Marks the bars opening a trading session
+///
+/// The first bar always opens a session.
+///
+/// # Arguments
+/// * `timestamps` - Strictly increasing bar start times
+/// * `boundary` - Rule starting a new session
+///
+/// # Returns
+/// * `Ok(Vec<bool>)` - Whether each bar opens a session
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{session_starts, SessionBoundary};
+///
+/// // Hourly bars from 09:00 to 15:00 on two days
+/// let timestamps: Vec<i64> = (9..16).chain(33..40).map(|h| h * 3_600).collect();
+/// let starts = session_starts(&timestamps, SessionBoundary::Gap(3_600)).unwrap();
+/// assert!(starts[0] && starts[7]);
+/// assert_eq!(starts.iter().filter(|&&s| s).count(), 2);
+/// ```
+pub fn session_starts(timestamps: &[Timestamp], boundary: SessionBoundary) -> TAResult<Vec<bool>> {
+    with_function("SESSION_STARTS", || {
+        validate_timestamps(timestamps)?;
+        boundary.validate()?;
+        Ok(core::iter::once(true)
+            .chain(timestamps.windows(2).map(|w| boundary.starts_session(w[0], w[1])))
+            .collect())
+    })
+}
+
+/// Splits bars into trading sessions
+///
+/// # Arguments
+/// * `timestamps` - Strictly increasing bar start times
+/// * `boundary` - Rule starting a new session
+///
+/// # Returns
+/// * `Ok(Vec<Range<usize>>)` - Index range of the bars of each session, in order
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{sessions, SessionBoundary};
+///
+/// // Round-the-clock 6-hour bars, split into UTC days
+/// let timestamps: Vec<i64> = (0..10).map(|i| i * 21_600).collect();
+/// let days = sessions(&timestamps, SessionBoundary::Period { length: 86_400, offset: 0 }).unwrap();
+/// assert_eq!(days, vec![0..4, 4..8, 8..10]);
+/// ```
+pub fn sessions(timestamps: &[Timestamp], boundary: SessionBoundary) -> TAResult<Vec<Range<usize>>> {
+    with_function("SESSIONS", || {
+        let starts = session_starts(timestamps, boundary)?;
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for i in 1..=starts.len() {
+            if i == starts.len() || starts[i] {
+                ranges.push(start..i);
+                start = i;
+            }
+        }
+        Ok(ranges)
+    })
+}
+
+/// Calculates the gap between sessions
+///
+/// The gap is the open of a session's first bar relative to the close of the previous
+/// session's last bar, e.g. 0.02 for an open 2% above the prior close.
+///
+/// # Arguments
+/// * `series` - Bars with timestamps
+/// * `boundary` - Rule starting a new session
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - The gap on the first bar of each session after the first; NaN on
+///   every other bar
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{session_gaps, OhlcvSeries, SessionBoundary};
+///
+/// let series = OhlcvSeries::new(
+///     vec![0, 60, 86_400, 86_460],
+///     vec![100.0, 101.0, 104.04, 103.0],
+///     vec![101.0, 102.0, 105.0, 104.0],
+///     vec![99.0, 100.5, 103.0, 102.0],
+///     vec![101.0, 102.0, 103.5, 103.0],
+///     vec![10.0; 4],
+/// ).unwrap();
+///
+/// let gaps = session_gaps(&series, SessionBoundary::Gap(3_600)).unwrap();
+/// assert!(gaps[1].is_nan());
+/// assert!((gaps[2] - 0.02).abs() < 1e-12);
+/// ```
+pub fn session_gaps(series: &OhlcvSeries, boundary: SessionBoundary) -> TAResult<Vec<f64>> {
+    with_function("SESSION_GAPS", || {
+        series.validate()?;
+        let starts = session_starts(&series.timestamp, boundary)?;
+        let mut gaps = vec![f64::NAN; series.len()];
+        for i in 1..series.len() {
+            if starts[i] {
+                gaps[i] = series.open[i] / series.close[i - 1] - 1.0;
+            }
+        }
+        Ok(gaps)
+    })
+}
+
+/// Applies an indicator to each trading session separately
+///
+/// The indicator sees the bars of one session at a time, so it restarts, warm-up
+/// included, at every session. A session too short for the indicator gives NaN.
+///
+/// # Arguments
+/// * `series` - Bars with timestamps
+/// * `boundary` - Rule starting a new session
+/// * `indicator` - Computes one value per bar of a session
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - The values of all sessions, one per bar
+/// * `Err(TAError)` - Error if inputs are invalid or the indicator fails other than for
+///   lack of data
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{per_session, OhlcvSeries, SessionBoundary};
+/// use ta_rust::overlap::sma;
+///
+/// let close = vec![10.0, 11.0, 12.0, 20.0, 21.0];
+/// let series = OhlcvSeries::new(
+///     vec![0, 60, 120, 90_000, 90_060],
+///     close.clone(), close.clone(), close.clone(), close, vec![1.0; 5],
+/// ).unwrap();
+///
+/// let result = per_session(&series, SessionBoundary::Gap(3_600), |bars| sma(&bars.close, 2)).unwrap();
+/// assert!(result[0].is_nan() && result[3].is_nan());
+/// assert_eq!(result[4], 20.5);
+/// ```
+pub fn per_session<F>(series: &OhlcvSeries, boundary: SessionBoundary, mut indicator: F) -> TAResult<Vec<f64>>
+where
+    F: FnMut(&OhlcvSeries) -> TAResult<Vec<f64>>,
+{
+    with_function("PER_SESSION", || {
+        series.validate()?;
+        let mut result = Vec::with_capacity(series.len());
+        for session in sessions(&series.timestamp, boundary)? {
+            let bars = OhlcvSeries {
+                timestamp: series.timestamp[session.clone()].to_vec(),
+                open: series.open[session.clone()].to_vec(),
+                high: series.high[session.clone()].to_vec(),
+                low: series.low[session.clone()].to_vec(),
+                close: series.close[session.clone()].to_vec(),
+                volume: series.volume[session.clone()].to_vec(),
+            };
+            match indicator(&bars) {
+                Ok(values) if values.len() == session.len() => result.extend(values),
+                Ok(_) => {
+                    return Err(TAError::mismatched_inputs("Indicator must return one value per bar of a session"));
+                }
+                Err(TAError::InsufficientData { .. }) => result.resize(session.end, f64::NAN),
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(result)
+    })
+}
+
+/// Calculates the volume-weighted average price of each session so far
+///
+/// # Formula
+/// ```text
+/// VWAP = Σ(typical price × volume) / Σ volume, summed from the session's first bar
+/// typical price = (high + low + close) / 3
+/// ```
+///
+/// # Arguments
+/// * `series` - Bars with timestamps
+/// * `boundary` - Rule starting a new session
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - VWAP of each bar's session up to the bar; NaN while the session has
+///   traded no volume
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{session_vwap, OhlcvSeries, SessionBoundary};
+///
+/// let series = OhlcvSeries::new(
+///     vec![0, 60, 90_000],
+///     vec![10.0, 12.0, 20.0],
+///     vec![10.0, 12.0, 20.0],
+///     vec![10.0, 12.0, 20.0],
+///     vec![10.0, 12.0, 20.0],
+///     vec![100.0, 300.0, 50.0],
+/// ).unwrap();
+///
+/// let vwap = session_vwap(&series, SessionBoundary::Gap(3_600)).unwrap();
+/// assert_eq!(vwap, vec![10.0, 11.5, 20.0]);
+/// ```
+pub fn session_vwap(series: &OhlcvSeries, boundary: SessionBoundary) -> TAResult<Vec<f64>> {
+    with_function("SESSION_VWAP", || {
+        series.validate()?;
+        let starts = session_starts(&series.timestamp, boundary)?;
+        let mut vwap = vec![f64::NAN; series.len()];
+        let (mut value, mut volume) = (0.0, 0.0);
+        for i in 0..series.len() {
+            if starts[i] {
+                value = 0.0;
+                volume = 0.0;
+            }
+            let typical = (series.high[i] + series.low[i] + series.close[i]) / 3.0;
+            value += typical * series.volume[i];
+            volume += series.volume[i];
+            if volume > 0.0 {
+                vwap[i] = value / volume;
+            }
+        }
+        Ok(vwap)
+    })
+}
+
+/// Calculates the high and low of each session so far
+///
+/// # Arguments
+/// * `series` - Bars with timestamps
+/// * `boundary` - Rule starting a new session
+///
+/// # Returns
+/// * `Ok((Vec<f64>, Vec<f64>))` - Tuple of (highest high, lowest low) of each bar's session
+///   up to and including the bar
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{session_high_low, OhlcvSeries, SessionBoundary};
+///
+/// let series = OhlcvSeries::new(
+///     vec![0, 60, 120, 90_000],
+///     vec![10.0; 4],
+///     vec![11.0, 13.0, 12.0, 10.5],
+///     vec![9.0, 9.5, 8.0, 9.8],
+///     vec![10.0; 4],
+///     vec![1.0; 4],
+/// ).unwrap();
+///
+/// let (high, low) = session_high_low(&series, SessionBoundary::Gap(3_600)).unwrap();
+/// assert_eq!(high, vec![11.0, 13.0, 13.0, 10.5]);
+/// assert_eq!(low, vec![9.0, 9.0, 8.0, 9.8]);
+/// ```
+pub fn session_high_low(series: &OhlcvSeries, boundary: SessionBoundary) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    with_function("SESSION_HIGH_LOW", || {
+        series.validate()?;
+        let starts = session_starts(&series.timestamp, boundary)?;
+        let mut highest = vec![f64::NAN; series.len()];
+        let mut lowest = vec![f64::NAN; series.len()];
+        for i in 0..series.len() {
+            if starts[i] {
+                highest[i] = series.high[i];
+                lowest[i] = series.low[i];
+            } else {
+                highest[i] = highest[i - 1].max(series.high[i]);
+                lowest[i] = lowest[i - 1].min(series.low[i]);
+            }
+        }
+        Ok((highest, lowest))
+    })
+}
+
+fn validate_timestamps(timestamps: &[Timestamp]) -> TAResult<()> {
+    if timestamps.is_empty() {
+        return Err(TAError::invalid_input("Timestamps cannot be empty"));
+    }
+
+    if timestamps.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(TAError::invalid_input("Timestamps must be strictly increasing"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::rsi;
+
+    /// Five-minute bars from 09:30 to 16:00 on three days, with a holiday in between
+    fn intraday() -> OhlcvSeries {
+        let day = 86_400;
+        let timestamps: Vec<i64> = [0, 1, 3]
+            .iter()
+            .flat_map(|d| (0..78).map(move |i| d * day + 34_200 + i * 300))
+            .collect();
+        let close: Vec<f64> = (0..timestamps.len()).map(|i| 50.0 + (i as f64 * 0.1).sin() * 2.0 + (i / 78) as f64).collect();
+        let open = close.iter().enumerate().map(|(i, c)| if i % 78 == 0 { c + 0.5 } else { *c }).collect();
+        OhlcvSeries::new(
+            timestamps,
+            open,
+            close.iter().map(|c| c + 0.3).collect(),
+            close.iter().map(|c| c - 0.2).collect(),
+            close,
+            (0..234).map(|i| 100.0 + (i % 7) as f64 * 10.0).collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gap_and_period_boundaries_agree() {
+        let series = intraday();
+        let by_gap = sessions(&series.timestamp, SessionBoundary::Gap(3_600)).unwrap();
+        let by_day = sessions(&series.timestamp, SessionBoundary::Period { length: 86_400, offset: 0 }).unwrap();
+        assert_eq!(by_gap, vec![0..78, 78..156, 156..234]);
+        assert_eq!(by_gap, by_day);
+
+        // Days starting at 12:00 join each afternoon with the next trading morning
+        let noon = sessions(&series.timestamp, SessionBoundary::Period { length: 86_400, offset: 43_200 }).unwrap();
+        assert_eq!(noon, vec![0..30, 30..108, 108..156, 156..186, 186..234]);
+
+        let gaps = session_gaps(&series, SessionBoundary::Gap(3_600)).unwrap();
+        assert_eq!(gaps.iter().filter(|g| !g.is_nan()).count(), 2);
+        assert_eq!(gaps[78], series.open[78] / series.close[77] - 1.0);
+    }
+
+    #[test]
+    fn test_session_indicators_reset() {
+        let series = intraday();
+        let boundary = SessionBoundary::Gap(3_600);
+        let vwap = session_vwap(&series, boundary).unwrap();
+        let (high, low) = session_high_low(&series, boundary).unwrap();
+        for session in sessions(&series.timestamp, boundary).unwrap() {
+            let typical = |i: usize| (series.high[i] + series.low[i] + series.close[i]) / 3.0;
+            let start = session.start;
+            assert!((vwap[start] - typical(start)).abs() < 1e-12);
+            let end = session.end - 1;
+            let expected = session.clone().map(|i| typical(i) * series.volume[i]).sum::<f64>()
+                / series.volume[session.clone()].iter().sum::<f64>();
+            assert!((vwap[end] - expected).abs() < 1e-9);
+            assert_eq!(high[end], series.high[session.clone()].iter().copied().fold(f64::MIN, f64::max));
+            assert_eq!(low[end], series.low[session.clone()].iter().copied().fold(f64::MAX, f64::min));
+        }
+
+        let session_rsi = per_session(&series, boundary, |bars| rsi(&bars.close, 14)).unwrap();
+        let first = rsi(&series.close[..78], 14).unwrap();
+        assert_eq!(session_rsi.len(), series.len());
+        assert!(session_rsi[78..92].iter().all(|v| v.is_nan()));
+        assert_eq!(session_rsi[20], first[20]);
+        // Sessions too short for the indicator give NaN
+        let long = per_session(&series, boundary, |bars| rsi(&bars.close, 100)).unwrap();
+        assert!(long.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_sessions_invalid_input() {
+        assert!(session_starts(&[], SessionBoundary::Gap(60)).is_err());
+        assert_eq!(session_starts(&[0, 0], SessionBoundary::Gap(60)).unwrap_err().function(), Some("SESSION_STARTS"));
+        assert!(session_starts(&[0, 60], SessionBoundary::Gap(0)).is_err());
+        assert!(session_starts(&[0, 60], SessionBoundary::Period { length: 0, offset: 0 }).is_err());
+        assert!(per_session(&intraday(), SessionBoundary::Gap(3_600), |bars| Ok(bars.close[1..].to_vec())).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Compensated summation
+//!
+//! Rolling windows keep a running sum, adding the newest value and removing the oldest on
+//! each bar. With plain floating point additions the rounding error of every step stays in
+//! the sum, so over millions of bars (years of minute data) the result drifts visibly away
+//! from the sum of the current window. [`NeumaierSum`] keeps the low-order bits lost by each
+//! addition in a separate compensation term (Neumaier's improvement of Kahan summation), so
+//! the error stays within a few units in the last place however long the series is.
+
+use crate::common::Float;
+
+/// Running sum with Neumaier compensation
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::NeumaierSum;
+///
+/// let mut sum = NeumaierSum::new();
+/// for value in [1.0, 1e100, 1.0, -1e100] {
+///     sum.add(value);
+/// }
+/// assert_eq!(sum.value(), 2.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeumaierSum<T = f64> {
+    sum: T,
+    compensation: T,
+}
+
+impl<T: Float> NeumaierSum<T> {
+    /// Creates an empty sum
+    pub fn new() -> Self {
+        Self { sum: T::ZERO, compensation: T::ZERO }
+    }
+
+    /// Adds a value to the sum
+    #[inline]
+    pub fn add(&mut self, value: T) {
+        let total = self.sum + value;
+        // Recover the bits of the smaller operand that did not fit into the total
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - total) + value;
+        } else {
+            self.compensation += (value - total) + self.sum;
+        }
+        self.sum = total;
+    }
+
+    /// Removes a value previously added to the sum
+    #[inline]
+    pub fn sub(&mut self, value: T) {
+        self.add(-value);
+    }
+
+    /// Current value of the sum
+    #[inline]
+    pub fn value(&self) -> T {
+        self.sum + self.compensation
+    }
+}
+
+impl<T: Float> Default for NeumaierSum<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sums values with Neumaier compensation
+///
+/// Drop-in replacement for `iter().sum()` where the values differ widely in magnitude or
+/// cancel each other out.
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::neumaier_sum;
+///
+/// let values = [0.1; 10];
+/// assert_eq!(neumaier_sum(values), 1.0);
+/// assert_ne!(values.iter().sum::<f64>(), 1.0);
+/// ```
+pub fn neumaier_sum<T: Float, I: IntoIterator<Item = T>>(values: I) -> T {
+    let mut sum = NeumaierSum::new();
+    for value in values {
+        sum.add(value);
+    }
+    sum.value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{fixed_point_prices, fixed_to_f64, FIXED_POINT_BITS};
+    #[cfg(not(feature = "std"))]
+    use crate::common::compat::*;
+
+    #[test]
+    fn test_cancellation() {
+        let mut sum = NeumaierSum::new();
+        sum.add(1e16);
+        sum.add(1.0);
+        sum.sub(1e16);
+        assert_eq!(sum.value(), 1.0);
+        assert_eq!(1e16 + 1.0 - 1e16, 0.0);
+    }
+
+    #[test]
+    fn test_rolling_sum_error_against_exact_reference() {
+        let period = 1_000;
+        let fixed = fixed_point_prices(500_000, 7);
+        let prices: Vec<f64> = fixed.iter().map(|&p| fixed_to_f64(p, FIXED_POINT_BITS)).collect();
+
+        let (mut exact, mut naive, mut compensated) = (0i128, 0.0, NeumaierSum::new());
+        let (mut naive_error, mut compensated_error) = (0.0f64, 0.0f64);
+        for i in 0..prices.len() {
+            exact += fixed[i];
+            naive += prices[i];
+            compensated.add(prices[i]);
+            if i >= period {
+                exact -= fixed[i - period];
+                naive -= prices[i - period];
+                compensated.sub(prices[i - period]);
+            }
+            // The exact sum rounded once to f64 is the best any method can do
+            let reference = fixed_to_f64(exact, FIXED_POINT_BITS);
+            naive_error = naive_error.max((naive - reference).abs() / reference);
+            compensated_error = compensated_error.max((compensated.value() - reference).abs() / reference);
+        }
+
+        assert!(compensated_error <= f64::EPSILON, "compensated error {}", compensated_error);
+        assert!(naive_error > 100.0 * f64::EPSILON, "naive error {}", naive_error);
+    }
+
+    #[test]
+    fn test_neumaier_sum() {
+        assert_eq!(neumaier_sum(vec![1e100, 1.0, -1e100]), 1.0);
+        assert_eq!(neumaier_sum(Vec::<f64>::new()), 0.0);
+        assert_eq!(neumaier_sum([0.1f32; 10]), 1.0);
+        assert!(neumaier_sum([1.0, f64::NAN]).is_nan());
+    }
+}
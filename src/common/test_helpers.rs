@@ -37,6 +37,33 @@ pub fn is_within_tolerance(actual: Price, expected: Price, tolerance: Price) ->
     }
 }
 
+/// Fractional bits of the prices from [`fixed_point_prices`]
+pub const FIXED_POINT_BITS: u32 = 30;
+
+/// Random-walk prices around 10,000 as exact fixed-point integers
+///
+/// Window sums of these prices (and of their squares and products) are exact in `i128` but
+/// need more than the 53 bits of an `f64`, so they give an exact reference for the rounding
+/// error of floating point sums.
+pub fn fixed_point_prices(len: usize, seed: u64) -> Vec<i128> {
+    let mut state = seed | 1;
+    let mut price: i128 = 10_000 << FIXED_POINT_BITS;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            price += (state % (1 << 24)) as i128 - (1 << 23);
+            price
+        })
+        .collect()
+}
+
+/// Nearest `f64` to a fixed-point value with `fraction_bits` fractional bits
+pub fn fixed_to_f64(value: i128, fraction_bits: u32) -> Price {
+    value as Price / (fraction_bits as Price).exp2()
+}
+
 /// Macro for asserting floating point equality with tolerance
 #[macro_export]
 macro_rules! assert_float_eq {
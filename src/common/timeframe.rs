@@ -0,0 +1,179 @@
+//! Multi-timeframe alignment
+//!
+//! Indicators computed on resampled bars have one value per higher-timeframe bar. To use
+//! them alongside base bars (e.g. a daily RSI filter on hourly bars), each base bar takes the
+//! value of the latest higher-timeframe bar that had already finished when the base bar
+//! closed. The still-forming higher bar is never visible, so the aligned series carries no
+//! lookahead bias.
+
+use crate::common::resample::{resample, validate_interval, PartialBar};
+use crate::common::{OhlcvSeries, TAError, TAResult, Timestamp};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Forward-fills higher-timeframe values onto base bars
+///
+/// A base bar starting at `t` closes at `t + step`, where `step` is the smallest gap between
+/// consecutive base timestamps (0 for a single bar). A higher bar starting at `s` is
+/// confirmed once `s + interval <= t + step`. When the base data stops before a bucket ends,
+/// as with an exchange session closing before midnight, the daily value therefore only
+/// appears on the first bar of the next session.
+///
+/// # Arguments
+/// * `base` - Timestamps of the base bars
+/// * `higher_timestamp` - Start timestamps of the higher-timeframe bars
+/// * `values` - Indicator values, one per higher-timeframe bar
+/// * `interval` - Length of a higher-timeframe bar
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - One value per base bar; NaN until the first higher bar is confirmed
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::align_to_base;
+///
+/// // 5-minute values on 1-minute bars
+/// let base: Vec<i64> = (0..12).map(|i| i * 60).collect();
+/// let aligned = align_to_base(&base, &[0, 300, 600], &[1.0, 2.0, 3.0], 300).unwrap();
+/// // The first 5-minute bar is confirmed at the close of the minute starting at 240
+/// assert!(aligned[3].is_nan());
+/// assert_eq!(&aligned[4..], &[1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0]);
+/// ```
+pub fn align_to_base(
+    base: &[Timestamp],
+    higher_timestamp: &[Timestamp],
+    values: &[f64],
+    interval: Timestamp,
+) -> TAResult<Vec<f64>> {
+    with_function("ALIGN_TO_BASE", || {
+        validate_interval(interval)?;
+        if base.is_empty() {
+            return Err(TAError::invalid_input("Base timestamps cannot be empty"));
+        }
+
+        if higher_timestamp.len() != values.len() {
+            return Err(TAError::mismatched_inputs(
+                "Higher timeframe timestamps and values must have the same length",
+            ));
+        }
+
+        if base.windows(2).chain(higher_timestamp.windows(2)).any(|w| w[1] <= w[0]) {
+            return Err(TAError::invalid_input("Timestamps must be strictly increasing"));
+        }
+
+        let step = base.windows(2).map(|w| w[1].saturating_sub(w[0])).min().unwrap_or(0);
+        let mut result = vec![f64::NAN; base.len()];
+        let mut confirmed = 0;
+        for (value, &t) in result.iter_mut().zip(base) {
+            let close = t.saturating_add(step);
+            while confirmed < higher_timestamp.len() && higher_timestamp[confirmed].saturating_add(interval) <= close {
+                confirmed += 1;
+            }
+            if confirmed > 0 {
+                *value = values[confirmed - 1];
+            }
+        }
+        Ok(result)
+    })
+}
+
+/// Computes an indicator on a higher timeframe and aligns it to the base bars
+///
+/// Resamples `series` to `interval`, applies `indicator` to the resampled bars and
+/// forward-fills the result onto the base bars with [`align_to_base`].
+///
+/// # Arguments
+/// * `series` - Base bars
+/// * `interval` - Length of a higher-timeframe bar
+/// * `indicator` - Computes one value per bar of the resampled series
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - One value per base bar; NaN until the first higher bar is confirmed
+/// * `Err(TAError)` - Error if inputs are invalid or the indicator fails
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{higher_timeframe, OhlcvSeries};
+/// use ta_rust::overlap::sma;
+///
+/// let close: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+/// let minutes = OhlcvSeries::new(
+///     (0..60).map(|i| i * 60).collect(),
+///     close.clone(), close.clone(), close.clone(), close, vec![1.0; 60],
+/// ).unwrap();
+///
+/// // 2-period SMA of 5-minute closes, on 1-minute bars
+/// let aligned = higher_timeframe(&minutes, 300, |bars| sma(&bars.close, 2)).unwrap();
+/// assert_eq!(aligned.len(), 60);
+/// assert!(aligned[8].is_nan());
+/// assert_eq!(aligned[9], (104.0 + 109.0) / 2.0);
+/// ```
+pub fn higher_timeframe<F>(series: &OhlcvSeries, interval: Timestamp, indicator: F) -> TAResult<Vec<f64>>
+where
+    F: FnOnce(&OhlcvSeries) -> TAResult<Vec<f64>>,
+{
+    with_function("HIGHER_TIMEFRAME", || {
+        let bars = resample(series, interval, PartialBar::Drop)?;
+        let values = indicator(&bars)?;
+        align_to_base(&series.timestamp, &bars.timestamp, &values, interval)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::rsi;
+
+    fn hours(count: i64) -> OhlcvSeries {
+        let close: Vec<f64> = (0..count).map(|i| 100.0 + (i as f64 * 0.3).sin() * 10.0).collect();
+        OhlcvSeries::new(
+            (0..count).map(|i| i * 3_600).collect(),
+            close.clone(),
+            close.iter().map(|c| c + 1.0).collect(),
+            close.iter().map(|c| c - 1.0).collect(),
+            close,
+            vec![1.0; count as usize],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_higher_timeframe_no_lookahead() {
+        let series = hours(24 * 30);
+        let daily = resample(&series, 86_400, PartialBar::Drop).unwrap();
+        let daily_rsi = rsi(&daily.close, 5).unwrap();
+        let aligned = higher_timeframe(&series, 86_400, |bars| rsi(&bars.close, 5)).unwrap();
+
+        for (i, &value) in aligned.iter().enumerate() {
+            // Bar i closes at hour i + 1, after which days 0..(i + 1) / 24 are complete
+            let days_done = (i + 1) / 24;
+            if days_done == 0 || daily_rsi[days_done - 1].is_nan() {
+                assert!(value.is_nan());
+            } else {
+                assert_eq!(value, daily_rsi[days_done - 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_align_session_gap() {
+        // Bars from 09:00 to 15:00 on two days; day 0 is confirmed on day 1's first bar
+        let day = 86_400;
+        let base: Vec<i64> = (9..16).chain(24 + 9..24 + 16).map(|h| h * 3_600).collect();
+        let aligned = align_to_base(&base, &[0, day], &[1.0, 2.0], day).unwrap();
+        assert!(aligned[..7].iter().all(|v| v.is_nan()));
+        assert!(aligned[7..].iter().all(|&v| v == 1.0));
+    }
+
+    #[test]
+    fn test_align_invalid_input() {
+        assert_eq!(align_to_base(&[], &[0], &[1.0], 60).unwrap_err().function(), Some("ALIGN_TO_BASE"));
+        assert!(align_to_base(&[0, 60], &[0], &[1.0, 2.0], 60).is_err());
+        assert!(align_to_base(&[60, 0], &[0], &[1.0], 60).is_err());
+        assert!(align_to_base(&[0, 60], &[0], &[1.0], 0).is_err());
+        let err = higher_timeframe(&hours(10), 86_400, |bars| rsi(&bars.close, 14)).unwrap_err();
+        assert_eq!(err.function(), Some("HIGHER_TIMEFRAME"));
+    }
+}
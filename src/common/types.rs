@@ -1,5 +1,10 @@
 //! Core type definitions for TA-Rust
 
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
 /// Price type used throughout the library
 /// 
 /// Uses f64 for maximum precision in financial calculations
@@ -8,16 +13,21 @@ pub type Price = f64;
 /// Volume type for trading volume data
 pub type Volume = f64;
 
-/// Period type for time periods in indicators
-pub type Period = usize;
+/// Timestamp type for bar data
+///
+/// Any integer time unit works (Unix seconds, milliseconds, ...) as long as it is used
+/// consistently, including for intervals passed alongside it.
+pub type Timestamp = i64;
 
 /// Moving Average types supported by the library
 /// 
 /// These correspond to the MA types available in TA-Lib
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum MAType {
     /// Simple Moving Average
+    #[default]
     SMA = 0,
     /// Exponential Moving Average
     EMA = 1,
@@ -39,7 +49,7 @@ pub enum MAType {
 
 impl MAType {
     /// Returns the default parameters for each MA type
-    pub fn default_period(self) -> Period {
+    pub fn default_period(self) -> usize {
         match self {
             MAType::SMA => 14,
             MAType::EMA => 14,
@@ -54,7 +64,7 @@ impl MAType {
     }
 
     /// Returns the minimum required data points for the MA type
-    pub fn min_period(self) -> Period {
+    pub fn min_period(self) -> usize {
         match self {
             MAType::SMA => 1,
             MAType::EMA => 1,
@@ -84,12 +94,6 @@ impl MAType {
     }
 }
 
-impl Default for MAType {
-    fn default() -> Self {
-        MAType::SMA
-    }
-}
-
 impl core::fmt::Display for MAType {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let name = match self {
@@ -109,6 +113,7 @@ impl core::fmt::Display for MAType {
 
 /// Candlestick data structure
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OHLC {
     /// Opening price
     pub open: Price,
@@ -190,8 +195,15 @@ impl OHLC {
     }
 }
 
+impl AsRef<OHLC> for OHLC {
+    fn as_ref(&self) -> &OHLC {
+        self
+    }
+}
+
 /// Candlestick data with volume
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OHLCV {
     /// OHLC data
     pub ohlc: OHLC,
@@ -214,6 +226,94 @@ impl OHLCV {
     }
 }
 
+impl AsRef<OHLC> for OHLCV {
+    fn as_ref(&self) -> &OHLC {
+        &self.ohlc
+    }
+}
+
+/// Timestamped bar series, one vector per field
+///
+/// Each bar is labelled with the timestamp of its start.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OhlcvSeries {
+    /// Bar start times, strictly increasing
+    pub timestamp: Vec<Timestamp>,
+    /// Opening prices
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub open: Vec<Price>,
+    /// Highest prices
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub high: Vec<Price>,
+    /// Lowest prices
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub low: Vec<Price>,
+    /// Closing prices
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub close: Vec<Price>,
+    /// Trading volumes
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub volume: Vec<Volume>,
+}
+
+impl OhlcvSeries {
+    /// Creates a series, checking that all fields have the same length and that
+    /// timestamps are strictly increasing
+    pub fn new(
+        timestamp: Vec<Timestamp>,
+        open: Vec<Price>,
+        high: Vec<Price>,
+        low: Vec<Price>,
+        close: Vec<Price>,
+        volume: Vec<Volume>,
+    ) -> TAResult<Self> {
+        with_function("OHLCV_SERIES", || {
+            let series = Self { timestamp, open, high, low, close, volume };
+            series.validate()?;
+            Ok(series)
+        })
+    }
+
+    /// Checks that all fields have the same length and that timestamps are strictly increasing
+    pub fn validate(&self) -> TAResult<()> {
+        with_function("OHLCV_SERIES", || {
+            let len = self.timestamp.len();
+            if [self.open.len(), self.high.len(), self.low.len(), self.close.len(), self.volume.len()]
+                .iter()
+                .any(|&l| l != len)
+            {
+                return Err(TAError::mismatched_inputs("All series fields must have the same length"));
+            }
+
+            if let Some(i) = self.timestamp.windows(2).position(|w| w[1] <= w[0]) {
+                return Err(TAError::invalid_input(format!(
+                    "Timestamps must be strictly increasing (index {})",
+                    i + 1
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    /// Number of bars
+    pub fn len(&self) -> usize {
+        self.timestamp.len()
+    }
+
+    /// Returns true if the series has no bars
+    pub fn is_empty(&self) -> bool {
+        self.timestamp.is_empty()
+    }
+
+    /// The bar at `index`, without its timestamp
+    pub fn bar(&self, index: usize) -> Option<OHLCV> {
+        (index < self.len()).then(|| {
+            OHLCV::new(self.open[index], self.high[index], self.low[index], self.close[index], self.volume[index])
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,4 +367,25 @@ mod tests {
         assert_eq!(ohlc.true_range(Some(8.0)), 4.0); // max(3.0, 4.0, 1.0)
         assert_eq!(ohlc.true_range(Some(13.0)), 4.0); // max(3.0, 1.0, 4.0)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ohlcv_series_validation() {
+        let series = OhlcvSeries::new(
+            vec![0, 60, 120],
+            vec![1.0, 2.0, 3.0],
+            vec![1.5, 2.5, 3.5],
+            vec![0.5, 1.5, 2.5],
+            vec![1.2, 2.2, 3.2],
+            vec![10.0, 20.0, 30.0],
+        )
+        .unwrap();
+        assert_eq!(series.len(), 3);
+        assert_eq!(series.bar(1), Some(OHLCV::new(2.0, 2.5, 1.5, 2.2, 20.0)));
+        assert_eq!(series.bar(3), None);
+
+        let unordered = OhlcvSeries { timestamp: vec![0, 120, 60], ..series.clone() };
+        assert!(unordered.validate().is_err());
+        let short = OhlcvSeries { volume: vec![10.0], ..series };
+        assert!(short.validate().is_err());
+    }
+}
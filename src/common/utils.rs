@@ -1,6 +1,10 @@
 //! Utility functions for TA-Rust
 
-use crate::common::{TAError, TAResult, Price, Period};
+use alloc::borrow::Cow;
+
+use crate::common::{TAError, TAResult, Price, Float, IntoParam, Period};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Validates that input data is not empty
 pub fn validate_not_empty<T>(data: &[T], name: &str) -> TAResult<()> {
@@ -12,7 +16,8 @@ pub fn validate_not_empty<T>(data: &[T], name: &str) -> TAResult<()> {
 }
 
 /// Validates that there is sufficient data for the given period
-pub fn validate_sufficient_data<T>(data: &[T], period: Period, _name: &str) -> TAResult<()> {
+pub fn validate_sufficient_data<T>(data: &[T], period: impl IntoParam<Period>, _name: &str) -> TAResult<()> {
+    let period = period.into_param("period")?.get();
     if data.len() < period {
         Err(TAError::insufficient_data(period, data.len()))
     } else {
@@ -21,15 +26,8 @@ pub fn validate_sufficient_data<T>(data: &[T], period: Period, _name: &str) -> T
 }
 
 /// Validates that a period is positive
-pub fn validate_period(period: Period, name: &str) -> TAResult<()> {
-    if period == 0 {
-        Err(TAError::invalid_parameter(
-            name,
-            "period must be greater than 0",
-        ))
-    } else {
-        Ok(())
-    }
+pub fn validate_period(period: impl IntoParam<Period>, name: &str) -> TAResult<()> {
+    period.into_param(name).map(|_| ())
 }
 
 /// Validates that multiple input arrays have the same length
@@ -48,7 +46,7 @@ pub fn validate_same_length<T, U>(data1: &[T], data2: &[U], name1: &str, name2:
 }
 
 /// Validates that input prices are valid (not NaN or infinite)
-pub fn validate_prices(prices: &[Price], name: &str) -> TAResult<()> {
+pub fn validate_prices<T: Float>(prices: &[T], name: &str) -> TAResult<()> {
     for (i, &price) in prices.iter().enumerate() {
         if !price.is_finite() {
             return Err(TAError::invalid_input(format!(
@@ -111,6 +109,174 @@ pub fn validate_ohlc(
     Ok(())
 }
 
+/// Checks applied by [`validate_hlc_with`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HlcValidation {
+    /// Reject NaN and infinite values; when false, bars with NaN are passed to the indicator
+    pub require_finite: bool,
+    /// Distance in price units by which close may lie outside the high-low range, for feeds
+    /// where rounding puts the close a tick beyond the high or low
+    pub close_tolerance: Price,
+}
+
+impl HlcValidation {
+    /// Finite values, high >= low and low <= close <= high on every bar
+    pub const STRICT: Self = Self { require_finite: true, close_tolerance: 0.0 };
+    /// Bar consistency only, leaving NaN handling to the indicator
+    pub const CONSISTENT: Self = Self { require_finite: false, close_tolerance: 0.0 };
+
+    /// Lets close lie up to `tolerance` outside the high-low range
+    pub fn with_close_tolerance(self, tolerance: Price) -> Self {
+        Self { close_tolerance: tolerance, ..self }
+    }
+}
+
+impl Default for HlcValidation {
+    fn default() -> Self {
+        Self::STRICT
+    }
+}
+
+/// Validates HLC data consistency (equal lengths, High >= Low, Low <= Close <= High)
+///
+/// Uses [`HlcValidation::STRICT`].
+pub fn validate_hlc<T: Float>(high: &[T], low: &[T], close: &[T]) -> TAResult<()> {
+    validate_hlc_with(high, low, close, HlcValidation::STRICT)
+}
+
+/// Validates HLC data consistency with configurable checks
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `checks` - Which checks to apply
+///
+/// # Returns
+/// * `Ok(())` - The bars pass every check
+/// * `Err(TAError)` - Error naming the first offending bar
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::utils::{validate_hlc, validate_hlc_with, HlcValidation};
+///
+/// let high = [10.0, 11.0];
+/// let low = [9.0, 10.0];
+/// let close = [9.5, 11.01]; // a hair above the high
+///
+/// assert!(validate_hlc(&high, &low, &close).is_err());
+/// let lenient = HlcValidation::STRICT.with_close_tolerance(0.05);
+/// assert!(validate_hlc_with(&high, &low, &close, lenient).is_ok());
+/// ```
+pub fn validate_hlc_with<T: Float>(high: &[T], low: &[T], close: &[T], checks: HlcValidation) -> TAResult<()> {
+    if high.len() != low.len() || high.len() != close.len() {
+        return Err(TAError::mismatched_inputs(
+            "High, Low, and Close arrays must have the same length".to_string(),
+        ));
+    }
+
+    let tolerance = T::from_f64(checks.close_tolerance);
+    for i in 0..high.len() {
+        let (h, l, c) = (high[i], low[i], close[i]);
+
+        if checks.require_finite && (!h.is_finite() || !l.is_finite() || !c.is_finite()) {
+            return Err(TAError::invalid_input(format!(
+                "Invalid HLC values at index {}: H={}, L={}, C={}",
+                i, h, l, c
+            )));
+        }
+
+        // Comparisons with NaN are false, so NaN bars pass when finiteness is not required
+        if h < l {
+            return Err(TAError::invalid_input(format!(
+                "High ({}) < Low ({}) at index {}",
+                h, l, i
+            )));
+        }
+        if c < l - tolerance || c > h + tolerance {
+            return Err(TAError::invalid_input(format!(
+                "Close ({}) is outside High-Low range [{}, {}] at index {}",
+                c, l, h, i
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// How functions treat bars whose close lies outside the high-low range
+///
+/// Every mode rejects mismatched lengths, non-finite values and bars with high < low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationMode {
+    /// Return an error naming the first such bar
+    #[default]
+    Strict,
+    /// Compute with the close as given
+    Lenient,
+    /// Clamp the close into the high-low range before computing
+    Repair,
+}
+
+/// Validates HLC data under a [`ValidationMode`]
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `mode` - How a close outside the high-low range is treated
+///
+/// # Returns
+/// * `Ok(Cow<[T]>)` - The close prices to compute with; owned only when repaired
+/// * `Err(TAError)` - Error if the bars fail validation under `mode`
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::utils::{prepare_hlc, ValidationMode};
+///
+/// let high = [10.0, 11.0];
+/// let low = [9.0, 10.0];
+/// let close = [9.5, 11.01];
+///
+/// assert!(prepare_hlc(&high, &low, &close, ValidationMode::Strict).is_err());
+/// assert_eq!(prepare_hlc(&high, &low, &close, ValidationMode::Lenient).unwrap()[1], 11.01);
+/// assert_eq!(prepare_hlc(&high, &low, &close, ValidationMode::Repair).unwrap()[1], 11.0);
+/// ```
+pub fn prepare_hlc<'a, T: Float>(high: &[T], low: &[T], close: &'a [T], mode: ValidationMode) -> TAResult<Cow<'a, [T]>> {
+    let lenient = HlcValidation::STRICT.with_close_tolerance(Price::INFINITY);
+    match mode {
+        ValidationMode::Strict => validate_hlc(high, low, close)?,
+        ValidationMode::Lenient => validate_hlc_with(high, low, close, lenient)?,
+        ValidationMode::Repair => {
+            validate_hlc_with(high, low, close, lenient)?;
+            return Ok(Cow::Owned(clamp_close(high, low, close)));
+        }
+    }
+    Ok(Cow::Borrowed(close))
+}
+
+/// Clamps each close into the high-low range of its bar
+///
+/// Repairs feeds where rounding puts the close slightly beyond the high or low, so that
+/// the result passes [`validate_hlc`]. NaN values are kept.
+pub fn clamp_close<T: Float>(high: &[T], low: &[T], close: &[T]) -> Vec<T> {
+    close
+        .iter()
+        .zip(high.iter().zip(low))
+        .map(|(&c, (&h, &l))| {
+            if c > h {
+                h
+            } else if c < l {
+                l
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
 /// Allocates and initializes an output vector with NaN values
 pub fn allocate_output(size: usize) -> Vec<Price> {
     vec![Price::NAN; size]
@@ -122,12 +288,12 @@ pub fn allocate_output_with_value(size: usize, value: Price) -> Vec<Price> {
 }
 
 /// Calculates the exponential moving average multiplier
-pub fn ema_multiplier(period: Period) -> Price {
+pub fn ema_multiplier(period: usize) -> Price {
     2.0 / (period as Price + 1.0)
 }
 
 /// Calculates the Wilder's smoothing multiplier (used in RSI, ATR, etc.)
-pub fn wilders_multiplier(period: Period) -> Price {
+pub fn wilders_multiplier(period: usize) -> Price {
     1.0 / period as Price
 }
 
@@ -142,13 +308,13 @@ pub fn lowest(data: &[Price]) -> Price {
 }
 
 /// Finds the highest value in a slice over a specific period
-pub fn highest_in_period(data: &[Price], start: usize, period: Period) -> Price {
+pub fn highest_in_period(data: &[Price], start: usize, period: usize) -> Price {
     let end = (start + period).min(data.len());
     highest(&data[start..end])
 }
 
 /// Finds the lowest value in a slice over a specific period
-pub fn lowest_in_period(data: &[Price], start: usize, period: Period) -> Price {
+pub fn lowest_in_period(data: &[Price], start: usize, period: usize) -> Price {
     let end = (start + period).min(data.len());
     lowest(&data[start..end])
 }
@@ -227,6 +393,34 @@ pub fn clamp(value: Price, min: Price, max: Price) -> Price {
     value.max(min).min(max)
 }
 
+/// Detects where `fast` crosses `slow`
+///
+/// Returns one signal per bar: 1 where `fast` crosses above `slow`, -1 where it crosses
+/// below, and 0 otherwise (including bars where either value, now or on the previous
+/// bar, is NaN or infinite). A touch followed by a move through counts as a cross.
+pub fn crossovers(fast: &[Price], slow: &[Price]) -> Vec<i8> {
+    let len = fast.len().min(slow.len());
+    crossings(&fast[..len], |i| slow[i])
+}
+
+/// Crossings of `series` over `level(i)`, with the rules of [`crossovers`]
+pub(crate) fn crossings(series: &[Price], level: impl Fn(usize) -> Price) -> Vec<i8> {
+    let mut signals = vec![0i8; series.len()];
+    for (i, signal) in signals.iter_mut().enumerate().skip(1) {
+        let (prev_a, prev_b, cur_a, cur_b) = (series[i - 1], level(i - 1), series[i], level(i));
+        if ![prev_a, prev_b, cur_a, cur_b].iter().all(|v| v.is_finite()) {
+            continue;
+        }
+
+        if prev_a <= prev_b && cur_a > cur_b {
+            *signal = 1;
+        } else if prev_a >= prev_b && cur_a < cur_b {
+            *signal = -1;
+        }
+    }
+    signals
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,7 +441,18 @@ mod tests {
     #[test]
     fn test_validate_period() {
         assert!(validate_period(5, "period").is_ok());
-        assert!(validate_period(0, "period").is_err());
+        assert!(matches!(
+            validate_period(0, "horizon"),
+            Err(TAError::InvalidParameter { parameter, .. }) if parameter == "horizon"
+        ));
+    }
+
+    #[test]
+    fn test_crossovers() {
+        let fast = [1.0, 2.0, 3.0, 3.0, 2.0, f64::NAN, 4.0];
+        let slow = [2.0, 2.0, 2.0, 3.0, 3.0, 3.0, 3.0];
+        assert_eq!(crossovers(&fast, &slow), vec![0, 0, 1, 0, -1, 0, 0]);
+        assert_eq!(crossovers(&[1.0, Price::INFINITY, 1.0], &[2.0; 3]), vec![0, 0, 0]);
     }
 
     #[test]
@@ -277,6 +482,64 @@ mod tests {
         assert!(validate_ohlc(&open, &invalid_high, &low, &close).is_err());
     }
 
+    #[test]
+    fn test_validate_hlc() {
+        let high = [12.0, 13.0, 14.0];
+        let low = [9.0, 10.0, 11.0];
+        assert!(validate_hlc(&high, &low, &[11.0, 12.0, 13.0]).is_ok());
+        assert!(validate_hlc(&high, &low, &[11.0, 13.001, 13.0]).is_err());
+        assert!(validate_hlc(&high, &low, &[11.0, 12.0, Price::NAN]).is_err());
+        assert!(validate_hlc(&high, &low, &[11.0, 12.0]).is_err());
+        assert!(validate_hlc(&[8.0, 13.0, 14.0], &low, &[8.5, 12.0, 13.0]).is_err());
+    }
+
+    #[test]
+    fn test_validate_hlc_with() {
+        let high = [12.0, 13.0, 14.0];
+        let low = [9.0, 10.0, 11.0];
+        let close = [11.0, 13.001, 10.999];
+
+        let tolerant = HlcValidation::STRICT.with_close_tolerance(0.01);
+        assert!(validate_hlc_with(&high, &low, &close, tolerant).is_ok());
+        assert!(validate_hlc_with(&high, &low, &[11.0, 13.02, 13.0], tolerant).is_err());
+
+        // NaN bars pass the consistency checks, inconsistent bars still fail
+        let gapped = [11.0, Price::NAN, 13.0];
+        assert!(validate_hlc_with(&high, &low, &gapped, HlcValidation::CONSISTENT).is_ok());
+        assert!(validate_hlc_with(&high, &low, &close, HlcValidation::CONSISTENT).is_err());
+    }
+
+    #[test]
+    fn test_clamp_close() {
+        let high = [12.0, 13.0, 14.0, 15.0];
+        let low = [9.0, 10.0, 11.0, 12.0];
+        let close = [11.0, 13.001, 10.999, Price::NAN];
+
+        let clamped = clamp_close(&high, &low, &close);
+        assert_eq!(&clamped[..3], &[11.0, 13.0, 11.0]);
+        assert!(clamped[3].is_nan());
+        assert!(validate_hlc(&high[..3], &low[..3], &clamped[..3]).is_ok());
+    }
+
+    #[test]
+    fn test_prepare_hlc() {
+        let high = [12.0, 13.0, 14.0];
+        let low = [9.0, 10.0, 11.0];
+        let close = [11.0, 13.5, 13.0];
+
+        assert!(prepare_hlc(&high, &low, &close, ValidationMode::default()).is_err());
+        let lenient = prepare_hlc(&high, &low, &close, ValidationMode::Lenient).unwrap();
+        assert!(matches!(lenient, Cow::Borrowed(_)));
+        assert_eq!(&*lenient, &close);
+        assert_eq!(&*prepare_hlc(&high, &low, &close, ValidationMode::Repair).unwrap(), &[11.0, 13.0, 13.0]);
+
+        // Only the close check is relaxed
+        for mode in [ValidationMode::Lenient, ValidationMode::Repair] {
+            assert!(prepare_hlc(&[8.0, 13.0, 14.0], &low, &close, mode).is_err());
+            assert!(prepare_hlc(&high, &low, &[11.0, Price::NAN, 13.0], mode).is_err());
+        }
+    }
+
     #[test]
     fn test_multipliers() {
         assert_relative_eq!(ema_multiplier(10), 2.0 / 11.0, epsilon = 1e-10);
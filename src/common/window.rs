@@ -0,0 +1,362 @@
+//! Rolling Windows
+//!
+//! [`RingBuffer`] holds the last `capacity` values of a series, handing back the value
+//! each push evicts. Statistics over the window are kept up to date by hooks, types
+//! implementing [`WindowHook`] that [`RingBuffer::push_with`] tells about every value
+//! entering and leaving: [`RunningSum`], and the monotonic-deque extrema [`RunningMax`]
+//! and [`RunningMin`], which also work on their own as they expire values by position.
+
+use crate::common::{Float, NeumaierSum};
+use alloc::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Fixed-capacity window over the most recent values
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::window::RingBuffer;
+///
+/// let mut window = RingBuffer::new(3);
+/// assert_eq!(window.push(1.0), None);
+/// window.push(2.0);
+/// window.push(3.0);
+/// assert_eq!(window.push(4.0), Some(1.0));
+/// assert_eq!(window.iter().copied().collect::<Vec<_>>(), vec![2.0, 3.0, 4.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RingBuffer<T> {
+    capacity: usize,
+    values: Vec<T>,
+    /// Slot of the oldest value once full
+    next: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty window holding up to `capacity` values
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, values: Vec::with_capacity(capacity), next: 0 }
+    }
+
+    /// Maximum number of values held
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of values held
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the window holds no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Whether the window holds `capacity` values
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    /// Appends a value, returning the value it evicts once full
+    ///
+    /// A window of capacity 0 evicts every value as it is pushed.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if self.capacity == 0 {
+            return Some(value);
+        }
+        if !self.is_full() {
+            self.values.push(value);
+            return None;
+        }
+        let evicted = core::mem::replace(&mut self.values[self.next], value);
+        self.next = (self.next + 1) % self.capacity;
+        Some(evicted)
+    }
+
+    /// Values from the oldest to the newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        let (newer, older) = self.values.split_at(self.next);
+        older.iter().chain(newer)
+    }
+
+    /// The oldest value
+    pub fn oldest(&self) -> Option<&T> {
+        self.values.get(self.next).or_else(|| self.values.first())
+    }
+
+    /// The newest value
+    pub fn newest(&self) -> Option<&T> {
+        match self.next {
+            0 => self.values.last(),
+            next => self.values.get(next - 1),
+        }
+    }
+
+    /// Removes all values
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.next = 0;
+    }
+}
+
+impl<T: Copy> RingBuffer<T> {
+    /// Appends a value like [`push`](RingBuffer::push), telling `hook` about the value
+    /// entering and then the value evicted, if any
+    ///
+    /// # Example
+    /// ```rust
+    /// use ta_rust::common::window::{RingBuffer, RunningSum};
+    ///
+    /// let mut window = RingBuffer::new(2);
+    /// let mut sum = RunningSum::new();
+    /// for value in [1.0, 2.0, 3.0] {
+    ///     window.push_with(value, &mut sum);
+    /// }
+    /// assert_eq!(sum.value(), 5.0);
+    /// ```
+    pub fn push_with<H: WindowHook<T>>(&mut self, value: T, hook: &mut H) -> Option<T> {
+        hook.pushed(value);
+        let evicted = self.push(value);
+        if let Some(evicted) = evicted {
+            hook.evicted(evicted);
+        }
+        evicted
+    }
+}
+
+/// A statistic kept up to date as values enter and leave a window
+pub trait WindowHook<T> {
+    /// A value entered the window
+    fn pushed(&mut self, value: T);
+    /// A value left the window
+    fn evicted(&mut self, value: T);
+}
+
+/// Compensated sum of a window
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunningSum<T: Float = f64> {
+    sum: NeumaierSum<T>,
+}
+
+impl<T: Float> RunningSum<T> {
+    /// Creates an empty sum
+    pub fn new() -> Self {
+        Self { sum: NeumaierSum::new() }
+    }
+
+    /// Sum of the values in the window
+    pub fn value(&self) -> T {
+        self.sum.value()
+    }
+}
+
+impl<T: Float> WindowHook<T> for RunningSum<T> {
+    fn pushed(&mut self, value: T) {
+        self.sum.add(value);
+    }
+
+    fn evicted(&mut self, value: T) {
+        self.sum.sub(value);
+    }
+}
+
+/// Candidates for the extreme of the last `period` values, oldest first
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Candidates<T> {
+    period: usize,
+    /// Number of values pushed
+    count: usize,
+    /// `(position, value)` of values no later value beats; the front is the extreme
+    values: VecDeque<(usize, T)>,
+}
+
+impl<T: PartialOrd + Copy> Candidates<T> {
+    fn new(period: usize) -> Self {
+        Self { period, count: 0, values: VecDeque::with_capacity(period) }
+    }
+
+    fn push(&mut self, value: T, beats: impl Fn(&T, &T) -> bool) -> Option<T> {
+        let position = self.count;
+        self.count += 1;
+        // Unordered values, NaN for floats, are skipped
+        if value.partial_cmp(&value).is_some() {
+            while self.values.back().is_some_and(|(_, older)| beats(&value, older)) {
+                self.values.pop_back();
+            }
+            self.values.push_back((position, value));
+        }
+        while self.values.front().is_some_and(|&(p, _)| p + self.period <= position) {
+            self.values.pop_front();
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<T> {
+        self.values.front().map(|&(_, value)| value)
+    }
+
+    fn clear(&mut self) {
+        self.count = 0;
+        self.values.clear();
+    }
+}
+
+/// Highest of the last `period` values, in O(1) amortized time per value
+///
+/// NaN values are skipped. As a [`WindowHook`] it only needs to see the pushed values,
+/// since it expires them by position; the window it tracks should have a capacity of
+/// `period`.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::window::RunningMax;
+///
+/// let mut max = RunningMax::new(3);
+/// let highest: Vec<_> = [1.0, 3.0, 2.0, 1.0, 0.5].into_iter().map(|v| max.push(v)).collect();
+/// assert_eq!(highest, [Some(1.0), Some(3.0), Some(3.0), Some(3.0), Some(2.0)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunningMax<T = f64> {
+    candidates: Candidates<T>,
+}
+
+impl<T: PartialOrd + Copy> RunningMax<T> {
+    /// Creates an empty maximum over `period` values
+    pub fn new(period: usize) -> Self {
+        Self { candidates: Candidates::new(period) }
+    }
+
+    /// Feeds the next value and returns the highest of the last `period`, or `None` if
+    /// they are all NaN
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.candidates.push(value, |newer, older| newer >= older)
+    }
+
+    /// Highest of the last `period` values
+    pub fn value(&self) -> Option<T> {
+        self.candidates.value()
+    }
+
+    /// Removes all values
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+    }
+}
+
+impl<T: PartialOrd + Copy> WindowHook<T> for RunningMax<T> {
+    fn pushed(&mut self, value: T) {
+        self.push(value);
+    }
+
+    fn evicted(&mut self, _value: T) {}
+}
+
+/// Lowest of the last `period` values, in O(1) amortized time per value
+///
+/// NaN values are skipped. As a [`WindowHook`] it only needs to see the pushed values,
+/// since it expires them by position; the window it tracks should have a capacity of
+/// `period`.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::window::RunningMin;
+///
+/// let mut min = RunningMin::new(2);
+/// let lowest: Vec<_> = [3.0, f64::NAN, 2.0, 4.0].into_iter().map(|v| min.push(v)).collect();
+/// assert_eq!(lowest, [Some(3.0), Some(3.0), Some(2.0), Some(2.0)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunningMin<T = f64> {
+    candidates: Candidates<T>,
+}
+
+impl<T: PartialOrd + Copy> RunningMin<T> {
+    /// Creates an empty minimum over `period` values
+    pub fn new(period: usize) -> Self {
+        Self { candidates: Candidates::new(period) }
+    }
+
+    /// Feeds the next value and returns the lowest of the last `period`, or `None` if
+    /// they are all NaN
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.candidates.push(value, |newer, older| newer <= older)
+    }
+
+    /// Lowest of the last `period` values
+    pub fn value(&self) -> Option<T> {
+        self.candidates.value()
+    }
+
+    /// Removes all values
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+    }
+}
+
+impl<T: PartialOrd + Copy> WindowHook<T> for RunningMin<T> {
+    fn pushed(&mut self, value: T) {
+        self.push(value);
+    }
+
+    fn evicted(&mut self, _value: T) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut window = RingBuffer::new(3);
+        assert!(window.is_empty());
+        assert_eq!((window.oldest(), window.newest()), (None, None));
+        for value in 1..=2 {
+            assert_eq!(window.push(value), None);
+        }
+        assert_eq!((window.oldest(), window.newest()), (Some(&1), Some(&2)));
+        for value in 3..=7 {
+            window.push(value);
+            assert_eq!(window.iter().copied().collect::<Vec<_>>(), ((value - 2).max(1)..=value).collect::<Vec<_>>());
+            assert_eq!((window.oldest(), window.newest()), (Some(&(value - 2).max(1)), Some(&value)));
+        }
+        assert_eq!(window.iter().next_back(), Some(&7));
+        assert!(window.is_full());
+        window.clear();
+        assert_eq!(window.len(), 0);
+        assert_eq!(window.push(8), None);
+        assert_eq!(RingBuffer::new(0).push(1), Some(1));
+    }
+
+    #[test]
+    fn test_hooks_match_window_scan() {
+        let data: Vec<f64> = (0..200)
+            .map(|i| if i % 17 == 5 { f64::NAN } else { ((i * 29) % 13) as f64 - (i as f64 * 0.2).cos() })
+            .collect();
+        for period in [1, 3, 8] {
+            let mut window = RingBuffer::new(period);
+            let (mut max, mut min) = (RunningMax::new(period), RunningMin::new(period));
+            let mut sum = RunningSum::new();
+            let mut sums = RingBuffer::new(period);
+            for (i, &value) in data.iter().enumerate() {
+                window.push_with(value, &mut max);
+                min.push(value);
+                if !value.is_nan() {
+                    sums.push_with(value, &mut sum);
+                }
+
+                let recent = &data[(i + 1).saturating_sub(period)..=i];
+                let finite = recent.iter().copied().filter(|v| !v.is_nan());
+                assert_eq!(max.value(), finite.clone().reduce(f64::max));
+                assert_eq!(min.value(), finite.reduce(f64::min));
+                let expected: f64 = sums.iter().sum();
+                assert!((sum.value() - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
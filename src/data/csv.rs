@@ -0,0 +1,183 @@
+//! CSV reader
+//!
+//! The first line is the header. Fields may be quoted with `"`, with `""` for a literal
+//! quote; quoted fields cannot span lines. Blank lines are skipped and empty price fields
+//! are read as NaN.
+
+use std::path::Path;
+
+use crate::common::{OhlcvSeries, TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::data::{into_series, parse_timestamp, OhlcvSchema, Row};
+
+/// Reads bars from a CSV file
+///
+/// # Arguments
+/// * `path` - CSV file
+/// * `schema` - Column names, timestamp format and delimiter
+///
+/// # Returns
+/// * `Ok(OhlcvSeries)` - Bars sorted by timestamp
+/// * `Err(TAError)` - Error if the file cannot be read or parsed
+pub fn read_ohlcv_csv<P: AsRef<Path>>(path: P, schema: &OhlcvSchema) -> TAResult<OhlcvSeries> {
+    with_function("READ_OHLCV_CSV", || {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| TAError::invalid_input(format!("Cannot read '{}': {}", path.display(), e)))?;
+        parse_ohlcv_csv(&text, schema)
+    })
+}
+
+/// Parses bars from CSV text
+///
+/// # Arguments
+/// * `text` - CSV content, starting with the header line
+/// * `schema` - Column names, timestamp format and delimiter
+///
+/// # Returns
+/// * `Ok(OhlcvSeries)` - Bars sorted by timestamp
+/// * `Err(TAError)` - Error if a column is missing or a field cannot be parsed
+///
+/// # Examples
+/// ```
+/// use ta_rust::data::{parse_ohlcv_csv, OhlcvSchema};
+///
+/// let text = "time;o;h;l;c\n60;1.5;2;1;1.8\n0;1;1.6;0.9;1.5\n";
+/// let schema = OhlcvSchema {
+///     timestamp: "time".into(),
+///     open: "o".into(),
+///     high: "h".into(),
+///     low: "l".into(),
+///     close: "c".into(),
+///     volume: None,
+///     delimiter: ';',
+///     ..Default::default()
+/// };
+/// let series = parse_ohlcv_csv(text, &schema).unwrap();
+/// assert_eq!(series.timestamp, vec![0, 60]);
+/// assert!(series.volume[0].is_nan());
+/// ```
+pub fn parse_ohlcv_csv(text: &str, schema: &OhlcvSchema) -> TAResult<OhlcvSeries> {
+    with_function("PARSE_OHLCV_CSV", || {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim_start_matches('\u{feff}')))
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let (_, header) = lines.next().ok_or_else(|| TAError::invalid_input("CSV is empty"))?;
+        let header = split_fields(header, schema.delimiter, 1)?;
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(name))
+                .ok_or_else(|| TAError::invalid_input(format!("Column '{}' not found", name)))
+        };
+
+        let timestamp = column(&schema.timestamp)?;
+        let prices = [column(&schema.open)?, column(&schema.high)?, column(&schema.low)?, column(&schema.close)?];
+        let volume = schema.volume.as_deref().map(column).transpose()?;
+
+        let mut rows: Vec<Row> = Vec::new();
+        for (line_number, line) in lines {
+            let fields = split_fields(line, schema.delimiter, line_number)?;
+            let field = |index: usize| {
+                fields.get(index).map(String::as_str).ok_or_else(|| {
+                    TAError::invalid_input(format!("Line {}: expected {} fields, got {}", line_number, header.len(), fields.len()))
+                })
+            };
+            let value = |index: usize| -> TAResult<f64> {
+                let text = field(index)?.trim();
+                if text.is_empty() {
+                    return Ok(f64::NAN);
+                }
+                text.parse()
+                    .map_err(|_| TAError::invalid_input(format!("Line {}: cannot parse number '{}'", line_number, text)))
+            };
+
+            let time = parse_timestamp(field(timestamp)?, schema.timestamp_format)
+                .map_err(|e| TAError::invalid_input(format!("Line {}: {}", line_number, e)))?;
+            let volume = volume.map_or(Ok(f64::NAN), value)?;
+            rows.push((time, [value(prices[0])?, value(prices[1])?, value(prices[2])?, value(prices[3])?, volume]));
+        }
+        into_series(rows)
+    })
+}
+
+/// Splits a line into fields, unquoting quoted ones
+fn split_fields(line: &str, delimiter: char, line_number: usize) -> TAResult<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => fields.push(core::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(TAError::invalid_input(format!("Line {}: unterminated quote", line_number)));
+    }
+    fields.push(field.trim().to_string());
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\u{feff}Timestamp,Open,High,Low,Close,Volume\r\n\
+                        2024-01-02T09:31:00Z,100.5,101,100,100.8,1200\r\n\
+                        \r\n\
+                        2024-01-02T09:30:00Z,100,100.7,99.8,100.5,1500\r\n";
+
+    #[test]
+    fn test_parse_csv() {
+        let series = parse_ohlcv_csv(TEXT, &OhlcvSchema::default()).unwrap();
+        assert_eq!(series.timestamp, vec![1_704_187_800, 1_704_187_860]);
+        assert_eq!(series.open, vec![100.0, 100.5]);
+        assert_eq!(series.high, vec![100.7, 101.0]);
+        assert_eq!(series.low, vec![99.8, 100.0]);
+        assert_eq!(series.close, vec![100.5, 100.8]);
+        assert_eq!(series.volume, vec![1500.0, 1200.0]);
+    }
+
+    #[test]
+    fn test_split_fields_quotes() {
+        assert_eq!(split_fields(r#"a, "b,c" ,"say ""hi""","#, ',', 1).unwrap(), vec!["a", "b,c", r#"say "hi""#, ""]);
+        assert!(split_fields(r#"a,"b"#, ',', 1).is_err());
+    }
+
+    #[test]
+    fn test_read_csv_file() {
+        let path = std::env::temp_dir().join(format!("ta_rust_csv_{}.csv", std::process::id()));
+        std::fs::write(&path, TEXT).unwrap();
+        let series = read_ohlcv_csv(&path, &OhlcvSchema::default());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(series.unwrap().len(), 2);
+        assert!(read_ohlcv_csv(&path, &OhlcvSchema::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_errors() {
+        let schema = OhlcvSchema::default();
+        assert_eq!(parse_ohlcv_csv("", &schema).unwrap_err().function(), Some("PARSE_OHLCV_CSV"));
+        assert!(parse_ohlcv_csv("timestamp,open,high,low,close\n0,1,1,1,1\n", &schema).is_err());
+        assert!(parse_ohlcv_csv("timestamp,open,high,low,close,volume\n0,1,1,1,x,1\n", &schema).is_err());
+        assert!(parse_ohlcv_csv("timestamp,open,high,low,close,volume\n0,1,1,1\n", &schema).is_err());
+        assert!(parse_ohlcv_csv("timestamp,open,high,low,close,volume\nnow,1,1,1,1,1\n", &schema).is_err());
+        let err = read_ohlcv_csv("no/such/file.csv", &schema).unwrap_err();
+        assert_eq!(err.function(), Some("READ_OHLCV_CSV"));
+    }
+}
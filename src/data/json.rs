@@ -0,0 +1,186 @@
+//! JSON reader
+//!
+//! Accepts either an array of bar objects, `[{"timestamp": 0, "open": 1.0, ...}, ...]`, or
+//! an object of columns, `{"timestamp": [0, ...], "open": [1.0, ...], ...}`. Timestamps
+//! may be numbers or strings, prices numbers or numeric strings; `null` prices are NaN.
+
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::common::{OhlcvSeries, TAError, TAResult, Timestamp};
+use crate::common::errors::with_function;
+use crate::data::{into_series, parse_timestamp, OhlcvSchema, Row};
+
+/// Reads bars from a JSON file
+///
+/// # Arguments
+/// * `path` - JSON file
+/// * `schema` - Field names and timestamp format
+///
+/// # Returns
+/// * `Ok(OhlcvSeries)` - Bars sorted by timestamp
+/// * `Err(TAError)` - Error if the file cannot be read or parsed
+pub fn read_ohlcv_json<P: AsRef<Path>>(path: P, schema: &OhlcvSchema) -> TAResult<OhlcvSeries> {
+    with_function("READ_OHLCV_JSON", || {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| TAError::invalid_input(format!("Cannot read '{}': {}", path.display(), e)))?;
+        parse_ohlcv_json(&text, schema)
+    })
+}
+
+/// Parses bars from JSON text
+///
+/// # Arguments
+/// * `text` - JSON content, row- or column-oriented
+/// * `schema` - Field names and timestamp format
+///
+/// # Returns
+/// * `Ok(OhlcvSeries)` - Bars sorted by timestamp
+/// * `Err(TAError)` - Error if a field is missing or cannot be parsed
+///
+/// # Examples
+/// ```
+/// use ta_rust::data::{parse_ohlcv_json, OhlcvSchema};
+///
+/// let rows = r#"[{"t": "2024-01-02", "open": 10, "high": 11, "low": 9, "close": 10.5, "volume": 100}]"#;
+/// let columns = r#"{"t": ["2024-01-02"], "open": [10], "high": [11], "low": [9], "close": [10.5], "volume": [100]}"#;
+///
+/// let schema = OhlcvSchema { timestamp: "t".into(), ..Default::default() };
+/// assert_eq!(parse_ohlcv_json(rows, &schema).unwrap(), parse_ohlcv_json(columns, &schema).unwrap());
+/// ```
+pub fn parse_ohlcv_json(text: &str, schema: &OhlcvSchema) -> TAResult<OhlcvSeries> {
+    with_function("PARSE_OHLCV_JSON", || {
+        let value: Value =
+            serde_json::from_str(text).map_err(|e| TAError::invalid_input(format!("Invalid JSON: {}", e)))?;
+
+        let rows = match value {
+            Value::Array(records) => records
+                .iter()
+                .enumerate()
+                .map(|(i, record)| {
+                    let record = record
+                        .as_object()
+                        .ok_or_else(|| TAError::invalid_input(format!("Bar {}: expected an object", i)))?;
+                    read_row(schema, |name| field(record, name).ok_or_else(|| missing(name)))
+                        .map_err(|e| TAError::invalid_input(format!("Bar {}: {}", i, e)))
+                })
+                .collect::<TAResult<Vec<Row>>>()?,
+            Value::Object(columns) => {
+                let column = |name: &str| -> TAResult<&Vec<Value>> {
+                    field(&columns, name)
+                        .ok_or_else(|| missing(name))?
+                        .as_array()
+                        .ok_or_else(|| TAError::invalid_input(format!("Field '{}' is not an array", name)))
+                };
+                let len = column(&schema.timestamp)?.len();
+                (0..len)
+                    .map(|i| {
+                        read_row(schema, |name| {
+                            column(name)?.get(i).ok_or_else(|| {
+                                TAError::mismatched_inputs(format!("Field '{}' has fewer than {} values", name, len))
+                            })
+                        })
+                        .map_err(|e| TAError::invalid_input(format!("Bar {}: {}", i, e)))
+                    })
+                    .collect::<TAResult<Vec<Row>>>()?
+            }
+            _ => return Err(TAError::invalid_input("Expected an array of bars or an object of columns")),
+        };
+        into_series(rows)
+    })
+}
+
+/// Reads one bar, looking fields up by name
+fn read_row<'a>(schema: &OhlcvSchema, get: impl Fn(&str) -> TAResult<&'a Value>) -> TAResult<Row> {
+    let timestamp = timestamp(get(&schema.timestamp)?, schema)?;
+    let volume = match &schema.volume {
+        Some(name) => number(get(name)?)?,
+        None => f64::NAN,
+    };
+    Ok((
+        timestamp,
+        [number(get(&schema.open)?)?, number(get(&schema.high)?)?, number(get(&schema.low)?)?, number(get(&schema.close)?)?, volume],
+    ))
+}
+
+/// Case-insensitive field lookup
+fn field<'a>(object: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    object.get(name).or_else(|| object.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, v)| v))
+}
+
+fn missing(name: &str) -> TAError {
+    TAError::invalid_input(format!("Field '{}' not found", name))
+}
+
+fn timestamp(value: &Value, schema: &OhlcvSchema) -> TAResult<Timestamp> {
+    match value {
+        Value::Number(n) => n.as_i64().ok_or_else(|| TAError::invalid_input(format!("Timestamp {} is not an integer", n))),
+        Value::String(s) => parse_timestamp(s, schema.timestamp_format),
+        _ => Err(TAError::invalid_input(format!("Cannot parse timestamp {}", value))),
+    }
+}
+
+fn number(value: &Value) -> TAResult<f64> {
+    let parsed = match value {
+        Value::Null => Some(f64::NAN),
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    parsed.ok_or_else(|| TAError::invalid_input(format!("Cannot parse number {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_rows() {
+        let text = r#"[
+            {"Timestamp": 60, "Open": "1.5", "High": 2, "Low": 1, "Close": 1.8, "Volume": 20},
+            {"Timestamp": 0, "Open": 1, "High": 1.6, "Low": 0.9, "Close": null, "Volume": 10}
+        ]"#;
+        let series = parse_ohlcv_json(text, &OhlcvSchema::default()).unwrap();
+        assert_eq!(series.timestamp, vec![0, 60]);
+        assert_eq!(series.open, vec![1.0, 1.5]);
+        assert!(series.close[0].is_nan());
+        assert_eq!(series.volume, vec![10.0, 20.0]);
+    }
+
+    #[test]
+    fn test_parse_json_columns() {
+        let text = r#"{"date": ["2024-01-01", "2024-01-02"], "open": [1, 2], "high": [1, 2], "low": [1, 2], "close": [1, 2]}"#;
+        let schema = OhlcvSchema { timestamp: "date".into(), volume: None, ..Default::default() };
+        let series = parse_ohlcv_json(text, &schema).unwrap();
+        assert_eq!(series.timestamp, vec![1_704_067_200, 1_704_153_600]);
+        assert!(series.volume.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_read_json_file() {
+        let path = std::env::temp_dir().join(format!("ta_rust_json_{}.json", std::process::id()));
+        std::fs::write(&path, r#"[{"timestamp": 0, "open": 1, "high": 1, "low": 1, "close": 1, "volume": 1}]"#).unwrap();
+        let series = read_ohlcv_json(&path, &OhlcvSchema::default());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(series.unwrap().len(), 1);
+        assert!(read_ohlcv_json(&path, &OhlcvSchema::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_errors() {
+        let schema = OhlcvSchema::default();
+        assert_eq!(parse_ohlcv_json("{", &schema).unwrap_err().function(), Some("PARSE_OHLCV_JSON"));
+        assert_eq!(read_ohlcv_json("no/such/file.json", &schema).unwrap_err().function(), Some("READ_OHLCV_JSON"));
+        assert!(parse_ohlcv_json("42", &schema).is_err());
+        assert!(parse_ohlcv_json("[]", &schema).is_err());
+        assert!(parse_ohlcv_json(r#"[{"timestamp": 0, "open": 1}]"#, &schema).is_err());
+        assert!(parse_ohlcv_json(r#"[{"timestamp": 0.5, "open": 1, "high": 1, "low": 1, "close": 1, "volume": 1}]"#, &schema).is_err());
+        assert!(parse_ohlcv_json(
+            r#"{"timestamp": [0, 1], "open": [1], "high": [1, 1], "low": [1, 1], "close": [1, 1], "volume": [1, 1]}"#,
+            &schema
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Loading bar data from CSV and JSON
+//!
+//! Readers produce an [`OhlcvSeries`] from files with arbitrary column names, described
+//! by an [`OhlcvSchema`]. Rows are sorted by timestamp, so files listed newest first load
+//! the same as those listed oldest first.
+//!
+//! # Example
+//! ```
+//! use ta_rust::data::{parse_ohlcv_csv, OhlcvSchema};
+//!
+//! let text = "Date,Open,High,Low,Close,Volume\n\
+//!             2024-01-03,11,12,10,11.5,900\n\
+//!             2024-01-02,10,11,9,10.5,1000\n";
+//! let series = parse_ohlcv_csv(text, &OhlcvSchema { timestamp: "date".into(), ..Default::default() }).unwrap();
+//! assert_eq!(series.timestamp, vec![1_704_153_600, 1_704_240_000]);
+//! assert_eq!(series.close, vec![10.5, 11.5]);
+//! ```
+
+pub mod csv;
+pub mod json;
+
+pub use self::csv::{parse_ohlcv_csv, read_ohlcv_csv};
+pub use self::json::{parse_ohlcv_json, read_ohlcv_json};
+
+use crate::common::{OhlcvSeries, TAError, TAResult, Timestamp};
+
+/// How timestamps are written in the source data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimestampFormat {
+    /// Integers are taken as they are, anything else is parsed as [`TimestampFormat::Iso8601`]
+    #[default]
+    Auto,
+    /// Integers in any unit (Unix seconds, milliseconds, ...), taken as they are
+    Integer,
+    /// Dates and times such as `2024-01-02`, `2024-01-02 09:30`, `2024-01-02T09:30:00.250Z`
+    /// or `2024-01-02T09:30:00+01:00`, converted to Unix seconds. Fractions of a second are
+    /// dropped and times without an offset are read as UTC.
+    Iso8601,
+}
+
+/// Column mapping for the readers
+///
+/// Column names are matched case-insensitively. Construct with struct update syntax over
+/// the defaults, e.g. `OhlcvSchema { timestamp: "time".into(), ..Default::default() }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OhlcvSchema {
+    /// Timestamp column (default `timestamp`)
+    pub timestamp: String,
+    /// Open column (default `open`)
+    pub open: String,
+    /// High column (default `high`)
+    pub high: String,
+    /// Low column (default `low`)
+    pub low: String,
+    /// Close column (default `close`)
+    pub close: String,
+    /// Volume column (default `volume`); `None` for data without volume, which is then NaN
+    pub volume: Option<String>,
+    /// Timestamp encoding (default [`TimestampFormat::Auto`])
+    pub timestamp_format: TimestampFormat,
+    /// CSV field delimiter (default `,`)
+    pub delimiter: char,
+}
+
+impl Default for OhlcvSchema {
+    fn default() -> Self {
+        Self {
+            timestamp: "timestamp".into(),
+            open: "open".into(),
+            high: "high".into(),
+            low: "low".into(),
+            close: "close".into(),
+            volume: Some("volume".into()),
+            timestamp_format: TimestampFormat::Auto,
+            delimiter: ',',
+        }
+    }
+}
+
+/// One bar as read from a file, before sorting
+type Row = (Timestamp, [f64; 5]);
+
+/// Sorts rows by timestamp and assembles the series
+fn into_series(mut rows: Vec<Row>) -> TAResult<OhlcvSeries> {
+    if rows.is_empty() {
+        return Err(TAError::invalid_input("No bars found"));
+    }
+
+    rows.sort_by_key(|&(timestamp, _)| timestamp);
+    let mut series = OhlcvSeries {
+        timestamp: Vec::with_capacity(rows.len()),
+        open: Vec::with_capacity(rows.len()),
+        high: Vec::with_capacity(rows.len()),
+        low: Vec::with_capacity(rows.len()),
+        close: Vec::with_capacity(rows.len()),
+        volume: Vec::with_capacity(rows.len()),
+    };
+    for (timestamp, [open, high, low, close, volume]) in rows {
+        series.timestamp.push(timestamp);
+        series.open.push(open);
+        series.high.push(high);
+        series.low.push(low);
+        series.close.push(close);
+        series.volume.push(volume);
+    }
+    series.validate()?;
+    Ok(series)
+}
+
+/// Parses a timestamp written as text
+fn parse_timestamp(text: &str, format: TimestampFormat) -> TAResult<Timestamp> {
+    let text = text.trim();
+    let integer = text.parse::<Timestamp>();
+    let parsed = match format {
+        TimestampFormat::Integer => integer.ok(),
+        TimestampFormat::Iso8601 => parse_iso8601(text),
+        TimestampFormat::Auto => integer.ok().or_else(|| parse_iso8601(text)),
+    };
+    parsed.ok_or_else(|| TAError::invalid_input(format!("Cannot parse timestamp '{}'", text)))
+}
+
+/// Unix seconds of an ISO 8601 date or date-time
+fn parse_iso8601(text: &str) -> Option<Timestamp> {
+    let number = |s: &str| -> Option<i64> {
+        (!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())).then(|| s.parse().ok()).flatten()
+    };
+
+    let (date, time) = match text.find(['T', 't', ' ']) {
+        Some(i) => (&text[..i], text[i + 1..].trim_start()),
+        None => (text, ""),
+    };
+
+    let mut parts = date.splitn(3, '-');
+    let year = number(parts.next()?)?;
+    let month = number(parts.next()?)?;
+    let day = number(parts.next()?)?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    if time.is_empty() {
+        return Some(seconds);
+    }
+
+    // Split off the UTC offset, if any
+    let (clock, offset) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0)
+    } else if let Some(i) = time.rfind(['+', '-']) {
+        let (sign, zone) = (if &time[i..=i] == "-" { -1 } else { 1 }, &time[i + 1..]);
+        let (hours, minutes) = zone.split_once(':').unwrap_or((zone.get(..2)?, zone.get(2..)?));
+        (&time[..i], sign * (number(hours)? * 3_600 + number(minutes)? * 60))
+    } else {
+        (time, 0)
+    };
+
+    let clock = clock.split('.').next()?;
+    let mut fields = clock.split(':');
+    let hour = number(fields.next()?)?;
+    let minute = number(fields.next()?)?;
+    let second = fields.next().map_or(Some(0), number)?;
+    if fields.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    seconds += hour * 3_600 + minute * 60 + second - offset;
+    Some(seconds)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(parse_iso8601("1970-01-01"), Some(0));
+        assert_eq!(parse_iso8601("2000-03-01"), Some(951_868_800));
+        assert_eq!(parse_iso8601("2024-02-29T12:30:15Z"), Some(1_709_209_815));
+        assert_eq!(parse_iso8601("2024-02-29 12:30"), Some(1_709_209_800));
+        assert_eq!(parse_iso8601("2024-02-29T13:30:15.750+01:00"), Some(1_709_209_815));
+        assert_eq!(parse_iso8601("2024-02-29T07:30:15-0500"), Some(1_709_209_815));
+        assert_eq!(parse_iso8601("1969-12-31T23:59:59Z"), Some(-1));
+
+        assert_eq!(parse_iso8601("2023-02-29"), None);
+        assert_eq!(parse_iso8601("2024-13-01"), None);
+        assert_eq!(parse_iso8601("2024-01-01T25:00"), None);
+        assert_eq!(parse_iso8601("yesterday"), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        assert_eq!(parse_timestamp(" 1700000000 ", TimestampFormat::Auto).unwrap(), 1_700_000_000);
+        assert_eq!(parse_timestamp("1970-01-02", TimestampFormat::Auto).unwrap(), 86_400);
+        assert!(parse_timestamp("1970-01-02", TimestampFormat::Integer).is_err());
+        assert!(parse_timestamp("1700000000", TimestampFormat::Iso8601).is_err());
+    }
+
+    #[test]
+    fn test_into_series_sorts_rows() {
+        let rows = vec![(120, [3.0; 5]), (0, [1.0; 5]), (60, [2.0; 5])];
+        let series = into_series(rows).unwrap();
+        assert_eq!(series.timestamp, vec![0, 60, 120]);
+        assert_eq!(series.close, vec![1.0, 2.0, 3.0]);
+
+        assert!(into_series(vec![(0, [1.0; 5]), (0, [2.0; 5])]).is_err());
+        assert!(into_series(Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+//! Triple-barrier labeling
+//!
+//! López de Prado's labeling method (*Advances in Financial Machine Learning*, ch. 3): a
+//! position opened at each bar is closed by the first of three barriers it touches, a
+//! take-profit above, a stop-loss below and a vertical barrier `max_horizon` bars later.
+//! The horizontal barriers are set in units of a volatility estimate at the entry bar, so
+//! they widen in turbulent markets. [`triple_barrier`] labels the side of the move;
+//! [`meta_labels`] labels whether the bets of a primary model were right, for a secondary
+//! model that learns when to act on them.
+
+use crate::common::{validate_not_empty, validate_same_length, IntoParam, Period, TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// The barrier that closed a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Barrier {
+    /// The position gained `pt_mult` times the volatility
+    TakeProfit,
+    /// The position lost `sl_mult` times the volatility
+    StopLoss,
+    /// `max_horizon` bars passed without touching either
+    Horizon,
+}
+
+/// A labeled entry bar and how its position ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarrierEvent {
+    /// Entry bar
+    pub bar: usize,
+    /// Bar of the first barrier touched, the event end time
+    pub end: usize,
+    /// Barrier touched first
+    pub barrier: Barrier,
+    /// Return of the position from `bar` to `end`, as a fraction
+    pub ret: f64,
+    /// 1, -1 or 0 from [`triple_barrier`]; 1 or 0 from [`meta_labels`]
+    pub label: f64,
+}
+
+/// Labels every bar with the first of three barriers its price path touches
+///
+/// For a long position entered at bar `t` at `prices[t]`, the take-profit barrier is a
+/// return of `pt_mult × vol[t]`, the stop-loss a return of `-sl_mult × vol[t]`, and the
+/// vertical barrier bar `t + max_horizon`. The label is 1 for the take-profit, -1 for the
+/// stop-loss and 0 for the vertical barrier. A multiplier of 0 removes that barrier.
+///
+/// `vol_series` is a volatility of returns at each bar, such as the rolling standard
+/// deviation of [`simple_returns`](crate::performance::simple_returns), or ATR divided by
+/// the close. Bars where it or the price is NaN or not positive, such as its warm-up,
+/// are not labeled; nor are bars whose outcome is unknown, touching no barrier before the
+/// series ends, or with a NaN price before the first touch.
+///
+/// # Arguments
+/// * `prices` - Prices, usually closes
+/// * `pt_mult` - Take-profit width in units of volatility, 0 for none
+/// * `sl_mult` - Stop-loss width in units of volatility, 0 for none
+/// * `max_horizon` - Number of bars after entry at which the vertical barrier lies
+/// * `vol_series` - Volatility of returns at each bar, as long as `prices`
+///
+/// # Returns
+/// * `Ok(Vec<BarrierEvent>)` - One event per labeled bar, in bar order
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::features::{triple_barrier, Barrier};
+///
+/// let prices = vec![100.0, 101.0, 103.0, 102.0, 99.0, 98.0, 100.0];
+/// let vol = vec![0.01; prices.len()];
+/// let events = triple_barrier(&prices, 2.0, 2.0, 3, &vol).unwrap();
+///
+/// // From 100, +2% is reached at bar 2
+/// assert_eq!((events[0].end, events[0].barrier, events[0].label), (2, Barrier::TakeProfit, 1.0));
+/// // From 103, -2% is reached at bar 4
+/// assert_eq!((events[2].end, events[2].barrier, events[2].label), (4, Barrier::StopLoss, -1.0));
+/// ```
+pub fn triple_barrier(
+    prices: &[f64],
+    pt_mult: f64,
+    sl_mult: f64,
+    max_horizon: impl IntoParam<Period>,
+    vol_series: &[f64],
+) -> TAResult<Vec<BarrierEvent>> {
+    with_function("TRIPLE_BARRIER", || {
+        let max_horizon = max_horizon.into_param("max_horizon")?.get();
+        validate(prices, pt_mult, sl_mult, vol_series)?;
+
+        Ok((0..prices.len())
+            .filter_map(|bar| {
+                let (end, barrier, ret) = first_touch(prices, bar, 1.0, pt_mult, sl_mult, max_horizon, vol_series[bar])?;
+                let label = match barrier {
+                    Barrier::TakeProfit => 1.0,
+                    Barrier::StopLoss => -1.0,
+                    Barrier::Horizon => 0.0,
+                };
+                Some(BarrierEvent { bar, end, barrier, ret, label })
+            })
+            .collect())
+    })
+}
+
+/// Meta-labels the bets of a primary model with the triple-barrier method
+///
+/// `side` is the position the primary model takes at each bar: positive for long,
+/// negative for short, 0 or NaN for none. Each bet is closed as in [`triple_barrier`],
+/// with the barriers taken on the return of the position, so a short takes profit when
+/// the price falls. The label is 1 when the bet made money and 0 otherwise, and `ret` is
+/// the return of the position. Bars without a bet are not labeled.
+///
+/// # Arguments
+/// * `prices` - Prices, usually closes
+/// * `side` - Position of the primary model at each bar, as long as `prices`
+/// * `pt_mult`, `sl_mult`, `max_horizon`, `vol_series` - As for [`triple_barrier`]
+///
+/// # Returns
+/// * `Ok(Vec<BarrierEvent>)` - One event per labeled bet, in bar order
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::features::meta_labels;
+///
+/// let prices = vec![100.0, 99.0, 97.0, 98.0, 101.0];
+/// let side = vec![-1.0, 0.0, 1.0, 0.0, 0.0];
+/// let vol = vec![0.01; prices.len()];
+/// let events = meta_labels(&prices, &side, 2.0, 2.0, 2, &vol).unwrap();
+///
+/// // The short from 100 takes profit at 97; the long from 97 ends up after two bars
+/// assert_eq!(events.iter().map(|e| (e.bar, e.label)).collect::<Vec<_>>(), vec![(0, 1.0), (2, 1.0)]);
+/// assert!((events[0].ret - 0.03).abs() < 1e-12);
+/// ```
+pub fn meta_labels(
+    prices: &[f64],
+    side: &[f64],
+    pt_mult: f64,
+    sl_mult: f64,
+    max_horizon: impl IntoParam<Period>,
+    vol_series: &[f64],
+) -> TAResult<Vec<BarrierEvent>> {
+    with_function("META_LABELS", || {
+        let max_horizon = max_horizon.into_param("max_horizon")?.get();
+        validate(prices, pt_mult, sl_mult, vol_series)?;
+        validate_same_length(prices, side, "prices", "side")?;
+
+        Ok((0..prices.len())
+            .filter(|&bar| side[bar] != 0.0 && !side[bar].is_nan())
+            .filter_map(|bar| {
+                let direction = side[bar].signum();
+                let (end, barrier, ret) = first_touch(prices, bar, direction, pt_mult, sl_mult, max_horizon, vol_series[bar])?;
+                let label = if ret > 0.0 { 1.0 } else { 0.0 };
+                Some(BarrierEvent { bar, end, barrier, ret, label })
+            })
+            .collect())
+    })
+}
+
+fn validate(prices: &[f64], pt_mult: f64, sl_mult: f64, vol_series: &[f64]) -> TAResult<()> {
+    validate_not_empty(prices, "prices")?;
+    validate_same_length(prices, vol_series, "prices", "vol_series")?;
+    if !(pt_mult.is_finite() && pt_mult >= 0.0 && sl_mult.is_finite() && sl_mult >= 0.0) {
+        return Err(TAError::invalid_parameter("barriers", "must be non-negative and finite"));
+    }
+    Ok(())
+}
+
+/// First barrier touched by a position of `direction` (1 long, -1 short) entered at `bar`,
+/// with the bar and position return; `None` when the outcome is unknown
+fn first_touch(
+    prices: &[f64],
+    bar: usize,
+    direction: f64,
+    pt_mult: f64,
+    sl_mult: f64,
+    max_horizon: usize,
+    vol: f64,
+) -> Option<(usize, Barrier, f64)> {
+    let entry = prices[bar];
+    if !(entry > 0.0 && vol > 0.0) {
+        return None;
+    }
+    let (take_profit, stop_loss) = (pt_mult * vol, sl_mult * vol);
+    let ret = |end: usize| direction * (prices[end] / entry - 1.0);
+
+    let horizon = bar.saturating_add(max_horizon);
+    for end in bar + 1..=horizon.min(prices.len() - 1) {
+        let r = ret(end);
+        if r.is_nan() {
+            return None;
+        } else if pt_mult > 0.0 && r >= take_profit {
+            return Some((end, Barrier::TakeProfit, r));
+        } else if sl_mult > 0.0 && r <= -stop_loss {
+            return Some((end, Barrier::StopLoss, r));
+        }
+    }
+    (horizon < prices.len()).then(|| (horizon, Barrier::Horizon, ret(horizon)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_triple_barrier_matches_scan() {
+        let prices: Vec<f64> = (0..200).map(|i| 50.0 * (1.0 + 0.03 * (i as f64 * 0.21).sin() + 0.002 * (i % 7) as f64)).collect();
+        let mut vol: Vec<f64> = (0..200).map(|i| 0.005 + 0.001 * (i % 5) as f64).collect();
+        vol[..10].fill(f64::NAN);
+        let events = triple_barrier(&prices, 1.5, 1.0, 8, &vol).unwrap();
+
+        assert_eq!(events[0].bar, 10);
+        // Every bar with a full horizon is labeled; later ones only if a barrier was touched
+        assert!(events.iter().filter(|e| e.bar < 192).count() == 182);
+        for event in &events {
+            let path: Vec<f64> = (event.bar + 1..=event.end).map(|i| prices[i] / prices[event.bar] - 1.0).collect();
+            let (up, down) = (1.5 * vol[event.bar], 1.0 * vol[event.bar]);
+            let inside = |r: &f64| *r < up && *r > -down;
+            assert!(path[..path.len() - 1].iter().all(inside));
+            assert_float_eq!(event.ret, path[path.len() - 1], 1e-15);
+            match event.barrier {
+                Barrier::TakeProfit => assert!(event.ret >= up && event.label == 1.0),
+                Barrier::StopLoss => assert!(event.ret <= -down && event.label == -1.0),
+                Barrier::Horizon => assert!(inside(&event.ret) && event.end == event.bar + 8 && event.label == 0.0),
+            }
+        }
+        assert!([Barrier::TakeProfit, Barrier::StopLoss, Barrier::Horizon]
+            .iter()
+            .all(|b| events.iter().any(|e| e.barrier == *b)));
+
+        // Without horizontal barriers every label comes from the vertical one
+        let vertical = triple_barrier(&prices, 0.0, 0.0, 8, &vol).unwrap();
+        assert!(vertical.iter().all(|e| e.barrier == Barrier::Horizon && e.end == e.bar + 8));
+        assert_eq!(vertical.len(), 182);
+    }
+
+    #[test]
+    fn test_meta_labels_and_errors() {
+        let prices = vec![100.0, 98.0, 96.0, 97.0, 99.0, 102.0, f64::NAN, 101.0];
+        let vol = vec![0.01; prices.len()];
+        let side = vec![1.0, -2.0, 0.0, 1.0, f64::NAN, 1.0, 0.0, 0.0];
+        let events = meta_labels(&prices, &side, 1.5, 1.5, 3, &vol).unwrap();
+        // The long from 100 is stopped at 98, the short from 98 takes profit at 96, the
+        // long from 97 takes profit at 99; the long from 102 meets a NaN price
+        let summary: Vec<(usize, usize, Barrier, f64)> = events.iter().map(|e| (e.bar, e.end, e.barrier, e.label)).collect();
+        assert_eq!(
+            summary,
+            vec![(0, 1, Barrier::StopLoss, 0.0), (1, 2, Barrier::TakeProfit, 1.0), (3, 4, Barrier::TakeProfit, 1.0)]
+        );
+        assert_float_eq!(events[1].ret, 2.0 / 98.0, 1e-12);
+
+        assert!(triple_barrier(&prices, -1.0, 1.0, 3, &vol).is_err());
+        assert!(triple_barrier(&prices, 1.0, f64::NAN, 3, &vol).is_err());
+        assert!(triple_barrier(&prices, 1.0, 1.0, 0, &vol).is_err());
+        assert!(triple_barrier(&prices, 1.0, 1.0, 3, &vol[1..]).is_err());
+        assert!(meta_labels(&prices, &side[1..], 1.0, 1.0, 3, &vol).is_err());
+        assert!(triple_barrier(&[], 1.0, 1.0, 3, &[]).is_err());
+
+        // A horizon past the end of the series never reaches the vertical barrier
+        let open = triple_barrier(&prices, 10.0, 10.0, usize::MAX, &vol).unwrap();
+        assert!(open.is_empty());
+    }
+}
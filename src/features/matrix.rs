@@ -0,0 +1,287 @@
+//! Feature matrices
+//!
+//! Training code expects one row per sample and one column per feature, with no missing
+//! values. A [`FeatureBuilder`] computes a list of indicators with a
+//! [`ComputePlan`], adds lagged copies of every output if asked, and keeps the bars where
+//! every column has a value, so the warm-up of the slowest indicator (plus the largest lag)
+//! is trimmed from all features alike.
+
+use crate::batch::{ComputePlan, Indicator, PlanResult};
+use crate::common::{TAError, TAResult};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Builder of a [`FeatureMatrix`] from indicator specs
+///
+/// Columns follow the order in which indicators were added, each output of an indicator
+/// followed by its lagged copies. Columns are named by the output keys of the indicators
+/// (see [`Indicator::output_keys`]), with `[t-n]` appended for a lag of `n` bars.
+///
+/// # Examples
+/// ```
+/// use ta_rust::batch::Indicator;
+/// use ta_rust::features::FeatureBuilder;
+/// use ta_rust::momentum::rsi;
+///
+/// let close: Vec<f64> = (0..100).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+/// let matrix = FeatureBuilder::new()
+///     .add(Indicator::Rsi(14))
+///     .add(Indicator::Ema(20))
+///     .lags(&[1, 2])
+///     .build(&close)
+///     .unwrap();
+///
+/// assert_eq!(matrix.names()[..3], ["RSI(14)", "RSI(14)[t-1]", "RSI(14)[t-2]"]);
+/// assert_eq!(matrix.width(), 6);
+/// // EMA(20) starts at bar 19, its second lag at bar 21
+/// assert_eq!(matrix.index()[0], 21);
+/// assert_eq!(matrix.rows()[0][2], rsi(&close, 14).unwrap()[19]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureBuilder {
+    plan: ComputePlan,
+    lags: Vec<usize>,
+}
+
+impl FeatureBuilder {
+    /// Creates a builder without features
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the outputs of an indicator as features, ignoring duplicates
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, indicator: Indicator) -> Self {
+        self.plan = self.plan.add(indicator);
+        self
+    }
+
+    /// Adds a copy of every feature lagged by each of `lags` bars
+    ///
+    /// Lags of 0 and repeated lags are ignored.
+    pub fn lags(mut self, lags: &[usize]) -> Self {
+        for &lag in lags {
+            if lag > 0 && !self.lags.contains(&lag) {
+                self.lags.push(lag);
+            }
+        }
+        self.lags.sort_unstable();
+        self
+    }
+
+    /// Indicators of the builder, in the order they were added
+    pub fn indicators(&self) -> &[Indicator] {
+        self.plan.indicators()
+    }
+
+    /// Names of the columns of the matrix
+    pub fn names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for key in self.plan.indicators().iter().flat_map(Indicator::output_keys) {
+            let lagged: Vec<String> = self.lags.iter().map(|lag| format!("{}[t-{}]", key, lag)).collect();
+            names.push(key);
+            names.extend(lagged);
+        }
+        names
+    }
+
+    /// Builds the matrix from a series of closes
+    ///
+    /// # Errors
+    /// - `InvalidInput` if no indicator was added, or as [`ComputePlan::run`]
+    /// - Any error of the individual indicators
+    pub fn build(&self, close: &[f64]) -> TAResult<FeatureMatrix> {
+        self.check()?;
+        Ok(self.assemble(&self.plan.run(close)?, close.len()))
+    }
+
+    /// Builds the matrix from high, low and close prices
+    ///
+    /// # Errors
+    /// - `InvalidInput` if no indicator was added, or as [`ComputePlan::run_hlc`]
+    /// - Any error of the individual indicators
+    pub fn build_hlc(&self, high: &[f64], low: &[f64], close: &[f64]) -> TAResult<FeatureMatrix> {
+        self.check()?;
+        Ok(self.assemble(&self.plan.run_hlc(high, low, close)?, close.len()))
+    }
+
+    fn check(&self) -> TAResult<()> {
+        if self.plan.indicators().is_empty() {
+            return Err(TAError::invalid_input("No features were added"));
+        }
+        Ok(())
+    }
+
+    fn assemble(&self, result: &PlanResult, len: usize) -> FeatureMatrix {
+        let shifts: Vec<usize> = core::iter::once(0).chain(self.lags.iter().copied()).collect();
+        let columns: Vec<(&[f64], usize)> = self
+            .plan
+            .indicators()
+            .iter()
+            .flat_map(Indicator::output_keys)
+            .flat_map(|key| {
+                let values = &result[key.as_str()];
+                shifts.iter().map(move |&shift| (values, shift))
+            })
+            .collect();
+
+        let value = |bar: usize, &(values, shift): &(&[f64], usize)| values[bar - shift];
+        let first = self.lags.last().copied().unwrap_or(0);
+        let mut index = Vec::new();
+        let mut rows = Vec::new();
+        for bar in first..len {
+            if columns.iter().all(|column| !value(bar, column).is_nan()) {
+                index.push(bar);
+                rows.push(columns.iter().map(|column| value(bar, column)).collect());
+            }
+        }
+
+        FeatureMatrix { names: self.names(), index, rows }
+    }
+}
+
+/// Aligned feature matrix, one row per bar and one column per feature
+///
+/// Only bars where every feature has a value are kept; [`FeatureMatrix::index`] gives the
+/// bar of each row in the input series, to align targets with the rows.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureMatrix {
+    names: Vec<String>,
+    index: Vec<usize>,
+    rows: Vec<Vec<f64>>,
+}
+
+impl FeatureMatrix {
+    /// Names of the columns
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Bar of each row in the input series
+    pub fn index(&self) -> &[usize] {
+        &self.index
+    }
+
+    /// Rows of the matrix
+    pub fn rows(&self) -> &[Vec<f64>] {
+        &self.rows
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the matrix has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Number of columns
+    pub fn width(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Values of the column with the given name
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        let column = self.names.iter().position(|n| n == name)?;
+        Some(self.rows.iter().map(|row| row[column]).collect())
+    }
+
+    /// Consumes the matrix, returning its rows
+    pub fn into_rows(self) -> Vec<Vec<f64>> {
+        self.rows
+    }
+
+    /// Copies the matrix into a `rows × columns` array
+    #[cfg(feature = "ndarray")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    pub fn to_array(&self) -> ndarray::Array2<f64> {
+        ndarray::Array2::from_shape_fn((self.len(), self.width()), |(row, column)| self.rows[row][column])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::MAType;
+    use crate::momentum::{macd, rsi};
+    use crate::volatility::atr;
+
+    fn prices() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let close: Vec<f64> = (0..120).map(|i| 50.0 + (i as f64 * 0.15).sin() * 4.0 + i as f64 * 0.02).collect();
+        let high = close.iter().enumerate().map(|(i, c)| c + 0.5 + (i % 3) as f64 * 0.2).collect();
+        let low = close.iter().enumerate().map(|(i, c)| c - 0.4 - (i % 4) as f64 * 0.1).collect();
+        (high, low, close)
+    }
+
+    #[test]
+    fn test_feature_matrix_alignment() {
+        let (high, low, close) = prices();
+        let matrix = FeatureBuilder::new()
+            .add(Indicator::Rsi(14))
+            .add(Indicator::Macd { fast: 12, slow: 26, signal: 9 })
+            .add(Indicator::Atr(10))
+            .lags(&[3, 1, 0, 1])
+            .build_hlc(&high, &low, &close)
+            .unwrap();
+
+        assert_eq!(matrix.width(), 5 * 3);
+        assert_eq!(matrix.names()[3], "MACD(12,26,9).macd");
+        assert_eq!(matrix.names()[5], "MACD(12,26,9).macd[t-3]");
+        // The MACD signal starts at bar 33; its lag of 3 bars at bar 36
+        assert_eq!(matrix.index()[0], 36);
+        assert_eq!(matrix.len(), close.len() - 36);
+        assert!(matrix.rows().iter().all(|row| row.len() == 15 && row.iter().all(|v| !v.is_nan())));
+
+        let rsi = rsi(&close, 14).unwrap();
+        let (_, signal, _) = macd(&close, 12, 26, 9).unwrap();
+        let atr = atr(&high, &low, &close, 10).unwrap();
+        for (row, &bar) in matrix.rows().iter().zip(matrix.index()) {
+            assert_eq!(row[0], rsi[bar]);
+            assert_eq!(row[1], rsi[bar - 1]);
+            assert_eq!(row[2], rsi[bar - 3]);
+            assert_eq!(row[7], signal[bar - 1]);
+            assert_eq!(row[14], atr[bar - 3]);
+        }
+        assert_eq!(matrix.column("RSI(14)[t-1]").unwrap()[0], rsi[35]);
+        assert!(matrix.column("RSI(15)").is_none());
+    }
+
+    #[test]
+    fn test_feature_matrix_drops_rows_with_nan() {
+        // A missing bar leaves its windows without a value
+        let mut close: Vec<f64> = (0..40).map(|i| 10.0 + (i as f64 * 0.5).sin()).collect();
+        close.extend(vec![f64::NAN; 1]);
+        close.extend((0..20).map(|i| 10.0 + (i as f64 * 0.5).cos()));
+        let matrix = FeatureBuilder::new()
+            .add(Indicator::Ma { period: 5, ma_type: MAType::SMA })
+            .build(&close)
+            .unwrap();
+        assert!(matrix.index().iter().all(|&bar| !(40..45).contains(&bar)));
+        assert_eq!(matrix.index()[0], 4);
+        assert_eq!(matrix.len(), (40 - 4) + (61 - 45));
+        assert_eq!(matrix.clone().into_rows().len(), matrix.len());
+    }
+
+    #[test]
+    fn test_feature_builder_errors() {
+        let (high, low, close) = prices();
+        assert!(FeatureBuilder::new().build(&close).is_err());
+        assert!(FeatureBuilder::new().add(Indicator::Atr(14)).build(&close).is_err());
+        assert!(FeatureBuilder::new().add(Indicator::Atr(14)).build_hlc(&high, &low, &close).is_ok());
+        assert!(FeatureBuilder::new().add(Indicator::Ema(200)).build(&close).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_feature_matrix_to_array() {
+        let (_, _, close) = prices();
+        let matrix = FeatureBuilder::new().add(Indicator::Sma(5)).lags(&[2]).build(&close).unwrap();
+        let array = matrix.to_array();
+        assert_eq!(array.dim(), (matrix.len(), 2));
+        assert_eq!(array[[3, 1]], matrix.rows()[3][1]);
+    }
+}
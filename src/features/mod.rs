@@ -0,0 +1,14 @@
+//! Features for machine learning
+//!
+//! Bridges indicator outputs to model training code: [`FeatureBuilder`] turns a list of
+//! indicator specs into an aligned feature matrix, [`windows`] cuts a series into
+//! labeled sliding windows, and [`triple_barrier`] labels bars with volatility-scaled
+//! barriers.
+
+pub mod matrix;
+pub mod windows;
+pub mod barriers;
+
+pub use matrix::*;
+pub use windows::*;
+pub use barriers::*;
@@ -0,0 +1,245 @@
+//! Sliding-window datasets
+//!
+//! Sequence models and quick prototypes train on fixed-length windows of a series, each
+//! paired with a label describing what followed it. [`windows`] cuts every such window and
+//! labels it from the next `horizon` bars with a [`Labeler`]; no label looks further ahead
+//! than the horizon, and no window overlaps its own label period.
+
+use crate::common::{validate_not_empty, validate_same_length, IntoParam, Period, TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// How a window is labeled from the prices that follow it
+///
+/// Every labeler sees the price at the last bar of the window and the prices of the next
+/// `horizon` bars.
+#[derive(Debug, Clone, Copy)]
+pub enum Labeler {
+    /// Return from the last bar of the window to the end of the horizon
+    Return,
+    /// Sign of that return: 1, -1, or 0 when its magnitude does not exceed the threshold
+    ReturnSign(f64),
+    /// First barrier touched: 1 when the price rises by `take_profit` (a fraction of the
+    /// current price), -1 when it falls by `stop_loss`, 0 when neither happens within the
+    /// horizon
+    TripleBarrier {
+        /// Upper barrier as a fraction of the current price, e.g. 0.02
+        take_profit: f64,
+        /// Lower barrier as a fraction of the current price, e.g. 0.01
+        stop_loss: f64,
+    },
+    /// Any function of the current price and the following prices
+    Custom(fn(f64, &[f64]) -> f64),
+}
+
+impl Labeler {
+    fn validate(&self) -> TAResult<()> {
+        match *self {
+            Labeler::ReturnSign(threshold) if !(threshold.is_finite() && threshold >= 0.0) => {
+                Err(TAError::invalid_parameter("threshold", "must be non-negative and finite"))
+            }
+            Labeler::TripleBarrier { take_profit, stop_loss }
+                if !(take_profit.is_finite() && take_profit > 0.0 && stop_loss.is_finite() && stop_loss > 0.0) =>
+            {
+                Err(TAError::invalid_parameter("barriers", "must be positive and finite"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Label of a window ending at price `current`, followed by `future`
+    fn label(&self, current: f64, future: &[f64]) -> f64 {
+        let ret = |price: f64| price / current - 1.0;
+        match *self {
+            Labeler::Return => ret(future[future.len() - 1]),
+            Labeler::ReturnSign(threshold) => {
+                let r = ret(future[future.len() - 1]);
+                if r.is_nan() {
+                    f64::NAN
+                } else if r > threshold {
+                    1.0
+                } else if r < -threshold {
+                    -1.0
+                } else {
+                    0.0
+                }
+            }
+            Labeler::TripleBarrier { take_profit, stop_loss } => {
+                for &price in future {
+                    let r = ret(price);
+                    if r.is_nan() {
+                        return f64::NAN;
+                    } else if r >= take_profit {
+                        return 1.0;
+                    } else if r <= -stop_loss {
+                        return -1.0;
+                    }
+                }
+                0.0
+            }
+            Labeler::Custom(label) => label(current, future),
+        }
+    }
+}
+
+/// A window of a series and its label
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabeledWindow {
+    /// Values of the window, oldest first
+    pub x: Vec<f64>,
+    /// Label computed from the bars after the window
+    pub y: f64,
+    /// Index of the last bar of the window in the series
+    pub bar: usize,
+}
+
+/// Cuts a series into labeled sliding windows
+///
+/// Same as [`windows_labeled_by`] with the series itself as prices.
+///
+/// # Examples
+/// ```
+/// use ta_rust::features::{windows, Labeler};
+///
+/// let close = vec![10.0, 10.5, 10.2, 10.8, 11.0, 10.6, 10.9];
+/// let dataset = windows(&close, 3, 2, Labeler::ReturnSign(0.0)).unwrap();
+///
+/// assert_eq!(dataset.len(), 3);
+/// assert_eq!(dataset[0].x, vec![10.0, 10.5, 10.2]);
+/// assert_eq!(dataset[0].y, 1.0); // 10.2 → 11.0
+/// assert_eq!(dataset[1].y, -1.0); // 10.8 → 10.6
+/// ```
+pub fn windows(
+    series: &[f64],
+    window_len: impl IntoParam<Period>,
+    horizon: impl IntoParam<Period>,
+    labeler: Labeler,
+) -> TAResult<Vec<LabeledWindow>> {
+    with_function("WINDOWS", || {
+        windows_labeled_by(series, series, window_len, horizon, labeler)
+    })
+}
+
+/// Cuts a series into sliding windows labeled from a price series
+///
+/// The window ending at bar `t` holds `series[t + 1 - window_len..=t]`; its label is computed
+/// from `prices[t]` and `prices[t + 1..=t + horizon]`. Windows holding a NaN, such as the
+/// warm-up of an indicator, and windows whose label is NaN are skipped, so every returned
+/// sample is complete. Windows whose horizon runs past the end of the series are not
+/// produced.
+///
+/// # Arguments
+/// * `series` - Series the windows are cut from, e.g. an indicator output
+/// * `prices` - Prices the labels are computed from, as long as `series`
+/// * `window_len` - Number of bars in each window
+/// * `horizon` - Number of bars after the window used for the label
+/// * `labeler` - How the label is computed
+///
+/// # Returns
+/// * `Ok(Vec<LabeledWindow>)` - Labeled windows in bar order
+/// * `Err(TAError)` - Error if inputs are invalid or too short for one window
+///
+/// # Examples
+/// ```
+/// use ta_rust::features::{windows_labeled_by, Labeler};
+/// use ta_rust::momentum::rsi;
+///
+/// let close: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.3).sin() * 3.0).collect();
+/// let rsi14 = rsi(&close, 14).unwrap();
+/// let barrier = Labeler::TripleBarrier { take_profit: 0.02, stop_loss: 0.02 };
+/// let dataset = windows_labeled_by(&rsi14, &close, 10, 5, barrier).unwrap();
+///
+/// // The first window ends once it holds ten RSI values
+/// assert_eq!(dataset[0].bar, 23);
+/// assert!(dataset.iter().all(|sample| [-1.0, 0.0, 1.0].contains(&sample.y)));
+/// ```
+pub fn windows_labeled_by(
+    series: &[f64],
+    prices: &[f64],
+    window_len: impl IntoParam<Period>,
+    horizon: impl IntoParam<Period>,
+    labeler: Labeler,
+) -> TAResult<Vec<LabeledWindow>> {
+    with_function("WINDOWS", || {
+        let window_len = window_len.into_param("window_len")?.get();
+        let horizon = horizon.into_param("horizon")?.get();
+        validate_not_empty(series, "series")?;
+        validate_same_length(series, prices, "series", "prices")?;
+        labeler.validate()?;
+        if series.len() < window_len + horizon {
+            return Err(TAError::insufficient_data(window_len + horizon, series.len()));
+        }
+
+        let mut samples = Vec::new();
+        for bar in (window_len - 1)..(series.len() - horizon) {
+            let x = &series[bar + 1 - window_len..=bar];
+            if x.iter().any(|v| v.is_nan()) {
+                continue;
+            }
+            let y = labeler.label(prices[bar], &prices[bar + 1..=bar + horizon]);
+            if !y.is_nan() {
+                samples.push(LabeledWindow { x: x.to_vec(), y, bar });
+            }
+        }
+        Ok(samples)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_windows_return_labels() {
+        let close: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.6).sin()).collect();
+        let dataset = windows(&close, 5, 3, Labeler::Return).unwrap();
+        assert_eq!(dataset.len(), 30 - 5 - 3 + 1);
+        for sample in &dataset {
+            assert_eq!(sample.x.len(), 5);
+            assert_eq!(sample.x[4], close[sample.bar]);
+            assert_float_eq!(sample.y, close[sample.bar + 3] / close[sample.bar] - 1.0, 1e-12);
+        }
+        assert_eq!(dataset.last().unwrap().bar, 26);
+
+        let signs = windows(&close, 5, 3, Labeler::ReturnSign(0.01)).unwrap();
+        for (sign, ret) in signs.iter().zip(&dataset) {
+            let expected = if ret.y > 0.01 { 1.0 } else if ret.y < -0.01 { -1.0 } else { 0.0 };
+            assert_eq!(sign.y, expected);
+        }
+        assert!(signs.iter().any(|s| s.y == 0.0) && signs.iter().any(|s| s.y == 1.0));
+    }
+
+    #[test]
+    fn test_windows_triple_barrier_and_custom() {
+        let prices = vec![100.0, 100.0, 101.0, 99.5, 102.5, 100.0, 97.0, 100.0, 100.5, 100.2, 100.1];
+        let barrier = Labeler::TripleBarrier { take_profit: 0.02, stop_loss: 0.02 };
+        let dataset = windows(&prices, 1, 3, barrier).unwrap();
+        let labels: Vec<f64> = dataset.iter().map(|s| s.y).collect();
+        // Bar 1: 100 → 102.5 within three bars; bar 2: 101 never moves 2%; bar 4: 102.5 → 100
+        assert_eq!(labels, vec![0.0, 1.0, 0.0, 1.0, -1.0, -1.0, 1.0, 0.0]);
+
+        let max_gain = Labeler::Custom(|current, future| future.iter().fold(f64::MIN, |m, &p| m.max(p)) / current - 1.0);
+        let gains = windows(&prices, 2, 2, max_gain).unwrap();
+        assert_float_eq!(gains[0].y, 0.01, 1e-12);
+    }
+
+    #[test]
+    fn test_windows_skip_nan_and_errors() {
+        let series = vec![f64::NAN, f64::NAN, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let prices = vec![10.0, 11.0, 12.0, 13.0, f64::NAN, 15.0, 16.0];
+        let dataset = windows_labeled_by(&series, &prices, 2, 1, Labeler::Return).unwrap();
+        // Bar 3 is labeled by the missing price at bar 4, bar 4 from a missing current price
+        let bars: Vec<usize> = dataset.iter().map(|s| s.bar).collect();
+        assert_eq!(bars, vec![5]);
+
+        assert!(windows(&series, 5, 3, Labeler::Return).is_err());
+        assert!(windows(&series, 0, 1, Labeler::Return).is_err());
+        assert_eq!(windows(&series, 2, 0, Labeler::Return).unwrap_err().function(), Some("WINDOWS"));
+        assert!(windows(&series, 2, 1, Labeler::ReturnSign(-0.1)).is_err());
+        assert!(windows(&series, 2, 1, Labeler::TripleBarrier { take_profit: 0.0, stop_loss: 0.1 }).is_err());
+        assert!(windows_labeled_by(&series, &prices[1..], 2, 1, Labeler::Return).is_err());
+    }
+}
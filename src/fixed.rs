@@ -0,0 +1,356 @@
+//! Fixed-Point Indicators
+//!
+//! Integer versions of the indicators that need no more than addition, comparison and a
+//! division by the period, for deployments that must give bit-identical results on every
+//! platform: exchange-side checks, embedded targets without an FPU, replicated state
+//! machines. A price is a [`Fixed`], an `i64` count of `10^-DECIMALS` units, with the
+//! number of decimals chosen as a const generic; no floating point is involved once the
+//! inputs are converted.
+//!
+//! Warm-up slots are `None` instead of NaN, and an `i64` overflow is an error rather than
+//! a wrapped or saturated value.
+
+use crate::common::{TAError, TAResult, IntoParam, Period, Float};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, validate_same_length};
+use crate::common::window::{RunningMax, RunningMin};
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// A decimal number stored as a count of `10^-DECIMALS` units
+///
+/// `Fixed<4>` holds prices to a ten-thousandth, `Fixed<0>` whole numbers. `DECIMALS` may
+/// be at most 18, the most an `i64` unit count can scale by.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::Fixed;
+///
+/// let price = Fixed::<2>::from_f64(101.255).unwrap();
+/// assert_eq!(price.raw(), 10126);
+/// assert_eq!(price.to_string(), "101.26");
+/// assert_eq!(Fixed::<2>::from_raw(-5).to_f64(), -0.05);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixed<const DECIMALS: u32>(i64);
+
+impl<const DECIMALS: u32> Fixed<DECIMALS> {
+    /// Number of units in 1
+    pub const SCALE: i64 = 10i64.pow(DECIMALS);
+
+    /// Wraps a count of `10^-DECIMALS` units
+    pub const fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+
+    /// The count of `10^-DECIMALS` units
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// Converts from an `f64`, rounding half away from zero to the nearest unit
+    ///
+    /// Returns `None` for NaN, infinities and values out of the `i64` range.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        let units = Float::round(value * Self::SCALE as f64);
+        // i64::MAX as f64 rounds up to 2^63, itself out of range
+        (units.is_finite() && units >= i64::MIN as f64 && units < i64::MAX as f64).then_some(Self(units as i64))
+    }
+
+    /// Converts to the nearest `f64`
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::SCALE as f64
+    }
+}
+
+impl<const DECIMALS: u32> fmt::Display for Fixed<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = self.0.unsigned_abs();
+        let scale = Self::SCALE as u64;
+        if DECIMALS == 0 {
+            write!(f, "{}{}", sign, units)
+        } else {
+            write!(f, "{}{}.{:0width$}", sign, units / scale, units % scale, width = DECIMALS as usize)
+        }
+    }
+}
+
+/// Converts prices to fixed point, rounding each to the nearest unit
+///
+/// # Errors
+/// - `InvalidInput` if a price is NaN, infinite or out of range
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::{to_fixed, Fixed};
+///
+/// let prices = to_fixed::<4>(&[1.2345, 1.23456]).unwrap();
+/// assert_eq!(prices, vec![Fixed::from_raw(12345), Fixed::from_raw(12346)]);
+/// assert!(to_fixed::<4>(&[f64::NAN]).is_err());
+/// ```
+pub fn to_fixed<const DECIMALS: u32>(prices: &[f64]) -> TAResult<Vec<Fixed<DECIMALS>>> {
+    with_function("TO_FIXED", || {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| {
+                Fixed::from_f64(price)
+                    .ok_or_else(|| TAError::invalid_input(format!("price {} at index {} has no fixed-point value", price, i)))
+            })
+            .collect()
+    })
+}
+
+/// Simple Moving Average in fixed point
+///
+/// The window is summed exactly in 128 bits and the mean rounded half away from zero to
+/// the nearest unit, so each value is the correctly rounded average of its window.
+///
+/// # Returns
+/// Vector of averages. The first `period-1` values will be `None`.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::{sma, Fixed};
+///
+/// let prices: Vec<Fixed<2>> = [1000, 1001, 1003, 1004].map(Fixed::from_raw).to_vec();
+/// let result = sma(&prices, 3).unwrap();
+/// assert_eq!(result[1], None);
+/// assert_eq!(result[2], Some(Fixed::from_raw(1001))); // 10.0133...
+/// assert_eq!(result[3], Some(Fixed::from_raw(1003))); // 10.0266...
+/// ```
+pub fn sma<const DECIMALS: u32>(
+    prices: &[Fixed<DECIMALS>],
+    period: impl IntoParam<Period>,
+) -> TAResult<Vec<Option<Fixed<DECIMALS>>>> {
+    with_function("FIXED_SMA", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(prices, "prices")?;
+        validate_sufficient_data(prices, period, "prices")?;
+
+        let mut output = vec![None; prices.len()];
+        let mut sum: i128 = 0;
+        for (i, price) in prices.iter().enumerate() {
+            sum += price.0 as i128;
+            if i >= period {
+                sum -= prices[i - period].0 as i128;
+            }
+            if i + 1 >= period {
+                // The mean of i64 values is within the i64 range
+                output[i] = Some(Fixed(div_round(sum, period as i128) as i64));
+            }
+        }
+        Ok(output)
+    })
+}
+
+/// Momentum in fixed point: the change over `period` bars
+///
+/// # Returns
+/// Vector of changes. The first `period` values will be `None`.
+///
+/// # Errors
+/// - `NumericalError` if a change overflows `i64`
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::{mom, Fixed};
+///
+/// let prices: Vec<Fixed<2>> = [1000, 1010, 995, 1020].map(Fixed::from_raw).to_vec();
+/// let result = mom(&prices, 2).unwrap();
+/// assert_eq!(result[2], Some(Fixed::from_raw(-5)));
+/// assert_eq!(result[3], Some(Fixed::from_raw(10)));
+/// ```
+pub fn mom<const DECIMALS: u32>(
+    prices: &[Fixed<DECIMALS>],
+    period: impl IntoParam<Period>,
+) -> TAResult<Vec<Option<Fixed<DECIMALS>>>> {
+    with_function("FIXED_MOM", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(prices, "prices")?;
+        validate_sufficient_data(prices, period + 1, "prices")?;
+
+        let mut output = vec![None; prices.len()];
+        for i in period..prices.len() {
+            let change = prices[i].0.checked_sub(prices[i - period].0).ok_or_else(overflow)?;
+            output[i] = Some(Fixed(change));
+        }
+        Ok(output)
+    })
+}
+
+/// On Balance Volume in fixed point, over integer volumes
+///
+/// Starts at the first volume, as [`obv`](crate::volume::obv) does.
+///
+/// # Errors
+/// - `MismatchedInputs` if `close` and `volume` differ in length
+/// - `NumericalError` if the running total overflows `i64`
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::{obv, Fixed};
+///
+/// let close: Vec<Fixed<2>> = [1000, 1100, 1050, 1050].map(Fixed::from_raw).to_vec();
+/// let result = obv(&close, &[1000, 1500, 800, 400]).unwrap();
+/// assert_eq!(result, vec![1000, 2500, 1700, 1700]);
+/// ```
+pub fn obv<const DECIMALS: u32>(close: &[Fixed<DECIMALS>], volume: &[i64]) -> TAResult<Vec<i64>> {
+    with_function("FIXED_OBV", || {
+        validate_not_empty(close, "close")?;
+        validate_same_length(close, volume, "close", "volume")?;
+
+        let mut output = Vec::with_capacity(close.len());
+        output.push(volume[0]);
+        for i in 1..close.len() {
+            let previous = output[i - 1];
+            let total = match close[i].cmp(&close[i - 1]) {
+                core::cmp::Ordering::Greater => previous.checked_add(volume[i]),
+                core::cmp::Ordering::Less => previous.checked_sub(volume[i]),
+                core::cmp::Ordering::Equal => Some(previous),
+            };
+            output.push(total.ok_or_else(overflow)?);
+        }
+        Ok(output)
+    })
+}
+
+/// Highest value over a rolling window, in fixed point
+///
+/// # Returns
+/// Vector of maxima. The first `period-1` values will be `None`.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::{max, Fixed};
+///
+/// let prices: Vec<Fixed<0>> = [1, 3, 2, 5, 4].map(Fixed::from_raw).to_vec();
+/// let result = max(&prices, 3).unwrap();
+/// assert_eq!(result[2..], [3, 5, 5].map(|v| Some(Fixed::from_raw(v))));
+/// ```
+pub fn max<const DECIMALS: u32>(
+    prices: &[Fixed<DECIMALS>],
+    period: impl IntoParam<Period>,
+) -> TAResult<Vec<Option<Fixed<DECIMALS>>>> {
+    with_function("FIXED_MAX", || {
+        let period = period.into_param("period")?.get();
+        rolling_extreme(prices, period, RunningMax::new(period), RunningMax::push)
+    })
+}
+
+/// Lowest value over a rolling window, in fixed point
+///
+/// # Returns
+/// Vector of minima. The first `period-1` values will be `None`.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::fixed::{min, Fixed};
+///
+/// let prices: Vec<Fixed<0>> = [4, 2, 3, 1, 5].map(Fixed::from_raw).to_vec();
+/// let result = min(&prices, 3).unwrap();
+/// assert_eq!(result[2..], [2, 1, 1].map(|v| Some(Fixed::from_raw(v))));
+/// ```
+pub fn min<const DECIMALS: u32>(
+    prices: &[Fixed<DECIMALS>],
+    period: impl IntoParam<Period>,
+) -> TAResult<Vec<Option<Fixed<DECIMALS>>>> {
+    with_function("FIXED_MIN", || {
+        let period = period.into_param("period")?.get();
+        rolling_extreme(prices, period, RunningMin::new(period), RunningMin::push)
+    })
+}
+
+/// Rolling extreme from a window hook, `push` feeding it one price and returning the
+/// extreme of its window
+fn rolling_extreme<const DECIMALS: u32, H>(
+    prices: &[Fixed<DECIMALS>],
+    period: usize,
+    mut extreme: H,
+    push: fn(&mut H, Fixed<DECIMALS>) -> Option<Fixed<DECIMALS>>,
+) -> TAResult<Vec<Option<Fixed<DECIMALS>>>> {
+    validate_not_empty(prices, "prices")?;
+    validate_sufficient_data(prices, period, "prices")?;
+
+    let mut output = vec![None; prices.len()];
+    for (i, &price) in prices.iter().enumerate() {
+        let value = push(&mut extreme, price);
+        if i + 1 >= period {
+            output[i] = value;
+        }
+    }
+    Ok(output)
+}
+
+/// Integer division rounding half away from zero, for a positive divisor
+fn div_round(numerator: i128, divisor: i128) -> i128 {
+    let half = divisor / 2;
+    if numerator >= 0 {
+        (numerator + half) / divisor
+    } else {
+        (numerator - half) / divisor
+    }
+}
+
+fn overflow() -> TAError {
+    TAError::numerical_error("fixed-point value overflows i64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_matches_float() {
+        let closes: Vec<f64> = (0..120).map(|i| 50.0 + ((i * 17) % 23) as f64 * 0.25 - (i as f64 * 0.1).sin()).collect();
+        let prices = to_fixed::<3>(&closes).unwrap();
+        let exact: Vec<f64> = prices.iter().map(|p| p.to_f64()).collect();
+
+        let average = sma(&prices, 10).unwrap();
+        let float_average = crate::overlap::sma(&exact, 10).unwrap();
+        assert!(average[8].is_none());
+        for i in 9..prices.len() {
+            crate::assert_float_eq!(average[i].unwrap().to_f64(), float_average[i], 0.0005 + 1e-9);
+        }
+
+        let highest = max(&prices, 7).unwrap();
+        let lowest = min(&prices, 7).unwrap();
+        let float_highest = crate::math_operators::max(&exact, 7).unwrap();
+        let float_lowest = crate::math_operators::min(&exact, 7).unwrap();
+        for i in 6..prices.len() {
+            assert_eq!(highest[i].unwrap().to_f64(), float_highest[i]);
+            assert_eq!(lowest[i].unwrap().to_f64(), float_lowest[i]);
+        }
+
+        let change = mom(&prices, 5).unwrap();
+        assert!(change[4].is_none());
+        assert_eq!(change[50].unwrap().raw(), prices[50].raw() - prices[45].raw());
+
+        let volume: Vec<i64> = (0..120).map(|i| 100 + i).collect();
+        let float_obv = crate::volume::obv(&exact, &volume.iter().map(|&v| v as f64).collect::<Vec<_>>()).unwrap();
+        let totals = obv(&prices, &volume).unwrap();
+        assert!(totals.iter().zip(&float_obv).all(|(&t, &f)| t as f64 == f));
+    }
+
+    #[test]
+    fn test_fixed_rounding_and_errors() {
+        assert_eq!(div_round(5, 2), 3);
+        assert_eq!(div_round(-5, 2), -3);
+        assert_eq!(div_round(-4, 3), -1);
+        assert_eq!(Fixed::<2>::from_f64(-0.125).unwrap().raw(), -13);
+        assert_eq!(Fixed::<3>::from_raw(-1).to_string(), "-0.001");
+        assert_eq!(Fixed::<0>::from_raw(42).to_string(), "42");
+        assert!(Fixed::<4>::from_f64(1e300).is_none());
+
+        let extremes = [Fixed::<0>::from_raw(i64::MIN), Fixed::from_raw(i64::MAX)];
+        assert_eq!(sma(&extremes, 2).unwrap()[1], Some(Fixed::from_raw(-1)));
+        assert!(mom(&extremes, 1).is_err());
+        assert!(obv(&extremes, &[i64::MAX, 1]).is_err());
+        assert!(obv(&extremes, &[1]).is_err());
+        assert!(max(&extremes, 3).is_err());
+        assert_eq!(sma(&extremes, 0).unwrap_err().function(), Some("FIXED_SMA"));
+    }
+}
@@ -0,0 +1,458 @@
+//! GPU Offload of Windowed Kernels
+//!
+//! Cross-sectional runs compute the same windowed statistic for thousands of symbols at
+//! every bar. [`GpuContext`] runs SMA, standard deviation and correlation as wgpu compute
+//! shaders over all the symbols at once, one invocation per output value, on whatever
+//! adapter wgpu finds (Vulkan, Metal, DX12 or OpenGL).
+//!
+//! The shaders compute in `f32`. Each symbol is shifted by its first value before the
+//! conversion, so results stay close to the CPU functions, to about `1e-5` of the price
+//! scale, but they are not bit-identical to them. Use the CPU functions, or
+//! [`batch::run_parallel`](crate::batch), where exact results matter.
+
+use std::sync::mpsc;
+
+use wgpu::util::DeviceExt;
+
+use crate::common::{IntoParam, Period, TAError, TAResult};
+use crate::common::utils::validate_prices;
+
+/// Number of invocations per workgroup, matching `@workgroup_size` in the shader
+const WORKGROUP_SIZE: u32 = 64;
+
+/// A GPU device with the windowed kernels compiled for it
+///
+/// Creating a context finds an adapter and compiles the shaders, so keep one around
+/// rather than creating it per call.
+///
+/// # Example
+/// ```rust,no_run
+/// use ta_rust::gpu::GpuContext;
+///
+/// let gpu = GpuContext::new().unwrap();
+/// let symbols: Vec<Vec<f64>> = (0..10_000)
+///     .map(|s| (0..390).map(|i| 100.0 + s as f64 * 0.01 + (i as f64 * 0.1).sin()).collect())
+///     .collect();
+/// let averages = gpu.sma(&symbols, 20).unwrap();
+/// assert_eq!(averages.len(), 10_000);
+/// ```
+#[derive(Debug)]
+pub struct GpuContext {
+    adapter: wgpu::AdapterInfo,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    sma: wgpu::ComputePipeline,
+    stddev: wgpu::ComputePipeline,
+    correl: wgpu::ComputePipeline,
+}
+
+/// Uniform parameters of a dispatch, laid out as `Params` in the shader
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    bars: u32,
+    symbols: u32,
+    period: u32,
+    deviations: f32,
+}
+
+impl GpuContext {
+    /// Connects to the default adapter and compiles the kernels
+    ///
+    /// # Errors
+    /// - `UnsupportedOperation` if no adapter or device is available
+    pub fn new() -> TAResult<Self> {
+        pollster::block_on(Self::connect())
+    }
+
+    async fn connect() -> TAResult<Self> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions::default())
+            .await
+            .map_err(|err| TAError::unsupported_operation(format!("no GPU adapter available: {}", err)))?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("ta-rust"),
+                required_limits: adapter.limits(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| TAError::unsupported_operation(format!("no GPU device available: {}", err)))?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("windowed"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("windowed.wgsl").into()),
+        });
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("windowed"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage(1, true),
+                storage(2, true),
+                storage(3, false),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("windowed"),
+            bind_group_layouts: &[Some(&layout)],
+            immediate_size: 0,
+        });
+        let pipeline = |entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let (sma, stddev, correl) = (pipeline("sma"), pipeline("stddev"), pipeline("correl"));
+        Ok(Self { adapter: adapter.get_info(), device, queue, layout, sma, stddev, correl })
+    }
+
+    /// Name of the adapter the kernels run on
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter.name
+    }
+
+    /// Simple Moving Average of every symbol
+    ///
+    /// # Arguments
+    /// * `series` - One price series per symbol, all of the same length
+    /// * `period` - Number of bars averaged
+    ///
+    /// # Returns
+    /// One vector per symbol, like [`sma`](crate::overlap::sma) of its series
+    ///
+    /// # Errors
+    /// - `InvalidInput` if there are no symbols, a series is empty or holds a non-finite
+    ///   value
+    /// - `MismatchedInputs` if the series differ in length
+    /// - `InvalidParameter` if `period` is 0
+    /// - `InsufficientData` if the series are shorter than `period`
+    /// - `InvalidInput` if the matrix exceeds the device's buffer or dispatch limits
+    /// - `UnsupportedOperation` if the device is lost or the results cannot be read back
+    pub fn sma<S: AsRef<[f64]>>(&self, series: &[S], period: impl IntoParam<Period>) -> TAResult<Vec<Vec<f64>>> {
+        let period = period.into_param("period")?.get();
+        let (xs, shifts) = matrix(series, period)?;
+        let mut outputs = self.dispatch(&self.sma, &xs, None, series.len(), period, 1.0)?;
+        for (output, shift) in outputs.iter_mut().zip(shifts) {
+            output[period - 1..].iter_mut().for_each(|value| *value += shift);
+        }
+        Ok(outputs)
+    }
+
+    /// Standard deviation of every symbol
+    ///
+    /// # Arguments
+    /// * `series` - One price series per symbol, all of the same length
+    /// * `period` - Number of bars in each window
+    /// * `deviations` - Multiplier of the standard deviation
+    ///
+    /// # Returns
+    /// One vector per symbol, like [`stddev`](crate::statistic::stddev) of its series
+    ///
+    /// # Errors
+    /// Same as [`sma`](GpuContext::sma)
+    pub fn stddev<S: AsRef<[f64]>>(
+        &self,
+        series: &[S],
+        period: impl IntoParam<Period>,
+        deviations: f64,
+    ) -> TAResult<Vec<Vec<f64>>> {
+        let period = period.into_param("period")?.get();
+        let (xs, _) = matrix(series, period)?;
+        self.dispatch(&self.stddev, &xs, None, series.len(), period, deviations as f32)
+    }
+
+    /// Pearson correlation of every symbol's pair of series
+    ///
+    /// Windows where either series has no variance are NaN, as with
+    /// [`correl`](crate::statistic::correl).
+    ///
+    /// # Arguments
+    /// * `series1` - First series of each symbol
+    /// * `series2` - Second series of each symbol, such as a benchmark repeated per symbol
+    /// * `period` - Number of bars in each window
+    ///
+    /// # Returns
+    /// One vector per symbol, like `correl(&series1[s], &series2[s], period)`
+    ///
+    /// # Errors
+    /// Same as [`sma`](GpuContext::sma), plus `MismatchedInputs` if `series1` and
+    /// `series2` hold different numbers of symbols
+    pub fn correl<S: AsRef<[f64]>, T: AsRef<[f64]>>(
+        &self,
+        series1: &[S],
+        series2: &[T],
+        period: impl IntoParam<Period>,
+    ) -> TAResult<Vec<Vec<f64>>> {
+        let period = period.into_param("period")?.get();
+        if series1.len() != series2.len() {
+            return Err(TAError::mismatched_inputs(format!(
+                "series1 has {} symbols, series2 has {}",
+                series1.len(),
+                series2.len()
+            )));
+        }
+        let (xs, _) = matrix(series1, period)?;
+        let (ys, _) = matrix(series2, period)?;
+        if xs.len() != ys.len() {
+            return Err(TAError::mismatched_inputs("series1 and series2 must have the same length"));
+        }
+        let mut outputs = self.dispatch(&self.correl, &xs, Some(&ys), series1.len(), period, 1.0)?;
+        for value in outputs.iter_mut().flatten() {
+            if *value > 1.0 {
+                *value = f64::NAN;
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Runs a kernel over a `symbols` x `bars` matrix and reads back one vector per
+    /// symbol, NaN over the warm-up bars
+    fn dispatch(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        xs: &[f32],
+        ys: Option<&[f32]>,
+        symbols: usize,
+        period: usize,
+        deviations: f32,
+    ) -> TAResult<Vec<Vec<f64>>> {
+        let bars = xs.len() / symbols;
+        let [groups_x, groups_y, groups_z] = workgroups(&self.device.limits(), symbols, bars)?;
+        // Both fit once the dispatch does, and period is at most bars
+        let params = Params { bars: bars as u32, symbols: symbols as u32, period: period as u32, deviations };
+        let input = |label, values: &[f32]| {
+            self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents: bytemuck::cast_slice(values),
+                usage: wgpu::BufferUsages::STORAGE,
+            })
+        };
+        let params = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let xs_buffer = input("xs", xs);
+        let ys_buffer = ys.map(|ys| input("ys", ys));
+        let size = core::mem::size_of_val(xs) as u64;
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("out"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("windowed"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: xs_buffer.as_entire_binding() },
+                // Kernels of one series bind it twice
+                wgpu::BindGroupEntry { binding: 2, resource: ys_buffer.as_ref().unwrap_or(&xs_buffer).as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: output.as_entire_binding() },
+            ],
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("windowed") });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("windowed"), timestamp_writes: None });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups_x, groups_y, groups_z);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        readback.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|err| TAError::unsupported_operation(format!("GPU device lost: {}", err)))?;
+        receiver
+            .recv()
+            .map_err(|_| TAError::unsupported_operation("GPU buffer mapping was cancelled"))?
+            .map_err(|err| TAError::unsupported_operation(format!("GPU buffer mapping failed: {}", err)))?;
+        let view = readback
+            .get_mapped_range(..)
+            .map_err(|err| TAError::unsupported_operation(format!("GPU buffer not readable: {}", err)))?;
+        let values: &[f32] = bytemuck::cast_slice(&view);
+        Ok(values
+            .chunks(bars)
+            .map(|row| {
+                let mut output: Vec<f64> = row.iter().map(|&v| v as f64).collect();
+                output[..period - 1].fill(f64::NAN);
+                output
+            })
+            .collect())
+    }
+}
+
+/// Workgroup counts of a dispatch over a `symbols` x `bars` matrix, checked against the
+/// device limits so that oversized inputs fail here rather than in wgpu validation
+fn workgroups(limits: &wgpu::Limits, symbols: usize, bars: usize) -> TAResult<[u32; 3]> {
+    let values = symbols * bars;
+    let size = (values * core::mem::size_of::<f32>()) as u64;
+    let max_size = limits.max_storage_buffer_binding_size.min(limits.max_buffer_size);
+    if u32::try_from(values).is_err() || size > max_size {
+        return Err(TAError::invalid_input(format!(
+            "{} symbols of {} bars take {} bytes, the GPU binds at most {}",
+            symbols, bars, size, max_size
+        )));
+    }
+
+    // Bars run along x; symbols are spread over y and z, each limited to the same count
+    let max_groups = limits.max_compute_workgroups_per_dimension;
+    let within = |groups: usize| u32::try_from(groups).ok().filter(|&groups| groups <= max_groups);
+    let groups_x = within(bars.div_ceil(WORKGROUP_SIZE as usize)).ok_or_else(|| {
+        TAError::invalid_input(format!(
+            "{} bars per symbol exceed the GPU limit of {}",
+            bars,
+            max_groups as usize * WORKGROUP_SIZE as usize
+        ))
+    })?;
+    let groups_z = within(symbols.div_ceil(max_groups as usize)).ok_or_else(|| {
+        TAError::invalid_input(format!(
+            "{} symbols exceed the GPU limit of {}",
+            symbols,
+            max_groups as usize * max_groups as usize
+        ))
+    })?;
+    Ok([groups_x, symbols.min(max_groups as usize) as u32, groups_z])
+}
+
+/// Checks the series and flattens them into a row per symbol, each shifted by its first
+/// value, which is returned alongside
+fn matrix<S: AsRef<[f64]>>(series: &[S], period: usize) -> TAResult<(Vec<f32>, Vec<f64>)> {
+    let Some(first) = series.first() else {
+        return Err(TAError::invalid_input("series must hold at least one symbol"));
+    };
+    let bars = first.as_ref().len();
+    if bars == 0 {
+        return Err(TAError::invalid_input("series cannot be empty"));
+    }
+    if period > bars {
+        return Err(TAError::insufficient_data(period, bars));
+    }
+
+    let mut values = Vec::with_capacity(series.len() * bars);
+    let mut shifts = Vec::with_capacity(series.len());
+    for (symbol, data) in series.iter().enumerate() {
+        let data = data.as_ref();
+        if data.len() != bars {
+            return Err(TAError::mismatched_inputs(format!(
+                "symbol {} has {} bars, symbol 0 has {}",
+                symbol,
+                data.len(),
+                bars
+            )));
+        }
+        validate_prices(data, "series")?;
+        let shift = data[0];
+        values.extend(data.iter().map(|&v| (v - shift) as f32));
+        shifts.push(shift);
+    }
+    Ok((values, shifts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::overlap::sma;
+    use crate::statistic::{correl, stddev};
+
+    /// Context for the tests, or `None` on machines without a usable adapter
+    fn context() -> Option<GpuContext> {
+        match GpuContext::new() {
+            Ok(gpu) => Some(gpu),
+            Err(err) => {
+                eprintln!("skipping GPU test: {}", err);
+                None
+            }
+        }
+    }
+
+    fn assert_close(gpu: &[f64], cpu: &[f64], tolerance: f64) {
+        assert_eq!(gpu.len(), cpu.len());
+        for (i, (g, c)) in gpu.iter().zip(cpu).enumerate() {
+            assert!(g.is_nan() == c.is_nan() && (g.is_nan() || (g - c).abs() <= tolerance), "bar {}: {} vs {}", i, g, c);
+        }
+    }
+
+    #[test]
+    fn test_kernels_match_cpu() {
+        let Some(gpu) = context() else { return };
+        let market: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 * 0.05).sin() * 8.0).collect();
+        let symbols: Vec<Vec<f64>> = (0..130)
+            .map(|s| market.iter().enumerate().map(|(i, m)| m * (1.0 + s as f64 * 0.01) + ((i * (s + 3)) % 7) as f64 * 0.2).collect())
+            .collect();
+
+        let (smas, stddevs) = (gpu.sma(&symbols, 20).unwrap(), gpu.stddev(&symbols, 20, 2.0).unwrap());
+        let correls = gpu.correl(&symbols, &vec![market.clone(); symbols.len()], 30).unwrap();
+        for (s, data) in symbols.iter().enumerate() {
+            assert_close(&smas[s], &sma(data, 20).unwrap(), 1e-4);
+            assert_close(&stddevs[s], &stddev(data, 20, 2.0).unwrap(), 1e-4);
+            assert_close(&correls[s], &correl(data, &market, 30).unwrap(), 1e-4);
+        }
+
+        // A flat window has no correlation
+        let flat = vec![vec![5.0; 40]];
+        assert!(gpu.correl(&flat, &[&market[..40]], 10).unwrap()[0].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let empty: [Vec<f64>; 0] = [];
+        assert!(matrix(&empty, 3).is_err());
+        assert!(matrix(&[vec![1.0, 2.0]], 3).is_err());
+        assert!(matrix(&[vec![1.0, 2.0, 3.0], vec![1.0, 2.0]], 2).is_err());
+        assert!(matrix(&[vec![1.0, f64::NAN, 3.0]], 2).is_err());
+        let (values, shifts) = matrix(&[vec![10.0, 11.0], vec![3.0, 1.0]], 2).unwrap();
+        assert_eq!((values, shifts), (vec![0.0, 1.0, 0.0, -2.0], vec![10.0, 3.0]));
+
+        let limits = wgpu::Limits::default();
+        assert_eq!(workgroups(&limits, 130, 300).unwrap(), [5, 130, 1]);
+        assert_eq!(workgroups(&limits, 70_000, 390).unwrap(), [7, 65_535, 2]);
+        // Too many bars for the x axis, then too many bytes for one binding
+        assert!(workgroups(&limits, 1, 5_000_000).is_err());
+        assert!(workgroups(&limits, 40_000, 1_000).is_err());
+
+        let Some(gpu) = context() else { return };
+        assert!(gpu.sma(&[vec![1.0, 2.0]], 0).is_err());
+        assert!(gpu.sma(&[vec![1.0; 5_000_000]], 2).is_err());
+        assert!(gpu.correl(&[vec![1.0, 2.0]], &[vec![1.0, 2.0], vec![3.0, 4.0]], 2).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+// Windowed kernels over a symbols x bars matrix, one invocation per output value.
+// Row `symbol` holds the bars of one symbol, shifted by its first value on the host.
+
+struct Params {
+    bars: u32,
+    symbols: u32,
+    period: u32,
+    // Multiplier of the standard deviation
+    deviations: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> xs: array<f32>;
+@group(0) @binding(2) var<storage, read> ys: array<f32>;
+@group(0) @binding(3) var<storage, read_write> out: array<f32>;
+
+// Written for windows whose correlation is undefined; read back as NaN
+const UNDEFINED: f32 = 2.0;
+
+// Index one past the last bar of the window ending at this invocation's bar, or 0 when
+// the invocation has no output. Symbols are spread over y and z to stay within the
+// dispatch limits.
+fn window_end(id: vec3<u32>, groups: vec3<u32>) -> u32 {
+    let bar = id.x;
+    let symbol = id.z * groups.y + id.y;
+    if (bar >= params.bars || symbol >= params.symbols || bar + 1u < params.period) {
+        return 0u;
+    }
+    return symbol * params.bars + bar + 1u;
+}
+
+fn mean_of(values_end: u32) -> f32 {
+    var sum = 0.0;
+    for (var i = values_end - params.period; i < values_end; i++) {
+        sum += xs[i];
+    }
+    return sum / f32(params.period);
+}
+
+@compute @workgroup_size(64)
+fn sma(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let end = window_end(id, groups);
+    if (end == 0u) {
+        return;
+    }
+    out[end - 1u] = mean_of(end);
+}
+
+@compute @workgroup_size(64)
+fn stddev(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let end = window_end(id, groups);
+    if (end == 0u) {
+        return;
+    }
+    // Two passes: the mean, then the squared deviations from it
+    let mean = mean_of(end);
+    var squares = 0.0;
+    for (var i = end - params.period; i < end; i++) {
+        let deviation = xs[i] - mean;
+        squares += deviation * deviation;
+    }
+    out[end - 1u] = sqrt(squares / f32(params.period)) * params.deviations;
+}
+
+@compute @workgroup_size(64)
+fn correl(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let end = window_end(id, groups);
+    if (end == 0u) {
+        return;
+    }
+    let start = end - params.period;
+    var sum_x = 0.0;
+    var sum_y = 0.0;
+    var squares_x = 0.0;
+    var squares_y = 0.0;
+    for (var i = start; i < end; i++) {
+        sum_x += xs[i];
+        sum_y += ys[i];
+        squares_x += xs[i] * xs[i];
+        squares_y += ys[i] * ys[i];
+    }
+    let mean_x = sum_x / f32(params.period);
+    let mean_y = sum_y / f32(params.period);
+    var sxx = 0.0;
+    var syy = 0.0;
+    var sxy = 0.0;
+    for (var i = start; i < end; i++) {
+        let dx = xs[i] - mean_x;
+        let dy = ys[i] - mean_y;
+        sxx += dx * dx;
+        syy += dy * dy;
+        sxy += dx * dy;
+    }
+    // Undefined when a series has no variance relative to the window's magnitude, as on
+    // the CPU but at f32 precision
+    let epsilon = 1.1920929e-7;
+    if (sxx > epsilon * squares_x && syy > epsilon * squares_y) {
+        out[end - 1u] = clamp(sxy / sqrt(sxx * syy), -1.0, 1.0);
+    } else {
+        out[end - 1u] = UNDEFINED;
+    }
+}
@@ -0,0 +1,263 @@
+//! Apache Arrow adapters
+//!
+//! Indicators accept Arrow [`PrimitiveArray`] columns (`Float64Array`, `Float32Array`)
+//! and return Arrow arrays. Inputs without nulls are read straight from the Arrow
+//! buffer; null slots are read as NaN. Outputs carry a validity bitmap instead of
+//! NaN padding, so warm-up periods and undefined values appear as nulls, and the
+//! computed `Vec` is moved into the array without copying.
+//!
+//! # Example
+//! ```
+//! use arrow_array::{Array, Float64Array};
+//! use ta_rust::interop::arrow;
+//!
+//! let close = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+//! let result = arrow::sma(&close, 3).unwrap();
+//! assert_eq!(result.null_count(), 2);
+//! assert_eq!(result.value(4), 4.0);
+//! ```
+
+use std::borrow::Cow;
+
+use arrow_array::{Array, ArrowPrimitiveType, PrimitiveArray};
+use arrow_buffer::{NullBuffer, ScalarBuffer};
+
+use crate::common::{Float, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::{momentum, overlap, price_transform, volatility};
+
+/// Returns the values of an Arrow array as a slice, with null slots as NaN
+///
+/// Borrows the underlying buffer when the array has no nulls and copies otherwise.
+pub fn values<T>(array: &PrimitiveArray<T>) -> Cow<'_, [T::Native]>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    match array.nulls() {
+        Some(nulls) if nulls.null_count() > 0 => Cow::Owned(
+            array
+                .values()
+                .iter()
+                .zip(nulls.iter())
+                .map(|(&v, valid)| if valid { v } else { T::Native::NAN })
+                .collect(),
+        ),
+        _ => Cow::Borrowed(array.values()),
+    }
+}
+
+/// Converts indicator output into an Arrow array, marking NaN values as null
+pub fn to_array<T>(values: Vec<T::Native>) -> PrimitiveArray<T>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    let nulls = NullBuffer::from_iter(values.iter().map(|v| !v.is_nan()));
+    let nulls = (nulls.null_count() > 0).then_some(nulls);
+    PrimitiveArray::new(ScalarBuffer::from(values), nulls)
+}
+
+/// Applies a single-input indicator to an Arrow array
+///
+/// # Example
+/// ```
+/// use arrow_array::Float64Array;
+/// use ta_rust::interop::arrow;
+///
+/// let close = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+/// let result = arrow::apply(&close, |data| ta_rust::overlap::wma(data, 2)).unwrap();
+/// ```
+pub fn apply<T, F>(input: &PrimitiveArray<T>, f: F) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+    F: FnOnce(&[T::Native]) -> TAResult<Vec<T::Native>>,
+{
+    f(&values(input)).map(to_array)
+}
+
+/// Applies a high/low/close indicator to three Arrow arrays
+pub fn apply_hlc<T, F>(
+    high: &PrimitiveArray<T>,
+    low: &PrimitiveArray<T>,
+    close: &PrimitiveArray<T>,
+    f: F,
+) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+    F: FnOnce(&[T::Native], &[T::Native], &[T::Native]) -> TAResult<Vec<T::Native>>,
+{
+    f(&values(high), &values(low), &values(close)).map(to_array)
+}
+
+/// Simple Moving Average over an Arrow array
+pub fn sma<T>(input: &PrimitiveArray<T>, period: impl IntoParam<Period>) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("SMA", || {
+        let period = period.into_param("period")?.get();
+        apply(input, |data| overlap::sma(data, period))
+    })
+}
+
+/// Exponential Moving Average over an Arrow array
+pub fn ema<T>(input: &PrimitiveArray<T>, period: impl IntoParam<Period>) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("EMA", || {
+        let period = period.into_param("period")?.get();
+        apply(input, |data| overlap::ema(data, period))
+    })
+}
+
+/// Weighted Moving Average over an Arrow array
+pub fn wma<T>(input: &PrimitiveArray<T>, period: impl IntoParam<Period>) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("WMA", || {
+        let period = period.into_param("period")?.get();
+        apply(input, |data| overlap::wma(data, period))
+    })
+}
+
+/// Momentum over an Arrow array
+pub fn mom<T>(input: &PrimitiveArray<T>, period: impl IntoParam<Period>) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        apply(input, |data| momentum::mom(data, period))
+    })
+}
+
+/// Rate of Change over an Arrow array
+pub fn roc<T>(input: &PrimitiveArray<T>, period: impl IntoParam<Period>) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("ROC", || {
+        let period = period.into_param("period")?.get();
+        apply(input, |data| momentum::roc(data, period))
+    })
+}
+
+/// Relative Strength Index over an Arrow array
+pub fn rsi<T>(input: &PrimitiveArray<T>, period: impl IntoParam<Period>) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        apply(input, |data| momentum::rsi(data, period))
+    })
+}
+
+/// True Range over Arrow high/low/close arrays
+pub fn trange<T>(
+    high: &PrimitiveArray<T>,
+    low: &PrimitiveArray<T>,
+    close: &PrimitiveArray<T>,
+) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("TRANGE", || {
+        apply_hlc(high, low, close, volatility::trange)
+    })
+}
+
+/// Average True Range over Arrow high/low/close arrays
+pub fn atr<T>(
+    high: &PrimitiveArray<T>,
+    low: &PrimitiveArray<T>,
+    close: &PrimitiveArray<T>,
+    period: impl IntoParam<Period>,
+) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        apply_hlc(high, low, close, |h, l, c| volatility::atr(h, l, c, period))
+    })
+}
+
+/// Typical Price over Arrow high/low/close arrays
+pub fn typprice<T>(
+    high: &PrimitiveArray<T>,
+    low: &PrimitiveArray<T>,
+    close: &PrimitiveArray<T>,
+) -> TAResult<PrimitiveArray<T>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Float,
+{
+    with_function("TYPPRICE", || {
+        apply_hlc(high, low, close, price_transform::typprice)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Float32Array, Float64Array};
+
+    #[test]
+    fn test_values_borrows_without_nulls() {
+        let array = Float64Array::from(vec![1.0, 2.0, 3.0]);
+        assert!(matches!(values(&array), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_values_nulls_become_nan() {
+        let array = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+        let v = values(&array);
+        assert_eq!(v[0], 1.0);
+        assert!(v[1].is_nan());
+    }
+
+    #[test]
+    fn test_sma_warmup_is_null() {
+        let close = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        let result = sma(&close, 3).unwrap();
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(result.null_count(), 2);
+        assert!(result.is_null(0) && result.is_null(1));
+        assert_eq!(result.values()[2..], [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_rsi_f32() {
+        let close = Float32Array::from(
+            (0..30)
+                .map(|i| 10.0 + (i as f32 * 0.5).sin())
+                .collect::<Vec<_>>(),
+        );
+        let result = rsi(&close, 14).unwrap();
+        assert_eq!(result.null_count(), 14);
+    }
+
+    #[test]
+    fn test_atr_mismatched_lengths() {
+        let high = Float64Array::from(vec![2.0, 3.0]);
+        let low = Float64Array::from(vec![1.0]);
+        let close = Float64Array::from(vec![1.5, 2.5]);
+        assert!(atr(&high, &low, &close, 1).is_err());
+        assert_eq!(atr(&high, &high, &close, 0).unwrap_err().function(), Some("ATR"));
+    }
+}
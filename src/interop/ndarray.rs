@@ -0,0 +1,298 @@
+//! ndarray adapters for matrix-style, multi-symbol computation
+//!
+//! The `*_axis` functions take an [`ArrayView2`] and run the indicator along every
+//! lane of the given axis. For a `symbols × time` matrix, `Axis(1)` computes each row
+//! (one series per symbol); for a `time × symbols` matrix use `Axis(0)`. The result
+//! has the same shape as the input, with the usual NaN warm-up in every lane.
+//!
+//! Lanes are computed with the allocation-free `*_into` variants. Contiguous lanes are
+//! read and written in place; strided lanes go through a scratch buffer.
+//!
+//! # Example
+//! ```
+//! use ndarray::{array, Axis};
+//! use ta_rust::interop::ndarray::sma_axis;
+//!
+//! let prices = array![[1.0, 2.0, 3.0, 4.0], [10.0, 20.0, 30.0, 40.0]];
+//! let result = sma_axis(prices.view(), 2, Axis(1)).unwrap();
+//!
+//! assert_eq!(result.dim(), (2, 4));
+//! assert_eq!(result[[1, 3]], 35.0);
+//! ```
+
+use ndarray::{Array2, ArrayView1, ArrayView2, ArrayViewMut1, Axis};
+
+use crate::common::{Float, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::{momentum, overlap, volatility};
+
+/// Applies an `*_into` style indicator along every lane of `axis`
+///
+/// Errors of `f` are returned unchanged, naming the indicator; an invalid `axis` names
+/// `APPLY_AXIS`.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Axis};
+/// use ta_rust::interop::ndarray::apply_axis;
+///
+/// let prices = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+/// // time × symbols: compute down each column
+/// let result = apply_axis(prices.view(), Axis(0), |data, out| {
+///     ta_rust::math_operators::sum_into(data, 2, out)
+/// })
+/// .unwrap();
+/// assert_eq!(result[[2, 1]], 10.0);
+/// ```
+pub fn apply_axis<T, F>(data: ArrayView2<'_, T>, axis: Axis, mut f: F) -> TAResult<Array2<T>>
+where
+    T: Float,
+    F: FnMut(&[T], &mut [T]) -> TAResult<usize>,
+{
+    with_function("APPLY_AXIS", || check_axis(axis))?;
+    let mut output = Array2::from_elem(data.raw_dim(), T::NAN);
+    let mut input_buf = Vec::new();
+    let mut output_buf = Vec::new();
+
+    for (lane, out) in data.lanes(axis).into_iter().zip(output.lanes_mut(axis)) {
+        let input = lane_slice(&lane, &mut input_buf);
+        write_lane(out, &mut output_buf, |out| f(input, out))?;
+    }
+
+    Ok(output)
+}
+
+/// Applies an `*_into` style high/low/close indicator along every lane of `axis`
+pub fn apply_axis_hlc<T, F>(
+    high: ArrayView2<'_, T>,
+    low: ArrayView2<'_, T>,
+    close: ArrayView2<'_, T>,
+    axis: Axis,
+    mut f: F,
+) -> TAResult<Array2<T>>
+where
+    T: Float,
+    F: FnMut(&[T], &[T], &[T], &mut [T]) -> TAResult<usize>,
+{
+    with_function("APPLY_AXIS_HLC", || {
+        check_axis(axis)?;
+        if high.dim() != low.dim() || high.dim() != close.dim() {
+            return Err(TAError::mismatched_inputs(format!(
+                "high {:?}, low {:?}, close {:?}",
+                high.dim(),
+                low.dim(),
+                close.dim()
+            )));
+        }
+        Ok(())
+    })?;
+
+    let mut output = Array2::from_elem(high.raw_dim(), T::NAN);
+    let (mut high_buf, mut low_buf, mut close_buf) = (Vec::new(), Vec::new(), Vec::new());
+    let mut output_buf = Vec::new();
+
+    let lanes = high
+        .lanes(axis)
+        .into_iter()
+        .zip(low.lanes(axis))
+        .zip(close.lanes(axis))
+        .zip(output.lanes_mut(axis));
+    for (((h, l), c), out) in lanes {
+        let h = lane_slice(&h, &mut high_buf);
+        let l = lane_slice(&l, &mut low_buf);
+        let c = lane_slice(&c, &mut close_buf);
+        write_lane(out, &mut output_buf, |out| f(h, l, c, out))?;
+    }
+
+    Ok(output)
+}
+
+/// Simple Moving Average along each lane of `axis`
+pub fn sma_axis<T: Float>(
+    data: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("SMA", || {
+        let period = period.into_param("period")?.get();
+        apply_axis(data, axis, |d, out| overlap::sma_into(d, period, out))
+    })
+}
+
+/// Exponential Moving Average along each lane of `axis`
+pub fn ema_axis<T: Float>(
+    data: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("EMA", || {
+        let period = period.into_param("period")?.get();
+        apply_axis(data, axis, |d, out| overlap::ema_into(d, period, out))
+    })
+}
+
+/// Weighted Moving Average along each lane of `axis`
+pub fn wma_axis<T: Float>(
+    data: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("WMA", || {
+        let period = period.into_param("period")?.get();
+        apply_axis(data, axis, |d, out| overlap::wma_into(d, period, out))
+    })
+}
+
+/// Momentum along each lane of `axis`
+pub fn mom_axis<T: Float>(
+    data: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        apply_axis(data, axis, |d, out| momentum::mom_into(d, period, out))
+    })
+}
+
+/// Rate of Change along each lane of `axis`
+pub fn roc_axis<T: Float>(
+    data: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("ROC", || {
+        let period = period.into_param("period")?.get();
+        apply_axis(data, axis, |d, out| momentum::roc_into(d, period, out))
+    })
+}
+
+/// Relative Strength Index along each lane of `axis`
+pub fn rsi_axis<T: Float>(
+    data: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        apply_axis(data, axis, |d, out| momentum::rsi_into(d, period, out))
+    })
+}
+
+/// True Range along each lane of `axis`
+pub fn trange_axis<T: Float>(
+    high: ArrayView2<'_, T>,
+    low: ArrayView2<'_, T>,
+    close: ArrayView2<'_, T>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("TRANGE", || {
+        apply_axis_hlc(high, low, close, axis, volatility::trange_into)
+    })
+}
+
+/// Average True Range along each lane of `axis`
+pub fn atr_axis<T: Float>(
+    high: ArrayView2<'_, T>,
+    low: ArrayView2<'_, T>,
+    close: ArrayView2<'_, T>,
+    period: impl IntoParam<Period>,
+    axis: Axis,
+) -> TAResult<Array2<T>> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        apply_axis_hlc(high, low, close, axis, |h, l, c, out| {
+            volatility::atr_into(h, l, c, period, out)
+        })
+    })
+}
+
+fn check_axis(axis: Axis) -> TAResult<()> {
+    if axis.index() > 1 {
+        return Err(TAError::invalid_parameter(
+            "axis".to_string(),
+            format!("must be Axis(0) or Axis(1), got Axis({})", axis.index()),
+        ));
+    }
+    Ok(())
+}
+
+// Returns the lane as a slice, copying strided lanes into `buf`
+fn lane_slice<'a, T: Float>(lane: &'a ArrayView1<'_, T>, buf: &'a mut Vec<T>) -> &'a [T] {
+    match lane.as_slice() {
+        Some(slice) => slice,
+        None => {
+            buf.clear();
+            buf.extend(lane.iter().copied());
+            buf
+        }
+    }
+}
+
+// Runs `f` on the output lane, via `buf` when the lane is strided
+fn write_lane<T, F>(mut lane: ArrayViewMut1<'_, T>, buf: &mut Vec<T>, f: F) -> TAResult<()>
+where
+    T: Float,
+    F: FnOnce(&mut [T]) -> TAResult<usize>,
+{
+    if let Some(slice) = lane.as_slice_mut() {
+        return f(slice).map(|_| ());
+    }
+
+    buf.clear();
+    buf.resize(lane.len(), T::NAN);
+    f(buf)?;
+    lane.assign(&ArrayView1::from(&buf[..]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use ndarray::{array, Array2};
+
+    fn matrix() -> Array2<f64> {
+        Array2::from_shape_fn((3, 40), |(s, t)| {
+            100.0 + (s as f64 + 1.0) * (t as f64 * 0.3).sin()
+        })
+    }
+
+    #[test]
+    fn test_rsi_axis_matches_rows() {
+        let data = matrix();
+        let result = rsi_axis(data.view(), 14, Axis(1)).unwrap();
+
+        for (row, out) in data.rows().into_iter().zip(result.rows()) {
+            let expected = momentum::rsi(&row.to_vec(), 14).unwrap();
+            assert_arrays_approx_equal(out.as_slice().unwrap(), &expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_axis0_matches_transposed() {
+        let data = matrix();
+        let by_row = sma_axis(data.view(), 5, Axis(1)).unwrap();
+        let by_col = sma_axis(data.t(), 5, Axis(0)).unwrap();
+
+        let transposed = by_col.t();
+        for (a, b) in by_row.iter().zip(transposed.iter()) {
+            assert!((a.is_nan() && b.is_nan()) || a == b);
+        }
+    }
+
+    #[test]
+    fn test_atr_axis_f32_and_errors() {
+        let high = array![[2.0f32, 3.0, 4.0], [5.0, 6.0, 7.0]];
+        let low = array![[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let close = array![[1.5f32, 2.5, 3.5], [4.5, 5.5, 6.5]];
+        let result = atr_axis(high.view(), low.view(), close.view(), 2, Axis(1)).unwrap();
+        assert!(result[[0, 0]].is_nan());
+        assert_eq!(result[[1, 2]], 1.375);
+
+        let short = array![[1.0f32, 2.0]];
+        assert!(atr_axis(high.view(), short.view(), close.view(), 1, Axis(1)).is_err());
+        assert_eq!(sma_axis(high.view(), 1, Axis(2)).unwrap_err().function(), Some("SMA"));
+        assert_eq!(sma_axis(high.view(), 0, Axis(1)).unwrap_err().function(), Some("SMA"));
+    }
+}
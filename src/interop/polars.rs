@@ -0,0 +1,221 @@
+//! Polars DataFrame helpers
+//!
+//! [`DataFrameTaExt::ta`] exposes indicators that read DataFrame columns and append
+//! the result as a new `Float64` column. Input columns are cast to `Float64` and
+//! nulls are read as NaN; NaN in the output (warm-up period, undefined values) is
+//! written back as null. Each method returns the helper again so calls can be chained.
+//!
+//! # Example
+//! ```
+//! use polars::prelude::*;
+//! use ta_rust::interop::polars::DataFrameTaExt;
+//!
+//! let mut df = df!("close" => [1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+//! df.ta().sma("close", 3).unwrap().mom("close", 1).unwrap();
+//!
+//! assert_eq!(df.column("close_sma_3").unwrap().null_count(), 2);
+//! assert!(df.column("close_mom_1").is_ok());
+//! ```
+
+use std::borrow::Cow;
+
+use polars::prelude::*;
+
+use crate::common::{TAError, TAResult};
+use crate::{momentum, overlap, price_transform, volatility};
+
+/// Extension trait adding technical analysis helpers to [`DataFrame`]
+pub trait DataFrameTaExt {
+    /// Returns a helper that computes indicators over this frame's columns
+    fn ta(&mut self) -> TaFrame<'_>;
+}
+
+impl DataFrameTaExt for DataFrame {
+    fn ta(&mut self) -> TaFrame<'_> {
+        TaFrame { df: self }
+    }
+}
+
+/// Indicator helper borrowed from a [`DataFrame`]
+///
+/// Created by [`DataFrameTaExt::ta`]. Output columns are named after the input column, the
+/// indicator and its period (`close_rsi_14`); use [`TaFrame::apply`] to pick a different name.
+pub struct TaFrame<'a> {
+    df: &'a mut DataFrame,
+}
+
+impl TaFrame<'_> {
+    /// Computes an indicator over the named input columns and stores it as `output`
+    ///
+    /// An existing column called `output` is replaced.
+    ///
+    /// # Example
+    /// ```
+    /// use polars::prelude::*;
+    /// use ta_rust::interop::polars::DataFrameTaExt;
+    ///
+    /// let mut df = df!("close" => [1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    /// df.ta()
+    ///     .apply(&["close"], "trima", |cols| ta_rust::overlap::trima(cols[0], 3))
+    ///     .unwrap();
+    /// ```
+    pub fn apply<F>(&mut self, inputs: &[&str], output: &str, f: F) -> PolarsResult<&mut Self>
+    where
+        F: FnOnce(&[&[f64]]) -> TAResult<Vec<f64>>,
+    {
+        let columns = inputs
+            .iter()
+            .map(|name| column_values(self.df.column(name)?))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let slices: Vec<&[f64]> = columns.iter().map(|c| c.as_ref()).collect();
+
+        let values = f(&slices).map_err(to_polars_error)?;
+        self.df.with_column(to_series(output, &values))?;
+        Ok(self)
+    }
+
+    /// Simple Moving Average, stored as `{column}_sma_{period}`
+    pub fn sma(&mut self, column: &str, period: usize) -> PolarsResult<&mut Self> {
+        let output = format!("{}_sma_{}", column, period);
+        self.apply(&[column], &output, |c| overlap::sma(c[0], period))
+    }
+
+    /// Exponential Moving Average, stored as `{column}_ema_{period}`
+    pub fn ema(&mut self, column: &str, period: usize) -> PolarsResult<&mut Self> {
+        let output = format!("{}_ema_{}", column, period);
+        self.apply(&[column], &output, |c| overlap::ema(c[0], period))
+    }
+
+    /// Weighted Moving Average, stored as `{column}_wma_{period}`
+    pub fn wma(&mut self, column: &str, period: usize) -> PolarsResult<&mut Self> {
+        let output = format!("{}_wma_{}", column, period);
+        self.apply(&[column], &output, |c| overlap::wma(c[0], period))
+    }
+
+    /// Momentum, stored as `{column}_mom_{period}`
+    pub fn mom(&mut self, column: &str, period: usize) -> PolarsResult<&mut Self> {
+        let output = format!("{}_mom_{}", column, period);
+        self.apply(&[column], &output, |c| momentum::mom(c[0], period))
+    }
+
+    /// Rate of Change, stored as `{column}_roc_{period}`
+    pub fn roc(&mut self, column: &str, period: usize) -> PolarsResult<&mut Self> {
+        let output = format!("{}_roc_{}", column, period);
+        self.apply(&[column], &output, |c| momentum::roc(c[0], period))
+    }
+
+    /// Relative Strength Index, stored as `{column}_rsi_{period}`
+    pub fn rsi(&mut self, column: &str, period: usize) -> PolarsResult<&mut Self> {
+        let output = format!("{}_rsi_{}", column, period);
+        self.apply(&[column], &output, |c| momentum::rsi(c[0], period))
+    }
+
+    /// True Range, stored as `trange`
+    pub fn trange(&mut self, high: &str, low: &str, close: &str) -> PolarsResult<&mut Self> {
+        self.apply(&[high, low, close], "trange", |c| {
+            volatility::trange(c[0], c[1], c[2])
+        })
+    }
+
+    /// Average True Range, stored as `atr_{period}`
+    pub fn atr(
+        &mut self,
+        high: &str,
+        low: &str,
+        close: &str,
+        period: usize,
+    ) -> PolarsResult<&mut Self> {
+        let output = format!("atr_{}", period);
+        self.apply(&[high, low, close], &output, |c| {
+            volatility::atr(c[0], c[1], c[2], period)
+        })
+    }
+
+    /// Typical Price, stored as `typprice`
+    pub fn typprice(&mut self, high: &str, low: &str, close: &str) -> PolarsResult<&mut Self> {
+        self.apply(&[high, low, close], "typprice", |c| {
+            price_transform::typprice(c[0], c[1], c[2])
+        })
+    }
+}
+
+/// Reads a column as `f64` values, with nulls as NaN
+///
+/// Borrows the column's buffer when it is already a contiguous `Float64` column
+/// without nulls.
+pub fn column_values(column: &Column) -> PolarsResult<Cow<'_, [f64]>> {
+    if let Ok(ca) = column.f64() {
+        if let Ok(slice) = ca.cont_slice() {
+            return Ok(Cow::Borrowed(slice));
+        }
+    }
+
+    let cast = column.cast(&DataType::Float64)?;
+    let values = cast.f64()?.iter().map(|v| v.unwrap_or(f64::NAN)).collect();
+    Ok(Cow::Owned(values))
+}
+
+/// Builds a `Float64` series from indicator output, writing NaN as null
+pub fn to_series(name: &str, values: &[f64]) -> Series {
+    Float64Chunked::from_iter_options(
+        name.into(),
+        values.iter().map(|&v| (!v.is_nan()).then_some(v)),
+    )
+    .into_series()
+}
+
+fn to_polars_error(err: TAError) -> PolarsError {
+    PolarsError::ComputeError(err.to_string().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_appends_column() {
+        let close: Vec<f64> = (0..30).map(|i| 10.0 + (i as f64 * 0.5).sin()).collect();
+        let mut df = df!("close" => close.clone()).unwrap();
+        df.ta().rsi("close", 14).unwrap();
+
+        let result = df.column("close_rsi_14").unwrap();
+        assert_eq!(result.null_count(), 14);
+
+        let expected = momentum::rsi(&close, 14).unwrap();
+        let actual = result.f64().unwrap().get(20).unwrap();
+        crate::assert_float_eq!(actual, expected[20], 1e-12);
+    }
+
+    #[test]
+    fn test_nulls_and_integer_columns() {
+        let mut df = df!(
+            "close" => [Some(1.0), None, Some(3.0), Some(4.0), Some(5.0), Some(6.0)],
+            "volume" => [1i64, 2, 3, 4, 5, 6]
+        )
+        .unwrap();
+        df.ta().sma("close", 2).unwrap().sma("volume", 2).unwrap();
+
+        let close_sma = df.column("close_sma_2").unwrap().f64().unwrap();
+        assert_eq!(close_sma.get(0), None);
+        assert_eq!(close_sma.get(1), None);
+        assert_eq!(close_sma.get(2), None);
+        assert_eq!(close_sma.get(3), Some(3.5));
+        assert_eq!(df.column("volume_sma_2").unwrap().null_count(), 1);
+        assert_eq!(df.width(), 4);
+    }
+
+    #[test]
+    fn test_hlc_and_errors() {
+        let mut df = df!(
+            "high" => [10.0, 11.0, 12.0, 13.0],
+            "low" => [9.0, 9.5, 10.5, 11.5],
+            "close" => [9.5, 10.5, 11.5, 12.5]
+        )
+        .unwrap();
+        df.ta().atr("high", "low", "close", 2).unwrap();
+        assert!(df.column("atr_2").is_ok());
+
+        assert!(df.ta().rsi("missing", 2).is_err());
+        assert!(df.ta().rsi("close", 0).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Lazy Indicator Iterators
+//!
+//! Iterator adapters computing indicators as values are pulled, such as
+//! `prices.iter().sma(20)`, without building intermediate vectors. Each adapter wraps one of
+//! the streaming states, [`MaState`] or [`RsiState`], and yields `Option<f64>`: `None`
+//! during warm-up, then the same values as the batch function.
+//!
+//! Adapters accept `f64`, `&f64` and `Option<f64>` items, so they compose: a `None` from
+//! an earlier stage passes through without updating the later one, and
+//! `prices.iter().sma(5)?.rsi(14)?` is the RSI of the 5-bar SMA, warming up once the SMA
+//! has. Any [`StreamingIndicator`] taking one value per bar plugs in with
+//! [`IndicatorIter::indicator`], yielding its output, a tuple for multi-output states such
+//! as [`MacdState`].
+//!
+//! For data too large to hold in memory, [`chunked`] computes an indicator over a sequence
+//! of slices, such as blocks of a memory-mapped file decoded by [`le_f64_chunks`], carrying
+//! the streaming state across chunk boundaries.
+
+use crate::common::errors::with_function;
+use crate::common::{Float, IntoParam, MAType, Period, TAError, TAResult, Timestamp};
+use crate::momentum::{AdxState, DiState, MacdState, RsiState, StochState};
+use crate::overlap::{BbandsState, EmaTimeState, KalmanState, KalmanTrendState, MaState, McGinleyState, SarState, SmaWindow};
+use crate::volatility::AtrState;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// An indicator updated one bar at a time
+///
+/// `Input` is what one bar feeds it, a price or a tuple such as `(high, low, close)`, and
+/// `Output` what it produces once warm, a value or a tuple of several.
+pub trait StreamingIndicator {
+    /// One bar of input
+    type Input;
+    /// The indicator for one bar
+    type Output;
+
+    /// Feeds the next bar and returns the indicator, or `None` during warm-up
+    fn next_value(&mut self, input: Self::Input) -> Option<Self::Output>;
+}
+
+/// Implements [`StreamingIndicator`] by forwarding to a state's `update`, which takes the
+/// fields of `Input` as separate arguments
+macro_rules! streaming {
+    ($state:ty, f64 => $output:ty) => {
+        impl StreamingIndicator for $state {
+            type Input = f64;
+            type Output = $output;
+
+            fn next_value(&mut self, input: f64) -> Option<$output> {
+                self.update(input)
+            }
+        }
+    };
+    ($state:ty, ($($field:ident: $ty:ty),+) => $output:ty) => {
+        impl StreamingIndicator for $state {
+            type Input = ($($ty,)+);
+            type Output = $output;
+
+            fn next_value(&mut self, ($($field,)+): ($($ty,)+)) -> Option<$output> {
+                self.update($($field),+)
+            }
+        }
+    };
+}
+
+streaming!(MaState, f64 => f64);
+streaming!(RsiState, f64 => f64);
+streaming!(McGinleyState, f64 => f64);
+streaming!(KalmanState, f64 => f64);
+streaming!(KalmanTrendState, f64 => (f64, f64));
+streaming!(MacdState, f64 => (f64, f64, f64));
+streaming!(BbandsState, f64 => (f64, f64, f64));
+streaming!(EmaTimeState, (timestamp: Timestamp, value: f64) => f64);
+streaming!(SarState, (high: f64, low: f64) => f64);
+streaming!(AtrState, (high: f64, low: f64, close: f64) => f64);
+streaming!(AdxState, (high: f64, low: f64, close: f64) => f64);
+streaming!(DiState, (high: f64, low: f64, close: f64) => (f64, f64));
+streaming!(StochState, (high: f64, low: f64, close: f64) => (f64, f64));
+
+impl<const N: usize, T: Float> StreamingIndicator for SmaWindow<N, T> {
+    type Input = T;
+    type Output = T;
+
+    fn next_value(&mut self, input: T) -> Option<T> {
+        self.update(input)
+    }
+}
+
+/// An item an indicator adapter can consume: a value, or `None` for a missing one
+pub trait StreamValue {
+    /// The value, or `None` if there is none
+    fn stream_value(self) -> Option<f64>;
+}
+
+impl StreamValue for f64 {
+    fn stream_value(self) -> Option<f64> {
+        Some(self)
+    }
+}
+
+impl StreamValue for &f64 {
+    fn stream_value(self) -> Option<f64> {
+        Some(*self)
+    }
+}
+
+impl StreamValue for Option<f64> {
+    fn stream_value(self) -> Option<f64> {
+        self
+    }
+}
+
+/// Iterator computing an indicator over the values of another, created by the methods of
+/// [`IndicatorIter`]
+#[derive(Debug, Clone)]
+pub struct Streamed<I, S> {
+    iter: I,
+    state: S,
+}
+
+impl<I, S> Streamed<I, S> {
+    /// Streaming state of the indicator, holding its current value
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+}
+
+impl<I, S> Iterator for Streamed<I, S>
+where
+    I: Iterator,
+    I::Item: StreamValue,
+    S: StreamingIndicator<Input = f64>,
+{
+    type Item = Option<S::Output>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some(item.stream_value().and_then(|value| self.state.next_value(value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, S> ExactSizeIterator for Streamed<I, S>
+where
+    I: ExactSizeIterator,
+    I::Item: StreamValue,
+    S: StreamingIndicator<Input = f64>,
+{
+}
+
+/// Indicator adapters for iterators of values
+///
+/// # Example
+/// ```rust
+/// use ta_rust::iter::IndicatorIter;
+/// use ta_rust::overlap::sma;
+///
+/// let prices = vec![10.0, 11.0, 12.0, 11.0, 13.0, 14.0];
+/// let lazy: Vec<Option<f64>> = prices.iter().sma(3).unwrap().collect();
+///
+/// let batch = sma(&prices, 3).unwrap();
+/// assert_eq!(lazy[1], None);
+/// assert_eq!(lazy[5], Some(batch[5]));
+///
+/// // Stages compose, each warming up after the one before it
+/// let smoothed_rsi: Vec<Option<f64>> = prices.iter().sma(2).unwrap().rsi(2).unwrap().collect();
+/// assert!(smoothed_rsi[..3].iter().all(Option::is_none));
+/// assert!(smoothed_rsi[3].is_some());
+/// ```
+pub trait IndicatorIter: Iterator + Sized
+where
+    Self::Item: StreamValue,
+{
+    /// Simple moving average, as [`sma`](crate::overlap::sma)
+    fn sma(self, period: impl IntoParam<Period>) -> TAResult<Streamed<Self, MaState>> {
+        self.ma(period, MAType::SMA)
+    }
+
+    /// Exponential moving average, as [`ema`](crate::overlap::ema)
+    fn ema(self, period: impl IntoParam<Period>) -> TAResult<Streamed<Self, MaState>> {
+        self.ma(period, MAType::EMA)
+    }
+
+    /// Moving average of any type [`MaState`] streams, as [`ma`](crate::overlap::ma)
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    /// - `UnsupportedOperation` for KAMA, MAMA and T3
+    fn ma(self, period: impl IntoParam<Period>, ma_type: MAType) -> TAResult<Streamed<Self, MaState>> {
+        Ok(self.indicator(MaState::new(period, ma_type)?))
+    }
+
+    /// Relative Strength Index, as [`rsi`](crate::momentum::rsi)
+    fn rsi(self, period: impl IntoParam<Period>) -> TAResult<Streamed<Self, RsiState>> {
+        Ok(self.indicator(RsiState::new(period)?))
+    }
+
+    /// Any streaming indicator taking one value per bar
+    fn indicator<S: StreamingIndicator<Input = f64>>(self, state: S) -> Streamed<Self, S> {
+        Streamed { iter: self, state }
+    }
+}
+
+impl<I> IndicatorIter for I
+where
+    I: Iterator,
+    I::Item: StreamValue,
+{
+}
+
+/// Iterator computing an indicator chunk by chunk, created by [`chunked`]
+#[derive(Debug, Clone)]
+pub struct Chunked<I, S> {
+    chunks: I,
+    state: S,
+}
+
+impl<I, S> Chunked<I, S> {
+    /// Streaming state of the indicator, as of the end of the last chunk
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Consumes the iterator, returning the state to continue from
+    pub fn into_state(self) -> S {
+        self.state
+    }
+}
+
+impl<I, S> Iterator for Chunked<I, S>
+where
+    I: Iterator,
+    I::Item: AsRef<[S::Input]>,
+    S: StreamingIndicator<Output = f64>,
+    S::Input: Copy,
+{
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+        Some(chunk.as_ref().iter().map(|&input| self.state.next_value(input).unwrap_or(f64::NAN)).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+/// Computes an indicator over a series given in chunks
+///
+/// Yields one output vector per chunk, as long as the chunk, with NaN during warm-up. The
+/// state carries over from one chunk to the next, so concatenating the outputs gives the
+/// indicator of the concatenated chunks, whatever their sizes; only one chunk and its
+/// output are in memory at a time. Chunks hold the state's input, such as
+/// `(high, low, close)` tuples for [`AtrState`].
+///
+/// # Example
+/// ```rust
+/// use ta_rust::iter::chunked;
+/// use ta_rust::overlap::{ema, MaState};
+/// use ta_rust::common::MAType;
+///
+/// let prices: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.2).sin()).collect();
+/// let state = MaState::new(10, MAType::EMA).unwrap();
+/// let outputs: Vec<f64> = chunked(state, prices.chunks(32)).flatten().collect();
+///
+/// let batch = ema(&prices, 10).unwrap();
+/// assert!(outputs[8].is_nan());
+/// assert!((outputs[50] - batch[50]).abs() < 1e-12);
+/// ```
+pub fn chunked<I, S>(state: S, chunks: I) -> Chunked<I::IntoIter, S>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[S::Input]>,
+    S: StreamingIndicator<Output = f64>,
+    S::Input: Copy,
+{
+    Chunked { chunks: chunks.into_iter(), state }
+}
+
+/// Iterator decoding little-endian `f64` values in chunks, created by [`le_f64_chunks`]
+#[derive(Debug, Clone)]
+pub struct LeF64Chunks<'a> {
+    bytes: core::slice::Chunks<'a, u8>,
+}
+
+impl Iterator for LeF64Chunks<'_> {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.bytes.next().map(decode_le_f64)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bytes.size_hint()
+    }
+}
+
+fn decode_le_f64(bytes: &[u8]) -> Vec<f64> {
+    bytes
+        .chunks_exact(8)
+        .map(|value| f64::from_le_bytes([value[0], value[1], value[2], value[3], value[4], value[5], value[6], value[7]]))
+        .collect()
+}
+
+/// Decodes raw little-endian `f64` values, such as a memory-mapped file, `chunk_len` at a time
+///
+/// The last chunk may be shorter. Only the chunk being decoded is copied, so a mapped file
+/// of any size can be fed to [`chunked`].
+///
+/// # Errors
+/// - `InvalidParameter` if `chunk_len` is 0, or its size in bytes overflows `usize`
+/// - `InvalidInput` if the byte length is not a multiple of 8
+///
+/// # Example
+/// ```rust
+/// use ta_rust::iter::le_f64_chunks;
+///
+/// let bytes: Vec<u8> = [1.5f64, 2.5, 3.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+/// let chunks: Vec<Vec<f64>> = le_f64_chunks(&bytes, 2).unwrap().collect();
+/// assert_eq!(chunks, vec![vec![1.5, 2.5], vec![3.5]]);
+/// ```
+///
+/// A file of closes mapped with `memmap2`, its EMA written to another file, holding one
+/// chunk of values at a time:
+/// ```rust
+/// use std::fs::File;
+/// use std::io::{BufWriter, Write};
+/// use memmap2::Mmap;
+/// use ta_rust::common::MAType;
+/// use ta_rust::iter::{chunked, le_f64_chunks};
+/// use ta_rust::overlap::{ema, MaState};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = std::env::temp_dir().join(format!("ta-rust-mmap-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir)?;
+/// # let closes: Vec<f64> = (0..5_000).map(|i| 100.0 + (i as f64 * 0.01).sin()).collect();
+/// # std::fs::write(dir.join("closes.f64"), closes.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>())?;
+/// let file = File::open(dir.join("closes.f64"))?;
+/// // Safety: the file is not modified while mapped
+/// let map = unsafe { Mmap::map(&file)? };
+///
+/// let mut writer = BufWriter::new(File::create(dir.join("ema.f64"))?);
+/// let mut outputs = chunked(MaState::new(200, MAType::EMA)?, le_f64_chunks(&map, 1024)?);
+/// for chunk in outputs.by_ref() {
+///     for value in chunk {
+///         writer.write_all(&value.to_le_bytes())?;
+///     }
+/// }
+/// writer.flush()?;
+/// let state = outputs.into_state(); // continue with the next file, or checkpoint it
+/// # assert_eq!(state.current(), ema(&closes, 200)?.last().copied());
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn le_f64_chunks(bytes: &[u8], chunk_len: usize) -> TAResult<LeF64Chunks<'_>> {
+    with_function("LE_F64_CHUNKS", || {
+        if chunk_len == 0 {
+            return Err(TAError::invalid_parameter("chunk_len", "must be greater than 0"));
+        }
+        let chunk_bytes = chunk_len.checked_mul(8).ok_or_else(|| {
+            TAError::invalid_parameter("chunk_len".to_string(), format!("must be at most {}", usize::MAX / 8))
+        })?;
+        if !bytes.len().is_multiple_of(8) {
+            return Err(TAError::invalid_input(format!(
+                "{} bytes is not a whole number of f64 values",
+                bytes.len()
+            )));
+        }
+        // Every chunk, the shorter last one included, is a whole number of values
+        Ok(LeF64Chunks { bytes: bytes.chunks(chunk_bytes) })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::rsi;
+    use crate::overlap::{ema, sma};
+
+    #[test]
+    fn test_adapters_match_batch() {
+        let prices: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.7).sin() * 4.0 + i as f64 * 0.1).collect();
+        for (lazy, batch) in [
+            (prices.iter().sma(7).unwrap().collect::<Vec<_>>(), sma(&prices, 7).unwrap()),
+            (prices.iter().copied().ema(5).unwrap().collect(), ema(&prices, 5).unwrap()),
+            (prices.iter().rsi(14).unwrap().collect(), rsi(&prices, 14).unwrap()),
+        ] {
+            assert_eq!(lazy.len(), batch.len());
+            for (value, expected) in lazy.iter().zip(&batch) {
+                match value {
+                    Some(v) => assert_eq!(*v, *expected),
+                    None => assert!(expected.is_nan()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_composition_and_errors() {
+        let prices = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let chained: Vec<Option<f64>> = prices.iter().sma(2).unwrap().sma(2).unwrap().collect();
+        assert_eq!(chained, vec![None, None, Some(2.0), Some(3.0), Some(4.0), Some(5.0)]);
+
+        let mut stream = prices.iter().ema(3).unwrap();
+        assert_eq!(stream.len(), 6);
+        stream.by_ref().take(3).for_each(drop);
+        assert_eq!(stream.state().current(), Some(2.0));
+
+        assert!(prices.iter().sma(0).is_err());
+        assert!(prices.iter().ma(3, MAType::KAMA).is_err());
+    }
+
+    #[test]
+    fn test_state_constructor_errors_name_the_indicator() {
+        let cases = [
+            (RsiState::new(0).unwrap_err(), "RSI"),
+            (MacdState::new(12, 0, 9).unwrap_err(), "MACD"),
+            (StochState::new(5, 3, MAType::SMA, 0, MAType::SMA).unwrap_err(), "STOCH"),
+            (StochState::new(5, 3, MAType::KAMA, 3, MAType::SMA).unwrap_err(), "STOCH"),
+            (AdxState::new(0).unwrap_err(), "ADX"),
+            (DiState::new(0).unwrap_err(), "DX"),
+            (AtrState::new(0).unwrap_err(), "ATR"),
+            (BbandsState::new(0, 2.0).unwrap_err(), "BBANDS"),
+            (MaState::new(3, MAType::MAMA).unwrap_err(), "MA"),
+            (McGinleyState::new(0).unwrap_err(), "MCGINLEY"),
+            (KalmanState::new(-1.0, 1.0).unwrap_err(), "KALMAN"),
+            (KalmanTrendState::new(1.0, 0.0).unwrap_err(), "KALMANTREND"),
+            (SarState::new(0.3, 0.2).unwrap_err(), "SAR"),
+            (EmaTimeState::new(f64::NAN).unwrap_err(), "EMA_TIME"),
+        ];
+        for (err, function) in cases {
+            assert_eq!(err.function(), Some(function), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_chunked_matches_batch() {
+        let prices: Vec<f64> = (0..250).map(|i| 20.0 + (i as f64 * 0.13).sin() * 2.0 + i as f64 * 0.01).collect();
+        let bytes: Vec<u8> = prices.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let batch = rsi(&prices, 14).unwrap();
+        for chunk_len in [1, 7, 64, 1000] {
+            let mut chunks = chunked(RsiState::new(14).unwrap(), le_f64_chunks(&bytes, chunk_len).unwrap());
+            let first = chunks.next().unwrap();
+            assert_eq!(first.len(), chunk_len.min(prices.len()));
+            let outputs: Vec<f64> = first.into_iter().chain(chunks.by_ref().flatten()).collect();
+            crate::common::assert_arrays_approx_equal(&outputs, &batch, 0.0);
+            assert_eq!(chunks.state().current(), batch.last().copied());
+        }
+
+        assert!(le_f64_chunks(&bytes, 0).is_err());
+        assert!(le_f64_chunks(&bytes[1..], 4).is_err());
+        let err = le_f64_chunks(&bytes, usize::MAX / 4).unwrap_err();
+        assert_eq!(err.kind(), crate::common::ErrorKind::InvalidParameter);
+        assert_eq!(err.function(), Some("LE_F64_CHUNKS"));
+    }
+}
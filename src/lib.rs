@@ -1,15 +1,20 @@
 //! # TA-Rust: Pure Rust Technical Analysis Library
 //!
-//! A pure Rust implementation of TA-Lib (Technical Analysis Library) with 100% compatibility.
+//! A pure Rust implementation of TA-Lib (Technical Analysis Library).
 //! This library provides 158+ technical analysis functions for financial market analysis.
 //!
 //! ## Features
 //!
 //! - **Pure Rust**: No external C dependencies
-//! - **100% TA-Lib Compatible**: Same algorithms, same results
+//! - **TA-Lib Compatible**: Through the [`common::registry`], TA-Lib's functions follow its
+//!   algorithms and are checked against its output; the remaining differences are listed
+//!   in `tests/accuracy_tests`
 //! - **High Performance**: Optimized for speed and memory efficiency
 //! - **Type Safe**: Leverages Rust's type system for correctness
-//! - **No Std Support**: Can be used in embedded environments
+//! - **f32 and f64**: Core indicators are generic over [`common::Float`]
+//! - **No Std Support**: Builds with `--no-default-features --features no_std`
+//!   (requires `alloc`; math is provided by `libm`); [`overlap::SmaWindow`] streams
+//!   without allocating
 //!
 //! ## Quick Start
 //!
@@ -34,14 +39,25 @@
 //! - **Statistic Functions**: Correlation, Linear Regression, etc.
 //! - **Math Transform**: Trigonometric and logarithmic functions
 //! - **Math Operators**: Basic arithmetic operations
+//! - **Signals**: Crossovers and divergence detection on any oscillator
+//! - **Backtesting**: Equity curve, trades and statistics for entry/exit signals
+//! - **Performance**: Sharpe, Sortino, Calmar, drawdown and annualized return/volatility
+//! - **Batch**: Several indicators in one pass with shared intermediate series
+//! - **Transforms**: Rolling normalization and squashing of indicator outputs
+//! - **Features**: Aligned feature matrices built from indicator specs
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+extern crate alloc;
+
+#[cfg(all(not(feature = "std"), not(feature = "no_std")))]
+compile_error!("either the `std` (default) or the `no_std` feature must be enabled");
+
 // Re-export common types and errors
-pub use common::{TAError, TAResult};
+pub use common::{ErrorKind, TAError, TAResult};
 
 // Core modules
 pub mod common;
@@ -60,15 +76,50 @@ pub mod math_transform;
 pub mod volume;
 pub mod statistic;
 
+// Signal generation and evaluation on top of indicator outputs
+pub mod signal;
+pub mod backtest;
+pub mod performance;
+
+// Computing many indicators at once
+pub mod batch;
+
+// Lazy indicator adapters for iterators
+pub mod iter;
+
+// Snapshots of streaming indicators to resume from
+pub mod checkpoint;
+
+pub mod fixed;
+
+// Feature engineering on indicator outputs
+pub mod transform;
+pub mod features;
+
+// Optional adapters for external data libraries
+pub mod interop;
+
+// Loaders for bar data files
+#[cfg(feature = "data")]
+#[cfg_attr(docsrs, doc(cfg(feature = "data")))]
+pub mod data;
+
+// Compute-shader kernels across many symbols
+#[cfg(feature = "gpu")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gpu")))]
+pub mod gpu;
+
+// Candlestick pattern building blocks
+pub mod pattern;
+
 // Function categories (will be implemented in subsequent phases)
 // pub mod cycle;
-// pub mod pattern;
 
 // Prelude for convenient imports
 pub mod prelude {
     //! Convenient re-exports of commonly used items
     
-    pub use crate::common::{TAError, TAResult, MAType, Price, Volume, Period};
+    pub use crate::common::{ErrorKind, TAError, TAResult, MAType, Price, Volume, Float};
     pub use crate::overlap::*;
     pub use crate::price_transform::*;
     pub use crate::math_operators::*;
@@ -77,5 +128,10 @@ pub mod prelude {
     pub use crate::math_transform::*;
     pub use crate::volume::*;
     pub use crate::statistic::*;
+    pub use crate::pattern::*;
+    pub use crate::signal::*;
+    pub use crate::performance::*;
+    pub use crate::transform::*;
+    pub use crate::iter::IndicatorIter;
     // Additional re-exports will be added as modules are implemented
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 //! Vector Arithmetic Add
 
 use crate::common::{TAResult, Price};
+use crate::common::errors::with_function;
 use crate::common::utils::{validate_not_empty, validate_same_length, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Performs element-wise addition of two price arrays
 ///
@@ -31,19 +34,21 @@ use crate::common::utils::{validate_not_empty, validate_same_length, allocate_ou
 /// // result = [5.0, 7.0, 9.0]
 /// ```
 pub fn add(array1: &[Price], array2: &[Price]) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(array1, "array1")?;
-    validate_not_empty(array2, "array2")?;
-    validate_same_length(array1, array2, "array1", "array2")?;
+    with_function("ADD", || {
+        // Input validation
+        validate_not_empty(array1, "array1")?;
+        validate_not_empty(array2, "array2")?;
+        validate_same_length(array1, array2, "array1", "array2")?;
 
-    let mut output = allocate_output(array1.len());
+        let mut output = allocate_output(array1.len());
     
-    // Perform element-wise addition
-    for i in 0..array1.len() {
-        output[i] = array1[i] + array2[i];
-    }
+        // Perform element-wise addition
+        for i in 0..array1.len() {
+            output[i] = array1[i] + array2[i];
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Adds a scalar value to each element of an array
@@ -64,15 +69,17 @@ pub fn add(array1: &[Price], array2: &[Price]) -> TAResult<Vec<Price>> {
 /// // result = [11.0, 12.0, 13.0]
 /// ```
 pub fn add_scalar(array: &[Price], scalar: Price) -> TAResult<Vec<Price>> {
-    validate_not_empty(array, "array")?;
+    with_function("ADD", || {
+        validate_not_empty(array, "array")?;
 
-    let mut output = allocate_output(array.len());
+        let mut output = allocate_output(array.len());
     
-    for i in 0..array.len() {
-        output[i] = array[i] + scalar;
-    }
+        for i in 0..array.len() {
+            output[i] = array[i] + scalar;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -1,7 +1,12 @@
 //! Maximum value over a specified period
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output, highest_in_period, highest_index};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
+use crate::math_operators::rolling_arg::extreme_indices;
+use crate::common::window::RunningMax;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Finds the highest value over a specified period
 ///
@@ -26,24 +31,30 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// // result[2] = max(1,3,2) = 3.0
 /// // result[3] = max(3,2,5) = 5.0
 /// ```
-pub fn max(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn max(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("MAX", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
-    
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        output[i] = highest_in_period(data, start_index, period);
-    }
+        let mut output = allocate_output(data.len());
+        let mut highest = RunningMax::new(period);
+        for (i, &value) in data.iter().enumerate() {
+            let max = highest.push(value);
+            if i + 1 >= period {
+                output[i] = max.unwrap_or(Price::NEG_INFINITY);
+            }
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Finds the index of the highest value over a specified period
 ///
-/// Returns the absolute index (not relative to the window) of the maximum value.
+/// Returns the absolute index (not relative to the window) of the maximum value. Ties
+/// are resolved as in TA-Lib: a later equal value takes over, and when the maximum leaves
+/// the window the earliest remaining tie replaces it.
 ///
 /// # Parameters
 /// - `data`: Slice of price data
@@ -61,21 +72,14 @@ pub fn max(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
 /// // result[2] = 1 (index of value 3.0)
 /// // result[3] = 3 (index of value 5.0)
 /// ```
-pub fn maxindex(data: &[Price], period: Period) -> TAResult<Vec<usize>> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
-
-    let mut output = vec![0; data.len()];
-    
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        let window = &data[start_index..=i];
-        let relative_index = highest_index(window);
-        output[i] = start_index + relative_index;
-    }
+pub fn maxindex(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<usize>> {
+    with_function("MAXINDEX", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    Ok(output)
+        Ok(extreme_indices(data, period, |newer, older| newer > older))
+    })
 }
 
 #[cfg(test)]
@@ -1,7 +1,12 @@
 //! Minimum value over a specified period
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output, lowest_in_period, lowest_index, highest_in_period, highest_index};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
+use crate::math_operators::rolling_arg::extreme_indices;
+use crate::common::window::{RunningMax, RunningMin};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Finds the lowest value over a specified period
 ///
@@ -26,24 +31,29 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// // result[2] = min(3,1,4) = 1.0
 /// // result[3] = min(1,4,2) = 1.0
 /// ```
-pub fn min(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn min(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("MIN", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
-    
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        output[i] = lowest_in_period(data, start_index, period);
-    }
+        let mut output = allocate_output(data.len());
+        let mut lowest = RunningMin::new(period);
+        for (i, &value) in data.iter().enumerate() {
+            let min = lowest.push(value);
+            if i + 1 >= period {
+                output[i] = min.unwrap_or(Price::INFINITY);
+            }
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Finds the index of the lowest value over a specified period
 ///
-/// Returns the absolute index (not relative to the window) of the minimum value.
+/// Returns the absolute index (not relative to the window) of the minimum value. Ties
+/// are resolved as in [`maxindex`](crate::math_operators::maxindex).
 ///
 /// # Parameters
 /// - `data`: Slice of price data
@@ -61,21 +71,14 @@ pub fn min(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
 /// // result[2] = 1 (index of value 1.0)
 /// // result[3] = 1 (index of value 1.0)
 /// ```
-pub fn minindex(data: &[Price], period: Period) -> TAResult<Vec<usize>> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn minindex(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<usize>> {
+    with_function("MININDEX", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = vec![0; data.len()];
-    
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        let window = &data[start_index..=i];
-        let relative_index = lowest_index(window);
-        output[i] = start_index + relative_index;
-    }
-
-    Ok(output)
+        Ok(extreme_indices(data, period, |newer, older| newer < older))
+    })
 }
 
 /// Finds both the lowest and highest values over a specified period
@@ -97,26 +100,31 @@ pub fn minindex(data: &[Price], period: Period) -> TAResult<Vec<usize>> {
 /// let (min_result, max_result) = minmax(&data, 3).unwrap();
 /// // min_result[2] = 1.0, max_result[2] = 4.0
 /// ```
-pub fn minmax(data: &[Price], period: Period) -> TAResult<(Vec<Price>, Vec<Price>)> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn minmax(data: &[Price], period: impl IntoParam<Period>) -> TAResult<(Vec<Price>, Vec<Price>)> {
+    with_function("MINMAX", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut min_output = allocate_output(data.len());
-    let mut max_output = allocate_output(data.len());
+        let mut min_output = allocate_output(data.len());
+        let mut max_output = allocate_output(data.len());
     
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        min_output[i] = lowest_in_period(data, start_index, period);
-        max_output[i] = highest_in_period(data, start_index, period);
-    }
+        let (mut lowest, mut highest) = (RunningMin::new(period), RunningMax::new(period));
+        for (i, &value) in data.iter().enumerate() {
+            let (min, max) = (lowest.push(value), highest.push(value));
+            if i + 1 >= period {
+                min_output[i] = min.unwrap_or(Price::INFINITY);
+                max_output[i] = max.unwrap_or(Price::NEG_INFINITY);
+            }
+        }
 
-    Ok((min_output, max_output))
+        Ok((min_output, max_output))
+    })
 }
 
 /// Finds the indexes of both the lowest and highest values over a specified period
 ///
-/// This is more efficient than calling minindex() and maxindex() separately.
+/// Gives the same indexes as minindex() and maxindex().
 ///
 /// # Parameters
 /// - `data`: Slice of price data
@@ -133,26 +141,17 @@ pub fn minmax(data: &[Price], period: Period) -> TAResult<(Vec<Price>, Vec<Price
 /// let (min_indices, max_indices) = minmaxindex(&data, 3).unwrap();
 /// // min_indices[2] = 1, max_indices[2] = 2
 /// ```
-pub fn minmaxindex(data: &[Price], period: Period) -> TAResult<(Vec<usize>, Vec<usize>)> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
-
-    let mut min_output = vec![0; data.len()];
-    let mut max_output = vec![0; data.len()];
-    
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        let window = &data[start_index..=i];
-        
-        let min_relative_index = lowest_index(window);
-        let max_relative_index = highest_index(window);
-        
-        min_output[i] = start_index + min_relative_index;
-        max_output[i] = start_index + max_relative_index;
-    }
+pub fn minmaxindex(data: &[Price], period: impl IntoParam<Period>) -> TAResult<(Vec<usize>, Vec<usize>)> {
+    with_function("MINMAXINDEX", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    Ok((min_output, max_output))
+        Ok((
+            extreme_indices(data, period, |newer, older| newer < older),
+            extreme_indices(data, period, |newer, older| newer > older),
+        ))
+    })
 }
 
 #[cfg(test)]
@@ -10,6 +10,7 @@ pub mod div;
 pub mod max;
 pub mod min;
 pub mod sum;
+pub mod rolling_arg;
 
 // Re-export all functions for convenient access
 pub use add::{add, add_scalar};
@@ -18,4 +19,5 @@ pub use mult::{mult, mult_scalar};
 pub use div::{div, div_scalar};
 pub use max::{max, maxindex};
 pub use min::{min, minindex, minmax, minmaxindex};
-pub use sum::{sum, sum_rolling};
\ No newline at end of file
+pub use sum::{sum, sum_into, sum_rolling};
+pub use rolling_arg::{rolling_argmax, rolling_argmin};
\ No newline at end of file
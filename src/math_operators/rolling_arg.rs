@@ -0,0 +1,155 @@
+//! Bars since the rolling extreme
+//!
+//! How many bars ago the highest or lowest value of each window occurred, the quantity
+//! Aroon is built on. Computed with a monotonic deque, in O(1) amortized time per bar
+//! whatever the period.
+
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
+use alloc::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Finds how many bars ago the highest value of each window occurred
+///
+/// When the highest value occurs several times in a window, the earliest counts. NaN
+/// values are skipped; a window of NaN only gives `period - 1`, its earliest bar.
+///
+/// # Parameters
+/// - `data`: Slice of price data
+/// - `period`: Window length, including the current value
+///
+/// # Returns
+/// Vector of bar counts from 0, the current bar, to `period - 1`. The first `period-1`
+/// values will be NaN.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::math_operators::rolling_argmax;
+///
+/// let data = vec![1.0, 3.0, 2.0, 5.0, 4.0, 3.0];
+/// let result = rolling_argmax(&data, 3).unwrap();
+/// assert_eq!(&result[2..], &[1.0, 0.0, 1.0, 2.0]);
+/// ```
+pub fn rolling_argmax(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("ROLLING_ARGMAX", || {
+        let period = period.into_param("period")?.get();
+        bars_since_extreme(data, period, |newer, older| newer > older)
+    })
+}
+
+/// Finds how many bars ago the lowest value of each window occurred
+///
+/// When the lowest value occurs several times in a window, the earliest counts. NaN
+/// values are skipped; a window of NaN only gives `period - 1`, its earliest bar.
+///
+/// # Parameters
+/// - `data`: Slice of price data
+/// - `period`: Window length, including the current value
+///
+/// # Returns
+/// Vector of bar counts from 0, the current bar, to `period - 1`. The first `period-1`
+/// values will be NaN.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::math_operators::rolling_argmin;
+///
+/// let data = vec![4.0, 2.0, 3.0, 2.0, 5.0, 6.0];
+/// let result = rolling_argmin(&data, 3).unwrap();
+/// assert_eq!(&result[2..], &[1.0, 2.0, 1.0, 2.0]);
+/// ```
+pub fn rolling_argmin(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("ROLLING_ARGMIN", || {
+        let period = period.into_param("period")?.get();
+        bars_since_extreme(data, period, |newer, older| newer < older)
+    })
+}
+
+/// Bars since the extreme, where `beats(newer, older)` tells whether a newer value
+/// replaces an older one as the extreme; beating equal values makes the latest tie count
+pub(crate) fn bars_since_extreme(data: &[Price], period: usize, beats: fn(Price, Price) -> bool) -> TAResult<Vec<Price>> {
+    validate_not_empty(data, "data")?;
+    validate_sufficient_data(data, period, "data")?;
+
+    let mut output = allocate_output(data.len());
+    // Bars whose values no later bar beats; the front is the extreme of the window
+    let mut candidates: VecDeque<usize> = VecDeque::with_capacity(period);
+    for (i, &value) in data.iter().enumerate() {
+        if !value.is_nan() {
+            while candidates.back().is_some_and(|&j| beats(value, data[j])) {
+                candidates.pop_back();
+            }
+            candidates.push_back(i);
+        }
+        while candidates.front().is_some_and(|&j| j + period <= i) {
+            candidates.pop_front();
+        }
+        if i + 1 >= period {
+            output[i] = candidates.front().map_or(period - 1, |&j| i - j) as Price;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Index of the extreme of each window, where `beats(newer, older)` tells whether a newer
+/// value is strictly more extreme; the first `period - 1` indices are 0
+///
+/// Ties are resolved as TA-Lib does for MAXINDEX and MININDEX: a new bar equal to the
+/// extreme takes over from it, and when the extreme leaves the window the earliest of the
+/// remaining ties replaces it. The window is only rescanned then, so the cost per bar is
+/// O(1) on most series.
+pub(crate) fn extreme_indices(data: &[Price], period: usize, beats: fn(Price, Price) -> bool) -> Vec<usize> {
+    let mut output = vec![0; data.len()];
+    let mut extreme: Option<usize> = None;
+    for i in period.saturating_sub(1)..data.len() {
+        let start = i + 1 - period;
+        extreme = match extreme {
+            Some(j) if j >= start => Some(if data[i] == data[j] || beats(data[i], data[j]) { i } else { j }),
+            _ => Some((start + 1..=i).fold(start, |best, k| if beats(data[k], data[best]) { k } else { best })),
+        };
+        output[i] = extreme.unwrap_or(i);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_arg_matches_scan() {
+        let data: Vec<f64> = (0..80).map(|i| ((i * 37) % 11) as f64 + (i as f64 * 0.3).sin()).collect();
+        for period in [1, 4, 9] {
+            let argmax = rolling_argmax(&data, period).unwrap();
+            let argmin = rolling_argmin(&data, period).unwrap();
+            for i in period - 1..data.len() {
+                let window = &data[i + 1 - period..=i];
+                let highest = window.iter().cloned().fold(f64::MIN, f64::max);
+                let lowest = window.iter().cloned().fold(f64::MAX, f64::min);
+                let first_high = window.iter().position(|&v| v == highest).unwrap();
+                let first_low = window.iter().position(|&v| v == lowest).unwrap();
+                assert_eq!(argmax[i], (period - 1 - first_high) as f64);
+                assert_eq!(argmin[i], (period - 1 - first_low) as f64);
+            }
+        }
+
+        let ties = rolling_argmax(&[2.0, 2.0, f64::NAN, 1.0, f64::NAN, f64::NAN], 3).unwrap();
+        assert!(ties[1].is_nan());
+        assert_eq!(&ties[2..], &[2.0, 2.0, 1.0, 2.0]);
+        assert!(rolling_argmin(&[1.0, 2.0], 3).is_err());
+        assert!(rolling_argmin(&[1.0, 2.0], 0).is_err());
+    }
+
+    #[test]
+    fn test_extreme_indices_ties() {
+        // A tie arriving later takes over, but once the extreme leaves the window the
+        // earliest remaining tie is chosen
+        let data = [5.0, 5.0, 1.0, 5.0, 2.0, 2.0];
+        assert_eq!(extreme_indices(&data, 3, |a, b| a > b), vec![0, 0, 0, 1, 3, 3]);
+        assert_eq!(extreme_indices(&data, 3, |a, b| a < b), vec![0, 0, 2, 2, 2, 4]);
+        assert_eq!(extreme_indices(&[1.0, 5.0, 2.0, 5.0, 3.0, 3.0], 3, |a, b| a > b), vec![0, 0, 1, 3, 3, 3]);
+    }
+}
@@ -1,7 +1,10 @@
 //! Summation over a specified period
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output};
+use crate::common::{TAResult, Price, Float, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, validate_same_length, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the sum over a specified period
 ///
@@ -26,53 +29,71 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// // result[2] = 1+2+3 = 6.0
 /// // result[3] = 2+3+4 = 9.0
 /// ```
-pub fn sum(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn sum<T: Float>(data: &[T], period: impl IntoParam<Period>) -> TAResult<Vec<T>> {
+    with_function("SUM", || {
+        let period = period.into_param("period")?.get();
+        let mut output = vec![T::NAN; data.len()];
+        sum_into(data, period, &mut output)?;
+        Ok(output)
+    })
+}
+
+/// Calculates the rolling sum into a caller-provided buffer
+///
+/// Allocation-free variant of [`sum`]. `out` must have the same length as `data`;
+/// its first `period-1` entries are set to NaN.
+///
+/// # Returns
+/// The number of valid values written (`data.len() - period + 1`).
+pub fn sum_into<T: Float>(data: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> TAResult<usize> {
+    with_function("SUM", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
+        validate_same_length(data, out, "data", "out")?;
 
-    let mut output = allocate_output(data.len());
-    let mut rolling_sum = 0.0;
+        out[..period - 1].fill(T::NAN);
     
-    // Initialize sum for first period
-    for i in 0..period {
-        rolling_sum += data[i];
-    }
-    output[period - 1] = rolling_sum;
+        // Initialize sum for first period
+        let mut rolling_sum: T = data[..period].iter().sum();
+        out[period - 1] = rolling_sum;
     
-    // Rolling calculation for remaining values
-    for i in period..data.len() {
-        rolling_sum = rolling_sum - data[i - period] + data[i];
-        output[i] = rolling_sum;
-    }
+        // Rolling calculation for remaining values
+        for i in period..data.len() {
+            rolling_sum = rolling_sum - data[i - period] + data[i];
+            out[i] = rolling_sum;
+        }
 
-    Ok(output)
+        Ok(data.len() - period + 1)
+    })
 }
 
 /// Calculates the sum using a rolling approach for better performance
 ///
 /// This version maintains a running sum and updates it incrementally.
-pub fn sum_rolling(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn sum_rolling(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("SUM", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
-    let mut rolling_sum = 0.0;
+        let mut output = allocate_output(data.len());
+        let mut rolling_sum = 0.0;
     
-    // Initialize sum for first period
-    for i in 0..period {
-        rolling_sum += data[i];
-    }
-    output[period - 1] = rolling_sum;
+        // Initialize sum for first period
+        for &value in &data[..period] {
+            rolling_sum += value;
+        }
+        output[period - 1] = rolling_sum;
     
-    // Rolling calculation for remaining values
-    for i in period..data.len() {
-        rolling_sum = rolling_sum - data[i - period] + data[i];
-        output[i] = rolling_sum;
-    }
+        // Rolling calculation for remaining values
+        for i in period..data.len() {
+            rolling_sum = rolling_sum - data[i - period] + data[i];
+            output[i] = rolling_sum;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -136,7 +157,7 @@ mod tests {
 
     #[test]
     fn test_sum_empty_data() {
-        let data = vec![];
+        let data: Vec<Price> = vec![];
         assert!(sum(&data, 3).is_err());
     }
 
@@ -3,6 +3,9 @@
 //! Vector arithmetic functions that operate on arrays of values.
 
 use crate::common::{TAError, validate_prices, validate_not_empty};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates square root of each value in the input array.
 /// 
@@ -28,20 +31,22 @@ use crate::common::{TAError, validate_prices, validate_not_empty};
 /// assert_eq!(result[4], 4.0);
 /// ```
 pub fn sqrt(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("SQRT", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    // Check that all values are non-negative
-    for &x in input {
-        if x < 0.0 {
-            return Err(TAError::invalid_input(
-                format!("SQRT input value {} must be non-negative", x)
-            ));
+        // Check that all values are non-negative
+        for &x in input {
+            if x < 0.0 {
+                return Err(TAError::invalid_input(
+                    format!("SQRT input value {} must be non-negative", x)
+                ));
+            }
         }
-    }
     
-    let result = input.iter().map(|&x| x.sqrt()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.sqrt()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates absolute value of each value in the input array.
@@ -68,11 +73,13 @@ pub fn sqrt(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert_eq!(result[4], 3.0);
 /// ```
 pub fn abs(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("ABS", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.abs()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.abs()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates power (x^exponent) of each value in the input array.
@@ -99,11 +106,13 @@ pub fn abs(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert_eq!(result[3], 16.0);
 /// ```
 pub fn pow(input: &[f64], exponent: f64) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("POW", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.powf(exponent)).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.powf(exponent)).collect();
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
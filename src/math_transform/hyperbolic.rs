@@ -3,6 +3,9 @@
 //! Vector hyperbolic functions that operate on arrays of values.
 
 use crate::common::{TAError, validate_prices};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates hyperbolic sine of each value in the input array.
 /// 
@@ -24,10 +27,12 @@ use crate::common::{TAError, validate_prices};
 /// assert!((result[0] - 0.0).abs() < 1e-10);
 /// ```
 pub fn sinh(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_prices(input, "input")?;
+    with_function("SINH", || {
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.sinh()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.sinh()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates hyperbolic cosine of each value in the input array.
@@ -50,10 +55,12 @@ pub fn sinh(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[0] - 1.0).abs() < 1e-10);
 /// ```
 pub fn cosh(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_prices(input, "input")?;
+    with_function("COSH", || {
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.cosh()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.cosh()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates hyperbolic tangent of each value in the input array.
@@ -76,10 +83,12 @@ pub fn cosh(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[0] - 0.0).abs() < 1e-10);
 /// ```
 pub fn tanh(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_prices(input, "input")?;
+    with_function("TANH", || {
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.tanh()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.tanh()).collect();
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
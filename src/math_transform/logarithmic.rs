@@ -3,6 +3,9 @@
 //! Vector logarithmic and exponential functions that operate on arrays of values.
 
 use crate::common::{TAError, validate_prices, validate_not_empty};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates natural logarithm of each value in the input array.
 /// 
@@ -26,20 +29,22 @@ use crate::common::{TAError, validate_prices, validate_not_empty};
 /// assert!((result[2] - 2.0).abs() < 1e-10);
 /// ```
 pub fn ln(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("LN", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    // Check that all values are positive
-    for &x in input {
-        if x <= 0.0 {
-            return Err(TAError::invalid_input(
-                format!("LN input value {} must be positive", x)
-            ));
+        // Check that all values are positive
+        for &x in input {
+            if x <= 0.0 {
+                return Err(TAError::invalid_input(
+                    format!("LN input value {} must be positive", x)
+                ));
+            }
         }
-    }
     
-    let result = input.iter().map(|&x| x.ln()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.ln()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates base-10 logarithm of each value in the input array.
@@ -65,20 +70,22 @@ pub fn ln(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[3] - 3.0).abs() < 1e-10);
 /// ```
 pub fn log10(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("LOG10", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    // Check that all values are positive
-    for &x in input {
-        if x <= 0.0 {
-            return Err(TAError::invalid_input(
-                format!("LOG10 input value {} must be positive", x)
-            ));
+        // Check that all values are positive
+        for &x in input {
+            if x <= 0.0 {
+                return Err(TAError::invalid_input(
+                    format!("LOG10 input value {} must be positive", x)
+                ));
+            }
         }
-    }
     
-    let result = input.iter().map(|&x| x.log10()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.log10()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates exponential (e^x) of each value in the input array.
@@ -103,11 +110,13 @@ pub fn log10(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[2] - std::f64::consts::E.powi(2)).abs() < 1e-10);
 /// ```
 pub fn exp(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("EXP", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.exp()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.exp()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates logarithm with custom base of each value in the input array.
@@ -134,27 +143,29 @@ pub fn exp(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[3] - 3.0).abs() < 1e-10);
 /// ```
 pub fn log_base(input: &[f64], base: f64) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
-    
-    if base <= 0.0 || base == 1.0 {
-        return Err(TAError::invalid_input(
-            format!("Logarithm base {} must be positive and not equal to 1", base)
-        ));
-    }
+    with_function("LOG_BASE", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    // Check that all values are positive
-    for &x in input {
-        if x <= 0.0 {
+        if base <= 0.0 || base == 1.0 {
             return Err(TAError::invalid_input(
-                format!("LOG_BASE input value {} must be positive", x)
+                format!("Logarithm base {} must be positive and not equal to 1", base)
             ));
         }
-    }
     
-    let log_base = base.ln();
-    let result = input.iter().map(|&x| x.ln() / log_base).collect();
-    Ok(result)
+        // Check that all values are positive
+        for &x in input {
+            if x <= 0.0 {
+                return Err(TAError::invalid_input(
+                    format!("LOG_BASE input value {} must be positive", x)
+                ));
+            }
+        }
+    
+        let log_base = base.ln();
+        let result = input.iter().map(|&x| x.ln() / log_base).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates power (base^x) of each value in the input array.
@@ -181,17 +192,19 @@ pub fn log_base(input: &[f64], base: f64) -> Result<Vec<f64>, TAError> {
 /// assert!((result[3] - 8.0).abs() < 1e-10);
 /// ```
 pub fn pow_base(input: &[f64], base: f64) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("POW_BASE", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    if base <= 0.0 {
-        return Err(TAError::invalid_input(
-            format!("Power base {} must be positive", base)
-        ));
-    }
+        if base <= 0.0 {
+            return Err(TAError::invalid_input(
+                format!("Power base {} must be positive", base)
+            ));
+        }
     
-    let result = input.iter().map(|&x| base.powf(x)).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| base.powf(x)).collect();
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
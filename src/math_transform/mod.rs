@@ -2,16 +2,18 @@
 //! 
 //! This module contains mathematical transformation functions that can be applied
 //! to price series or any numerical data. These include trigonometric, hyperbolic,
-//! logarithmic, and rounding functions.
+//! logarithmic, and rounding functions, and smoothing filters.
 
 pub mod trigonometric;
 pub mod hyperbolic;
 pub mod logarithmic;
 pub mod rounding;
 pub mod arithmetic;
+pub mod smoothing;
 
 pub use trigonometric::*;
 pub use hyperbolic::*;
 pub use logarithmic::*;
 pub use rounding::*;
-pub use arithmetic::*;
\ No newline at end of file
+pub use arithmetic::*;
+pub use smoothing::{gaussian_smooth, savgol, savgol_slope};
\ No newline at end of file
@@ -3,6 +3,9 @@
 //! Vector rounding functions that operate on arrays of values.
 
 use crate::common::{TAError, validate_prices, validate_not_empty};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates ceiling (smallest integer >= input) of each value in the input array.
 /// 
@@ -27,11 +30,13 @@ use crate::common::{TAError, validate_prices, validate_not_empty};
 /// assert_eq!(result[3], 0.0);
 /// ```
 pub fn ceil(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("CEIL", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.ceil()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.ceil()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates floor (largest integer <= input) of each value in the input array.
@@ -57,11 +62,13 @@ pub fn ceil(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert_eq!(result[3], 0.0);
 /// ```
 pub fn floor(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("FLOOR", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.floor()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.floor()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates round (nearest integer) of each value in the input array.
@@ -87,11 +94,13 @@ pub fn floor(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert_eq!(result[3], 1.0);
 /// ```
 pub fn round(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("ROUND", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.round()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.round()).collect();
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
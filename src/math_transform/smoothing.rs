@@ -0,0 +1,240 @@
+//! Smoothing Filters
+//!
+//! Low-lag filters for smoothing price series and indicator outputs. Both are trailing
+//! filters: each output uses the current and past values only, so they can be used in
+//! signals and backtests without lookahead. NaN values, such as an indicator's warm-up,
+//! give NaN for every window containing them.
+
+use crate::common::{validate_not_empty, validate_sufficient_data, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::rolling_ols::solve;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Smooths a series with a trailing Savitzky-Golay filter
+///
+/// Fits a polynomial of degree `polyorder` to the last `window` values by least squares
+/// and takes its value at the current bar. Unlike a moving average, which assumes the
+/// series is flat over the window, the fit follows trends and turns, so the output lags
+/// much less; a higher order follows faster moves but smooths less.
+///
+/// # Arguments
+///
+/// * `series` - Input values
+/// * `window` - Number of values in each fit
+/// * `polyorder` - Degree of the polynomial, less than `window`
+///
+/// # Returns
+///
+/// Returns `Ok(Vec<f64>)` containing the smoothed values, the first `window-1` of which
+/// are NaN, or `Err(TAError)` on invalid input.
+///
+/// # Example
+///
+/// ```
+/// use ta_rust::math_transform::savgol;
+/// use ta_rust::overlap::sma;
+///
+/// // On a straight line a linear fit has no lag, an SMA lags half its window
+/// let line: Vec<f64> = (0..20).map(|i| 2.0 * i as f64).collect();
+/// let smoothed = savgol(&line, 9, 1).unwrap();
+/// assert!((smoothed[19] - 38.0).abs() < 1e-9);
+/// assert_eq!(sma(&line, 9).unwrap()[19], 30.0);
+/// ```
+pub fn savgol(series: &[f64], window: impl IntoParam<Period>, polyorder: usize) -> TAResult<Vec<f64>> {
+    with_function("SAVGOL", || {
+        let window = window.into_param("window")?.get();
+        let weights = savgol_weights(window, polyorder, 0)?;
+        apply_trailing(series, &weights)
+    })
+}
+
+/// Calculates the slope of a series with a trailing Savitzky-Golay filter
+///
+/// The derivative at the current bar of the polynomial fitted by [`savgol`], in units of
+/// the series per bar. It is much less noisy than the bar-to-bar change, and lags less
+/// than the slope of a moving average.
+///
+/// # Arguments
+///
+/// * `series` - Input values
+/// * `window` - Number of values in each fit
+/// * `polyorder` - Degree of the polynomial, at least 1 and less than `window`
+///
+/// # Returns
+///
+/// Returns `Ok(Vec<f64>)` containing the slopes, the first `window-1` of which are NaN,
+/// or `Err(TAError)` on invalid input.
+///
+/// # Example
+///
+/// ```
+/// use ta_rust::math_transform::savgol_slope;
+///
+/// // A parabola x² has slope 2x
+/// let parabola: Vec<f64> = (0..30).map(|i| (i * i) as f64).collect();
+/// let slope = savgol_slope(&parabola, 7, 2).unwrap();
+/// assert!((slope[29] - 58.0).abs() < 1e-8);
+/// ```
+pub fn savgol_slope(series: &[f64], window: impl IntoParam<Period>, polyorder: usize) -> TAResult<Vec<f64>> {
+    with_function("SAVGOL", || {
+        let window = window.into_param("window")?.get();
+        if polyorder == 0 {
+            return Err(TAError::invalid_parameter("polyorder", "must be at least 1 for a slope"));
+        }
+        let weights = savgol_weights(window, polyorder, 1)?;
+        apply_trailing(series, &weights)
+    })
+}
+
+/// Smooths a series with a trailing half-Gaussian kernel
+///
+/// Weighs the current value and the past `⌈3σ⌉` values by a Gaussian of the bar distance,
+/// so the weight is highest on the current bar and falls off smoothly. That gives a
+/// cleaner output than the hard cut-off of an SMA window, with less lag than an SMA
+/// spanning the same bars.
+///
+/// # Formula
+/// ```text
+/// w[k]   = exp(-k² / (2σ²)),   k = 0..=⌈3σ⌉
+/// out[t] = Σ w[k] × x[t-k] / Σ w[k]
+/// ```
+///
+/// # Arguments
+///
+/// * `series` - Input values
+/// * `sigma` - Standard deviation of the kernel, in bars
+///
+/// # Returns
+///
+/// Returns `Ok(Vec<f64>)` containing the smoothed values, the first `⌈3σ⌉` of which are
+/// NaN, or `Err(TAError)` on invalid input.
+///
+/// # Example
+///
+/// ```
+/// use ta_rust::math_transform::gaussian_smooth;
+///
+/// let series = vec![1.0, 1.0, 1.0, 1.0, 10.0];
+/// let smoothed = gaussian_smooth(&series, 1.0).unwrap();
+/// // The spike gets the largest weight, but not all of it
+/// assert!(smoothed[4] > 4.0 && smoothed[4] < 10.0);
+/// assert!(smoothed[2].is_nan());
+/// ```
+pub fn gaussian_smooth(series: &[f64], sigma: f64) -> TAResult<Vec<f64>> {
+    with_function("GAUSSIAN_SMOOTH", || {
+        if !(sigma.is_finite() && sigma > 0.0) {
+            return Err(TAError::invalid_parameter("sigma", "must be positive and finite"));
+        }
+
+        let reach = (3.0 * sigma).ceil() as usize;
+        // Oldest first, to line up with the window
+        let mut weights: Vec<f64> = (0..=reach)
+            .rev()
+            .map(|k| (-((k * k) as f64) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        weights.iter_mut().for_each(|w| *w /= total);
+        apply_trailing(series, &weights)
+    })
+}
+
+/// Weights of a trailing Savitzky-Golay filter, oldest value first, giving the
+/// `derivative`-th derivative per bar at the newest value
+fn savgol_weights(window: usize, polyorder: usize, derivative: usize) -> TAResult<Vec<f64>> {
+    if polyorder >= window {
+        return Err(TAError::invalid_parameter("polyorder", "must be less than the window"));
+    }
+    if window == 1 {
+        return Ok(vec![1.0]);
+    }
+
+    // Positions scaled to [-1, 0] keep the normal equations well conditioned
+    let scale = (window - 1) as f64;
+    let positions: Vec<f64> = (0..window).map(|j| (j as f64 - scale) / scale).collect();
+    let terms = polyorder + 1;
+
+    // The fitted polynomial at 0 is its constant coefficient, its slope there the linear one.
+    // Solving AᵀA x = e, with A[j][m] = position[j]^m, picks that coefficient out of the fit:
+    // weight[j] = Σ x[m] u[j]^m. The system is augmented with e as its last column.
+    let mut system = vec![vec![0.0; terms + 1]; terms];
+    for &u in &positions {
+        for (m, row) in system.iter_mut().enumerate() {
+            for (k, value) in row[..terms].iter_mut().enumerate() {
+                *value += u.powi((m + k) as i32);
+            }
+        }
+    }
+    system[derivative][terms] = 1.0;
+    let x = solve(&mut system)
+        .ok_or_else(|| TAError::numerical_error("Savitzky-Golay normal equations are singular"))?;
+
+    let unit = scale.powi(derivative as i32);
+    Ok(positions
+        .iter()
+        .map(|&u| x.iter().enumerate().map(|(m, &c)| c * u.powi(m as i32)).sum::<f64>() / unit)
+        .collect())
+}
+
+/// Dot product of `weights` with each trailing window of `series`
+fn apply_trailing(series: &[f64], weights: &[f64]) -> TAResult<Vec<f64>> {
+    validate_not_empty(series, "series")?;
+    validate_sufficient_data(series, weights.len(), "series")?;
+
+    let mut output = vec![f64::NAN; series.len()];
+    for (out, window) in output[weights.len() - 1..].iter_mut().zip(series.windows(weights.len())) {
+        *out = window.iter().zip(weights).map(|(x, w)| x * w).sum();
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::overlap::sma;
+
+    #[test]
+    fn test_savgol_reproduces_polynomials() {
+        let cubic: Vec<f64> = (0..40).map(|i| {
+            let t = i as f64 * 0.1;
+            1.0 - 2.0 * t + 0.5 * t * t + 0.3 * t * t * t
+        }).collect();
+        let smoothed = savgol(&cubic, 11, 3).unwrap();
+        let slope = savgol_slope(&cubic, 11, 3).unwrap();
+        for i in 10..40 {
+            let t = i as f64 * 0.1;
+            assert_float_eq!(smoothed[i], cubic[i], 1e-9);
+            assert_float_eq!(slope[i], (-2.0 + t + 0.9 * t * t) * 0.1, 1e-9);
+        }
+        assert!(smoothed[9].is_nan());
+
+        // Order 0 is the moving average; the weights of every fit sum to 1
+        let noisy: Vec<f64> = (0..30).map(|i| (i as f64 * 1.3).sin()).collect();
+        let flat = savgol(&noisy, 5, 0).unwrap();
+        let average = sma(&noisy, 5).unwrap();
+        for i in 4..30 {
+            assert_float_eq!(flat[i], average[i], 1e-12);
+        }
+        assert_float_eq!(savgol_weights(21, 4, 0).unwrap().iter().sum::<f64>(), 1.0, 1e-9);
+    }
+
+    #[test]
+    fn test_smoothing_errors_and_nan() {
+        let series: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        assert!(savgol(&series, 5, 5).is_err());
+        assert!(savgol(&series, 0, 0).is_err());
+        assert!(savgol(&series, 11, 2).is_err());
+        assert!(savgol_slope(&series, 5, 0).is_err());
+        assert!(gaussian_smooth(&series, 0.0).is_err());
+        assert!(gaussian_smooth(&series, 4.0).is_err());
+        assert!(gaussian_smooth(&[], 1.0).is_err());
+
+        let mut with_warmup = series.clone();
+        with_warmup[..3].fill(f64::NAN);
+        let smoothed = gaussian_smooth(&with_warmup, 0.5).unwrap();
+        assert!(smoothed[..5].iter().all(|v| v.is_nan()));
+        assert!(smoothed[5..].iter().all(|v| !v.is_nan()));
+        assert_eq!(savgol(&series, 1, 0).unwrap(), series);
+    }
+}
@@ -4,6 +4,9 @@
 //! All angle inputs and outputs are in radians.
 
 use crate::common::{TAError, validate_prices, validate_not_empty};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates sine of each value in the input array.
 /// 
@@ -27,11 +30,13 @@ use crate::common::{TAError, validate_prices, validate_not_empty};
 /// assert!((result[2] - 0.0).abs() < 1e-10);
 /// ```
 pub fn sin(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("SIN", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.sin()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.sin()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates cosine of each value in the input array.
@@ -56,11 +61,13 @@ pub fn sin(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[2] - (-1.0)).abs() < 1e-10);
 /// ```
 pub fn cos(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("COS", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.cos()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.cos()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates tangent of each value in the input array.
@@ -84,11 +91,13 @@ pub fn cos(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[1] - 1.0).abs() < 1e-10);
 /// ```
 pub fn tan(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("TAN", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.tan()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.tan()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates arcsine (inverse sine) of each value in the input array.
@@ -113,20 +122,22 @@ pub fn tan(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[2] - std::f64::consts::PI / 2.0).abs() < 1e-10);
 /// ```
 pub fn asin(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("ASIN", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    // Check that all values are in valid range [-1, 1]
-    for &x in input {
-        if x < -1.0 || x > 1.0 {
-            return Err(TAError::invalid_input(
-                format!("ASIN input value {} is outside valid range [-1, 1]", x)
-            ));
+        // Check that all values are in valid range [-1, 1]
+        for &x in input {
+            if x.abs() > 1.0 {
+                return Err(TAError::invalid_input(
+                    format!("ASIN input value {} is outside valid range [-1, 1]", x)
+                ));
+            }
         }
-    }
     
-    let result = input.iter().map(|&x| x.asin()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.asin()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates arccosine (inverse cosine) of each value in the input array.
@@ -152,20 +163,40 @@ pub fn asin(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[2] - std::f64::consts::PI).abs() < 1e-10);
 /// ```
 pub fn acos(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("ACOS", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    // Check that all values are in valid range [-1, 1]
-    for &x in input {
-        if x < -1.0 || x > 1.0 {
-            return Err(TAError::invalid_input(
-                format!("ACOS input value {} is outside valid range [-1, 1]", x)
-            ));
+        // Check that all values are in valid range [-1, 1]
+        for &x in input {
+            if x.abs() > 1.0 {
+                return Err(TAError::invalid_input(
+                    format!("ACOS input value {} is outside valid range [-1, 1]", x)
+                ));
+            }
         }
-    }
     
-    let result = input.iter().map(|&x| x.acos()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.acos()).collect();
+        Ok(result)
+    })
+}
+
+/// Arcsine of each value as TA-Lib gives it: NaN outside [-1, 1] rather than an error
+pub(crate) fn asin_talib(input: &[f64]) -> Result<Vec<f64>, TAError> {
+    with_function("ASIN", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
+        Ok(input.iter().map(|&x| x.asin()).collect())
+    })
+}
+
+/// Arccosine of each value as TA-Lib gives it: NaN outside [-1, 1] rather than an error
+pub(crate) fn acos_talib(input: &[f64]) -> Result<Vec<f64>, TAError> {
+    with_function("ACOS", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
+        Ok(input.iter().map(|&x| x.acos()).collect())
+    })
 }
 
 /// Calculates arctangent (inverse tangent) of each value in the input array.
@@ -191,11 +222,13 @@ pub fn acos(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((result[2] - (-std::f64::consts::PI / 4.0)).abs() < 1e-10);
 /// ```
 pub fn atan(input: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("ATAN", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let result = input.iter().map(|&x| x.atan()).collect();
-    Ok(result)
+        let result = input.iter().map(|&x| x.atan()).collect();
+        Ok(result)
+    })
 }
 
 /// Calculates sine and cosine simultaneously for better performance.
@@ -220,19 +253,21 @@ pub fn atan(input: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((cos_vals[0] - 1.0).abs() < 1e-10);
 /// ```
 pub fn sin_cos(input: &[f64]) -> Result<(Vec<f64>, Vec<f64>), TAError> {
-    validate_not_empty(input, "input")?;
-    validate_prices(input, "input")?;
+    with_function("SINCOS", || {
+        validate_not_empty(input, "input")?;
+        validate_prices(input, "input")?;
     
-    let mut sin_result = Vec::with_capacity(input.len());
-    let mut cos_result = Vec::with_capacity(input.len());
+        let mut sin_result = Vec::with_capacity(input.len());
+        let mut cos_result = Vec::with_capacity(input.len());
     
-    for &x in input {
-        let (s, c) = x.sin_cos();
-        sin_result.push(s);
-        cos_result.push(c);
-    }
+        for &x in input {
+            let (s, c) = (x.sin(), x.cos());
+            sin_result.push(s);
+            cos_result.push(c);
+        }
     
-    Ok((sin_result, cos_result))
+        Ok((sin_result, cos_result))
+    })
 }
 
 /// Converts degrees to radians.
@@ -258,11 +293,13 @@ pub fn sin_cos(input: &[f64]) -> Result<(Vec<f64>, Vec<f64>), TAError> {
 /// assert!((radians[3] - 2.0 * std::f64::consts::PI).abs() < 1e-10);
 /// ```
 pub fn deg_to_rad(degrees: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(degrees, "degrees")?;
-    validate_prices(degrees, "degrees")?;
+    with_function("DEG_TO_RAD", || {
+        validate_not_empty(degrees, "degrees")?;
+        validate_prices(degrees, "degrees")?;
     
-    let result = degrees.iter().map(|&x| x.to_radians()).collect();
-    Ok(result)
+        let result = degrees.iter().map(|&x| x.to_radians()).collect();
+        Ok(result)
+    })
 }
 
 /// Converts radians to degrees.
@@ -287,11 +324,13 @@ pub fn deg_to_rad(degrees: &[f64]) -> Result<Vec<f64>, TAError> {
 /// assert!((degrees[2] - 180.0).abs() < 1e-10);
 /// ```
 pub fn rad_to_deg(radians: &[f64]) -> Result<Vec<f64>, TAError> {
-    validate_not_empty(radians, "radians")?;
-    validate_prices(radians, "radians")?;
+    with_function("RAD_TO_DEG", || {
+        validate_not_empty(radians, "radians")?;
+        validate_prices(radians, "radians")?;
     
-    let result = radians.iter().map(|&x| x.to_degrees()).collect();
-    Ok(result)
+        let result = radians.iter().map(|&x| x.to_degrees()).collect();
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
@@ -387,6 +426,15 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_talib_inverse_trig_out_of_range() {
+        let input = vec![-1.5, 0.5, 1.5];
+        let (acos, asin) = (acos_talib(&input).unwrap(), asin_talib(&input).unwrap());
+        assert!(acos[0].is_nan() && acos[2].is_nan() && asin[0].is_nan() && asin[2].is_nan());
+        assert_eq!((acos[1], asin[1]), (0.5f64.acos(), 0.5f64.asin()));
+        assert!(acos_talib(&[]).is_err());
+    }
+
     #[test]
     fn test_atan_basic() {
         let input = vec![0.0, 1.0, -1.0, 3.0_f64.sqrt()];
@@ -0,0 +1,188 @@
+//! Dominant-cycle adaptive oscillators
+//!
+//! Ehlers' adaptive indicators size their window from the market's dominant cycle instead
+//! of a fixed period: each bar uses the smoothed cycle period measured by the Hilbert
+//! Transform of [`mama`](crate::overlap::mama), so the oscillator tracks a full swing
+//! whether the market is cycling every 10 bars or every 40. The RSI uses half the cycle,
+//! the stochastic a whole one.
+
+use crate::common::{validate_hlc_with, HlcValidation, IntoParam, MAType, Period, TAResult};
+use crate::common::errors::with_function;
+use crate::momentum::StochOutput;
+use crate::overlap::ma::ma_skip_nan;
+use crate::overlap::mama_period;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Fast and slow limits of the MAMA run that measures the cycle, its usual defaults
+const FAST_LIMIT: f64 = 0.5;
+const SLOW_LIMIT: f64 = 0.05;
+
+/// Calculates an RSI whose period follows the dominant cycle
+///
+/// Each bar sums the up and down closes over half the measured cycle period, rounded and at
+/// least 2 changes, as in Ehlers' adaptive RSI. The cycle is that of [`mama_period`].
+///
+/// # Formula
+/// ```text
+/// n[t]   = max(round(Cycle[t] / 2), 2)
+/// CU     = Σ max(Close - Close[-1], 0) over the last n[t] changes
+/// CD     = Σ max(Close[-1] - Close, 0) over the last n[t] changes
+/// RSI[t] = 100 × CU / (CU + CD)
+/// ```
+///
+/// # Arguments
+/// * `prices` - Price series, at least 33 values for the cycle measurement
+///
+/// # Returns
+/// Vector of RSI values, NaN until the cycle is measured and `n[t]` changes are available.
+/// A window without any movement gives 0, as in [`imi`](crate::momentum::imi).
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::rsi_adaptive_cycle;
+///
+/// let prices: Vec<f64> = (0..200)
+///     .map(|i| 100.0 + (i as f64 * 2.0 * std::f64::consts::PI / 20.0).sin())
+///     .collect();
+/// let rsi = rsi_adaptive_cycle(&prices).unwrap();
+/// assert!(rsi[..32].iter().all(|v| v.is_nan()));
+/// // Swings across the range every cycle
+/// let last_cycle = &rsi[180..];
+/// assert!(last_cycle.iter().cloned().fold(f64::MIN, f64::max) > 90.0);
+/// assert!(last_cycle.iter().cloned().fold(f64::MAX, f64::min) < 10.0);
+/// ```
+pub fn rsi_adaptive_cycle(prices: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("RSICYCLE", || {
+        let windows = cycle_windows(prices, 0.5)?;
+
+        let mut rsi = vec![f64::NAN; prices.len()];
+        for (i, (value, window)) in rsi.iter_mut().zip(windows).enumerate() {
+            let Some(n) = window.filter(|&n| n <= i) else { continue };
+            let (mut up, mut down) = (0.0, 0.0);
+            for j in (i + 1 - n)..=i {
+                let change = prices[j] - prices[j - 1];
+                if change > 0.0 {
+                    up += change;
+                } else {
+                    down -= change;
+                }
+            }
+            *value = if up + down > 0.0 { 100.0 * up / (up + down) } else { 0.0 };
+        }
+        Ok(rsi)
+    })
+}
+
+/// Calculates a stochastic oscillator whose %K window follows the dominant cycle
+///
+/// Each bar's %K places the close within the high-low range of the last cycle, the
+/// smoothed period of [`mama_period`] on the closes, rounded. %D is the SMA of %K over
+/// `d_period` bars.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices, at least 33 values for the cycle measurement
+/// * `d_period` - Number of %K values averaged into %D (typically 3)
+///
+/// # Returns
+/// [`StochOutput`] with %K, NaN until the cycle is measured and a whole cycle of bars is
+/// available, and %D. A window with no range gives a %K of 0, as in
+/// [`stoch`](crate::momentum::stoch).
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::stoch_adaptive_cycle;
+///
+/// let close: Vec<f64> = (0..200)
+///     .map(|i| 100.0 + (i as f64 * 2.0 * std::f64::consts::PI / 20.0).sin())
+///     .collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.1).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 0.1).collect();
+/// let stoch = stoch_adaptive_cycle(&high, &low, &close, 3).unwrap();
+/// assert!(stoch.k[180..].iter().all(|k| (0.0..=100.0).contains(k)));
+/// assert!(stoch.d[..8].iter().all(|v| v.is_nan()));
+/// ```
+pub fn stoch_adaptive_cycle(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    d_period: impl IntoParam<Period>,
+) -> TAResult<StochOutput> {
+    with_function("STOCHCYCLE", || {
+        let d_period = d_period.into_param("d_period")?.get();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let windows = cycle_windows(close, 1.0)?;
+
+        let mut k = vec![f64::NAN; close.len()];
+        for (i, (value, window)) in k.iter_mut().zip(windows).enumerate() {
+            let Some(n) = window.filter(|&n| n <= i + 1) else { continue };
+            let hh = high[i + 1 - n..=i].iter().cloned().fold(f64::MIN, f64::max);
+            let ll = low[i + 1 - n..=i].iter().cloned().fold(f64::MAX, f64::min);
+            let range = hh - ll;
+            *value = if range.abs() < 1e-12 { 0.0 } else { 100.0 * (close[i] - ll) / range };
+        }
+        let d = ma_skip_nan(&k, d_period, MAType::SMA)?;
+        Ok(StochOutput { k, d })
+    })
+}
+
+/// Window of each bar, `fraction` of the smoothed dominant cycle rounded and at least 2,
+/// or `None` before the cycle is measured
+fn cycle_windows(prices: &[f64], fraction: f64) -> TAResult<Vec<Option<usize>>> {
+    let cycle = mama_period(prices, FAST_LIMIT, SLOW_LIMIT)?;
+    Ok(cycle
+        .iter()
+        .map(|&period| (!period.is_nan()).then(|| ((period * fraction).round() as usize).max(2)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::stoch;
+
+    fn sine(len: usize, cycle: f64) -> Vec<f64> {
+        (0..len).map(|i| 100.0 + (i as f64 * 2.0 * core::f64::consts::PI / cycle).sin()).collect()
+    }
+
+    #[test]
+    fn test_windows_follow_cycle() {
+        let short = cycle_windows(&sine(300, 12.0), 1.0).unwrap();
+        let long = cycle_windows(&sine(300, 36.0), 1.0).unwrap();
+        assert!(short[..32].iter().all(|w| w.is_none()));
+        let (short, long) = (short[299].unwrap(), long[299].unwrap());
+        assert!((10..=14).contains(&short), "{}", short);
+        assert!((31..=41).contains(&long), "{}", long);
+    }
+
+    #[test]
+    fn test_adaptive_matches_fixed_window() {
+        // Where the window is known, each bar equals the fixed-period indicator at that window
+        let close = sine(200, 24.0);
+        let high: Vec<f64> = close.iter().map(|c| c + 0.2).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.2).collect();
+        let windows = cycle_windows(&close, 1.0).unwrap();
+        let adaptive = stoch_adaptive_cycle(&high, &low, &close, 1).unwrap();
+        for i in [120, 150, 199] {
+            let n = windows[i].unwrap();
+            let (fixed, _) = stoch(&high, &low, &close, n, 1, MAType::SMA, 1, MAType::SMA).unwrap();
+            crate::assert_float_eq!(adaptive.k[i], fixed[i], 1e-9);
+        }
+        crate::common::assert_arrays_approx_equal(&adaptive.k, &adaptive.d, 1e-12);
+
+        let rsi = rsi_adaptive_cycle(&close).unwrap();
+        assert!(rsi[40..].iter().all(|v| (0.0..=100.0).contains(v)));
+        assert_eq!(rsi_adaptive_cycle(&[100.0; 40]).unwrap()[39], 0.0);
+    }
+
+    #[test]
+    fn test_adaptive_errors() {
+        assert!(rsi_adaptive_cycle(&[]).is_err());
+        assert!(rsi_adaptive_cycle(&sine(20, 10.0)).is_err());
+        let close = sine(60, 10.0);
+        assert!(stoch_adaptive_cycle(&close, &close, &close, 0).is_err());
+        assert!(stoch_adaptive_cycle(&close[1..], &close, &close, 3).is_err());
+    }
+}
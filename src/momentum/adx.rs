@@ -1,9 +1,16 @@
 // ADX - Average Directional Movement Index
-use crate::common::TAResult;
-use crate::momentum::dx;
-use crate::overlap::ema;
+use crate::common::{TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::DiState;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Average Directional Movement Index.
+///
+/// Wilder's average of DX: the first value, at bar `2 * period - 1`, is the mean of the
+/// first `period` DX values, and each later one moves `1 / period` of the way to the DX.
+/// As in TA-Lib, bars whose directional indicators sum to zero count as 0 in the mean and
+/// leave the average unchanged after it.
 /// 
 /// # Arguments
 /// * `high` - High prices
@@ -17,9 +24,169 @@ pub fn adx(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let dx_vec = dx(high, low, close, period)?;
-    let adx_vec = ema(&dx_vec, period)?;
-    Ok(adx_vec)
-} 
\ No newline at end of file
+    with_function("ADX", || {
+        let period = period.into_param("period")?.get();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let mut state = AdxState::new(period)?;
+        let mut out = vec![f64::NAN; close.len()];
+        for i in 0..close.len() {
+            out[i] = state.update(high[i], low[i], close[i]).unwrap_or(f64::NAN);
+        }
+        Ok(out)
+    })
+}
+
+/// Streaming Average Directional Movement Index
+///
+/// Produces the same values as [`adx`] one bar at a time, in O(1) time and memory. The
+/// directional indicators and DX it is built on are available through [`AdxState::di`]
+/// and [`AdxState::dx`].
+///
+/// # Example
+/// ```rust
+/// use ta_rust::momentum::{adx, AdxState};
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+/// let batch = adx(&high, &low, &close, 5).unwrap();
+///
+/// let mut state = AdxState::new(5).unwrap();
+/// assert_eq!(state.bars_until_warm(), 10);
+/// for i in 0..close.len() {
+///     match state.update(high[i], low[i], close[i]) {
+///         Some(value) => assert!((value - batch[i]).abs() < 1e-9),
+///         None => assert!(batch[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdxState {
+    di: DiState,
+    /// Number of bars with directional indicators
+    dx_count: usize,
+    /// Sum of the first `period` DX values, seeding the average
+    dx_sum: f64,
+    value: Option<f64>,
+}
+
+impl AdxState {
+    /// Creates a new state for the given period
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn new(period: impl IntoParam<Period>) -> TAResult<Self> {
+        with_function("ADX", || {
+            let period = period.into_param("period")?.get();
+            Ok(Self { di: DiState::new(period)?, dx_count: 0, dx_sum: 0.0, value: None })
+        })
+    }
+
+    /// Feeds the next bar and returns the updated ADX, or `None` during warm-up
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.di.update(high, low, close)?;
+        let dx = self.di.raw_dx();
+        let period = self.period();
+        self.dx_count += 1;
+        if self.dx_count <= period {
+            self.dx_sum += dx.unwrap_or(0.0);
+            if self.dx_count == period {
+                self.value = Some(self.dx_sum / period as f64);
+            }
+        } else if let (Some(adx), Some(dx)) = (self.value, dx) {
+            self.value = Some((adx * (period - 1) as f64 + dx) / period as f64);
+        }
+        self.value
+    }
+
+    /// Current ADX, or `None` during warm-up
+    pub fn current(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Current `(+DI, -DI)`, or `None` during their warm-up
+    pub fn di(&self) -> Option<(f64, f64)> {
+        self.di.current()
+    }
+
+    /// Current DX, or `None` during its warm-up
+    pub fn dx(&self) -> Option<f64> {
+        self.di.dx()
+    }
+
+    /// Whether the ADX has a value
+    pub fn is_warm(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// Number of further bars needed before the ADX has a value
+    pub fn bars_until_warm(&self) -> usize {
+        // The bar that warms up the directional indicators also gives the first DX
+        self.di.bars_until_warm().saturating_sub(1) + self.period().saturating_sub(self.dx_count)
+    }
+
+    /// Period of the ADX
+    pub fn period(&self) -> usize {
+        self.di.period()
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.di.reset();
+        self.dx_count = 0;
+        self.dx_sum = 0.0;
+        self.value = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::dx;
+
+    #[test]
+    fn test_adx_state_matches_batch() {
+        let close: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+
+        for period in [2, 5, 14] {
+            let batch = adx(&high, &low, &close, period).unwrap();
+            let first = batch.iter().position(|v| !v.is_nan()).unwrap();
+            let mut state = AdxState::new(period).unwrap();
+            for i in 0..close.len() {
+                assert_eq!(state.bars_until_warm(), (first + 1).saturating_sub(i), "{} at {}", period, i);
+                match state.update(high[i], low[i], close[i]) {
+                    Some(value) => assert!((value - batch[i]).abs() < 1e-9, "{} at {}", period, i),
+                    None => assert!(batch[i].is_nan(), "{} at {}", period, i),
+                }
+                assert_eq!(state.is_warm(), !batch[i].is_nan());
+            }
+            assert!(state.di().is_some() && state.dx().is_some());
+            state.reset();
+            assert_eq!(state, AdxState::new(period).unwrap());
+        }
+
+        assert!(AdxState::new(0).is_err());
+    }
+
+    #[test]
+    fn test_adx_wilder_average_of_dx() {
+        // Seeded with the mean of the first `period` DX values, then Wilder-smoothed, as in
+        // TA-Lib; an EMA of DX drifted from TA-Lib's values for the whole series
+        let close: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+        let dx_vec = dx(&high, &low, &close, 5).unwrap();
+        let result = adx(&high, &low, &close, 5).unwrap();
+        assert_eq!(result.iter().take_while(|v| v.is_nan()).count(), crate::common::adx_lookback(5));
+
+        let first = crate::common::dx_lookback(5);
+        let seed = dx_vec[first..first + 5].iter().sum::<f64>() / 5.0;
+        assert!((result[9] - seed).abs() < 1e-12);
+        assert!((result[10] - (seed * 4.0 + dx_vec[10]) / 5.0).abs() < 1e-12);
+    }
+}
@@ -1,8 +1,13 @@
 // ADXR - Average Directional Movement Index Rating
-use crate::common::TAResult;
+use crate::common::{TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::momentum::adx;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Average Directional Movement Index Rating.
+///
+/// The mean of the ADX and the ADX `period - 1` bars earlier, as in TA-Lib.
 /// 
 /// # Arguments
 /// * `high` - High prices
@@ -16,13 +21,35 @@ pub fn adxr(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let adx_vec = adx(high, low, close, period)?;
-    let len = adx_vec.len();
-    let mut out = vec![f64::NAN; len];
-    for i in period..len {
-        out[i] = (adx_vec[i] + adx_vec[i - period]) / 2.0;
+    with_function("ADXR", || {
+        let period = period.into_param("period")?.get();
+        let adx_vec = adx(high, low, close, period)?;
+        let len = adx_vec.len();
+        let mut out = vec![f64::NAN; len];
+        for i in period - 1..len {
+            out[i] = (adx_vec[i] + adx_vec[i + 1 - period]) / 2.0;
+        }
+        Ok(out)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adxr_values_after_lookback() {
+        // ADXR averages ADX with its value `period - 1` bars ago; before ADX smoothed only its
+        // valid DX values, both were NaN and so was every ADXR value
+        let close: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+        let result = adxr(&high, &low, &close, 5).unwrap();
+        assert_eq!(result.iter().take_while(|v| v.is_nan()).count(), crate::common::adxr_lookback(5));
+
+        let adx_vec = adx(&high, &low, &close, 5).unwrap();
+        assert!((result[13] - (adx_vec[13] + adx_vec[9]) / 2.0).abs() < 1e-12);
     }
-    Ok(out)
-} 
\ No newline at end of file
+}
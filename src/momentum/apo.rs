@@ -1,9 +1,14 @@
 // APO - Absolute Price Oscillator
-use crate::common::{TAError, TAResult, MAType};
-use crate::overlap::ma;
+use crate::common::{TAResult, MAType, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::price_oscillator::price_oscillator;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Absolute Price Oscillator (APO).
 /// 
+/// The absolute form of [`price_oscillator`].
+/// 
 /// # Arguments
 /// * `price` - Input price series
 /// * `fast_period` - Fast moving average period
@@ -14,21 +19,11 @@ use crate::overlap::ma;
 /// Vector of APO values
 pub fn apo(
     price: &[f64],
-    fast_period: usize,
-    slow_period: usize,
+    fast_period: impl IntoParam<Period>,
+    slow_period: impl IntoParam<Period>,
     ma_type: MAType,
 ) -> TAResult<Vec<f64>> {
-    if price.len() < slow_period {
-        return Err(TAError::insufficient_data(slow_period, price.len()));
-    }
-    let fast = ma(price, fast_period, ma_type)?;
-    let slow = ma(price, slow_period, ma_type)?;
-    let mut apo = vec![f64::NAN; price.len()];
-    for i in 0..price.len() {
-        if i < slow_period - 1 {
-            continue;
-        }
-        apo[i] = fast[i] - slow[i];
-    }
-    Ok(apo)
-} 
\ No newline at end of file
+    with_function("APO", || {
+        price_oscillator(price, fast_period, slow_period, ma_type, false)
+    })
+}
@@ -1,7 +1,84 @@
 // AROON - Aroon Up/Down
-use crate::common::{TAError, TAResult};
+use crate::common::{crossovers, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::rolling_arg::bars_since_extreme;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Aroon result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AroonOutput {
+    /// Aroon Up
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub up: Vec<f64>,
+    /// Aroon Down
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub down: Vec<f64>,
+}
+
+impl AroonOutput {
+    /// Up/Down crossovers: 1 where Aroon Up crosses above Aroon Down, -1 where it crosses below
+    pub fn crossovers(&self) -> Vec<i8> {
+        crossovers(&self.up, &self.down)
+    }
+}
+
+impl From<(Vec<f64>, Vec<f64>)> for AroonOutput {
+    /// Converts the `(up, down)` tuple returned by [`aroon`]
+    fn from((up, down): (Vec<f64>, Vec<f64>)) -> Self {
+        Self { up, down }
+    }
+}
+
+/// Scale of the Aroon lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AroonScale {
+    /// From 0 to 100, as TA-Lib
+    #[default]
+    Percent,
+    /// From 0 to 1
+    Fraction,
+}
+
+impl AroonScale {
+    /// Value of a line at the top of the scale
+    fn top(self) -> f64 {
+        match self {
+            AroonScale::Percent => 100.0,
+            AroonScale::Fraction => 1.0,
+        }
+    }
+}
+
+/// Aroon Up, Aroon Down and the Aroon Oscillator, returned by [`aroon_full`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AroonFull {
+    /// Aroon Up
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub up: Vec<f64>,
+    /// Aroon Down
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub down: Vec<f64>,
+    /// Aroon Oscillator, Up minus Down
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub oscillator: Vec<f64>,
+}
+
+impl AroonFull {
+    /// Up/Down crossovers: 1 where Aroon Up crosses above Aroon Down, -1 where it crosses below
+    pub fn crossovers(&self) -> Vec<i8> {
+        crossovers(&self.up, &self.down)
+    }
+}
 
 /// Calculates the Aroon Up and Aroon Down indicators.
+///
+/// Over windows of `period + 1` bars, Aroon Up is `100 * (period - bars since the highest
+/// high) / period` and Aroon Down the same for the lowest low; the latest bar wins ties,
+/// as in TA-Lib. The first `period` values are NaN.
 /// 
 /// # Arguments
 /// * `high` - High prices
@@ -13,35 +90,124 @@ use crate::common::{TAError, TAResult};
 pub fn aroon(
     high: &[f64],
     low: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    with_function("AROON", || {
+        let period = period.into_param("period")?.get();
+        aroon_scaled(high, low, period, AroonScale::Percent)
+    })
+}
+
+/// Calculates Aroon Up, Aroon Down and the Aroon Oscillator together
+///
+/// The lines are those of [`aroon`] and the oscillator that of
+/// [`aroonosc`](crate::momentum::aroonosc), from a single pass over the windows, on the
+/// chosen scale: the oscillator runs from -100 to 100 in percent, from -1 to 1 as a
+/// fraction.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `period` - Period for calculation
+/// * `scale` - Scale of the outputs
+///
+/// # Returns
+/// * `Ok(AroonFull)` - The three series; the first `period` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::{aroon_full, aroonosc, AroonScale};
+///
+/// let high = [10.0, 11.0, 12.0, 11.5, 11.0, 10.5, 10.0, 9.5];
+/// let low = [9.0, 10.0, 11.0, 10.5, 10.0, 9.5, 9.0, 8.5];
+///
+/// let full = aroon_full(&high, &low, 4, AroonScale::Percent).unwrap();
+/// assert_eq!(full.oscillator[7], aroonosc(&high, &low, 4).unwrap()[7]);
+///
+/// let fraction = aroon_full(&high, &low, 4, AroonScale::Fraction).unwrap();
+/// assert!((fraction.up[5] - full.up[5] / 100.0).abs() < 1e-12);
+/// ```
+pub fn aroon_full(
+    high: &[f64],
+    low: &[f64],
+    period: impl IntoParam<Period>,
+    scale: AroonScale,
+) -> TAResult<AroonFull> {
+    with_function("AROON", || {
+        let period = period.into_param("period")?.get();
+        let (up, down) = aroon_scaled(high, low, period, scale)?;
+        let oscillator = up.iter().zip(&down).map(|(u, d)| u - d).collect();
+        Ok(AroonFull { up, down, oscillator })
+    })
+}
+
+/// Aroon Up and Down on the given scale
+fn aroon_scaled(high: &[f64], low: &[f64], period: usize, scale: AroonScale) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    let top = scale.top();
     let len = high.len();
     if low.len() != len {
         return Err(TAError::mismatched_inputs(format!("high: {}, low: {}", len, low.len())));
     }
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
-    let mut up = vec![f64::NAN; len];
-    let mut down = vec![f64::NAN; len];
-    for i in (period - 1)..len {
-        let mut max_idx = 0;
-        let mut min_idx = 0;
-        let mut max = f64::MIN;
-        let mut min = f64::MAX;
-        for j in 0..period {
-            let idx = i + 1 - period + j;
-            if high[idx] > max {
-                max = high[idx];
-                max_idx = j;
-            }
-            if low[idx] < min {
-                min = low[idx];
-                min_idx = j;
-            }
-        }
-        up[i] = 100.0 * (period as f64 - max_idx as f64) / period as f64;
-        down[i] = 100.0 * (period as f64 - min_idx as f64) / period as f64;
+    if len <= period {
+        return Err(TAError::insufficient_data(period + 1, len));
+    }
+    // Windows of `period + 1` bars, the latest of tied extremes counting
+    let since_high = bars_since_extreme(high, period + 1, |newer, older| newer >= older)?;
+    let since_low = bars_since_extreme(low, period + 1, |newer, older| newer <= older)?;
+    let line = |bars: &f64| top * (period as f64 - bars) / period as f64;
+    Ok((since_high.iter().map(line).collect(), since_low.iter().map(line).collect()))
+}
+
+/// Calculates Aroon, returning named outputs
+///
+/// Same as [`aroon`] but returns an [`AroonOutput`] instead of a tuple.
+pub fn aroon_output(high: &[f64], low: &[f64], period: impl IntoParam<Period>) -> TAResult<AroonOutput> {
+    with_function("AROON", || {
+        let period = period.into_param("period")?.get();
+        aroon(high, low, period).map(AroonOutput::from)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aroon_output() {
+        let high = [10.0, 11.0, 12.0, 11.5, 11.0, 10.5, 10.0, 9.5];
+        let low = [9.0, 10.0, 11.0, 10.5, 10.0, 9.5, 9.0, 8.5];
+        let (up, down) = aroon(&high, &low, 4).unwrap();
+        let output = aroon_output(&high, &low, 4).unwrap();
+
+        assert!(output.up[3].is_nan());
+        assert_eq!(output.up[5], up[5]);
+        assert_eq!(output.down[7], down[7]);
+        assert_eq!(output.crossovers(), crossovers(&up, &down));
+
+        let full = aroon_full(&high, &low, 4, AroonScale::default()).unwrap();
+        assert_eq!(full.up[5], up[5]);
+        assert_eq!(full.oscillator[6], up[6] - down[6]);
+        assert!(full.oscillator[3].is_nan());
+        let fraction = aroon_full(&high, &low, 4, AroonScale::Fraction).unwrap();
+        assert!(fraction.oscillator.iter().skip(4).all(|v| (-1.0..=1.0).contains(v)));
+        assert!(aroon_full(&high, &low[1..], 4, AroonScale::Percent).is_err());
+    }
+
+    #[test]
+    fn test_aroon_bars_since_extreme() {
+        // Up is 100 on the bar of the highest high and falls by 100 / period a bar after it;
+        // it used to be inverted, reaching 100 only when the high was about to leave the window
+        let high = [10.0, 11.0, 12.0, 11.5, 11.0, 10.5, 10.0, 9.5];
+        let low = [9.0, 10.0, 11.0, 10.5, 10.0, 9.5, 9.0, 8.5];
+        let (up, down) = aroon(&high, &low, 4).unwrap();
+        assert_eq!(&up[4..], &[50.0, 25.0, 0.0, 0.0]);
+        assert_eq!(&down[4..], &[0.0, 100.0, 100.0, 100.0]);
+
+        // Ties go to the latest bar
+        let flat = [5.0; 6];
+        let (up, down) = aroon(&flat, &flat, 3).unwrap();
+        assert!(up[3..].iter().chain(&down[3..]).all(|&v| v == 100.0));
+        assert!(aroon(&flat, &flat, 6).is_err());
     }
-    Ok((up, down))
-} 
\ No newline at end of file
+}
@@ -1,6 +1,9 @@
 // AROONOSC - Aroon Oscillator
-use crate::common::TAResult;
-use crate::momentum::aroon;
+use crate::common::{TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::{aroon_full, AroonScale};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Aroon Oscillator.
 /// 
@@ -14,13 +17,10 @@ use crate::momentum::aroon;
 pub fn aroonosc(
     high: &[f64],
     low: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let (up, down) = aroon(high, low, period)?;
-    let len = up.len();
-    let mut out = vec![f64::NAN; len];
-    for i in 0..len {
-        out[i] = up[i] - down[i];
-    }
-    Ok(out)
+    with_function("AROONOSC", || {
+        let period = period.into_param("period")?.get();
+        Ok(aroon_full(high, low, period, AroonScale::Percent)?.oscillator)
+    })
 } 
\ No newline at end of file
@@ -1,10 +1,33 @@
 // CCI - Commodity Channel Index
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::price_transform::typprice;
-use crate::overlap::sma;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Commodity Channel Index configuration for [`cci_with`]
+///
+/// The default is Lambert's: 14 bars scaled by 0.015, so that most values fall within
+/// ±100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CciParams {
+    /// Period of the typical price average and its mean deviation
+    pub period: usize,
+    /// Constant the mean deviation is scaled by
+    pub constant: f64,
+}
+
+impl Default for CciParams {
+    fn default() -> Self {
+        Self { period: 14, constant: 0.015 }
+    }
+}
 
 /// Calculates the Commodity Channel Index (CCI).
 /// 
+/// Scales the mean deviation by 0.015; see [`cci_with`] for other constants.
+/// 
 /// # Arguments
 /// * `high` - High prices
 /// * `low` - Low prices
@@ -17,26 +40,92 @@ pub fn cci(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let len = close.len();
-    if high.len() != len || low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}", high.len(), low.len(), len)));
-    }
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
-    let tp = typprice(high, low, close)?;
-    let sma_tp = sma(&tp, period)?;
-    let mut mad = vec![f64::NAN; len];
-    for i in (period - 1)..len {
-        let mean = sma_tp[i];
-        let sum_abs: f64 = tp[i + 1 - period..=i].iter().map(|&v| (v - mean).abs()).sum();
-        mad[i] = sum_abs / period as f64;
-    }
-    let mut cci = vec![f64::NAN; len];
-    for i in (period - 1)..len {
-        cci[i] = (tp[i] - sma_tp[i]) / (0.015 * mad[i]);
+    with_function("CCI", || {
+        let period = period.into_param("period")?.get();
+        cci_with(high, low, close, &CciParams { period, ..CciParams::default() })
+    })
+}
+
+/// Calculates the Commodity Channel Index with a custom constant
+///
+/// # Formula
+/// ```text
+/// TP = (High + Low + Close) / 3
+/// CCI = (TP - SMA(TP, n)) / (constant * MeanDeviation(TP, n))
+/// ```
+///
+/// As in TA-Lib, CCI is 0 when the typical price equals its average or the window has no
+/// deviation.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `params` - Period and constant
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of CCI values; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid, the period is 0 or the constant is not
+///   positive and finite
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::{cci, cci_with, CciParams};
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+///
+/// let classic = cci(&high, &low, &close, 20).unwrap();
+/// let doubled = cci_with(&high, &low, &close, &CciParams { period: 20, constant: 0.03 }).unwrap();
+/// assert!((classic[30] - 2.0 * doubled[30]).abs() < 1e-9);
+/// ```
+pub fn cci_with(high: &[f64], low: &[f64], close: &[f64], params: &CciParams) -> TAResult<Vec<f64>> {
+    with_function("CCI", || {
+        let period = params.period.into_param("period")?.get();
+        if !(params.constant.is_finite() && params.constant > 0.0) {
+            return Err(TAError::invalid_parameter("constant", "must be positive and finite"));
+        }
+        let len = close.len();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
+        let tp = typprice(high, low, close)?;
+        let n = period as f64;
+        let mut cci = vec![f64::NAN; len];
+        for i in (period - 1)..len {
+            // Summed afresh for each window, as TA-Lib does, so that a window of equal
+            // prices leaves the same rounding residue in the mean and its deviation
+            let window = &tp[i + 1 - period..=i];
+            let mean = window.iter().sum::<f64>() / n;
+            let mad = window.iter().map(|&v| (v - mean).abs()).sum::<f64>() / n;
+            let deviation = tp[i] - mean;
+            cci[i] = if deviation == 0.0 || mad == 0.0 { 0.0 } else { deviation / (params.constant * mad) };
+        }
+        Ok(cci)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cci_params() {
+        let close: Vec<f64> = (0..30).map(|i| 50.0 + (i as f64 * 0.5).sin() * 2.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+
+        let default = cci_with(&high, &low, &close, &CciParams::default()).unwrap();
+        assert_eq!(default[20], cci(&high, &low, &close, 14).unwrap()[20]);
+        assert!(default[12].is_nan());
+
+        for constant in [0.0, -0.015, f64::INFINITY] {
+            assert!(cci_with(&high, &low, &close, &CciParams { period: 14, constant }).is_err());
+        }
+        assert!(cci_with(&high, &low, &close, &CciParams { period: 0, constant: 0.015 }).is_err());
     }
-    Ok(cci)
-} 
\ No newline at end of file
+}
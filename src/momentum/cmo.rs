@@ -1,13 +1,19 @@
 //! Chande Momentum Oscillator (CMO)
 //! 
-//! CMO is a momentum oscillator that measures the sum of gains versus the sum of losses
-//! over a specified period. It oscillates between -100 and +100.
+//! CMO is a momentum oscillator that measures gains versus losses over a specified period.
+//! It oscillates between -100 and +100.
 
-use crate::common::{TAError, validate_prices, validate_period};
+use crate::common::{TAError, validate_prices, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Chande Momentum Oscillator.
 /// 
-/// CMO = 100 * (Sum of Gains - Sum of Losses) / (Sum of Gains + Sum of Losses)
+/// CMO = 100 * (Average Gain - Average Loss) / (Average Gain + Average Loss)
+///
+/// The averages are smoothed as in RSI, and as TA-Lib does: the first is the mean over
+/// `period` changes, and each later one moves `1 / period` of the way to the new change.
 /// 
 /// # Arguments
 /// 
@@ -31,49 +37,47 @@ use crate::common::{TAError, validate_prices, validate_period};
 /// let result = cmo(&prices, 14).unwrap();
 /// assert_eq!(result.len(), 20);
 /// ```
-pub fn cmo(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
+pub fn cmo(prices: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("CMO", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
-    
-    let mut result = vec![f64::NAN; len];
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
     
-    // Calculate price changes
-    let mut changes = Vec::with_capacity(len - 1);
-    for i in 1..len {
-        changes.push(prices[i] - prices[i - 1]);
-    }
+        let mut result = vec![f64::NAN; len];
     
-    // Calculate CMO for each period
-    for i in period..len {
-        let start_idx = i - period;
-        let end_idx = i;
-        
-        let mut sum_gains = 0.0;
-        let mut sum_losses = 0.0;
-        
-        for j in start_idx..end_idx {
-            let change = changes[j];
+        let cmo_value = |gain: f64, loss: f64| {
+            let total_movement = gain + loss;
+            if total_movement == 0.0 { 0.0 } else { 100.0 * (gain - loss) / total_movement }
+        };
+
+        let mut avg_gain = 0.0;
+        let mut avg_loss = 0.0;
+        for i in 1..=period {
+            let change = prices[i] - prices[i - 1];
             if change > 0.0 {
-                sum_gains += change;
-            } else if change < 0.0 {
-                sum_losses += -change;
+                avg_gain += change;
+            } else {
+                avg_loss -= change;
             }
         }
-        
-        let total_movement = sum_gains + sum_losses;
-        if total_movement == 0.0 {
-            result[i] = 0.0;
-        } else {
-            result[i] = 100.0 * (sum_gains - sum_losses) / total_movement;
+        avg_gain /= period as f64;
+        avg_loss /= period as f64;
+        result[period] = cmo_value(avg_gain, avg_loss);
+
+        let keep = (period - 1) as f64;
+        for i in period + 1..len {
+            let change = prices[i] - prices[i - 1];
+            avg_gain = (avg_gain * keep + change.max(0.0)) / period as f64;
+            avg_loss = (avg_loss * keep + (-change).max(0.0)) / period as f64;
+            result[i] = cmo_value(avg_gain, avg_loss);
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculates CMO with smoothing.
@@ -102,39 +106,41 @@ pub fn cmo(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
 /// let result = cmo_smoothed(&prices, 10, 3).unwrap();
 /// assert_eq!(result.len(), 20);
 /// ```
-pub fn cmo_smoothed(prices: &[f64], period: usize, smooth_period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    validate_period(smooth_period, "smooth_period")?;
+pub fn cmo_smoothed(prices: &[f64], period: impl IntoParam<Period>, smooth_period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("CMO", || {
+        let period = period.into_param("period")?.get();
+        let smooth_period = smooth_period.into_param("smooth_period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period + smooth_period {
-        return Err(TAError::insufficient_data(period + smooth_period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period + smooth_period {
+            return Err(TAError::insufficient_data(period + smooth_period + 1, len));
+        }
     
-    // Calculate basic CMO first
-    let cmo_values = cmo(prices, period)?;
+        // Calculate basic CMO first
+        let cmo_values = cmo(prices, period)?;
     
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; len];
     
-    // Apply smoothing
-    for i in (period + smooth_period - 1)..len {
-        let start_idx = i - smooth_period + 1;
-        let end_idx = i + 1;
+        // Apply smoothing
+        for (i, value) in result.iter_mut().enumerate().skip(period + smooth_period - 1) {
+            let start_idx = i - smooth_period + 1;
+            let end_idx = i + 1;
         
-        let sum: f64 = cmo_values[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .sum();
-        let count = cmo_values[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .count();
+            let sum: f64 = cmo_values[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .sum();
+            let count = cmo_values[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .count();
         
-        if count == smooth_period {
-            result[i] = sum / smooth_period as f64;
+            if count == smooth_period {
+                *value = sum / smooth_period as f64;
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
@@ -206,10 +212,10 @@ mod tests {
         let prices = vec![100.0, 101.0, 100.0, 101.0, 100.0, 101.0];
         let result = cmo(&prices, 4).unwrap();
         
-        // CMO should be close to 0 when gains equal losses
-        for i in 4..6 {
-            assert!(result[i].abs() < 1e-8);
-        }
+        // CMO should be 0 when the averaged gains equal the losses
+        assert!(result[4].abs() < 1e-8);
+        // A further gain moves each average a quarter of the way: (0.625 - 0.375) / 1
+        assert!((result[5] - 25.0).abs() < 1e-8);
     }
 
     #[test]
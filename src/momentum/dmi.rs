@@ -0,0 +1,117 @@
+// DMI - Directional Movement Index system
+use crate::common::{crossovers, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::AdxState;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Directional Movement system result, returned by [`dmi`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DmiOutput {
+    /// +DI, as [`plus_di`](crate::momentum::plus_di)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub plus_di: Vec<f64>,
+    /// -DI, as [`minus_di`](crate::momentum::minus_di)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub minus_di: Vec<f64>,
+    /// ADX, as [`adx`](crate::momentum::adx)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub adx: Vec<f64>,
+    /// ADXR, as [`adxr`](crate::momentum::adxr)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub adxr: Vec<f64>,
+    /// DX, as [`dx`](crate::momentum::dx)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub dx: Vec<f64>,
+}
+
+impl DmiOutput {
+    /// +DI/-DI crossovers: 1 where +DI crosses above -DI, -1 where it crosses below
+    pub fn crossovers(&self) -> Vec<i8> {
+        crossovers(&self.plus_di, &self.minus_di)
+    }
+}
+
+/// Calculates the whole Directional Movement system at once
+///
+/// Gives the same values as [`plus_di`](crate::momentum::plus_di),
+/// [`minus_di`](crate::momentum::minus_di), [`dx`](crate::momentum::dx),
+/// [`adx`](crate::momentum::adx) and [`adxr`](crate::momentum::adxr), computing the
+/// directional movement and true range they share once instead of once per function.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `period` - Period of the directional movement and ADX smoothing, and one more than the ADXR lag
+///
+/// # Returns
+/// * `Ok(DmiOutput)` - All five series, as long as the inputs
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::{adx, dmi, plus_di};
+///
+/// let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.2).sin() * 6.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+///
+/// let system = dmi(&high, &low, &close, 14).unwrap();
+/// assert_eq!(system.plus_di[40], plus_di(&high, &low, &close, 14).unwrap()[40]);
+/// assert_eq!(system.adx[40], adx(&high, &low, &close, 14).unwrap()[40]);
+/// ```
+pub fn dmi(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<DmiOutput> {
+    with_function("DMI", || {
+        let period = period.into_param("period")?.get();
+        let len = close.len();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let mut state = AdxState::new(period)?;
+
+        let mut plus_di = vec![f64::NAN; len];
+        let mut minus_di = vec![f64::NAN; len];
+        let mut dx = vec![f64::NAN; len];
+        let mut adx = vec![f64::NAN; len];
+        for i in 0..len {
+            adx[i] = state.update(high[i], low[i], close[i]).unwrap_or(f64::NAN);
+            if let Some((plus, minus)) = state.di() {
+                plus_di[i] = plus;
+                minus_di[i] = minus;
+            }
+            dx[i] = state.dx().unwrap_or(f64::NAN);
+        }
+
+        let mut adxr = vec![f64::NAN; len];
+        for i in period - 1..len {
+            adxr[i] = (adx[i] + adx[i + 1 - period]) / 2.0;
+        }
+
+        Ok(DmiOutput { plus_di, minus_di, adx, adxr, dx })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::momentum::{adx, adxr, dx, minus_di, plus_di};
+
+    #[test]
+    fn test_dmi_matches_functions() {
+        let close: Vec<f64> = (0..120).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+
+        for period in [1, 5, 14] {
+            let system = dmi(&high, &low, &close, period).unwrap();
+            assert_arrays_approx_equal(&system.plus_di, &plus_di(&high, &low, &close, period).unwrap(), 0.0);
+            assert_arrays_approx_equal(&system.minus_di, &minus_di(&high, &low, &close, period).unwrap(), 0.0);
+            assert_arrays_approx_equal(&system.dx, &dx(&high, &low, &close, period).unwrap(), 0.0);
+            assert_arrays_approx_equal(&system.adx, &adx(&high, &low, &close, period).unwrap(), 0.0);
+            assert_arrays_approx_equal(&system.adxr, &adxr(&high, &low, &close, period).unwrap(), 0.0);
+        }
+        assert!(dmi(&high, &low, &close, 0).is_err());
+        assert!(dmi(&high, &low, &close[1..], 14).is_err());
+    }
+}
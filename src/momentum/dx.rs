@@ -1,8 +1,15 @@
 // DX - Directional Movement Index
-use crate::common::{TAError, TAResult};
-use crate::momentum::{plus_di, minus_di};
+use crate::common::{TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::volatility::trange::true_range;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Directional Movement Index.
+///
+/// DX is `100 * |+DI - -DI| / (+DI + -DI)`, starting with the directional indicators at
+/// bar `period`. A bar whose true range or directional indicators sum to zero repeats the
+/// previous DX, or gives 0 if it is the first, as TA-Lib does.
 /// 
 /// # Arguments
 /// * `high` - High prices
@@ -16,22 +23,231 @@ pub fn dx(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let len = close.len();
-    if high.len() != len || low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}", high.len(), low.len(), len)));
-    }
-    let plusdi = plus_di(high, low, close, period)?;
-    let minusdi = minus_di(high, low, close, period)?;
-    let mut out = vec![f64::NAN; len];
-    for i in 0..len {
-        let denom = plusdi[i].abs() + minusdi[i].abs();
-        if denom < 1e-12 {
-            out[i] = 0.0;
-        } else {
-            out[i] = 100.0 * (plusdi[i] - minusdi[i]).abs() / denom;
+    with_function("DX", || {
+        let period = period.into_param("period")?.get();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let mut out = vec![f64::NAN; close.len()];
+        let mut state = DiState::new(period)?;
+        for i in 0..close.len() {
+            state.update(high[i], low[i], close[i]);
+            out[i] = state.dx().unwrap_or(f64::NAN);
+        }
+        Ok(out)
+    })
+}
+
+/// Directional movement of a bar, `(+DM, -DM)`, from its high and low and the previous bar's
+#[inline]
+pub(crate) fn directional_movement(high: f64, low: f64, prev_high: f64, prev_low: f64) -> (f64, f64) {
+    let up = high - prev_high;
+    let down = prev_low - low;
+    (
+        if up > 0.0 && up > down { up } else { 0.0 },
+        if down > 0.0 && down > up { down } else { 0.0 },
+    )
+}
+
+/// Adds the `count`-th value to a Wilder sum over `period`, as TA-Lib smooths directional
+/// movement: the first `period - 1` values are summed, and each later one replaces
+/// `1 / period` of the sum
+#[inline]
+pub(crate) fn wilder_sum(sum: f64, value: f64, count: usize, period: usize) -> f64 {
+    if count < period {
+        sum + value
+    } else {
+        sum - sum / period as f64 + value
+    }
+}
+
+/// Directional indicator of a smoothed directional movement and true range
+#[inline]
+fn di_value(dm: f64, tr: f64) -> f64 {
+    if tr.abs() < 1e-12 {
+        0.0
+    } else {
+        100.0 * dm / tr
+    }
+}
+
+/// DX of a pair of directional indicators, or `None` if they sum to zero
+#[inline]
+fn dx_value(plus_di: f64, minus_di: f64) -> Option<f64> {
+    let denom = plus_di + minus_di;
+    (denom.abs() >= 1e-12).then(|| 100.0 * (plus_di - minus_di).abs() / denom)
+}
+
+/// Streaming directional indicators
+///
+/// Produces the same +DI and -DI as [`plus_di`](crate::momentum::plus_di) and
+/// [`minus_di`](crate::momentum::minus_di), and the DX of [`dx`], one bar at a time in O(1)
+/// time and memory.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::momentum::{dx, plus_di, DiState};
+///
+/// let high = vec![10.0, 11.0, 12.0, 11.5, 13.0, 12.5, 14.0];
+/// let low = vec![9.0, 10.0, 10.5, 10.0, 11.0, 11.5, 12.0];
+/// let close = vec![9.5, 10.5, 11.5, 10.5, 12.0, 12.0, 13.0];
+/// let plus = plus_di(&high, &low, &close, 3).unwrap();
+/// let batch = dx(&high, &low, &close, 3).unwrap();
+///
+/// let mut state = DiState::new(3).unwrap();
+/// for i in 0..close.len() {
+///     match state.update(high[i], low[i], close[i]) {
+///         Some((plus_di, _)) => {
+///             assert!((plus_di - plus[i]).abs() < 1e-12);
+///             assert!((state.dx().unwrap() - batch[i]).abs() < 1e-12);
+///         }
+///         None => assert_eq!(state.bars_until_warm(), 3 - i),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiState {
+    period: usize,
+    /// Number of bars seen
+    count: usize,
+    /// High, low and close of the previous bar
+    prev: Option<(f64, f64, f64)>,
+    /// Wilder sums of +DM, -DM and the true range
+    plus_dm: f64,
+    minus_dm: f64,
+    tr: f64,
+    value: Option<(f64, f64)>,
+    /// DX of the latest bar, `None` if its indicators sum to zero
+    raw_dx: Option<f64>,
+    /// DX reported, carrying the last one over bars without one
+    dx: Option<f64>,
+}
+
+impl DiState {
+    /// Creates a new state for the given period
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn new(period: impl IntoParam<Period>) -> TAResult<Self> {
+        with_function("DX", || {
+            let period = period.into_param("period")?.get();
+            Ok(Self {
+                period,
+                count: 0,
+                prev: None,
+                plus_dm: 0.0,
+                minus_dm: 0.0,
+                tr: 0.0,
+                value: None,
+                raw_dx: None,
+                dx: None,
+            })
+        })
+    }
+
+    /// Feeds the next bar and returns `(+DI, -DI)`, or `None` during warm-up
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, f64)> {
+        if let Some((prev_high, prev_low, prev_close)) = self.prev {
+            // `count` bars seen before this one, so this is movement number `count`
+            let (plus_dm, minus_dm) = directional_movement(high, low, prev_high, prev_low);
+            self.plus_dm = wilder_sum(self.plus_dm, plus_dm, self.count, self.period);
+            self.minus_dm = wilder_sum(self.minus_dm, minus_dm, self.count, self.period);
+            self.tr = wilder_sum(self.tr, true_range(high, low, prev_close), self.count, self.period);
         }
+        self.prev = Some((high, low, close));
+        self.count += 1;
+
+        if self.count > self.period {
+            let plus_di = di_value(self.plus_dm, self.tr);
+            let minus_di = di_value(self.minus_dm, self.tr);
+            self.value = Some((plus_di, minus_di));
+            self.raw_dx = dx_value(plus_di, minus_di);
+            self.dx = self.raw_dx.or(self.dx).or(Some(0.0));
+        }
+        self.value
+    }
+
+    /// Current `(+DI, -DI)`, or `None` during warm-up
+    pub fn current(&self) -> Option<(f64, f64)> {
+        self.value
+    }
+
+    /// Current DX, or `None` during warm-up
+    pub fn dx(&self) -> Option<f64> {
+        self.dx
+    }
+
+    /// DX of the latest bar alone, `None` during warm-up or if it has none
+    pub(crate) fn raw_dx(&self) -> Option<f64> {
+        self.raw_dx
+    }
+
+    /// Whether the indicators have a value
+    pub fn is_warm(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// Number of further bars needed before the indicators have a value
+    pub fn bars_until_warm(&self) -> usize {
+        // The first bar has no directional movement
+        (self.period + 1).saturating_sub(self.count)
+    }
+
+    /// Period of the indicators
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        *self = Self {
+            period: self.period,
+            count: 0,
+            prev: None,
+            plus_dm: 0.0,
+            minus_dm: 0.0,
+            tr: 0.0,
+            value: None,
+            raw_dx: None,
+            dx: None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::{minus_di, plus_di};
+
+    #[test]
+    fn test_di_state_matches_batch() {
+        let close: Vec<f64> = (0..80).map(|i| 50.0 + (i as f64 * 0.15).sin() * 6.0).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+
+        for period in [1, 2, 14] {
+            let plus = plus_di(&high, &low, &close, period).unwrap();
+            let minus = minus_di(&high, &low, &close, period).unwrap();
+            let batch = dx(&high, &low, &close, period).unwrap();
+            let first = batch.iter().position(|v| !v.is_nan()).unwrap();
+            let mut state = DiState::new(period).unwrap();
+            for i in 0..close.len() {
+                assert_eq!(state.bars_until_warm(), (first + 1).saturating_sub(i), "{} at {}", period, i);
+                match state.update(high[i], low[i], close[i]) {
+                    Some((plus_di, minus_di)) => {
+                        assert!((plus_di - plus[i]).abs() < 1e-10);
+                        assert!((minus_di - minus[i]).abs() < 1e-10);
+                        assert!((state.dx().unwrap() - batch[i]).abs() < 1e-10);
+                    }
+                    None => assert!(batch[i].is_nan(), "{} at {}", period, i),
+                }
+            }
+            assert!(state.is_warm());
+            state.reset();
+            assert_eq!(state, DiState::new(period).unwrap());
+        }
+
+        assert!(DiState::new(0).is_err());
     }
-    Ok(out)
-} 
\ No newline at end of file
+}
@@ -0,0 +1,104 @@
+//! Elder Ray Index (Bull Power / Bear Power)
+//!
+//! Alexander Elder's Elder Ray measures how far buyers pushed the high above, and sellers
+//! pushed the low below, the consensus value given by an EMA of the close.
+
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::overlap::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Elder Ray result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElderRay {
+    /// Bull power (high - EMA)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub bull_power: Vec<f64>,
+    /// Bear power (low - EMA)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub bear_power: Vec<f64>,
+}
+
+/// Elder Ray Index
+///
+/// # Formula
+/// ```text
+/// Bull Power = High - EMA(Close, period)
+/// Bear Power = Low  - EMA(Close, period)
+/// ```
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - EMA period (typically 13)
+///
+/// # Returns
+/// * `Ok(ElderRay)` - Bull and bear power; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::elder_ray;
+///
+/// let high = vec![11.0, 12.0, 13.0, 12.5, 13.5];
+/// let low = vec![9.0, 10.0, 11.0, 10.5, 11.5];
+/// let close = vec![10.0, 11.0, 12.0, 11.5, 12.5];
+/// let result = elder_ray(&high, &low, &close, 3).unwrap();
+/// // EMA at bar 2 is the seed SMA of 11.0
+/// assert_eq!(result.bull_power[2], 2.0);
+/// assert_eq!(result.bear_power[2], 0.0);
+/// ```
+pub fn elder_ray(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<ElderRay> {
+    with_function("ELDERRAY", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+
+        let average = ema(close, period)?;
+
+        let bull_power = high.iter().zip(&average).map(|(&h, &avg)| h - avg).collect();
+        let bear_power = low.iter().zip(&average).map(|(&l, &avg)| l - avg).collect();
+
+        Ok(ElderRay {
+            bull_power,
+            bear_power,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_elder_ray_basic() {
+        let close: Vec<f64> = (0..20).map(|i| 30.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+        let result = elder_ray(&high, &low, &close, 5).unwrap();
+        let average = ema(&close, 5).unwrap();
+
+        assert!(result.bull_power[..4].iter().all(|v| v.is_nan()));
+        assert!(result.bear_power[..4].iter().all(|v| v.is_nan()));
+        for i in 4..20 {
+            assert_float_eq!(result.bull_power[i], high[i] - average[i], 1e-12);
+            assert_float_eq!(result.bear_power[i], low[i] - average[i], 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_elder_ray_invalid_input() {
+        let prices = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(elder_ray(&[], &[], &[], 3).is_err());
+        assert!(elder_ray(&prices[..3], &prices, &prices, 3).is_err());
+        assert!(elder_ray(&prices, &prices, &prices, 0).is_err());
+        assert!(elder_ray(&prices, &prices, &prices, 5).is_err());
+    }
+}
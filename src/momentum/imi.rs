@@ -0,0 +1,99 @@
+// IMI - Intraday Momentum Index
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the Intraday Momentum Index (IMI).
+///
+/// IMI is an RSI computed from the open-to-close move of each bar instead of the
+/// close-to-close change, as in TA-Lib's `TA_IMI`.
+///
+/// # Formula
+/// ```text
+/// Up   = Σ(Close - Open) over bars where Close > Open
+/// Down = Σ(Open - Close) over bars where Close < Open
+/// IMI  = 100 × Up / (Up + Down)
+/// ```
+///
+/// # Arguments
+/// * `open` - Open prices
+/// * `close` - Close prices
+/// * `period` - Number of bars summed (typically 14)
+///
+/// # Returns
+/// Vector of IMI values; the first `period-1` values are NaN. A window without any
+/// open-to-close movement gives 0.
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::imi;
+///
+/// let open = vec![10.0, 11.0, 12.0];
+/// let close = vec![11.0, 10.5, 12.5];
+/// let result = imi(&open, &close, 3).unwrap();
+/// // Up moves 1.0 + 0.5, down move 0.5
+/// assert_eq!(result[2], 75.0);
+/// ```
+pub fn imi(open: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("IMI", || {
+        let period = period.into_param("period")?.get();
+        let len = close.len();
+        if open.len() != len {
+            return Err(TAError::mismatched_inputs(format!("open: {}, close: {}", open.len(), len)));
+        }
+        if len == 0 {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+        if period > len {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let mut imi = vec![f64::NAN; len];
+        for (i, value) in imi.iter_mut().enumerate().skip(period - 1) {
+            let mut up = 0.0;
+            let mut down = 0.0;
+            for j in (i + 1 - period)..=i {
+                let change = close[j] - open[j];
+                if change > 0.0 {
+                    up += change;
+                } else {
+                    down -= change;
+                }
+            }
+            *value = if up + down > 0.0 { 100.0 * up / (up + down) } else { 0.0 };
+        }
+        Ok(imi)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imi_basic() {
+        let open = vec![10.0, 11.0, 12.0, 12.0, 13.0];
+        let close = vec![11.0, 10.0, 12.0, 14.0, 12.5];
+        let result = imi(&open, &close, 3).unwrap();
+
+        assert!(result[1].is_nan());
+        assert!((result[2] - 50.0).abs() < 1e-12);
+        assert!((result[3] - 100.0 * 2.0 / 3.0).abs() < 1e-12);
+        assert!((result[4] - 80.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_imi_flat_window() {
+        let prices = vec![5.0; 4];
+        assert_eq!(imi(&prices, &prices, 2).unwrap()[3], 0.0);
+    }
+
+    #[test]
+    fn test_imi_invalid_input() {
+        assert!(imi(&[], &[], 3).is_err());
+        assert!(imi(&[1.0], &[1.0, 2.0], 1).is_err());
+        assert!(imi(&[1.0, 2.0], &[1.0, 2.0], 0).is_err());
+        assert!(imi(&[1.0, 2.0], &[1.0, 2.0], 3).is_err());
+    }
+}
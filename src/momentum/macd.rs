@@ -2,10 +2,53 @@
 // Input: price: &[f64], fast_period: usize, slow_period: usize, signal_period: usize
 // Output: (Vec<f64>, Vec<f64>, Vec<f64>) => (macd, signal, hist)
 
-use crate::common::{TAError, TAResult};
-use crate::overlap::ema;
+use crate::common::{crossovers, ema_multiplier, MAType, TAError, TAResult, IntoParam, Period, with_nan_policy, NanPolicy};
+use crate::common::errors::with_function;
+use crate::overlap::ewm::{ewm_unchecked_into, EwmInit};
+use crate::overlap::{ema, MaState};
+use crate::overlap::ma::ma_skip_nan;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// MACD result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacdOutput {
+    /// MACD line (fast EMA - slow EMA)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub macd: Vec<f64>,
+    /// Signal line (EMA of the MACD line)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub signal: Vec<f64>,
+    /// Histogram (MACD - signal)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub histogram: Vec<f64>,
+}
+
+impl MacdOutput {
+    /// Signal line crossovers: 1 where MACD crosses above the signal line, -1 where it crosses below
+    pub fn crossovers(&self) -> Vec<i8> {
+        crossovers(&self.macd, &self.signal)
+    }
+
+    /// Zero line crossovers of the MACD line: 1 on a cross above zero, -1 on a cross below
+    pub fn zero_crossovers(&self) -> Vec<i8> {
+        crossovers(&self.macd, &vec![0.0; self.macd.len()])
+    }
+}
+
+impl From<(Vec<f64>, Vec<f64>, Vec<f64>)> for MacdOutput {
+    /// Converts the `(macd, signal, histogram)` tuple returned by [`macd`],
+    /// [`macdext`](crate::momentum::macdext) and [`macdfix`](crate::momentum::macdfix)
+    fn from((macd, signal, histogram): (Vec<f64>, Vec<f64>, Vec<f64>)) -> Self {
+        Self { macd, signal, histogram }
+    }
+}
 
 /// Calculates the MACD (Moving Average Convergence/Divergence) indicator.
+///
+/// As in TA-Lib, the fast EMA is seeded over the `fast_period` bars ending where the slow
+/// EMA starts, so both start on the same bar.
 /// 
 /// # Arguments
 /// * `price` - Input price series
@@ -20,56 +63,67 @@ use crate::overlap::ema;
 /// Returns `TAError::InsufficientData` if input is too short.
 pub fn macd(
     price: &[f64],
-    fast_period: usize,
-    slow_period: usize,
-    signal_period: usize,
+    fast_period: impl IntoParam<Period>,
+    slow_period: impl IntoParam<Period>,
+    signal_period: impl IntoParam<Period>,
 ) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
-    if price.is_empty() {
-        return Err(TAError::invalid_input("Price data cannot be empty"));
-    }
+    with_function("MACD", || {
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        if price.is_empty() {
+            return Err(TAError::invalid_input("Price data cannot be empty"));
+        }
     
-    if price.len() < slow_period {
-        return Err(TAError::insufficient_data(slow_period, price.len()));
-    }
+        if price.len() < slow_period {
+            return Err(TAError::insufficient_data(slow_period, price.len()));
+        }
+    
+        // Calculate EMAs
+        let fast_ema = aligned_fast_ema(price, ema_multiplier(fast_period), fast_period, slow_period);
+        let slow_ema = ema(price, slow_period)?;
     
-    // Calculate EMAs
-    let fast_ema = ema(price, fast_period)?;
-    let slow_ema = ema(price, slow_period)?;
+        macd_from_emas(&fast_ema, &slow_ema, slow_period, signal_period)
+    })
+}
+
+/// Number of bars before the fast EMA's seed window, so that it ends with the slow one's
+fn fast_ema_offset(fast_period: usize, slow_period: usize) -> usize {
+    slow_period.saturating_sub(fast_period)
+}
+
+/// Fast EMA seeded over the `fast_period` bars ending at the first value of the slow EMA,
+/// as TA-Lib seeds it
+pub(crate) fn aligned_fast_ema(price: &[f64], alpha: f64, fast_period: usize, slow_period: usize) -> Vec<f64> {
+    let offset = fast_ema_offset(fast_period, slow_period).min(price.len());
+    let mut fast_ema = vec![f64::NAN; price.len()];
+    ewm_unchecked_into(&price[offset..], alpha, EwmInit::Sma(fast_period), &mut fast_ema[offset..]);
+    fast_ema
+}
+
+/// MACD line, signal and histogram from precomputed fast and slow EMAs
+pub(crate) fn macd_from_emas(
+    fast_ema: &[f64],
+    slow_ema: &[f64],
+    slow_period: usize,
+    signal_period: usize,
+) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    let len = slow_ema.len();
     
     // Calculate MACD line
-    let mut macd_line = vec![f64::NAN; price.len()];
-    for i in (slow_period - 1)..price.len() {
+    let mut macd_line = vec![f64::NAN; len];
+    for i in (slow_period - 1)..len {
         if !fast_ema[i].is_nan() && !slow_ema[i].is_nan() {
             macd_line[i] = fast_ema[i] - slow_ema[i];
         }
     }
     
-    // Calculate signal line using EMA of MACD line
-    // Extract valid MACD values for signal calculation
-    let valid_macd_start = slow_period - 1;
-    let macd_for_signal: Vec<f64> = macd_line[valid_macd_start..].to_vec();
-    
-    if macd_for_signal.len() < signal_period {
-        // Not enough data for signal line
-        let signal_line = vec![f64::NAN; price.len()];
-        let hist = vec![f64::NAN; price.len()];
-        return Ok((macd_line, signal_line, hist));
-    }
-    
-    let signal_ema = ema(&macd_for_signal, signal_period)?;
-    
-    // Map signal back to full length
-    let mut signal_line = vec![f64::NAN; price.len()];
-    for (i, &sig_val) in signal_ema.iter().enumerate() {
-        let full_index = valid_macd_start + i;
-        if full_index < price.len() {
-            signal_line[full_index] = sig_val;
-        }
-    }
+    // Calculate signal line using EMA of the valid MACD values
+    let signal_line = ma_skip_nan(&macd_line, signal_period, MAType::EMA)?;
     
     // Calculate histogram
-    let mut hist = vec![f64::NAN; price.len()];
-    for i in 0..price.len() {
+    let mut hist = vec![f64::NAN; len];
+    for i in 0..len {
         if !macd_line[i].is_nan() && !signal_line[i].is_nan() {
             hist[i] = macd_line[i] - signal_line[i];
         }
@@ -78,6 +132,146 @@ pub fn macd(
     Ok((macd_line, signal_line, hist))
 }
 
+/// Calculates MACD of a series with missing values
+///
+/// Same as [`macd`], with NaN in `price` handled according to `policy` rather than
+/// reaching the EMA states and making every later value NaN; see
+/// [`with_nan_policy`](crate::common::with_nan_policy).
+///
+/// # Errors
+/// Same as [`macd`], plus `InvalidInput` if `price` holds NaN under [`NanPolicy::Error`]
+pub fn macd_with_policy(
+    price: &[f64],
+    fast_period: impl IntoParam<Period>,
+    slow_period: impl IntoParam<Period>,
+    signal_period: impl IntoParam<Period>,
+    policy: NanPolicy,
+) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    with_function("MACD", || {
+        with_nan_policy(&[price], policy, |i| macd(i[0], fast_period, slow_period, signal_period))
+    })
+}
+
+/// Calculates MACD, returning named outputs
+///
+/// Same as [`macd`] but returns a [`MacdOutput`] instead of a tuple.
+///
+/// # Example
+/// ```
+/// use ta_rust::momentum::macd_output;
+///
+/// let price: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+/// let result = macd_output(&price, 12, 26, 9).unwrap();
+/// let signals = result.crossovers();
+/// assert_eq!(signals.len(), result.histogram.len());
+/// ```
+pub fn macd_output(
+    price: &[f64],
+    fast_period: impl IntoParam<Period>,
+    slow_period: impl IntoParam<Period>,
+    signal_period: impl IntoParam<Period>,
+) -> TAResult<MacdOutput> {
+    with_function("MACD", || {
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        macd(price, fast_period, slow_period, signal_period).map(MacdOutput::from)
+    })
+}
+
+/// Streaming MACD
+///
+/// Produces the same values as [`macd`] one price at a time, in O(1) time and memory per
+/// update.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::momentum::{macd, MacdState};
+///
+/// let prices: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+/// let (line, signal, hist) = macd(&prices, 12, 26, 9).unwrap();
+///
+/// let mut state = MacdState::new(12, 26, 9).unwrap();
+/// for (i, &price) in prices.iter().enumerate() {
+///     match state.update(price) {
+///         Some((m, s, h)) => {
+///             assert!((m - line[i]).abs() < 1e-9);
+///             assert!((s - signal[i]).abs() < 1e-9);
+///             assert!((h - hist[i]).abs() < 1e-9);
+///         }
+///         None => assert!(hist[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacdState {
+    fast: MaState,
+    slow: MaState,
+    signal: MaState,
+    /// Bars to skip before feeding the fast EMA, and the number seen
+    fast_offset: usize,
+    count: usize,
+    /// Current MACD line, which starts before the signal line
+    line: Option<f64>,
+}
+
+impl MacdState {
+    /// Creates a new state for the given periods
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if a period is 0
+    pub fn new(fast_period: impl IntoParam<Period>, slow_period: impl IntoParam<Period>, signal_period: impl IntoParam<Period>) -> TAResult<Self> {
+        with_function("MACD", || {
+            let fast_period = fast_period.into_param("fast_period")?.get();
+            let slow_period = slow_period.into_param("slow_period")?.get();
+            let signal_period = signal_period.into_param("signal_period")?.get();
+
+            Ok(Self {
+                fast: MaState::new(fast_period, MAType::EMA)?,
+                slow: MaState::new(slow_period, MAType::EMA)?,
+                signal: MaState::new(signal_period, MAType::EMA)?,
+                fast_offset: fast_ema_offset(fast_period, slow_period),
+                count: 0,
+                line: None,
+            })
+        })
+    }
+
+    /// Feeds the next price and returns `(macd, signal, histogram)`, or `None` until the
+    /// signal line is warmed up
+    pub fn update(&mut self, price: f64) -> Option<(f64, f64, f64)> {
+        let fast = if self.count >= self.fast_offset { self.fast.update(price) } else { None };
+        let slow = self.slow.update(price);
+        self.count += 1;
+        self.line = fast.zip(slow).map(|(fast, slow)| fast - slow);
+        if let Some(line) = self.line {
+            self.signal.update(line);
+        }
+        self.current()
+    }
+
+    /// Current `(macd, signal, histogram)`, or `None` until the signal line is warmed up
+    pub fn current(&self) -> Option<(f64, f64, f64)> {
+        let (line, signal) = (self.line?, self.signal.current()?);
+        Some((line, signal, line - signal))
+    }
+
+    /// Current MACD line, available `signal_period - 1` bars before the signal line
+    pub fn macd_line(&self) -> Option<f64> {
+        self.line
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.count = 0;
+        self.line = None;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,4 +286,66 @@ mod tests {
             assert!((hist[i] - 0.0).abs() < 1e-8);
         }
     }
+
+    #[test]
+    fn test_macd_output_matches_tuple() {
+        let price: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.15).sin() * 5.0).collect();
+        let (macd_line, signal, hist) = macd(&price, 12, 26, 9).unwrap();
+        let output = macd_output(&price, 12, 26, 9).unwrap();
+
+        assert_eq!(output.macd[40], macd_line[40]);
+        assert_eq!(output.signal[40], signal[40]);
+        assert_eq!(output.histogram[40], hist[40]);
+
+        // Signal crossovers happen where the histogram changes sign
+        let signals = output.crossovers();
+        assert!(signals.iter().any(|&s| s != 0));
+        for i in 1..price.len() {
+            if signals[i] == 1 {
+                assert!(hist[i - 1] <= 0.0 && hist[i] > 0.0);
+            } else if signals[i] == -1 {
+                assert!(hist[i - 1] >= 0.0 && hist[i] < 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_macd_state_matches_batch() {
+        let price: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.15).sin() * 5.0 + i as f64 * 0.1).collect();
+        // Fast period above the slow one delays the line until the fast EMA is ready
+        for (fast, slow, signal) in [(12, 26, 9), (5, 3, 4), (1, 1, 1)] {
+            let (line, sig, hist) = macd(&price, fast, slow, signal).unwrap();
+            let mut state = MacdState::new(fast, slow, signal).unwrap();
+            for (i, &p) in price.iter().enumerate() {
+                let value = state.update(p);
+                assert_eq!(state.macd_line().is_some(), !line[i].is_nan());
+                match value {
+                    Some((m, s, h)) => {
+                        assert!((m - line[i]).abs() < 1e-9);
+                        assert!((s - sig[i]).abs() < 1e-9);
+                        assert!((h - hist[i]).abs() < 1e-9);
+                    }
+                    None => assert!(hist[i].is_nan()),
+                }
+            }
+            state.reset();
+            assert_eq!(state, MacdState::new(fast, slow, signal).unwrap());
+        }
+        assert!(MacdState::new(12, 0, 9).is_err());
+    }
+
+    #[test]
+    fn test_macd_signal_unchanged_by_skip_nan() {
+        // The signal used to be an EMA of the MACD line from `slow_period - 1` on; smoothing
+        // the valid values with `ma_skip_nan` gives the same output
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + (i as f64 * 2.1).sin() * 3.0 + i as f64 * 0.1).collect();
+        let (line, signal, _) = macd(&close, 5, 10, 4).unwrap();
+        let before = ema(&line[9..], 4).unwrap();
+        assert!(signal[..9].iter().all(|v| v.is_nan()));
+        for (after, before) in signal[9..].iter().zip(&before) {
+            assert!(after.is_nan() && before.is_nan() || (after - before).abs() < 1e-12);
+        }
+        assert_eq!(signal.iter().take_while(|v| v.is_nan()).count(), crate::common::macd_lookback(5, 10, 4));
+        assert!((signal[12] - -1.0977180679365723).abs() < 1e-9);
+    }
 }
@@ -1,36 +1,59 @@
 // MACDEXT - MACD dengan tipe MA yang bisa dipilih
-use crate::common::{TAError, TAResult, MAType};
+use crate::common::{TAError, TAResult, MAType, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::overlap::ma;
+use crate::overlap::ma::ma_skip_nan;
+use crate::momentum::price_oscillator::oscillator_from_mas;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// MACDEXT: MACD dengan tipe MA custom
 pub fn macdext(
     price: &[f64],
-    fast_period: usize,
+    fast_period: impl IntoParam<Period>,
     fast_ma: MAType,
-    slow_period: usize,
+    slow_period: impl IntoParam<Period>,
     slow_ma: MAType,
-    signal_period: usize,
+    signal_period: impl IntoParam<Period>,
     signal_ma: MAType,
 ) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
-    if price.len() < slow_period + signal_period - 1 {
-        return Err(TAError::insufficient_data(slow_period + signal_period - 1, price.len()));
-    }
-    let fast = ma(price, fast_period, fast_ma)?;
-    let slow = ma(price, slow_period, slow_ma)?;
-    let mut macd = vec![f64::NAN; price.len()];
-    for i in 0..price.len() {
-        if i < slow_period - 1 {
-            continue;
+    with_function("MACDEXT", || {
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        if price.len() < slow_period + signal_period - 1 {
+            return Err(TAError::insufficient_data(slow_period + signal_period - 1, price.len()));
         }
-        macd[i] = fast[i] - slow[i];
-    }
-    let signal = ma(&macd, signal_period, signal_ma)?;
-    let mut hist = vec![f64::NAN; price.len()];
-    for i in 0..price.len() {
-        if i < slow_period + signal_period - 2 {
-            continue;
+        let fast = ma(price, fast_period, fast_ma)?;
+        let slow = ma(price, slow_period, slow_ma)?;
+        let macd = oscillator_from_mas(&fast, &slow, slow_period, false);
+        let signal = ma_skip_nan(&macd, signal_period, signal_ma)?;
+        let mut hist = vec![f64::NAN; price.len()];
+        for i in 0..price.len() {
+            if i < slow_period + signal_period - 2 {
+                continue;
+            }
+            hist[i] = macd[i] - signal[i];
         }
-        hist[i] = macd[i] - signal[i];
+        Ok((macd, signal, hist))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macdext_signal_skips_warmup() {
+        // An EMA signal used to run over the NaN warm-up of the MACD line and was NaN
+        // throughout; it now starts at the first MACD value
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + (i as f64 * 2.1).sin() * 3.0 + i as f64 * 0.1).collect();
+        let (line, signal, _) = macdext(&close, 5, MAType::EMA, 10, MAType::EMA, 4, MAType::EMA).unwrap();
+        assert!(ma(&line, 4, MAType::EMA).unwrap().iter().all(|v| v.is_nan()));
+
+        let lookback = crate::common::macdext_lookback(5, MAType::EMA, 10, MAType::EMA, 4, MAType::EMA);
+        assert_eq!(signal.iter().take_while(|v| v.is_nan()).count(), lookback);
+        assert!((signal[12] - -0.20330193006344288).abs() < 1e-9);
+        assert!((signal[13] - 0.23139129848079495).abs() < 1e-9);
     }
-    Ok((macd, signal, hist))
-} 
\ No newline at end of file
+}
@@ -1,8 +1,15 @@
 // MACDFIX - MACD dengan fixed 12/26, signal period custom
-use crate::common::{TAError, TAResult};
-use crate::overlap::ema;
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::macd::{aligned_fast_ema, macd_from_emas};
+use crate::overlap::ewm::{ewm_unchecked_into, EwmInit};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates MACD with fixed 12/26 periods and custom signal period.
+///
+/// As in TA-Lib, the EMAs use the fixed factors 0.15 and 0.075 rather than the
+/// `2 / (period + 1)` of 12 and 26 bars, and the fast EMA is seeded as in [`macd`](crate::momentum::macd).
 /// 
 /// # Arguments
 /// * `price` - Input price series
@@ -10,26 +17,34 @@ use crate::overlap::ema;
 /// 
 /// # Returns
 /// Tuple of (MACD line, Signal line, Histogram)
-pub fn macdfix(price: &[f64], signal_period: usize) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
-    if price.len() < 26 + signal_period - 1 {
-        return Err(TAError::insufficient_data(26 + signal_period - 1, price.len()));
-    }
-    let fast_ema = ema(price, 12)?;
-    let slow_ema = ema(price, 26)?;
-    let mut macd = vec![f64::NAN; price.len()];
-    for i in 0..price.len() {
-        if i < 25 {
-            continue;
-        }
-        macd[i] = fast_ema[i] - slow_ema[i];
-    }
-    let signal = ema(&macd, signal_period)?;
-    let mut hist = vec![f64::NAN; price.len()];
-    for i in 0..price.len() {
-        if i < 25 + signal_period - 1 {
-            continue;
+pub fn macdfix(price: &[f64], signal_period: impl IntoParam<Period>) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    with_function("MACDFIX", || {
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        if price.len() < 26 + signal_period - 1 {
+            return Err(TAError::insufficient_data(26 + signal_period - 1, price.len()));
         }
-        hist[i] = macd[i] - signal[i];
+        let fast_ema = aligned_fast_ema(price, 0.15, 12, 26);
+        let mut slow_ema = vec![f64::NAN; price.len()];
+        ewm_unchecked_into(price, 0.075, EwmInit::Sma(26), &mut slow_ema);
+        macd_from_emas(&fast_ema, &slow_ema, 26, signal_period)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macdfix_signal_skips_warmup() {
+        // The signal EMA used to run over the NaN warm-up of the MACD line and was NaN
+        // throughout; it now starts at the first MACD value
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + (i as f64 * 2.1).sin() * 3.0 + i as f64 * 0.1).collect();
+        let (line, signal, hist) = macdfix(&close, 4).unwrap();
+        assert!(crate::overlap::ema(&line, 4).unwrap().iter().all(|v| v.is_nan()));
+
+        assert_eq!(signal.iter().take_while(|v| v.is_nan()).count(), 28);
+        assert!((signal[28] - -0.3663481719343764).abs() < 1e-9);
+        assert!((signal[29] - -0.06762701640979857).abs() < 1e-9);
+        assert!((hist[28] - (line[28] - signal[28])).abs() < 1e-12);
     }
-    Ok((macd, signal, hist))
-} 
\ No newline at end of file
+}
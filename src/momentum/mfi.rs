@@ -1,6 +1,9 @@
 // MFI - Money Flow Index
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::price_transform::typprice;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Money Flow Index (MFI).
 /// 
@@ -18,36 +21,38 @@ pub fn mfi(
     low: &[f64],
     close: &[f64],
     volume: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let len = close.len();
-    if high.len() != len || low.len() != len || volume.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}, volume: {}", high.len(), low.len(), len, volume.len())));
-    }
-    if len < period + 1 {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
-    let tp = typprice(high, low, close)?;
-    let mut pos_mf = vec![0.0; len];
-    let mut neg_mf = vec![0.0; len];
-    for i in 1..len {
-        let mf = tp[i] * volume[i];
-        if tp[i] > tp[i - 1] {
-            pos_mf[i] = mf;
-        } else if tp[i] < tp[i - 1] {
-            neg_mf[i] = mf;
+    with_function("MFI", || {
+        let period = period.into_param("period")?.get();
+        let len = close.len();
+        if high.len() != len || low.len() != len || volume.len() != len {
+            return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}, volume: {}", high.len(), low.len(), len, volume.len())));
         }
-    }
-    let mut mfi = vec![f64::NAN; len];
-    for i in period..len {
-        let pos_sum: f64 = pos_mf[i + 1 - period..=i].iter().sum();
-        let neg_sum: f64 = neg_mf[i + 1 - period..=i].iter().sum();
-        if neg_sum.abs() < 1e-12 {
-            mfi[i] = 100.0;
-        } else {
-            let mfr = pos_sum / neg_sum;
-            mfi[i] = 100.0 - (100.0 / (1.0 + mfr));
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        if len < period + 1 {
+            return Err(TAError::insufficient_data(period + 1, len));
         }
-    }
-    Ok(mfi)
+        let tp = typprice(high, low, close)?;
+        let mut pos_mf = vec![0.0; len];
+        let mut neg_mf = vec![0.0; len];
+        for i in 1..len {
+            let mf = tp[i] * volume[i];
+            if tp[i] > tp[i - 1] {
+                pos_mf[i] = mf;
+            } else if tp[i] < tp[i - 1] {
+                neg_mf[i] = mf;
+            }
+        }
+        let mut mfi = vec![f64::NAN; len];
+        for i in period..len {
+            let pos_sum: f64 = pos_mf[i + 1 - period..=i].iter().sum();
+            let neg_sum: f64 = neg_mf[i + 1 - period..=i].iter().sum();
+            // 100 · pos / (pos + neg) is 100 - 100 / (1 + money ratio); 0 when no money
+            // flowed at all, as TA-Lib reports it
+            let total = pos_sum + neg_sum;
+            mfi[i] = if total == 0.0 { 0.0 } else { 100.0 * pos_sum / total };
+        }
+        Ok(mfi)
+    })
 } 
\ No newline at end of file
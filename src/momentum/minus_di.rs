@@ -1,9 +1,14 @@
 // MINUS_DI - Minus Directional Indicator
-use crate::common::{TAError, TAResult};
-use crate::momentum::minus_dm;
-use crate::volatility::atr;
+use crate::common::{TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::DiState;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Minus Directional Indicator.
+///
+/// `100 * minus DM / TR`, both Wilder sums over `period` as in [`minus_dm`](crate::momentum::minus_dm),
+/// starting at bar `period`.
 /// 
 /// # Arguments
 /// * `high` - High prices
@@ -17,21 +22,19 @@ pub fn minus_di(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    period: usize,
+    period: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let len = close.len();
-    if high.len() != len || low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}", high.len(), low.len(), len)));
-    }
-    let minusdm = minus_dm(high, low)?;
-    let atr = atr(high, low, close, period)?;
-    let mut out = vec![f64::NAN; len];
-    for i in 0..len {
-        if atr[i].abs() < 1e-12 {
-            out[i] = 0.0;
-        } else {
-            out[i] = 100.0 * minusdm[i] / atr[i];
+    with_function("MINUS_DI", || {
+        let period = period.into_param("period")?.get();
+        let len = close.len();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let mut state = DiState::new(period)?;
+        let mut out = vec![f64::NAN; len];
+        for i in 0..len {
+            if let Some(di) = state.update(high[i], low[i], close[i]) {
+                out[i] = di.1;
+            }
         }
-    }
-    Ok(out)
-} 
\ No newline at end of file
+        Ok(out)
+    })
+}
@@ -1,28 +1,39 @@
 // MINUS_DM - Minus Directional Movement
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::momentum::dx::{directional_movement, wilder_sum};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Minus Directional Movement.
+///
+/// The per-bar minus DM, smoothed as TA-Lib does: the first value, at bar `period - 1`,
+/// sums the movements so far, and each later one replaces `1 / period` of the sum. A
+/// period of 1 gives the unsmoothed movement from bar 1.
 /// 
 /// # Arguments
 /// * `high` - High prices
 /// * `low` - Low prices
+/// * `period` - Period of the smoothing
 /// 
 /// # Returns
 /// Vector of Minus DM values
-pub fn minus_dm(high: &[f64], low: &[f64]) -> TAResult<Vec<f64>> {
-    let len = high.len();
-    if low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}", len, low.len())));
-    }
-    let mut out = vec![f64::NAN; len];
-    for i in 1..len {
-        let up = high[i] - high[i - 1];
-        let down = low[i - 1] - low[i];
-        if down > 0.0 && down > up {
-            out[i] = down;
-        } else {
-            out[i] = 0.0;
+pub fn minus_dm(high: &[f64], low: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MINUS_DM", || {
+        let period = period.into_param("period")?.get();
+        let len = high.len();
+        if low.len() != len {
+            return Err(TAError::mismatched_inputs(format!("high: {}, low: {}", len, low.len())));
         }
-    }
-    Ok(out)
-} 
\ No newline at end of file
+        let mut out = vec![f64::NAN; len];
+        let mut sum = 0.0;
+        for i in 1..len {
+            let dm = directional_movement(high[i], low[i], high[i - 1], low[i - 1]).1;
+            sum = wilder_sum(sum, dm, i, period);
+            if i + 1 >= period {
+                out[i] = sum;
+            }
+        }
+        Ok(out)
+    })
+}
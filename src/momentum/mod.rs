@@ -24,6 +24,8 @@ pub mod stoch;
 pub mod stochf;
 /// STOCHRSI - Stochastic RSI
 pub mod stochrsi;
+/// STOCH_OF - Stochastic of an arbitrary series
+pub mod stoch_of;
 /// CCI - Commodity Channel Index
 pub mod cci;
 /// MFI - Money Flow Index
@@ -34,6 +36,8 @@ pub mod bop;
 pub mod apo;
 /// PPO - Percentage Price Oscillator
 pub mod ppo;
+/// Price oscillator kernel of APO, PPO and MACDEXT
+pub mod price_oscillator;
 /// ULTOSC - Ultimate Oscillator
 pub mod ultosc;
 /// PLUS_DM - Plus Directional Movement
@@ -50,10 +54,20 @@ pub mod dx;
 pub mod adx;
 /// ADXR - Average Directional Movement Index Rating
 pub mod adxr;
+/// DMI - +DI, -DI, DX, ADX and ADXR together
+pub mod dmi;
 /// AROON - Aroon Up/Down
 pub mod aroon;
 /// AROONOSC - Aroon Oscillator
 pub mod aroonosc;
+/// Elder Ray - Bull Power / Bear Power
+pub mod elder_ray;
+/// RVI - Relative Vigor Index
+pub mod rvi;
+/// IMI - Intraday Momentum Index
+pub mod imi;
+/// Dominant-cycle adaptive RSI and Stochastic
+pub mod adaptive_cycle;
 
 pub use mom::*;
 pub use roc::*;
@@ -69,11 +83,13 @@ pub use macdfix::*;
 pub use stoch::*;
 pub use stochf::*;
 pub use stochrsi::*;
+pub use stoch_of::*;
 pub use cci::*;
 pub use mfi::*;
 pub use bop::*;
 pub use apo::*;
 pub use ppo::*;
+pub use price_oscillator::*;
 pub use ultosc::*;
 pub use plus_dm::*;
 pub use minus_dm::*;
@@ -82,5 +98,10 @@ pub use minus_di::*;
 pub use dx::*;
 pub use adx::*;
 pub use adxr::*;
+pub use dmi::*;
 pub use aroon::*;
-pub use aroonosc::*;
\ No newline at end of file
+pub use aroonosc::*;
+pub use elder_ray::*;
+pub use rvi::*;
+pub use imi::*;
+pub use adaptive_cycle::*;
\ No newline at end of file
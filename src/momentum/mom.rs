@@ -4,7 +4,10 @@
 //! It's one of the simplest momentum indicators, calculated as the difference
 //! between the current price and the price n periods ago.
 
-use crate::common::{TAError, validate_prices, validate_period};
+use crate::common::{TAError, Float, validate_prices, validate_same_length, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Momentum indicator.
 /// 
@@ -32,23 +35,43 @@ use crate::common::{TAError, validate_prices, validate_period};
 /// assert_eq!(result.len(), 8);
 /// // result[3] = 11.5 - 10.0 = 1.5
 /// ```
-pub fn mom(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
+pub fn mom<T: Float>(prices: &[T], period: impl IntoParam<Period>) -> Result<Vec<T>, TAError> {
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![T::NAN; prices.len()];
+        mom_into(prices, period, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Calculates Momentum into a caller-provided buffer.
+/// 
+/// Allocation-free variant of [`mom`]. `out` must have the same length as `prices`;
+/// its first `period` entries are set to NaN.
+/// 
+/// # Returns
+/// 
+/// Returns the number of valid values written (`prices.len() - period`).
+pub fn mom_into<T: Float>(prices: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> Result<usize, TAError> {
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+        validate_same_length(prices, out, "prices", "out")?;
     
-    let mut result = vec![f64::NAN; len];
+        out[..period].fill(T::NAN);
     
-    // Calculate momentum starting from period index
-    for i in period..len {
-        result[i] = prices[i] - prices[i - period];
-    }
+        // Calculate momentum starting from period index
+        for (i, value) in out.iter_mut().enumerate().skip(period) {
+            *value = prices[i] - prices[i - period];
+        }
     
-    Ok(result)
+        Ok(len - period)
+    })
 }
 
 /// Calculates Momentum with percentage output.
@@ -76,27 +99,29 @@ pub fn mom(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
 /// assert_eq!(result.len(), 5);
 /// // result[2] = ((120.0 / 100.0) - 1) * 100 = 20.0%
 /// ```
-pub fn mom_percent(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
+pub fn mom_percent(prices: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
     
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; len];
     
-    // Calculate momentum percentage starting from period index
-    for i in period..len {
-        if prices[i - period] == 0.0 {
-            result[i] = f64::NAN;
-        } else {
-            result[i] = ((prices[i] / prices[i - period]) - 1.0) * 100.0;
+        // Calculate momentum percentage starting from period index
+        for i in period..len {
+            if prices[i - period] == 0.0 {
+                result[i] = f64::NAN;
+            } else {
+                result[i] = ((prices[i] / prices[i - period]) - 1.0) * 100.0;
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculates Momentum oscillator (normalized between -100 and +100).
@@ -122,46 +147,48 @@ pub fn mom_percent(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
 /// let result = mom_oscillator(&prices, 3, 2).unwrap();
 /// assert_eq!(result.len(), 7);
 /// ```
-pub fn mom_oscillator(prices: &[f64], period: usize, smooth_period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    validate_period(smooth_period, "smooth_period")?;
+pub fn mom_oscillator(prices: &[f64], period: impl IntoParam<Period>, smooth_period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        let smooth_period = smooth_period.into_param("smooth_period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period + smooth_period {
-        return Err(TAError::insufficient_data(period + smooth_period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period + smooth_period {
+            return Err(TAError::insufficient_data(period + smooth_period + 1, len));
+        }
     
-    // Calculate basic momentum first
-    let momentum = mom(prices, period)?;
+        // Calculate basic momentum first
+        let momentum = mom(prices, period)?;
     
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; len];
     
-    // Smooth and normalize momentum
-    for i in (period + smooth_period - 1)..len {
-        let start_idx = i - smooth_period + 1;
-        let end_idx = i + 1;
-        
-        // Calculate average momentum over smooth_period
-        let sum: f64 = momentum[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .sum();
-        let count = momentum[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .count();
-        
-        if count == smooth_period {
-            let avg_momentum = sum / count as f64;
-            let base_price = prices[i - period];
+        // Smooth and normalize momentum
+        for i in (period + smooth_period - 1)..len {
+            let start_idx = i - smooth_period + 1;
+            let end_idx = i + 1;
+        
+            // Calculate average momentum over smooth_period
+            let sum: f64 = momentum[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .sum();
+            let count = momentum[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .count();
+        
+            if count == smooth_period {
+                let avg_momentum = sum / count as f64;
+                let base_price = prices[i - period];
             
-            if base_price != 0.0 {
-                // Normalize to percentage
-                result[i] = (avg_momentum / base_price) * 100.0;
+                if base_price != 0.0 {
+                    // Normalize to percentage
+                    result[i] = (avg_momentum / base_price) * 100.0;
+                }
             }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculates Momentum with signal line (smoothed momentum).
@@ -188,39 +215,41 @@ pub fn mom_oscillator(prices: &[f64], period: usize, smooth_period: usize) -> Re
 /// assert_eq!(momentum.len(), 8);
 /// assert_eq!(signal.len(), 8);
 /// ```
-pub fn mom_with_signal(prices: &[f64], period: usize, signal_period: usize) -> Result<(Vec<f64>, Vec<f64>), TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    validate_period(signal_period, "signal_period")?;
+pub fn mom_with_signal(prices: &[f64], period: impl IntoParam<Period>, signal_period: impl IntoParam<Period>) -> Result<(Vec<f64>, Vec<f64>), TAError> {
+    with_function("MOM", || {
+        let period = period.into_param("period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period + signal_period {
-        return Err(TAError::insufficient_data(period + signal_period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period + signal_period {
+            return Err(TAError::insufficient_data(period + signal_period + 1, len));
+        }
     
-    // Calculate momentum
-    let momentum = mom(prices, period)?;
+        // Calculate momentum
+        let momentum = mom(prices, period)?;
     
-    // Calculate signal line using SMA of momentum
-    let mut signal = vec![f64::NAN; len];
+        // Calculate signal line using SMA of momentum
+        let mut signal = vec![f64::NAN; len];
     
-    for i in (period + signal_period - 1)..len {
-        let start_idx = i - signal_period + 1;
-        let end_idx = i + 1;
-        
-        let sum: f64 = momentum[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .sum();
-        let count = momentum[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .count();
-        
-        if count == signal_period {
-            signal[i] = sum / signal_period as f64;
+        for (i, value) in signal.iter_mut().enumerate().skip(period + signal_period - 1) {
+            let start_idx = i - signal_period + 1;
+            let end_idx = i + 1;
+        
+            let sum: f64 = momentum[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .sum();
+            let count = momentum[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .count();
+        
+            if count == signal_period {
+                *value = sum / signal_period as f64;
+            }
         }
-    }
     
-    Ok((momentum, signal))
+        Ok((momentum, signal))
+    })
 }
 
 #[cfg(test)]
@@ -283,7 +312,7 @@ mod tests {
         let result = mom(&prices, 3);
         assert!(result.is_err());
         
-        if let Err(TAError::InsufficientData { required, provided }) = result {
+        if let Err(TAError::InsufficientData { required, provided, .. }) = result {
             assert_eq!(required, 4);
             assert_eq!(provided, 2);
         } else {
@@ -293,7 +322,7 @@ mod tests {
 
     #[test]
     fn test_mom_empty_data() {
-        let prices = vec![];
+        let prices: Vec<f64> = vec![];
         let result = mom(&prices, 10);
         assert!(result.is_err());
     }
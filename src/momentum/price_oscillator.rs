@@ -0,0 +1,144 @@
+//! Price Oscillator
+//!
+//! The difference between a fast and a slow moving average of the same prices, in price
+//! units or as a percentage of the slow average. APO and PPO are this kernel with both
+//! averages of one type, and the MACDEXT line is its absolute form with a type for each.
+
+use crate::common::{TAError, TAResult, MAType, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_same_length};
+use crate::overlap::ma;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Slow averages closer to zero than this give a percentage of 0
+const ZERO_AVERAGE: f64 = 1e-12;
+
+/// Calculates a price oscillator: fast minus slow moving average
+///
+/// # Formula
+/// ```text
+/// Absolute = MA(fast) - MA(slow)
+/// Percent  = 100 * (MA(fast) - MA(slow)) / MA(slow)
+/// ```
+///
+/// # Arguments
+/// * `prices` - Input price series
+/// * `fast_period` - Fast moving average period
+/// * `slow_period` - Slow moving average period
+/// * `ma_type` - Type of both moving averages
+/// * `percent` - Whether to express the difference as a percentage of the slow average,
+///   which is 0 where that average is 0
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of oscillator values; the first `slow_period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::MAType;
+/// use ta_rust::momentum::{apo, ppo, price_oscillator};
+///
+/// let prices: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+/// let absolute = price_oscillator(&prices, 5, 20, MAType::WMA, false).unwrap();
+/// assert_eq!(absolute[25], apo(&prices, 5, 20, MAType::WMA).unwrap()[25]);
+///
+/// let percent = price_oscillator(&prices, 5, 20, MAType::TEMA, true).unwrap();
+/// assert_eq!(percent[70], ppo(&prices, 5, 20, MAType::TEMA).unwrap()[70]);
+/// ```
+pub fn price_oscillator(
+    prices: &[f64],
+    fast_period: impl IntoParam<Period>,
+    slow_period: impl IntoParam<Period>,
+    ma_type: MAType,
+    percent: bool,
+) -> TAResult<Vec<f64>> {
+    with_function("PRICE_OSCILLATOR", || {
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        if prices.len() < slow_period {
+            return Err(TAError::insufficient_data(slow_period, prices.len()));
+        }
+        let fast = ma(prices, fast_period, ma_type)?;
+        let slow = ma(prices, slow_period, ma_type)?;
+        Ok(oscillator_from_mas(&fast, &slow, slow_period, percent))
+    })
+}
+
+/// Price oscillator of two precomputed moving averages of any kind
+///
+/// For pairs [`MAType`] does not cover, such as ZLEMAs or averages of different types. A
+/// bar where either average is NaN gives NaN.
+///
+/// # Arguments
+/// * `fast` - Fast moving average
+/// * `slow` - Slow moving average
+/// * `percent` - As for [`price_oscillator`]
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of oscillator values
+/// * `Err(TAError)` - Error if the averages are empty or differ in length
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::price_oscillator_from;
+/// use ta_rust::overlap::zlema;
+///
+/// let prices: Vec<f64> = (0..40).map(|i| 100.0 + i as f64).collect();
+/// let fast = zlema(&prices, 5).unwrap();
+/// let slow = zlema(&prices, 15).unwrap();
+/// let result = price_oscillator_from(&fast, &slow, true).unwrap();
+/// assert!(result[39] > 0.0);
+/// ```
+pub fn price_oscillator_from(fast: &[f64], slow: &[f64], percent: bool) -> TAResult<Vec<f64>> {
+    with_function("PRICE_OSCILLATOR", || {
+        validate_not_empty(fast, "fast")?;
+        validate_same_length(fast, slow, "fast", "slow")?;
+        Ok(fast.iter().zip(slow).map(|(&f, &s)| oscillate(f, s, percent)).collect())
+    })
+}
+
+/// Oscillator of moving averages whose slow average starts at `slow_period - 1`
+pub(crate) fn oscillator_from_mas(fast: &[f64], slow: &[f64], slow_period: usize, percent: bool) -> Vec<f64> {
+    let mut result = vec![f64::NAN; slow.len()];
+    for i in slow_period - 1..slow.len() {
+        result[i] = oscillate(fast[i], slow[i], percent);
+    }
+    result
+}
+
+fn oscillate(fast: f64, slow: f64, percent: bool) -> f64 {
+    if !percent {
+        fast - slow
+    } else if slow.abs() < ZERO_AVERAGE {
+        0.0
+    } else {
+        100.0 * (fast - slow) / slow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::overlap::{ema, sma};
+
+    #[test]
+    fn test_price_oscillator() {
+        let prices: Vec<f64> = (0..30).map(|i| 50.0 + (i as f64 * 0.4).cos() * 3.0).collect();
+        let fast = sma(&prices, 4).unwrap();
+        let slow = ema(&prices, 10).unwrap();
+        let mixed = price_oscillator_from(&fast, &slow, false).unwrap();
+        assert!(mixed[8].is_nan());
+        crate::assert_float_eq!(mixed[20], fast[20] - slow[20], 1e-12);
+
+        let percent = price_oscillator(&prices, 4, 10, MAType::SMA, true).unwrap();
+        assert!(percent[8].is_nan());
+        let slow = sma(&prices, 10).unwrap();
+        crate::assert_float_eq!(percent[9], 100.0 * (fast[9] - slow[9]) / slow[9], 1e-12);
+
+        assert_eq!(price_oscillator_from(&[0.0, 1.0], &[0.0, 0.0], true).unwrap(), vec![0.0, 0.0]);
+        assert!(price_oscillator_from(&fast, &slow[1..], true).is_err());
+        assert!(price_oscillator(&prices, 4, 40, MAType::SMA, false).is_err());
+        assert!(price_oscillator(&prices, 0, 10, MAType::SMA, false).is_err());
+    }
+}
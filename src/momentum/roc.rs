@@ -3,7 +3,10 @@
 //! ROC measures the percentage change in price over a specified period.
 //! It's calculated as ((Price[today] / Price[n periods ago]) - 1) * 100.
 
-use crate::common::{TAError, validate_prices, validate_period};
+use crate::common::{TAError, Float, validate_prices, validate_same_length, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Rate of Change as a percentage.
 /// 
@@ -29,27 +32,49 @@ use crate::common::{TAError, validate_prices, validate_period};
 /// assert_eq!(result.len(), 5);
 /// // result[2] = ((120.0 / 100.0) - 1) * 100 = 20.0%
 /// ```
-pub fn roc(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
+pub fn roc<T: Float>(prices: &[T], period: impl IntoParam<Period>) -> Result<Vec<T>, TAError> {
+    with_function("ROC", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![T::NAN; prices.len()];
+        roc_into(prices, period, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Calculates Rate of Change into a caller-provided buffer.
+/// 
+/// Allocation-free variant of [`roc`]. `out` must have the same length as `prices`;
+/// its first `period` entries are set to NaN.
+/// 
+/// # Returns
+/// 
+/// Returns the number of values computed (`prices.len() - period`). Bars whose
+/// reference price is zero are still written as NaN.
+pub fn roc_into<T: Float>(prices: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> Result<usize, TAError> {
+    with_function("ROC", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+        validate_same_length(prices, out, "prices", "out")?;
     
-    let mut result = vec![f64::NAN; len];
+        out[..period].fill(T::NAN);
+        let hundred = T::from_f64(100.0);
     
-    // Calculate ROC starting from period index
-    for i in period..len {
-        if prices[i - period] == 0.0 {
-            result[i] = f64::NAN;
-        } else {
-            result[i] = ((prices[i] / prices[i - period]) - 1.0) * 100.0;
+        // Calculate ROC starting from period index
+        for (i, value) in out.iter_mut().enumerate().skip(period) {
+            if prices[i - period] == T::ZERO {
+                *value = T::NAN;
+            } else {
+                *value = ((prices[i] / prices[i - period]) - T::ONE) * hundred;
+            }
         }
-    }
     
-    Ok(result)
+        Ok(len - period)
+    })
 }
 
 #[cfg(test)]
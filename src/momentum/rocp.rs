@@ -3,7 +3,10 @@
 //! ROCP measures the percentage change in price over a specified period.
 //! It's calculated as (Price[today] - Price[n periods ago]) / Price[n periods ago].
 
-use crate::common::{TAError, validate_prices, validate_period};
+use crate::common::{TAError, validate_prices, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Rate of Change Percentage.
 /// 
@@ -29,27 +32,29 @@ use crate::common::{TAError, validate_prices, validate_period};
 /// assert_eq!(result.len(), 5);
 /// // result[2] = (120.0 - 100.0) / 100.0 = 0.2 (20%)
 /// ```
-pub fn rocp(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
+pub fn rocp(prices: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("ROCP", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
     
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; len];
     
-    // Calculate ROCP starting from period index
-    for i in period..len {
-        if prices[i - period] == 0.0 {
-            result[i] = f64::NAN;
-        } else {
-            result[i] = (prices[i] - prices[i - period]) / prices[i - period];
+        // Calculate ROCP starting from period index
+        for i in period..len {
+            if prices[i - period] == 0.0 {
+                result[i] = f64::NAN;
+            } else {
+                result[i] = (prices[i] - prices[i - period]) / prices[i - period];
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
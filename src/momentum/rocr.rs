@@ -3,7 +3,10 @@
 //! ROCR measures the ratio of current price to price n periods ago.
 //! It's calculated as Price[today] / Price[n periods ago].
 
-use crate::common::{TAError, validate_prices, validate_period};
+use crate::common::{TAError, validate_prices, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Rate of Change Ratio.
 /// 
@@ -29,27 +32,29 @@ use crate::common::{TAError, validate_prices, validate_period};
 /// assert_eq!(result.len(), 5);
 /// // result[2] = 120.0 / 100.0 = 1.2
 /// ```
-pub fn rocr(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
+pub fn rocr(prices: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("ROCR", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
     
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; len];
     
-    // Calculate ROCR starting from period index
-    for i in period..len {
-        if prices[i - period] == 0.0 {
-            result[i] = f64::NAN;
-        } else {
-            result[i] = prices[i] / prices[i - period];
+        // Calculate ROCR starting from period index
+        for i in period..len {
+            if prices[i - period] == 0.0 {
+                result[i] = f64::NAN;
+            } else {
+                result[i] = prices[i] / prices[i - period];
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
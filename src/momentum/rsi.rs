@@ -4,7 +4,11 @@
 //! It oscillates between 0 and 100, with values above 70 typically considered overbought
 //! and values below 30 considered oversold.
 
-use crate::common::{TAError, validate_prices, validate_period};
+use crate::common::{TAError, Float, validate_prices, validate_same_length, Alpha, IntoParam, Percent, Period, TAOutput, rsi_lookback, TAResult, with_nan_policy, NanPolicy};
+use crate::common::errors::with_function;
+use crate::overlap::ewm::smoothed;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Relative Strength Index using Wilder's smoothing method.
 /// 
@@ -35,57 +39,100 @@ use crate::common::{TAError, validate_prices, validate_period};
 /// let result = rsi(&prices, 14).unwrap();
 /// assert_eq!(result.len(), 20);
 /// ```
-pub fn rsi(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
-    
-    let mut result = vec![f64::NAN; len];
-    let alpha = 1.0 / period as f64;
-    
-    // Calculate price changes
-    let mut gains = Vec::with_capacity(len - 1);
-    let mut losses = Vec::with_capacity(len - 1);
+pub fn rsi<T: Float>(prices: &[T], period: impl IntoParam<Period>) -> Result<Vec<T>, TAError> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![T::NAN; prices.len()];
+        rsi_into(prices, period, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Calculates the RSI with where its values start
+///
+/// Same as [`rsi`], wrapped in a [`TAOutput`] with the lookback of [`rsi_lookback`](crate::common::lookback::rsi_lookback).
+///
+/// # Errors
+/// Same as [`rsi`]
+pub fn rsi_ex(prices: &[f64], period: impl IntoParam<Period>) -> TAResult<TAOutput> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(rsi(prices, period)?, rsi_lookback(period)))
+    })
+}
+
+/// Calculates the RSI of a series with missing values
+///
+/// Same as [`rsi`], which rejects NaN, with NaN in `prices` handled according to `policy`
+/// instead; see [`with_nan_policy`](crate::common::with_nan_policy). Skipped bars leave the
+/// Wilder smoothing state untouched.
+///
+/// # Errors
+/// Same as [`rsi`], plus `InvalidInput` if `prices` holds NaN under [`NanPolicy::Error`]
+pub fn rsi_with_policy(prices: &[f64], period: impl IntoParam<Period>, policy: NanPolicy) -> TAResult<Vec<f64>> {
+    with_function("RSI", || with_nan_policy(&[prices], policy, |i| rsi(i[0], period)))
+}
+
+/// Calculates RSI into a caller-provided buffer.
+/// 
+/// Allocation-free variant of [`rsi`]: gains and losses are derived on the fly
+/// rather than collected first. `out` must have the same length as `prices`;
+/// its first `period` entries are set to NaN.
+/// 
+/// # Returns
+/// 
+/// Returns the number of valid values written (`prices.len() - period`).
+/// 
+/// # Example
+/// 
+/// ```
+/// use ta_rust::momentum::rsi_into;
+/// 
+/// let prices = [44.0, 44.25, 44.5, 43.75, 44.5, 44.0, 44.25, 44.75];
+/// let mut out = [0.0; 8];
+/// let valid = rsi_into(&prices, 5, &mut out).unwrap();
+/// assert_eq!(valid, 3);
+/// ```
+pub fn rsi_into<T: Float>(prices: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> Result<usize, TAError> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        validate_prices(prices, "prices")?;
     
-    for i in 1..len {
-        let change = prices[i] - prices[i - 1];
-        gains.push(if change > 0.0 { change } else { 0.0 });
-        losses.push(if change < 0.0 { -change } else { 0.0 });
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+        validate_same_length(prices, out, "prices", "out")?;
     
-    // Initialize with SMA of first 'period' gains and losses
-    let initial_avg_gain: f64 = gains[0..period].iter().sum::<f64>() / period as f64;
-    let initial_avg_loss: f64 = losses[0..period].iter().sum::<f64>() / period as f64;
+        let alpha = T::ONE / T::from_usize(period);
+        let gain_loss = |i: usize| {
+            let change = prices[i] - prices[i - 1];
+            (if change > T::ZERO { change } else { T::ZERO }, if change < T::ZERO { -change } else { T::ZERO })
+        };
+        out[..period].fill(T::NAN);
     
-    let mut avg_gain = initial_avg_gain;
-    let mut avg_loss = initial_avg_loss;
+        // Wilder's smoothing of gains and losses, seeded with their SMA over the first 'period' changes
+        let gains = smoothed((1..len).map(|i| gain_loss(i).0), alpha, period);
+        let losses = smoothed((1..len).map(|i| gain_loss(i).1), alpha, period);
+        for (value, (avg_gain, avg_loss)) in out[period..].iter_mut().zip(gains.zip(losses)) {
+            *value = rsi_from_averages(avg_gain, avg_loss);
+        }
     
-    // Calculate first RSI value
-    if avg_loss == 0.0 {
-        result[period] = 100.0;
+        Ok(len - period)
+    })
+}
+
+/// RSI of an average gain and loss: 100 with no losses, and 0 when prices have not moved
+/// at all, as TA-Lib reports it
+pub(crate) fn rsi_from_averages<T: Float>(avg_gain: T, avg_loss: T) -> T {
+    let hundred = T::from_f64(100.0);
+    if avg_gain == T::ZERO && avg_loss == T::ZERO {
+        T::ZERO
+    } else if avg_loss == T::ZERO {
+        hundred
     } else {
-        let rs = avg_gain / avg_loss;
-        result[period] = 100.0 - (100.0 / (1.0 + rs));
+        hundred - (hundred / (T::ONE + avg_gain / avg_loss))
     }
-    
-    // Apply Wilder's smoothing for remaining values
-    for i in (period + 1)..len {
-        avg_gain = alpha * gains[i - 1] + (1.0 - alpha) * avg_gain;
-        avg_loss = alpha * losses[i - 1] + (1.0 - alpha) * avg_loss;
-        
-        if avg_loss == 0.0 {
-            result[i] = 100.0;
-        } else {
-            let rs = avg_gain / avg_loss;
-            result[i] = 100.0 - (100.0 / (1.0 + rs));
-        }
-    }
-    
-    Ok(result)
 }
 
 /// Calculates RSI with custom smoothing factor.
@@ -112,60 +159,27 @@ pub fn rsi(prices: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
 /// let result = rsi_custom(&prices, 5, 0.2).unwrap();
 /// assert_eq!(result.len(), 9);
 /// ```
-pub fn rsi_custom(prices: &[f64], period: usize, alpha: f64) -> Result<Vec<f64>, TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    
-    if alpha <= 0.0 || alpha > 1.0 {
-        return Err(TAError::invalid_input("Alpha must be between 0 and 1"));
-    }
-    
-    let len = prices.len();
-    if len <= period {
-        return Err(TAError::insufficient_data(period + 1, len));
-    }
-    
-    let mut result = vec![f64::NAN; len];
-    
-    // Calculate price changes
-    let mut gains = Vec::with_capacity(len - 1);
-    let mut losses = Vec::with_capacity(len - 1);
-    
-    for i in 1..len {
-        let change = prices[i] - prices[i - 1];
-        gains.push(if change > 0.0 { change } else { 0.0 });
-        losses.push(if change < 0.0 { -change } else { 0.0 });
-    }
+pub fn rsi_custom(prices: &[f64], period: impl IntoParam<Period>, alpha: impl IntoParam<Alpha>) -> Result<Vec<f64>, TAError> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        let alpha = alpha.into_param("alpha")?.get();
+        validate_prices(prices, "prices")?;
     
-    // Initialize with SMA of first 'period' gains and losses
-    let initial_avg_gain: f64 = gains[0..period].iter().sum::<f64>() / period as f64;
-    let initial_avg_loss: f64 = losses[0..period].iter().sum::<f64>() / period as f64;
     
-    let mut avg_gain = initial_avg_gain;
-    let mut avg_loss = initial_avg_loss;
-    
-    // Calculate first RSI value
-    if avg_loss == 0.0 {
-        result[period] = 100.0;
-    } else {
-        let rs = avg_gain / avg_loss;
-        result[period] = 100.0 - (100.0 / (1.0 + rs));
-    }
+        let len = prices.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
     
-    // Apply custom smoothing for remaining values
-    for i in (period + 1)..len {
-        avg_gain = alpha * gains[i - 1] + (1.0 - alpha) * avg_gain;
-        avg_loss = alpha * losses[i - 1] + (1.0 - alpha) * avg_loss;
-        
-        if avg_loss == 0.0 {
-            result[i] = 100.0;
-        } else {
-            let rs = avg_gain / avg_loss;
-            result[i] = 100.0 - (100.0 / (1.0 + rs));
+        let mut result = vec![f64::NAN; len];
+        let gains = smoothed(prices.windows(2).map(|w| (w[1] - w[0]).max(0.0)), alpha, period);
+        let losses = smoothed(prices.windows(2).map(|w| (w[0] - w[1]).max(0.0)), alpha, period);
+        for (value, (avg_gain, avg_loss)) in result[period..].iter_mut().zip(gains.zip(losses)) {
+            *value = rsi_from_averages(avg_gain, avg_loss);
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculates RSI divergence signals.
@@ -196,70 +210,68 @@ pub fn rsi_custom(prices: &[f64], period: usize, alpha: f64) -> Result<Vec<f64>,
 /// assert_eq!(rsi_vals.len(), 16);
 /// assert_eq!(signals.len(), 16);
 /// ```
-pub fn rsi_divergence(prices: &[f64], period: usize, lookback: usize) -> Result<(Vec<f64>, Vec<i8>), TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    validate_period(lookback, "lookback")?;
+pub fn rsi_divergence(prices: &[f64], period: impl IntoParam<Period>, lookback: impl IntoParam<Period>) -> Result<(Vec<f64>, Vec<i8>), TAError> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        let lookback = lookback.into_param("lookback")?.get();
+        validate_prices(prices, "prices")?;
     
-    let len = prices.len();
-    if len <= period + lookback {
-        return Err(TAError::insufficient_data(period + lookback + 1, len));
-    }
+        let len = prices.len();
+        if len <= period + lookback {
+            return Err(TAError::insufficient_data(period + lookback + 1, len));
+        }
     
-    let rsi_values = rsi(prices, period)?;
-    let mut signals = vec![0i8; len];
+        let rsi_values = rsi(prices, period)?;
+        let mut signals = vec![0i8; len];
     
-    // Look for divergences starting from period + lookback
-    for i in (period + lookback)..len {
-        let current_price = prices[i];
-        let current_rsi = rsi_values[i];
+        // Look for divergences starting from period + lookback
+        for i in (period + lookback)..len {
+            let current_price = prices[i];
+            let current_rsi = rsi_values[i];
         
-        if current_rsi.is_nan() {
-            continue;
-        }
+            if current_rsi.is_nan() {
+                continue;
+            }
         
-        // Find previous significant high/low within lookback period
-        let start_idx = i.saturating_sub(lookback);
+            // Find previous significant high/low within lookback period
+            let start_idx = i.saturating_sub(lookback);
         
-        // Check for bearish divergence (price higher high, RSI lower high)
-        let mut price_higher_high = false;
-        let mut rsi_lower_high = false;
+            // Check for bearish divergence (price higher high, RSI lower high)
+            let mut price_higher_high = false;
+            let mut rsi_lower_high = false;
         
-        for j in start_idx..i {
-            if !rsi_values[j].is_nan() {
-                if prices[j] < current_price && rsi_values[j] > current_rsi {
+            for j in start_idx..i {
+                if !rsi_values[j].is_nan() && prices[j] < current_price && rsi_values[j] > current_rsi {
                     price_higher_high = true;
                     rsi_lower_high = true;
                     break;
                 }
             }
-        }
         
-        if price_higher_high && rsi_lower_high {
-            signals[i] = -1; // Bearish divergence
-            continue;
-        }
+            if price_higher_high && rsi_lower_high {
+                signals[i] = -1; // Bearish divergence
+                continue;
+            }
         
-        // Check for bullish divergence (price lower low, RSI higher low)
-        let mut price_lower_low = false;
-        let mut rsi_higher_low = false;
+            // Check for bullish divergence (price lower low, RSI higher low)
+            let mut price_lower_low = false;
+            let mut rsi_higher_low = false;
         
-        for j in start_idx..i {
-            if !rsi_values[j].is_nan() {
-                if prices[j] > current_price && rsi_values[j] < current_rsi {
+            for j in start_idx..i {
+                if !rsi_values[j].is_nan() && prices[j] > current_price && rsi_values[j] < current_rsi {
                     price_lower_low = true;
                     rsi_higher_low = true;
                     break;
                 }
             }
-        }
         
-        if price_lower_low && rsi_higher_low {
-            signals[i] = 1; // Bullish divergence
+            if price_lower_low && rsi_higher_low {
+                signals[i] = 1; // Bullish divergence
+            }
         }
-    }
     
-    Ok((rsi_values, signals))
+        Ok((rsi_values, signals))
+    })
 }
 
 /// Calculates RSI with overbought/oversold levels.
@@ -291,32 +303,126 @@ pub fn rsi_divergence(prices: &[f64], period: usize, lookback: usize) -> Result<
 /// assert_eq!(rsi_vals.len(), 20);
 /// assert_eq!(signals.len(), 20);
 /// ```
-pub fn rsi_levels(prices: &[f64], period: usize, overbought: f64, oversold: f64) -> Result<(Vec<f64>, Vec<i8>), TAError> {
-    validate_prices(prices, "prices")?;
-    validate_period(period, "period")?;
-    
-    if overbought <= oversold {
-        return Err(TAError::invalid_input("Overbought level must be greater than oversold level"));
-    }
+pub fn rsi_levels(
+    prices: &[f64],
+    period: impl IntoParam<Period>,
+    overbought: impl IntoParam<Percent>,
+    oversold: impl IntoParam<Percent>,
+) -> Result<(Vec<f64>, Vec<i8>), TAError> {
+    with_function("RSI", || {
+        let period = period.into_param("period")?.get();
+        let overbought = overbought.into_param("overbought")?.get();
+        let oversold = oversold.into_param("oversold")?.get();
+        validate_prices(prices, "prices")?;
     
-    if overbought > 100.0 || oversold < 0.0 {
-        return Err(TAError::invalid_input("RSI levels must be between 0 and 100"));
-    }
+        if overbought <= oversold {
+            return Err(TAError::invalid_input("Overbought level must be greater than oversold level"));
+        }
     
-    let rsi_values = rsi(prices, period)?;
-    let mut signals = vec![0i8; rsi_values.len()];
+        let rsi_values = rsi(prices, period)?;
+        let mut signals = vec![0i8; rsi_values.len()];
     
-    for (i, &rsi_val) in rsi_values.iter().enumerate() {
-        if !rsi_val.is_nan() {
-            if rsi_val >= overbought {
-                signals[i] = -1; // Overbought
-            } else if rsi_val <= oversold {
-                signals[i] = 1; // Oversold
+        for (i, &rsi_val) in rsi_values.iter().enumerate() {
+            if !rsi_val.is_nan() {
+                if rsi_val >= overbought {
+                    signals[i] = -1; // Overbought
+                } else if rsi_val <= oversold {
+                    signals[i] = 1; // Oversold
+                }
             }
         }
-    }
     
-    Ok((rsi_values, signals))
+        Ok((rsi_values, signals))
+    })
+}
+
+/// Incremental RSI for streaming data
+///
+/// Produces the same values as [`rsi`] one price at a time, in O(1) time and memory per
+/// update: the average gain and loss are seeded with their mean over the first `period`
+/// changes, then smoothed with Wilder's alpha of `1 / period`.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::momentum::{rsi, RsiState};
+///
+/// let prices = vec![44.0, 44.25, 44.5, 43.75, 44.5, 44.0, 44.25, 44.75, 45.0, 45.25];
+/// let batch = rsi(&prices, 5).unwrap();
+///
+/// let mut state = RsiState::new(5).unwrap();
+/// for (i, &price) in prices.iter().enumerate() {
+///     match state.update(price) {
+///         Some(value) => assert_eq!(value, batch[i]),
+///         None => assert!(batch[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RsiState {
+    period: usize,
+    /// Previous price, once one has been seen
+    previous: Option<f64>,
+    /// Number of changes seen so far, saturating at `period`
+    count: usize,
+    /// Running sums of the first `period` gains and losses for the seed
+    seed_gain: f64,
+    seed_loss: f64,
+    /// Average gain and loss, once warmed up
+    averages: Option<(f64, f64)>,
+    /// Current RSI value, once warmed up
+    value: Option<f64>,
+}
+
+impl RsiState {
+    /// Creates a new state for the given period
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn new(period: impl IntoParam<Period>) -> TAResult<Self> {
+        with_function("RSI", || {
+            let period = period.into_param("period")?.get();
+            Ok(Self { period, previous: None, count: 0, seed_gain: 0.0, seed_loss: 0.0, averages: None, value: None })
+        })
+    }
+
+    /// Feeds the next price and returns the updated RSI, or `None` during warm-up
+    pub fn update(&mut self, price: f64) -> Option<f64> {
+        let previous = self.previous.replace(price)?;
+        let change = price - previous;
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+
+        self.averages = match self.averages {
+            Some((avg_gain, avg_loss)) => {
+                let alpha = 1.0 / self.period as f64;
+                Some((alpha * gain + (1.0 - alpha) * avg_gain, alpha * loss + (1.0 - alpha) * avg_loss))
+            }
+            None => {
+                self.count += 1;
+                self.seed_gain += gain;
+                self.seed_loss += loss;
+                let period = self.period as f64;
+                (self.count == self.period).then(|| (self.seed_gain / period, self.seed_loss / period))
+            }
+        };
+        self.value = self.averages.map(|(avg_gain, avg_loss)| rsi_from_averages(avg_gain, avg_loss));
+        self.value
+    }
+
+    /// Current value, or `None` if fewer than `period + 1` prices have been seen
+    pub fn current(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Period of the indicator
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        *self = Self { period: self.period, previous: None, count: 0, seed_gain: 0.0, seed_loss: 0.0, averages: None, value: None };
+    }
 }
 
 #[cfg(test)]
@@ -396,11 +502,9 @@ mod tests {
         let prices = vec![50.0; 10];
         let result = rsi(&prices, 5).unwrap();
         
-        // RSI should be 50 for constant prices (no gains or losses)
-        // Actually, with no changes, avg_gain = avg_loss = 0, so RSI = 50
+        // With no gains or losses RSI is 0, as TA-Lib reports it
         for i in 5..10 {
-            // When both avg_gain and avg_loss are 0, RSI should be 50
-            assert!(!result[i].is_nan());
+            assert_eq!(result[i], 0.0);
         }
     }
 
@@ -410,7 +514,7 @@ mod tests {
         let result = rsi(&prices, 5);
         assert!(result.is_err());
         
-        if let Err(TAError::InsufficientData { required, provided }) = result {
+        if let Err(TAError::InsufficientData { required, provided, .. }) = result {
             assert_eq!(required, 6);
             assert_eq!(provided, 2);
         } else {
@@ -420,7 +524,7 @@ mod tests {
 
     #[test]
     fn test_rsi_empty_data() {
-        let prices = vec![];
+        let prices: Vec<f64> = vec![];
         let result = rsi(&prices, 14);
         assert!(result.is_err());
     }
@@ -596,4 +700,38 @@ mod tests {
             assert!(result[i] <= 100.0);
         }
     }
+
+    #[test]
+    fn test_rsi_into_matches_rsi() {
+        let prices = vec![
+            44.0, 44.25, 44.5, 43.75, 44.5, 44.0, 44.25, 44.75, 45.0, 45.25,
+            45.5, 45.25, 45.0, 44.5, 44.0, 44.25, 44.5, 44.75, 45.0, 45.25
+        ];
+        let expected = rsi(&prices, 6).unwrap();
+        let mut out = vec![0.0; prices.len()];
+        let valid = rsi_into(&prices, 6, &mut out).unwrap();
+        
+        assert_eq!(valid, prices.len() - 6);
+        for (a, e) in out.iter().zip(&expected) {
+            assert!((a.is_nan() && e.is_nan()) || a == e);
+        }
+        
+        let mut short = vec![0.0; prices.len() - 1];
+        assert!(rsi_into(&prices, 6, &mut short).is_err());
+    }
+
+    #[test]
+    fn test_rsi_f32_matches_f64() {
+        let prices = vec![
+            44.0, 44.25, 44.5, 43.75, 44.5, 44.0, 44.25, 44.75, 45.0, 45.25,
+            45.5, 45.25, 45.0, 44.5, 44.0, 44.25, 44.5, 44.75, 45.0, 45.25
+        ];
+        let prices_f32: Vec<f32> = prices.iter().map(|&p| p as f32).collect();
+        let expected = rsi(&prices, 14).unwrap();
+        let result = rsi(&prices_f32, 14).unwrap();
+        
+        for i in 14..prices.len() {
+            assert!((result[i] as f64 - expected[i]).abs() < 1e-3);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+//! Relative Vigor Index (RVI)
+//!
+//! John Ehlers' RVI compares the close-minus-open of each bar with its high-minus-low range:
+//! in rising markets prices tend to close above the open, in falling markets below it. Both
+//! series are smoothed with a symmetric 4-bar weighting before being averaged.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Relative Vigor Index result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RviOutput {
+    /// RVI line
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub rvi: Vec<f64>,
+    /// Signal line (4-bar symmetric weighting of the RVI)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub signal: Vec<f64>,
+}
+
+/// Symmetric 4-bar weighting (1, 2, 2, 1) / 6 ending at each bar; the first 3 values are NaN
+fn symmetric_weight(data: &[f64]) -> Vec<f64> {
+    let mut result = vec![f64::NAN; data.len()];
+    for (i, window) in data.windows(4).enumerate() {
+        result[i + 3] = (window[3] + 2.0 * window[2] + 2.0 * window[1] + window[0]) / 6.0;
+    }
+    result
+}
+
+/// Relative Vigor Index
+///
+/// # Formula
+/// ```text
+/// Numerator   = SWMA(Close - Open)
+/// Denominator = SWMA(High - Low)
+/// RVI         = SUM(Numerator, period) / SUM(Denominator, period)
+/// Signal      = SWMA(RVI)
+///
+/// where SWMA(x) = (x + 2·x[1] + 2·x[2] + x[3]) / 6
+/// ```
+///
+/// Windows with no range give an RVI of 0.
+///
+/// # Arguments
+/// * `open` - Slice of opening prices
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - Averaging period (typically 10)
+///
+/// # Returns
+/// * `Ok(RviOutput)` - RVI valid from index `period + 2`, signal from `period + 5`
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::rvi;
+///
+/// let close: Vec<f64> = (0..30).map(|i| 50.0 + i as f64).collect();
+/// let open: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.5).collect();
+///
+/// let result = rvi(&open, &high, &low, &close, 10).unwrap();
+/// assert!(result.rvi[11].is_nan());
+/// // Every bar closes a quarter of its range above the open
+/// assert!((result.rvi[12] - 0.25).abs() < 1e-12);
+/// assert!((result.signal[15] - 0.25).abs() < 1e-12);
+/// ```
+pub fn rvi(open: &[f64], high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<RviOutput> {
+    with_function("RVI", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+
+        let len = close.len();
+        if open.len() != len || high.len() != len || low.len() != len {
+            return Err(TAError::mismatched_inputs("Open, High, Low, and Close arrays must have the same length"));
+        }
+
+        if len < period + 3 {
+            return Err(TAError::insufficient_data(period + 3, len));
+        }
+
+        let change: Vec<f64> = close.iter().zip(open).map(|(&c, &o)| c - o).collect();
+        let range: Vec<f64> = high.iter().zip(low).map(|(&h, &l)| h - l).collect();
+        let numerator = symmetric_weight(&change);
+        let denominator = symmetric_weight(&range);
+
+        let mut rvi = vec![f64::NAN; len];
+        for (i, value) in rvi.iter_mut().enumerate().skip(period + 2) {
+            let window = (i + 1 - period)..=i;
+            let num: f64 = numerator[window.clone()].iter().sum();
+            let den: f64 = denominator[window].iter().sum();
+            *value = if den.abs() < f64::EPSILON { 0.0 } else { num / den };
+        }
+
+        let signal = symmetric_weight(&rvi);
+
+        Ok(RviOutput { rvi, signal })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_rvi_basic() {
+        let open = vec![10.0, 10.5, 11.0, 10.8, 11.2, 11.5, 11.0, 11.6];
+        let high = vec![11.0, 11.5, 11.6, 11.4, 12.0, 12.1, 11.8, 12.2];
+        let low = vec![9.8, 10.2, 10.6, 10.5, 11.0, 11.2, 10.7, 11.3];
+        let close = vec![10.6, 11.2, 10.9, 11.3, 11.8, 11.1, 11.7, 12.0];
+        let result = rvi(&open, &high, &low, &close, 2).unwrap();
+
+        let swma = |x: &dyn Fn(usize) -> f64, i: usize| (x(i) + 2.0 * x(i - 1) + 2.0 * x(i - 2) + x(i - 3)) / 6.0;
+        let change = |i: usize| close[i] - open[i];
+        let range = |i: usize| high[i] - low[i];
+
+        assert!(result.rvi[3].is_nan());
+        let expected = (swma(&change, 3) + swma(&change, 4)) / (swma(&range, 3) + swma(&range, 4));
+        assert_float_eq!(result.rvi[4], expected, 1e-12);
+
+        assert!(result.signal[6].is_nan());
+        let rvi_at = |i: usize| result.rvi[i];
+        assert_float_eq!(result.signal[7], swma(&rvi_at, 7), 1e-12);
+    }
+
+    #[test]
+    fn test_rvi_sign_follows_closes() {
+        let open: Vec<f64> = (0..20).map(|i| 20.0 + (i as f64 * 0.5).sin()).collect();
+        let high: Vec<f64> = open.iter().map(|o| o + 1.0).collect();
+        let low: Vec<f64> = open.iter().map(|o| o - 1.0).collect();
+
+        let up: Vec<f64> = open.iter().map(|o| o + 0.5).collect();
+        let down: Vec<f64> = open.iter().map(|o| o - 0.5).collect();
+
+        let bullish = rvi(&open, &high, &low, &up, 4).unwrap();
+        let bearish = rvi(&open, &high, &low, &down, 4).unwrap();
+        assert!(bullish.rvi[6..].iter().all(|&v| v > 0.0));
+        assert!(bearish.rvi[6..].iter().all(|&v| v < 0.0));
+    }
+
+    #[test]
+    fn test_rvi_invalid_input() {
+        let prices = vec![1.0; 6];
+        assert!(rvi(&[], &[], &[], &[], 2).is_err());
+        assert!(rvi(&prices[..5], &prices, &prices, &prices, 2).is_err());
+        assert!(rvi(&prices, &prices, &prices, &prices, 0).is_err());
+        assert!(rvi(&prices, &prices, &prices, &prices, 4).is_err());
+    }
+}
@@ -1,9 +1,45 @@
 // STOCH - Stochastic Oscillator
-use crate::common::{TAError, TAResult, MAType};
-use crate::overlap::ma;
+use crate::common::{crossovers, TAError, TAResult, MAType, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::window::{RunningMax, RunningMin};
+use crate::statistic::range_position;
+use crate::overlap::ma::{ma_skip_nan, MaState};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Stochastic result structure, shared by [`stoch`], [`stochf`](crate::momentum::stochf)
+/// and [`stochrsi`](crate::momentum::stochrsi)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StochOutput {
+    /// %K line
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub k: Vec<f64>,
+    /// %D line (moving average of %K)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub d: Vec<f64>,
+}
+
+impl StochOutput {
+    /// %K/%D crossovers: 1 where %K crosses above %D, -1 where it crosses below
+    pub fn crossovers(&self) -> Vec<i8> {
+        crossovers(&self.k, &self.d)
+    }
+}
+
+impl From<(Vec<f64>, Vec<f64>)> for StochOutput {
+    /// Converts a `(k, d)` tuple
+    fn from((k, d): (Vec<f64>, Vec<f64>)) -> Self {
+        Self { k, d }
+    }
+}
 
 /// Calculates the Stochastic Oscillator.
 /// 
+/// Like TA-Lib's STOCH, each smoothing stage takes its own moving average type: the raw
+/// %K is smoothed into slow %K by `slowk_ma`, and slow %K into slow %D by `slowd_ma`.
+/// SMA for both gives the classic slow stochastic, EMA the Metastock-style one.
+/// 
 /// # Arguments
 /// * `high` - High prices
 /// * `low` - Low prices
@@ -16,37 +52,260 @@ use crate::overlap::ma;
 /// 
 /// # Returns
 /// Tuple of (Slow %K, Slow %D)
+/// 
+/// # Example
+/// ```
+/// use ta_rust::common::MAType;
+/// use ta_rust::momentum::stoch;
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.4).sin() * 5.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+///
+/// // Metastock-style: %K and %D smoothed with EMAs
+/// let (k, d) = stoch(&high, &low, &close, 14, 3, MAType::EMA, 3, MAType::EMA).unwrap();
+/// assert!(k[15].is_finite() && d[17].is_finite());
+/// ```
+#[allow(clippy::too_many_arguments)]
 pub fn stoch(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    fastk_period: usize,
-    slowk_period: usize,
+    fastk_period: impl IntoParam<Period>,
+    slowk_period: impl IntoParam<Period>,
     slowk_ma: MAType,
-    slowd_period: usize,
+    slowd_period: impl IntoParam<Period>,
     slowd_ma: MAType,
 ) -> TAResult<(Vec<f64>, Vec<f64>)> {
-    let len = close.len();
-    if high.len() != len || low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}", high.len(), low.len(), len)));
-    }
-    if len < fastk_period {
-        return Err(TAError::insufficient_data(fastk_period, len));
-    }
-    let mut fastk = vec![f64::NAN; len];
-    for i in (fastk_period - 1)..len {
-        let (hh, ll) = (
-            high[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MIN, f64::max),
-            low[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MAX, f64::min),
-        );
-        let denom = hh - ll;
-        if denom.abs() < 1e-12 {
-            fastk[i] = 0.0;
-        } else {
-            fastk[i] = 100.0 * (close[i] - ll) / denom;
+    with_function("STOCH", || {
+        let fastk_period = fastk_period.into_param("fastk_period")?.get();
+        let slowk_period = slowk_period.into_param("slowk_period")?.get();
+        let slowd_period = slowd_period.into_param("slowd_period")?.get();
+        let len = close.len();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        if len < fastk_period {
+            return Err(TAError::insufficient_data(fastk_period, len));
+        }
+        let mut fastk = vec![f64::NAN; len];
+        for i in (fastk_period - 1)..len {
+            let (hh, ll) = (
+                high[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MIN, f64::max),
+                low[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MAX, f64::min),
+            );
+            fastk[i] = range_position(close[i], ll, hh).map_or(0.0, |position| 100.0 * position);
+        }
+        let slowk = ma_skip_nan(&fastk, slowk_period, slowk_ma)?;
+        let slowd = ma_skip_nan(&slowk, slowd_period, slowd_ma)?;
+        Ok((slowk, slowd))
+    })
+}
+
+/// Calculates the Stochastic Oscillator, returning named outputs
+///
+/// Same as [`stoch`] but returns a [`StochOutput`] (slow %K and slow %D) instead of a tuple.
+#[allow(clippy::too_many_arguments)]
+pub fn stoch_output(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    fastk_period: impl IntoParam<Period>,
+    slowk_period: impl IntoParam<Period>,
+    slowk_ma: MAType,
+    slowd_period: impl IntoParam<Period>,
+    slowd_ma: MAType,
+) -> TAResult<StochOutput> {
+    with_function("STOCH", || {
+        let fastk_period = fastk_period.into_param("fastk_period")?.get();
+        let slowk_period = slowk_period.into_param("slowk_period")?.get();
+        let slowd_period = slowd_period.into_param("slowd_period")?.get();
+        stoch(high, low, close, fastk_period, slowk_period, slowk_ma, slowd_period, slowd_ma).map(StochOutput::from)
+    })
+}
+
+/// Streaming Stochastic Oscillator
+///
+/// Produces the same slow %K and slow %D as [`stoch`] one bar at a time, in O(1) amortized
+/// time per update. The highest high and lowest low of the %K window are tracked with
+/// monotonic queues instead of rescanning the window on every bar.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::MAType;
+/// use ta_rust::momentum::{stoch, StochState};
+///
+/// let close: Vec<f64> = (0..30).map(|i| 100.0 + (i as f64 * 0.4).sin() * 5.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+/// let (k, d) = stoch(&high, &low, &close, 5, 3, MAType::SMA, 3, MAType::SMA).unwrap();
+///
+/// let mut state = StochState::new(5, 3, MAType::SMA, 3, MAType::SMA).unwrap();
+/// for i in 0..close.len() {
+///     match state.update(high[i], low[i], close[i]) {
+///         Some((slowk, slowd)) => {
+///             assert!((slowk - k[i]).abs() < 1e-9);
+///             assert!((slowd - d[i]).abs() < 1e-9);
+///         }
+///         None => assert!(d[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StochState {
+    fastk_period: usize,
+    /// Number of bars seen
+    index: usize,
+    /// Highest high of the window
+    highs: RunningMax,
+    /// Lowest low of the window
+    lows: RunningMin,
+    slowk: MaState,
+    slowd: MaState,
+    value: Option<(f64, f64)>,
+}
+
+impl StochState {
+    /// Creates a new state with the same parameters as [`stoch`]
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if any period is 0
+    /// - `UnsupportedOperation` if a moving average type has no streaming form
+    pub fn new(
+        fastk_period: impl IntoParam<Period>,
+        slowk_period: impl IntoParam<Period>,
+        slowk_ma: MAType,
+        slowd_period: impl IntoParam<Period>,
+        slowd_ma: MAType,
+    ) -> TAResult<Self> {
+        with_function("STOCH", || {
+            let fastk_period = fastk_period.into_param("fastk_period")?.get();
+            let slowk_period = slowk_period.into_param("slowk_period")?.get();
+            let slowd_period = slowd_period.into_param("slowd_period")?.get();
+
+            Ok(Self {
+                fastk_period,
+                index: 0,
+                highs: RunningMax::new(fastk_period),
+                lows: RunningMin::new(fastk_period),
+                slowk: MaState::new(slowk_period, slowk_ma)?,
+                slowd: MaState::new(slowd_period, slowd_ma)?,
+                value: None,
+            })
+        })
+    }
+
+    /// Feeds the next bar and returns `(slow %K, slow %D)`, or `None` during warm-up
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, f64)> {
+        self.index += 1;
+        // Like the `f64::max`/`f64::min` folds of `stoch`, missing values are ignored
+        let hh = self.highs.push(high).unwrap_or(f64::MIN);
+        let ll = self.lows.push(low).unwrap_or(f64::MAX);
+        if self.index < self.fastk_period {
+            return None;
+        }
+
+        let fastk = range_position(close, ll, hh).map_or(0.0, |position| 100.0 * position);
+
+        self.value = self.slowk.update(fastk).and_then(|k| Some((k, self.slowd.update(k)?)));
+        self.value
+    }
+
+    /// Current `(slow %K, slow %D)`, or `None` during warm-up
+    pub fn current(&self) -> Option<(f64, f64)> {
+        self.value
+    }
+
+    /// Current slow %K, available before slow %D has warmed up
+    pub fn slowk(&self) -> Option<f64> {
+        self.slowk.current()
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.highs.clear();
+        self.lows.clear();
+        self.slowk.reset();
+        self.slowd.reset();
+        self.value = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stoch_output() {
+        let close: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+
+        let (k, d) = stoch(&high, &low, &close, 5, 3, MAType::SMA, 3, MAType::SMA).unwrap();
+        let output = stoch_output(&high, &low, &close, 5, 3, MAType::SMA, 3, MAType::SMA).unwrap();
+        assert_eq!(output.k[20], k[20]);
+        assert_eq!(output.d[20], d[20]);
+        assert!(output.crossovers().iter().any(|&s| s != 0));
+    }
+
+    #[test]
+    fn test_stoch_state_matches_batch() {
+        let close: Vec<f64> = (0..100).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0 + (i as f64 * 0.11).cos() * 3.0).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.5 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.5 - (i % 3) as f64 * 0.4).collect();
+
+        for (slowk_ma, slowd_ma) in [(MAType::SMA, MAType::SMA), (MAType::EMA, MAType::WMA)] {
+            let (k, d) = stoch(&high, &low, &close, 14, 3, slowk_ma, 5, slowd_ma).unwrap();
+            let mut state = StochState::new(14, 3, slowk_ma, 5, slowd_ma).unwrap();
+            for i in 0..close.len() {
+                match state.update(high[i], low[i], close[i]) {
+                    Some((slowk, slowd)) => {
+                        assert!((slowk - k[i]).abs() < 1e-9, "%K at {}", i);
+                        assert!((slowd - d[i]).abs() < 1e-9, "%D at {}", i);
+                    }
+                    None => assert!(d[i].is_nan(), "%D at {}", i),
+                }
+            }
+            assert_eq!(state.slowk(), Some(state.current().unwrap().0));
+            state.reset();
+            assert_eq!(state, StochState::new(14, 3, slowk_ma, 5, slowd_ma).unwrap());
         }
+
+        assert!(StochState::new(0, 3, MAType::SMA, 3, MAType::SMA).is_err());
+        assert!(StochState::new(5, 3, MAType::KAMA, 3, MAType::SMA).is_err());
+    }
+
+    #[test]
+    fn test_stoch_smoothing_types() {
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.25).sin() * 4.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 0.8).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.6).collect();
+
+        let (fastk, _) = crate::momentum::stochf(&high, &low, &close, 14, 1, MAType::SMA).unwrap();
+        let (k, d) = stoch(&high, &low, &close, 14, 3, MAType::EMA, 4, MAType::WMA).unwrap();
+        let expected_k = ma_skip_nan(&fastk, 3, MAType::EMA).unwrap();
+        let expected_d = ma_skip_nan(&expected_k, 4, MAType::WMA).unwrap();
+        crate::common::assert_arrays_approx_equal(&k, &expected_k, 1e-12);
+        crate::common::assert_arrays_approx_equal(&d, &expected_d, 1e-12);
+
+        let (sma_k, _) = stoch(&high, &low, &close, 14, 3, MAType::SMA, 4, MAType::WMA).unwrap();
+        assert!((sma_k[30] - k[30]).abs() > 1e-9);
+    }
+
+    #[test]
+    fn test_stoch_ema_smoothing_skips_warmup() {
+        // EMA smoothing of %K used to run over its NaN warm-up, leaving both lines NaN
+        // throughout; each average now starts at the first value it smooths
+        let close: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+        let (fastk, _) = crate::momentum::stochf(&high, &low, &close, 5, 1, MAType::SMA).unwrap();
+        assert!(crate::overlap::ma(&fastk, 3, MAType::EMA).unwrap().iter().all(|v| v.is_nan()));
+
+        let (slowk, slowd) = stoch(&high, &low, &close, 5, 3, MAType::EMA, 3, MAType::EMA).unwrap();
+        assert_eq!(slowk.iter().take_while(|v| v.is_nan()).count(), 6);
+        assert_eq!(slowd.iter().take_while(|v| v.is_nan()).count(), 8);
+        assert!((slowk[6] - 75.57786800003058).abs() < 1e-9);
+        assert!((slowd[8] - 71.7601751473268).abs() < 1e-9);
     }
-    let slowk = ma(&fastk, slowk_period, slowk_ma)?;
-    let slowd = ma(&slowk, slowd_period, slowd_ma)?;
-    Ok((slowk, slowd))
-} 
\ No newline at end of file
+}
@@ -0,0 +1,110 @@
+// STOCH_OF - Stochastic of an arbitrary series
+use crate::common::{validate_not_empty, MAType, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::overlap::ma::ma_skip_nan;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the Stochastic of any series.
+///
+/// Applies the stochastic normalization of [`stochf`](crate::momentum::stochf) to a single
+/// series: %K places each value within the range of the last `k_period` values of the series
+/// itself, and %D is the SMA of %K. Stochastic RSI, the Schaff Trend Cycle and similar
+/// oscillators are this normalization applied to another indicator.
+///
+/// Leading NaNs, such as the warm-up of the input indicator, are skipped; a window holding
+/// any other NaN gives a NaN %K. A flat window gives a %K of 0.
+///
+/// # Formula
+/// ```text
+/// %K = 100 × (x - min(x, k_period)) / (max(x, k_period) - min(x, k_period))
+/// %D = SMA(%K, d_period)
+/// ```
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `k_period` - %K period
+/// * `d_period` - %D period
+///
+/// # Returns
+/// Tuple of (%K, %D)
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::MAType;
+/// use ta_rust::momentum::{rsi, stoch_of, stochrsi};
+///
+/// let close: Vec<f64> = (0..60).map(|i| 50.0 + (i as f64 * 0.3).sin() * 4.0).collect();
+/// let (k, d) = stoch_of(&rsi(&close, 14).unwrap(), 5, 3).unwrap();
+/// let (fastk, fastd) = stochrsi(&close, 14, 5, 3, MAType::SMA).unwrap();
+/// assert_eq!(k[30], fastk[30]);
+/// assert_eq!(d[30], fastd[30]);
+/// ```
+pub fn stoch_of(series: &[f64], k_period: impl IntoParam<Period>, d_period: impl IntoParam<Period>) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    with_function("STOCH_OF", || {
+        let k_period = k_period.into_param("k_period")?.get();
+        let d_period = d_period.into_param("d_period")?.get();
+        stoch_of_ma(series, k_period, d_period, MAType::SMA)
+    })
+}
+
+/// [`stoch_of`] with a chosen %D moving average type
+pub(crate) fn stoch_of_ma(series: &[f64], k_period: usize, d_period: usize, d_ma: MAType) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    validate_not_empty(series, "series")?;
+    let len = series.len();
+    if len < k_period {
+        return Err(TAError::insufficient_data(k_period, len));
+    }
+    let start = series.iter().take_while(|v| v.is_nan()).count();
+    let mut k = vec![f64::NAN; len];
+    for i in (start + k_period - 1)..len {
+        let window = &series[i + 1 - k_period..=i];
+        if window.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        let lowest = window.iter().cloned().fold(f64::MAX, f64::min);
+        let highest = window.iter().cloned().fold(f64::MIN, f64::max);
+        let denom = highest - lowest;
+        if denom.abs() < 1e-12 {
+            k[i] = 0.0;
+        } else {
+            k[i] = 100.0 * (series[i] - lowest) / denom;
+        }
+    }
+    let d = ma_skip_nan(&k, d_period, d_ma)?;
+    Ok((k, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::momentum::stochf;
+
+    #[test]
+    fn test_stoch_of_matches_stochf_on_close() {
+        // With high = low = close the range of the bars is the range of the closes
+        let close: Vec<f64> = (0..40).map(|i| 20.0 + (i as f64 * 0.45).sin() * 2.0 + i as f64 * 0.05).collect();
+        let (k, d) = stoch_of(&close, 7, 3).unwrap();
+        let (fastk, fastd) = stochf(&close, &close, &close, 7, 3, MAType::SMA).unwrap();
+        assert_arrays_approx_equal(&k, &fastk, 1e-12);
+        assert_arrays_approx_equal(&d, &fastd, 1e-12);
+        assert!(k[6..].iter().all(|&v| (0.0..=100.0 + 1e-9).contains(&v)));
+    }
+
+    #[test]
+    fn test_stoch_of_nan_handling() {
+        let series = vec![f64::NAN, f64::NAN, 1.0, 3.0, 2.0, 2.0, f64::NAN, 5.0, 4.0, 6.0];
+        let (k, d) = stoch_of(&series, 3, 2).unwrap();
+        assert!(k[..4].iter().all(|v| v.is_nan()));
+        assert_eq!(k[4], 50.0);
+        assert_eq!(k[5], 0.0);
+        assert!(k[6..9].iter().all(|v| v.is_nan()));
+        assert_eq!(k[9], 100.0);
+        assert_eq!(d[5], 25.0);
+
+        assert!(matches!(stoch_of(&[1.0, 2.0], 3, 2), Err(TAError::InsufficientData { .. })));
+        assert!(stoch_of(&[1.0, 2.0], 0, 2).is_err());
+        assert!(stoch_of(&[], 3, 2).is_err());
+    }
+}
@@ -1,6 +1,10 @@
 // STOCHF - Stochastic Fast
-use crate::common::{TAError, TAResult, MAType};
-use crate::overlap::ma;
+use crate::common::{TAError, TAResult, MAType, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::range_position;
+use crate::overlap::ma::ma_skip_nan;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Fast Stochastic Oscillator.
 /// 
@@ -18,30 +22,47 @@ pub fn stochf(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    fastk_period: usize,
-    fastd_period: usize,
+    fastk_period: impl IntoParam<Period>,
+    fastd_period: impl IntoParam<Period>,
     fastd_ma: MAType,
 ) -> TAResult<(Vec<f64>, Vec<f64>)> {
-    let len = close.len();
-    if high.len() != len || low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}", high.len(), low.len(), len)));
-    }
-    if len < fastk_period {
-        return Err(TAError::insufficient_data(fastk_period, len));
-    }
-    let mut fastk = vec![f64::NAN; len];
-    for i in (fastk_period - 1)..len {
-        let (hh, ll) = (
-            high[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MIN, f64::max),
-            low[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MAX, f64::min),
-        );
-        let denom = hh - ll;
-        if denom.abs() < 1e-12 {
-            fastk[i] = 0.0;
-        } else {
-            fastk[i] = 100.0 * (close[i] - ll) / denom;
+    with_function("STOCHF", || {
+        let fastk_period = fastk_period.into_param("fastk_period")?.get();
+        let fastd_period = fastd_period.into_param("fastd_period")?.get();
+        let len = close.len();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        if len < fastk_period {
+            return Err(TAError::insufficient_data(fastk_period, len));
+        }
+        let mut fastk = vec![f64::NAN; len];
+        for i in (fastk_period - 1)..len {
+            let (hh, ll) = (
+                high[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MIN, f64::max),
+                low[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MAX, f64::min),
+            );
+            fastk[i] = range_position(close[i], ll, hh).map_or(0.0, |position| 100.0 * position);
         }
+        let fastd = ma_skip_nan(&fastk, fastd_period, fastd_ma)?;
+        Ok((fastk, fastd))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stochf_ema_smoothing_skips_warmup() {
+        // An EMA %D used to run over the NaN warm-up of %K and was NaN throughout; it now
+        // starts at the first %K value
+        let close: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+        let (fastk, fastd) = stochf(&high, &low, &close, 5, 3, MAType::EMA).unwrap();
+        assert!(crate::overlap::ma(&fastk, 3, MAType::EMA).unwrap().iter().all(|v| v.is_nan()));
+
+        assert_eq!(fastd.iter().take_while(|v| v.is_nan()).count(), crate::common::stochf_lookback(5, 3, MAType::EMA));
+        assert!((fastd[6] - 75.57786800003058).abs() < 1e-9);
+        assert!((fastd[7] - 68.63312940316294).abs() < 1e-9);
     }
-    let fastd = ma(&fastk, fastd_period, fastd_ma)?;
-    Ok((fastk, fastd))
-} 
\ No newline at end of file
+}
@@ -1,10 +1,16 @@
 // STOCHRSI - Stochastic RSI
-use crate::common::{TAError, TAResult, MAType};
+use crate::common::{validate_prices, TAError, TAResult, MAType, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::momentum::rsi;
-use crate::overlap::ma;
+use crate::momentum::stoch_of::stoch_of_ma;
+use crate::overlap::ma::ma_skip_nan;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Stochastic RSI.
 /// 
+/// The [`stoch_of`](crate::momentum::stoch_of) of the RSI.
+/// 
 /// # Arguments
 /// * `price` - Input price series
 /// * `rsi_period` - RSI period
@@ -15,29 +21,109 @@ use crate::overlap::ma;
 /// # Returns
 /// Tuple of (Fast %K, Fast %D)
 pub fn stochrsi(
+    price: &[f64],
+    rsi_period: impl IntoParam<Period>,
+    fastk_period: impl IntoParam<Period>,
+    fastd_period: impl IntoParam<Period>,
+    fastd_ma: MAType,
+) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    with_function("STOCHRSI", || {
+        let rsi_period = rsi_period.into_param("rsi_period")?.get();
+        let fastk_period = fastk_period.into_param("fastk_period")?.get();
+        let fastd_period = fastd_period.into_param("fastd_period")?.get();
+        let rsi_vec = rsi(price, rsi_period)?;
+        stoch_of_ma(&rsi_vec, fastk_period, fastd_period, fastd_ma)
+    })
+}
+
+/// [`stochrsi`] in TA-Lib's order of operations
+///
+/// Where prices stop moving, the RSI is constant up to rounding: TA-Lib scales that rounding
+/// to a %K of 0 or 100 where [`stochrsi`] gives 0. Following its arithmetic for the Wilder
+/// averages and the %K ratio gives the same values there. Both outputs start as in [`stochrsi`].
+pub(crate) fn stochrsi_talib(
     price: &[f64],
     rsi_period: usize,
     fastk_period: usize,
     fastd_period: usize,
     fastd_ma: MAType,
 ) -> TAResult<(Vec<f64>, Vec<f64>)> {
-    let rsi_vec = rsi(price, rsi_period)?;
-    let len = rsi_vec.len();
-    if len < fastk_period {
-        return Err(TAError::insufficient_data(fastk_period, len));
-    }
-    let mut stochrsi = vec![f64::NAN; len];
-    for i in (fastk_period - 1)..len {
-        let min_rsi = rsi_vec[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MAX, f64::min);
-        let max_rsi = rsi_vec[i + 1 - fastk_period..=i].iter().cloned().fold(f64::MIN, f64::max);
-        let denom = max_rsi - min_rsi;
-        if denom.abs() < 1e-12 {
-            stochrsi[i] = 0.0;
-        } else {
-            stochrsi[i] = (rsi_vec[i] - min_rsi) / denom;
+    with_function("STOCHRSI", || {
+        validate_prices(price, "price")?;
+        let len = price.len();
+        if len < rsi_period + fastk_period {
+            return Err(TAError::insufficient_data(rsi_period + fastk_period, len));
+        }
+
+        let periods = rsi_period as f64;
+        let (mut gain, mut loss) = (0.0, 0.0);
+        let mut rsi_vec = vec![f64::NAN; len];
+        for i in 1..len {
+            if i > rsi_period {
+                gain *= periods - 1.0;
+                loss *= periods - 1.0;
+            }
+            let change = price[i] - price[i - 1];
+            if change < 0.0 {
+                loss -= change;
+            } else {
+                gain += change;
+            }
+            if i >= rsi_period {
+                gain /= periods;
+                loss /= periods;
+                let total = gain + loss;
+                rsi_vec[i] = if total.abs() < 1e-8 { 0.0 } else { 100.0 * (gain / total) };
+            }
         }
+
+        let mut fastk = vec![f64::NAN; len];
+        for i in (rsi_period + fastk_period - 1)..len {
+            let window = &rsi_vec[i + 1 - fastk_period..=i];
+            let lowest = window.iter().cloned().fold(f64::MAX, f64::min);
+            let highest = window.iter().cloned().fold(f64::MIN, f64::max);
+            let diff = (highest - lowest) / 100.0;
+            fastk[i] = if diff != 0.0 { (rsi_vec[i] - lowest) / diff } else { 0.0 };
+        }
+        let fastd = ma_skip_nan(&fastk, fastd_period, fastd_ma)?;
+        Ok((fastk, fastd))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stochrsi_windows_of_valid_rsi() {
+        // %K used to start at `rsi_period`, over windows still holding the RSI warm-up; it
+        // now starts once a window holds `fastk_period` RSI values
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + (i as f64 * 2.1).sin() * 3.0 + i as f64 * 0.1).collect();
+        let rsi_vec = rsi(&close, 5).unwrap();
+        let (fastk, fastd) = stochrsi(&close, 5, 4, 3, MAType::SMA).unwrap();
+        assert!(fastk[..8].iter().all(|v| v.is_nan()));
+
+        for i in 8..10 {
+            let window = &rsi_vec[i - 3..=i];
+            let (min, max) = window.iter().fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+            assert!((fastk[i] - 100.0 * (rsi_vec[i] - min) / (max - min)).abs() < 1e-9);
+        }
+        assert_eq!((fastk[8], fastk[9]), (0.0, 100.0));
+        assert_eq!(fastd.iter().take_while(|v| v.is_nan()).count(), crate::common::stochrsi_lookback(5, 4, 3, MAType::SMA));
+    }
+
+    #[test]
+    fn test_stochrsi_talib() {
+        // Same values as stochrsi where the RSI moves
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1).collect();
+        let (fastk, fastd) = stochrsi(&close, 5, 4, 3, MAType::SMA).unwrap();
+        let (talib_k, talib_d) = stochrsi_talib(&close, 5, 4, 3, MAType::SMA).unwrap();
+        crate::common::assert_arrays_approx_equal(&talib_k, &fastk, 1e-9);
+        crate::common::assert_arrays_approx_equal(&talib_d, &fastd, 1e-9);
+
+        // Without any price change both give 0
+        let (flat_k, _) = stochrsi_talib(&[10.0; 12], 5, 4, 3, MAType::SMA).unwrap();
+        assert!(flat_k[8..].iter().all(|&v| v == 0.0));
+        assert!(stochrsi_talib(&close[..8], 5, 4, 3, MAType::SMA).is_err());
     }
-    let fastk: Vec<f64> = stochrsi.iter().map(|&v| v * 100.0).collect();
-    let fastd = ma(&fastk, fastd_period, fastd_ma)?;
-    Ok((fastk, fastd))
-} 
\ No newline at end of file
+}
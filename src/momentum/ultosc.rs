@@ -1,8 +1,31 @@
 // ULTOSC - Ultimate Oscillator
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Ultimate Oscillator configuration for [`ultosc_with`]
+///
+/// The default is Williams' original: periods 7, 14 and 28 weighted 4, 2 and 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UltoscParams {
+    /// Periods of the three buying pressure averages, usually short to long
+    pub periods: [usize; 3],
+    /// Weight of each average, in the order of `periods`
+    pub weights: [f64; 3],
+}
+
+impl Default for UltoscParams {
+    fn default() -> Self {
+        Self { periods: [7, 14, 28], weights: [4.0, 2.0, 1.0] }
+    }
+}
 
 /// Calculates the Ultimate Oscillator.
 /// 
+/// Weights the three averages 4, 2 and 1; see [`ultosc_with`] for other weights.
+/// 
 /// # Arguments
 /// * `high` - High prices
 /// * `low` - Low prices
@@ -17,36 +40,146 @@ pub fn ultosc(
     high: &[f64],
     low: &[f64],
     close: &[f64],
-    period1: usize,
-    period2: usize,
-    period3: usize,
+    period1: impl IntoParam<Period>,
+    period2: impl IntoParam<Period>,
+    period3: impl IntoParam<Period>,
 ) -> TAResult<Vec<f64>> {
-    let len = close.len();
-    if high.len() != len || low.len() != len {
-        return Err(TAError::mismatched_inputs(format!("high: {}, low: {}, close: {}", high.len(), low.len(), len)));
-    }
-    if len < period3 {
-        return Err(TAError::insufficient_data(period3, len));
+    with_function("ULTOSC", || {
+        let periods = [
+            period1.into_param("period1")?.get(),
+            period2.into_param("period2")?.get(),
+            period3.into_param("period3")?.get(),
+        ];
+        ultosc_with(high, low, close, &UltoscParams { periods, ..UltoscParams::default() })
+    })
+}
+
+/// Calculates the Ultimate Oscillator with custom periods and weights
+///
+/// # Formula
+/// ```text
+/// BP = Close - min(Low, Previous Close)
+/// TR = max(High, Previous Close) - min(Low, Previous Close)
+/// Average(n) = sum(BP, n) / sum(TR, n), or 0 if sum(TR, n) is 0
+/// ULTOSC = 100 * (w1 * Average(n1) + w2 * Average(n2) + w3 * Average(n3)) / (w1 + w2 + w3)
+/// ```
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `params` - Periods and weights
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of values from 0 to 100; the first `longest period` values are NaN,
+///   as every window needs the previous close of its bars
+/// * `Err(TAError)` - Error if inputs are invalid, a period is 0 or the weights are negative,
+///   not finite or all zero
+///
+/// # Examples
+/// ```
+/// use ta_rust::momentum::{ultosc, ultosc_with, UltoscParams};
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+///
+/// let classic = ultosc_with(&high, &low, &close, &UltoscParams::default()).unwrap();
+/// assert_eq!(classic[30], ultosc(&high, &low, &close, 7, 14, 28).unwrap()[30]);
+///
+/// let equal = UltoscParams { weights: [1.0, 1.0, 1.0], ..UltoscParams::default() };
+/// assert!(ultosc_with(&high, &low, &close, &equal).unwrap()[30] != classic[30]);
+/// ```
+pub fn ultosc_with(high: &[f64], low: &[f64], close: &[f64], params: &UltoscParams) -> TAResult<Vec<f64>> {
+    with_function("ULTOSC", || {
+        let [period1, period2, period3] = params.periods;
+        let weights = params.weights;
+        let len = close.len();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        if period1 == 0 || period2 == 0 || period3 == 0 {
+            return Err(TAError::invalid_parameter("period", "must be greater than 0"));
+        }
+        if weights.iter().any(|w| !(w.is_finite() && *w >= 0.0)) {
+            return Err(TAError::invalid_parameter("weights", "must be non-negative and finite"));
+        }
+        let total_weight: f64 = weights.iter().sum();
+        if total_weight <= 0.0 {
+            return Err(TAError::invalid_parameter("weights", "must not all be zero"));
+        }
+        let longest = period1.max(period2).max(period3);
+        if len <= longest {
+            return Err(TAError::insufficient_data(longest + 1, len));
+        }
+        let mut bp = vec![f64::NAN; len];
+        let mut tr = vec![f64::NAN; len];
+        for i in 1..len {
+            let prev_close = close[i - 1];
+            bp[i] = close[i] - low[i].min(prev_close);
+            tr[i] = high[i].max(prev_close) - low[i].min(prev_close);
+        }
+        let mut out = vec![f64::NAN; len];
+        for i in longest..len {
+            let sum1: f64 = bp[i + 1 - period1..=i].iter().sum();
+            let sumtr1: f64 = tr[i + 1 - period1..=i].iter().sum();
+            let sum2: f64 = bp[i + 1 - period2..=i].iter().sum();
+            let sumtr2: f64 = tr[i + 1 - period2..=i].iter().sum();
+            let sum3: f64 = bp[i + 1 - period3..=i].iter().sum();
+            let sumtr3: f64 = tr[i + 1 - period3..=i].iter().sum();
+            // A window without range adds nothing, as in TA-Lib
+            let average = |sum: f64, sumtr: f64| if sumtr == 0.0 { 0.0 } else { sum / sumtr };
+            let avg1 = average(sum1, sumtr1);
+            let avg2 = average(sum2, sumtr2);
+            let avg3 = average(sum3, sumtr3);
+            out[i] = 100.0 * ((weights[0] * avg1) + (weights[1] * avg2) + (weights[2] * avg3)) / total_weight;
+        }
+        Ok(out)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ultosc_params() {
+        let close: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.5).sin() * 2.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+
+        // A single weighted average is that average, as a percentage
+        let only_fast = UltoscParams { periods: [5, 10, 20], weights: [1.0, 0.0, 0.0] };
+        let single = ultosc_with(&high, &low, &close, &only_fast).unwrap();
+        let same = ultosc_with(&high, &low, &close, &UltoscParams { periods: [5, 5, 5], ..UltoscParams::default() }).unwrap();
+        crate::assert_float_eq!(single[25], same[25], 1e-9);
+        assert!(single[19].is_nan() && single[20].is_finite());
+
+        for weights in [[0.0; 3], [1.0, -1.0, 1.0], [f64::NAN, 1.0, 1.0]] {
+            let params = UltoscParams { weights, ..UltoscParams::default() };
+            assert!(ultosc_with(&high, &low, &close, &params).is_err());
+        }
+        let zero_period = UltoscParams { periods: [0, 14, 28], ..UltoscParams::default() };
+        assert!(ultosc_with(&high, &low, &close, &zero_period).is_err());
     }
-    let mut bp = vec![f64::NAN; len];
-    let mut tr = vec![f64::NAN; len];
-    for i in 0..len {
-        let prev_close = if i == 0 { close[0] } else { close[i - 1] };
-        bp[i] = close[i] - low[i].min(prev_close);
-        tr[i] = high[i].max(prev_close) - low[i].min(prev_close);
+
+    #[test]
+    fn test_ultosc_third_period_not_longest() {
+        // The third period used to be taken as the longest and shorter ones overflowed the
+        // window start; the longest of the three now sets the warm-up
+        let close: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.1).sin() * 8.0 + (i as f64 * 0.37).cos()).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.4 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.6 - (i % 3) as f64 * 0.2).collect();
+        let result = ultosc(&high, &low, &close, 7, 14, 5).unwrap();
+        assert_eq!(result.iter().take_while(|v| v.is_nan()).count(), 14);
+        assert!((result[14] - 50.50813589479268).abs() < 1e-9);
     }
-    let mut out = vec![f64::NAN; len];
-    for i in (period3 - 1)..len {
-        let sum1: f64 = bp[i + 1 - period1..=i].iter().sum();
-        let sumtr1: f64 = tr[i + 1 - period1..=i].iter().sum();
-        let sum2: f64 = bp[i + 1 - period2..=i].iter().sum();
-        let sumtr2: f64 = tr[i + 1 - period2..=i].iter().sum();
-        let sum3: f64 = bp[i + 1 - period3..=i].iter().sum();
-        let sumtr3: f64 = tr[i + 1 - period3..=i].iter().sum();
-        let avg1 = sum1 / sumtr1;
-        let avg2 = sum2 / sumtr2;
-        let avg3 = sum3 / sumtr3;
-        out[i] = 100.0 * ((4.0 * avg1) + (2.0 * avg2) + avg3) / 7.0;
+
+    #[test]
+    fn test_ultosc_flat_window() {
+        // The 3-bar window has no range and adds nothing; the others still count
+        let close = [10.0, 10.0, 11.0, 12.0, 12.0, 12.0, 12.0];
+        let result = ultosc(&close, &close, &close, 3, 4, 6).unwrap();
+        assert!(result[..6].iter().all(|v| v.is_nan()));
+        assert!((result[6] - 100.0 * (2.0 + 1.0) / 7.0).abs() < 1e-12);
+        assert_eq!(ultosc(&[5.0; 8], &[5.0; 8], &[5.0; 8], 2, 3, 4).unwrap()[4..], [0.0; 4]);
     }
-    Ok(out)
-} 
\ No newline at end of file
+}
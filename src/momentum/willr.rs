@@ -4,43 +4,12 @@
 //! It oscillates between -100 and 0, with values above -20 considered overbought
 //! and values below -80 considered oversold.
 
-use crate::common::{TAError, validate_period};
+use crate::common::{TAError, ValidationMode, prepare_hlc, validate_hlc, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::math_operators::{max, min};
-
-/// Validates HLC data consistency
-fn validate_hlc(high: &[f64], low: &[f64], close: &[f64]) -> Result<(), TAError> {
-    // Validate that high, low, close have same length
-    if high.len() != low.len() || high.len() != close.len() {
-        return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length".to_string()));
-    }
-    
-    // Validate HLC constraints for each bar
-    for i in 0..high.len() {
-        let (h, l, c) = (high[i], low[i], close[i]);
-        
-        if !h.is_finite() || !l.is_finite() || !c.is_finite() {
-            return Err(TAError::invalid_input(format!(
-                "Invalid HLC values at index {}: H={}, L={}, C={}",
-                i, h, l, c
-            )));
-        }
-        
-        if h < l {
-            return Err(TAError::invalid_input(format!(
-                "High ({}) < Low ({}) at index {}",
-                h, l, i
-            )));
-        }
-        if c < l || c > h {
-            return Err(TAError::invalid_input(format!(
-                "Close ({}) is outside High-Low range [{}, {}] at index {}",
-                c, l, h, i
-            )));
-        }
-    }
-    
-    Ok(())
-}
+use crate::statistic::range_position;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Williams' %R.
 /// 
@@ -70,40 +39,78 @@ fn validate_hlc(high: &[f64], low: &[f64], close: &[f64]) -> Result<(), TAError>
 /// let result = willr(&high, &low, &close, 3).unwrap();
 /// assert_eq!(result.len(), 7);
 /// ```
-pub fn willr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    validate_hlc(high, low, close)?;
-    validate_period(period, "period")?;
+pub fn willr(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("WILLR", || {
+        let period = period.into_param("period")?.get();
+        willr_with_mode(high, low, close, period, ValidationMode::Strict)
+    })
+}
+
+/// Calculates Williams' %R with a chosen treatment of closes outside the bar range.
+/// 
+/// With [`ValidationMode::Lenient`] a close above the high or below the low is used as given,
+/// so %R can leave the [-100, 0] range; [`ValidationMode::Repair`] clamps it into the bar first.
+/// 
+/// # Arguments
+/// 
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `period` - Period for calculation (typically 14)
+/// * `mode` - How a close outside the high-low range is treated
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(Vec<f64>)` containing Williams' %R values, or `Err(TAError)` on invalid input.
+/// 
+/// # Example
+/// 
+/// ```
+/// use ta_rust::common::ValidationMode;
+/// use ta_rust::momentum::{willr, willr_with_mode};
+/// 
+/// let high = vec![10.0, 11.0, 12.0, 11.5];
+/// let low = vec![9.0, 10.0, 10.5, 10.0];
+/// let close = vec![9.5, 11.001, 11.5, 10.5]; // rounded above the high
+/// 
+/// assert!(willr(&high, &low, &close, 3).is_err());
+/// let result = willr_with_mode(&high, &low, &close, 3, ValidationMode::Repair).unwrap();
+/// assert_eq!(result.len(), 4);
+/// ```
+pub fn willr_with_mode(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, mode: ValidationMode) -> Result<Vec<f64>, TAError> {
+    with_function("WILLR", || {
+        let period = period.into_param("period")?.get();
+        let close = prepare_hlc(high, low, close, mode)?;
     
-    let len = high.len();
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
+        let len = high.len();
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
     
-    // Calculate highest high and lowest low over period
-    let highest_high = max(high, period)?;
-    let lowest_low = min(low, period)?;
+        // Calculate highest high and lowest low over period
+        let highest_high = max(high, period)?;
+        let lowest_low = min(low, period)?;
     
+        Ok(willr_from_extremes(&highest_high, &lowest_low, &close))
+    })
+}
+
+/// Williams' %R from the precomputed highest high and lowest low
+pub(crate) fn willr_from_extremes(highest_high: &[f64], lowest_low: &[f64], close: &[f64]) -> Vec<f64> {
+    let len = highest_high.len();
     let mut result = Vec::with_capacity(len);
     
-    // Calculate Williams' %R
+    // Calculate Williams' %R, 0 when there is no range as in TA-Lib
     for i in 0..len {
         if highest_high[i].is_nan() || lowest_low[i].is_nan() {
             result.push(f64::NAN);
         } else {
-            let hh = highest_high[i];
-            let ll = lowest_low[i];
-            let range = hh - ll;
-            
-            if range == 0.0 {
-                result.push(-50.0); // Midpoint when no range
-            } else {
-                let willr_val = -100.0 * (hh - close[i]) / range;
-                result.push(willr_val);
-            }
+            let position = range_position(close[i], lowest_low[i], highest_high[i]).unwrap_or(1.0);
+            result.push(100.0 * position - 100.0);
         }
     }
     
-    Ok(result)
+    result
 }
 
 /// Calculates Williams' %R using OHLC data structure.
@@ -133,16 +140,19 @@ pub fn willr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<
 /// let result = willr_ohlc(&data, 3).unwrap();
 /// assert_eq!(result.len(), 4);
 /// ```
-pub fn willr_ohlc(ohlc: &[crate::common::types::OHLC], period: usize) -> Result<Vec<f64>, TAError> {
-    if ohlc.is_empty() {
-        return Err(TAError::invalid_input("OHLC data cannot be empty"));
-    }
+pub fn willr_ohlc(ohlc: &[crate::common::types::OHLC], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("WILLR", || {
+        let period = period.into_param("period")?.get();
+        if ohlc.is_empty() {
+            return Err(TAError::invalid_input("OHLC data cannot be empty"));
+        }
     
-    let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
-    let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
-    let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
+        let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
+        let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
+        let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
     
-    willr(&high, &low, &close, period)
+        willr(&high, &low, &close, period)
+    })
 }
 
 /// Calculates Williams' %R with overbought/oversold levels.
@@ -176,32 +186,34 @@ pub fn willr_ohlc(ohlc: &[crate::common::types::OHLC], period: usize) -> Result<
 /// assert_eq!(willr_vals.len(), 7);
 /// assert_eq!(signals.len(), 7);
 /// ```
-pub fn willr_levels(high: &[f64], low: &[f64], close: &[f64], period: usize, overbought: f64, oversold: f64) -> Result<(Vec<f64>, Vec<i8>), TAError> {
-    validate_hlc(high, low, close)?;
-    validate_period(period, "period")?;
+pub fn willr_levels(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, overbought: f64, oversold: f64) -> Result<(Vec<f64>, Vec<i8>), TAError> {
+    with_function("WILLR", || {
+        let period = period.into_param("period")?.get();
+        validate_hlc(high, low, close)?;
     
-    if overbought <= oversold {
-        return Err(TAError::invalid_input("Overbought level must be greater than oversold level"));
-    }
+        if overbought <= oversold {
+            return Err(TAError::invalid_input("Overbought level must be greater than oversold level"));
+        }
     
-    if overbought > 0.0 || oversold < -100.0 {
-        return Err(TAError::invalid_input("Williams %R levels must be between -100 and 0"));
-    }
+        if overbought > 0.0 || oversold < -100.0 {
+            return Err(TAError::invalid_input("Williams %R levels must be between -100 and 0"));
+        }
     
-    let willr_values = willr(high, low, close, period)?;
-    let mut signals = vec![0i8; willr_values.len()];
+        let willr_values = willr(high, low, close, period)?;
+        let mut signals = vec![0i8; willr_values.len()];
     
-    for (i, &willr_val) in willr_values.iter().enumerate() {
-        if !willr_val.is_nan() {
-            if willr_val >= overbought {
-                signals[i] = -1; // Overbought
-            } else if willr_val <= oversold {
-                signals[i] = 1; // Oversold
+        for (i, &willr_val) in willr_values.iter().enumerate() {
+            if !willr_val.is_nan() {
+                if willr_val >= overbought {
+                    signals[i] = -1; // Overbought
+                } else if willr_val <= oversold {
+                    signals[i] = 1; // Oversold
+                }
             }
         }
-    }
     
-    Ok((willr_values, signals))
+        Ok((willr_values, signals))
+    })
 }
 
 /// Calculates Williams' %R with smoothing.
@@ -232,39 +244,41 @@ pub fn willr_levels(high: &[f64], low: &[f64], close: &[f64], period: usize, ove
 /// let result = willr_smoothed(&high, &low, &close, 3, 2).unwrap();
 /// assert_eq!(result.len(), 9);
 /// ```
-pub fn willr_smoothed(high: &[f64], low: &[f64], close: &[f64], period: usize, smooth_period: usize) -> Result<Vec<f64>, TAError> {
-    validate_hlc(high, low, close)?;
-    validate_period(period, "period")?;
-    validate_period(smooth_period, "smooth_period")?;
+pub fn willr_smoothed(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, smooth_period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("WILLR", || {
+        let period = period.into_param("period")?.get();
+        let smooth_period = smooth_period.into_param("smooth_period")?.get();
+        validate_hlc(high, low, close)?;
     
-    let len = high.len();
-    if len < period + smooth_period - 1 {
-        return Err(TAError::insufficient_data(period + smooth_period - 1, len));
-    }
+        let len = high.len();
+        if len < period + smooth_period - 1 {
+            return Err(TAError::insufficient_data(period + smooth_period - 1, len));
+        }
     
-    // Calculate basic Williams' %R first
-    let willr_values = willr(high, low, close, period)?;
+        // Calculate basic Williams' %R first
+        let willr_values = willr(high, low, close, period)?;
     
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; len];
     
-    // Apply smoothing
-    for i in (period + smooth_period - 2)..len {
-        let start_idx = i - smooth_period + 1;
-        let end_idx = i + 1;
-        
-        let sum: f64 = willr_values[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .sum();
-        let count = willr_values[start_idx..end_idx].iter()
-            .filter(|&&x| !x.is_nan())
-            .count();
-        
-        if count == smooth_period {
-            result[i] = sum / smooth_period as f64;
+        // Apply smoothing
+        for (i, value) in result.iter_mut().enumerate().skip(period + smooth_period - 2) {
+            let start_idx = i - smooth_period + 1;
+            let end_idx = i + 1;
+        
+            let sum: f64 = willr_values[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .sum();
+            let count = willr_values[start_idx..end_idx].iter()
+                .filter(|&&x| !x.is_nan())
+                .count();
+        
+            if count == smooth_period {
+                *value = sum / smooth_period as f64;
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
@@ -340,8 +354,25 @@ mod tests {
         
         let result = willr(&high, &low, &close, 3).unwrap();
         
-        // When there's no range, %R should be -50 (midpoint)
-        assert!((result[2] - (-50.0)).abs() < 1e-8);
+        // When there's no range, %R is 0 as in TA-Lib
+        assert_eq!(result[2], 0.0);
+    }
+
+    #[test]
+    fn test_willr_validation_modes() {
+        let high = vec![10.0, 11.0, 12.0];
+        let low = vec![9.0, 10.0, 10.5];
+        let close = vec![9.5, 10.5, 12.1];
+
+        assert!(willr(&high, &low, &close, 3).is_err());
+        assert!(willr_with_mode(&high, &low, &close, 3, ValidationMode::Strict).is_err());
+
+        // Close above the highest high pushes %R above 0
+        let lenient = willr_with_mode(&high, &low, &close, 3, ValidationMode::Lenient).unwrap();
+        assert!((lenient[2] - 100.0 * 0.1 / 3.0).abs() < 1e-8);
+
+        let repaired = willr_with_mode(&high, &low, &close, 3, ValidationMode::Repair).unwrap();
+        assert!(repaired[2].abs() < 1e-8);
     }
 
     #[test]
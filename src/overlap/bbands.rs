@@ -4,20 +4,51 @@
 //! deviations away from the middle band. They are used to measure volatility and identify
 //! overbought/oversold conditions.
 
-use crate::common::{TAError, TAResult};
-use crate::overlap::sma;
+use crate::common::{MAType, NeumaierSum, TAError, TAResult, IntoParam, Period, with_nan_policy, NanPolicy, PolicyOutput};
+use crate::common::errors::with_function;
+use crate::common::window::RingBuffer;
+use crate::overlap::ma::{ma, MaState};
+use crate::statistic::{range_position, stddev};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Bollinger Bands result structure
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BollingerBands {
     /// Upper band values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
     pub upper: Vec<f64>,
     /// Middle band values (SMA)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
     pub middle: Vec<f64>,
     /// Lower band values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
     pub lower: Vec<f64>,
 }
 
+impl BollingerBands {
+    /// %B of `close` relative to these bands, see [`bbands_percent_b`]
+    pub fn percent_b(&self, close: &[f64]) -> TAResult<Vec<f64>> {
+        bbands_percent_b(close, self)
+    }
+
+    /// Bandwidth of these bands, see [`bbands_bandwidth`]
+    pub fn bandwidth(&self) -> TAResult<Vec<f64>> {
+        bbands_bandwidth(self)
+    }
+}
+
+impl PolicyOutput for BollingerBands {
+    fn scatter(self, bars: &[usize], len: usize) -> Self {
+        Self { upper: self.upper.scatter(bars, len), middle: self.middle.scatter(bars, len), lower: self.lower.scatter(bars, len) }
+    }
+}
+
+/// Named-output alias for [`BollingerBands`], matching [`MacdOutput`](crate::momentum::MacdOutput),
+/// [`StochOutput`](crate::momentum::StochOutput) and [`AroonOutput`](crate::momentum::AroonOutput)
+pub type BbandsOutput = BollingerBands;
+
 /// Bollinger Bands (BBANDS)
 ///
 /// Bollinger Bands are volatility bands placed above and below a moving average.
@@ -50,58 +81,106 @@ pub struct BollingerBands {
 /// // result.upper contains middle + 2 * standard deviation
 /// // result.lower contains middle - 2 * standard deviation
 /// ```
-pub fn bbands(close: &[f64], period: usize, std_dev_multiplier: f64) -> TAResult<BollingerBands> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn bbands(close: &[f64], period: impl IntoParam<Period>, std_dev_multiplier: f64) -> TAResult<BollingerBands> {
+    with_function("BBANDS", || {
+        let period = period.into_param("period")?.get();
+        bbands_ma(close, period, std_dev_multiplier, std_dev_multiplier, MAType::SMA)
+    })
+}
+
+/// Bollinger Bands of a series with missing values
+///
+/// Same as [`bbands`], with NaN in `close` handled according to `policy` rather than
+/// leaving every window that holds one NaN; see
+/// [`with_nan_policy`](crate::common::with_nan_policy).
+///
+/// # Errors
+/// Same as [`bbands`], plus `InvalidInput` if `close` holds NaN under [`NanPolicy::Error`]
+pub fn bbands_with_policy(
+    close: &[f64],
+    period: impl IntoParam<Period>,
+    std_dev_multiplier: f64,
+    policy: NanPolicy,
+) -> TAResult<BollingerBands> {
+    with_function("BBANDS", || with_nan_policy(&[close], policy, |i| bbands(i[0], period, std_dev_multiplier)))
+}
+
+/// Bollinger Bands with a selectable middle band moving average
+///
+/// Matches TA-Lib's `TA_BBANDS`: the middle band is a moving average of type `ma_type`,
+/// and the bands are offset by separate multiples of the rolling (population) standard
+/// deviation of `close` above and below it.
+///
+/// # Arguments
+/// * `close` - Slice of closing prices
+/// * `period` - Period for the moving average and standard deviation calculation
+/// * `nb_dev_up` - Number of standard deviations for the upper band
+/// * `nb_dev_dn` - Number of standard deviations for the lower band
+/// * `ma_type` - Moving average type of the middle band
+///
+/// # Returns
+/// * `Ok(BollingerBands)` - Structure containing upper, middle, and lower bands
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::MAType;
+/// use ta_rust::overlap::bbands_ma;
+///
+/// let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0];
+/// let result = bbands_ma(&close, 5, 2.0, 2.0, MAType::EMA).unwrap();
+/// let bandwidth = result.bandwidth().unwrap();
+/// ```
+pub fn bbands_ma(
+    close: &[f64],
+    period: impl IntoParam<Period>,
+    nb_dev_up: f64,
+    nb_dev_dn: f64,
+    ma_type: MAType,
+) -> TAResult<BollingerBands> {
+    with_function("BBANDS", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    if period > close.len() {
-        return Err(TAError::insufficient_data(period, close.len()));
-    }
+        if period > close.len() {
+            return Err(TAError::insufficient_data(period, close.len()));
+        }
     
-    if std_dev_multiplier < 0.0 {
-        return Err(TAError::invalid_parameter("std_dev_multiplier", "must be non-negative"));
-    }
+        if nb_dev_up < 0.0 || nb_dev_dn < 0.0 {
+            return Err(TAError::invalid_parameter("std_dev_multiplier", "must be non-negative"));
+        }
     
-    let len = close.len();
+        // Middle band and rolling standard deviation of the closes
+        let middle = ma(close, period, ma_type)?;
+        let std_dev = stddev(close, period, 1.0)?;
     
-    // Calculate middle band (SMA)
-    let middle = sma(close, period)?;
+        Ok(bands_from(middle, &std_dev, nb_dev_up, nb_dev_dn))
+    })
+}
+
+/// Bollinger Bands from a precomputed middle band and standard deviation
+pub(crate) fn bands_from(middle: Vec<f64>, std_dev: &[f64], nb_dev_up: f64, nb_dev_dn: f64) -> BollingerBands {
+    let len = middle.len();
     
     // Initialize result vectors
     let mut upper = vec![f64::NAN; len];
     let mut lower = vec![f64::NAN; len];
     
-    // Calculate standard deviation and bands for each valid point
-    for i in (period - 1)..len {
-        if !middle[i].is_nan() {
-            // Calculate standard deviation for the current window
-            let start_idx = i + 1 - period;
-            let window = &close[start_idx..=i];
-            let mean = middle[i];
-            
-            // Calculate variance
-            let variance = window.iter()
-                .map(|&x| (x - mean).powi(2))
-                .sum::<f64>() / period as f64;
-            
-            let std_dev = variance.sqrt();
-            
-            // Calculate bands
-            upper[i] = mean + std_dev_multiplier * std_dev;
-            lower[i] = mean - std_dev_multiplier * std_dev;
+    // Bands are defined wherever both the middle band and the deviation are
+    for (i, (&mid, &sd)) in middle.iter().zip(std_dev).enumerate() {
+        if !mid.is_nan() && !sd.is_nan() {
+            upper[i] = mid + nb_dev_up * sd;
+            lower[i] = mid - nb_dev_dn * sd;
         }
     }
     
-    Ok(BollingerBands {
+    BollingerBands {
         upper,
         middle,
         lower,
-    })
+    }
 }
 
 /// Bollinger Bands with default parameters (20 period, 2.0 standard deviations)
@@ -131,30 +210,29 @@ pub fn bbands_default(close: &[f64]) -> TAResult<BollingerBands> {
 /// * `Ok(Vec<f64>)` - Vector of %B values
 /// * `Err(TAError)` - Error if inputs are invalid
 pub fn bbands_percent_b(close: &[f64], bands: &BollingerBands) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
+    with_function("BBANDS", || {
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    if close.len() != bands.upper.len() || close.len() != bands.lower.len() {
-        return Err(TAError::mismatched_inputs("Close prices and bands must have the same length"));
-    }
+        if close.len() != bands.upper.len() || close.len() != bands.lower.len() {
+            return Err(TAError::mismatched_inputs("Close prices and bands must have the same length"));
+        }
     
-    let len = close.len();
-    let mut result = vec![f64::NAN; len];
+        let len = close.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in 0..len {
-        if !bands.upper[i].is_nan() && !bands.lower[i].is_nan() {
-            let band_width = bands.upper[i] - bands.lower[i];
-            if band_width.abs() > f64::EPSILON {
-                result[i] = (close[i] - bands.lower[i]) / band_width;
+        for i in 0..len {
+            if !bands.upper[i].is_nan() && !bands.lower[i].is_nan() {
+                result[i] = range_position(close[i], bands.lower[i], bands.upper[i]).unwrap_or(f64::NAN);
             }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
-/// Calculate Bollinger Band Width
+/// Calculate Bollinger Bandwidth
 ///
 /// Band Width measures the width of the bands relative to the middle band.
 /// Width = (Upper Band - Lower Band) / Middle Band
@@ -165,25 +243,160 @@ pub fn bbands_percent_b(close: &[f64], bands: &BollingerBands) -> TAResult<Vec<f
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of band width values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn bbands_width(bands: &BollingerBands) -> TAResult<Vec<f64>> {
-    let len = bands.upper.len();
-    let mut result = vec![f64::NAN; len];
+pub fn bbands_bandwidth(bands: &BollingerBands) -> TAResult<Vec<f64>> {
+    with_function("BBANDS", || {
+        let len = bands.upper.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in 0..len {
-        if !bands.upper[i].is_nan() && !bands.lower[i].is_nan() && !bands.middle[i].is_nan() {
-            if bands.middle[i].abs() > f64::EPSILON {
-                result[i] = (bands.upper[i] - bands.lower[i]) / bands.middle[i];
+        for (i, value) in result.iter_mut().enumerate() {
+            if !bands.upper[i].is_nan()
+                && !bands.lower[i].is_nan()
+                && !bands.middle[i].is_nan()
+                && bands.middle[i].abs() > f64::EPSILON
+            {
+                *value = (bands.upper[i] - bands.lower[i]) / bands.middle[i];
             }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
+}
+
+/// Calculate Bollinger Band Width
+///
+/// Same as [`bbands_bandwidth`], kept for compatibility.
+pub fn bbands_width(bands: &BollingerBands) -> TAResult<Vec<f64>> {
+    with_function("BBANDS", || {
+        bbands_bandwidth(bands)
+    })
+}
+
+/// Streaming Bollinger Bands
+///
+/// Produces the same values as [`bbands_ma`] one close at a time, in O(1) time per update
+/// and O(period) memory. The deviation comes from running sums of the closes and their
+/// squares, taken relative to the first close so the two terms of the variance do not cancel.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{bbands, BbandsState};
+///
+/// let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0];
+/// let batch = bbands(&close, 5, 2.0).unwrap();
+///
+/// let mut state = BbandsState::new(5, 2.0).unwrap();
+/// for (i, &price) in close.iter().enumerate() {
+///     match state.update(price) {
+///         Some((upper, middle, lower)) => {
+///             assert!((upper - batch.upper[i]).abs() < 1e-9);
+///             assert!((middle - batch.middle[i]).abs() < 1e-9);
+///             assert!((lower - batch.lower[i]).abs() < 1e-9);
+///         }
+///         None => assert!(batch.middle[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BbandsState {
+    nb_dev_up: f64,
+    nb_dev_dn: f64,
+    middle: MaState,
+    /// Closes of the current window, relative to `shift`
+    window: RingBuffer<f64>,
+    shift: Option<f64>,
+    sum: NeumaierSum,
+    sum_sq: NeumaierSum,
+    value: Option<(f64, f64, f64)>,
+}
+
+impl BbandsState {
+    /// Creates a new state with an SMA middle band and symmetric bands, like [`bbands`]
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0 or the multiplier is negative
+    pub fn new(period: impl IntoParam<Period>, std_dev_multiplier: f64) -> TAResult<Self> {
+        with_function("BBANDS", || {
+            let period = period.into_param("period")?.get();
+            Self::with_ma(period, std_dev_multiplier, std_dev_multiplier, MAType::SMA)
+        })
+    }
+
+    /// Creates a new state with the given middle band type and deviations, like [`bbands_ma`]
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0 or either deviation is negative
+    /// - `UnsupportedOperation` if `ma_type` has no streaming form
+    pub fn with_ma(period: impl IntoParam<Period>, nb_dev_up: f64, nb_dev_dn: f64, ma_type: MAType) -> TAResult<Self> {
+        with_function("BBANDS", || {
+            let period = period.into_param("period")?.get();
+            if nb_dev_up < 0.0 || nb_dev_dn < 0.0 {
+                return Err(TAError::invalid_parameter("std_dev_multiplier", "must be non-negative"));
+            }
+
+            Ok(Self {
+                nb_dev_up,
+                nb_dev_dn,
+                middle: MaState::new(period, ma_type)?,
+                window: RingBuffer::new(period),
+                shift: None,
+                sum: NeumaierSum::new(),
+                sum_sq: NeumaierSum::new(),
+                value: None,
+            })
+        })
+    }
+
+    /// Feeds the next close and returns `(upper, middle, lower)`, or `None` during warm-up
+    pub fn update(&mut self, close: f64) -> Option<(f64, f64, f64)> {
+        let middle = self.middle.update(close);
+
+        let x = close - *self.shift.get_or_insert(close);
+        self.sum.add(x);
+        self.sum_sq.add(x * x);
+        if let Some(evicted) = self.window.push(x) {
+            self.sum.sub(evicted);
+            self.sum_sq.sub(evicted * evicted);
+        }
+
+        self.value = match middle {
+            Some(middle) if self.window.is_full() => {
+                let n = self.window.len() as f64;
+                let mean = self.sum.value() / n;
+                let std_dev = (self.sum_sq.value() / n - mean * mean).max(0.0).sqrt();
+                Some((middle + self.nb_dev_up * std_dev, middle, middle - self.nb_dev_dn * std_dev))
+            }
+            _ => None,
+        };
+        self.value
+    }
+
+    /// Current `(upper, middle, lower)`, or `None` during warm-up
+    pub fn current(&self) -> Option<(f64, f64, f64)> {
+        self.value
+    }
+
+    /// Period of the bands
+    pub fn period(&self) -> usize {
+        self.middle.period()
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.middle.reset();
+        self.window.clear();
+        self.shift = None;
+        self.sum = NeumaierSum::new();
+        self.sum_sq = NeumaierSum::new();
+        self.value = None;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::assert_float_eq;
+    use crate::common::assert_arrays_approx_equal;
     #[test]
     fn test_bbands_basic() {
         let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0];
@@ -292,6 +505,18 @@ mod tests {
         assert!(bbands(&close, 2, -1.0).is_err()); // Negative multiplier
     }
 
+    #[test]
+    fn test_bbands_error_names_function() {
+        // Direct calls name the function called, not the SMA the bands are built on
+        let close = vec![20.0, 21.0, 22.0];
+        let err = bbands(&close, 5, 2.0).unwrap_err();
+        assert_eq!(err.function(), Some("BBANDS"));
+        assert_eq!(err.to_string(), "BBANDS: Insufficient data: need at least 5 data points, got 3");
+        assert_eq!(bbands(&close, 0, 2.0).unwrap_err().parameter(), Some("period"));
+        assert_eq!(bbands(&close, 0, 2.0).unwrap_err().function(), Some("BBANDS"));
+        assert_eq!(crate::overlap::sma(&close, 5).unwrap_err().function(), Some("SMA"));
+    }
+
     #[test]
     fn test_bbands_single_period() {
         let close = vec![20.0, 21.0, 22.0];
@@ -304,4 +529,66 @@ mod tests {
             assert_float_eq!(result.lower[i], close[i], 1e-10);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bbands_ma_types() {
+        let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+
+        // SMA middle band matches bbands
+        let sma_bands = bbands_ma(&close, 10, 2.0, 2.0, MAType::SMA).unwrap();
+        let bands = bbands(&close, 10, 2.0).unwrap();
+        assert_arrays_approx_equal(&sma_bands.upper, &bands.upper, 1e-10);
+
+        // EMA middle band, asymmetric deviations
+        let ema_bands = bbands_ma(&close, 10, 2.0, 1.0, MAType::EMA).unwrap();
+        let middle = crate::overlap::ema(&close, 10).unwrap();
+        let sd = stddev(&close, 10, 1.0).unwrap();
+        assert_arrays_approx_equal(&ema_bands.middle, &middle, 1e-10);
+        for (i, &sd) in sd.iter().enumerate().skip(9) {
+            assert_float_eq!(ema_bands.upper[i] - ema_bands.middle[i], 2.0 * sd, 1e-10);
+            assert_float_eq!(ema_bands.middle[i] - ema_bands.lower[i], sd, 1e-10);
+        }
+
+        assert!(bbands_ma(&close, 10, 2.0, -1.0, MAType::EMA).is_err());
+    }
+
+    #[test]
+    fn test_bbands_bandwidth_and_percent_b_methods() {
+        let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0];
+        let bands = bbands(&close, 5, 2.0).unwrap();
+
+        let bandwidth = bands.bandwidth().unwrap();
+        assert_arrays_approx_equal(&bandwidth, &bbands_width(&bands).unwrap(), 0.0);
+        let i = 6;
+        assert_float_eq!(bandwidth[i], (bands.upper[i] - bands.lower[i]) / bands.middle[i], 1e-12);
+
+        let percent_b = bands.percent_b(&close).unwrap();
+        assert_float_eq!(percent_b[i], (close[i] - bands.lower[i]) / (bands.upper[i] - bands.lower[i]), 1e-12);
+    }
+
+    #[test]
+    fn test_bbands_state_matches_batch() {
+        let close: Vec<f64> = (0..120).map(|i| 5000.0 + (i as f64 * 0.3).sin() * 40.0 + i as f64).collect();
+
+        for (ma_type, up, dn) in [(MAType::SMA, 2.0, 2.0), (MAType::EMA, 2.0, 1.5), (MAType::WMA, 1.0, 3.0)] {
+            let batch = bbands_ma(&close, 20, up, dn, ma_type).unwrap();
+            let mut state = BbandsState::with_ma(20, up, dn, ma_type).unwrap();
+            for (i, &price) in close.iter().enumerate() {
+                match state.update(price) {
+                    Some((upper, middle, lower)) => {
+                        assert_float_eq!(upper, batch.upper[i], 1e-8);
+                        assert_float_eq!(middle, batch.middle[i], 1e-8);
+                        assert_float_eq!(lower, batch.lower[i], 1e-8);
+                    }
+                    None => assert!(batch.upper[i].is_nan(), "{} at {}", ma_type, i),
+                }
+            }
+            assert!(state.current().is_some());
+            state.reset();
+            assert_eq!(state, BbandsState::with_ma(20, up, dn, ma_type).unwrap());
+        }
+
+        assert!(BbandsState::new(0, 2.0).is_err());
+        assert!(BbandsState::new(20, -1.0).is_err());
+    }
+}
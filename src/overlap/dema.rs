@@ -1,8 +1,11 @@
 //! Double Exponential Moving Average (DEMA)
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
 use crate::overlap::ema::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Double Exponential Moving Average (DEMA)
 ///
@@ -39,95 +42,99 @@ use crate::overlap::ema::ema;
 /// assert!(result[3].is_nan());
 /// assert!(!result[4].is_nan());
 /// ```
-pub fn dema(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
+pub fn dema(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("DEMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
     
-    // DEMA requires at least 2×period-1 data points
-    let min_required = 2 * period - 1;
-    validate_sufficient_data(data, min_required, "data")?;
+        // DEMA requires at least 2×period-1 data points
+        let min_required = 2 * period - 1;
+        validate_sufficient_data(data, min_required, "data")?;
 
-    // Calculate first EMA
-    let ema1 = ema(data, period)?;
+        // Calculate first EMA
+        let ema1 = ema(data, period)?;
     
-    // Extract non-NaN values from EMA1 for second EMA calculation
-    let ema1_valid: Vec<Price> = ema1.iter()
-        .skip(period - 1)
-        .copied()
-        .collect();
+        // Extract non-NaN values from EMA1 for second EMA calculation
+        let ema1_valid: Vec<Price> = ema1.iter()
+            .skip(period - 1)
+            .copied()
+            .collect();
     
-    // Calculate second EMA
-    let ema2_partial = ema(&ema1_valid, period)?;
+        // Calculate second EMA
+        let ema2_partial = ema(&ema1_valid, period)?;
     
-    // Combine results
-    let mut output = allocate_output(data.len());
+        // Combine results
+        let mut output = allocate_output(data.len());
     
-    // Calculate DEMA values
-    let start_index = 2 * period - 2;
-    for i in start_index..data.len() {
-        let ema1_val = ema1[i];
-        let ema2_index = i - (period - 1);
-        if ema2_index < ema2_partial.len() && !ema2_partial[ema2_index].is_nan() {
-            let ema2_val = ema2_partial[ema2_index];
-            output[i] = 2.0 * ema1_val - ema2_val;
+        // Calculate DEMA values
+        let start_index = 2 * period - 2;
+        for i in start_index..data.len() {
+            let ema1_val = ema1[i];
+            let ema2_index = i - (period - 1);
+            if ema2_index < ema2_partial.len() && !ema2_partial[ema2_index].is_nan() {
+                let ema2_val = ema2_partial[ema2_index];
+                output[i] = 2.0 * ema1_val - ema2_val;
+            }
         }
-    }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates DEMA using a more direct approach
 ///
 /// This version calculates both EMAs in a single pass for better performance.
-pub fn dema_direct(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
+pub fn dema_direct(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("DEMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
     
-    let min_required = 2 * period - 1;
-    validate_sufficient_data(data, min_required, "data")?;
+        let min_required = 2 * period - 1;
+        validate_sufficient_data(data, min_required, "data")?;
 
-    let mut output = allocate_output(data.len());
-    let multiplier = 2.0 / (period as Price + 1.0);
+        let mut output = allocate_output(data.len());
+        let multiplier = 2.0 / (period as Price + 1.0);
     
-    // Initialize first EMA with SMA of first 'period' values
-    let initial_sum: Price = data[0..period].iter().sum();
-    let mut ema1 = initial_sum / period as Price;
+        // Initialize first EMA with SMA of first 'period' values
+        let initial_sum: Price = data[0..period].iter().sum();
+        let mut ema1 = initial_sum / period as Price;
     
-    // Track EMA1 values for EMA2 calculation
-    let mut ema1_values = Vec::with_capacity(data.len());
+        // Track EMA1 values for EMA2 calculation
+        let mut ema1_values = Vec::with_capacity(data.len());
     
-    // Calculate EMA1 for all values
-    for i in 0..data.len() {
-        if i >= period - 1 {
-            if i > period - 1 {
-                ema1 = (data[i] * multiplier) + (ema1 * (1.0 - multiplier));
+        // Calculate EMA1 for all values
+        for (i, &price) in data.iter().enumerate() {
+            if i >= period - 1 {
+                if i > period - 1 {
+                    ema1 = (price * multiplier) + (ema1 * (1.0 - multiplier));
+                }
+                ema1_values.push(ema1);
             }
-            ema1_values.push(ema1);
         }
-    }
     
-    // Calculate EMA2 from EMA1 values
-    if ema1_values.len() >= period {
-        let initial_sum2: Price = ema1_values[0..period].iter().sum();
-        let mut ema2 = initial_sum2 / period as Price;
+        // Calculate EMA2 from EMA1 values
+        if ema1_values.len() >= period {
+            let initial_sum2: Price = ema1_values[0..period].iter().sum();
+            let mut ema2 = initial_sum2 / period as Price;
         
-        // Calculate DEMA values
-        let start_index = 2 * period - 2;
-        for i in start_index..data.len() {
-            let ema1_val = ema1_values[i - (period - 1)];
+            // Calculate DEMA values
+            let start_index = 2 * period - 2;
+            for i in start_index..data.len() {
+                let ema1_val = ema1_values[i - (period - 1)];
             
-            if i > start_index {
-                let ema1_for_ema2 = ema1_values[i - (period - 1)];
-                ema2 = (ema1_for_ema2 * multiplier) + (ema2 * (1.0 - multiplier));
-            }
+                if i > start_index {
+                    let ema1_for_ema2 = ema1_values[i - (period - 1)];
+                    ema2 = (ema1_for_ema2 * multiplier) + (ema2 * (1.0 - multiplier));
+                }
             
-            output[i] = 2.0 * ema1_val - ema2;
+                output[i] = 2.0 * ema1_val - ema2;
+            }
         }
-    }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -0,0 +1,138 @@
+//! Donchian Channels
+//!
+//! Donchian Channels track the highest high and lowest low over a rolling window, with the
+//! middle line halfway between them. They are the basis of turtle-style breakout systems.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::{max, min};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Donchian Channels result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DonchianChannels {
+    /// Upper channel (highest high over the period)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub upper: Vec<f64>,
+    /// Middle line ((upper + lower) / 2)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub middle: Vec<f64>,
+    /// Lower channel (lowest low over the period)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub lower: Vec<f64>,
+}
+
+impl DonchianChannels {
+    /// Channel breakouts of `close`
+    ///
+    /// Compares each close with the channel of the *previous* bar, since the current bar's
+    /// channel always contains its own high and low. Returns 1 where the close breaks above
+    /// the prior upper channel, -1 where it breaks below the prior lower channel, and 0
+    /// otherwise.
+    pub fn breakouts(&self, close: &[f64]) -> TAResult<Vec<i8>> {
+        if close.len() != self.upper.len() {
+            return Err(TAError::mismatched_inputs("Close prices and channels must have the same length"));
+        }
+
+        let mut signals = vec![0i8; close.len()];
+        for (i, signal) in signals.iter_mut().enumerate().skip(1) {
+            if close[i] > self.upper[i - 1] {
+                *signal = 1;
+            } else if close[i] < self.lower[i - 1] {
+                *signal = -1;
+            }
+        }
+        Ok(signals)
+    }
+}
+
+/// Donchian Channels
+///
+/// # Formula
+/// ```text
+/// Upper  = Highest(High, period)
+/// Lower  = Lowest(Low, period)
+/// Middle = (Upper + Lower) / 2
+/// ```
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `period` - Lookback period (20 and 55 are the classic turtle settings)
+///
+/// # Returns
+/// * `Ok(DonchianChannels)` - Structure containing upper, middle, and lower channels;
+///   the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::donchian;
+///
+/// let high = vec![10.0, 11.0, 12.0, 11.0, 10.0];
+/// let low = vec![9.0, 10.0, 11.0, 10.0, 8.0];
+/// let result = donchian(&high, &low, 3).unwrap();
+/// assert_eq!(result.upper[4], 12.0);
+/// assert_eq!(result.lower[4], 8.0);
+/// assert_eq!(result.middle[4], 10.0);
+/// ```
+pub fn donchian(high: &[f64], low: &[f64], period: impl IntoParam<Period>) -> TAResult<DonchianChannels> {
+    with_function("DONCHIAN", || {
+        let period = period.into_param("period")?.get();
+        if high.len() != low.len() {
+            return Err(TAError::mismatched_inputs("High and Low arrays must have the same length"));
+        }
+
+        let upper = max(high, period)?;
+        let lower = min(low, period)?;
+        let middle = upper.iter().zip(&lower).map(|(&up, &lo)| (up + lo) / 2.0).collect();
+
+        Ok(DonchianChannels {
+            upper,
+            middle,
+            lower,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_donchian_basic() {
+        let high = vec![10.0, 11.0, 12.0, 11.0, 10.0, 9.5];
+        let low = vec![9.0, 10.0, 11.0, 10.0, 8.0, 8.5];
+        let result = donchian(&high, &low, 3).unwrap();
+
+        assert!(result.upper[1].is_nan());
+        assert!(result.middle[1].is_nan());
+        assert!(result.lower[1].is_nan());
+
+        assert_eq!(result.upper[2..], [12.0, 12.0, 12.0, 11.0]);
+        assert_eq!(result.lower[2..], [9.0, 10.0, 8.0, 8.0]);
+        assert_eq!(result.middle[5], 9.5);
+    }
+
+    #[test]
+    fn test_donchian_breakouts() {
+        let high = vec![10.0, 10.5, 10.2, 11.5, 10.8, 9.0];
+        let low = vec![9.0, 9.5, 9.4, 10.0, 9.9, 8.0];
+        let close = vec![9.5, 10.0, 9.8, 11.2, 10.5, 8.2];
+        let channels = donchian(&high, &low, 3).unwrap();
+
+        // Bar 3 closes above the prior 3-bar high (10.5); bar 5 below the prior low (9.4)
+        assert_eq!(channels.breakouts(&close).unwrap(), vec![0, 0, 0, 1, 0, -1]);
+        assert!(channels.breakouts(&close[..4]).is_err());
+    }
+
+    #[test]
+    fn test_donchian_invalid_input() {
+        assert!(donchian(&[], &[], 3).is_err());
+        assert!(donchian(&[1.0, 2.0], &[1.0], 1).is_err());
+        assert!(donchian(&[1.0, 2.0], &[1.0, 2.0], 0).is_err());
+        assert!(donchian(&[1.0, 2.0], &[1.0, 2.0], 3).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Ehlers Digital Filters
+//!
+//! John Ehlers' recursive filters for separating trend from cycles, the usual companions
+//! to MAMA and the Hilbert Transform cycle measurements. Each is specified by a cutoff
+//! period in bars: a low-pass filter keeps the swings longer than it, a high-pass filter
+//! keeps the shorter ones.
+//!
+//! - [`super_smoother`]: two-pole low-pass filter, smooth with little lag
+//! - [`high_pass`]: two-pole high-pass filter, the series with its trend removed
+//! - [`decycler`]: the trend left after removing the cycles shorter than the cutoff
+//! - [`roofing_filter`]: high-pass then Super Smoother, keeping a band of cycle periods
+//! - [`bandpass`]: narrow band-pass filter around a single cycle period
+//!
+//! The filters are recursive and defined from the first bar, following Ehlers' code, but
+//! take a few cutoff periods to settle. Leading NaNs, such as the warm-up of an indicator
+//! being filtered, are skipped; a NaN after the start propagates to all later values.
+
+use core::f64::consts::PI;
+
+use crate::common::{validate_not_empty, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Applies Ehlers' two-pole Super Smoother filter
+///
+/// # Formula
+/// ```text
+/// a  = exp(-√2 π / period),   c2 = 2a cos(√2 π / period),   c3 = -a²,   c1 = 1 - c2 - c3
+/// SS = c1 (x + x[1]) / 2 + c2 SS[1] + c3 SS[2]
+/// ```
+/// The first two values are the input.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `period` - Cutoff period in bars (at least 2)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Filtered series
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::super_smoother;
+///
+/// let prices: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 * 2.5).sin()).collect();
+/// let smooth = super_smoother(&prices, 10).unwrap();
+/// // The 2.5-bar wiggle is far below the cutoff and is nearly removed
+/// assert!(smooth[30..].iter().all(|v| (v - 100.0).abs() < 0.1));
+/// ```
+pub fn super_smoother(series: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("SUPERSMOOTHER", || {
+        let period = cutoff(period, "period")?;
+        validate_not_empty(series, "series")?;
+        Ok(filtered(series, |x, y| super_smoother_into(x, period, y)))
+    })
+}
+
+/// Applies Ehlers' two-pole high-pass filter
+///
+/// # Formula
+/// ```text
+/// α  = (cos(0.707 × 2π / period) + sin(0.707 × 2π / period) - 1) / cos(0.707 × 2π / period)
+/// HP = (1 - α/2)² (x - 2x[1] + x[2]) + 2(1 - α) HP[1] - (1 - α)² HP[2]
+/// ```
+/// The first two values are 0.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `period` - Cutoff period in bars (at least 2); longer cycles and trends are removed
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Filtered series, oscillating around 0
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::high_pass;
+///
+/// // A steady trend has no cycles shorter than the cutoff
+/// let trend: Vec<f64> = (0..200).map(|i| 50.0 + 0.3 * i as f64).collect();
+/// let hp = high_pass(&trend, 48).unwrap();
+/// assert!(hp[199].abs() < 1e-3);
+/// ```
+pub fn high_pass(series: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("HIGHPASS", || {
+        let period = cutoff(period, "period")?;
+        validate_not_empty(series, "series")?;
+        Ok(filtered(series, |x, y| high_pass_into(x, period, y)))
+    })
+}
+
+/// Applies Ehlers' Decycler
+///
+/// The series minus its cycles shorter than `period`: a trend line with very little lag,
+/// computed with a one-pole high-pass filter.
+///
+/// # Formula
+/// ```text
+/// α        = (cos(2π / period) + sin(2π / period) - 1) / cos(2π / period)
+/// Decycle  = α/2 (x + x[1]) + (1 - α) Decycle[1]
+/// ```
+/// The first value is the input.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `period` - Cutoff period in bars (at least 5)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Filtered series
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::decycler;
+///
+/// // A 4-bar cycle around 100
+/// let prices: Vec<f64> = (0..200).map(|i| 100.0 + [0.0, 1.0, 0.0, -1.0][i % 4]).collect();
+/// let trend = decycler(&prices, 20).unwrap();
+/// assert!(trend[100..].iter().all(|v| (v - 100.0).abs() < 0.2));
+/// ```
+pub fn decycler(series: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("DECYCLER", || {
+        let period = period.into_param("period")?.get();
+        // Below 5 bars cos(2π / period) is too small or negative for a stable filter
+        if period < 5 {
+            return Err(TAError::invalid_parameter("period", "must be at least 5"));
+        }
+        validate_not_empty(series, "series")?;
+
+        let angle = 2.0 * PI / period as f64;
+        let alpha = (angle.cos() + angle.sin() - 1.0) / angle.cos();
+        Ok(filtered(series, |x, y| {
+            y[0] = x[0];
+            for i in 1..x.len() {
+                y[i] = alpha / 2.0 * (x[i] + x[i - 1]) + (1.0 - alpha) * y[i - 1];
+            }
+        }))
+    })
+}
+
+/// Applies Ehlers' Roofing filter
+///
+/// A high-pass filter removing the trend and cycles longer than `hp_period`, followed by a
+/// Super Smoother removing the noise shorter than `ss_period`. What remains are the
+/// cycles between the two, ready for oscillators and cycle measurements.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `hp_period` - Longest cycle kept, in bars (Ehlers uses 48)
+/// * `ss_period` - Shortest cycle kept, in bars (Ehlers uses 10)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Filtered series, oscillating around 0
+/// * `Err(TAError)` - Error if inputs are invalid or `ss_period >= hp_period`
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::roofing_filter;
+///
+/// // Trend, a 20-bar cycle and 3-bar noise
+/// let prices: Vec<f64> = (0..300)
+///     .map(|i| {
+///         let t = i as f64;
+///         100.0 + 0.2 * t + (t * 2.0 * std::f64::consts::PI / 20.0).sin() + 0.5 * (t * 2.1).sin()
+///     })
+///     .collect();
+/// let roof = roofing_filter(&prices, 48, 10).unwrap();
+/// // Only the cycle is left, swinging around 0
+/// let recent = &roof[240..]; // three cycles
+/// assert!(recent.iter().cloned().fold(f64::MIN, f64::max) > 0.5);
+/// assert!(recent.iter().cloned().fold(f64::MAX, f64::min) < -0.5);
+/// assert!(recent.iter().sum::<f64>().abs() / 60.0 < 0.1);
+/// ```
+pub fn roofing_filter(
+    series: &[f64],
+    hp_period: impl IntoParam<Period>,
+    ss_period: impl IntoParam<Period>,
+) -> TAResult<Vec<f64>> {
+    with_function("ROOFING", || {
+        let hp_period = cutoff(hp_period, "hp_period")?;
+        let ss_period = cutoff(ss_period, "ss_period")?;
+        if ss_period >= hp_period {
+            return Err(TAError::invalid_parameter("ss_period", "must be less than hp_period"));
+        }
+        validate_not_empty(series, "series")?;
+
+        Ok(filtered(series, |x, y| {
+            let mut hp = vec![0.0; x.len()];
+            high_pass_into(x, hp_period, &mut hp);
+            super_smoother_into(&hp, ss_period, y);
+        }))
+    })
+}
+
+/// Applies Ehlers' band-pass filter
+///
+/// Keeps the cycles near `period` bars and removes longer and shorter ones. The width of
+/// the pass band is `bandwidth` as a fraction of the period, e.g. 0.3 for ±15%.
+///
+/// # Formula
+/// ```text
+/// β  = cos(2π / period),   γ = 1 / cos(bandwidth × 2π / period),   σ = γ - √(γ² - 1)
+/// BP = (1 - σ)/2 (x - x[2]) + β(1 + σ) BP[1] - σ BP[2]
+/// ```
+/// The first two values are 0.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `period` - Center period of the band, in bars (at least 2)
+/// * `bandwidth` - Width of the band relative to the period, in (0, 1)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Filtered series, oscillating around 0
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::bandpass;
+///
+/// let cycle = |p: f64| -> Vec<f64> { (0..400).map(|i| (i as f64 * 2.0 * std::f64::consts::PI / p).sin()).collect() };
+/// let amplitude = |v: Vec<f64>| v[300..].iter().fold(0.0f64, |m, x| m.max(x.abs()));
+///
+/// // A cycle at the center period passes, one far from it does not
+/// assert!(amplitude(bandpass(&cycle(20.0), 20, 0.3).unwrap()) > 0.9);
+/// assert!(amplitude(bandpass(&cycle(60.0), 20, 0.3).unwrap()) < 0.2);
+/// ```
+pub fn bandpass(series: &[f64], period: impl IntoParam<Period>, bandwidth: f64) -> TAResult<Vec<f64>> {
+    with_function("BANDPASS", || {
+        let period = cutoff(period, "period")?;
+        if !(bandwidth > 0.0 && bandwidth < 1.0) {
+            return Err(TAError::invalid_parameter("bandwidth", "must be between 0 and 1 (exclusive)"));
+        }
+        let band_angle = bandwidth * 2.0 * PI / period;
+        if band_angle >= PI / 2.0 {
+            return Err(TAError::invalid_parameter("bandwidth", "must be less than a quarter of the period"));
+        }
+        validate_not_empty(series, "series")?;
+
+        let beta = (2.0 * PI / period).cos();
+        let gamma = 1.0 / band_angle.cos();
+        let sigma = gamma - (gamma * gamma - 1.0).sqrt();
+        Ok(filtered(series, |x, y| {
+            for i in 2..x.len() {
+                y[i] = 0.5 * (1.0 - sigma) * (x[i] - x[i - 2]) + beta * (1.0 + sigma) * y[i - 1] - sigma * y[i - 2];
+            }
+        }))
+    })
+}
+
+/// Validates a cutoff period of at least 2 bars
+fn cutoff(period: impl IntoParam<Period>, name: &str) -> TAResult<f64> {
+    let period = period.into_param(name)?.get();
+    if period < 2 {
+        return Err(TAError::invalid_parameter(name, "must be at least 2"));
+    }
+    Ok(period as f64)
+}
+
+/// Runs `filter` on the series after its leading NaNs, with a zeroed output
+fn filtered(series: &[f64], filter: impl FnOnce(&[f64], &mut [f64])) -> Vec<f64> {
+    let start = series.iter().take_while(|v| v.is_nan()).count();
+    let mut output = vec![f64::NAN; series.len()];
+    if start < series.len() {
+        output[start..].fill(0.0);
+        filter(&series[start..], &mut output[start..]);
+    }
+    output
+}
+
+fn super_smoother_into(x: &[f64], period: f64, y: &mut [f64]) {
+    let angle = core::f64::consts::SQRT_2 * PI / period;
+    let a = (-angle).exp();
+    let c2 = 2.0 * a * angle.cos();
+    let c3 = -a * a;
+    let c1 = 1.0 - c2 - c3;
+
+    for i in 0..x.len() {
+        y[i] = if i < 2 {
+            x[i]
+        } else {
+            c1 * (x[i] + x[i - 1]) / 2.0 + c2 * y[i - 1] + c3 * y[i - 2]
+        };
+    }
+}
+
+fn high_pass_into(x: &[f64], period: f64, y: &mut [f64]) {
+    let angle = 0.707 * 2.0 * PI / period;
+    let alpha = (angle.cos() + angle.sin() - 1.0) / angle.cos();
+    let gain = (1.0 - alpha / 2.0) * (1.0 - alpha / 2.0);
+
+    for i in 2..x.len() {
+        y[i] = gain * (x[i] - 2.0 * x[i - 1] + x[i - 2]) + 2.0 * (1.0 - alpha) * y[i - 1]
+            - (1.0 - alpha) * (1.0 - alpha) * y[i - 2];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    fn wave(period: f64, len: usize) -> Vec<f64> {
+        (0..len).map(|i| (i as f64 * 2.0 * PI / period).sin()).collect()
+    }
+
+    fn amplitude(values: &[f64]) -> f64 {
+        values.iter().fold(0.0, |m, v| m.max(v.abs()))
+    }
+
+    #[test]
+    fn test_low_and_high_pass_split_cycles() {
+        let constant = vec![7.0; 100];
+        assert!(super_smoother(&constant, 10).unwrap().iter().all(|&v| (v - 7.0).abs() < 1e-12));
+        assert!(decycler(&constant, 20).unwrap().iter().all(|&v| (v - 7.0).abs() < 1e-12));
+        assert!(high_pass(&constant, 20).unwrap().iter().all(|&v| v.abs() < 1e-12));
+
+        // A cycle well above the cutoff passes the low-pass filter, one well below it the high-pass
+        let long = wave(80.0, 600);
+        let short = wave(6.0, 600);
+        assert!(amplitude(&super_smoother(&long, 10).unwrap()[400..]) > 0.9);
+        assert!(amplitude(&super_smoother(&short, 20).unwrap()[400..]) < 0.1);
+        assert!(amplitude(&high_pass(&short, 48).unwrap()[400..]) > 0.9);
+        assert!(amplitude(&high_pass(&long, 10).unwrap()[400..]) < 0.1);
+
+        // The Decycler's one pole rolls off more gently
+        assert!(amplitude(&decycler(&long, 20).unwrap()[400..]) > 0.9);
+        assert!(amplitude(&decycler(&wave(4.0, 600), 20).unwrap()[400..]) < 0.2);
+    }
+
+    #[test]
+    fn test_roofing_and_bandpass() {
+        let prices: Vec<f64> = (0..500).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let roof = roofing_filter(&prices, 48, 10).unwrap();
+        assert_float_eq!(roof[499], 0.0, 1e-3);
+
+        let cycle = wave(20.0, 500);
+        assert!(amplitude(&roofing_filter(&cycle, 48, 10).unwrap()[400..]) > 0.7);
+        assert!(amplitude(&bandpass(&cycle, 20, 0.3).unwrap()[400..]) > 0.9);
+        assert!(amplitude(&bandpass(&wave(5.0, 500), 20, 0.3).unwrap()[400..]) < 0.2);
+    }
+
+    #[test]
+    fn test_ehlers_nan_and_errors() {
+        let mut series = wave(15.0, 50);
+        series[..4].fill(f64::NAN);
+        let smooth = super_smoother(&series, 10).unwrap();
+        assert!(smooth[..4].iter().all(|v| v.is_nan()));
+        assert_eq!(smooth[4], series[4]);
+        assert_eq!(high_pass(&series, 10).unwrap()[5], 0.0);
+        assert!(bandpass(&[f64::NAN; 3], 10, 0.3).unwrap().iter().all(|v| v.is_nan()));
+
+        assert!(super_smoother(&series, 1).is_err());
+        assert!(decycler(&series, 4).is_err());
+        assert!(roofing_filter(&series, 10, 10).is_err());
+        assert!(bandpass(&series, 20, 0.0).is_err());
+        assert!(bandpass(&series, 2, 0.9).is_err());
+        assert!(high_pass(&[], 10).is_err());
+    }
+}
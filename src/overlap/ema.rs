@@ -1,7 +1,10 @@
 //! Exponential Moving Average (EMA)
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output, ema_multiplier};
+use crate::common::{TAResult, Price, Float, IntoParam, Period, TAOutput, ema_lookback, with_nan_policy, NanPolicy};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, validate_same_length, allocate_output, ema_multiplier};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Exponential Moving Average (EMA)
 ///
@@ -32,59 +35,108 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// ```rust
 /// use ta_rust::overlap::ema;
 ///
-/// let prices = vec![22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29];
+/// let prices: Vec<f64> = vec![22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29];
 /// let result = ema(&prices, 10).unwrap();
 /// 
 /// // First 9 values are NaN, then EMA starts
 /// assert!(result[8].is_nan());
 /// assert!(!result[9].is_nan());
 /// ```
-pub fn ema(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn ema<T: Float>(data: &[T], period: impl IntoParam<Period>) -> TAResult<Vec<T>> {
+    with_function("EMA", || {
+        let period = period.into_param("period")?.get();
+        let mut output = vec![T::NAN; data.len()];
+        ema_into(data, period, &mut output)?;
+        Ok(output)
+    })
+}
 
-    let mut output = allocate_output(data.len());
-    let multiplier = ema_multiplier(period);
+/// Calculates the EMA with where its values start
+///
+/// Same as [`ema`], wrapped in a [`TAOutput`] with the lookback of [`ema_lookback`](crate::common::lookback::ema_lookback).
+///
+/// # Errors
+/// Same as [`ema`]
+pub fn ema_ex(data: &[Price], period: impl IntoParam<Period>) -> TAResult<TAOutput> {
+    with_function("EMA", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(ema(data, period)?, ema_lookback(period)))
+    })
+}
+
+/// Calculates the EMA of a series with missing values
+///
+/// Same as [`ema`], with NaN in `data` handled according to `policy` rather than reaching
+/// the recursive state and making every later value NaN; see
+/// [`with_nan_policy`](crate::common::with_nan_policy).
+///
+/// # Errors
+/// Same as [`ema`], plus `InvalidInput` if `data` holds NaN under [`NanPolicy::Error`]
+pub fn ema_with_policy(data: &[Price], period: impl IntoParam<Period>, policy: NanPolicy) -> TAResult<Vec<Price>> {
+    with_function("EMA", || with_nan_policy(&[data], policy, |i| ema(i[0], period)))
+}
+
+/// Calculates the EMA into a caller-provided buffer
+///
+/// Allocation-free variant of [`ema`]. `out` must have the same length as `data`;
+/// its first `period-1` entries are set to NaN.
+///
+/// # Returns
+/// The number of valid values written (`data.len() - period + 1`).
+///
+/// # Errors
+/// Same as [`ema`], plus `MismatchedInputs` if `out.len() != data.len()`
+pub fn ema_into<T: Float>(data: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> TAResult<usize> {
+    with_function("EMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
+        validate_same_length(data, out, "data", "out")?;
+
+        let multiplier = T::from_f64(ema_multiplier(period));
+        out[..period - 1].fill(T::NAN);
     
-    // Initialize EMA with SMA of first 'period' values
-    let initial_sum: Price = data[0..period].iter().sum();
-    let mut ema_value = initial_sum / period as Price;
-    output[period - 1] = ema_value;
+        // Initialize EMA with SMA of first 'period' values
+        let initial_sum: T = data[0..period].iter().sum();
+        let mut ema_value = initial_sum / T::from_usize(period);
+        out[period - 1] = ema_value;
     
-    // Calculate EMA for remaining values
-    for i in period..data.len() {
-        ema_value = (data[i] * multiplier) + (ema_value * (1.0 - multiplier));
-        output[i] = ema_value;
-    }
+        // Calculate EMA for remaining values
+        for (value, &price) in out[period..].iter_mut().zip(&data[period..]) {
+            ema_value = (price * multiplier) + (ema_value * (T::ONE - multiplier));
+            *value = ema_value;
+        }
 
-    Ok(output)
+        Ok(data.len() - period + 1)
+    })
 }
 
 /// Calculates EMA starting from the first data point (alternative initialization)
 ///
 /// This version uses the first price as the initial EMA value instead of SMA,
 /// which is sometimes preferred for shorter datasets.
-pub fn ema_from_first(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
+pub fn ema_from_first(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("EMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
 
-    let mut output = allocate_output(data.len());
-    let multiplier = ema_multiplier(period);
+        let mut output = allocate_output(data.len());
+        let multiplier = ema_multiplier(period);
     
-    // Initialize with first price
-    let mut ema_value = data[0];
-    output[0] = ema_value;
+        // Initialize with first price
+        let mut ema_value = data[0];
+        output[0] = ema_value;
     
-    // Calculate EMA for all subsequent values
-    for i in 1..data.len() {
-        ema_value = (data[i] * multiplier) + (ema_value * (1.0 - multiplier));
-        output[i] = ema_value;
-    }
+        // Calculate EMA for all subsequent values
+        for i in 1..data.len() {
+            ema_value = (data[i] * multiplier) + (ema_value * (1.0 - multiplier));
+            output[i] = ema_value;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates EMA with custom smoothing factor
@@ -103,29 +155,31 @@ pub fn ema_from_first(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
 /// let result = ema_custom(&prices, 0.5).unwrap(); // 50% smoothing
 /// ```
 pub fn ema_custom(data: &[Price], smoothing_factor: Price) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
+    with_function("EMA", || {
+        // Input validation
+        validate_not_empty(data, "data")?;
     
-    if smoothing_factor <= 0.0 || smoothing_factor >= 1.0 {
-        return Err(crate::common::TAError::invalid_parameter(
-            "smoothing_factor",
-            "must be between 0 and 1 (exclusive)"
-        ));
-    }
+        if smoothing_factor <= 0.0 || smoothing_factor >= 1.0 {
+            return Err(crate::common::TAError::invalid_parameter(
+                "smoothing_factor",
+                "must be between 0 and 1 (exclusive)"
+            ));
+        }
 
-    let mut output = allocate_output(data.len());
+        let mut output = allocate_output(data.len());
     
-    // Initialize with first price
-    let mut ema_value = data[0];
-    output[0] = ema_value;
+        // Initialize with first price
+        let mut ema_value = data[0];
+        output[0] = ema_value;
     
-    // Calculate EMA for all subsequent values
-    for i in 1..data.len() {
-        ema_value = (data[i] * smoothing_factor) + (ema_value * (1.0 - smoothing_factor));
-        output[i] = ema_value;
-    }
+        // Calculate EMA for all subsequent values
+        for i in 1..data.len() {
+            ema_value = (data[i] * smoothing_factor) + (ema_value * (1.0 - smoothing_factor));
+            output[i] = ema_value;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -245,7 +299,7 @@ mod tests {
 
     #[test]
     fn test_ema_empty_data() {
-        let data = vec![];
+        let data: Vec<Price> = vec![];
         assert!(ema(&data, 3).is_err());
     }
 
@@ -0,0 +1,211 @@
+//! Time-Aware Exponential Moving Average
+//!
+//! A fixed smoothing factor assumes evenly spaced samples. Ticks, trades and other
+//! event-driven data arrive at irregular intervals, so the weight of a new sample should
+//! depend on how much time has passed since the previous one: a sample after a long gap
+//! should replace most of the average, one a moment later should barely move it. This
+//! EMA decays with elapsed time, so its memory is a duration rather than a sample count.
+
+use crate::common::{TAError, TAResult, Price, Timestamp};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_same_length, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates an EMA of irregularly spaced samples
+///
+/// Each sample is weighted by the time elapsed since the previous one, so that the weight
+/// of a past value halves every `half_life` time units whatever the number of samples in
+/// between.
+///
+/// # Formula
+/// ```text
+/// τ        = half_life / ln 2
+/// α[t]     = 1 - exp(-(time[t] - time[t-1]) / τ)
+/// EMA[t]   = α[t] × x[t] + (1 - α[t]) × EMA[t-1]
+/// EMA[0]   = x[0]
+/// ```
+///
+/// NaN values are skipped: their output is NaN and the next sample's elapsed time is
+/// measured from the last valid one.
+///
+/// # Parameters
+/// - `values`: Slice of sample values
+/// - `timestamps`: Strictly increasing sample times
+/// - `half_life`: Time for the weight of a sample to halve, in the unit of the timestamps
+///
+/// # Returns
+/// Vector of averages, starting at the first valid sample.
+///
+/// # Errors
+/// - `InvalidInput` if values is empty or the timestamps are not strictly increasing
+/// - `MismatchedInputs` if values and timestamps differ in length
+/// - `InvalidParameter` if half_life is not positive and finite
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::ema_time;
+///
+/// let values = vec![10.0, 20.0, 20.0];
+/// let timestamps = vec![0, 60, 61];
+/// let result = ema_time(&values, &timestamps, 60.0).unwrap();
+///
+/// // One half-life later the new sample gets half the weight
+/// assert!((result[1] - 15.0).abs() < 1e-12);
+/// // A second later it barely moves the average
+/// assert!(result[2] > 15.0 && result[2] < 15.1);
+/// ```
+pub fn ema_time(values: &[Price], timestamps: &[Timestamp], half_life: f64) -> TAResult<Vec<Price>> {
+    with_function("EMA_TIME", || {
+        validate_not_empty(values, "values")?;
+        validate_same_length(values, timestamps, "values", "timestamps")?;
+        if timestamps.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(TAError::invalid_input("Timestamps must be strictly increasing"));
+        }
+
+        let mut state = EmaTimeState::new(half_life)?;
+        let mut output = allocate_output(values.len());
+        for (out, (&value, &timestamp)) in output.iter_mut().zip(values.iter().zip(timestamps)) {
+            if !value.is_nan() {
+                *out = state.update(timestamp, value).unwrap_or(Price::NAN);
+            }
+        }
+
+        Ok(output)
+    })
+}
+
+/// Streaming time-aware EMA
+///
+/// Produces the same values as [`ema_time`] one sample at a time, in O(1) time and memory.
+/// A sample timestamped at or before the previous one has no elapsed time and leaves the
+/// average unchanged, as do NaN values.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{ema_time, EmaTimeState};
+///
+/// let values = vec![10.0, 12.0, 11.0, 15.0];
+/// let timestamps = vec![0, 5, 30, 31];
+/// let batch = ema_time(&values, &timestamps, 10.0).unwrap();
+///
+/// let mut state = EmaTimeState::new(10.0).unwrap();
+/// for i in 0..values.len() {
+///     assert_eq!(state.update(timestamps[i], values[i]), Some(batch[i]));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmaTimeState {
+    /// Decay time constant τ, in the unit of the timestamps
+    tau: f64,
+    half_life: f64,
+    /// Time and value of the average after the last valid sample
+    last: Option<(Timestamp, Price)>,
+}
+
+impl EmaTimeState {
+    /// Creates a new state for the given half-life
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if half_life is not positive and finite
+    pub fn new(half_life: f64) -> TAResult<Self> {
+        with_function("EMA_TIME", || {
+            if !(half_life.is_finite() && half_life > 0.0) {
+                return Err(TAError::invalid_parameter("half_life", "must be positive and finite"));
+            }
+
+            Ok(Self {
+                tau: half_life / core::f64::consts::LN_2,
+                half_life,
+                last: None,
+            })
+        })
+    }
+
+    /// Feeds the next sample and returns the updated average, or `None` before the first
+    /// valid sample
+    pub fn update(&mut self, timestamp: Timestamp, value: Price) -> Option<Price> {
+        if value.is_nan() {
+            return self.current();
+        }
+        self.last = match self.last {
+            Some((last_time, average)) if timestamp > last_time => {
+                let alpha = 1.0 - (-((timestamp - last_time) as f64) / self.tau).exp();
+                Some((timestamp, alpha * value + (1.0 - alpha) * average))
+            }
+            Some(last) => Some(last),
+            None => Some((timestamp, value)),
+        };
+        self.current()
+    }
+
+    /// Current average, or `None` before the first valid sample
+    pub fn current(&self) -> Option<Price> {
+        self.last.map(|(_, average)| average)
+    }
+
+    /// Half-life of the average
+    pub fn half_life(&self) -> f64 {
+        self.half_life
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::overlap::ema_custom;
+
+    #[test]
+    fn test_ema_time_regular_spacing_matches_fixed_alpha() {
+        // Evenly spaced samples give a constant alpha
+        let values: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.5).sin()).collect();
+        let timestamps: Vec<Timestamp> = (0..30).map(|i| i * 15).collect();
+        let result = ema_time(&values, &timestamps, 45.0).unwrap();
+        let alpha = 1.0 - 0.5f64.powf(15.0 / 45.0);
+        let expected = ema_custom(&values, alpha).unwrap();
+        for i in 0..30 {
+            assert_float_eq!(result[i], expected[i], 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ema_time_gaps_and_nan() {
+        let values = vec![f64::NAN, 100.0, 110.0, f64::NAN, 90.0];
+        let timestamps = vec![0, 10, 20, 25, 40];
+        let result = ema_time(&values, &timestamps, 10.0).unwrap();
+        assert!(result[0].is_nan());
+        assert_eq!(result[1], 100.0);
+        assert_float_eq!(result[2], 105.0, 1e-12);
+        assert!(result[3].is_nan());
+        // Two half-lives since the last valid sample: weight 3/4 on the new value
+        assert_float_eq!(result[4], 0.75 * 90.0 + 0.25 * 105.0, 1e-12);
+
+        assert!(ema_time(&values, &[0, 10, 10, 25, 40], 10.0).is_err());
+        assert!(ema_time(&values, &timestamps[1..], 10.0).is_err());
+        assert!(ema_time(&values, &timestamps, 0.0).is_err());
+        assert!(ema_time(&[], &[], 10.0).is_err());
+    }
+
+    #[test]
+    fn test_ema_time_state() {
+        let mut state = EmaTimeState::new(10.0).unwrap();
+        assert_eq!(state.update(0, f64::NAN), None);
+        assert_eq!(state.update(5, 50.0), Some(50.0));
+        // Out of order and duplicate samples leave the average unchanged
+        assert_eq!(state.update(5, 80.0), Some(50.0));
+        assert_eq!(state.update(3, 80.0), Some(50.0));
+        assert_float_eq!(state.update(15, 60.0).unwrap(), 55.0, 1e-12);
+        assert_eq!(state.half_life(), 10.0);
+
+        state.reset();
+        assert_eq!(state, EmaTimeState::new(10.0).unwrap());
+        assert!(EmaTimeState::new(f64::INFINITY).is_err());
+    }
+}
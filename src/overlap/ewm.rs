@@ -0,0 +1,210 @@
+//! Exponential Smoothing of Arbitrary Series
+//!
+//! The exponential average behind EMA, Wilder's RSI and ATR and the ADX, as a building
+//! block for any series: a smoothing factor and a choice of seed, nothing else. Series with
+//! a warm-up, such as indicator outputs, can be smoothed directly since leading NaNs are
+//! skipped.
+
+use crate::common::{validate_not_empty, validate_period, validate_same_length, Float, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// How an exponential average is seeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EwmInit {
+    /// Seeded with the SMA of the first `period` values, as TA-Lib does; the first value
+    /// of the average is at the end of that window
+    Sma(usize),
+    /// Seeded with the first value, as pandas' `ewm(adjust=False)` does
+    First,
+}
+
+impl EwmInit {
+    /// Number of values averaged into the seed
+    fn seed_len(self) -> usize {
+        match self {
+            EwmInit::Sma(period) => period,
+            EwmInit::First => 1,
+        }
+    }
+}
+
+/// Calculates the exponentially weighted mean of a series
+///
+/// # Formula
+/// ```text
+/// avg[seed] = SMA(x, period) for EwmInit::Sma(period), x[first] for EwmInit::First
+/// avg[t]    = α × x[t] + (1 - α) × avg[t-1]
+/// ```
+///
+/// Leading NaNs are skipped and the average starts after them; a NaN after the start
+/// propagates to all later values.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `alpha` - Smoothing factor (0 < alpha <= 1)
+/// * `init` - How the average is seeded
+///
+/// # Returns
+/// * `Ok(Vec<T>)` - Vector of averages; values before the seed are NaN
+/// * `Err(TAError)` - Error if inputs are invalid or too short for the seed
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::{ema, ewm, EwmInit};
+///
+/// let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+/// assert_eq!(ewm(&data, 0.5, EwmInit::Sma(3)).unwrap()[2..], ema(&data, 3).unwrap()[2..]);
+///
+/// let from_first = ewm(&data, 0.5, EwmInit::First).unwrap();
+/// assert_eq!(from_first[..3], [1.0, 1.5, 2.25]);
+/// ```
+pub fn ewm<T: Float>(series: &[T], alpha: T, init: EwmInit) -> TAResult<Vec<T>> {
+    with_function("EWM", || {
+        let mut output = vec![T::NAN; series.len()];
+        ewm_into(series, alpha, init, &mut output)?;
+        Ok(output)
+    })
+}
+
+/// Calculates the exponentially weighted mean into a caller-provided buffer
+///
+/// Allocation-free variant of [`ewm`]. `out` must have the same length as `series`.
+///
+/// # Returns
+/// The number of valid values written.
+///
+/// # Errors
+/// Same as [`ewm`], plus `MismatchedInputs` if `out.len() != series.len()`
+pub fn ewm_into<T: Float>(series: &[T], alpha: T, init: EwmInit, out: &mut [T]) -> TAResult<usize> {
+    with_function("EWM", || {
+        validate_not_empty(series, "series")?;
+        validate_period(init.seed_len(), "period")?;
+        if !(alpha > T::ZERO && alpha <= T::ONE) {
+            return Err(TAError::invalid_parameter("alpha", "must be between 0 (exclusive) and 1 (inclusive)"));
+        }
+        validate_same_length(series, out, "series", "out")?;
+
+        let needed = leading_nan(series) + init.seed_len();
+        if series.len() < needed {
+            return Err(TAError::insufficient_data(needed, series.len()));
+        }
+        Ok(ewm_unchecked_into(series, alpha, init, out))
+    })
+}
+
+/// Applies Wilder's smoothing to a series
+///
+/// The exponential average with `α = 1 / period` seeded with an SMA, as used by RSI, ATR
+/// and the directional movement system. Equivalent to an EMA of period `2 × period - 1`
+/// after the seed.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `period` - Smoothing period
+///
+/// # Returns
+/// * `Ok(Vec<T>)` - Vector of averages; the first `period-1` values after any leading
+///   NaNs are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::wilder_smooth;
+/// use ta_rust::volatility::{atr, trange};
+///
+/// let high = vec![10.0, 11.0, 12.0, 11.5, 13.0, 12.5];
+/// let low = vec![9.0, 10.0, 10.5, 10.0, 11.0, 11.5];
+/// let close = vec![9.5, 10.5, 11.5, 10.5, 12.0, 12.0];
+///
+/// let tr = trange(&high, &low, &close).unwrap();
+/// assert_eq!(wilder_smooth(&tr, 3).unwrap()[2..], atr(&high, &low, &close, 3).unwrap()[2..]);
+/// ```
+pub fn wilder_smooth<T: Float>(series: &[T], period: impl IntoParam<Period>) -> TAResult<Vec<T>> {
+    with_function("WILDER_SMOOTH", || {
+        let period = period.into_param("period")?.get();
+        ewm(series, T::ONE / T::from_usize(period), EwmInit::Sma(period))
+    })
+}
+
+/// Exponential average of a series already validated by the caller, into `out`
+///
+/// Leaves `out` all NaN, and returns 0, when the series is too short for the seed.
+pub(crate) fn ewm_unchecked_into<T: Float>(series: &[T], alpha: T, init: EwmInit, out: &mut [T]) -> usize {
+    let start = leading_nan(series);
+    let seed_len = init.seed_len();
+    if series.len() < start + seed_len {
+        out.fill(T::NAN);
+        return 0;
+    }
+    let first = start + seed_len - 1;
+    out[..first].fill(T::NAN);
+    for (value, average) in out[first..].iter_mut().zip(smoothed(series[start..].iter().copied(), alpha, seed_len)) {
+        *value = average;
+    }
+    series.len() - first
+}
+
+/// Exponential average of `values`, seeded with the mean of the first `seed_len` of them
+///
+/// Yields the seed, then one average per further value. The caller ensures there are at
+/// least `seed_len >= 1` values.
+pub(crate) fn smoothed<T: Float>(mut values: impl Iterator<Item = T>, alpha: T, seed_len: usize) -> impl Iterator<Item = T> {
+    let seed = values.by_ref().take(seed_len).sum::<T>() / T::from_usize(seed_len);
+    core::iter::once(seed).chain(values.scan(seed, move |average, value| {
+        *average = alpha * value + (T::ONE - alpha) * *average;
+        Some(*average)
+    }))
+}
+
+fn leading_nan<T: Float>(series: &[T]) -> usize {
+    series.iter().take_while(|v| v.is_nan()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::overlap::{ema, ema_custom};
+
+    #[test]
+    fn test_ewm_matches_ema_variants() {
+        let data: Vec<f64> = (0..40).map(|i| 30.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let alpha = 2.0 / 11.0;
+        assert_arrays_approx_equal(&ewm(&data, alpha, EwmInit::Sma(10)).unwrap(), &ema(&data, 10).unwrap(), 0.0);
+        assert_arrays_approx_equal(&ewm(&data, 0.3, EwmInit::First).unwrap(), &ema_custom(&data, 0.3).unwrap(), 1e-12);
+
+        // alpha = 1 follows the series from the seed on
+        let raw = ewm(&data, 1.0, EwmInit::Sma(4)).unwrap();
+        assert_eq!(&raw[4..], &data[4..]);
+
+        let f32_data: Vec<f32> = data.iter().map(|&v| v as f32).collect();
+        let f32_result = ewm(&f32_data, 0.2f32, EwmInit::Sma(5)).unwrap();
+        assert!((f32_result[39] as f64 - ewm(&data, 0.2, EwmInit::Sma(5)).unwrap()[39]).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_ewm_skips_leading_nan() {
+        let series = vec![f64::NAN, f64::NAN, 2.0, 4.0, 6.0, 8.0, f64::NAN, 1.0];
+        let result = wilder_smooth(&series, 2).unwrap();
+        assert!(result[..3].iter().all(|v| v.is_nan()));
+        assert_eq!(result[3], 3.0);
+        assert_eq!(result[4], 4.5);
+        assert_eq!(result[5], 6.25);
+        assert!(result[6..].iter().all(|v| v.is_nan()));
+
+        let mut out = vec![0.0; series.len()];
+        assert_eq!(ewm_into(&series, 0.5, EwmInit::First, &mut out).unwrap(), 6);
+        assert_eq!(out[2], 2.0);
+
+        assert!(matches!(wilder_smooth(&series, 7), Err(TAError::InsufficientData { required: 9, provided: 8, .. })));
+        assert!(wilder_smooth(&series, 0).is_err());
+        assert!(ewm(&series, 0.0, EwmInit::First).is_err());
+        assert!(ewm(&series, 1.5, EwmInit::First).is_err());
+        assert!(ewm(&series, f64::NAN, EwmInit::First).is_err());
+        assert!(ewm::<f64>(&[], 0.5, EwmInit::First).is_err());
+        assert!(ewm_into(&series, 0.5, EwmInit::First, &mut out[1..]).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Fractal Adaptive Moving Average (FRAMA)
+//!
+//! John Ehlers' FRAMA estimates the fractal dimension of the price series over a window and
+//! uses it to set the smoothing factor of an EMA. Trending (near one-dimensional) price moves
+//! give a fast filter; choppy, space-filling price action gives a very slow one.
+
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Smallest smoothing factor, equivalent to an EMA of roughly 200 bars
+const MIN_ALPHA: f64 = 0.01;
+
+/// Highest high minus lowest low over a window
+fn range(high: &[f64], low: &[f64]) -> f64 {
+    let hh = high.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let ll = low.iter().cloned().fold(f64::INFINITY, f64::min);
+    hh - ll
+}
+
+/// Fractal Adaptive Moving Average (FRAMA)
+///
+/// The window of `period` bars is split into two halves. Comparing the average range of each
+/// half with that of the whole window gives the fractal dimension `D`, which lies between 1
+/// (a straight line) and 2 (noise filling the plane).
+///
+/// # Formula
+/// ```text
+/// N1 = (Highest(High, n/2) - Lowest(Low, n/2)) / (n/2)          most recent half
+/// N2 = (Highest(High, n/2) - Lowest(Low, n/2)) / (n/2)          older half
+/// N3 = (Highest(High, n)   - Lowest(Low, n))   / n              whole window
+///
+/// D     = (ln(N1 + N2) - ln(N3)) / ln(2)
+/// alpha = exp(-4.6 × (D - 1)), clamped to [0.01, 1]
+///
+/// FRAMA[today] = alpha × Close[today] + (1 - alpha) × FRAMA[yesterday]
+/// ```
+///
+/// The filter is seeded with the close of the first complete window. When a window has no
+/// range the dimension is undefined and the previous smoothing factor is kept.
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - Window length; must be even and at least 2 (typically 16)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of FRAMA values; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::frama;
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + i as f64).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+///
+/// let result = frama(&high, &low, &close, 16).unwrap();
+/// assert!(result[14].is_nan());
+/// // A clean trend has dimension ~1, so FRAMA hugs the price
+/// assert!((close[39] - result[39]).abs() < 2.0);
+/// ```
+pub fn frama(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("FRAMA", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+
+        if period < 2 || !period.is_multiple_of(2) {
+            return Err(TAError::invalid_parameter("period", "must be an even number of at least 2"));
+        }
+
+        if period > close.len() {
+            return Err(TAError::insufficient_data(period, close.len()));
+        }
+
+        let len = close.len();
+        let half = period / 2;
+        let mut result = vec![f64::NAN; len];
+
+        let mut value = close[period - 1];
+        result[period - 1] = value;
+        let mut alpha = 1.0;
+
+        for i in period..len {
+            let start = i + 1 - period;
+            let mid = start + half;
+
+            let n1 = range(&high[mid..=i], &low[mid..=i]) / half as f64;
+            let n2 = range(&high[start..mid], &low[start..mid]) / half as f64;
+            let n3 = range(&high[start..=i], &low[start..=i]) / period as f64;
+
+            if n1 + n2 > 0.0 && n3 > 0.0 {
+                let dimension = ((n1 + n2).ln() - n3.ln()) / core::f64::consts::LN_2;
+                alpha = (-4.6 * (dimension - 1.0)).exp().clamp(MIN_ALPHA, 1.0);
+            }
+
+            value = alpha * close[i] + (1.0 - alpha) * value;
+            result[i] = value;
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_frama_warmup_and_seed() {
+        let close: Vec<f64> = (0..20).map(|i| 50.0 + (i as f64 * 0.7).sin()).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let result = frama(&high, &low, &close, 8).unwrap();
+
+        assert_eq!(result.len(), 20);
+        assert!(result[..7].iter().all(|v| v.is_nan()));
+        assert_eq!(result[7], close[7]);
+        assert!(result[8..].iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_frama_trend_is_fast() {
+        // On a straight line D <= 1, so alpha is clamped to 1 and FRAMA equals the close
+        let close: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let result = frama(&close, &close, &close, 10).unwrap();
+        for i in 9..30 {
+            assert_float_eq!(result[i], close[i], 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_frama_chop_is_slow() {
+        // Alternating bars give every half the same range as the whole window: D = 2
+        let close: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 10.0 } else { 12.0 }).collect();
+        let result = frama(&close, &close, &close, 10).unwrap();
+
+        let alpha = (-4.6f64).exp();
+        let expected = alpha * close[10] + (1.0 - alpha) * close[9];
+        assert_float_eq!(result[10], expected, 1e-12);
+    }
+
+    #[test]
+    fn test_frama_invalid_input() {
+        let close = vec![1.0; 10];
+        assert!(frama(&[], &[], &[], 4).is_err());
+        assert!(frama(&close[..5], &close, &close, 4).is_err());
+        assert!(frama(&close, &close, &close, 0).is_err());
+        assert!(frama(&close, &close, &close, 5).is_err());
+        assert!(frama(&close, &close, &close, 12).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! Ichimoku Kinko Hyo (Ichimoku Cloud)
+//!
+//! Ichimoku combines three midpoint lines with forward and backward displacement to show
+//! trend, momentum and support/resistance at a glance. The displacement is what makes the
+//! raw arrays easy to misalign, so [`IchimokuCloud`] documents exactly which bar each
+//! value belongs to.
+
+use crate::common::{TAError, TAResult, IntoParam, Period, validate_hlc_with, HlcValidation};
+use crate::common::errors::with_function;
+use crate::overlap::donchian;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Ichimoku result structure
+///
+/// All series are indexed by the bar at which they are *plotted*:
+///
+/// * `tenkan_sen`, `kijun_sen` and `chikou_span` have the same length as the input.
+/// * `senkou_span_a` and `senkou_span_b` are `displacement` bars longer than the input.
+///   Index `i` holds the value computed at bar `i - displacement`, so the cloud for bar `i`
+///   is `senkou_span_a[i]`/`senkou_span_b[i]`, and the last `displacement` entries are the
+///   cloud projected into the future.
+/// * `chikou_span[i]` is `close[i + displacement]` (the close plotted `displacement` bars
+///   back); the last `displacement` entries are NaN.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IchimokuCloud {
+    /// Conversion line: midpoint of the `tenkan` period high/low
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub tenkan_sen: Vec<f64>,
+    /// Base line: midpoint of the `kijun` period high/low
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub kijun_sen: Vec<f64>,
+    /// Leading span A: (tenkan + kijun) / 2, shifted forward by `displacement`
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub senkou_span_a: Vec<f64>,
+    /// Leading span B: midpoint of the `senkou_b` period high/low, shifted forward by `displacement`
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub senkou_span_b: Vec<f64>,
+    /// Lagging span: close shifted back by `displacement`
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub chikou_span: Vec<f64>,
+    /// Number of bars the leading spans are shifted forward and the lagging span back
+    pub displacement: usize,
+}
+
+impl IchimokuCloud {
+    /// Returns the cloud (span A, span B) plotted at bar `index`
+    ///
+    /// `index` may run up to `displacement - 1` bars past the end of the input to read the
+    /// projected cloud; returns `None` beyond that.
+    pub fn cloud_at(&self, index: usize) -> Option<(f64, f64)> {
+        Some((*self.senkou_span_a.get(index)?, *self.senkou_span_b.get(index)?))
+    }
+}
+
+/// Ichimoku Cloud
+///
+/// # Formula
+/// ```text
+/// Tenkan-sen    = (Highest(High, tenkan) + Lowest(Low, tenkan)) / 2
+/// Kijun-sen     = (Highest(High, kijun) + Lowest(Low, kijun)) / 2
+/// Senkou Span A = (Tenkan-sen + Kijun-sen) / 2, plotted kijun bars ahead
+/// Senkou Span B = (Highest(High, senkou_b) + Lowest(Low, senkou_b)) / 2, plotted kijun bars ahead
+/// Chikou Span   = Close, plotted kijun bars behind
+/// ```
+///
+/// The displacement equals the `kijun` period, as in the standard 9/26/52 setup.
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `tenkan` - Conversion line period (typically 9)
+/// * `kijun` - Base line period and displacement (typically 26)
+/// * `senkou_b` - Leading span B period (typically 52)
+///
+/// # Returns
+/// * `Ok(IchimokuCloud)` - See [`IchimokuCloud`] for the alignment of each series
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::ichimoku;
+///
+/// let high: Vec<f64> = (0..80).map(|i| 101.0 + (i as f64 * 0.1).sin()).collect();
+/// let low: Vec<f64> = high.iter().map(|h| h - 2.0).collect();
+/// let close: Vec<f64> = high.iter().map(|h| h - 1.0).collect();
+///
+/// let cloud = ichimoku(&high, &low, &close, 9, 26, 52).unwrap();
+/// assert_eq!(cloud.senkou_span_a.len(), 80 + 26);
+/// // Cloud under the last bar, and 25 bars into the future
+/// let (a, b) = cloud.cloud_at(79).unwrap();
+/// let projected = cloud.cloud_at(79 + 25).unwrap();
+/// ```
+pub fn ichimoku(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    tenkan: impl IntoParam<Period>,
+    kijun: impl IntoParam<Period>,
+    senkou_b: impl IntoParam<Period>,
+) -> TAResult<IchimokuCloud> {
+    with_function("ICHIMOKU", || {
+        let tenkan = tenkan.into_param("tenkan")?.get();
+        let kijun = kijun.into_param("kijun")?.get();
+        let senkou_b = senkou_b.into_param("senkou_b")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+
+        let len = close.len();
+        let displacement = kijun;
+
+        let tenkan_sen = donchian(high, low, tenkan)?.middle;
+        let kijun_sen = donchian(high, low, kijun)?.middle;
+        let span_b = donchian(high, low, senkou_b)?.middle;
+
+        // Leading spans: the value computed at bar i is plotted at bar i + displacement
+        let mut senkou_span_a = vec![f64::NAN; len + displacement];
+        let mut senkou_span_b = vec![f64::NAN; len + displacement];
+        for (i, (&tenkan_val, &kijun_val)) in tenkan_sen.iter().zip(&kijun_sen).enumerate() {
+            senkou_span_a[i + displacement] = (tenkan_val + kijun_val) / 2.0;
+        }
+        senkou_span_b[displacement..].copy_from_slice(&span_b);
+
+        // Lagging span: the close of bar i + displacement is plotted at bar i
+        let mut chikou_span = vec![f64::NAN; len];
+        if displacement < len {
+            chikou_span[..len - displacement].copy_from_slice(&close[displacement..]);
+        }
+
+        Ok(IchimokuCloud {
+            tenkan_sen,
+            kijun_sen,
+            senkou_span_a,
+            senkou_span_b,
+            chikou_span,
+            displacement,
+        })
+    })
+}
+
+/// Ichimoku Cloud with the standard 9/26/52 periods
+pub fn ichimoku_default(high: &[f64], low: &[f64], close: &[f64]) -> TAResult<IchimokuCloud> {
+    ichimoku(high, low, close, 9, 26, 52)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    fn sample(len: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let close: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0 + i as f64 * 0.05).collect();
+        let high = close.iter().map(|c| c + 1.0).collect();
+        let low = close.iter().map(|c| c - 1.0).collect();
+        (high, low, close)
+    }
+
+    #[test]
+    fn test_ichimoku_alignment() {
+        let (high, low, close) = sample(40);
+        let cloud = ichimoku(&high, &low, &close, 3, 5, 10).unwrap();
+
+        assert_eq!(cloud.displacement, 5);
+        assert_eq!(cloud.tenkan_sen.len(), 40);
+        assert_eq!(cloud.kijun_sen.len(), 40);
+        assert_eq!(cloud.chikou_span.len(), 40);
+        assert_eq!(cloud.senkou_span_a.len(), 45);
+        assert_eq!(cloud.senkou_span_b.len(), 45);
+
+        // Tenkan at bar 10 is the midpoint of bars 8..=10
+        let hh = high[8..=10].iter().cloned().fold(f64::MIN, f64::max);
+        let ll = low[8..=10].iter().cloned().fold(f64::MAX, f64::min);
+        assert_float_eq!(cloud.tenkan_sen[10], (hh + ll) / 2.0, 1e-12);
+
+        // Span A computed at bar 20 is plotted at bar 25
+        let span_a = (cloud.tenkan_sen[20] + cloud.kijun_sen[20]) / 2.0;
+        assert_float_eq!(cloud.senkou_span_a[25], span_a, 1e-12);
+
+        // Span B needs 10 bars of history and is then shifted by 5
+        assert!(cloud.senkou_span_b[13].is_nan());
+        assert!(!cloud.senkou_span_b[14].is_nan());
+
+        // Chikou at bar 10 is the close of bar 15; the last 5 are undefined
+        assert_eq!(cloud.chikou_span[10], close[15]);
+        assert!(cloud.chikou_span[35..].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_ichimoku_cloud_at() {
+        let (high, low, close) = sample(100);
+        let cloud = ichimoku_default(&high, &low, &close).unwrap();
+
+        // Span B is first defined at bar 51 + 26
+        assert!(cloud.cloud_at(76).unwrap().1.is_nan());
+        assert_eq!(cloud.cloud_at(90), Some((cloud.senkou_span_a[90], cloud.senkou_span_b[90])));
+        assert!(cloud.cloud_at(100 + 25).is_some());
+        assert!(cloud.cloud_at(100 + 26).is_none());
+    }
+
+    #[test]
+    fn test_ichimoku_invalid_input() {
+        let (high, low, close) = sample(30);
+        assert!(ichimoku(&[], &[], &[], 9, 26, 52).is_err());
+        assert!(ichimoku(&high[..20], &low, &close, 3, 5, 10).is_err());
+        assert!(ichimoku(&high, &low, &close, 0, 5, 10).is_err());
+        let err = ichimoku(&high, &low, &close, 3, 0, 10).unwrap_err();
+        assert_eq!((err.parameter(), err.function()), (Some("kijun"), Some("ICHIMOKU")));
+        assert!(ichimoku_default(&high, &low, &close).is_err());
+    }
+}
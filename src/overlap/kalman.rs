@@ -0,0 +1,379 @@
+//! Kalman Filter Moving Averages
+//!
+//! A Kalman filter treats the price as a noisy measurement of a hidden "true" price and
+//! weighs each new bar by how much it trusts it: the gain rises when the model's own
+//! estimate is uncertain and falls when the measurements are noisy. The ratio of the
+//! process variance (how much the true price moves per bar) to the measurement variance
+//! (how noisy the bars are) plays the role of an MA's period, but the filter adapts its
+//! weights until they settle instead of using a fixed window.
+//!
+//! [`kalman`] tracks the price level alone, like an EMA whose smoothing factor is learnt.
+//! [`kalman_trend`] also tracks the velocity, so it follows trends without the lag of a
+//! level-only average and gives the trend slope per bar.
+
+use crate::common::{TAError, TAResult, Price};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Level and velocity estimated by [`kalman_trend`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KalmanTrend {
+    /// Filtered price
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub level: Vec<f64>,
+    /// Estimated price change per bar
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub velocity: Vec<f64>,
+}
+
+/// Calculates a Kalman filter of the price level
+///
+/// # Formula
+/// ```text
+/// Predict:  P = P + q
+/// Update:   K = P / (P + r)
+///           x = x + K × (price - x)
+///           P = (1 - K) × P
+/// ```
+/// where `q` is the process variance and `r` the measurement variance. The filter starts
+/// at the first valid price with `P = r`. The gain settles where a larger `q / r` follows
+/// the price more closely and a smaller one smooths more.
+///
+/// NaN prices are skipped: their output is NaN and the estimate is unchanged.
+///
+/// # Parameters
+/// - `prices`: Slice of prices
+/// - `process_var`: Variance of the true price change per bar
+/// - `measurement_var`: Variance of the noise in each price
+///
+/// # Returns
+/// Vector of filtered prices, starting at the first valid price.
+///
+/// # Errors
+/// - `InvalidInput` if prices is empty
+/// - `InvalidParameter` if a variance is not positive and finite
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::kalman;
+///
+/// let prices = vec![10.0, 10.4, 9.8, 10.1, 10.3, 9.9, 10.0];
+/// let filtered = kalman(&prices, 0.01, 1.0).unwrap();
+///
+/// assert_eq!(filtered[0], 10.0);
+/// // Heavy smoothing keeps the estimate close to the mean level
+/// assert!(filtered[1..].iter().all(|v| (v - 10.1).abs() < 0.25));
+/// ```
+pub fn kalman(prices: &[Price], process_var: f64, measurement_var: f64) -> TAResult<Vec<Price>> {
+    with_function("KALMAN", || {
+        validate_not_empty(prices, "prices")?;
+        let mut state = KalmanState::new(process_var, measurement_var)?;
+
+        let mut output = allocate_output(prices.len());
+        for (out, &price) in output.iter_mut().zip(prices) {
+            if !price.is_nan() {
+                *out = state.update(price).unwrap_or(Price::NAN);
+            }
+        }
+        Ok(output)
+    })
+}
+
+/// Calculates a Kalman filter of the price level and its velocity
+///
+/// A constant-velocity model: each bar the level moves by the velocity, and both are
+/// disturbed by a random acceleration of variance `process_var`.
+///
+/// # Formula
+/// ```text
+/// state x = [level, velocity],   F = [[1, 1], [0, 1]],   H = [1, 0]
+/// Q = q × [[1/4, 1/2], [1/2, 1]]
+///
+/// Predict:  x = F x,             P = F P Fᵀ + Q
+/// Update:   K = P Hᵀ / (H P Hᵀ + r)
+///           x = x + K × (price - level)
+///           P = (I - K H) P
+/// ```
+/// The filter starts at the first valid price with zero velocity and `P = r × I`.
+///
+/// NaN prices are skipped: their outputs are NaN and the estimate is unchanged.
+///
+/// # Parameters
+/// - `prices`: Slice of prices
+/// - `process_var`: Variance of the random acceleration per bar
+/// - `measurement_var`: Variance of the noise in each price
+///
+/// # Returns
+/// [`KalmanTrend`] with the filtered level and velocity, starting at the first valid price.
+///
+/// # Errors
+/// - `InvalidInput` if prices is empty
+/// - `InvalidParameter` if a variance is not positive and finite
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::kalman_trend;
+///
+/// // A steady trend of 0.5 per bar
+/// let prices: Vec<f64> = (0..100).map(|i| 50.0 + 0.5 * i as f64).collect();
+/// let trend = kalman_trend(&prices, 0.001, 1.0).unwrap();
+///
+/// assert!((trend.velocity[99] - 0.5).abs() < 0.01);
+/// assert!((trend.level[99] - prices[99]).abs() < 0.1);
+/// ```
+pub fn kalman_trend(prices: &[Price], process_var: f64, measurement_var: f64) -> TAResult<KalmanTrend> {
+    with_function("KALMANTREND", || {
+        validate_not_empty(prices, "prices")?;
+        let mut state = KalmanTrendState::new(process_var, measurement_var)?;
+
+        let mut level = allocate_output(prices.len());
+        let mut velocity = allocate_output(prices.len());
+        for (i, &price) in prices.iter().enumerate() {
+            if let (false, Some((l, v))) = (price.is_nan(), state.update(price)) {
+                level[i] = l;
+                velocity[i] = v;
+            }
+        }
+        Ok(KalmanTrend { level, velocity })
+    })
+}
+
+fn validate_variances(process_var: f64, measurement_var: f64) -> TAResult<()> {
+    for (name, value) in [("process_var", process_var), ("measurement_var", measurement_var)] {
+        if !(value.is_finite() && value > 0.0) {
+            return Err(TAError::invalid_parameter(name, "must be positive and finite"));
+        }
+    }
+    Ok(())
+}
+
+/// Streaming Kalman filter of the price level
+///
+/// Produces the same values as [`kalman`] one bar at a time, in O(1) time and memory.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{kalman, KalmanState};
+///
+/// let prices = vec![20.0, 20.5, 19.8, 21.0, 20.7];
+/// let batch = kalman(&prices, 0.1, 0.5).unwrap();
+///
+/// let mut state = KalmanState::new(0.1, 0.5).unwrap();
+/// for (i, &price) in prices.iter().enumerate() {
+///     assert_eq!(state.update(price), Some(batch[i]));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KalmanState {
+    process_var: f64,
+    measurement_var: f64,
+    /// Estimate and its variance, after the last valid price
+    estimate: Option<(f64, f64)>,
+}
+
+impl KalmanState {
+    /// Creates a new state
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if a variance is not positive and finite
+    pub fn new(process_var: f64, measurement_var: f64) -> TAResult<Self> {
+        with_function("KALMAN", || {
+            validate_variances(process_var, measurement_var)?;
+            Ok(Self { process_var, measurement_var, estimate: None })
+        })
+    }
+
+    /// Feeds the next price and returns the filtered price, or `None` before the first
+    /// valid price
+    pub fn update(&mut self, price: Price) -> Option<Price> {
+        if price.is_nan() {
+            return self.current();
+        }
+        self.estimate = Some(match self.estimate {
+            Some((level, variance)) => {
+                let predicted = variance + self.process_var;
+                let gain = predicted / (predicted + self.measurement_var);
+                (level + gain * (price - level), (1.0 - gain) * predicted)
+            }
+            None => (price, self.measurement_var),
+        });
+        self.current()
+    }
+
+    /// Current filtered price, or `None` before the first valid price
+    pub fn current(&self) -> Option<Price> {
+        self.estimate.map(|(level, _)| level)
+    }
+
+    /// Current Kalman gain, the weight the next price would get, or `None` before the
+    /// first valid price
+    pub fn gain(&self) -> Option<f64> {
+        self.estimate.map(|(_, variance)| {
+            let predicted = variance + self.process_var;
+            predicted / (predicted + self.measurement_var)
+        })
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.estimate = None;
+    }
+}
+
+/// Streaming Kalman filter of the price level and velocity
+///
+/// Produces the same values as [`kalman_trend`] one bar at a time, in O(1) time and memory.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{kalman_trend, KalmanTrendState};
+///
+/// let prices = vec![20.0, 20.5, 20.9, 21.6, 22.0];
+/// let batch = kalman_trend(&prices, 0.05, 0.5).unwrap();
+///
+/// let mut state = KalmanTrendState::new(0.05, 0.5).unwrap();
+/// for (i, &price) in prices.iter().enumerate() {
+///     assert_eq!(state.update(price), Some((batch.level[i], batch.velocity[i])));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KalmanTrendState {
+    process_var: f64,
+    measurement_var: f64,
+    /// Level, velocity and their covariance `[[p00, p01], [p01, p11]]`, after the last
+    /// valid price
+    estimate: Option<TrendEstimate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct TrendEstimate {
+    level: f64,
+    velocity: f64,
+    p00: f64,
+    p01: f64,
+    p11: f64,
+}
+
+impl KalmanTrendState {
+    /// Creates a new state
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if a variance is not positive and finite
+    pub fn new(process_var: f64, measurement_var: f64) -> TAResult<Self> {
+        with_function("KALMANTREND", || {
+            validate_variances(process_var, measurement_var)?;
+            Ok(Self { process_var, measurement_var, estimate: None })
+        })
+    }
+
+    /// Feeds the next price and returns the filtered level and velocity, or `None` before
+    /// the first valid price
+    pub fn update(&mut self, price: Price) -> Option<(f64, f64)> {
+        if price.is_nan() {
+            return self.current();
+        }
+        let q = self.process_var;
+        let r = self.measurement_var;
+        self.estimate = Some(match self.estimate {
+            Some(e) => {
+                // Predict with F = [[1, 1], [0, 1]]
+                let level = e.level + e.velocity;
+                let p00 = e.p00 + 2.0 * e.p01 + e.p11 + 0.25 * q;
+                let p01 = e.p01 + e.p11 + 0.5 * q;
+                let p11 = e.p11 + q;
+
+                // Update with the price, a measurement of the level
+                let innovation = price - level;
+                let k0 = p00 / (p00 + r);
+                let k1 = p01 / (p00 + r);
+                TrendEstimate {
+                    level: level + k0 * innovation,
+                    velocity: e.velocity + k1 * innovation,
+                    p00: (1.0 - k0) * p00,
+                    p01: (1.0 - k0) * p01,
+                    p11: p11 - k1 * p01,
+                }
+            }
+            None => TrendEstimate { level: price, velocity: 0.0, p00: r, p01: 0.0, p11: r },
+        });
+        self.current()
+    }
+
+    /// Current level and velocity, or `None` before the first valid price
+    pub fn current(&self) -> Option<(f64, f64)> {
+        self.estimate.map(|e| (e.level, e.velocity))
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.estimate = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_kalman_level() {
+        let constant = vec![42.0; 20];
+        assert!(kalman(&constant, 0.5, 2.0).unwrap().iter().all(|&v| v == 42.0));
+
+        // The first update weighs the price by (r + q) / (2r + q)
+        let result = kalman(&[10.0, 20.0], 1.0, 1.0).unwrap();
+        assert_float_eq!(result[1], 10.0 + 10.0 * 2.0 / 3.0, 1e-12);
+
+        // The gain settles at the steady state of P = (P + q) r / (P + q + r)
+        let mut state = KalmanState::new(0.1, 1.0).unwrap();
+        for i in 0..200 {
+            state.update(100.0 + (i % 3) as f64);
+        }
+        let p: f64 = (-0.1 + (0.01f64 + 0.4).sqrt()) / 2.0;
+        assert_float_eq!(state.gain().unwrap(), (p + 0.1) / (p + 1.1), 1e-9);
+
+        let with_gap = kalman(&[f64::NAN, 10.0, f64::NAN, 20.0], 1.0, 1.0).unwrap();
+        assert!(with_gap[0].is_nan() && with_gap[2].is_nan());
+        assert_float_eq!(with_gap[3], result[1], 1e-12);
+
+        assert!(kalman(&[], 1.0, 1.0).is_err());
+        assert!(kalman(&constant, 0.0, 1.0).is_err());
+        assert!(kalman(&constant, 1.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_kalman_trend_follows_slope() {
+        let prices: Vec<f64> = (0..200).map(|i| 100.0 - 0.25 * i as f64 + (i as f64 * 1.7).sin() * 0.3).collect();
+        let trend = kalman_trend(&prices, 1e-4, 0.1).unwrap();
+        assert_eq!(trend.velocity[0], 0.0);
+        assert_float_eq!(trend.velocity[199], -0.25, 0.02);
+
+        // Less lag than the level-only filter on the same trend
+        let level = kalman(&prices, 1e-4, 0.1).unwrap();
+        assert!((trend.level[199] - prices[199]).abs() < (level[199] - prices[199]).abs());
+    }
+
+    #[test]
+    fn test_kalman_states_reset() {
+        let mut state = KalmanTrendState::new(0.01, 1.0).unwrap();
+        assert_eq!(state.update(f64::NAN), None);
+        state.update(5.0);
+        state.update(6.0);
+        assert_eq!(state.update(f64::NAN), state.current());
+        state.reset();
+        assert_eq!(state, KalmanTrendState::new(0.01, 1.0).unwrap());
+
+        let mut level = KalmanState::new(0.01, 1.0).unwrap();
+        assert_eq!(level.gain(), None);
+        level.update(3.0);
+        level.reset();
+        assert_eq!(level.current(), None);
+        assert!(KalmanTrendState::new(1.0, -1.0).is_err());
+    }
+}
@@ -4,7 +4,10 @@
 //! It adapts to price movements by speeding up when prices are trending and
 //! slowing down when prices are moving sideways.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Kaufman Adaptive Moving Average (KAMA)
 ///
@@ -25,6 +28,9 @@ use crate::common::{TAError, TAResult};
 /// KAMA[today] = KAMA[yesterday] + SC × (Price[today] - KAMA[yesterday])
 /// ```
 ///
+/// The first `period` values are NaN; the first KAMA updates the price before it, as in
+/// TA-Lib.
+///
 /// # Arguments
 /// * `close` - Slice of closing prices
 /// * `period` - Period for efficiency ratio calculation (typically 10)
@@ -42,68 +48,48 @@ use crate::common::{TAError, TAResult};
 /// let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0, 18.0, 17.0];
 /// let result = kama(&close, 10, 2, 30).unwrap();
 /// ```
-pub fn kama(close: &[f64], period: usize, fast_period: usize, slow_period: usize) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if period == 0 || fast_period == 0 || slow_period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
-    
-    if period >= close.len() {
-        return Err(TAError::insufficient_data(period, close.len()));
-    }
-    
-    if fast_period >= slow_period {
-        return Err(TAError::invalid_parameter("period", "less than slow period"));
-    }
+pub fn kama(close: &[f64], period: impl IntoParam<Period>, fast_period: impl IntoParam<Period>, slow_period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("KAMA", || {
+        let period = period.into_param("period")?.get();
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    let len = close.len();
-    let mut result = vec![f64::NAN; len];
+        if period >= close.len() {
+            return Err(TAError::insufficient_data(period, close.len()));
+        }
     
-    // Calculate smoothing constants
-    let fast_sc = 2.0 / (fast_period as f64 + 1.0);
-    let slow_sc = 2.0 / (slow_period as f64 + 1.0);
-    let sc_diff = fast_sc - slow_sc;
+        if fast_period >= slow_period {
+            return Err(TAError::invalid_parameter("period", "less than slow period"));
+        }
     
-    // Initialize KAMA with first valid value (SMA of first period+1 values)
-    let start_idx = period;
-    if start_idx >= len {
-        return Ok(result);
-    }
+        let len = close.len();
+        let mut result = vec![f64::NAN; len];
     
-    // Initialize KAMA with simple average of first period+1 values
-    let mut kama = close[0..=start_idx].iter().sum::<f64>() / (start_idx + 1) as f64;
-    result[start_idx] = kama;
+        // Calculate smoothing constants
+        let fast_sc = 2.0 / (fast_period as f64 + 1.0);
+        let slow_sc = 2.0 / (slow_period as f64 + 1.0);
+        let sc_diff = fast_sc - slow_sc;
     
-    // Calculate KAMA for remaining values
-    for i in (start_idx + 1)..len {
-        // Calculate direction (change over period)
-        let direction = (close[i] - close[i - period]).abs();
-        
-        // Calculate volatility (sum of absolute changes over period)
-        let mut volatility = 0.0;
-        for j in (i - period + 1)..=i {
-            volatility += (close[j] - close[j - 1]).abs();
-        }
+        // Seeded with the price before the first value, as in TA-Lib
+        let mut kama = close[period - 1];
+        for i in period..len {
+            // Direction (change over period) and volatility (sum of absolute changes)
+            let direction = (close[i] - close[i - period]).abs();
+            let volatility: f64 = close[i - period..=i].windows(2).map(|w| (w[1] - w[0]).abs()).sum();
         
-        // Calculate efficiency ratio
-        let er = if volatility.abs() < f64::EPSILON {
-            0.0  // No volatility means no efficiency
-        } else {
-            direction / volatility
-        };
+            // A window without movement counts as fully efficient, as in TA-Lib
+            let er = if volatility == 0.0 { 1.0 } else { (direction / volatility).min(1.0) };
+            let sc = (er * sc_diff + slow_sc).powi(2);
         
-        // Calculate smoothing constant
-        let sc = (er * sc_diff + slow_sc).powi(2);
-        
-        // Update KAMA
-        kama = kama + sc * (close[i] - kama);
-        result[i] = kama;
-    }
+            kama += sc * (close[i] - kama);
+            result[i] = kama;
+        }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// KAMA with default parameters (10, 2, 30)
@@ -132,41 +118,40 @@ pub fn kama_default(close: &[f64]) -> TAResult<Vec<f64>> {
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of efficiency ratio values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn kama_efficiency_ratio(close: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn kama_efficiency_ratio(close: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("KAMA", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    if period >= close.len() {
-        return Err(TAError::insufficient_data(period, close.len()));
-    }
+        if period >= close.len() {
+            return Err(TAError::insufficient_data(period, close.len()));
+        }
     
-    let len = close.len();
-    let mut result = vec![f64::NAN; len];
+        let len = close.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in period..len {
-        // Calculate direction (change over period)
-        let direction = (close[i] - close[i - period]).abs();
+        for i in period..len {
+            // Calculate direction (change over period)
+            let direction = (close[i] - close[i - period]).abs();
         
-        // Calculate volatility (sum of absolute changes over period)
-        let mut volatility = 0.0;
-        for j in (i - period + 1)..=i {
-            volatility += (close[j] - close[j - 1]).abs();
-        }
+            // Calculate volatility (sum of absolute changes over period)
+            let mut volatility = 0.0;
+            for j in (i - period + 1)..=i {
+                volatility += (close[j] - close[j - 1]).abs();
+            }
         
-        // Calculate efficiency ratio
-        result[i] = if volatility.abs() < f64::EPSILON {
-            0.0
-        } else {
-            direction / volatility
-        };
-    }
+            // Calculate efficiency ratio
+            result[i] = if volatility.abs() < f64::EPSILON {
+                0.0
+            } else {
+                direction / volatility
+            };
+        }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
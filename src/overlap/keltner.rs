@@ -0,0 +1,238 @@
+//! Keltner Channels
+//!
+//! Keltner Channels are volatility envelopes placed around an EMA of the close, offset by a
+//! multiple of the Average True Range. Compared with Bollinger Bands they react less to single
+//! large closes, and a Bollinger Band contracting inside the Keltner Channel is the classic
+//! "squeeze" setup.
+
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::overlap::{ema, BollingerBands};
+use crate::volatility::atr;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Keltner Channels result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeltnerChannels {
+    /// Upper channel values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub upper: Vec<f64>,
+    /// Middle line values (EMA of close)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub middle: Vec<f64>,
+    /// Lower channel values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub lower: Vec<f64>,
+}
+
+/// Keltner Channels
+///
+/// # Formula
+/// ```text
+/// Middle = EMA(Close, ema_period)
+/// Upper  = Middle + multiplier × ATR(atr_period)
+/// Lower  = Middle - multiplier × ATR(atr_period)
+/// ```
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `ema_period` - Period of the middle line EMA (typically 20)
+/// * `atr_period` - Period of the ATR (typically 10 or 20)
+/// * `multiplier` - ATR multiple for the channel width (typically 2.0)
+///
+/// # Returns
+/// * `Ok(KeltnerChannels)` - Structure containing upper, middle, and lower channels;
+///   the first `max(ema_period, atr_period) - 1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::keltner;
+///
+/// let high = vec![11.0, 12.0, 13.0, 12.5, 13.5, 14.0, 13.0, 12.0, 12.5, 13.0];
+/// let low = vec![9.0, 10.0, 11.0, 10.5, 11.5, 12.0, 11.0, 10.0, 10.5, 11.0];
+/// let close = vec![10.0, 11.0, 12.0, 11.5, 12.5, 13.0, 12.0, 11.0, 11.5, 12.0];
+/// let result = keltner(&high, &low, &close, 5, 5, 2.0).unwrap();
+/// assert!(result.upper[4] > result.middle[4]);
+/// ```
+pub fn keltner(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    ema_period: impl IntoParam<Period>,
+    atr_period: impl IntoParam<Period>,
+    multiplier: f64,
+) -> TAResult<KeltnerChannels> {
+    with_function("KELTNER", || {
+        let ema_period = ema_period.into_param("ema_period")?.get();
+        let atr_period = atr_period.into_param("atr_period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+
+        if !(multiplier >= 0.0 && multiplier.is_finite()) {
+            return Err(TAError::invalid_parameter("multiplier", "must be finite and non-negative"));
+        }
+
+        let middle = ema(close, ema_period)?;
+        let range = atr(high, low, close, atr_period)?;
+
+        let len = close.len();
+        let mut upper = vec![f64::NAN; len];
+        let mut lower = vec![f64::NAN; len];
+
+        for (i, (&mid, &atr_val)) in middle.iter().zip(&range).enumerate() {
+            if !mid.is_nan() && !atr_val.is_nan() {
+                upper[i] = mid + multiplier * atr_val;
+                lower[i] = mid - multiplier * atr_val;
+            }
+        }
+
+        // Report the middle line only where the channel is defined
+        let mut middle = middle;
+        for (mid, up) in middle.iter_mut().zip(&upper) {
+            if up.is_nan() {
+                *mid = f64::NAN;
+            }
+        }
+
+        Ok(KeltnerChannels {
+            upper,
+            middle,
+            lower,
+        })
+    })
+}
+
+/// Keltner Channels with default parameters (20 period EMA, 10 period ATR, 2.0 multiplier)
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+///
+/// # Returns
+/// * `Ok(KeltnerChannels)` - Structure containing upper, middle, and lower channels
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn keltner_default(high: &[f64], low: &[f64], close: &[f64]) -> TAResult<KeltnerChannels> {
+    keltner(high, low, close, 20, 10, 2.0)
+}
+
+/// Bollinger Band / Keltner Channel squeeze
+///
+/// A bar is "in the squeeze" when both Bollinger Bands lie inside the Keltner Channel,
+/// i.e. volatility has contracted relative to the average range. Bars where either
+/// indicator is undefined are reported as `false`.
+///
+/// # Arguments
+/// * `bands` - Bollinger Bands
+/// * `channels` - Keltner Channels over the same bars
+///
+/// # Returns
+/// * `Ok(Vec<bool>)` - `true` where the squeeze is on
+/// * `Err(TAError)` - Error if the inputs have different lengths
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::{bbands, keltner, squeeze};
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.5).sin()).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 2.0).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 2.0).collect();
+///
+/// let bands = bbands(&close, 20, 2.0).unwrap();
+/// let channels = keltner(&high, &low, &close, 20, 10, 1.5).unwrap();
+/// let on = squeeze(&bands, &channels).unwrap();
+/// assert!(on[39]);
+/// ```
+pub fn squeeze(bands: &BollingerBands, channels: &KeltnerChannels) -> TAResult<Vec<bool>> {
+    with_function("SQUEEZE", || {
+        if bands.upper.len() != channels.upper.len() {
+            return Err(TAError::mismatched_inputs("Bollinger Bands and Keltner Channels must have the same length"));
+        }
+
+        Ok(bands
+            .upper
+            .iter()
+            .zip(&bands.lower)
+            .zip(channels.upper.iter().zip(&channels.lower))
+            .map(|((&bb_up, &bb_lo), (&kc_up, &kc_lo))| bb_up < kc_up && bb_lo > kc_lo)
+            .collect())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::overlap::bbands;
+
+    fn sample() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let close: Vec<f64> = (0..30).map(|i| 50.0 + (i as f64 * 0.3).sin() * 4.0).collect();
+        let high = close.iter().map(|c| c + 1.0).collect();
+        let low = close.iter().map(|c| c - 1.5).collect();
+        (high, low, close)
+    }
+
+    #[test]
+    fn test_keltner_basic() {
+        let (high, low, close) = sample();
+        let result = keltner(&high, &low, &close, 10, 5, 2.0).unwrap();
+
+        let middle = ema(&close, 10).unwrap();
+        let range = atr(&high, &low, &close, 5).unwrap();
+
+        for i in 0..9 {
+            assert!(result.upper[i].is_nan());
+            assert!(result.middle[i].is_nan());
+            assert!(result.lower[i].is_nan());
+        }
+        for i in 9..30 {
+            assert_float_eq!(result.middle[i], middle[i], 1e-12);
+            assert_float_eq!(result.upper[i], middle[i] + 2.0 * range[i], 1e-12);
+            assert_float_eq!(result.lower[i], middle[i] - 2.0 * range[i], 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_keltner_warmup_follows_longer_period() {
+        let (high, low, close) = sample();
+        let result = keltner(&high, &low, &close, 5, 14, 1.5).unwrap();
+        assert!(result.middle[12].is_nan());
+        assert!(!result.middle[13].is_nan());
+    }
+
+    #[test]
+    fn test_keltner_invalid_input() {
+        let (high, low, close) = sample();
+        assert!(keltner(&[], &[], &[], 10, 5, 2.0).is_err());
+        assert!(keltner(&high[..10], &low, &close, 10, 5, 2.0).is_err());
+        assert!(keltner(&high, &low, &close, 10, 5, -1.0).is_err());
+        assert!(keltner(&high, &low, &close, 10, 5, f64::NAN).is_err());
+        assert!(keltner(&high, &low, &close, 10, 5, f64::INFINITY).is_err());
+        assert!(keltner(&high, &low, &close, 0, 5, 2.0).is_err());
+        assert!(keltner(&high, &low, &close, 40, 5, 2.0).is_err());
+    }
+
+    #[test]
+    fn test_squeeze() {
+        let (high, low, close) = sample();
+        let bands = bbands(&close, 10, 2.0).unwrap();
+        let narrow = keltner(&high, &low, &close, 10, 5, 0.1).unwrap();
+        let wide = keltner(&high, &low, &close, 10, 5, 10.0).unwrap();
+
+        assert!(squeeze(&bands, &narrow).unwrap().iter().all(|&on| !on));
+        let on = squeeze(&bands, &wide).unwrap();
+        assert!(!on[8]);
+        assert!(on[9..].iter().all(|&on| on));
+
+        let short = keltner(&high[..20], &low[..20], &close[..20], 10, 5, 2.0).unwrap();
+        assert!(squeeze(&bands, &short).is_err());
+    }
+}
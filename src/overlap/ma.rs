@@ -1,8 +1,13 @@
 //! Generic Moving Average (MA)
 
-use crate::common::{TAResult, Price, Period, MAType};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data};
-use crate::overlap::{sma, ema, wma, dema, tema, trima};
+use crate::common::{TAResult, Price, MAType, NeumaierSum, IntoParam, Period, TAOutput, ma_lookback, mama_lookback};
+use crate::common::errors::with_function;
+use crate::common::window::{RingBuffer, RunningSum};
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, ema_multiplier};
+use crate::common::defaults;
+use crate::overlap::{sma, ema, wma, dema, tema, trima, kama, mama, t3};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates a Moving Average using the specified type
 ///
@@ -18,11 +23,15 @@ use crate::overlap::{sma, ema, wma, dema, tema, trima};
 /// # Returns
 /// Vector of MA values. The number of leading NaN values depends on the MA type.
 ///
+/// Like TA-Lib's `TA_MA`, KAMA uses the default fast and slow periods (2 and 30), T3 the
+/// default volume factor (0.7), and MAMA the default limits (0.5 and 0.05), ignoring
+/// `period`; call [`kama`](crate::overlap::kama), [`t3`](crate::overlap::t3) or
+/// [`mama`](crate::overlap::mama) directly for other settings.
+///
 /// # Errors
 /// - `EmptyInput` if data is empty
 /// - `InvalidParameter` if period is 0
 /// - `InsufficientData` if data length is insufficient for the MA type
-/// - `UnsupportedOperation` if MA type is not yet implemented
 ///
 /// # Example
 /// ```rust
@@ -40,50 +49,67 @@ use crate::overlap::{sma, ema, wma, dema, tema, trima};
 /// // Calculate WMA
 /// let wma_result = ma(&prices, 3, MAType::WMA).unwrap();
 /// ```
-pub fn ma(data: &[Price], period: Period, ma_type: MAType) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
+pub fn ma(data: &[Price], period: impl IntoParam<Period>, ma_type: MAType) -> TAResult<Vec<Price>> {
+    with_function("MA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
     
-    // Check minimum data requirements based on MA type
-    let min_required = match ma_type {
+        validate_sufficient_data(data, min_required(period, ma_type), "data")?;
+
+        // Dispatch to appropriate MA function
+        match ma_type {
+            MAType::SMA => sma::sma(data, period),
+            MAType::EMA => ema::ema(data, period),
+            MAType::WMA => wma::wma(data, period),
+            MAType::DEMA => dema::dema(data, period),
+            MAType::TEMA => tema::tema(data, period),
+            MAType::TRIMA => trima::trima(data, period),
+            MAType::KAMA => kama::kama(data, period, defaults::KAMA_FAST_PERIOD, defaults::KAMA_SLOW_PERIOD),
+            MAType::MAMA => mama::mama(data, defaults::MAMA_FAST_LIMIT, defaults::MAMA_SLOW_LIMIT).map(|result| result.mama),
+            MAType::T3 => t3::t3(data, period, defaults::T3_VOLUME_FACTOR),
+        }
+    })
+}
+
+/// Calculates the moving average with where its values start
+///
+/// Same as [`ma`], wrapped in a [`TAOutput`] with the lookback of [`ma_lookback`](crate::common::lookback::ma_lookback).
+///
+/// # Errors
+/// Same as [`ma`]
+pub fn ma_ex(data: &[Price], period: impl IntoParam<Period>, ma_type: MAType) -> TAResult<TAOutput> {
+    with_function("MA", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(ma(data, period, ma_type)?, ma_lookback(period, ma_type)))
+    })
+}
+
+/// Minimum data length required by each MA type
+fn min_required(period: usize, ma_type: MAType) -> usize {
+    match ma_type {
         MAType::SMA | MAType::EMA | MAType::WMA | MAType::TRIMA => period,
         MAType::DEMA => 2 * period - 1,
         MAType::TEMA => 3 * period - 2,
         MAType::KAMA => period + 1, // KAMA needs extra data for efficiency ratio
-        MAType::MAMA => 32, // MAMA requires significant data for Hilbert Transform
-        MAType::T3 => 6 * period, // T3 requires multiple EMA calculations
-    };
-    
-    validate_sufficient_data(data, min_required, "data")?;
+        MAType::MAMA => mama_lookback() + 1, // Values start once the Hilbert Transform settles
+        MAType::T3 => 6 * (period - 1) + 1, // Six chained EMAs
+    }
+}
 
-    // Dispatch to appropriate MA function
-    match ma_type {
-        MAType::SMA => sma::sma(data, period),
-        MAType::EMA => ema::ema(data, period),
-        MAType::WMA => wma::wma(data, period),
-        MAType::DEMA => dema::dema(data, period),
-        MAType::TEMA => tema::tema(data, period),
-        MAType::TRIMA => trima::trima(data, period),
-        MAType::KAMA => {
-            // KAMA will be implemented in a later phase
-            Err(crate::common::TAError::unsupported_operation(
-                "KAMA not yet implemented - will be available in Phase 5"
-            ))
-        },
-        MAType::MAMA => {
-            // MAMA will be implemented in Phase 6 (Hilbert Transform)
-            Err(crate::common::TAError::unsupported_operation(
-                "MAMA not yet implemented - will be available in Phase 6"
-            ))
-        },
-        MAType::T3 => {
-            // T3 will be implemented in Phase 5
-            Err(crate::common::TAError::unsupported_operation(
-                "T3 not yet implemented - will be available in Phase 5"
-            ))
-        },
+/// Moving average of a series that starts with NaN, such as the output of another indicator
+///
+/// The leading NaNs are skipped and the average starts from the first value after them, so
+/// recursive types (EMA, DEMA, TEMA) do not carry the NaN prefix through the whole output.
+/// The output is all NaN when too few values follow the prefix.
+pub(crate) fn ma_skip_nan(data: &[Price], period: usize, ma_type: MAType) -> TAResult<Vec<Price>> {
+    let start = data.iter().take_while(|v| v.is_nan()).count();
+    let mut output = vec![Price::NAN; data.len()];
+    if data.len() - start >= min_required(period, ma_type) {
+        let values = ma(&data[start..], period, ma_type)?;
+        output[start..].copy_from_slice(&values);
     }
+    Ok(output)
 }
 
 /// Calculates multiple moving averages at once
@@ -117,7 +143,7 @@ pub fn ma(data: &[Price], period: Period, ma_type: MAType) -> TAResult<Vec<Price
 /// ```
 pub fn ma_multiple(
     data: &[Price], 
-    period: Period, 
+    period: usize, 
     ma_types: &[MAType]
 ) -> Vec<(MAType, TAResult<Vec<Price>>)> {
     ma_types.iter()
@@ -142,29 +168,31 @@ pub fn ma_multiple(
 /// - Trending data: EMA (good trend following)
 /// - Stable data: SMA (smooth)
 /// - Very noisy data: TRIMA (maximum smoothing)
-pub fn ma_auto(data: &[Price], period: Period) -> TAResult<(MAType, Vec<Price>)> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period * 2, "data")?; // Need extra data for analysis
+pub fn ma_auto(data: &[Price], period: impl IntoParam<Period>) -> TAResult<(MAType, Vec<Price>)> {
+    with_function("MA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period * 2, "data")?; // Need extra data for analysis
     
-    // Analyze data characteristics
-    let volatility = calculate_volatility(data);
-    let trend_strength = calculate_trend_strength(data);
+        // Analyze data characteristics
+        let volatility = calculate_volatility(data);
+        let trend_strength = calculate_trend_strength(data);
     
-    // Select MA type based on characteristics
-    let selected_type = if volatility > 0.05 && trend_strength > 0.3 {
-        MAType::WMA // High volatility + trending: use WMA
-    } else if trend_strength > 0.2 {
-        MAType::EMA // Trending: use EMA
-    } else if volatility < 0.02 {
-        MAType::SMA // Low volatility: use SMA
-    } else {
-        MAType::TRIMA // Noisy data: use TRIMA for smoothing
-    };
+        // Select MA type based on characteristics
+        let selected_type = if volatility > 0.05 && trend_strength > 0.3 {
+            MAType::WMA // High volatility + trending: use WMA
+        } else if trend_strength > 0.2 {
+            MAType::EMA // Trending: use EMA
+        } else if volatility < 0.02 {
+            MAType::SMA // Low volatility: use SMA
+        } else {
+            MAType::TRIMA // Noisy data: use TRIMA for smoothing
+        };
     
-    let result = ma(data, period, selected_type)?;
-    Ok((selected_type, result))
+        let result = ma(data, period, selected_type)?;
+        Ok((selected_type, result))
+    })
 }
 
 /// Calculates data volatility for MA type selection
@@ -199,6 +227,209 @@ fn calculate_trend_strength(data: &[Price]) -> Price {
     ((late_avg - early_avg) / early_avg).abs()
 }
 
+/// Streaming Moving Average of any supported type
+///
+/// Produces the same values as [`ma`] one price at a time, in O(1) time per update and
+/// O(period) memory for the window-based types (SMA, WMA, TRIMA).
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::MAType;
+/// use ta_rust::overlap::{ma, MaState};
+///
+/// let prices = vec![10.0, 10.5, 11.0, 10.8, 11.2, 11.6, 11.4, 12.0, 12.3, 12.1];
+/// let batch = ma(&prices, 3, MAType::DEMA).unwrap();
+///
+/// let mut state = MaState::new(3, MAType::DEMA).unwrap();
+/// for (i, &price) in prices.iter().enumerate() {
+///     match state.update(price) {
+///         Some(value) => assert!((value - batch[i]).abs() < 1e-12),
+///         None => assert!(batch[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaState {
+    period: usize,
+    ma_type: MAType,
+    stages: Stages,
+    /// Current value, once warmed up
+    value: Option<Price>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Stages {
+    Sma(SmaStage),
+    Ema(EmaStage),
+    Wma(WmaStage),
+    Dema(EmaStage, EmaStage),
+    Tema(EmaStage, EmaStage, EmaStage),
+    Trima(SmaStage, SmaStage),
+}
+
+impl MaState {
+    /// Creates a new state for the given period and moving average type
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    /// - `UnsupportedOperation` for KAMA, MAMA and T3, which have no streaming form yet
+    pub fn new(period: impl IntoParam<Period>, ma_type: MAType) -> TAResult<Self> {
+        with_function("MA", || {
+            let period = period.into_param("period")?.get();
+
+            let stages = match ma_type {
+                MAType::SMA => Stages::Sma(SmaStage::new(period)),
+                MAType::EMA => Stages::Ema(EmaStage::new(period)),
+                MAType::WMA => Stages::Wma(WmaStage::new(period)),
+                MAType::DEMA => Stages::Dema(EmaStage::new(period), EmaStage::new(period)),
+                MAType::TEMA => Stages::Tema(EmaStage::new(period), EmaStage::new(period), EmaStage::new(period)),
+                MAType::TRIMA => {
+                    // Same split of the period into two SMAs as `trima`
+                    let (first, second) = if period % 2 == 1 {
+                        (period.div_ceil(2), period.div_ceil(2))
+                    } else {
+                        (period / 2 + 1, period / 2)
+                    };
+                    Stages::Trima(SmaStage::new(first), SmaStage::new(second))
+                }
+                MAType::KAMA | MAType::MAMA | MAType::T3 => {
+                    return Err(crate::common::TAError::unsupported_operation(format!(
+                        "Streaming {} is not supported",
+                        ma_type
+                    )));
+                }
+            };
+
+            Ok(Self { period, ma_type, stages, value: None })
+        })
+    }
+
+    /// Feeds the next price and returns the updated value, or `None` during warm-up
+    pub fn update(&mut self, price: Price) -> Option<Price> {
+        self.value = match &mut self.stages {
+            Stages::Sma(sma) => sma.update(price),
+            Stages::Ema(ema) => ema.update(price),
+            Stages::Wma(wma) => wma.update(price),
+            Stages::Dema(ema1, ema2) => ema1.update(price).and_then(|e1| {
+                let e2 = ema2.update(e1)?;
+                Some(2.0 * e1 - e2)
+            }),
+            Stages::Tema(ema1, ema2, ema3) => ema1.update(price).and_then(|e1| {
+                let e2 = ema2.update(e1)?;
+                let e3 = ema3.update(e2)?;
+                Some(3.0 * e1 - 3.0 * e2 + e3)
+            }),
+            Stages::Trima(sma1, sma2) => sma1.update(price).and_then(|s1| sma2.update(s1)),
+        };
+        self.value
+    }
+
+    /// Current value, or `None` during warm-up
+    pub fn current(&self) -> Option<Price> {
+        self.value
+    }
+
+    /// Period of the moving average
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Type of the moving average
+    pub fn ma_type(&self) -> MAType {
+        self.ma_type
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period, self.ma_type).expect("parameters were validated on creation");
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct SmaStage {
+    window: RingBuffer<Price>,
+    sum: RunningSum,
+}
+
+impl SmaStage {
+    fn new(period: usize) -> Self {
+        Self { window: RingBuffer::new(period), sum: RunningSum::new() }
+    }
+
+    fn update(&mut self, price: Price) -> Option<Price> {
+        self.window.push_with(price, &mut self.sum);
+        self.window.is_full().then(|| self.sum.value() / self.window.len() as Price)
+    }
+}
+
+/// EMA seeded with the SMA of the first `period` values, like [`ema`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct EmaStage {
+    period: usize,
+    count: usize,
+    seed_sum: Price,
+    value: Option<Price>,
+}
+
+impl EmaStage {
+    fn new(period: usize) -> Self {
+        Self { period, count: 0, seed_sum: 0.0, value: None }
+    }
+
+    fn update(&mut self, price: Price) -> Option<Price> {
+        self.value = match self.value {
+            Some(prev) => {
+                let multiplier = ema_multiplier(self.period);
+                Some(price * multiplier + prev * (1.0 - multiplier))
+            }
+            None => {
+                self.count += 1;
+                self.seed_sum += price;
+                (self.count == self.period).then(|| self.seed_sum / self.period as Price)
+            }
+        };
+        self.value
+    }
+}
+
+/// WMA keeping the weighted sum up to date as the window slides
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct WmaStage {
+    window: RingBuffer<Price>,
+    /// Plain sum of the window
+    sum: NeumaierSum,
+    /// Sum of the window weighted 1 (oldest) to period (newest)
+    weighted: NeumaierSum,
+}
+
+impl WmaStage {
+    fn new(period: usize) -> Self {
+        Self { window: RingBuffer::new(period), sum: NeumaierSum::new(), weighted: NeumaierSum::new() }
+    }
+
+    fn update(&mut self, price: Price) -> Option<Price> {
+        let period = self.window.capacity();
+        match self.window.push(price) {
+            // Every value loses one unit of weight, the evicted one its last
+            Some(evicted) => {
+                self.weighted.sub(self.sum.value());
+                self.weighted.add(period as Price * price);
+                self.sum.sub(evicted);
+            }
+            // Until full, the window fills from the oldest weight upwards
+            None => self.weighted.add(self.window.len() as Price * price),
+        }
+        self.sum.add(price);
+        let weight_sum = (period * (period + 1)) as Price / 2.0;
+        self.window.is_full().then(|| self.weighted.value() / weight_sum)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,13 +484,23 @@ mod tests {
     }
 
     #[test]
-    fn test_ma_unsupported_types() {
-        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        
-        // These should return errors as they're not implemented yet
-        assert!(ma(&data, 3, MAType::KAMA).is_err());
-        assert!(ma(&data, 3, MAType::MAMA).is_err());
-        assert!(ma(&data, 3, MAType::T3).is_err());
+    fn test_ma_adaptive_types() {
+        let data: Vec<f64> = (0..60).map(|i| 50.0 + (i as f64 * 0.3).sin() * 4.0).collect();
+
+        // Default settings of the dedicated functions, as in TA-Lib
+        let result = ma(&data, 10, MAType::KAMA).unwrap();
+        assert_arrays_approx_equal(&result, &kama::kama(&data, 10, 2, 30).unwrap(), DEFAULT_TOLERANCE);
+        let result = ma(&data, 5, MAType::T3).unwrap();
+        assert_arrays_approx_equal(&result, &t3::t3(&data, 5, 0.7).unwrap(), DEFAULT_TOLERANCE);
+        let result = ma(&data, 30, MAType::MAMA).unwrap();
+        assert_arrays_approx_equal(&result, &mama::mama(&data, 0.5, 0.05).unwrap().mama, DEFAULT_TOLERANCE);
+
+        // Each still needs enough data
+        let short = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(ma(&short, 5, MAType::KAMA).is_err());
+        assert!(ma(&short, 3, MAType::MAMA).is_err());
+        assert!(ma(&short, 2, MAType::T3).is_err());
+        assert!(ma(&short, 1, MAType::T3).is_ok());
     }
 
     #[test]
@@ -365,4 +606,41 @@ mod tests {
         assert!(all_types.contains(&MAType::MAMA));
         assert!(all_types.contains(&MAType::T3));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ma_skip_nan() {
+        let data = vec![f64::NAN, f64::NAN, 1.0, 2.0, 3.0, 4.0, 5.0];
+        for ma_type in [MAType::SMA, MAType::EMA, MAType::DEMA] {
+            let result = ma_skip_nan(&data, 2, ma_type).unwrap();
+            let expected = ma(&data[2..], 2, ma_type).unwrap();
+            let lead = 2 + expected.iter().take_while(|v| v.is_nan()).count();
+            assert!(result[..lead].iter().all(|v| v.is_nan()));
+            assert_eq!(&result[lead..], &expected[lead - 2..]);
+        }
+        assert!(ma_skip_nan(&data, 6, MAType::EMA).unwrap().iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_ma_state_matches_batch() {
+        let data: Vec<Price> = (0..80).map(|i| 50.0 + (i as Price * 0.4).sin() * 4.0 + i as Price * 0.05).collect();
+        let supported = [MAType::SMA, MAType::EMA, MAType::WMA, MAType::DEMA, MAType::TEMA, MAType::TRIMA];
+        for ma_type in supported {
+            for period in [1, 2, 5, 8] {
+                let batch = ma(&data, period, ma_type).unwrap();
+                let mut state = MaState::new(period, ma_type).unwrap();
+                for (i, &price) in data.iter().enumerate() {
+                    match state.update(price) {
+                        Some(value) => assert!((value - batch[i]).abs() < 1e-9, "{} {} at {}", ma_type, period, i),
+                        None => assert!(batch[i].is_nan(), "{} {} at {}", ma_type, period, i),
+                    }
+                }
+                assert!((state.current().unwrap() - batch[79]).abs() < 1e-9);
+                state.reset();
+                assert_eq!(state, MaState::new(period, ma_type).unwrap());
+            }
+        }
+
+        assert!(MaState::new(0, MAType::SMA).is_err());
+        assert!(MaState::new(5, MAType::KAMA).is_err());
+    }
+}
@@ -5,27 +5,44 @@
 //! It provides both MAMA and FAMA (Following Adaptive Moving Average) outputs.
 
 use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::common::mama_lookback;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// MAMA result structure
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MamaResult {
     /// MESA Adaptive Moving Average values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
     pub mama: Vec<f64>,
     /// Following Adaptive Moving Average values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
     pub fama: Vec<f64>,
+    /// Dominant cycle period measured by the Hilbert Transform
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub period: Vec<f64>,
+    /// Dominant cycle period after a further smoothing pass
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub smooth_period: Vec<f64>,
 }
 
 /// MESA Adaptive Moving Average (MAMA)
 ///
 /// MAMA uses Hilbert Transform to calculate the dominant cycle period and adapts
 /// its smoothing factor accordingly. FAMA is a slower version that follows MAMA.
+/// The calculation follows TA-Lib's: the Hilbert Transform runs on a 4-bar WMA of the
+/// prices with separate histories for even and odd bars, both averages start from 0,
+/// and values are output from bar 32, once the start has decayed.
 ///
 /// # Formula
 /// ```text
-/// Uses Hilbert Transform to calculate adaptive period
-/// Smoothing Factor = adaptive based on dominant cycle
-/// MAMA = α × Price + (1 - α) × MAMA[prev]
-/// FAMA = 0.5 × α × MAMA + (1 - 0.5 × α) × FAMA[prev]
+/// Phase  = atan(Q1 / I1) in degrees
+/// ΔPhase = max(Phase[prev] - Phase, 1)
+/// α      = max(fast_limit / ΔPhase, slow_limit), or fast_limit when ΔPhase is 1
+/// MAMA   = α × Price + (1 - α) × MAMA[prev]
+/// FAMA   = 0.5 × α × MAMA + (1 - 0.5 × α) × FAMA[prev]
 /// ```
 ///
 /// # Arguments
@@ -34,155 +51,171 @@ pub struct MamaResult {
 /// * `slow_limit` - Slow limit for smoothing factor (typically 0.05)
 ///
 /// # Returns
-/// * `Ok(MamaResult)` - Structure containing MAMA and FAMA values
+/// * `Ok(MamaResult)` - Structure containing MAMA and FAMA values and the dominant cycle periods
 /// * `Err(TAError)` - Error if inputs are invalid
 ///
 /// # Examples
 /// ```
 /// use ta_rust::overlap::mama;
 ///
-/// // MAMA requires at least 32 data points
+/// // MAMA requires at least 33 data points
 /// let close: Vec<f64> = (0..50).map(|i| 20.0 + (i as f64 * 0.1).sin()).collect();
 /// let result = mama(&close, 0.5, 0.05).unwrap();
 /// // result.mama contains the adaptive moving average
 /// // result.fama contains the following adaptive moving average
+/// // result.period and result.smooth_period contain the dominant cycle period
 /// ```
 pub fn mama(close: &[f64], fast_limit: f64, slow_limit: f64) -> TAResult<MamaResult> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
+    with_function("MAMA", || {
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    if fast_limit <= 0.0 || slow_limit <= 0.0 {
-        return Err(TAError::invalid_parameter("parameter", "must be greater than 0"));
-    }
+        if fast_limit <= 0.0 || slow_limit <= 0.0 {
+            return Err(TAError::invalid_parameter("parameter", "must be greater than 0"));
+        }
     
-    if fast_limit <= slow_limit {
-        return Err(TAError::invalid_input("Fast limit must be greater than slow limit"));
-    }
+        if fast_limit <= slow_limit {
+            return Err(TAError::invalid_input("Fast limit must be greater than slow limit"));
+        }
     
-    if fast_limit > 1.0 || slow_limit > 1.0 {
-        return Err(TAError::invalid_input("Limits must be <= 1.0"));
-    }
+        if fast_limit > 1.0 || slow_limit > 1.0 {
+            return Err(TAError::invalid_input("Limits must be <= 1.0"));
+        }
     
-    let len = close.len();
-    if len < 32 {
-        return Err(TAError::invalid_input("Need at least 32 data points for MAMA"));
-    }
+        let len = close.len();
+        if len <= mama_lookback() {
+            return Err(TAError::insufficient_data(mama_lookback() + 1, len));
+        }
     
-    let mut mama_values = vec![f64::NAN; len];
-    let mut fama_values = vec![f64::NAN; len];
+        let mut mama_values = vec![f64::NAN; len];
+        let mut fama_values = vec![f64::NAN; len];
+        let mut period_values = vec![f64::NAN; len];
+        let mut smooth_period_values = vec![f64::NAN; len];
     
-    // Initialize arrays for Hilbert Transform calculations
-    let mut smooth = vec![0.0; len];
-    let mut detrender = vec![0.0; len];
-    let mut i1 = vec![0.0; len];
-    let mut q1 = vec![0.0; len];
-    let mut ji = vec![0.0; len];
-    let mut jq = vec![0.0; len];
-    let mut i2 = vec![0.0; len];
-    let mut q2 = vec![0.0; len];
-    let mut re = vec![0.0; len];
-    let mut im = vec![0.0; len];
-    let mut period = vec![0.0; len];
-    let mut smooth_period = vec![0.0; len];
+        // Four bar WMA of the prices, kept as a running sum and a running sum of weights
+        let mut wma_sub = close[0] + close[1] + close[2];
+        let mut wma_sum = close[0] + 2.0 * close[1] + 3.0 * close[2];
+        let mut trailing = 0.0;
+        let mut trailing_idx = 0;
+        let mut smooth_price = |price: f64| {
+            wma_sub += price;
+            wma_sub -= trailing;
+            wma_sum += 4.0 * price;
+            trailing = close[trailing_idx];
+            trailing_idx += 1;
+            let smoothed = 0.1 * wma_sum;
+            wma_sum -= wma_sub;
+            smoothed
+        };
+        for &price in &close[3..12] {
+            smooth_price(price);
+        }
     
-    // Initialize MAMA and FAMA
-    let mut mama_val = close[0];
-    let mut fama_val = close[0];
+        let mut detrender = HilbertFilter::default();
+        let mut q1 = HilbertFilter::default();
+        let mut ji = HilbertFilter::default();
+        let mut jq = HilbertFilter::default();
+        let mut hilbert_idx = 0;
+        let (mut i1_odd_prev2, mut i1_odd_prev3) = (0.0, 0.0);
+        let (mut i1_even_prev2, mut i1_even_prev3) = (0.0, 0.0);
+        let (mut prev_i2, mut prev_q2) = (0.0, 0.0);
+        let (mut re, mut im) = (0.0, 0.0);
+        let (mut period, mut smooth_period) = (0.0, 0.0);
+        let (mut mama_val, mut fama_val) = (0.0, 0.0);
+        let mut prev_phase = 0.0;
     
-    // Start calculations from index 6 (need enough history for i-6 access)
-    for i in 6..len {
-        // Smooth the price data
-        smooth[i] = (4.0 * close[i] + 3.0 * close[i-1] + 2.0 * close[i-2] + close[i-3]) / 10.0;
-        
-        // Detrend the smoothed data
-        detrender[i] = (0.0962 * smooth[i] + 0.5769 * smooth[i-2] - 0.5769 * smooth[i-4] - 0.0962 * smooth[i-6]) * (0.075 * period[i-1] + 0.54);
-        
-        // Compute InPhase and Quadrature components
-        q1[i] = (0.0962 * detrender[i] + 0.5769 * detrender[i-2] - 0.5769 * detrender[i-4] - 0.0962 * detrender[i-6]) * (0.075 * period[i-1] + 0.54);
-        i1[i] = detrender[i-3];
-        
-        // Advance the phase of I1 and Q1 by 90 degrees
-        ji[i] = (0.0962 * i1[i] + 0.5769 * i1[i-2] - 0.5769 * i1[i-4] - 0.0962 * i1[i-6]) * (0.075 * period[i-1] + 0.54);
-        jq[i] = (0.0962 * q1[i] + 0.5769 * q1[i-2] - 0.5769 * q1[i-4] - 0.0962 * q1[i-6]) * (0.075 * period[i-1] + 0.54);
-        
-        // Phasor addition for 3 bar averaging
-        i2[i] = i1[i] - jq[i];
-        q2[i] = q1[i] + ji[i];
-        
-        // Smooth the I and Q components before applying the discriminator
-        i2[i] = 0.2 * i2[i] + 0.8 * i2[i-1];
-        q2[i] = 0.2 * q2[i] + 0.8 * q2[i-1];
-        
-        // Homodyne Discriminator
-        re[i] = i2[i] * i2[i-1] + q2[i] * q2[i-1];
-        im[i] = i2[i] * q2[i-1] - q2[i] * i2[i-1];
-        re[i] = 0.2 * re[i] + 0.8 * re[i-1];
-        im[i] = 0.2 * im[i] + 0.8 * im[i-1];
-        
-        // Compute the period
-        if im[i] != 0.0 && re[i] != 0.0 {
-            period[i] = 2.0 * std::f64::consts::PI / im[i].atan2(re[i]);
-        }
-        
-        // Constrain the period
-        if period[i] > 1.5 * period[i-1] {
-            period[i] = 1.5 * period[i-1];
-        }
-        if period[i] < 0.67 * period[i-1] {
-            period[i] = 0.67 * period[i-1];
-        }
-        if period[i] < 6.0 {
-            period[i] = 6.0;
-        }
-        if period[i] > 50.0 {
-            period[i] = 50.0;
-        }
-        
-        // Smooth the period
-        period[i] = 0.2 * period[i] + 0.8 * period[i-1];
-        smooth_period[i] = 0.33 * period[i] + 0.67 * smooth_period[i-1];
-        
-        // Compute the adaptive factor
-        let phase = if i1[i] != 0.0 {
-            q1[i].atan2(i1[i])
-        } else {
-            0.0
-        };
-        
-        let delta_phase = if i >= 1 {
-            let mut dp = phase - period[i-1];
-            if dp < 1.0 {
-                dp = 1.0;
+        for (i, &price) in close.iter().enumerate().skip(12) {
+            let adjusted_period = 0.075 * period + 0.54;
+            let smoothed = smooth_price(price);
+        
+            // Even and odd bars run separate filter histories, each two bars apart
+            let even = i % 2 == 0;
+            let parity = usize::from(!even);
+            let i1 = if even { i1_even_prev3 } else { i1_odd_prev3 };
+            let detrended = detrender.next(parity, hilbert_idx, smoothed, adjusted_period);
+            let q1_val = q1.next(parity, hilbert_idx, detrended, adjusted_period);
+            let ji_val = ji.next(parity, hilbert_idx, i1, adjusted_period);
+            let jq_val = jq.next(parity, hilbert_idx, q1_val, adjusted_period);
+            if even {
+                hilbert_idx = (hilbert_idx + 1) % 3;
+                i1_odd_prev3 = i1_odd_prev2;
+                i1_odd_prev2 = detrended;
+            } else {
+                i1_even_prev3 = i1_even_prev2;
+                i1_even_prev2 = detrended;
             }
-            dp
-        } else {
-            1.0
-        };
-        
-        let alpha = fast_limit / delta_phase;
-        let alpha = if alpha < slow_limit {
-            slow_limit
-        } else if alpha > fast_limit {
-            fast_limit
-        } else {
-            alpha
-        };
         
-        // Update MAMA and FAMA
-        mama_val = alpha * close[i] + (1.0 - alpha) * mama_val;
-        fama_val = 0.5 * alpha * mama_val + (1.0 - 0.5 * alpha) * fama_val;
-        
-        mama_values[i] = mama_val;
-        fama_values[i] = fama_val;
-    }
+            // Phasor addition for 3 bar averaging
+            let q2 = 0.2 * (q1_val + ji_val) + 0.8 * prev_q2;
+            let i2 = 0.2 * (i1 - jq_val) + 0.8 * prev_i2;
+        
+            // The phase change in degrees sets the smoothing factor
+            let phase = if i1 != 0.0 { (q1_val / i1).atan().to_degrees() } else { 0.0 };
+            let delta_phase = (prev_phase - phase).max(1.0);
+            prev_phase = phase;
+            let alpha = if delta_phase > 1.0 { (fast_limit / delta_phase).max(slow_limit) } else { fast_limit };
+        
+            mama_val = alpha * price + (1.0 - alpha) * mama_val;
+            fama_val = 0.5 * alpha * mama_val + (1.0 - 0.5 * alpha) * fama_val;
+        
+            // Homodyne Discriminator
+            re = 0.2 * (i2 * prev_i2 + q2 * prev_q2) + 0.8 * re;
+            im = 0.2 * (i2 * prev_q2 - q2 * prev_i2) + 0.8 * im;
+            prev_i2 = i2;
+            prev_q2 = q2;
+        
+            // Measure the period, constrained against the previous one, and smooth it
+            let prev_period = period;
+            if im != 0.0 && re != 0.0 {
+                period = 360.0 / (im / re).atan().to_degrees();
+            }
+            period = period.min(1.5 * prev_period).max(0.67 * prev_period).clamp(6.0, 50.0);
+            period = 0.2 * period + 0.8 * prev_period;
+            smooth_period = 0.33 * period + 0.67 * smooth_period;
+        
+            if i >= mama_lookback() {
+                mama_values[i] = mama_val;
+                fama_values[i] = fama_val;
+                period_values[i] = period;
+                smooth_period_values[i] = smooth_period;
+            }
+        }
     
-    Ok(MamaResult {
-        mama: mama_values,
-        fama: fama_values,
+        Ok(MamaResult {
+            mama: mama_values,
+            fama: fama_values,
+            period: period_values,
+            smooth_period: smooth_period_values,
+        })
     })
 }
 
+/// Hilbert Transform FIR filter of TA-Lib's MESA functions
+///
+/// Keeps one history per bar parity, so each output combines inputs 2, 4 and 6 bars back.
+#[derive(Default)]
+struct HilbertFilter {
+    scaled: [[f64; 3]; 2],
+    prev: [f64; 2],
+    prev_input: [f64; 2],
+}
+
+impl HilbertFilter {
+    fn next(&mut self, parity: usize, idx: usize, input: f64, adjusted_period: f64) -> f64 {
+        const A: f64 = 0.0962;
+        const B: f64 = 0.5769;
+        let scaled = A * input;
+        let mut value = scaled - self.scaled[parity][idx];
+        self.scaled[parity][idx] = scaled;
+        value -= self.prev[parity];
+        self.prev[parity] = B * self.prev_input[parity];
+        value += self.prev[parity];
+        self.prev_input[parity] = input;
+        value * adjusted_period
+    }
+}
+
 /// MAMA with default parameters (0.5, 0.05)
 ///
 /// This is a convenience function using the standard default parameters.
@@ -197,10 +230,11 @@ pub fn mama_default(close: &[f64]) -> TAResult<MamaResult> {
     mama(close, 0.5, 0.05)
 }
 
-/// Extract dominant cycle period from MAMA calculation
+/// Dominant cycle period from the MAMA calculation
 ///
-/// This function performs the same Hilbert Transform calculations as MAMA
-/// but returns the dominant cycle period instead of the moving averages.
+/// Runs the same Hilbert Transform pipeline as [`mama`] and returns its smoothed dominant
+/// cycle period, at most 50 bars. Use [`mama`] directly to get the moving averages,
+/// the raw period and the smoothed period from a single pass.
 ///
 /// # Arguments
 /// * `close` - Slice of closing prices
@@ -210,30 +244,22 @@ pub fn mama_default(close: &[f64]) -> TAResult<MamaResult> {
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of dominant cycle periods
 /// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::mama_period;
+///
+/// // A sine wave with a 20 bar cycle
+/// let close: Vec<f64> = (0..200)
+///     .map(|i| 100.0 + (i as f64 * 2.0 * std::f64::consts::PI / 20.0).sin())
+///     .collect();
+/// let periods = mama_period(&close, 0.5, 0.05).unwrap();
+/// assert!((periods[199] - 20.0).abs() < 3.0);
+/// ```
 pub fn mama_period(close: &[f64], fast_limit: f64, slow_limit: f64) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    let len = close.len();
-    if len < 32 {
-        return Err(TAError::invalid_input("Need at least 32 data points for period calculation"));
-    }
-    
-    // This is a simplified version - in practice, you'd extract the period
-    // calculation from the main MAMA function
-    let _result = mama(close, fast_limit, slow_limit)?;
-    
-    // For now, return a placeholder - in a full implementation,
-    // you'd return the actual period values calculated during MAMA
-    let mut periods = vec![f64::NAN; len];
-    
-    // Fill with estimated periods (this is a simplification)
-    for i in 6..len {
-        periods[i] = 20.0; // Default period estimate
-    }
-    
-    Ok(periods)
+    with_function("MAMA", || {
+        Ok(mama(close, fast_limit, slow_limit)?.smooth_period)
+    })
 }
 
 #[cfg(test)]
@@ -249,15 +275,15 @@ mod tests {
         assert_eq!(result.mama.len(), 50);
         assert_eq!(result.fama.len(), 50);
         
-        // First few values should be NaN
-        for i in 0..6 {
+        // Values start at bar 32, as in TA-Lib
+        for i in 0..32 {
             assert!(result.mama[i].is_nan());
             assert!(result.fama[i].is_nan());
         }
         
         // Later values should be valid
         let mut valid_count = 0;
-        for i in 6..50 {
+        for i in 32..50 {
             if !result.mama[i].is_nan() && !result.fama[i].is_nan() {
                 valid_count += 1;
                 // MAMA and FAMA should be reasonable values
@@ -290,6 +316,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mama_talib_values() {
+        // Reference values from TA-Lib's algorithm, whose averages start from 0
+        let close: Vec<f64> = (0..50).map(|i| 20.0 + (i as f64 * 0.1).sin() + 0.05 * i as f64).collect();
+        let result = mama(&close, 0.5, 0.05).unwrap();
+        assert_float_eq!(result.mama[32], 21.720536081851424, 1e-10);
+        assert_float_eq!(result.fama[32], 20.069789891173663, 1e-10);
+        assert_float_eq!(result.mama[49], 21.439167068982208, 1e-10);
+        assert_float_eq!(result.fama[49], 20.969218443399093, 1e-10);
+    }
+
     #[test]
     fn test_mama_trending_data() {
         // Create trending data
@@ -319,12 +356,25 @@ mod tests {
     fn test_mama_period() {
         let close: Vec<f64> = (0..50).map(|i| 20.0 + (i as f64 * 0.1).sin()).collect();
         let periods = mama_period(&close, 0.5, 0.05).unwrap();
+        let result = mama(&close, 0.5, 0.05).unwrap();
         
         assert_eq!(periods.len(), 50);
+        assert!(periods[..32].iter().all(|p| p.is_nan()));
+        assert!(result.period[..32].iter().all(|p| p.is_nan()));
+        for ((&period, &smooth), &measured) in periods.iter().zip(&result.smooth_period).zip(&result.period).skip(32) {
+            assert_float_eq!(period, smooth, 1e-12);
+            assert!(measured > 0.0 && measured <= 50.0);
+        }
         
-        // Should have some valid period values
-        let valid_count = periods.iter().filter(|&&x| !x.is_nan()).count();
-        assert!(valid_count > 0);
+        // The measured period follows the cycle length of a sine wave
+        for cycle in [10.0, 20.0, 40.0] {
+            let close: Vec<f64> = (0..300)
+                .map(|i| 100.0 + (i as f64 * 2.0 * core::f64::consts::PI / cycle).sin())
+                .collect();
+            let result = mama(&close, 0.5, 0.05).unwrap();
+            assert!((result.period[299] - cycle).abs() < 1.0, "cycle {}: {}", cycle, result.period[299]);
+            assert!((result.smooth_period[299] - cycle).abs() < 1.0);
+        }
     }
 
     #[test]
@@ -333,8 +383,9 @@ mod tests {
         assert!(mama(&close, 0.5, 0.05).is_err());
         
         // Not enough data points
-        let close = vec![20.0; 10];
+        let close = vec![20.0; 32];
         assert!(mama(&close, 0.5, 0.05).is_err());
+        assert!(mama(&[20.0; 33], 0.5, 0.05).is_ok());
         
         let close = vec![20.0; 50];
         
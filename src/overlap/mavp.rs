@@ -3,7 +3,11 @@
 //! MAVP calculates a simple moving average where the period can vary for each data point.
 //! This allows for adaptive moving averages based on external conditions or indicators.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{MAType, TAError, TAResult, IntoParam, Period, ma_lookback};
+use crate::common::errors::with_function;
+use crate::overlap::ma;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Moving Average with Variable Period (MAVP)
 ///
@@ -35,7 +39,105 @@ use crate::common::{TAError, TAResult};
 /// let periods = vec![5.0, 5.0, 3.0, 3.0, 7.0, 7.0, 4.0, 4.0, 6.0, 6.0];
 /// let result = mavp(&close, &periods, 2, 10).unwrap();
 /// ```
-pub fn mavp(close: &[f64], periods: &[f64], min_period: usize, max_period: usize) -> TAResult<Vec<f64>> {
+pub fn mavp(close: &[f64], periods: &[f64], min_period: impl IntoParam<Period>, max_period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MAVP", || {
+        let min_period = min_period.into_param("min_period")?.get();
+        let max_period = max_period.into_param("max_period")?.get();
+        validate_mavp(close, periods, min_period, max_period)?;
+    
+        let len = close.len();
+        let mut result = vec![f64::NAN; len];
+    
+        for i in 0..len {
+            // Get the period for this data point, constrained by min/max
+            let Some(period) = bar_period(periods[i], min_period, max_period) else {
+                continue; // Skip invalid periods
+            };
+        
+            // Check if we have enough data for this period
+            if i + 1 >= period {
+                // Calculate SMA for this period
+                let start_idx = i + 1 - period;
+                let sum: f64 = close[start_idx..=i].iter().sum();
+                result[i] = sum / period as f64;
+            }
+        }
+    
+        Ok(result)
+    })
+}
+
+/// Moving Average with Variable Period of any type
+///
+/// Like TA-Lib's `TA_MAVP`, each bar takes its value from the moving average of type
+/// `ma_type` with that bar's period. Values start at the lookback of the maximum period,
+/// and each distinct period is computed once, seeded so that it is warmed up exactly at
+/// that bar, as TA-Lib does. With `MAType::SMA` this matches [`mavp`] from there on.
+///
+/// # Arguments
+/// * `close` - Slice of closing prices
+/// * `periods` - Slice of period values for each data point
+/// * `min_period` - Minimum allowed period
+/// * `max_period` - Maximum allowed period
+/// * `ma_type` - Type of the moving averages
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of variable period moving average values
+/// * `Err(TAError)` - Error if inputs are invalid, or a moving average lacks data
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::MAType;
+/// use ta_rust::overlap::mavp_ma;
+///
+/// let close: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.4).sin()).collect();
+/// let periods: Vec<f64> = (0..30).map(|i| if i < 15 { 4.0 } else { 8.0 }).collect();
+/// let result = mavp_ma(&close, &periods, 2, 10, MAType::EMA).unwrap();
+/// ```
+pub fn mavp_ma(close: &[f64], periods: &[f64], min_period: impl IntoParam<Period>, max_period: impl IntoParam<Period>, ma_type: MAType) -> TAResult<Vec<f64>> {
+    with_function("MAVP", || {
+        let min_period = min_period.into_param("min_period")?.get();
+        let max_period = max_period.into_param("max_period")?.get();
+        validate_mavp(close, periods, min_period, max_period)?;
+    
+        let start = ma_lookback(max_period, ma_type);
+        if close.len() <= start {
+            return Err(TAError::insufficient_data(start + 1, close.len()));
+        }
+    
+        // Each average starts at `offset` and is indexed from there
+        let mut averages: Vec<(usize, usize, Vec<f64>)> = Vec::new();
+        let mut result = vec![f64::NAN; close.len()];
+    
+        for (i, &raw_period) in periods.iter().enumerate().take(close.len()).skip(start) {
+            let Some(period) = bar_period(raw_period, min_period, max_period) else {
+                continue;
+            };
+            let k = match averages.iter().position(|(p, _, _)| *p == period) {
+                Some(k) => k,
+                None => {
+                    let offset = start - ma_lookback(period, ma_type);
+                    averages.push((period, offset, ma(&close[offset..], period, ma_type)?));
+                    averages.len() - 1
+                }
+            };
+            let (_, offset, average) = &averages[k];
+            result[i] = average[i - offset];
+        }
+    
+        Ok(result)
+    })
+}
+
+/// Period of one bar, rounded and bounded, or `None` if it is not a valid period
+fn bar_period(raw_period: f64, min_period: usize, max_period: usize) -> Option<usize> {
+    if raw_period.is_nan() || raw_period <= 0.0 {
+        return None;
+    }
+    Some((raw_period.round() as usize).clamp(min_period, max_period))
+}
+
+fn validate_mavp(close: &[f64], periods: &[f64], min_period: usize, max_period: usize) -> TAResult<()> {
     if close.is_empty() || periods.is_empty() {
         return Err(TAError::invalid_input("Input arrays cannot be empty"));
     }
@@ -44,10 +146,6 @@ pub fn mavp(close: &[f64], periods: &[f64], min_period: usize, max_period: usize
         return Err(TAError::mismatched_inputs("Close and periods arrays must have the same length"));
     }
     
-    if min_period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
-    
     if min_period > max_period {
         return Err(TAError::invalid_input("Minimum period cannot be greater than maximum period"));
     }
@@ -56,28 +154,7 @@ pub fn mavp(close: &[f64], periods: &[f64], min_period: usize, max_period: usize
         return Err(TAError::insufficient_data(max_period, close.len()));
     }
     
-    let len = close.len();
-    let mut result = vec![f64::NAN; len];
-    
-    for i in 0..len {
-        // Get the period for this data point, constrained by min/max
-        let raw_period = periods[i];
-        if raw_period.is_nan() || raw_period <= 0.0 {
-            continue; // Skip invalid periods
-        }
-        
-        let period = (raw_period.round() as usize).clamp(min_period, max_period);
-        
-        // Check if we have enough data for this period
-        if i + 1 >= period {
-            // Calculate SMA for this period
-            let start_idx = i + 1 - period;
-            let sum: f64 = close[start_idx..=i].iter().sum();
-            result[i] = sum / period as f64;
-        }
-    }
-    
-    Ok(result)
+    Ok(())
 }
 
 /// MAVP with integer periods
@@ -93,9 +170,13 @@ pub fn mavp(close: &[f64], periods: &[f64], min_period: usize, max_period: usize
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of variable period moving average values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn mavp_int(close: &[f64], periods: &[usize], min_period: usize, max_period: usize) -> TAResult<Vec<f64>> {
-    let float_periods: Vec<f64> = periods.iter().map(|&p| p as f64).collect();
-    mavp(close, &float_periods, min_period, max_period)
+pub fn mavp_int(close: &[f64], periods: &[usize], min_period: impl IntoParam<Period>, max_period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MAVP", || {
+        let min_period = min_period.into_param("min_period")?.get();
+        let max_period = max_period.into_param("max_period")?.get();
+        let float_periods: Vec<f64> = periods.iter().map(|&p| p as f64).collect();
+        mavp(close, &float_periods, min_period, max_period)
+    })
 }
 
 /// MAVP with adaptive periods based on volatility
@@ -113,44 +194,45 @@ pub fn mavp_int(close: &[f64], periods: &[usize], min_period: usize, max_period:
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of adaptive moving average values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn mavp_adaptive(close: &[f64], volatility_period: usize, min_period: usize, max_period: usize) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if volatility_period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn mavp_adaptive(close: &[f64], volatility_period: impl IntoParam<Period>, min_period: impl IntoParam<Period>, max_period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MAVP", || {
+        let volatility_period = volatility_period.into_param("volatility_period")?.get();
+        let min_period = min_period.into_param("min_period")?.get();
+        let max_period = max_period.into_param("max_period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    let len = close.len();
-    let mut periods = vec![max_period as f64; len];
+        let len = close.len();
+        let mut periods = vec![max_period as f64; len];
     
-    // Calculate adaptive periods based on volatility
-    for i in volatility_period..len {
-        // Calculate volatility as standard deviation over the volatility period
-        let start_idx = i + 1 - volatility_period;
-        let window = &close[start_idx..=i];
-        
-        let mean = window.iter().sum::<f64>() / volatility_period as f64;
-        let variance = window.iter()
-            .map(|&x| (x - mean).powi(2))
-            .sum::<f64>() / volatility_period as f64;
-        let volatility = variance.sqrt();
-        
-        // Normalize volatility to determine period
-        // Higher volatility -> shorter period (more responsive)
-        // Lower volatility -> longer period (smoother)
-        let max_volatility = mean * 0.1; // Assume 10% volatility is high
-        let normalized_vol = (volatility / max_volatility).min(1.0);
-        
-        // Invert the relationship: high volatility = short period
-        let period_factor = 1.0 - normalized_vol;
-        let adaptive_period = min_period as f64 + period_factor * (max_period - min_period) as f64;
-        
-        periods[i] = adaptive_period;
-    }
+        // Calculate adaptive periods based on volatility
+        for i in volatility_period..len {
+            // Calculate volatility as standard deviation over the volatility period
+            let start_idx = i + 1 - volatility_period;
+            let window = &close[start_idx..=i];
+        
+            let mean = window.iter().sum::<f64>() / volatility_period as f64;
+            let variance = window.iter()
+                .map(|&x| (x - mean).powi(2))
+                .sum::<f64>() / volatility_period as f64;
+            let volatility = variance.sqrt();
+        
+            // Normalize volatility to determine period
+            // Higher volatility -> shorter period (more responsive)
+            // Lower volatility -> longer period (smoother)
+            let max_volatility = mean * 0.1; // Assume 10% volatility is high
+            let normalized_vol = (volatility / max_volatility).min(1.0);
+        
+            // Invert the relationship: high volatility = short period
+            let period_factor = 1.0 - normalized_vol;
+            let adaptive_period = min_period as f64 + period_factor * (max_period - min_period) as f64;
+        
+            periods[i] = adaptive_period;
+        }
     
-    mavp(close, &periods, min_period, max_period)
+        mavp(close, &periods, min_period, max_period)
+    })
 }
 
 /// MAVP with periods based on another indicator
@@ -171,57 +253,62 @@ pub fn mavp_adaptive(close: &[f64], volatility_period: usize, min_period: usize,
 pub fn mavp_indicator_based(
     close: &[f64], 
     indicator: &[f64], 
-    min_period: usize, 
-    max_period: usize,
+    min_period: impl IntoParam<Period>, 
+    max_period: impl IntoParam<Period>,
     invert: bool
 ) -> TAResult<Vec<f64>> {
-    if close.is_empty() || indicator.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
+    with_function("MAVP", || {
+        let min_period = min_period.into_param("min_period")?.get();
+        let max_period = max_period.into_param("max_period")?.get();
+        if close.is_empty() || indicator.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    if close.len() != indicator.len() {
-        return Err(TAError::mismatched_inputs("Close and indicator arrays must have the same length"));
-    }
+        if close.len() != indicator.len() {
+            return Err(TAError::mismatched_inputs("Close and indicator arrays must have the same length"));
+        }
     
-    // Find min and max of indicator for normalization
-    let valid_indicator: Vec<f64> = indicator.iter().filter(|&&x| !x.is_nan()).cloned().collect();
-    if valid_indicator.is_empty() {
-        return Err(TAError::invalid_input("Indicator contains no valid values"));
-    }
+        // Find min and max of indicator for normalization
+        let valid_indicator: Vec<f64> = indicator.iter().filter(|&&x| !x.is_nan()).cloned().collect();
+        if valid_indicator.is_empty() {
+            return Err(TAError::invalid_input("Indicator contains no valid values"));
+        }
     
-    let indicator_min = valid_indicator.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-    let indicator_max = valid_indicator.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-    let indicator_range = indicator_max - indicator_min;
+        let indicator_min = valid_indicator.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let indicator_max = valid_indicator.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let indicator_range = indicator_max - indicator_min;
     
-    if indicator_range.abs() < f64::EPSILON {
-        // Constant indicator - use middle period
-        let middle_period = (min_period + max_period) as f64 / 2.0;
-        let periods = vec![middle_period; close.len()];
-        return mavp(close, &periods, min_period, max_period);
-    }
+        if indicator_range.abs() < f64::EPSILON {
+            // Constant indicator - use middle period
+            let middle_period = (min_period + max_period) as f64 / 2.0;
+            let periods = vec![middle_period; close.len()];
+            return mavp(close, &periods, min_period, max_period);
+        }
     
-    // Calculate periods based on normalized indicator
-    let mut periods = vec![0.0; close.len()];
-    for i in 0..close.len() {
-        if indicator[i].is_nan() {
-            periods[i] = (min_period + max_period) as f64 / 2.0; // Default to middle
-        } else {
-            // Normalize indicator to 0-1 range
-            let normalized = (indicator[i] - indicator_min) / indicator_range;
+        // Calculate periods based on normalized indicator
+        let mut periods = vec![0.0; close.len()];
+        for i in 0..close.len() {
+            if indicator[i].is_nan() {
+                periods[i] = (min_period + max_period) as f64 / 2.0; // Default to middle
+            } else {
+                // Normalize indicator to 0-1 range
+                let normalized = (indicator[i] - indicator_min) / indicator_range;
             
-            // Map to period range
-            let period_factor = if invert { 1.0 - normalized } else { normalized };
-            periods[i] = min_period as f64 + period_factor * (max_period - min_period) as f64;
+                // Map to period range
+                let period_factor = if invert { 1.0 - normalized } else { normalized };
+                periods[i] = min_period as f64 + period_factor * (max_period - min_period) as f64;
+            }
         }
-    }
     
-    mavp(close, &periods, min_period, max_period)
+        mavp(close, &periods, min_period, max_period)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::assert_float_eq;
+    use crate::common::assert_arrays_approx_equal;
     #[test]
     fn test_mavp_basic() {
         let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0];
@@ -409,4 +496,29 @@ mod tests {
             assert_float_eq!(result[i], close[i], 1e-10);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mavp_ma_types() {
+        let close: Vec<f64> = (0..80).map(|i| 50.0 + (i as f64 * 0.25).sin() * 3.0).collect();
+        let periods: Vec<f64> = (0..80).map(|i| (3 + i % 5) as f64).collect();
+
+        let sma = mavp_ma(&close, &periods, 2, 10, MAType::SMA).unwrap();
+        assert!(sma[..9].iter().all(|v| v.is_nan()));
+        assert_arrays_approx_equal(&sma[9..], &mavp(&close, &periods, 2, 10).unwrap()[9..], 1e-10);
+
+        // Each average is seeded so that it has warmed up at the maximum period's lookback
+        for ma_type in [MAType::EMA, MAType::KAMA, MAType::T3] {
+            let result = mavp_ma(&close, &periods, 2, 10, ma_type).unwrap();
+            let start = ma_lookback(10, ma_type);
+            assert!(result[..start].iter().all(|v| v.is_nan()));
+            for i in [start, start + 1, 79] {
+                let period = periods[i] as usize;
+                let offset = start - ma_lookback(period, ma_type);
+                let expected = ma(&close[offset..], period, ma_type).unwrap()[i - offset];
+                assert_float_eq!(result[i], expected, 1e-12);
+            }
+        }
+
+        assert!(mavp_ma(&close, &periods[1..], 2, 10, MAType::EMA).is_err());
+    }
+}
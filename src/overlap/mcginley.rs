@@ -0,0 +1,223 @@
+//! McGinley Dynamic
+//!
+//! The McGinley Dynamic is a moving average whose speed adjusts to the market: each step is
+//! divided by the fourth power of price relative to the line, so the line slows down when
+//! price runs above it and speeds up when price falls below it, following declines more
+//! closely than rallies.
+
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the McGinley Dynamic
+///
+/// # Formula
+/// ```text
+/// MD[first] = SMA(Price, period)
+/// MD[today] = MD[yesterday] + (Price - MD[yesterday]) / (period × (Price / MD[yesterday])⁴)
+/// ```
+///
+/// Prices are expected to be positive, as with any price series.
+///
+/// # Parameters
+/// - `prices`: Slice of price data
+/// - `period`: Number of periods for the moving average
+///
+/// # Returns
+/// Vector of McGinley Dynamic values. The first `period - 1` values will be NaN.
+///
+/// # Errors
+/// - `EmptyInput` if prices is empty
+/// - `InvalidParameter` if period is 0
+/// - `InsufficientData` if prices length < period
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::mcginley_dynamic;
+///
+/// let prices = vec![10.0, 10.5, 11.0, 11.5, 12.0, 12.5, 13.0, 13.5];
+/// let result = mcginley_dynamic(&prices, 4).unwrap();
+///
+/// assert!(result[2].is_nan());
+/// assert!((result[3] - 10.75).abs() < 1e-10);
+/// ```
+pub fn mcginley_dynamic(prices: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("MCGINLEY", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(prices, "prices")?;
+        validate_sufficient_data(prices, period, "prices")?;
+
+        let mut state = McGinleyState::new(period)?;
+        let mut output = allocate_output(prices.len());
+        for (out, &price) in output.iter_mut().zip(prices) {
+            if let Some(value) = state.update(price) {
+                *out = value;
+            }
+        }
+
+        Ok(output)
+    })
+}
+
+/// Streaming McGinley Dynamic
+///
+/// Produces the same values as [`mcginley_dynamic`] one price at a time, in O(1) time and
+/// memory per update.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{mcginley_dynamic, McGinleyState};
+///
+/// let prices = vec![10.0, 10.5, 11.0, 11.5, 12.0, 12.5, 13.0, 13.5];
+/// let batch = mcginley_dynamic(&prices, 4).unwrap();
+///
+/// let mut state = McGinleyState::new(4).unwrap();
+/// for (i, &price) in prices.iter().enumerate() {
+///     match state.update(price) {
+///         Some(value) => assert_eq!(value, batch[i]),
+///         None => assert!(batch[i].is_nan()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct McGinleyState {
+    period: usize,
+    /// Number of prices seen so far, saturating at `period`
+    count: usize,
+    /// Running sum of the first `period` prices for the SMA seed
+    seed_sum: Price,
+    /// Current McGinley Dynamic value, once warmed up
+    value: Option<Price>,
+}
+
+impl McGinleyState {
+    /// Creates a new state for the given period
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn new(period: impl IntoParam<Period>) -> TAResult<Self> {
+        with_function("MCGINLEY", || {
+            let period = period.into_param("period")?.get();
+            Ok(Self {
+                period,
+                count: 0,
+                seed_sum: 0.0,
+                value: None,
+            })
+        })
+    }
+
+    /// Feeds the next price and returns the updated value, or `None` during warm-up
+    pub fn update(&mut self, price: Price) -> Option<Price> {
+        self.value = match self.value {
+            Some(prev) => {
+                // The ratio is undefined on a zero line, and a zero price makes the divisor
+                // 0; both take a plain 1/period step instead
+                let divisor = self.period as Price * (price / prev).powi(4);
+                let divisor = if prev == 0.0 || divisor == 0.0 { self.period as Price } else { divisor };
+                Some(prev + (price - prev) / divisor)
+            }
+            None => {
+                self.count += 1;
+                self.seed_sum += price;
+                if self.count == self.period {
+                    Some(self.seed_sum / self.period as Price)
+                } else {
+                    None
+                }
+            }
+        };
+        self.value
+    }
+
+    /// Current value, or `None` if fewer than `period` prices have been seen
+    pub fn current(&self) -> Option<Price> {
+        self.value
+    }
+
+    /// Period of the indicator
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.seed_sum = 0.0;
+        self.value = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::overlap::ema;
+
+    #[test]
+    fn test_mcginley_basic() {
+        let prices = vec![10.0, 11.0, 12.0, 13.0, 12.0];
+        let result = mcginley_dynamic(&prices, 3).unwrap();
+
+        assert!(result[0].is_nan());
+        assert!(result[1].is_nan());
+        assert_float_eq!(result[2], 11.0, 1e-12);
+
+        // 11 + (13 - 11) / (3 × (13/11)^4)
+        let expected = 11.0 + 2.0 / (3.0 * (13.0f64 / 11.0).powi(4));
+        assert_float_eq!(result[3], expected, 1e-12);
+        let expected = expected + (12.0 - expected) / (3.0 * (12.0 / expected).powi(4));
+        assert_float_eq!(result[4], expected, 1e-12);
+    }
+
+    #[test]
+    fn test_mcginley_tracks_drop_faster_than_ema() {
+        // Price below the line makes the ratio < 1 and the step larger
+        let mut prices = vec![100.0; 10];
+        prices.extend(vec![80.0; 5]);
+        let md = mcginley_dynamic(&prices, 10).unwrap();
+        let e = ema(&prices, 10).unwrap();
+
+        assert!(md[14] < e[14]);
+        assert!(md[14] > 80.0);
+    }
+
+    #[test]
+    fn test_mcginley_zero_line_and_price() {
+        // A zero line or price takes a 1/period step rather than producing NaN or infinity
+        let result = mcginley_dynamic(&[0.0, 0.0, 6.0, 0.0], 2).unwrap();
+        assert_eq!(result[1], 0.0);
+        assert_float_eq!(result[2], 3.0, 1e-12);
+        assert_float_eq!(result[3], 1.5, 1e-12);
+    }
+
+    #[test]
+    fn test_mcginley_state_matches_batch() {
+        let prices: Vec<f64> = (0..50).map(|i| 50.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        let batch = mcginley_dynamic(&prices, 8).unwrap();
+
+        let mut state = McGinleyState::new(8).unwrap();
+        for (i, &price) in prices.iter().enumerate() {
+            match state.update(price) {
+                Some(value) => assert_eq!(value, batch[i]),
+                None => assert!(batch[i].is_nan()),
+            }
+        }
+        assert_eq!(state.current(), Some(batch[49]));
+
+        state.reset();
+        assert_eq!(state, McGinleyState::new(8).unwrap());
+        assert_eq!(state.current(), None);
+    }
+
+    #[test]
+    fn test_mcginley_invalid_input() {
+        assert!(mcginley_dynamic(&[], 3).is_err());
+        assert!(mcginley_dynamic(&[1.0, 2.0], 0).is_err());
+        assert!(mcginley_dynamic(&[1.0, 2.0], 3).is_err());
+        assert!(McGinleyState::new(0).is_err());
+    }
+}
@@ -1,7 +1,11 @@
 //! MidPoint over period
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output, highest_in_period, lowest_in_period};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
+use crate::common::window::{RunningMax, RunningMin};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the MidPoint over a specified period
 ///
@@ -37,49 +41,52 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// // For index 3: highest(3,2,5) = 5, lowest(3,2,5) = 2, midpoint = 3.5
 /// assert_eq!(result[3], 3.5);
 /// ```
-pub fn midpoint(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn midpoint(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("MIDPOINT", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
-    
-    // Calculate midpoint for each position starting from period-1
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        let highest = highest_in_period(data, start_index, period);
-        let lowest = lowest_in_period(data, start_index, period);
-        output[i] = (highest + lowest) / 2.0;
-    }
+        let mut output = allocate_output(data.len());
+        let (mut highest, mut lowest) = (RunningMax::new(period), RunningMin::new(period));
+        for (i, &value) in data.iter().enumerate() {
+            let (high, low) = (highest.push(value), lowest.push(value));
+            if i + 1 >= period {
+                output[i] = (high.unwrap_or(Price::NEG_INFINITY) + low.unwrap_or(Price::INFINITY)) / 2.0;
+            }
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates MidPoint using a rolling approach for better performance
 ///
 /// This version maintains running highest and lowest values and updates
 /// them more efficiently for large datasets.
-pub fn midpoint_rolling(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn midpoint_rolling(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("MIDPOINT", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
+        let mut output = allocate_output(data.len());
     
-    // For rolling calculation, we need to track the window
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        let window = &data[start_index..=i];
+        // For rolling calculation, we need to track the window
+        for i in (period - 1)..data.len() {
+            let start_index = i + 1 - period;
+            let window = &data[start_index..=i];
         
-        let highest = window.iter().fold(Price::NEG_INFINITY, |acc, &x| acc.max(x));
-        let lowest = window.iter().fold(Price::INFINITY, |acc, &x| acc.min(x));
+            let highest = window.iter().fold(Price::NEG_INFINITY, |acc, &x| acc.max(x));
+            let lowest = window.iter().fold(Price::INFINITY, |acc, &x| acc.min(x));
         
-        output[i] = (highest + lowest) / 2.0;
-    }
+            output[i] = (highest + lowest) / 2.0;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates MidPoint with custom aggregation functions
@@ -88,7 +95,7 @@ pub fn midpoint_rolling(data: &[Price], period: Period) -> TAResult<Vec<Price>>
 /// values, which could be useful for specialized applications.
 pub fn midpoint_custom<F1, F2>(
     data: &[Price], 
-    period: Period,
+    period: impl IntoParam<Period>,
     high_fn: F1,
     low_fn: F2
 ) -> TAResult<Vec<Price>>
@@ -96,25 +103,27 @@ where
     F1: Fn(&[Price]) -> Price,
     F2: Fn(&[Price]) -> Price,
 {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+    with_function("MIDPOINT", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
+        let mut output = allocate_output(data.len());
     
-    // Calculate midpoint for each position using custom functions
-    for i in (period - 1)..data.len() {
-        let start_index = i + 1 - period;
-        let window = &data[start_index..=i];
+        // Calculate midpoint for each position using custom functions
+        for i in (period - 1)..data.len() {
+            let start_index = i + 1 - period;
+            let window = &data[start_index..=i];
         
-        let highest = high_fn(window);
-        let lowest = low_fn(window);
+            let highest = high_fn(window);
+            let lowest = low_fn(window);
         
-        output[i] = (highest + lowest) / 2.0;
-    }
+            output[i] = (highest + lowest) / 2.0;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -1,7 +1,10 @@
 //! Midpoint Price over period
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, validate_same_length, allocate_output, highest_in_period, lowest_in_period};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, validate_same_length, allocate_output, highest_in_period, lowest_in_period};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Midpoint Price over a specified period
 ///
@@ -38,25 +41,26 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// // For index 2: highest_high(2,4,3) = 4, lowest_low(1,2,1) = 1, midprice = 2.5
 /// assert_eq!(result[2], 2.5);
 /// ```
-pub fn midprice(high: &[Price], low: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_same_length(high, low, "high", "low")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(high, period, "high")?;
+pub fn midprice(high: &[Price], low: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("MIDPRICE", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_same_length(high, low, "high", "low")?;
+        validate_sufficient_data(high, period, "high")?;
 
-    let mut output = allocate_output(high.len());
+        let mut output = allocate_output(high.len());
     
-    // Calculate midprice for each position starting from period-1
-    for i in (period - 1)..high.len() {
-        let start_index = i + 1 - period;
-        let highest_high = highest_in_period(high, start_index, period);
-        let lowest_low = lowest_in_period(low, start_index, period);
-        output[i] = (highest_high + lowest_low) / 2.0;
-    }
+        // Calculate midprice for each position starting from period-1
+        for (start_index, value) in output[period - 1..].iter_mut().enumerate() {
+            let highest_high = highest_in_period(high, start_index, period);
+            let lowest_low = lowest_in_period(low, start_index, period);
+            *value = (highest_high + lowest_low) / 2.0;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates MidPrice using OHLC data
@@ -77,10 +81,13 @@ pub fn midprice_ohlc(
     high: &[Price], 
     low: &[Price], 
     _close: &[Price], 
-    period: Period
+    period: impl IntoParam<Period>
 ) -> TAResult<Vec<Price>> {
-    // We only need high and low for midprice calculation
-    midprice(high, low, period)
+    with_function("MIDPRICE", || {
+        let period = period.into_param("period")?.get();
+        // We only need high and low for midprice calculation
+        midprice(high, low, period)
+    })
 }
 
 /// Calculates MidPrice with custom percentile levels
@@ -107,49 +114,51 @@ pub fn midprice_ohlc(
 pub fn midprice_percentile(
     high: &[Price], 
     low: &[Price], 
-    period: Period,
+    period: impl IntoParam<Period>,
     high_percentile: Price,
     low_percentile: Price
 ) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_same_length(high, low, "high", "low")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(high, period, "high")?;
+    with_function("MIDPRICE", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_same_length(high, low, "high", "low")?;
+        validate_sufficient_data(high, period, "high")?;
     
-    if high_percentile < 0.0 || high_percentile > 1.0 {
-        return Err(crate::common::TAError::invalid_parameter(
-            "high_percentile",
-            "must be between 0.0 and 1.0"
-        ));
-    }
+        if !(0.0..=1.0).contains(&high_percentile) {
+            return Err(crate::common::TAError::invalid_parameter(
+                "high_percentile",
+                "must be between 0.0 and 1.0"
+            ));
+        }
     
-    if low_percentile < 0.0 || low_percentile > 1.0 {
-        return Err(crate::common::TAError::invalid_parameter(
-            "low_percentile",
-            "must be between 0.0 and 1.0"
-        ));
-    }
+        if !(0.0..=1.0).contains(&low_percentile) {
+            return Err(crate::common::TAError::invalid_parameter(
+                "low_percentile",
+                "must be between 0.0 and 1.0"
+            ));
+        }
 
-    let mut output = allocate_output(high.len());
+        let mut output = allocate_output(high.len());
     
-    // Calculate midprice for each position using percentiles
-    for i in (period - 1)..high.len() {
-        let start_index = i + 1 - period;
+        // Calculate midprice for each position using percentiles
+        for i in (period - 1)..high.len() {
+            let start_index = i + 1 - period;
         
-        // Get high and low windows
-        let high_window = &high[start_index..=i];
-        let low_window = &low[start_index..=i];
+            // Get high and low windows
+            let high_window = &high[start_index..=i];
+            let low_window = &low[start_index..=i];
         
-        // Calculate percentiles
-        let high_value = calculate_percentile(high_window, high_percentile);
-        let low_value = calculate_percentile(low_window, low_percentile);
+            // Calculate percentiles
+            let high_value = calculate_percentile(high_window, high_percentile);
+            let low_value = calculate_percentile(low_window, low_percentile);
         
-        output[i] = (high_value + low_value) / 2.0;
-    }
+            output[i] = (high_value + low_value) / 2.0;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Helper function to calculate percentile of a slice
@@ -159,7 +168,7 @@ fn calculate_percentile(data: &[Price], percentile: Price) -> Price {
     }
     
     let mut sorted_data = data.to_vec();
-    sorted_data.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted_data.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
     
     let index = (percentile * (sorted_data.len() - 1) as Price).round() as usize;
     sorted_data[index.min(sorted_data.len() - 1)]
@@ -172,47 +181,49 @@ fn calculate_percentile(data: &[Price], percentile: Price) -> Price {
 pub fn midprice_adaptive(
     high: &[Price], 
     low: &[Price], 
-    base_period: Period,
-    volatility_period: Period
+    base_period: impl IntoParam<Period>,
+    volatility_period: impl IntoParam<Period>
 ) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_same_length(high, low, "high", "low")?;
-    validate_period(base_period, "base_period")?;
-    validate_period(volatility_period, "volatility_period")?;
+    with_function("MIDPRICE", || {
+        let base_period = base_period.into_param("base_period")?.get();
+        let volatility_period = volatility_period.into_param("volatility_period")?.get();
+        // Input validation
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_same_length(high, low, "high", "low")?;
     
-    let min_required = base_period.max(volatility_period);
-    validate_sufficient_data(high, min_required, "high")?;
+        let min_required = base_period.max(volatility_period);
+        validate_sufficient_data(high, min_required, "high")?;
 
-    let mut output = allocate_output(high.len());
+        let mut output = allocate_output(high.len());
     
-    // Calculate adaptive midprice
-    for i in min_required..high.len() {
-        // Calculate recent volatility
-        let vol_start = i + 1 - volatility_period;
-        let high_range = &high[vol_start..=i];
-        let low_range = &low[vol_start..=i];
+        // Calculate adaptive midprice
+        for i in min_required..high.len() {
+            // Calculate recent volatility
+            let vol_start = i + 1 - volatility_period;
+            let high_range = &high[vol_start..=i];
+            let low_range = &low[vol_start..=i];
         
-        let volatility = calculate_hl_volatility(high_range, low_range);
+            let volatility = calculate_hl_volatility(high_range, low_range);
         
-        // Adjust period based on volatility (higher volatility = shorter period)
-        let adjusted_period = if volatility > 0.05 {
-            (base_period as Price * 0.7).round() as Period
-        } else if volatility < 0.02 {
-            (base_period as Price * 1.3).round() as Period
-        } else {
-            base_period
-        }.max(1).min(i + 1);
+            // Adjust period based on volatility (higher volatility = shorter period)
+            let adjusted_period = if volatility > 0.05 {
+                (base_period as Price * 0.7).round() as usize
+            } else if volatility < 0.02 {
+                (base_period as Price * 1.3).round() as usize
+            } else {
+                base_period
+            }.max(1).min(i + 1);
         
-        // Calculate midprice with adjusted period
-        let start_index = i + 1 - adjusted_period;
-        let highest_high = highest_in_period(high, start_index, adjusted_period);
-        let lowest_low = lowest_in_period(low, start_index, adjusted_period);
-        output[i] = (highest_high + lowest_low) / 2.0;
-    }
+            // Calculate midprice with adjusted period
+            let start_index = i + 1 - adjusted_period;
+            let highest_high = highest_in_period(high, start_index, adjusted_period);
+            let lowest_low = lowest_in_period(low, start_index, adjusted_period);
+            output[i] = (highest_high + lowest_low) / 2.0;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Helper function to calculate high-low volatility
@@ -5,6 +5,10 @@
 
 pub mod sma;
 pub mod ema;
+pub mod ewm;
+pub mod ema_time;
+pub mod kalman;
+pub mod ehlers;
 pub mod wma;
 pub mod dema;
 pub mod tema;
@@ -22,15 +26,26 @@ pub mod t3;
 pub mod mama;
 pub mod mavp;
 pub mod trix;
+pub mod keltner;
+pub mod donchian;
+pub mod ichimoku;
+pub mod zlema;
+pub mod frama;
+pub mod mcginley;
+pub mod zigzag;
 
 // Re-export all functions for convenient access
-pub use sma::{sma, sma_rolling};
-pub use ema::{ema, ema_from_first, ema_custom};
-pub use wma::{wma, wma_custom, wma_rolling};
+pub use sma::{sma, sma_const, sma_ex, sma_into, sma_rolling, sma_with_policy, SmaWindow};
+pub use ema::{ema, ema_ex, ema_into, ema_from_first, ema_custom, ema_with_policy};
+pub use ewm::{ewm, ewm_into, wilder_smooth, EwmInit};
+pub use ema_time::{ema_time, EmaTimeState};
+pub use ehlers::{bandpass, decycler, high_pass, roofing_filter, super_smoother};
+pub use kalman::{kalman, kalman_trend, KalmanState, KalmanTrend, KalmanTrendState};
+pub use wma::{wma, wma_ex, wma_into, wma_custom, wma_rolling};
 pub use dema::{dema, dema_direct};
 pub use tema::{tema, tema_direct};
 pub use trima::{trima, trima_direct, trima_custom_peak};
-pub use ma::{ma, ma_multiple, ma_auto};
+pub use ma::{ma, ma_ex, ma_multiple, ma_auto, MaState};
 pub use midpoint::{midpoint, midpoint_rolling, midpoint_custom};
 pub use midprice::{midprice, midprice_ohlc, midprice_percentile, midprice_adaptive};
 
@@ -42,4 +57,11 @@ pub use kama::*;
 pub use t3::*;
 pub use mama::*;
 pub use mavp::*;
-pub use trix::*;
\ No newline at end of file
+pub use trix::*;
+pub use keltner::{keltner, keltner_default, squeeze, KeltnerChannels};
+pub use donchian::{donchian, DonchianChannels};
+pub use ichimoku::{ichimoku, ichimoku_default, IchimokuCloud};
+pub use zlema::{zlema, zlema_custom};
+pub use frama::frama;
+pub use mcginley::{mcginley_dynamic, McGinleyState};
+pub use zigzag::{zigzag, zigzag_atr, ZigZag, ZigZagPivot};
\ No newline at end of file
@@ -4,6 +4,9 @@
 //! It appears as dots above or below the price, indicating the direction of the trend.
 
 use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Parabolic SAR (Stop and Reverse)
 ///
@@ -12,14 +15,19 @@ use crate::common::{TAError, TAResult};
 ///
 /// # Formula
 /// ```text
+/// Initial trend = down if bar 1 has a minus directional movement, up otherwise
 /// Initial SAR = Low[0] for uptrend, High[0] for downtrend
 /// EP (Extreme Point) = highest high in uptrend, lowest low in downtrend
-/// SAR[tomorrow] = SAR[today] + AF × (EP - SAR[today])
+/// SAR[tomorrow] = SAR[today] + AF × (EP - SAR[today]),
+///                 kept below the lows (above the highs) of today and yesterday
 /// 
 /// AF starts at acceleration, increases by acceleration when new EP, max at max_acceleration
-/// Reverse when price crosses SAR
+/// Reverse when price crosses SAR: the SAR jumps to the EP and AF restarts
 /// ```
 ///
+/// The rules are TA-Lib's, and so are the values from the second bar on; the first bar
+/// repeats the second's SAR where TA-Lib has none.
+///
 /// # Arguments
 /// * `high` - Slice of high prices
 /// * `low` - Slice of low prices
@@ -39,115 +47,242 @@ use crate::common::{TAError, TAResult};
 /// let result = sar(&high, &low, 0.02, 0.20).unwrap();
 /// ```
 pub fn sar(high: &[f64], low: &[f64], acceleration: f64, max_acceleration: f64) -> TAResult<Vec<f64>> {
-    if high.is_empty() || low.is_empty() {
-        return Err(TAError::invalid_input("High and low arrays cannot be empty"));
-    }
-    
-    if high.len() != low.len() {
-        return Err(TAError::mismatched_inputs("High and low arrays must have the same length"));
-    }
-    
-    if acceleration <= 0.0 || max_acceleration <= 0.0 {
-        return Err(TAError::invalid_parameter("parameter", "must be greater than 0"));
-    }
-    
-    if acceleration > max_acceleration {
-        return Err(TAError::invalid_input("Acceleration cannot be greater than max acceleration"));
-    }
-    
-    let len = high.len();
-    if len < 2 {
-        return Err(TAError::invalid_input("Need at least 2 data points"));
-    }
-    
-    let mut result = vec![f64::NAN; len];
+    with_function("SAR", || {
+        if high.is_empty() || low.is_empty() {
+            return Err(TAError::invalid_input("High and low arrays cannot be empty"));
+        }
     
-    // Initialize for first calculation
-    let mut af = acceleration;
-    let mut ep: f64;
-    let mut sar: f64;
-    let mut is_long: bool;
+        if high.len() != low.len() {
+            return Err(TAError::mismatched_inputs("High and low arrays must have the same length"));
+        }
     
-    // Determine initial trend direction based on first two periods
-    if high[1] > high[0] {
-        // Start with uptrend
-        is_long = true;
-        sar = low[0];
-        ep = high[1];
-    } else {
-        // Start with downtrend
-        is_long = false;
-        sar = high[0];
-        ep = low[1];
-    }
+        let mut state = SarState::new(acceleration, max_acceleration)?;
     
-    // Set initial SAR values
-    result[0] = sar;
-    result[1] = sar;
+        let len = high.len();
+        if len < 2 {
+            return Err(TAError::invalid_input("Need at least 2 data points"));
+        }
     
-    for i in 2..len {
-        // Update extreme point before calculating new SAR
-        if is_long {
-            if high[i - 1] > ep {
-                ep = high[i - 1];
-                af = (af + acceleration).min(max_acceleration);
-            }
-        } else {
-            if low[i - 1] < ep {
-                ep = low[i - 1];
-                af = (af + acceleration).min(max_acceleration);
+        let mut result = vec![f64::NAN; len];
+        for i in 0..len {
+            if let Some(value) = state.update(high[i], low[i]) {
+                result[i] = value;
             }
         }
-        
-        let prev_sar = sar;
-        
-        // Calculate new SAR
-        sar = prev_sar + af * (ep - prev_sar);
-        
-        if is_long {
-            // In uptrend
-            // SAR cannot be above the low of current or previous period
-            sar = sar.min(low[i]).min(low[i - 1]);
-            
-            // Check for trend reversal
-            if low[i] <= sar {
-                // Trend reversal to downtrend
-                is_long = false;
-                sar = ep;  // SAR becomes the previous EP
-                ep = low[i];  // New EP is current low
-                af = acceleration;  // Reset AF
+        // The first SAR needs the second bar to set the trend, and holds for both
+        result[0] = result[1];
+    
+        Ok(result)
+    })
+}
+
+/// [`sar`] as TA-Lib reports it, with no value on the first bar
+pub(crate) fn sar_talib(high: &[f64], low: &[f64], acceleration: f64, max_acceleration: f64) -> TAResult<Vec<f64>> {
+    with_function("SAR", || {
+        let mut result = sar(high, low, acceleration, max_acceleration)?;
+        result[0] = f64::NAN;
+        Ok(result)
+    })
+}
+
+/// Initial acceleration factor, its increment on each new extreme point and its maximum,
+/// for one direction of a Parabolic SAR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SarFactors {
+    pub init: f64,
+    pub step: f64,
+    pub max: f64,
+}
+
+/// Trend, extreme point and acceleration of a Parabolic SAR
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct SarPosition {
+    /// SAR of the latest bar
+    value: f64,
+    /// SAR projected for the next bar
+    sar: f64,
+    /// Extreme point: highest high of an uptrend, lowest low of a downtrend
+    ep: f64,
+    af: f64,
+    is_long: bool,
+}
+
+impl SarPosition {
+    /// Position after the first two bars, following TA-Lib
+    ///
+    /// A `start_value` of 0 starts long unless the second bar moves down, by the minus
+    /// directional movement of the two bars, with the SAR at the first bar's low (or high).
+    /// Otherwise its sign gives the direction and its magnitude the SAR.
+    pub(crate) fn start(first: (f64, f64), second: (f64, f64), start_value: f64, long: SarFactors, short: SarFactors) -> Self {
+        let ((first_high, first_low), (high, low)) = (first, second);
+        let is_long = if start_value == 0.0 {
+            let (up, down) = (high - first_high, first_low - low);
+            !(down > 0.0 && down > up)
+        } else {
+            start_value > 0.0
+        };
+        let sar = match (start_value == 0.0, is_long) {
+            (true, true) => first_low,
+            (true, false) => first_high,
+            (false, _) => start_value.abs(),
+        };
+        let (ep, af) = if is_long { (high, long.init) } else { (low, short.init) };
+        let mut position = Self { value: sar, sar, ep, af, is_long };
+        // TA-Lib takes the second bar for the previous one on its first step
+        position.next(second, second, long, short, 0.0);
+        position
+    }
+
+    /// Moves to the next bar, with `prev` the high and low of the bar before it, and
+    /// returns its SAR
+    ///
+    /// On a reversal the SAR jumps to the extreme point, moved away from prices by
+    /// `offset` as a fraction of itself. The projected SAR never enters the range of the
+    /// last two bars.
+    pub(crate) fn next(&mut self, prev: (f64, f64), bar: (f64, f64), long: SarFactors, short: SarFactors, offset: f64) -> f64 {
+        let ((prev_high, prev_low), (high, low)) = (prev, bar);
+        let ceiling = |sar: f64| sar.max(prev_high).max(high);
+        let floor = |sar: f64| sar.min(prev_low).min(low);
+        if self.is_long {
+            if low <= self.sar {
+                // Trend reversal to a downtrend
+                let sar = ceiling(self.ep);
+                self.value = sar + sar * offset;
+                self.is_long = false;
+                self.af = short.init;
+                self.ep = low;
+                self.sar = ceiling(self.value + self.af * (self.ep - self.value));
             } else {
-                // Continue uptrend - check current period for new EP
-                if high[i] > ep {
-                    ep = high[i];  // New extreme point
-                    af = (af + acceleration).min(max_acceleration);  // Increase AF
+                self.value = self.sar;
+                if high > self.ep {
+                    self.ep = high;
+                    self.af = (self.af + long.step).min(long.max);
                 }
+                self.sar = floor(self.sar + self.af * (self.ep - self.sar));
             }
+        } else if high >= self.sar {
+            // Trend reversal to an uptrend
+            let sar = floor(self.ep);
+            self.value = sar - sar * offset;
+            self.is_long = true;
+            self.af = long.init;
+            self.ep = high;
+            self.sar = floor(self.value + self.af * (self.ep - self.value));
         } else {
-            // In downtrend
-            // SAR cannot be below the high of current or previous period
-            sar = sar.max(high[i]).max(high[i - 1]);
-            
-            // Check for trend reversal
-            if high[i] >= sar {
-                // Trend reversal to uptrend
-                is_long = true;
-                sar = ep;  // SAR becomes the previous EP
-                ep = high[i];  // New EP is current high
-                af = acceleration;  // Reset AF
-            } else {
-                // Continue downtrend - check current period for new EP
-                if low[i] < ep {
-                    ep = low[i];  // New extreme point
-                    af = (af + acceleration).min(max_acceleration);  // Increase AF
-                }
+            self.value = self.sar;
+            if low < self.ep {
+                self.ep = low;
+                self.af = (self.af + short.step).min(short.max);
             }
+            self.sar = ceiling(self.sar + self.af * (self.ep - self.sar));
         }
+        self.value
+    }
+
+    /// SAR of the latest bar
+    pub(crate) fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Whether the trend is up, with the SAR below prices
+    pub(crate) fn is_long(&self) -> bool {
+        self.is_long
+    }
+}
+
+/// Streaming Parabolic SAR
+///
+/// Produces the same values as [`sar`] one bar at a time, from the second bar on: the
+/// first only records prices, since the second sets the initial trend. The trend, extreme
+/// point and acceleration factor are exposed, and a state fed a history continues exactly
+/// as [`sar`] would over the history and the bars that follow.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{sar, SarState};
+///
+/// let high = vec![22.0, 23.0, 24.0, 25.0, 24.5, 23.5, 22.5, 21.5, 20.5, 19.5];
+/// let low = vec![20.0, 21.0, 22.0, 23.0, 22.5, 21.5, 20.5, 19.5, 18.5, 17.5];
+/// let batch = sar(&high, &low, 0.02, 0.20).unwrap();
+///
+/// // Historical bars, then live ones
+/// let mut state = SarState::new(0.02, 0.20).unwrap();
+/// for i in 0..6 {
+///     state.update(high[i], low[i]);
+/// }
+/// assert_eq!(state.is_long(), Some(true));
+/// assert_eq!(state.extreme_point(), Some(25.0));
+/// for i in 6..high.len() {
+///     assert_eq!(state.update(high[i], low[i]), Some(batch[i]));
+/// }
+/// assert_eq!(state.is_long(), Some(false));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SarState {
+    acceleration: f64,
+    max_acceleration: f64,
+    /// High and low of the previous bar
+    prev: Option<(f64, f64)>,
+    position: Option<SarPosition>,
+}
+
+impl SarState {
+    /// Creates a new state with the parameters of [`sar`]
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if either factor is not positive
+    /// - `InvalidInput` if `acceleration` exceeds `max_acceleration`
+    pub fn new(acceleration: f64, max_acceleration: f64) -> TAResult<Self> {
+        with_function("SAR", || {
+            if acceleration <= 0.0 || max_acceleration <= 0.0 {
+                return Err(TAError::invalid_parameter("parameter", "must be greater than 0"));
+            }
         
-        result[i] = sar;
+            if acceleration > max_acceleration {
+                return Err(TAError::invalid_input("Acceleration cannot be greater than max acceleration"));
+            }
+        
+            Ok(Self { acceleration, max_acceleration, prev: None, position: None })
+        })
+    }
+
+    /// Feeds the next bar and returns the SAR, or `None` for the first bar
+    pub fn update(&mut self, high: f64, low: f64) -> Option<f64> {
+        let prev = self.prev.replace((high, low))?;
+        let factors = SarFactors { init: self.acceleration, step: self.acceleration, max: self.max_acceleration };
+        Some(match self.position.as_mut() {
+            Some(position) => position.next(prev, (high, low), factors, factors, 0.0),
+            None => self.position.insert(SarPosition::start(prev, (high, low), 0.0, factors, factors)).value,
+        })
+    }
+
+    /// Current SAR, or `None` before the second bar
+    pub fn current(&self) -> Option<f64> {
+        self.position.map(|position| position.value)
+    }
+
+    /// Whether the trend is up, with the SAR below prices, or `None` before the second bar
+    pub fn is_long(&self) -> Option<bool> {
+        self.position.map(|position| position.is_long)
+    }
+
+    /// Current extreme point: the highest high of an uptrend, the lowest low of a downtrend
+    pub fn extreme_point(&self) -> Option<f64> {
+        self.position.map(|position| position.ep)
+    }
+
+    /// Current acceleration factor
+    pub fn acceleration_factor(&self) -> Option<f64> {
+        self.position.map(|position| position.af)
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.position = None;
     }
-    
-    Ok(result)
 }
 
 /// Parabolic SAR with default parameters (0.02, 0.20)
@@ -179,29 +314,31 @@ pub fn sar_default(high: &[f64], low: &[f64]) -> TAResult<Vec<f64>> {
 /// * `Ok(Vec<f64>)` - Vector of trend direction values
 /// * `Err(TAError)` - Error if inputs are invalid
 pub fn sar_trend(high: &[f64], low: &[f64], sar_values: &[f64]) -> TAResult<Vec<f64>> {
-    if high.len() != low.len() || high.len() != sar_values.len() {
-        return Err(TAError::mismatched_inputs("All arrays must have the same length"));
-    }
+    with_function("SAR", || {
+        if high.len() != low.len() || high.len() != sar_values.len() {
+            return Err(TAError::mismatched_inputs("All arrays must have the same length"));
+        }
     
-    let len = high.len();
-    let mut result = vec![f64::NAN; len];
+        let len = high.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in 0..len {
-        if !sar_values[i].is_nan() {
-            // If SAR is below the low, it's an uptrend
-            // If SAR is above the high, it's a downtrend
-            if sar_values[i] < low[i] {
-                result[i] = 1.0;  // Uptrend
-            } else if sar_values[i] > high[i] {
-                result[i] = -1.0;  // Downtrend
-            } else {
-                // SAR is between high and low (transition)
-                result[i] = 0.0;
+        for i in 0..len {
+            if !sar_values[i].is_nan() {
+                // If SAR is below the low, it's an uptrend
+                // If SAR is above the high, it's a downtrend
+                if sar_values[i] < low[i] {
+                    result[i] = 1.0;  // Uptrend
+                } else if sar_values[i] > high[i] {
+                    result[i] = -1.0;  // Downtrend
+                } else {
+                    // SAR is between high and low (transition)
+                    result[i] = 0.0;
+                }
             }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
@@ -319,4 +456,52 @@ mod tests {
             assert!(!value.is_nan());
         }
     }
+
+    #[test]
+    fn test_sar_state_matches_batch() {
+        let high: Vec<f64> = (0..120).map(|i| 50.0 + (i as f64 * 0.15).sin() * 6.0 + (i % 3) as f64 * 0.4).collect();
+        let low: Vec<f64> = high.iter().enumerate().map(|(i, h)| h - 1.0 - (i % 4) as f64 * 0.3).collect();
+        let batch = sar(&high, &low, 0.02, 0.20).unwrap();
+
+        let mut state = SarState::new(0.02, 0.20).unwrap();
+        assert_eq!(state.update(high[0], low[0]), None);
+        assert_eq!(state.current(), None);
+        let mut reversals = 0;
+        for i in 1..high.len() {
+            let was_long = state.is_long();
+            assert_eq!(state.update(high[i], low[i]), Some(batch[i]), "at {}", i);
+            if was_long.is_some() && was_long != state.is_long() {
+                reversals += 1;
+                assert_eq!(state.acceleration_factor(), Some(0.02));
+            }
+            let af = state.acceleration_factor().unwrap();
+            assert!((0.02..=0.20).contains(&af));
+        }
+        assert!(reversals > 2);
+
+        state.reset();
+        assert_eq!(state, SarState::new(0.02, 0.20).unwrap());
+        assert!(SarState::new(0.3, 0.2).is_err());
+        assert!(SarState::new(0.0, 0.2).is_err());
+    }
+
+    #[test]
+    fn test_sar_talib_rules() {
+        let high = vec![22.0, 23.0, 24.0, 25.0, 24.5, 23.5, 22.5, 21.5, 20.5, 19.5];
+        let low = vec![20.0, 21.0, 22.0, 23.0, 22.5, 21.5, 20.5, 19.5, 18.5, 17.5];
+        let result = sar(&high, &low, 0.02, 0.20).unwrap();
+        // Long from the first low; the factor grows with each new high
+        let expected = [20.0, 20.0, 20.06, 20.2176, 20.504544];
+        for (value, expected) in result.iter().zip(expected) {
+            assert_float_eq!(*value, expected, 1e-12);
+        }
+        // The low of bar 6 crosses the SAR, which jumps to the highest high
+        assert!(result[5] < low[5]);
+        assert_eq!(result[6], 25.0);
+        assert_float_eq!(result[7], 25.0 + 0.02 * (20.5 - 25.0), 1e-12);
+
+        // A second bar moving down starts short from the first high
+        let down = sar(&[10.0, 9.5, 9.0], &[9.0, 8.0, 7.5], 0.02, 0.20).unwrap();
+        assert_eq!(down[1], 10.0);
+    }
 }
@@ -5,6 +5,10 @@
 //! different acceleration factors for long and short positions.
 
 use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::overlap::sar::{SarFactors, SarPosition};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Parabolic SAR Extended (SAREXT)
 ///
@@ -12,11 +16,18 @@ use crate::common::{TAError, TAResult};
 /// Allows for different acceleration factors for long and short positions,
 /// custom start values, and offset on reverse.
 ///
+/// The parameters and values are TA-Lib's, except that TA-Lib reports the SAR of short
+/// positions as a negative number, and the first bar repeats the second's SAR where
+/// TA-Lib has none. With the same factors for both directions and no offset or start
+/// value this is [`sar`](crate::overlap::sar).
+///
 /// # Arguments
 /// * `high` - Slice of high prices
 /// * `low` - Slice of low prices
-/// * `start_value` - Starting value for SAR calculation
-/// * `offset_on_reverse` - Offset to add when trend reverses
+/// * `start_value` - Starting SAR: 0 to derive the trend and SAR from the first two bars,
+///   a positive value to start long at that SAR, a negative one to start short at its
+///   magnitude
+/// * `offset_on_reverse` - Fraction of the SAR it is moved away from prices by on a reversal
 /// * `af_init_long` - Initial acceleration factor for long positions
 /// * `af_long` - Acceleration increment for long positions
 /// * `af_max_long` - Maximum acceleration factor for long positions
@@ -30,11 +41,12 @@ use crate::common::{TAError, TAResult};
 ///
 /// # Examples
 /// ```
-/// use ta_rust::overlap::sarext;
+/// use ta_rust::overlap::{sar, sarext};
 ///
 /// let high = vec![22.0, 23.0, 24.0, 25.0, 24.5, 23.5, 22.5, 21.5, 20.5, 19.5];
 /// let low = vec![20.0, 21.0, 22.0, 23.0, 22.5, 21.5, 20.5, 19.5, 18.5, 17.5];
 /// let result = sarext(&high, &low, 0.0, 0.0, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20).unwrap();
+/// assert_eq!(result, sar(&high, &low, 0.02, 0.20).unwrap());
 /// ```
 #[allow(clippy::too_many_arguments)]
 pub fn sarext(
@@ -49,118 +61,74 @@ pub fn sarext(
     af_short: f64,
     af_max_short: f64,
 ) -> TAResult<Vec<f64>> {
-    if high.is_empty() || low.is_empty() {
-        return Err(TAError::invalid_input("High and low arrays cannot be empty"));
-    }
-    
-    if high.len() != low.len() {
-        return Err(TAError::mismatched_inputs("High and low arrays must have the same length"));
-    }
-    
-    if af_init_long <= 0.0 || af_long <= 0.0 || af_max_long <= 0.0 ||
-       af_init_short <= 0.0 || af_short <= 0.0 || af_max_short <= 0.0 {
-        return Err(TAError::invalid_parameter("parameter", "must be greater than 0"));
-    }
-    
-    if af_init_long > af_max_long || af_init_short > af_max_short {
-        return Err(TAError::invalid_input("Initial AF cannot be greater than max AF"));
-    }
-    
-    let len = high.len();
-    if len < 2 {
-        return Err(TAError::invalid_input("Need at least 2 data points"));
-    }
+    with_function("SAREXT", || {
+        let signed = sarext_talib(
+            high, low, start_value, offset_on_reverse, af_init_long, af_long, af_max_long, af_init_short, af_short,
+            af_max_short,
+        )?;
+        let mut result: Vec<f64> = signed.into_iter().map(f64::abs).collect();
+        // The first SAR needs the second bar to set the trend, and holds for both
+        result[0] = result[1];
+        Ok(result)
+    })
+}
+
+/// [`sarext`] as TA-Lib reports it, with the SAR of short positions negative and none on
+/// the first bar
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sarext_talib(
+    high: &[f64],
+    low: &[f64],
+    start_value: f64,
+    offset_on_reverse: f64,
+    af_init_long: f64,
+    af_long: f64,
+    af_max_long: f64,
+    af_init_short: f64,
+    af_short: f64,
+    af_max_short: f64,
+) -> TAResult<Vec<f64>> {
+    with_function("SAREXT", || {
+        if high.is_empty() || low.is_empty() {
+            return Err(TAError::invalid_input("High and low arrays cannot be empty"));
+        }
     
-    let mut result = vec![f64::NAN; len];
+        if high.len() != low.len() {
+            return Err(TAError::mismatched_inputs("High and low arrays must have the same length"));
+        }
     
-    // Initialize for first calculation
-    let mut af: f64;
-    let mut ep: f64;
-    let mut sar: f64;
-    let mut is_long: bool;
+        if af_init_long <= 0.0 || af_long <= 0.0 || af_max_long <= 0.0 ||
+           af_init_short <= 0.0 || af_short <= 0.0 || af_max_short <= 0.0 {
+            return Err(TAError::invalid_parameter("parameter", "must be greater than 0"));
+        }
     
-    // Use start_value if provided, otherwise determine from data
-    if start_value != 0.0 {
-        sar = start_value;
-        // Determine initial trend based on start value relative to first price
-        if start_value < (high[0] + low[0]) / 2.0 {
-            is_long = true;
-            af = af_init_long;
-            ep = high[0];
-        } else {
-            is_long = false;
-            af = af_init_short;
-            ep = low[0];
+        if af_init_long > af_max_long || af_init_short > af_max_short {
+            return Err(TAError::invalid_input("Initial AF cannot be greater than max AF"));
         }
-    } else {
-        // Determine initial trend direction from price movement
-        if high[1] > high[0] {
-            // Start with uptrend
-            is_long = true;
-            sar = low[0];
-            ep = high[1];
-            af = af_init_long;
-        } else {
-            // Start with downtrend
-            is_long = false;
-            sar = high[0];
-            ep = low[1];
-            af = af_init_short;
+
+        if !(offset_on_reverse.is_finite() && offset_on_reverse >= 0.0) {
+            return Err(TAError::invalid_parameter("offset_on_reverse", "must be non-negative and finite"));
         }
-    }
     
-    result[0] = sar;
+        let len = high.len();
+        if len < 2 {
+            return Err(TAError::invalid_input("Need at least 2 data points"));
+        }
     
-    for i in 1..len {
-        let prev_sar = sar;
-        
-        // Calculate new SAR
-        sar = prev_sar + af * (ep - prev_sar);
-        
-        if is_long {
-            // In uptrend
-            // SAR cannot be above the low of current or previous period
-            sar = sar.min(low[i]).min(low[i - 1]);
-            
-            // Check for trend reversal
-            if low[i] <= sar {
-                // Trend reversal to downtrend
-                is_long = false;
-                sar = ep + offset_on_reverse;  // SAR becomes the previous EP plus offset
-                ep = low[i];  // New EP is current low
-                af = af_init_short;  // Reset AF for short
-            } else {
-                // Continue uptrend
-                if high[i] > ep {
-                    ep = high[i];  // New extreme point
-                    af = (af + af_long).min(af_max_long);  // Increase AF
-                }
-            }
-        } else {
-            // In downtrend
-            // SAR cannot be below the high of current or previous period
-            sar = sar.max(high[i]).max(high[i - 1]);
-            
-            // Check for trend reversal
-            if high[i] >= sar {
-                // Trend reversal to uptrend
-                is_long = true;
-                sar = ep - offset_on_reverse;  // SAR becomes the previous EP minus offset
-                ep = high[i];  // New EP is current high
-                af = af_init_long;  // Reset AF for long
-            } else {
-                // Continue downtrend
-                if low[i] < ep {
-                    ep = low[i];  // New extreme point
-                    af = (af + af_short).min(af_max_short);  // Increase AF
-                }
-            }
+        let long = SarFactors { init: af_init_long, step: af_long, max: af_max_long };
+        let short = SarFactors { init: af_init_short, step: af_short, max: af_max_short };
+        let signed = |position: &SarPosition| if position.is_long() { position.value() } else { -position.value() };
+
+        let mut result = vec![f64::NAN; len];
+        let mut position = SarPosition::start((high[0], low[0]), (high[1], low[1]), start_value, long, short);
+        result[1] = signed(&position);
+        for i in 2..len {
+            position.next((high[i - 1], low[i - 1]), (high[i], low[i]), long, short, offset_on_reverse);
+            result[i] = signed(&position);
         }
-        
-        result[i] = sar;
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// SAREXT with standard parameters (equivalent to regular SAR)
@@ -175,7 +143,9 @@ pub fn sarext(
 /// * `Ok(Vec<f64>)` - Vector of SAR values
 /// * `Err(TAError)` - Error if inputs are invalid
 pub fn sarext_standard(high: &[f64], low: &[f64]) -> TAResult<Vec<f64>> {
-    sarext(high, low, 0.0, 0.0, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20)
+    with_function("SAREXT", || {
+        sarext(high, low, 0.0, 0.0, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20)
+    })
 }
 
 /// SAREXT with asymmetric acceleration factors
@@ -201,7 +171,9 @@ pub fn sarext_asymmetric(
     af_short: f64,
     af_max_short: f64,
 ) -> TAResult<Vec<f64>> {
-    sarext(high, low, 0.0, 0.0, af_long, af_long, af_max_long, af_short, af_short, af_max_short)
+    with_function("SAREXT", || {
+        sarext(high, low, 0.0, 0.0, af_long, af_long, af_max_long, af_short, af_short, af_max_short)
+    })
 }
 
 #[cfg(test)]
@@ -324,4 +296,26 @@ mod tests {
             assert!(!value.is_nan());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sarext_talib_conventions() {
+        let high = vec![22.0, 23.0, 24.0, 25.0, 24.5, 23.5, 22.5, 21.5, 20.5, 19.5];
+        let low = vec![20.0, 21.0, 22.0, 23.0, 22.5, 21.5, 20.5, 19.5, 18.5, 17.5];
+        let signed = sarext_talib(&high, &low, 0.0, 0.0, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20).unwrap();
+        let plain = sarext(&high, &low, 0.0, 0.0, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20).unwrap();
+        assert!(signed[0].is_nan() && plain[0] == plain[1]);
+        assert!(signed[1..6].iter().all(|&v| v > 0.0));
+        assert!(signed[6..].iter().all(|&v| v < 0.0));
+        assert!(signed[1..].iter().zip(&plain[1..]).all(|(s, p)| s.abs() == *p));
+
+        // The offset moves the SAR away from prices by a fraction of itself on reversals
+        let offset = sarext(&high, &low, 0.0, 0.1, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20).unwrap();
+        assert_eq!(&offset[..6], &plain[..6]);
+        assert_float_eq!(offset[6], 27.5, 1e-12);
+
+        // A negative start value starts short at its magnitude
+        let short = sarext_talib(&high, &low, -30.0, 0.0, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20).unwrap();
+        assert_eq!(short[1], -30.0);
+        assert!(sarext(&high, &low, 0.0, -0.1, 0.02, 0.02, 0.20, 0.02, 0.02, 0.20).is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! Simple Moving Average (SMA)
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output};
+use crate::common::{TAResult, Price, Float, NeumaierSum, IntoParam, Period, TAOutput, sma_lookback, with_nan_policy, NanPolicy};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, validate_same_length, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Simple Moving Average (SMA)
 ///
@@ -32,7 +35,7 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// ```rust
 /// use ta_rust::overlap::sma;
 ///
-/// let prices = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+/// let prices: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
 /// let result = sma(&prices, 3).unwrap();
 /// 
 /// // First 2 values are NaN, then [2.0, 3.0, 4.0]
@@ -42,55 +45,307 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// assert_eq!(result[3], 3.0); // (2+3+4)/3
 /// assert_eq!(result[4], 4.0); // (3+4+5)/3
 /// ```
-pub fn sma(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn sma<T: Float>(data: &[T], period: impl IntoParam<Period>) -> TAResult<Vec<T>> {
+    with_function("SMA", || {
+        let period = period.into_param("period")?.get();
+        let mut output = vec![T::NAN; data.len()];
+        sma_into(data, period, &mut output)?;
+        Ok(output)
+    })
+}
+
+/// Calculates the SMA with where its values start
+///
+/// Same as [`sma`], wrapped in a [`TAOutput`] with the lookback of [`sma_lookback`](crate::common::lookback::sma_lookback).
+///
+/// # Errors
+/// Same as [`sma`]
+pub fn sma_ex(data: &[Price], period: impl IntoParam<Period>) -> TAResult<TAOutput> {
+    with_function("SMA", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(sma(data, period)?, sma_lookback(period)))
+    })
+}
+
+/// Calculates the SMA of a series with missing values
+///
+/// Same as [`sma`], with NaN in `data` handled according to `policy` rather than leaving
+/// every window that holds one NaN; see [`with_nan_policy`](crate::common::with_nan_policy).
+///
+/// # Errors
+/// Same as [`sma`], plus `InvalidInput` if `data` holds NaN under [`NanPolicy::Error`]
+pub fn sma_with_policy(data: &[Price], period: impl IntoParam<Period>, policy: NanPolicy) -> TAResult<Vec<Price>> {
+    with_function("SMA", || with_nan_policy(&[data], policy, |i| sma(i[0], period)))
+}
+
+/// Calculates the SMA into a caller-provided buffer
+///
+/// Allocation-free variant of [`sma`]. `out` must have the same length as `data`;
+/// its first `period-1` entries are set to NaN.
+///
+/// # Returns
+/// The number of valid values written (`data.len() - period + 1`).
+///
+/// # Errors
+/// Same as [`sma`], plus `MismatchedInputs` if `out.len() != data.len()`
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::sma_into;
+///
+/// let prices = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let mut out = [0.0; 5];
+/// let valid = sma_into(&prices, 3, &mut out).unwrap();
+/// assert_eq!(valid, 3);
+/// assert_eq!(out[4], 4.0);
+/// ```
+pub fn sma_into<T: Float>(data: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> TAResult<usize> {
+    with_function("SMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
+        validate_same_length(data, out, "data", "out")?;
 
-    let mut output = allocate_output(data.len());
+        out[..period - 1].fill(T::NAN);
     
-    // Calculate SMA for each position starting from period-1
-    for i in (period - 1)..data.len() {
-        let sum: Price = data[(i + 1 - period)..=i].iter().sum();
-        output[i] = sum / period as Price;
-    }
+        // Compensated running sum, so that long series do not drift from the window sums
+        let mut sum = NeumaierSum::new();
+        // Non-finite values are kept out of the running sum; their windows are summed directly
+        let mut invalid = 0usize;
+        for i in 0..data.len() {
+            if data[i].is_finite() {
+                sum.add(data[i]);
+            } else {
+                invalid += 1;
+            }
+            if i + 1 < period {
+                continue;
+            }
 
-    Ok(output)
+            let start = i + 1 - period;
+            out[i] = if invalid == 0 {
+                sum.value() / T::from_usize(period)
+            } else {
+                data[start..=i].iter().sum::<T>() / T::from_usize(period)
+            };
+
+            if data[start].is_finite() {
+                sum.sub(data[start]);
+            } else {
+                invalid -= 1;
+            }
+        }
+
+        Ok(data.len() - period + 1)
+    })
 }
 
 /// Calculates SMA with a rolling window approach (more memory efficient for large datasets)
 ///
-/// This version maintains a running sum and updates it incrementally,
-/// which can be more efficient for very large datasets.
-pub fn sma_rolling(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+/// This version maintains a compensated running sum and updates it incrementally,
+/// which can be more efficient for very large datasets. Unlike [`sma`], a NaN in the
+/// input makes every later value NaN.
+pub fn sma_rolling(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("SMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
-    let mut sum = 0.0;
+        let mut output = allocate_output(data.len());
+        let mut sum = NeumaierSum::new();
     
-    // Initialize sum for first period
-    for i in 0..period {
-        sum += data[i];
-    }
-    output[period - 1] = sum / period as Price;
+        // Initialize sum for first period
+        for &value in &data[..period] {
+            sum.add(value);
+        }
+        output[period - 1] = sum.value() / period as Price;
     
-    // Rolling calculation for remaining values
-    for i in period..data.len() {
-        sum = sum - data[i - period] + data[i];
-        output[i] = sum / period as Price;
+        // Rolling calculation for remaining values
+        for i in period..data.len() {
+            sum.sub(data[i - period]);
+            sum.add(data[i]);
+            output[i] = sum.value() / period as Price;
+        }
+
+        Ok(output)
+    })
+}
+
+/// Calculates the SMA over a period known at compile time
+///
+/// Same values as [`sma`], bit for bit, computed with a [`SmaWindow`] whose window lives
+/// on the stack.
+///
+/// # Errors
+/// - `EmptyInput` if data is empty
+/// - `InsufficientData` if data length < `N`
+///
+/// A period of 0 does not compile.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::{sma, sma_const};
+///
+/// let prices: Vec<f64> = (0..30).map(|i| 100.0 + (i as f64 * 0.5).sin()).collect();
+/// let result = sma_const::<14>(&prices).unwrap();
+/// assert!(result[12].is_nan());
+/// assert_eq!(result[20], sma(&prices, 14).unwrap()[20]);
+/// ```
+pub fn sma_const<const N: usize>(data: &[Price]) -> TAResult<Vec<Price>> {
+    with_function("SMA", || {
+        let mut window = SmaWindow::<N>::new();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, N, "data")?;
+        Ok(data.iter().map(|&value| window.update(value).unwrap_or(Price::NAN)).collect())
+    })
+}
+
+/// Streaming SMA over a period known at compile time, without heap allocation
+///
+/// The last `N` values are kept in an array, so the whole state lives inline and fits
+/// targets without an allocator. It produces the same values as [`sma`], including for
+/// windows holding NaN, which only affect the windows they are in.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::SmaWindow;
+///
+/// let mut sma = SmaWindow::<3, f32>::new();
+/// assert_eq!(sma.update(1.0), None);
+/// assert_eq!(sma.update(2.0), None);
+/// assert_eq!(sma.update(3.0), Some(2.0));
+/// assert_eq!(sma.update(7.0), Some(4.0));
+/// ```
+///
+/// With the `serde` feature it serializes like the other streaming states; deserializing
+/// checks that the window holds `N` values and a consistent position and NaN count.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "SmaWindowSnapshot<T>", try_from = "SmaWindowSnapshot<T>"))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "T: serde::Serialize", deserialize = "T: serde::Deserialize<'de>")))]
+pub struct SmaWindow<const N: usize, T: Float = Price> {
+    /// Window values in a ring, `next` pointing at the oldest once full
+    values: [T; N],
+    next: usize,
+    /// Number of values seen, up to `N`
+    filled: usize,
+    /// Compensated sum of the finite values in the window
+    sum: NeumaierSum<T>,
+    /// Number of non-finite values in the window
+    invalid: usize,
+    /// Current value, once warmed up
+    value: Option<T>,
+}
+
+impl<const N: usize, T: Float> SmaWindow<N, T> {
+    /// Creates an empty window
+    pub fn new() -> Self {
+        const { assert!(N > 0, "the SMA period must be at least 1") };
+        Self { values: [T::ZERO; N], next: 0, filled: 0, sum: NeumaierSum::new(), invalid: 0, value: None }
     }
 
-    Ok(output)
+    /// Feeds the next value and returns the average, or `None` until `N` values were seen
+    pub fn update(&mut self, value: T) -> Option<T> {
+        // Same order of operations as `sma_into`: add, average, then drop the oldest
+        if value.is_finite() {
+            self.sum.add(value);
+        } else {
+            self.invalid += 1;
+        }
+        self.values[self.next] = value;
+        self.next = (self.next + 1) % N;
+        self.filled = (self.filled + 1).min(N);
+        if self.filled < N {
+            return None;
+        }
+
+        let average = if self.invalid == 0 {
+            self.sum.value()
+        } else {
+            let (newer, older) = self.values.split_at(self.next);
+            older.iter().chain(newer).copied().sum::<T>()
+        } / T::from_usize(N);
+
+        let oldest = self.values[self.next];
+        if oldest.is_finite() {
+            self.sum.sub(oldest);
+        } else {
+            self.invalid -= 1;
+        }
+        self.value = Some(average);
+        self.value
+    }
+
+    /// Latest average, once warmed up
+    pub fn current(&self) -> Option<T> {
+        self.value
+    }
+
+    /// Clears the window
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize, T: Float> Default for SmaWindow<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fields of a serialized [`SmaWindow`], the ring as a sequence with NaN as `None`
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SmaWindowSnapshot<T> {
+    values: Vec<Option<T>>,
+    next: usize,
+    filled: usize,
+    sum: NeumaierSum<T>,
+    invalid: usize,
+    value: Option<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize, T: Float> From<SmaWindow<N, T>> for SmaWindowSnapshot<T> {
+    fn from(window: SmaWindow<N, T>) -> Self {
+        let encode = |value: T| if value.is_nan() { None } else { Some(value) };
+        Self {
+            values: window.values.iter().map(|&value| encode(value)).collect(),
+            next: window.next,
+            filled: window.filled,
+            sum: window.sum,
+            invalid: window.invalid,
+            value: window.value.and_then(encode),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize, T: Float> TryFrom<SmaWindowSnapshot<T>> for SmaWindow<N, T> {
+    type Error = crate::common::TAError;
+
+    fn try_from(snapshot: SmaWindowSnapshot<T>) -> TAResult<Self> {
+        let values: Vec<T> = snapshot.values.iter().map(|value| value.unwrap_or(T::NAN)).collect();
+        let values: [T; N] = values.try_into().map_err(|values: Vec<T>| {
+            crate::common::TAError::invalid_input(format!("window holds {} values, expected {}", values.len(), N))
+        })?;
+        let invalid = values.iter().filter(|value| !value.is_finite()).count();
+        let position_ok = (snapshot.filled == N && snapshot.next < N) || (snapshot.filled < N && snapshot.next == snapshot.filled);
+        if !position_ok || snapshot.invalid != invalid {
+            return Err(crate::common::TAError::invalid_input("window position or NaN count does not match its values"));
+        }
+        // A warm window has a value, NaN when it holds NaN
+        let value = (snapshot.filled == N).then(|| snapshot.value.unwrap_or(T::NAN));
+        Ok(Self { values, next: snapshot.next, filled: snapshot.filled, sum: snapshot.sum, invalid, value })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
+    use crate::common::{assert_arrays_approx_equal, fixed_point_prices, fixed_to_f64, DEFAULT_TOLERANCE, FIXED_POINT_BITS};
 
     #[test]
     fn test_sma_basic() {
@@ -152,7 +407,7 @@ mod tests {
 
     #[test]
     fn test_sma_empty_data() {
-        let data = vec![];
+        let data: Vec<Price> = vec![];
         assert!(sma(&data, 3).is_err());
     }
 
@@ -176,6 +431,36 @@ mod tests {
         assert!((result[49] - expected_at_50).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_sma_long_series_accuracy() {
+        // Half a million bars, compared with the exactly computed window means
+        let period = 1_000;
+        let fixed = fixed_point_prices(500_000, 11);
+        let data: Vec<Price> = fixed.iter().map(|&p| fixed_to_f64(p, FIXED_POINT_BITS)).collect();
+        let result = sma(&data, period).unwrap();
+
+        let mut window: i128 = fixed[..period - 1].iter().sum();
+        let mut max_error: Price = 0.0;
+        for i in period - 1..data.len() {
+            window += fixed[i];
+            let exact = fixed_to_f64(window, FIXED_POINT_BITS) / period as Price;
+            max_error = max_error.max((result[i] - exact).abs() / exact);
+            window -= fixed[i + 1 - period];
+        }
+        assert!(max_error <= 2.0 * Price::EPSILON, "relative error {}", max_error);
+        assert_arrays_approx_equal(&sma_rolling(&data, period).unwrap(), &result, 1e-9);
+    }
+
+    #[test]
+    fn test_sma_nan_only_affects_its_windows() {
+        let mut data: Vec<Price> = (1..=10).map(|x| x as Price).collect();
+        data[4] = Price::NAN;
+        let result = sma(&data, 3).unwrap();
+        assert!(result[3].is_finite());
+        assert!(result[4..7].iter().all(|v| v.is_nan()));
+        assert_eq!(result[9], 9.0);
+    }
+
     #[test]
     fn test_sma_constant_values() {
         let data = vec![5.0; 10];
@@ -186,4 +471,75 @@ mod tests {
             assert!((result[i] - 5.0).abs() < 1e-10);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sma_into_matches_sma() {
+        let data: Vec<Price> = (1..=20).map(|x| (x as Price).sqrt()).collect();
+        let mut out = vec![0.0; data.len()];
+        let valid = sma_into(&data, 4, &mut out).unwrap();
+        
+        assert_eq!(valid, 17);
+        assert_arrays_approx_equal(&out, &sma(&data, 4).unwrap(), DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_sma_into_wrong_buffer_length() {
+        let data = vec![1.0, 2.0, 3.0, 4.0];
+        let mut out = vec![0.0; 3];
+        assert!(sma_into(&data, 2, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_sma_const_matches_sma() {
+        let mut data: Vec<Price> = (0..200).map(|i| 50.0 + (i as Price * 0.37).sin() * 4.0 + (i % 9) as Price * 0.01).collect();
+        data[60] = Price::NAN;
+        let expected = sma(&data, 14).unwrap();
+        let result = sma_const::<14>(&data).unwrap();
+        assert!(result.iter().zip(&expected).all(|(a, b)| a.to_bits() == b.to_bits()));
+        assert!(result[60..74].iter().all(|v| v.is_nan()) && result[74].is_finite());
+        assert!(sma_const::<14>(&data[..13]).is_err());
+
+        let mut window = SmaWindow::<1>::new();
+        assert_eq!(window.update(3.5), Some(3.5));
+        window.reset();
+        assert_eq!(window.current(), None);
+    }
+
+    #[test]
+    fn test_sma_f32() {
+        let data: Vec<f32> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let result = sma(&data, 3).unwrap();
+        
+        assert!(result[1].is_nan());
+        assert_eq!(result[2..], [2.0f32, 3.0, 4.0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_sma_window_serde_round_trip() {
+        use crate::checkpoint::Checkpointed;
+
+        let mut data: Vec<Price> = (0..80).map(|i| 20.0 + (i as Price * 0.41).cos() * 3.0).collect();
+        data[30] = Price::NAN;
+        // Snapshots while filling, with NaN in the window, and after it has left
+        for split in [2, 33, 60] {
+            let mut live = Checkpointed::new(SmaWindow::<5>::new(), 3).unwrap();
+            data[..split].iter().for_each(|&v| { live.update(v); });
+            let json = serde_json::to_string(&live).unwrap();
+            let mut resumed: Checkpointed<SmaWindow<5>> = serde_json::from_str(&json).unwrap();
+            for &v in &data[split..] {
+                let (a, b) = (live.update(v), resumed.update(v));
+                assert_eq!(a.map(Price::to_bits), b.map(Price::to_bits));
+            }
+        }
+
+        let mut window = SmaWindow::<3, f32>::new();
+        [1.0, 2.0, 3.0, 4.0].iter().for_each(|&v| { window.update(v); });
+        let json = serde_json::to_string(&window).unwrap();
+        assert_eq!(serde_json::from_str::<SmaWindow<3, f32>>(&json).unwrap(), window);
+        // The ring must hold N values at a consistent position
+        assert!(serde_json::from_str::<SmaWindow<4, f32>>(&json).is_err());
+        let edited = json.replace("\"next\":1", "\"next\":3");
+        assert!(serde_json::from_str::<SmaWindow<3, f32>>(&edited).is_err());
+    }
+}
@@ -4,8 +4,12 @@
 //! It applies exponential smoothing six times with a volume factor to control
 //! the balance between responsiveness and smoothness.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{MAType, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::overlap::ema;
+use crate::overlap::ma::ma_skip_nan;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// T3 - Triple Exponential Moving Average
 ///
@@ -46,49 +50,48 @@ use crate::overlap::ema;
 /// let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0, 18.0, 17.0];
 /// let result = t3(&close, 5, 0.7).unwrap();
 /// ```
-pub fn t3(close: &[f64], period: usize, volume_factor: f64) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn t3(close: &[f64], period: impl IntoParam<Period>, volume_factor: f64) -> TAResult<Vec<f64>> {
+    with_function("T3", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    if volume_factor < 0.0 || volume_factor > 1.0 {
-        return Err(TAError::invalid_parameter("factor", "between 0.0 and 1.0"));
-    }
+        if !(0.0..=1.0).contains(&volume_factor) {
+            return Err(TAError::invalid_parameter("factor", "between 0.0 and 1.0"));
+        }
     
-    let len = close.len();
+        let len = close.len();
     
-    // Calculate the six EMAs
-    let e1 = ema(close, period)?;
-    let e2 = ema(&e1, period)?;
-    let e3 = ema(&e2, period)?;
-    let e4 = ema(&e3, period)?;
-    let e5 = ema(&e4, period)?;
-    let e6 = ema(&e5, period)?;
+        // Calculate the six EMAs
+        let e1 = ema(close, period)?;
+        let e2 = ma_skip_nan(&e1, period, MAType::EMA)?;
+        let e3 = ma_skip_nan(&e2, period, MAType::EMA)?;
+        let e4 = ma_skip_nan(&e3, period, MAType::EMA)?;
+        let e5 = ma_skip_nan(&e4, period, MAType::EMA)?;
+        let e6 = ma_skip_nan(&e5, period, MAType::EMA)?;
     
-    // Calculate coefficients
-    let v = volume_factor;
-    let v2 = v * v;
-    let v3 = v2 * v;
+        // Calculate coefficients
+        let v = volume_factor;
+        let v2 = v * v;
+        let v3 = v2 * v;
     
-    let c1 = -v3;
-    let c2 = 3.0 * v2 + 3.0 * v3;
-    let c3 = -6.0 * v2 - 3.0 * v - 3.0 * v3;
-    let c4 = 1.0 + 3.0 * v + v3 + 3.0 * v2;
+        let c1 = -v3;
+        let c2 = 3.0 * v2 + 3.0 * v3;
+        let c3 = -6.0 * v2 - 3.0 * v - 3.0 * v3;
+        let c4 = 1.0 + 3.0 * v + v3 + 3.0 * v2;
     
-    // Calculate T3
-    let mut result = vec![f64::NAN; len];
+        // Calculate T3
+        let mut result = vec![f64::NAN; len];
     
-    for i in 0..len {
-        if !e3[i].is_nan() && !e4[i].is_nan() && !e5[i].is_nan() && !e6[i].is_nan() {
-            result[i] = c1 * e6[i] + c2 * e5[i] + c3 * e4[i] + c4 * e3[i];
+        for i in 0..len {
+            if !e3[i].is_nan() && !e4[i].is_nan() && !e5[i].is_nan() && !e6[i].is_nan() {
+                result[i] = c1 * e6[i] + c2 * e5[i] + c3 * e4[i] + c4 * e3[i];
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// T3 with default parameters (period=5, volume_factor=0.7)
@@ -117,7 +120,9 @@ pub fn t3_default(close: &[f64]) -> TAResult<Vec<f64>> {
 /// * `Ok(Vec<f64>)` - Vector of T3 values
 /// * `Err(TAError)` - Error if inputs are invalid
 pub fn t3_custom_volume(close: &[f64], volume_factor: f64) -> TAResult<Vec<f64>> {
-    t3(close, 5, volume_factor)
+    with_function("T3", || {
+        t3(close, 5, volume_factor)
+    })
 }
 
 /// Calculate T3 lag reduction factor
@@ -331,4 +336,18 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_t3_chained_emas_skip_warmup() {
+        // Each EMA used to run over the NaN warm-up of the one before, so every value was
+        // NaN; each now starts at the first value of the previous one
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + (i as f64 * 2.1).sin() * 3.0 + i as f64 * 0.1).collect();
+        let e1 = crate::overlap::ema(&close, 3).unwrap();
+        assert!(crate::overlap::ema(&e1, 3).unwrap().iter().all(|v| v.is_nan()));
+
+        let result = t3(&close, 3, 0.7).unwrap();
+        assert_eq!(result.iter().take_while(|v| v.is_nan()).count(), crate::common::t3_lookback(3));
+        assert_float_eq!(result[12], 104.36884364906047, 1e-9);
+        assert_float_eq!(result[13], 105.29825972429728, 1e-9);
+    }
 }
@@ -1,8 +1,11 @@
 //! Triple Exponential Moving Average (TEMA)
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
 use crate::overlap::ema::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Triple Exponential Moving Average (TEMA)
 ///
@@ -41,130 +44,134 @@ use crate::overlap::ema::ema;
 /// assert!(result[5].is_nan());
 /// assert!(!result[6].is_nan());
 /// ```
-pub fn tema(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
+pub fn tema(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("TEMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
     
-    // TEMA requires at least 3×period-2 data points
-    let min_required = 3 * period - 2;
-    validate_sufficient_data(data, min_required, "data")?;
+        // TEMA requires at least 3×period-2 data points
+        let min_required = 3 * period - 2;
+        validate_sufficient_data(data, min_required, "data")?;
 
-    // Calculate first EMA
-    let ema1 = ema(data, period)?;
+        // Calculate first EMA
+        let ema1 = ema(data, period)?;
     
-    // Extract non-NaN values from EMA1 for second EMA calculation
-    let ema1_valid: Vec<Price> = ema1.iter()
-        .skip(period - 1)
-        .copied()
-        .collect();
+        // Extract non-NaN values from EMA1 for second EMA calculation
+        let ema1_valid: Vec<Price> = ema1.iter()
+            .skip(period - 1)
+            .copied()
+            .collect();
     
-    // Calculate second EMA
-    let ema2_partial = ema(&ema1_valid, period)?;
+        // Calculate second EMA
+        let ema2_partial = ema(&ema1_valid, period)?;
     
-    // Extract non-NaN values from EMA2 for third EMA calculation
-    let ema2_valid: Vec<Price> = ema2_partial.iter()
-        .skip(period - 1)
-        .copied()
-        .collect();
+        // Extract non-NaN values from EMA2 for third EMA calculation
+        let ema2_valid: Vec<Price> = ema2_partial.iter()
+            .skip(period - 1)
+            .copied()
+            .collect();
     
-    // Calculate third EMA
-    let ema3_partial = ema(&ema2_valid, period)?;
+        // Calculate third EMA
+        let ema3_partial = ema(&ema2_valid, period)?;
     
-    // Combine results
-    let mut output = allocate_output(data.len());
+        // Combine results
+        let mut output = allocate_output(data.len());
     
-    // Calculate TEMA values
-    let start_index = 3 * period - 3;
-    for i in start_index..data.len() {
-        let ema1_val = ema1[i];
-        let ema2_index = i - (period - 1);
-        let ema3_index = i - 2 * (period - 1);
+        // Calculate TEMA values
+        let start_index = 3 * period - 3;
+        for i in start_index..data.len() {
+            let ema1_val = ema1[i];
+            let ema2_index = i - (period - 1);
+            let ema3_index = i - 2 * (period - 1);
         
-        if ema2_index < ema2_partial.len() && 
-           ema3_index < ema3_partial.len() &&
-           !ema2_partial[ema2_index].is_nan() && 
-           !ema3_partial[ema3_index].is_nan() {
-            let ema2_val = ema2_partial[ema2_index];
-            let ema3_val = ema3_partial[ema3_index];
-            output[i] = 3.0 * ema1_val - 3.0 * ema2_val + ema3_val;
+            if ema2_index < ema2_partial.len() && 
+               ema3_index < ema3_partial.len() &&
+               !ema2_partial[ema2_index].is_nan() && 
+               !ema3_partial[ema3_index].is_nan() {
+                let ema2_val = ema2_partial[ema2_index];
+                let ema3_val = ema3_partial[ema3_index];
+                output[i] = 3.0 * ema1_val - 3.0 * ema2_val + ema3_val;
+            }
         }
-    }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates TEMA using a more direct approach
 ///
 /// This version calculates all three EMAs in a more integrated manner
 /// for potentially better performance.
-pub fn tema_direct(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
+pub fn tema_direct(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("TEMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
     
-    let min_required = 3 * period - 2;
-    validate_sufficient_data(data, min_required, "data")?;
+        let min_required = 3 * period - 2;
+        validate_sufficient_data(data, min_required, "data")?;
 
-    let mut output = allocate_output(data.len());
-    let multiplier = 2.0 / (period as Price + 1.0);
+        let mut output = allocate_output(data.len());
+        let multiplier = 2.0 / (period as Price + 1.0);
     
-    // Initialize first EMA with SMA of first 'period' values
-    let initial_sum: Price = data[0..period].iter().sum();
-    let mut ema1 = initial_sum / period as Price;
+        // Initialize first EMA with SMA of first 'period' values
+        let initial_sum: Price = data[0..period].iter().sum();
+        let mut ema1 = initial_sum / period as Price;
     
-    // Track EMA values for subsequent calculations
-    let mut ema1_values = Vec::with_capacity(data.len());
-    let mut ema2_values = Vec::with_capacity(data.len());
+        // Track EMA values for subsequent calculations
+        let mut ema1_values = Vec::with_capacity(data.len());
+        let mut ema2_values = Vec::with_capacity(data.len());
     
-    // Calculate EMA1 for all values
-    for i in 0..data.len() {
-        if i >= period - 1 {
-            if i > period - 1 {
-                ema1 = (data[i] * multiplier) + (ema1 * (1.0 - multiplier));
+        // Calculate EMA1 for all values
+        for (i, &price) in data.iter().enumerate() {
+            if i >= period - 1 {
+                if i > period - 1 {
+                    ema1 = (price * multiplier) + (ema1 * (1.0 - multiplier));
+                }
+                ema1_values.push(ema1);
             }
-            ema1_values.push(ema1);
         }
-    }
     
-    // Calculate EMA2 from EMA1 values
-    if ema1_values.len() >= period {
-        let initial_sum2: Price = ema1_values[0..period].iter().sum();
-        let mut ema2 = initial_sum2 / period as Price;
-        ema2_values.push(ema2);
+        // Calculate EMA2 from EMA1 values
+        if ema1_values.len() >= period {
+            let initial_sum2: Price = ema1_values[0..period].iter().sum();
+            let mut ema2 = initial_sum2 / period as Price;
+            ema2_values.push(ema2);
         
-        for i in 1..ema1_values.len() {
-            if i >= period - 1 {
-                ema2 = (ema1_values[i] * multiplier) + (ema2 * (1.0 - multiplier));
-            }
-            if i >= period - 1 {
-                ema2_values.push(ema2);
+            for (i, &ema1_value) in ema1_values.iter().enumerate().skip(1) {
+                if i >= period - 1 {
+                    ema2 = (ema1_value * multiplier) + (ema2 * (1.0 - multiplier));
+                }
+                if i >= period - 1 {
+                    ema2_values.push(ema2);
+                }
             }
         }
-    }
     
-    // Calculate EMA3 and TEMA
-    if ema2_values.len() >= period {
-        let initial_sum3: Price = ema2_values[0..period].iter().sum();
-        let mut ema3 = initial_sum3 / period as Price;
+        // Calculate EMA3 and TEMA
+        if ema2_values.len() >= period {
+            let initial_sum3: Price = ema2_values[0..period].iter().sum();
+            let mut ema3 = initial_sum3 / period as Price;
         
-        let start_index = 3 * period - 3;
-        for i in start_index..data.len() {
-            let ema1_val = ema1_values[i - (period - 1)];
-            let ema2_val = ema2_values[i - 2 * (period - 1)];
+            let start_index = 3 * period - 3;
+            for i in start_index..data.len() {
+                let ema1_val = ema1_values[i - (period - 1)];
+                let ema2_val = ema2_values[i - 2 * (period - 1)];
             
-            if i > start_index {
-                let ema2_for_ema3_index = i - 2 * (period - 1);
-                if ema2_for_ema3_index < ema2_values.len() {
-                    ema3 = (ema2_values[ema2_for_ema3_index] * multiplier) + (ema3 * (1.0 - multiplier));
+                if i > start_index {
+                    let ema2_for_ema3_index = i - 2 * (period - 1);
+                    if ema2_for_ema3_index < ema2_values.len() {
+                        ema3 = (ema2_values[ema2_for_ema3_index] * multiplier) + (ema3 * (1.0 - multiplier));
+                    }
                 }
-            }
             
-            output[i] = 3.0 * ema1_val - 3.0 * ema2_val + ema3;
+                output[i] = 3.0 * ema1_val - 3.0 * ema2_val + ema3;
+            }
         }
-    }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -1,8 +1,11 @@
 //! Triangular Moving Average (TRIMA)
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output};
+use crate::common::{TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
 use crate::overlap::sma::sma;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Triangular Moving Average (TRIMA)
 ///
@@ -41,147 +44,148 @@ use crate::overlap::sma::sma;
 /// assert!(result[3].is_nan());
 /// assert!(!result[4].is_nan());
 /// ```
-pub fn trima(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn trima(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("TRIMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    // Calculate the periods for the two SMAs
-    let (first_period, second_period) = if period % 2 == 1 {
-        // Odd period
-        let half_period = (period + 1) / 2;
-        (half_period, half_period)
-    } else {
-        // Even period
-        let first = period / 2 + 1;
-        let second = period / 2;
-        (first, second)
-    };
+        // Calculate the periods for the two SMAs
+        let (first_period, second_period) = if period % 2 == 1 {
+            // Odd period
+            let half_period = period.div_ceil(2);
+            (half_period, half_period)
+        } else {
+            // Even period
+            let first = period / 2 + 1;
+            let second = period / 2;
+            (first, second)
+        };
 
-    // Calculate first SMA
-    let first_sma = sma(data, first_period)?;
+        // Calculate first SMA
+        let first_sma = sma(data, first_period)?;
     
-    // Extract non-NaN values from first SMA
-    let first_sma_valid: Vec<Price> = first_sma.iter()
-        .skip(first_period - 1)
-        .copied()
-        .collect();
+        // Extract non-NaN values from first SMA
+        let first_sma_valid: Vec<Price> = first_sma.iter()
+            .skip(first_period - 1)
+            .copied()
+            .collect();
     
-    // Calculate second SMA
-    let second_sma = sma(&first_sma_valid, second_period)?;
+        // Calculate second SMA
+        let second_sma = sma(&first_sma_valid, second_period)?;
     
-    // Combine results
-    let mut output = allocate_output(data.len());
+        // Combine results
+        let mut output = allocate_output(data.len());
     
-    // Map second SMA results back to original indices
-    let start_index = first_period + second_period - 2;
-    for i in start_index..data.len() {
-        let second_sma_index = i - (first_period - 1);
-        if second_sma_index < second_sma.len() && !second_sma[second_sma_index].is_nan() {
-            output[i] = second_sma[second_sma_index];
+        // Map second SMA results back to original indices
+        let start_index = first_period + second_period - 2;
+        for (value, &second) in output[start_index..].iter_mut().zip(&second_sma[second_period - 1..]) {
+            if !second.is_nan() {
+                *value = second;
+            }
         }
-    }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates TRIMA using a direct calculation approach
 ///
 /// This version calculates the triangular weights directly without
 /// using intermediate SMA calculations.
-pub fn trima_direct(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn trima_direct(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("TRIMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
+        let mut output = allocate_output(data.len());
     
-    // Calculate triangular weights
-    let mut weights = Vec::with_capacity(period);
-    let half_period = (period + 1) / 2;
+        // Calculate triangular weights
+        let mut weights = Vec::with_capacity(period);
+        let half_period = period.div_ceil(2);
     
-    for i in 0..period {
-        let weight = if i < half_period {
-            (i + 1) as Price
-        } else {
-            (period - i) as Price
-        };
-        weights.push(weight);
-    }
+        for i in 0..period {
+            let weight = if i < half_period {
+                (i + 1) as Price
+            } else {
+                (period - i) as Price
+            };
+            weights.push(weight);
+        }
     
-    let weight_sum: Price = weights.iter().sum();
+        let weight_sum: Price = weights.iter().sum();
     
-    // Calculate TRIMA for each position
-    for i in (period - 1)..data.len() {
-        let mut weighted_sum = 0.0;
+        // Calculate TRIMA for each position
+        for (value, window) in output[period - 1..].iter_mut().zip(data.windows(period)) {
+            let mut weighted_sum = 0.0;
         
-        for j in 0..period {
-            let price_index = i + 1 - period + j;
-            weighted_sum += data[price_index] * weights[j];
-        }
+            for (&price, &weight) in window.iter().zip(&weights) {
+                weighted_sum += price * weight;
+            }
         
-        output[i] = weighted_sum / weight_sum;
-    }
+            *value = weighted_sum / weight_sum;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates TRIMA with custom triangular weighting
 ///
 /// This version allows for custom triangular weight distribution.
-pub fn trima_custom_peak(data: &[Price], period: Period, peak_position: Price) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn trima_custom_peak(data: &[Price], period: impl IntoParam<Period>, peak_position: Price) -> TAResult<Vec<Price>> {
+    with_function("TRIMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
     
-    if peak_position < 0.0 || peak_position > 1.0 {
-        return Err(crate::common::TAError::invalid_parameter(
-            "peak_position",
-            "must be between 0.0 and 1.0"
-        ));
-    }
+        if !(0.0..=1.0).contains(&peak_position) {
+            return Err(crate::common::TAError::invalid_parameter(
+                "peak_position",
+                "must be between 0.0 and 1.0"
+            ));
+        }
 
-    let mut output = allocate_output(data.len());
+        let mut output = allocate_output(data.len());
     
-    // Calculate custom triangular weights
-    let mut weights = Vec::with_capacity(period);
-    let peak_index = (peak_position * (period - 1) as Price).round() as usize;
+        // Calculate custom triangular weights
+        let mut weights = Vec::with_capacity(period);
+        let peak_index = (peak_position * (period - 1) as Price).round() as usize;
     
-    for i in 0..period {
-        let weight = if i <= peak_index {
-            if peak_index == 0 {
-                1.0
-            } else {
-                (i as Price / peak_index as Price) + 1.0
-            }
-        } else {
-            if peak_index == period - 1 {
+        for i in 0..period {
+            let weight = if i <= peak_index {
+                if peak_index == 0 {
+                    1.0
+                } else {
+                    (i as Price / peak_index as Price) + 1.0
+                }
+            } else if peak_index == period - 1 {
                 1.0
             } else {
                 ((period - 1 - i) as Price / (period - 1 - peak_index) as Price) + 1.0
-            }
-        };
-        weights.push(weight);
-    }
+            };
+            weights.push(weight);
+        }
     
-    let weight_sum: Price = weights.iter().sum();
+        let weight_sum: Price = weights.iter().sum();
     
-    // Calculate TRIMA for each position
-    for i in (period - 1)..data.len() {
-        let mut weighted_sum = 0.0;
+        // Calculate TRIMA for each position
+        for (value, window) in output[period - 1..].iter_mut().zip(data.windows(period)) {
+            let mut weighted_sum = 0.0;
         
-        for j in 0..period {
-            let price_index = i + 1 - period + j;
-            weighted_sum += data[price_index] * weights[j];
-        }
+            for (&price, &weight) in window.iter().zip(&weights) {
+                weighted_sum += price * weight;
+            }
         
-        output[i] = weighted_sum / weight_sum;
-    }
+            *value = weighted_sum / weight_sum;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -4,8 +4,12 @@
 //! exponentially smoothed moving average. It's designed to filter out price
 //! movements that are considered insignificant.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{MAType, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
 use crate::overlap::ema;
+use crate::overlap::ma::ma_skip_nan;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// TRIX - 1-day Rate-Of-Change of Triple Smooth EMA
 ///
@@ -18,7 +22,7 @@ use crate::overlap::ema;
 /// EMA1 = EMA(Price, n)
 /// EMA2 = EMA(EMA1, n)
 /// EMA3 = EMA(EMA2, n)
-/// TRIX = 10000 × (EMA3[today] - EMA3[yesterday]) / EMA3[yesterday]
+/// TRIX = 100 × (EMA3[today] - EMA3[yesterday]) / EMA3[yesterday]
 /// ```
 ///
 /// # Arguments
@@ -26,7 +30,7 @@ use crate::overlap::ema;
 /// * `period` - Period for the EMA calculations
 ///
 /// # Returns
-/// * `Ok(Vec<f64>)` - Vector of TRIX values (in basis points)
+/// * `Ok(Vec<f64>)` - Vector of TRIX values (in percent, as TA-Lib)
 /// * `Err(TAError)` - Error if inputs are invalid
 ///
 /// # Examples
@@ -36,37 +40,36 @@ use crate::overlap::ema;
 /// let close = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0, 18.0, 17.0];
 /// let result = trix(&close, 5).unwrap();
 /// ```
-pub fn trix(close: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn trix(close: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("TRIX", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    if close.len() < 2 {
-        return Err(TAError::invalid_input("Need at least 2 data points for rate of change"));
-    }
+        if close.len() < 2 {
+            return Err(TAError::invalid_input("Need at least 2 data points for rate of change"));
+        }
     
-    let len = close.len();
+        let len = close.len();
     
-    // Calculate the three EMAs
-    let ema1 = ema(close, period)?;
-    let ema2 = ema(&ema1, period)?;
-    let ema3 = ema(&ema2, period)?;
+        // Calculate the three EMAs
+        let ema1 = ema(close, period)?;
+        let ema2 = ma_skip_nan(&ema1, period, MAType::EMA)?;
+        let ema3 = ma_skip_nan(&ema2, period, MAType::EMA)?;
     
-    // Calculate TRIX as rate of change of EMA3
-    let mut result = vec![f64::NAN; len];
+        // Calculate TRIX as rate of change of EMA3
+        let mut result = vec![f64::NAN; len];
     
-    for i in 1..len {
-        if !ema3[i].is_nan() && !ema3[i - 1].is_nan() && ema3[i - 1].abs() > f64::EPSILON {
-            // Calculate rate of change in basis points (multiply by 10000)
-            result[i] = 10000.0 * (ema3[i] - ema3[i - 1]) / ema3[i - 1];
+        for i in 1..len {
+            if !ema3[i].is_nan() && !ema3[i - 1].is_nan() && ema3[i - 1].abs() > f64::EPSILON {
+                // Calculate rate of change in percent
+                result[i] = 100.0 * (ema3[i] - ema3[i - 1]) / ema3[i - 1];
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// TRIX with default period (14)
@@ -96,37 +99,41 @@ pub fn trix_default(close: &[f64]) -> TAResult<Vec<f64>> {
 /// # Returns
 /// * `Ok((Vec<f64>, Vec<f64>))` - Tuple of (TRIX values, Signal line values)
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn trix_signal(close: &[f64], trix_period: usize, signal_period: usize) -> TAResult<(Vec<f64>, Vec<f64>)> {
-    let trix_values = trix(close, trix_period)?;
+pub fn trix_signal(close: &[f64], trix_period: impl IntoParam<Period>, signal_period: impl IntoParam<Period>) -> TAResult<(Vec<f64>, Vec<f64>)> {
+    with_function("TRIX", || {
+        let trix_period = trix_period.into_param("trix_period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        let trix_values = trix(close, trix_period)?;
     
-    // Calculate signal line as EMA of TRIX
-    // First, we need to extract valid TRIX values for EMA calculation
-    let mut valid_trix = Vec::new();
-    let mut valid_indices = Vec::new();
+        // Calculate signal line as EMA of TRIX
+        // First, we need to extract valid TRIX values for EMA calculation
+        let mut valid_trix = Vec::new();
+        let mut valid_indices = Vec::new();
     
-    for (i, &value) in trix_values.iter().enumerate() {
-        if !value.is_nan() {
-            valid_trix.push(value);
-            valid_indices.push(i);
+        for (i, &value) in trix_values.iter().enumerate() {
+            if !value.is_nan() {
+                valid_trix.push(value);
+                valid_indices.push(i);
+            }
         }
-    }
     
-    if valid_trix.is_empty() {
-        return Ok((trix_values, vec![f64::NAN; close.len()]));
-    }
+        if valid_trix.is_empty() {
+            return Ok((trix_values, vec![f64::NAN; close.len()]));
+        }
     
-    // Calculate EMA of valid TRIX values
-    let signal_ema = ema(&valid_trix, signal_period)?;
+        // Calculate EMA of valid TRIX values
+        let signal_ema = ema(&valid_trix, signal_period)?;
     
-    // Map back to original indices
-    let mut signal_line = vec![f64::NAN; close.len()];
-    for (i, &original_idx) in valid_indices.iter().enumerate() {
-        if i < signal_ema.len() && !signal_ema[i].is_nan() {
-            signal_line[original_idx] = signal_ema[i];
+        // Map back to original indices
+        let mut signal_line = vec![f64::NAN; close.len()];
+        for (i, &original_idx) in valid_indices.iter().enumerate() {
+            if i < signal_ema.len() && !signal_ema[i].is_nan() {
+                signal_line[original_idx] = signal_ema[i];
+            }
         }
-    }
     
-    Ok((trix_values, signal_line))
+        Ok((trix_values, signal_line))
+    })
 }
 
 /// TRIX Histogram
@@ -141,19 +148,23 @@ pub fn trix_signal(close: &[f64], trix_period: usize, signal_period: usize) -> T
 /// # Returns
 /// * `Ok((Vec<f64>, Vec<f64>, Vec<f64>))` - Tuple of (TRIX, Signal, Histogram)
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn trix_histogram(close: &[f64], trix_period: usize, signal_period: usize) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
-    let (trix_values, signal_line) = trix_signal(close, trix_period, signal_period)?;
+pub fn trix_histogram(close: &[f64], trix_period: impl IntoParam<Period>, signal_period: impl IntoParam<Period>) -> TAResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    with_function("TRIX", || {
+        let trix_period = trix_period.into_param("trix_period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        let (trix_values, signal_line) = trix_signal(close, trix_period, signal_period)?;
     
-    let len = close.len();
-    let mut histogram = vec![f64::NAN; len];
+        let len = close.len();
+        let mut histogram = vec![f64::NAN; len];
     
-    for i in 0..len {
-        if !trix_values[i].is_nan() && !signal_line[i].is_nan() {
-            histogram[i] = trix_values[i] - signal_line[i];
+        for i in 0..len {
+            if !trix_values[i].is_nan() && !signal_line[i].is_nan() {
+                histogram[i] = trix_values[i] - signal_line[i];
+            }
         }
-    }
     
-    Ok((trix_values, signal_line, histogram))
+        Ok((trix_values, signal_line, histogram))
+    })
 }
 
 /// Get the triple smoothed EMA used in TRIX calculation
@@ -168,21 +179,20 @@ pub fn trix_histogram(close: &[f64], trix_period: usize, signal_period: usize) -
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of triple smoothed EMA values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn trix_ema3(close: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if close.is_empty() {
-        return Err(TAError::invalid_input("Close prices cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn trix_ema3(close: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("TRIX", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
     
-    // Calculate the three EMAs
-    let ema1 = ema(close, period)?;
-    let ema2 = ema(&ema1, period)?;
-    let ema3 = ema(&ema2, period)?;
+        // Calculate the three EMAs
+        let ema1 = ema(close, period)?;
+        let ema2 = ma_skip_nan(&ema1, period, MAType::EMA)?;
+        let ema3 = ma_skip_nan(&ema2, period, MAType::EMA)?;
     
-    Ok(ema3)
+        Ok(ema3)
+    })
 }
 
 #[cfg(test)]
@@ -207,7 +217,7 @@ mod tests {
         // TRIX values should be reasonable (not extremely large)
         for &value in result.iter() {
             if !value.is_nan() {
-                assert!(value.abs() < 10000.0); // Should be reasonable basis points
+                assert!(value.abs() < 100.0); // Should be a reasonable percentage
             }
         }
     }
@@ -396,4 +406,18 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_trix_chained_emas_skip_warmup() {
+        // The second and third EMAs used to run over the NaN warm-up of the one before, so
+        // every value was NaN; each now starts at the first value of the previous one
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + (i as f64 * 2.1).sin() * 3.0 + i as f64 * 0.1).collect();
+        let ema1 = crate::overlap::ema(&close, 5).unwrap();
+        assert!(crate::overlap::ema(&ema1, 5).unwrap().iter().all(|v| v.is_nan()));
+
+        let result = trix(&close, 5).unwrap();
+        assert_eq!(result.iter().take_while(|v| v.is_nan()).count(), crate::common::trix_lookback(5));
+        assert_float_eq!(result[13], 0.62864969189277105, 1e-11);
+        assert_float_eq!(result[14], 0.32178971667550364, 1e-11);
+    }
 }
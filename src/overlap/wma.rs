@@ -1,7 +1,10 @@
 //! Weighted Moving Average (WMA)
 
-use crate::common::{TAResult, Price, Period};
-use crate::common::utils::{validate_not_empty, validate_period, validate_sufficient_data, allocate_output};
+use crate::common::{TAResult, Price, Float, IntoParam, Period, TAOutput, wma_lookback};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, validate_same_length, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Weighted Moving Average (WMA)
 ///
@@ -35,38 +38,72 @@ use crate::common::utils::{validate_not_empty, validate_period, validate_suffici
 /// ```rust
 /// use ta_rust::overlap::wma;
 ///
-/// let prices = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+/// let prices: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
 /// let result = wma(&prices, 3).unwrap();
 /// 
 /// // WMA calculation for index 2: (1×1 + 2×2 + 3×3) / (1+2+3) = 14/6 = 2.333...
 /// assert!((result[2] - 2.333333333333333).abs() < 1e-10);
 /// ```
-pub fn wma(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn wma<T: Float>(data: &[T], period: impl IntoParam<Period>) -> TAResult<Vec<T>> {
+    with_function("WMA", || {
+        let period = period.into_param("period")?.get();
+        let mut output = vec![T::NAN; data.len()];
+        wma_into(data, period, &mut output)?;
+        Ok(output)
+    })
+}
+
+/// Calculates the WMA with where its values start
+///
+/// Same as [`wma`], wrapped in a [`TAOutput`] with the lookback of [`wma_lookback`](crate::common::lookback::wma_lookback).
+///
+/// # Errors
+/// Same as [`wma`]
+pub fn wma_ex(data: &[Price], period: impl IntoParam<Period>) -> TAResult<TAOutput> {
+    with_function("WMA", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(wma(data, period)?, wma_lookback(period)))
+    })
+}
 
-    let mut output = allocate_output(data.len());
+/// Calculates the WMA into a caller-provided buffer
+///
+/// Allocation-free variant of [`wma`]. `out` must have the same length as `data`;
+/// its first `period-1` entries are set to NaN.
+///
+/// # Returns
+/// The number of valid values written (`data.len() - period + 1`).
+///
+/// # Errors
+/// Same as [`wma`], plus `MismatchedInputs` if `out.len() != data.len()`
+pub fn wma_into<T: Float>(data: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> TAResult<usize> {
+    with_function("WMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
+        validate_same_length(data, out, "data", "out")?;
+
+        out[..period - 1].fill(T::NAN);
     
-    // Pre-calculate weight sum: 1 + 2 + ... + n = n(n+1)/2
-    let weight_sum = (period * (period + 1)) as Price / 2.0;
+        // Pre-calculate weight sum: 1 + 2 + ... + n = n(n+1)/2
+        let weight_sum = T::from_f64((period * (period + 1)) as Price / 2.0);
     
-    // Calculate WMA for each position starting from period-1
-    for i in (period - 1)..data.len() {
-        let mut weighted_sum = 0.0;
+        // Calculate WMA for each position starting from period-1
+        for (i, value) in out.iter_mut().enumerate().skip(period - 1) {
+            let window = &data[(i + 1 - period)..=i];
         
-        // Calculate weighted sum for current window
-        for j in 0..period {
-            let weight = (j + 1) as Price; // Weight increases with recency
-            let price_index = i + 1 - period + j;
-            weighted_sum += data[price_index] * weight;
-        }
+            // Weight increases with recency
+            let weighted_sum: T = window.iter()
+                .enumerate()
+                .map(|(j, &price)| price * T::from_usize(j + 1))
+                .sum();
         
-        output[i] = weighted_sum / weight_sum;
-    }
+            *value = weighted_sum / weight_sum;
+        }
 
-    Ok(output)
+        Ok(data.len() - period + 1)
+    })
 }
 
 /// Calculates WMA with custom weights
@@ -86,82 +123,84 @@ pub fn wma(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
 /// let result = wma_custom(&prices, &weights).unwrap();
 /// ```
 pub fn wma_custom(data: &[Price], weights: &[Price]) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_not_empty(weights, "weights")?;
+    with_function("WMA", || {
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_not_empty(weights, "weights")?;
     
-    let period = weights.len();
-    validate_sufficient_data(data, period, "data")?;
+        let period = weights.len();
+        validate_sufficient_data(data, period, "data")?;
+    
+        // Validate weights are positive
+        for (i, &weight) in weights.iter().enumerate() {
+            if weight < 0.0 || !weight.is_finite() {
+                return Err(crate::common::TAError::invalid_parameter(
+                    "weights",
+                    &format!("weight at index {} must be non-negative and finite", i)
+                ));
+            }
+        }
     
-    // Validate weights are positive
-    for (i, &weight) in weights.iter().enumerate() {
-        if weight < 0.0 || !weight.is_finite() {
+        let weight_sum: Price = weights.iter().sum();
+        if weight_sum <= 0.0 {
             return Err(crate::common::TAError::invalid_parameter(
                 "weights",
-                &format!("weight at index {} must be non-negative and finite", i)
+                "sum of weights must be positive"
             ));
         }
-    }
-    
-    let weight_sum: Price = weights.iter().sum();
-    if weight_sum <= 0.0 {
-        return Err(crate::common::TAError::invalid_parameter(
-            "weights",
-            "sum of weights must be positive"
-        ));
-    }
 
-    let mut output = allocate_output(data.len());
+        let mut output = allocate_output(data.len());
     
-    // Calculate WMA for each position starting from period-1
-    for i in (period - 1)..data.len() {
-        let mut weighted_sum = 0.0;
+        // Calculate WMA for each position starting from period-1
+        for (value, window) in output[period - 1..].iter_mut().zip(data.windows(period)) {
+            let mut weighted_sum = 0.0;
         
-        // Calculate weighted sum for current window
-        for j in 0..period {
-            let price_index = i + 1 - period + j;
-            weighted_sum += data[price_index] * weights[j];
-        }
+            // Calculate weighted sum for current window
+            for (&price, &weight) in window.iter().zip(weights) {
+                weighted_sum += price * weight;
+            }
         
-        output[i] = weighted_sum / weight_sum;
-    }
+            *value = weighted_sum / weight_sum;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 /// Calculates WMA using a rolling approach for better performance
 ///
 /// This version is more efficient for large datasets as it maintains
 /// running weighted sums and updates them incrementally.
-pub fn wma_rolling(data: &[Price], period: Period) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(data, "data")?;
-    validate_period(period, "period")?;
-    validate_sufficient_data(data, period, "data")?;
+pub fn wma_rolling(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("WMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
 
-    let mut output = allocate_output(data.len());
-    let weight_sum = (period * (period + 1)) as Price / 2.0;
+        let mut output = allocate_output(data.len());
+        let weight_sum = (period * (period + 1)) as Price / 2.0;
     
-    // Calculate first WMA value
-    let mut weighted_sum = 0.0;
-    for j in 0..period {
-        weighted_sum += data[j] * (j + 1) as Price;
-    }
-    output[period - 1] = weighted_sum / weight_sum;
+        // Calculate first WMA value
+        let mut weighted_sum = 0.0;
+        for (j, &price) in data[..period].iter().enumerate() {
+            weighted_sum += price * (j + 1) as Price;
+        }
+        output[period - 1] = weighted_sum / weight_sum;
     
-    // Rolling calculation for remaining values
-    for i in period..data.len() {
-        // Remove contribution of oldest value and add newest
-        // This is more complex for WMA due to changing weights
-        weighted_sum = 0.0;
-        for j in 0..period {
-            let price_index = i + 1 - period + j;
-            weighted_sum += data[price_index] * (j + 1) as Price;
+        // Rolling calculation for remaining values
+        for (value, window) in output[period..].iter_mut().zip(data[1..].windows(period)) {
+            // Remove contribution of oldest value and add newest
+            // This is more complex for WMA due to changing weights
+            weighted_sum = 0.0;
+            for (j, &price) in window.iter().enumerate() {
+                weighted_sum += price * (j + 1) as Price;
+            }
+            *value = weighted_sum / weight_sum;
         }
-        output[i] = weighted_sum / weight_sum;
-    }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
@@ -275,7 +314,7 @@ mod tests {
 
     #[test]
     fn test_wma_empty_data() {
-        let data = vec![];
+        let data: Vec<Price> = vec![];
         assert!(wma(&data, 3).is_err());
     }
 
@@ -0,0 +1,316 @@
+//! ZigZag
+//!
+//! ZigZag connects alternating swing highs and lows, ignoring any move smaller than a
+//! threshold. A swing is only known once price has reversed by the threshold, so the last
+//! leg of the line is provisional and may be redrawn as new bars arrive.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::volatility::atr;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// A ZigZag pivot
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZigZagPivot {
+    /// Index of the bar at which the extreme occurred
+    pub index: usize,
+    /// High of that bar for swing highs, low for swing lows
+    pub price: f64,
+    /// `true` for a swing high, `false` for a swing low
+    pub is_high: bool,
+    /// Index of the bar whose reversal confirmed the pivot; `None` for the provisional last
+    /// pivot
+    pub confirmed_at: Option<usize>,
+}
+
+/// ZigZag result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZigZag {
+    /// Alternating swing highs and lows in bar order; the last one is provisional
+    pub pivots: Vec<ZigZagPivot>,
+    /// Line interpolated between pivots, NaN before the first and after the last pivot
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub line: Vec<f64>,
+}
+
+impl ZigZag {
+    /// Confirmed swing highs and lows
+    ///
+    /// Drops the provisional last pivot, leaving only swings that no future bar can move.
+    pub fn swings(&self) -> Vec<ZigZagPivot> {
+        self.pivots.iter().filter(|p| p.confirmed_at.is_some()).copied().collect()
+    }
+}
+
+/// ZigZag with a percentage threshold
+///
+/// A swing high is confirmed once a low falls `percent`% below it, and a swing low once a
+/// high rises `percent`% above it. While a leg is running, a bar that extends it is never
+/// also taken as a reversal.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `percent` - Minimum reversal in percent (positive, e.g. 5.0 for 5%)
+///
+/// # Returns
+/// * `Ok(ZigZag)` - Pivots and interpolated line; bars with non-finite prices are skipped
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::zigzag;
+///
+/// let high = vec![10.0, 11.0, 12.0, 11.5, 10.6, 11.0, 12.5];
+/// let low = vec![9.5, 10.5, 11.5, 10.8, 10.0, 10.5, 12.0];
+/// let result = zigzag(&high, &low, 10.0).unwrap();
+///
+/// let swings = result.swings();
+/// assert_eq!(swings.len(), 3);
+/// assert_eq!((swings[0].index, swings[0].price), (0, 9.5));
+/// assert_eq!((swings[1].index, swings[1].price), (2, 12.0));
+/// assert_eq!((swings[2].index, swings[2].price), (4, 10.0));
+/// assert_eq!(result.line[1], 10.75);
+/// ```
+pub fn zigzag(high: &[f64], low: &[f64], percent: f64) -> TAResult<ZigZag> {
+    with_function("ZIGZAG", || {
+        validate(high, low)?;
+
+        if !percent.is_finite() || percent <= 0.0 {
+            return Err(TAError::invalid_parameter("percent", "must be a positive finite number"));
+        }
+
+        let fraction = percent / 100.0;
+        Ok(build(high, low, |_, price| price.abs() * fraction))
+    })
+}
+
+/// ZigZag with a threshold in multiples of the Average True Range
+///
+/// The reversal needed at each bar is `ATR × multiplier` at that bar, so the threshold
+/// adapts to volatility without using future data. No pivot is confirmed before the ATR is
+/// available.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `period` - ATR period (typically 14)
+/// * `multiplier` - Minimum reversal in ATRs (positive, typically 2.0 to 3.0)
+///
+/// # Returns
+/// * `Ok(ZigZag)` - Pivots and interpolated line; bars with non-finite prices are skipped
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn zigzag_atr(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, multiplier: f64) -> TAResult<ZigZag> {
+    with_function("ZIGZAG", || {
+        let period = period.into_param("period")?.get();
+        validate(high, low)?;
+
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(TAError::invalid_parameter("multiplier", "must be a positive finite number"));
+        }
+
+        let range = atr(high, low, close, period)?;
+        Ok(build(high, low, |i, _| range[i] * multiplier))
+    })
+}
+
+fn validate(high: &[f64], low: &[f64]) -> TAResult<()> {
+    if high.is_empty() || low.is_empty() {
+        return Err(TAError::invalid_input("Input arrays cannot be empty"));
+    }
+
+    if high.len() != low.len() {
+        return Err(TAError::mismatched_inputs("High and Low arrays must have the same length"));
+    }
+
+    Ok(())
+}
+
+/// Progress of the pivot search
+enum Leg {
+    /// No pivot yet: the highest high and lowest low so far
+    Seeking { high: (usize, f64), low: (usize, f64) },
+    /// Last pivot was a low; tracking the highest high since
+    Rising((usize, f64)),
+    /// Last pivot was a high; tracking the lowest low since
+    Falling((usize, f64)),
+}
+
+/// Finds pivots with the reversal required at bar `i` from an extreme `price` given by
+/// `threshold(i, price)`; a NaN threshold confirms nothing
+fn build(high: &[f64], low: &[f64], threshold: impl Fn(usize, f64) -> f64) -> ZigZag {
+    let mut pivots = Vec::new();
+    let mut leg: Option<Leg> = None;
+
+    let pivot = |(index, price): (usize, f64), is_high: bool, confirmed_at: Option<usize>| ZigZagPivot {
+        index,
+        price,
+        is_high,
+        confirmed_at,
+    };
+
+    for (i, (&h, &l)) in high.iter().zip(low).enumerate() {
+        if !h.is_finite() || !l.is_finite() {
+            continue;
+        }
+
+        leg = Some(match leg {
+            None => Leg::Seeking { high: (i, h), low: (i, l) },
+            Some(Leg::Seeking { mut high, mut low }) => {
+                if h > high.1 {
+                    high = (i, h);
+                }
+                if l < low.1 {
+                    low = (i, l);
+                }
+
+                // The earlier extreme becomes the first pivot once the range covers the
+                // threshold measured from it
+                let range = high.1 - low.1;
+                if low.0 < high.0 && range >= threshold(i, low.1) {
+                    pivots.push(pivot(low, false, Some(i)));
+                    Leg::Rising(high)
+                } else if high.0 < low.0 && range >= threshold(i, high.1) {
+                    pivots.push(pivot(high, true, Some(i)));
+                    Leg::Falling(low)
+                } else {
+                    Leg::Seeking { high, low }
+                }
+            }
+            Some(Leg::Rising(extreme)) => {
+                if h > extreme.1 {
+                    Leg::Rising((i, h))
+                } else if extreme.1 - l >= threshold(i, extreme.1) {
+                    pivots.push(pivot(extreme, true, Some(i)));
+                    Leg::Falling((i, l))
+                } else {
+                    Leg::Rising(extreme)
+                }
+            }
+            Some(Leg::Falling(extreme)) => {
+                if l < extreme.1 {
+                    Leg::Falling((i, l))
+                } else if h - extreme.1 >= threshold(i, extreme.1) {
+                    pivots.push(pivot(extreme, false, Some(i)));
+                    Leg::Rising((i, h))
+                } else {
+                    Leg::Falling(extreme)
+                }
+            }
+        });
+    }
+
+    match leg {
+        Some(Leg::Rising(extreme)) => pivots.push(pivot(extreme, true, None)),
+        Some(Leg::Falling(extreme)) => pivots.push(pivot(extreme, false, None)),
+        _ => {}
+    }
+
+    let mut line = vec![f64::NAN; high.len()];
+    if let Some(first) = pivots.first() {
+        line[first.index] = first.price;
+    }
+    for pair in pivots.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let step = (to.price - from.price) / (to.index - from.index) as f64;
+        for (offset, value) in line[from.index..=to.index].iter_mut().enumerate() {
+            *value = from.price + step * offset as f64;
+        }
+    }
+
+    ZigZag { pivots, line }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_zigzag_pivots_alternate() {
+        let high = vec![100.0, 104.0, 108.0, 105.0, 101.0, 99.0, 103.0, 107.0, 110.0, 106.0];
+        let low = vec![98.0, 102.0, 106.0, 102.0, 97.0, 96.0, 100.0, 104.0, 108.0, 103.0];
+        let result = zigzag(&high, &low, 5.0).unwrap();
+
+        let expected = [
+            (0, 98.0, false, Some(1)),
+            (2, 108.0, true, Some(3)),
+            (5, 96.0, false, Some(6)),
+            (8, 110.0, true, Some(9)),
+            (9, 103.0, false, None),
+        ];
+        assert_eq!(result.pivots.len(), expected.len());
+        for (p, &(index, price, is_high, confirmed_at)) in result.pivots.iter().zip(&expected) {
+            assert_eq!(*p, ZigZagPivot { index, price, is_high, confirmed_at });
+        }
+        for pair in result.pivots.windows(2) {
+            assert_ne!(pair[0].is_high, pair[1].is_high);
+        }
+
+        assert_eq!(result.swings().len(), 4);
+        assert!(result.swings().iter().all(|p| p.confirmed_at.is_some()));
+    }
+
+    #[test]
+    fn test_zigzag_line() {
+        let high = vec![100.0, 104.0, 108.0, 105.0, 101.0, 99.0, 103.0, 107.0, 110.0, 106.0];
+        let low = vec![98.0, 102.0, 106.0, 102.0, 97.0, 96.0, 100.0, 104.0, 108.0, 103.0];
+        let result = zigzag(&high, &low, 5.0).unwrap();
+
+        assert_float_eq!(result.line[0], 98.0, 1e-12);
+        assert_float_eq!(result.line[1], 103.0, 1e-12);
+        assert_float_eq!(result.line[2], 108.0, 1e-12);
+        assert_float_eq!(result.line[3], 104.0, 1e-12);
+        assert_float_eq!(result.line[5], 96.0, 1e-12);
+        assert_float_eq!(result.line[8], 110.0, 1e-12);
+        assert_float_eq!(result.line[9], 103.0, 1e-12);
+
+        // Nothing is drawn before the first pivot
+        let high = [&[101.0], &high[..]].concat();
+        let low = [&[99.0], &low[..]].concat();
+        let result = zigzag(&high, &low, 5.0).unwrap();
+        assert_eq!(result.pivots[0].index, 1);
+        assert!(result.line[0].is_nan());
+    }
+
+    #[test]
+    fn test_zigzag_small_moves_ignored() {
+        let high = vec![10.0, 10.2, 10.1, 10.3, 10.2];
+        let low = vec![9.9, 10.0, 9.95, 10.1, 10.0];
+        let result = zigzag(&high, &low, 5.0).unwrap();
+        assert!(result.pivots.is_empty());
+        assert!(result.line.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_zigzag_atr() {
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.25).sin() * 10.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+        let range = atr(&high, &low, &close, 10).unwrap();
+
+        let result = zigzag_atr(&high, &low, &close, 10, 3.0).unwrap();
+        let swings = result.swings();
+        assert!(swings.len() >= 3);
+        for p in &swings {
+            let confirmed_at = p.confirmed_at.unwrap();
+            assert!(confirmed_at >= 9);
+            let reversal = if p.is_high { p.price - low[confirmed_at] } else { high[confirmed_at] - p.price };
+            assert!(reversal >= range[confirmed_at] * 3.0);
+        }
+    }
+
+    #[test]
+    fn test_zigzag_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0];
+        assert!(zigzag(&[], &[], 5.0).is_err());
+        assert!(zigzag(&data, &data[..2], 5.0).is_err());
+        assert!(zigzag(&data, &data, 0.0).is_err());
+        assert!(zigzag_atr(&data, &data, &data, 2, -1.0).is_err());
+        assert!(zigzag_atr(&data, &data, &data[..2], 2, 1.0).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Zero-Lag Exponential Moving Average (ZLEMA)
+
+use crate::common::{TAError, TAResult, Price, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data, allocate_output};
+use crate::overlap::ema::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the Zero-Lag Exponential Moving Average (ZLEMA)
+///
+/// ZLEMA removes most of the EMA's lag by smoothing a "de-lagged" price: the current
+/// price plus its change over the last `(period-1)/2` bars, which is roughly the lag of
+/// an EMA of that period.
+///
+/// # Formula
+/// ```text
+/// lag       = (period - 1) / 2
+/// DeLagged  = Price + (Price - Price[lag bars ago])
+/// ZLEMA     = EMA(DeLagged, period)
+/// ```
+///
+/// # Parameters
+/// - `data`: Slice of price data
+/// - `period`: Number of periods for the moving average
+///
+/// # Returns
+/// Vector of ZLEMA values. The first `lag + period - 1` values will be NaN.
+///
+/// # Errors
+/// - `EmptyInput` if data is empty
+/// - `InvalidParameter` if period is 0
+/// - `InsufficientData` if data length < lag + period
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::zlema;
+///
+/// let prices = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+/// let result = zlema(&prices, 5).unwrap();
+///
+/// // lag = 2, so valid values start at index 2 + 5 - 1 = 6
+/// assert!(result[5].is_nan());
+/// // On a straight line the de-lagged EMA tracks the price exactly
+/// assert!((result[9] - 10.0).abs() < 1e-10);
+/// ```
+pub fn zlema(data: &[Price], period: impl IntoParam<Period>) -> TAResult<Vec<Price>> {
+    with_function("ZLEMA", || {
+        let period = period.into_param("period")?.get();
+        zlema_custom(data, period, 1.0)
+    })
+}
+
+/// Calculates ZLEMA with a custom error-correction gain
+///
+/// The de-lagged price becomes `Price + gain × (Price - Price[lag bars ago])`. A gain of
+/// 1.0 is the standard ZLEMA and 0.0 reduces to a plain EMA (shifted by the lag warm-up);
+/// values in between trade responsiveness for smoothness.
+///
+/// # Parameters
+/// - `data`: Slice of price data
+/// - `period`: Number of periods for the moving average
+/// - `gain`: Error-correction gain (must be finite and non-negative)
+///
+/// # Returns
+/// Vector of ZLEMA values. The first `lag + period - 1` values will be NaN.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::overlap::zlema_custom;
+///
+/// let prices = vec![10.0, 11.0, 12.0, 11.0, 10.0, 11.0, 12.0, 13.0, 14.0, 13.0];
+/// let result = zlema_custom(&prices, 4, 0.5).unwrap();
+/// ```
+pub fn zlema_custom(data: &[Price], period: impl IntoParam<Period>, gain: Price) -> TAResult<Vec<Price>> {
+    with_function("ZLEMA", || {
+        let period = period.into_param("period")?.get();
+        // Input validation
+        validate_not_empty(data, "data")?;
+
+        if !gain.is_finite() || gain < 0.0 {
+            return Err(TAError::invalid_parameter("gain", "must be finite and non-negative"));
+        }
+
+        let lag = (period - 1) / 2;
+        validate_sufficient_data(data, lag + period, "data")?;
+
+        // De-lagged series, defined from index `lag` onwards
+        let delagged: Vec<Price> = data[lag..]
+            .iter()
+            .zip(data)
+            .map(|(&price, &lagged)| price + gain * (price - lagged))
+            .collect();
+
+        let smoothed = ema(&delagged, period)?;
+
+        let mut output = allocate_output(data.len());
+        output[lag..].copy_from_slice(&smoothed);
+
+        Ok(output)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
+
+    #[test]
+    fn test_zlema_warmup() {
+        let prices: Vec<f64> = (1..=20).map(|x| x as f64).collect();
+        let result = zlema(&prices, 6).unwrap();
+
+        // lag = 2, first value at index 2 + 6 - 1 = 7
+        assert_eq!(result.len(), 20);
+        assert!(result[..7].iter().all(|v| v.is_nan()));
+        assert!(!result[7].is_nan());
+    }
+
+    #[test]
+    fn test_zlema_less_lag_than_ema() {
+        let prices: Vec<f64> = (0..40).map(|x| 100.0 + x as f64 * 0.5).collect();
+        let zl = zlema(&prices, 9).unwrap();
+        let e = ema(&prices, 9).unwrap();
+
+        // On a trend the EMA lags by ~(period-1)/2 bars; ZLEMA does not
+        let last = prices.len() - 1;
+        assert!((prices[last] - zl[last]).abs() < 1e-9);
+        assert!(prices[last] - e[last] > 1.5);
+    }
+
+    #[test]
+    fn test_zlema_custom_zero_gain_is_ema() {
+        let prices = vec![10.0, 11.0, 12.0, 11.0, 10.0, 11.0, 12.0, 13.0, 14.0, 13.0, 12.0];
+        let result = zlema_custom(&prices, 5, 0.0).unwrap();
+
+        let mut expected = vec![f64::NAN; prices.len()];
+        expected[2..].copy_from_slice(&ema(&prices[2..], 5).unwrap());
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_zlema_invalid_input() {
+        let prices = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(zlema(&[], 3).is_err());
+        assert!(zlema(&prices, 0).is_err());
+        assert!(zlema(&prices, 5).is_err()); // needs lag 2 + period 5
+        assert!(zlema_custom(&prices, 3, -1.0).is_err());
+        assert!(zlema_custom(&prices, 3, f64::NAN).is_err());
+    }
+}
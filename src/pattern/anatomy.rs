@@ -0,0 +1,236 @@
+//! Candle Anatomy
+//!
+//! The parts of a candle that candlestick patterns are defined by: the real body between
+//! open and close, the shadows above and below it, the full high-low range, the candle's
+//! color and the gaps between neighbouring candles. Each function returns one value per
+//! bar, so custom patterns can be written as comparisons between these series.
+//!
+//! Definitions follow TA-Lib's candle macros (`TA_REALBODY`, `TA_CANDLECOLOR`,
+//! `TA_REALBODYGAPUP`, ...), so patterns built on them reconcile with TA-Lib's.
+
+use crate::common::{validate_hlc_with, validate_not_empty, validate_same_length, HlcValidation, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the real body of each candle, the distance between open and close
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::real_body;
+///
+/// let body = real_body(&[10.0, 12.0], &[11.5, 11.0]).unwrap();
+/// assert_eq!(body, vec![1.5, 1.0]);
+/// ```
+pub fn real_body(open: &[f64], close: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("REAL_BODY", || {
+        validate_open_close(open, close)?;
+        Ok(open.iter().zip(close).map(|(o, c)| (c - o).abs()).collect())
+    })
+}
+
+/// Calculates the upper shadow of each candle, from the top of the body to the high
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::upper_shadow;
+///
+/// let shadow = upper_shadow(&[10.0], &[12.0], &[9.0], &[11.0]).unwrap();
+/// assert_eq!(shadow, vec![1.0]);
+/// ```
+pub fn upper_shadow(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("UPPER_SHADOW", || {
+        validate_bars(open, high, low, close)?;
+        Ok((0..close.len()).map(|i| high[i] - open[i].max(close[i])).collect())
+    })
+}
+
+/// Calculates the lower shadow of each candle, from the bottom of the body to the low
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::lower_shadow;
+///
+/// let shadow = lower_shadow(&[10.0], &[12.0], &[9.0], &[11.0]).unwrap();
+/// assert_eq!(shadow, vec![1.0]);
+/// ```
+pub fn lower_shadow(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("LOWER_SHADOW", || {
+        validate_bars(open, high, low, close)?;
+        Ok((0..close.len()).map(|i| open[i].min(close[i]) - low[i]).collect())
+    })
+}
+
+/// Calculates the high-low range of each candle
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::candle_range;
+///
+/// assert_eq!(candle_range(&[12.0, 11.0], &[9.0, 10.5]).unwrap(), vec![3.0, 0.5]);
+/// ```
+pub fn candle_range(high: &[f64], low: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("CANDLE_RANGE", || {
+        validate_not_empty(high, "high")?;
+        validate_same_length(high, low, "high", "low")?;
+        Ok(high.iter().zip(low).map(|(h, l)| h - l).collect())
+    })
+}
+
+/// Calculates the real body of each candle as a fraction of its range
+///
+/// Near 1 for a marubozu, near 0 for a doji. A candle with no range has a ratio of 0.
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::body_ratio;
+///
+/// let ratio = body_ratio(&[10.0, 10.0], &[14.0, 10.0], &[10.0, 10.0], &[13.0, 10.0]).unwrap();
+/// assert_eq!(ratio, vec![0.75, 0.0]);
+/// ```
+pub fn body_ratio(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("BODY_RATIO", || {
+        validate_bars(open, high, low, close)?;
+        Ok((0..close.len())
+            .map(|i| {
+                let range = high[i] - low[i];
+                if range > 0.0 { (close[i] - open[i]).abs() / range } else if range.is_nan() { f64::NAN } else { 0.0 }
+            })
+            .collect())
+    })
+}
+
+/// Calculates the color of each candle: 1 for white (close at or above open), -1 for black
+///
+/// As in TA-Lib, a candle closing at its open counts as white. A bar with a NaN open or
+/// close gives 0.
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::candle_color;
+///
+/// assert_eq!(candle_color(&[10.0, 11.0, 11.0], &[11.0, 10.0, 11.0]).unwrap(), vec![1, -1, 1]);
+/// ```
+pub fn candle_color(open: &[f64], close: &[f64]) -> TAResult<Vec<i8>> {
+    with_function("CANDLE_COLOR", || {
+        validate_open_close(open, close)?;
+        Ok(open
+            .iter()
+            .zip(close)
+            .map(|(o, c)| if c >= o { 1 } else if c < o { -1 } else { 0 })
+            .collect())
+    })
+}
+
+/// Flags candles whose low is above the previous candle's high
+///
+/// The first candle, and any next to a NaN, is never a gap.
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{gap_down, gap_up};
+///
+/// let high = vec![11.0, 13.0, 12.5];
+/// let low = vec![10.0, 11.5, 10.0];
+/// assert_eq!(gap_up(&high, &low).unwrap(), vec![false, true, false]);
+/// assert_eq!(gap_down(&high, &low).unwrap(), vec![false, false, false]);
+/// ```
+pub fn gap_up(high: &[f64], low: &[f64]) -> TAResult<Vec<bool>> {
+    with_function("GAP_UP", || {
+        validate_not_empty(high, "high")?;
+        validate_same_length(high, low, "high", "low")?;
+        Ok(gaps(high.len(), |i| low[i] > high[i - 1]))
+    })
+}
+
+/// Flags candles whose high is below the previous candle's low
+///
+/// The first candle, and any next to a NaN, is never a gap.
+pub fn gap_down(high: &[f64], low: &[f64]) -> TAResult<Vec<bool>> {
+    with_function("GAP_DOWN", || {
+        validate_not_empty(high, "high")?;
+        validate_same_length(high, low, "high", "low")?;
+        Ok(gaps(high.len(), |i| high[i] < low[i - 1]))
+    })
+}
+
+/// Flags candles whose real body is entirely above the previous candle's real body
+///
+/// The gap most candlestick patterns use, e.g. the star of a morning or evening star;
+/// shadows may overlap.
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{body_gap_down, body_gap_up};
+///
+/// let open = vec![10.0, 11.5, 11.0];
+/// let close = vec![11.0, 12.0, 10.0];
+/// assert_eq!(body_gap_up(&open, &close).unwrap(), vec![false, true, false]);
+/// assert_eq!(body_gap_down(&open, &close).unwrap(), vec![false, false, true]);
+/// ```
+pub fn body_gap_up(open: &[f64], close: &[f64]) -> TAResult<Vec<bool>> {
+    with_function("BODY_GAP_UP", || {
+        validate_open_close(open, close)?;
+        Ok(gaps(close.len(), |i| open[i].min(close[i]) > open[i - 1].max(close[i - 1])))
+    })
+}
+
+/// Flags candles whose real body is entirely below the previous candle's real body
+pub fn body_gap_down(open: &[f64], close: &[f64]) -> TAResult<Vec<bool>> {
+    with_function("BODY_GAP_DOWN", || {
+        validate_open_close(open, close)?;
+        Ok(gaps(close.len(), |i| open[i].max(close[i]) < open[i - 1].min(close[i - 1])))
+    })
+}
+
+/// Gap flags, false on the first bar; comparisons with NaN are false
+fn gaps(len: usize, gapped: impl Fn(usize) -> bool) -> Vec<bool> {
+    core::iter::once(false).chain((1..len).map(gapped)).collect()
+}
+
+fn validate_open_close(open: &[f64], close: &[f64]) -> TAResult<()> {
+    validate_not_empty(close, "close")?;
+    validate_same_length(open, close, "open", "close")
+}
+
+fn validate_bars(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> TAResult<()> {
+    validate_open_close(open, close)?;
+    validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::OHLC;
+
+    #[test]
+    fn test_anatomy_matches_ohlc() {
+        let open = [10.0, 12.0, 11.0, 11.0];
+        let high = [12.0, 12.5, 11.0, 13.0];
+        let low = [9.0, 10.0, 11.0, 10.5];
+        let close = [11.0, 10.5, 11.0, 12.5];
+        let (upper, lower) = (upper_shadow(&open, &high, &low, &close).unwrap(), lower_shadow(&open, &high, &low, &close).unwrap());
+        let body = real_body(&open, &close).unwrap();
+        let range = candle_range(&high, &low).unwrap();
+        for i in 0..4 {
+            let bar = OHLC::new(open[i], high[i], low[i], close[i]);
+            assert_eq!((body[i], upper[i], lower[i]), (bar.body_size(), bar.upper_shadow(), bar.lower_shadow()));
+            assert_eq!(body[i] + upper[i] + lower[i], range[i]);
+        }
+        assert_eq!(candle_color(&open, &close).unwrap(), vec![1, -1, 1, 1]);
+        assert_eq!(body_ratio(&open, &high, &low, &close).unwrap()[2], 0.0);
+    }
+
+    #[test]
+    fn test_gaps_and_errors() {
+        let high = [11.0, f64::NAN, 14.0];
+        let low = [10.0, f64::NAN, 13.0];
+        assert_eq!(gap_up(&high, &low).unwrap(), vec![false; 3]);
+        assert_eq!(candle_color(&[f64::NAN], &[1.0]).unwrap(), vec![0]);
+
+        assert!(real_body(&[], &[]).is_err());
+        assert!(real_body(&[1.0], &[1.0, 2.0]).is_err());
+        assert!(upper_shadow(&[1.0], &[1.0], &[2.0], &[1.5]).is_err());
+        assert!(gap_down(&[1.0, 2.0], &[1.0]).is_err());
+    }
+}
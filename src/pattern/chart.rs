@@ -0,0 +1,362 @@
+//! Chart Patterns
+//!
+//! The classic multi-swing price patterns: double tops and bottoms, head and shoulders,
+//! triangles and flags. Each is a fixed shape of consecutive alternating swings, so they
+//! are matched against the swing highs and lows of [`fractals`](crate::pattern::fractals),
+//! with [`ChartPatternConfig`] setting how loosely prices must line up.
+//!
+//! A pattern is known once its last swing is confirmed. The breakout that completes it
+//! is not required: [`ChartPattern::level`] is the price to watch, and
+//! [`ChartPattern::target`] the measured-move objective once it breaks.
+
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::pattern::Pivot;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Kind of chart pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChartPatternKind {
+    /// Two level highs with a low between them; bearish below the low
+    DoubleTop,
+    /// Two level lows with a high between them; bullish above the high
+    DoubleBottom,
+    /// A high between two lower, level highs; bearish below the neckline
+    HeadAndShoulders,
+    /// A low between two higher, level lows; bullish above the neckline
+    InverseHeadAndShoulders,
+    /// Level highs over rising lows; bullish above the highs
+    AscendingTriangle,
+    /// Falling highs over level lows; bearish below the lows
+    DescendingTriangle,
+    /// Falling highs over rising lows; either way
+    SymmetricalTriangle,
+    /// A sharp rise, then a shallow drift down or sideways; bullish above the flag
+    BullFlag,
+    /// A sharp fall, then a shallow drift up or sideways; bearish below the flag
+    BearFlag,
+}
+
+impl ChartPatternKind {
+    /// Expected breakout direction: 1 up, -1 down, 0 either way
+    pub fn bias(self) -> i8 {
+        match self {
+            ChartPatternKind::DoubleBottom
+            | ChartPatternKind::InverseHeadAndShoulders
+            | ChartPatternKind::AscendingTriangle
+            | ChartPatternKind::BullFlag => 1,
+            ChartPatternKind::DoubleTop
+            | ChartPatternKind::HeadAndShoulders
+            | ChartPatternKind::DescendingTriangle
+            | ChartPatternKind::BearFlag => -1,
+            ChartPatternKind::SymmetricalTriangle => 0,
+        }
+    }
+}
+
+/// Geometry tolerances of chart pattern detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChartPatternConfig {
+    /// Difference, in percent, within which two swing prices count as level
+    pub level_tolerance: f64,
+    /// Smallest move of a flag's pole, in percent
+    pub min_pole: f64,
+    /// Largest retracement of the pole by a flag, in percent of the pole
+    pub max_flag_retrace: f64,
+}
+
+impl Default for ChartPatternConfig {
+    fn default() -> Self {
+        Self { level_tolerance: 3.0, min_pole: 5.0, max_flag_retrace: 50.0 }
+    }
+}
+
+impl ChartPatternConfig {
+    fn validate(&self) -> TAResult<()> {
+        let checks = [
+            ("level_tolerance", self.level_tolerance),
+            ("min_pole", self.min_pole),
+            ("max_flag_retrace", self.max_flag_retrace),
+        ];
+        for (name, value) in checks {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(TAError::invalid_parameter(name, "must be non-negative and finite"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether two prices are level within the tolerance
+    fn level(&self, a: f64, b: f64) -> bool {
+        (a - b).abs() <= self.level_tolerance / 100.0 * (a.abs() + b.abs()) / 2.0
+    }
+
+    /// 1 if `to` is above `from` beyond the tolerance, -1 if below, 0 if level
+    fn direction(&self, from: f64, to: f64) -> i8 {
+        if self.level(from, to) {
+            0
+        } else if to > from {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+/// A detected chart pattern
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChartPattern {
+    /// Kind of pattern
+    pub kind: ChartPatternKind,
+    /// The swings forming the pattern, in order
+    pub pivots: Vec<Pivot>,
+    /// Breakout level: the neckline, the triangle side or the flag's edge at the last swing;
+    /// for a symmetrical triangle, the middle of its last high and low
+    pub level: f64,
+    /// Height of the pattern, the measured move expected after the breakout
+    pub height: f64,
+}
+
+impl ChartPattern {
+    /// Bar of the first swing
+    pub fn start(&self) -> usize {
+        self.pivots[0].index
+    }
+
+    /// Bar of the last swing
+    pub fn end(&self) -> usize {
+        self.pivots[self.pivots.len() - 1].index
+    }
+
+    /// Bar from which the pattern is known, when its last swing is confirmed
+    pub fn confirmed_at(&self) -> usize {
+        self.pivots.iter().map(|p| p.confirmed_at).max().unwrap_or(0)
+    }
+
+    /// Price objective once the level breaks in the direction of the pattern, `None` for
+    /// a symmetrical triangle
+    pub fn target(&self) -> Option<f64> {
+        match self.kind.bias() {
+            1 => Some(self.level + self.height),
+            -1 => Some(self.level - self.height),
+            _ => None,
+        }
+    }
+}
+
+/// Detects chart patterns in a sequence of swings
+///
+/// Runs of swing highs (or lows) without a swing of the other kind between them are
+/// reduced to their most extreme one, so the patterns are matched on alternating swings:
+/// three for double tops and bottoms, four for triangles, five for head and shoulders and
+/// flags. Patterns may overlap.
+///
+/// # Arguments
+/// * `pivots` - Swing highs and lows, e.g. the `pivots` of [`fractals`](crate::pattern::fractals)
+/// * `config` - Geometry tolerances, [`ChartPatternConfig::default`] for common ones
+///
+/// # Returns
+/// * `Ok(Vec<ChartPattern>)` - Patterns ordered by their last swing
+/// * `Err(TAError)` - Error if the configuration is invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{chart_patterns, fractals, ChartPatternConfig, ChartPatternKind};
+///
+/// // Left shoulder at 12, head at 14, right shoulder at 12.2, neckline at 10
+/// let close = vec![9.0, 12.0, 10.0, 14.0, 10.0, 12.2, 9.5, 8.0];
+/// let swings = fractals(&close, &close, 1).unwrap().pivots;
+/// let patterns = chart_patterns(&swings, &ChartPatternConfig::default()).unwrap();
+///
+/// let hs = patterns.iter().find(|p| p.kind == ChartPatternKind::HeadAndShoulders).unwrap();
+/// assert_eq!((hs.start(), hs.end()), (1, 5));
+/// assert_eq!(hs.level, 10.0);
+/// assert_eq!(hs.target(), Some(6.0));
+/// ```
+pub fn chart_patterns(pivots: &[Pivot], config: &ChartPatternConfig) -> TAResult<Vec<ChartPattern>> {
+    with_function("CHART_PATTERNS", || {
+        config.validate()?;
+        let swings = alternate(pivots);
+
+        let mut patterns = Vec::new();
+        for end in 0..swings.len() {
+            if end >= 2 {
+                patterns.extend(double(&swings[end - 2..=end], config));
+            }
+            if end >= 3 {
+                patterns.extend(triangle(&swings[end - 3..=end], config));
+            }
+            if end >= 4 {
+                patterns.extend(head_and_shoulders(&swings[end - 4..=end], config));
+                patterns.extend(flag(&swings[end - 4..=end], config));
+            }
+        }
+        Ok(patterns)
+    })
+}
+
+/// Swings in bar order with each run of one kind reduced to its most extreme swing
+fn alternate(pivots: &[Pivot]) -> Vec<Pivot> {
+    let mut sorted: Vec<Pivot> = pivots.iter().filter(|p| p.price.is_finite()).copied().collect();
+    // On an outside bar, take the high first
+    sorted.sort_by_key(|p| (p.index, !p.is_high));
+
+    let mut swings: Vec<Pivot> = Vec::with_capacity(sorted.len());
+    for pivot in sorted {
+        match swings.last_mut() {
+            Some(last) if last.is_high == pivot.is_high => {
+                let more_extreme = if pivot.is_high { pivot.price > last.price } else { pivot.price < last.price };
+                if more_extreme {
+                    *last = pivot;
+                }
+            }
+            _ => swings.push(pivot),
+        }
+    }
+    swings
+}
+
+/// Sign of a swing's extreme: 1 for highs, -1 for lows
+fn sign(pivot: &Pivot) -> f64 {
+    if pivot.is_high { 1.0 } else { -1.0 }
+}
+
+fn pattern(kind: ChartPatternKind, swings: &[Pivot], level: f64, height: f64) -> ChartPattern {
+    ChartPattern { kind, pivots: swings.to_vec(), level, height }
+}
+
+fn double(swings: &[Pivot], config: &ChartPatternConfig) -> Option<ChartPattern> {
+    let [first, middle, second] = swings else { return None };
+    if !config.level(first.price, second.price) {
+        return None;
+    }
+    let (kind, extreme) = if first.is_high {
+        (ChartPatternKind::DoubleTop, first.price.max(second.price))
+    } else {
+        (ChartPatternKind::DoubleBottom, first.price.min(second.price))
+    };
+    Some(pattern(kind, swings, middle.price, (extreme - middle.price).abs()))
+}
+
+fn head_and_shoulders(swings: &[Pivot], config: &ChartPatternConfig) -> Option<ChartPattern> {
+    let [left, neck_left, head, neck_right, right] = swings else { return None };
+    let s = sign(head);
+    let head_beyond = |shoulder: &Pivot| s * (head.price - shoulder.price) > 0.0 && !config.level(head.price, shoulder.price);
+    if !(head_beyond(left) && head_beyond(right) && config.level(left.price, right.price)) {
+        return None;
+    }
+
+    let slope = (neck_right.price - neck_left.price) / (neck_right.index - neck_left.index) as f64;
+    let neckline = |index: usize| neck_left.price + slope * (index as f64 - neck_left.index as f64);
+    let kind = if head.is_high { ChartPatternKind::HeadAndShoulders } else { ChartPatternKind::InverseHeadAndShoulders };
+    Some(pattern(kind, swings, neckline(right.index), (head.price - neckline(head.index)).abs()))
+}
+
+fn triangle(swings: &[Pivot], config: &ChartPatternConfig) -> Option<ChartPattern> {
+    let (mut highs, mut lows) = (Vec::with_capacity(2), Vec::with_capacity(2));
+    for pivot in swings {
+        if pivot.is_high { highs.push(pivot) } else { lows.push(pivot) }
+    }
+    let ([first_high, last_high], [first_low, last_low]) = (highs.as_slice(), lows.as_slice()) else { return None };
+
+    let kind = match (config.direction(first_high.price, last_high.price), config.direction(first_low.price, last_low.price)) {
+        (0, 1) => ChartPatternKind::AscendingTriangle,
+        (-1, 0) => ChartPatternKind::DescendingTriangle,
+        (-1, 1) => ChartPatternKind::SymmetricalTriangle,
+        _ => return None,
+    };
+    let level = match kind {
+        ChartPatternKind::AscendingTriangle => first_high.price.max(last_high.price),
+        ChartPatternKind::DescendingTriangle => first_low.price.min(last_low.price),
+        _ => (last_high.price + last_low.price) / 2.0,
+    };
+    // The widest part of the triangle, at its start
+    let height = (swings[0].price - swings[1].price).abs();
+    Some(pattern(kind, swings, level, height))
+}
+
+fn flag(swings: &[Pivot], config: &ChartPatternConfig) -> Option<ChartPattern> {
+    let [base, top, first, edge, second] = swings else { return None };
+    let s = sign(top);
+    let pole = s * (top.price - base.price);
+    if base.price == 0.0 || pole / base.price.abs() * 100.0 < config.min_pole {
+        return None;
+    }
+    // The flag may not extend the pole, nor push its counter-swings further from it
+    let deepest = if top.is_high { first.price.min(second.price) } else { first.price.max(second.price) };
+    let retrace = s * (top.price - deepest);
+    if config.direction(top.price, edge.price) as f64 * s > 0.0
+        || config.direction(first.price, second.price) as f64 * s > 0.0
+        || retrace > config.max_flag_retrace / 100.0 * pole
+    {
+        return None;
+    }
+    let kind = if top.is_high { ChartPatternKind::BullFlag } else { ChartPatternKind::BearFlag };
+    Some(pattern(kind, swings, edge.price, pole))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swings(prices: &[f64]) -> Vec<Pivot> {
+        // Alternating swings 3 bars apart, starting with a high when the second price is lower
+        let first_high = prices[0] > prices[1];
+        prices
+            .iter()
+            .enumerate()
+            .map(|(k, &price)| Pivot { index: 3 * k, price, is_high: (k % 2 == 0) == first_high, confirmed_at: 3 * k + 1 })
+            .collect()
+    }
+
+    fn kinds(prices: &[f64]) -> Vec<ChartPatternKind> {
+        chart_patterns(&swings(prices), &ChartPatternConfig::default()).unwrap().iter().map(|p| p.kind).collect()
+    }
+
+    #[test]
+    fn test_double_and_triangle_shapes() {
+        assert_eq!(kinds(&[20.0, 18.0, 20.2]), vec![ChartPatternKind::DoubleTop]);
+        assert_eq!(kinds(&[10.0, 12.0, 10.1]), vec![ChartPatternKind::DoubleBottom]);
+        assert!(kinds(&[20.0, 18.0, 22.0]).is_empty());
+
+        assert!(kinds(&[20.0, 15.0, 20.1, 17.0]).contains(&ChartPatternKind::AscendingTriangle));
+        assert!(kinds(&[15.0, 20.0, 15.1, 18.0]).contains(&ChartPatternKind::DescendingTriangle));
+        let symmetrical = kinds(&[20.0, 15.0, 18.0, 16.5]);
+        assert_eq!(symmetrical, vec![ChartPatternKind::SymmetricalTriangle]);
+
+        let patterns = chart_patterns(&swings(&[20.0, 15.0, 20.1, 17.0]), &ChartPatternConfig::default()).unwrap();
+        let ascending = patterns.iter().find(|p| p.kind == ChartPatternKind::AscendingTriangle).unwrap();
+        assert_eq!((ascending.level, ascending.target()), (20.1, Some(25.1)));
+        assert_eq!((ascending.confirmed_at(), ChartPatternKind::SymmetricalTriangle.bias()), (10, 0));
+    }
+
+    #[test]
+    fn test_flags_and_inverse_head_and_shoulders() {
+        // Pole from 100 to 110, then a drift down holding above 105
+        let patterns = chart_patterns(&swings(&[100.0, 110.0, 107.0, 109.0, 106.0]), &ChartPatternConfig::default()).unwrap();
+        let flag = patterns.iter().find(|p| p.kind == ChartPatternKind::BullFlag).unwrap();
+        assert_eq!((flag.level, flag.height, flag.target()), (109.0, 10.0, Some(119.0)));
+        // Retracing more than half the pole is no flag
+        assert!(!kinds(&[100.0, 110.0, 103.0, 109.0, 104.0]).contains(&ChartPatternKind::BullFlag));
+        assert!(kinds(&[110.0, 100.0, 103.0, 101.0, 104.0]).contains(&ChartPatternKind::BearFlag));
+
+        assert!(kinds(&[10.0, 12.0, 8.0, 12.0, 10.1]).contains(&ChartPatternKind::InverseHeadAndShoulders));
+
+        let loose = ChartPatternConfig { level_tolerance: -1.0, ..Default::default() };
+        assert!(chart_patterns(&[], &loose).is_err());
+        assert!(chart_patterns(&[], &ChartPatternConfig::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_alternate_keeps_extremes() {
+        let mut pivots = swings(&[10.0, 12.0, 9.0]);
+        pivots.insert(1, Pivot { index: 1, price: 11.0, is_high: true, confirmed_at: 2 });
+        pivots.insert(3, Pivot { index: 4, price: 13.0, is_high: true, confirmed_at: 5 });
+        let reduced = alternate(&pivots);
+        assert_eq!(reduced.iter().map(|p| p.price).collect::<Vec<_>>(), vec![10.0, 13.0, 9.0]);
+    }
+}
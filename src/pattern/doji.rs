@@ -0,0 +1,75 @@
+//! CDLDOJI - Doji
+
+use crate::pattern::{candle_average, CandleSettings};
+use crate::common::TAResult;
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Flags doji candles, whose open and close are almost equal
+///
+/// As in TA-Lib's `TA_CDLDOJI`, a candle is a doji when its real body is no larger than
+/// the `body_doji` threshold of `settings`, by default 10% of the average high-low range
+/// of the previous 10 candles.
+///
+/// # Arguments
+/// * `open` - Open prices
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `settings` - Candle thresholds, [`CandleSettings::default`] for TA-Lib's
+///
+/// # Returns
+/// * `Ok(Vec<i8>)` - 100 on a doji, otherwise 0, including the first `avg_period` bars
+///   of `body_doji`
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{cdl_doji, CandleSettings};
+///
+/// let mut open = vec![10.0; 12];
+/// let high = vec![12.0; 12];
+/// let low = vec![9.0; 12];
+/// let mut close = vec![11.5; 12];
+/// // Bodies of 0.2 on a 3.0 range, against a threshold of 0.3
+/// open[11] = 10.4;
+/// close[11] = 10.6;
+///
+/// let doji = cdl_doji(&open, &high, &low, &close, &CandleSettings::default()).unwrap();
+/// assert_eq!(doji[11], 100);
+/// assert_eq!(doji[10], 0);
+///
+/// // Stricter settings no longer count it
+/// let mut strict = CandleSettings::default();
+/// strict.body_doji.factor = 0.05;
+/// assert_eq!(cdl_doji(&open, &high, &low, &close, &strict).unwrap()[11], 0);
+/// ```
+pub fn cdl_doji(open: &[f64], high: &[f64], low: &[f64], close: &[f64], settings: &CandleSettings) -> TAResult<Vec<i8>> {
+    with_function("CDL_DOJI", || {
+        let threshold = candle_average(open, high, low, close, settings.body_doji)?;
+        Ok((0..close.len())
+            .map(|i| if (close[i] - open[i]).abs() <= threshold[i] { 100 } else { 0 })
+            .collect())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cdl_doji_warmup_and_lookback() {
+        let open = vec![10.0; 15];
+        let close = open.clone();
+        let high = vec![11.0; 15];
+        let low = vec![9.0; 15];
+        let doji = cdl_doji(&open, &high, &low, &close, &CandleSettings::TA_LIB).unwrap();
+        assert!(doji[..10].iter().all(|&d| d == 0));
+        assert!(doji[10..].iter().all(|&d| d == 100));
+
+        let mut quick = CandleSettings::TA_LIB;
+        quick.body_doji.avg_period = 0;
+        assert_eq!(cdl_doji(&open, &high, &low, &close, &quick).unwrap()[0], 100);
+    }
+}
@@ -0,0 +1,141 @@
+//! Fractals and Swing Points
+//!
+//! A swing high is a bar whose high stands above the `width` bars on each side, a swing
+//! low one whose low stands below them. With a width of 2 these are Bill Williams'
+//! fractals, the five-bar turning points of his trading system. Since a swing needs
+//! `width` bars after it, it is only known `width` bars late.
+//!
+//! [`divergence`](crate::signal::divergence) finds its swings with the same rule.
+
+use crate::common::{validate_not_empty, validate_same_length, IntoParam, Period, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// A swing high or low
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pivot {
+    /// Index of the bar at the extreme
+    pub index: usize,
+    /// High of that bar for swing highs, low for swing lows
+    pub price: f64,
+    /// `true` for a swing high, `false` for a swing low
+    pub is_high: bool,
+    /// Index of the bar completing the pattern, `width` bars after the extreme, from
+    /// which the pivot is known
+    pub confirmed_at: usize,
+}
+
+/// Swing highs and lows of a series of bars
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fractals {
+    /// Whether each bar is a swing high
+    pub highs: Vec<bool>,
+    /// Whether each bar is a swing low
+    pub lows: Vec<bool>,
+    /// Every swing in bar order, a high before a low on the same bar
+    pub pivots: Vec<Pivot>,
+}
+
+/// Finds fractals, the swing highs and lows of `width` bars on each side
+///
+/// A bar is a swing high when its high is above the highs of the `width` bars before it
+/// and at least the highs of the `width` bars after it, so among equal highs the earliest
+/// counts; swing lows mirror this. A bar with the highest high and the lowest low of its
+/// neighbourhood, such as an outside bar, is both.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `width` - Bars on each side, 2 for Bill Williams' fractals
+///
+/// # Returns
+/// * `Ok(Fractals)` - Swing flags per bar and the list of swings; the first and last
+///   `width` bars, and windows holding NaN, have none
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::fractals;
+///
+/// let high = vec![10.0, 11.0, 13.0, 12.0, 11.5, 12.5, 14.0, 13.0, 12.0];
+/// let low = vec![9.0, 10.0, 12.0, 11.0, 9.5, 11.0, 13.0, 12.0, 11.0];
+/// let result = fractals(&high, &low, 2).unwrap();
+///
+/// assert!(result.highs[2] && result.highs[6]);
+/// assert!(result.lows[4]);
+/// assert_eq!(result.pivots.len(), 3);
+/// assert_eq!((result.pivots[1].price, result.pivots[1].confirmed_at), (9.5, 6));
+/// ```
+pub fn fractals(high: &[f64], low: &[f64], width: impl IntoParam<Period>) -> TAResult<Fractals> {
+    with_function("FRACTALS", || {
+        let width = width.into_param("width")?.get();
+        validate_not_empty(high, "high")?;
+        validate_same_length(high, low, "high", "low")?;
+
+        let mut result = Fractals { highs: vec![false; high.len()], lows: vec![false; low.len()], pivots: Vec::new() };
+        for index in pivots(high, width, true) {
+            result.highs[index] = true;
+        }
+        for index in pivots(low, width, false) {
+            result.lows[index] = true;
+        }
+        for index in 0..high.len() {
+            for (flags, series, is_high) in [(&result.highs, high, true), (&result.lows, low, false)] {
+                if flags[index] {
+                    result.pivots.push(Pivot { index, price: series[index], is_high, confirmed_at: index + width });
+                }
+            }
+        }
+        Ok(result)
+    })
+}
+
+/// Indices of the pivot highs (or lows) of `series` with `width` bars on each side
+pub(crate) fn pivots(series: &[f64], width: usize, is_high: bool) -> Vec<usize> {
+    // `a` beats `b` when it is strictly more extreme in the pivot's direction
+    let beats = |a: f64, b: f64| if is_high { a > b } else { a < b };
+
+    (width..series.len().saturating_sub(width))
+        .filter(|&i| {
+            let value = series[i];
+            value.is_finite()
+                && series[i - width..i].iter().all(|&v| v.is_finite() && beats(value, v))
+                && series[i + 1..=i + width].iter().all(|&v| v.is_finite() && !beats(v, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fractals_ties_and_outside_bars() {
+        // Equal highs at bars 2 and 3: only the first is a swing
+        let high = [1.0, 2.0, 5.0, 5.0, 3.0, 2.0, 6.0, 2.0, 1.0];
+        let low = [0.5, 1.5, 4.0, 4.5, 2.0, 1.0, 0.0, 1.5, 0.5];
+        let result = fractals(&high, &low, 1).unwrap();
+        assert_eq!(result.highs.iter().filter(|&&h| h).count(), 2);
+        assert!(result.highs[2] && !result.highs[3]);
+
+        // Bar 6 is both the highest high and the lowest low
+        let outside: Vec<&Pivot> = result.pivots.iter().filter(|p| p.index == 6).collect();
+        assert_eq!(outside.len(), 2);
+        assert!(outside[0].is_high && !outside[1].is_high);
+        assert_eq!(outside[1].confirmed_at, 7);
+    }
+
+    #[test]
+    fn test_fractals_nan_and_errors() {
+        let high = [1.0, 3.0, f64::NAN, 1.0, 4.0, 1.0];
+        let result = fractals(&high, &high, 1).unwrap();
+        assert_eq!(result.highs, vec![false, false, false, false, true, false]);
+        assert!(fractals(&high, &high[1..], 1).is_err());
+        assert!(fractals(&high, &high, 0).is_err());
+        assert!(fractals(&[], &[], 2).is_err());
+        assert!(fractals(&high[..2], &high[..2], 2).unwrap().pivots.is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+//! Harmonic Patterns
+//!
+//! Harmonic patterns are five-point XABCD swings whose legs stand in Fibonacci ratios.
+//! B retraces the XA leg, C retraces AB, and D completes where two projections meet: a
+//! retracement (or extension) of XA and an extension of BC. The overlap of the two is the
+//! potential reversal zone, known as soon as C is in place, so a pattern can be watched
+//! before D forms. Ratios follow Scott Carney's definitions.
+
+use crate::common::{IntoParam, Percent, TAResult};
+use crate::common::errors::with_function;
+use crate::overlap::ZigZagPivot;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Kind of harmonic pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HarmonicKind {
+    /// B at 0.618 of XA, D at 0.786 of XA
+    Gartley,
+    /// B at 0.382 to 0.5 of XA, D at 0.886 of XA
+    Bat,
+    /// B at 0.786 of XA, D extending XA to 1.27 to 1.618
+    Butterfly,
+    /// B at 0.382 to 0.618 of XA, D extending XA to 1.618
+    Crab,
+}
+
+/// Leg ratios of one pattern, each an inclusive range
+struct Ratios {
+    kind: HarmonicKind,
+    /// AB over XA
+    b: (f64, f64),
+    /// BC over AB
+    c: (f64, f64),
+    /// XD over XA, measured from A
+    d: (f64, f64),
+    /// CD over BC
+    cd: (f64, f64),
+}
+
+const PATTERNS: [Ratios; 4] = [
+    Ratios { kind: HarmonicKind::Gartley, b: (0.618, 0.618), c: (0.382, 0.886), d: (0.786, 0.786), cd: (1.27, 1.618) },
+    Ratios { kind: HarmonicKind::Bat, b: (0.382, 0.5), c: (0.382, 0.886), d: (0.886, 0.886), cd: (1.618, 2.618) },
+    Ratios { kind: HarmonicKind::Butterfly, b: (0.786, 0.786), c: (0.382, 0.886), d: (1.27, 1.618), cd: (1.618, 2.24) },
+    Ratios { kind: HarmonicKind::Crab, b: (0.382, 0.618), c: (0.382, 0.886), d: (1.618, 1.618), cd: (2.24, 3.618) },
+];
+
+/// A harmonic pattern, complete or awaiting its D point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarmonicPattern {
+    /// Kind of pattern
+    pub kind: HarmonicKind,
+    /// `true` when D is a low, a buy; `false` when D is a high, a sell
+    pub bullish: bool,
+    /// The X, A, B and C swings
+    pub points: [ZigZagPivot; 4],
+    /// The D swing, once a swing after C has formed inside the completion zone
+    pub d: Option<ZigZagPivot>,
+    /// Lower bound of the potential reversal zone
+    pub zone_low: f64,
+    /// Upper bound of the potential reversal zone
+    pub zone_high: f64,
+}
+
+impl HarmonicPattern {
+    /// Whether a price lies in the potential reversal zone
+    pub fn in_zone(&self, price: f64) -> bool {
+        price >= self.zone_low && price <= self.zone_high
+    }
+}
+
+/// Detects Gartley, Bat, Butterfly and Crab patterns in a sequence of zigzag swings
+///
+/// Every four consecutive alternating swings are tried as X, A, B and C. Where AB and BC
+/// retrace within a pattern's ratios, widened by `tolerance`, the completion zone is where
+/// the projections of D from XA and from BC overlap. If a swing follows C, it must fall in
+/// the zone to become D, otherwise the candidate is dropped; without one, the pattern is
+/// returned with `d` of `None`.
+///
+/// # Arguments
+/// * `pivots` - Alternating swings, e.g. the `pivots` of [`zigzag`](crate::overlap::zigzag)
+/// * `tolerance` - Relative widening of every ratio range, in percent (e.g. 5.0)
+///
+/// # Returns
+/// * `Ok(Vec<HarmonicPattern>)` - Patterns ordered by their C swing
+/// * `Err(TAError)` - Error if parameters are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::overlap::ZigZagPivot;
+/// use ta_rust::pattern::{harmonic_patterns, HarmonicKind};
+///
+/// let swing = |index, price, is_high| ZigZagPivot { index, price, is_high, confirmed_at: Some(index + 1) };
+/// // X 100, A 200, B 138.2 (0.618 of XA), C 170
+/// let pivots = vec![swing(0, 100.0, false), swing(5, 200.0, true), swing(10, 138.2, false), swing(15, 170.0, true)];
+/// let patterns = harmonic_patterns(&pivots, 2.0).unwrap();
+///
+/// let gartley = patterns.iter().find(|p| p.kind == HarmonicKind::Gartley).unwrap();
+/// assert!(gartley.bullish && gartley.d.is_none());
+/// // D at 0.786 of XA is 121.4
+/// assert!(gartley.in_zone(121.4));
+/// ```
+pub fn harmonic_patterns(pivots: &[ZigZagPivot], tolerance: impl IntoParam<Percent>) -> TAResult<Vec<HarmonicPattern>> {
+    with_function("HARMONIC_PATTERNS", || {
+        let tolerance = tolerance.into_param("tolerance")?.fraction();
+        let widen = |(low, high): (f64, f64)| (low * (1.0 - tolerance), high * (1.0 + tolerance));
+        let within = |value: f64, range: (f64, f64)| {
+            let (low, high) = widen(range);
+            value >= low && value <= high
+        };
+
+        let mut patterns = Vec::new();
+        for start in 0..pivots.len().saturating_sub(3) {
+            let points = [pivots[start], pivots[start + 1], pivots[start + 2], pivots[start + 3]];
+            let [x, a, b, c] = points;
+            let alternating = points.windows(2).all(|w| w[0].is_high != w[1].is_high);
+            if !alternating || points.iter().any(|p| !p.price.is_finite()) {
+                continue;
+            }
+
+            let xa = a.price - x.price;
+            let ab = a.price - b.price;
+            let bc = c.price - b.price;
+            if xa == 0.0 || ab == 0.0 {
+                continue;
+            }
+            let next = pivots.get(start + 4).filter(|d| d.is_high == x.is_high);
+
+            for ratios in &PATTERNS {
+                if !(within(ab / xa, ratios.b) && within(bc / ab, ratios.c)) {
+                    continue;
+                }
+                // D from A by a multiple of XA, and from C by a multiple of BC
+                let (d_low, d_high) = widen(ratios.d);
+                let (cd_low, cd_high) = widen(ratios.cd);
+                let from_xa = ordered(a.price - d_low * xa, a.price - d_high * xa);
+                let from_bc = ordered(c.price - cd_low * bc, c.price - cd_high * bc);
+                let (zone_low, zone_high) = (from_xa.0.max(from_bc.0), from_xa.1.min(from_bc.1));
+                if zone_low > zone_high {
+                    continue;
+                }
+
+                let pattern = HarmonicPattern { kind: ratios.kind, bullish: !x.is_high, points, d: next.copied(), zone_low, zone_high };
+                match next {
+                    Some(d) if !pattern.in_zone(d.price) => {}
+                    _ => patterns.push(pattern),
+                }
+            }
+        }
+        Ok(patterns)
+    })
+}
+
+fn ordered(a: f64, b: f64) -> (f64, f64) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    fn swings(prices: &[f64], first_high: bool) -> Vec<ZigZagPivot> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(k, &price)| ZigZagPivot { index: 4 * k, price, is_high: (k % 2 == 0) == first_high, confirmed_at: Some(4 * k + 2) })
+            .collect()
+    }
+
+    #[test]
+    fn test_bearish_bat_with_d() {
+        // X 200, A 100, B 145 (0.45 of XA), C 110 (0.78 of AB); D at 0.886 of XA is 188.6
+        let pivots = swings(&[200.0, 100.0, 145.0, 110.0, 188.0], true);
+        let patterns = harmonic_patterns(&pivots, 1.0).unwrap();
+        assert_eq!(patterns.len(), 1);
+        let bat = patterns[0];
+        assert_eq!((bat.kind, bat.bullish), (HarmonicKind::Bat, false));
+        assert_eq!(bat.d.map(|d| d.index), Some(16));
+        // 0.886 of XA widened by 1% on each side
+        assert_float_eq!(bat.zone_low, 100.0 + 0.886 * 0.99 * 100.0, 1e-9);
+        assert_float_eq!(bat.zone_high, 100.0 + 0.886 * 1.01 * 100.0, 1e-9);
+
+        // A D far beyond the zone fails the pattern
+        let overshoot = swings(&[200.0, 100.0, 145.0, 110.0, 230.0], true);
+        assert!(harmonic_patterns(&overshoot, 1.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_butterfly_and_errors() {
+        // B at 0.786 of XA, C at 0.618 of AB: a Butterfly, D below X
+        let pivots = swings(&[100.0, 200.0, 121.4, 170.0], false);
+        let patterns = harmonic_patterns(&pivots, 2.0).unwrap();
+        let kinds: Vec<HarmonicKind> = patterns.iter().map(|p| p.kind).collect();
+        assert_eq!(kinds, vec![HarmonicKind::Butterfly]);
+        assert!(patterns[0].zone_high < 100.0);
+
+        // Non-alternating swings are skipped
+        let mut broken = pivots.clone();
+        broken[2].is_high = true;
+        assert!(harmonic_patterns(&broken, 2.0).unwrap().is_empty());
+        assert!(harmonic_patterns(&pivots, 150.0).is_err());
+    }
+}
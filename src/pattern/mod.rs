@@ -0,0 +1,27 @@
+//! Pattern Recognition
+//!
+//! Candlestick patterns and their building blocks: the anatomy of each candle (body,
+//! shadows, range, color and gaps) and the [`CandleSettings`] thresholds that decide what
+//! counts as a long body or a short shadow. Price patterns start from the swing highs and
+//! lows found by [`fractals`], which [`market_structure`] reads as trend, [`trendlines`]
+//! joins into support and resistance and [`chart_patterns`] matches to the classic shapes;
+//! [`harmonic_patterns`] fits Fibonacci-ratio XABCD patterns to zigzag swings.
+
+pub mod anatomy;
+pub mod settings;
+/// CDLDOJI - Doji
+pub mod doji;
+pub mod fractals;
+pub mod structure;
+pub mod trendlines;
+pub mod chart;
+pub mod harmonic;
+
+pub use anatomy::*;
+pub use settings::*;
+pub use doji::*;
+pub use fractals::*;
+pub use structure::*;
+pub use trendlines::*;
+pub use chart::*;
+pub use harmonic::*;
@@ -0,0 +1,219 @@
+//! Candle Settings
+//!
+//! Candlestick patterns are defined with vague terms, a "long" body or a "very short"
+//! shadow, which TA-Lib makes precise by comparing each candle with the average of recent
+//! candles. [`CandleSettings`] holds those comparisons, one [`CandleSetting`] per term,
+//! with TA-Lib's defaults. Where TA-Lib changes them globally with `TA_SetCandleSettings`,
+//! pattern functions here take the settings as an argument, so differently tuned patterns
+//! can run side by side; with the defaults, results reconcile with TA-Lib's.
+
+use crate::common::{validate_hlc_with, validate_not_empty, validate_same_length, HlcValidation, TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Part of the candle a setting measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RangeType {
+    /// Distance between open and close
+    RealBody,
+    /// Distance between high and low
+    HighLow,
+    /// Both shadows, averaged as a single shadow's length
+    Shadows,
+}
+
+/// Threshold of one candle term, as in TA-Lib's `TA_CandleSetting`
+///
+/// The threshold on a bar is `factor` times the average `range_type` of the
+/// `avg_period` bars before it, or of the bar itself when `avg_period` is 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CandleSetting {
+    /// Part of the candle averaged
+    pub range_type: RangeType,
+    /// Number of previous bars averaged, 0 for the current bar
+    pub avg_period: usize,
+    /// Multiple of the average giving the threshold
+    pub factor: f64,
+}
+
+impl CandleSetting {
+    /// Creates a setting
+    pub const fn new(range_type: RangeType, avg_period: usize, factor: f64) -> Self {
+        Self { range_type, avg_period, factor }
+    }
+
+    /// Size of the measured part of one candle
+    pub fn range(self, open: f64, high: f64, low: f64, close: f64) -> f64 {
+        match self.range_type {
+            RangeType::RealBody => (close - open).abs(),
+            RangeType::HighLow => high - low,
+            RangeType::Shadows => (high - low) - (close - open).abs(),
+        }
+    }
+
+    fn validate(self) -> TAResult<()> {
+        if self.factor.is_finite() && self.factor >= 0.0 {
+            Ok(())
+        } else {
+            Err(TAError::invalid_parameter("factor", "must be non-negative and finite"))
+        }
+    }
+}
+
+/// Thresholds of every candle term, with TA-Lib's defaults
+///
+/// # Example
+/// ```
+/// use ta_rust::pattern::{CandleSettings, RangeType};
+///
+/// // Doji bodies up to 5% of the average range instead of 10%
+/// let mut strict = CandleSettings::default();
+/// strict.body_doji.factor = 0.05;
+/// assert_eq!(strict.body_doji.range_type, RangeType::HighLow);
+/// assert_eq!(CandleSettings::TA_LIB.body_long.avg_period, 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CandleSettings {
+    /// Long real body: at least the average body of the last 10 candles
+    pub body_long: CandleSetting,
+    /// Very long real body: at least 3 times the average body of the last 10 candles
+    pub body_very_long: CandleSetting,
+    /// Short real body: at most the average body of the last 10 candles
+    pub body_short: CandleSetting,
+    /// Doji body: at most 10% of the average range of the last 10 candles
+    pub body_doji: CandleSetting,
+    /// Long shadow: at least the candle's own body
+    pub shadow_long: CandleSetting,
+    /// Very long shadow: at least twice the candle's own body
+    pub shadow_very_long: CandleSetting,
+    /// Short shadow: at most the average shadow of the last 10 candles
+    pub shadow_short: CandleSetting,
+    /// Very short shadow: at most 10% of the average range of the last 10 candles
+    pub shadow_very_short: CandleSetting,
+    /// Prices near each other: within 20% of the average range of the last 5 candles
+    pub near: CandleSetting,
+    /// Prices far apart: at least 60% of the average range of the last 5 candles
+    pub far: CandleSetting,
+    /// Prices equal: within 5% of the average range of the last 5 candles
+    pub equal: CandleSetting,
+}
+
+impl CandleSettings {
+    /// TA-Lib's default settings
+    pub const TA_LIB: Self = Self {
+        body_long: CandleSetting::new(RangeType::RealBody, 10, 1.0),
+        body_very_long: CandleSetting::new(RangeType::RealBody, 10, 3.0),
+        body_short: CandleSetting::new(RangeType::RealBody, 10, 1.0),
+        body_doji: CandleSetting::new(RangeType::HighLow, 10, 0.1),
+        shadow_long: CandleSetting::new(RangeType::RealBody, 0, 1.0),
+        shadow_very_long: CandleSetting::new(RangeType::RealBody, 0, 2.0),
+        shadow_short: CandleSetting::new(RangeType::Shadows, 10, 1.0),
+        shadow_very_short: CandleSetting::new(RangeType::HighLow, 10, 0.1),
+        near: CandleSetting::new(RangeType::HighLow, 5, 0.2),
+        far: CandleSetting::new(RangeType::HighLow, 5, 0.6),
+        equal: CandleSetting::new(RangeType::HighLow, 5, 0.05),
+    };
+}
+
+impl Default for CandleSettings {
+    fn default() -> Self {
+        Self::TA_LIB
+    }
+}
+
+/// Calculates the threshold of a candle setting on each bar
+///
+/// TA-Lib's `TA_CANDLEAVERAGE`: `factor` times the average range of the `avg_period`
+/// previous bars, halved for [`RangeType::Shadows`] to give the length of one shadow.
+///
+/// # Arguments
+/// * `open` - Open prices
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `setting` - Threshold to compute
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Threshold of each bar; the first `avg_period` are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{candle_average, CandleSettings};
+///
+/// let open = vec![10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let high = vec![12.0, 12.0, 12.0, 12.0, 12.0, 11.0];
+/// let low = vec![9.0, 9.0, 9.0, 9.0, 9.0, 9.0];
+/// let close = vec![11.0; 6];
+///
+/// // 20% of the average 3.0 range of the five bars before the last
+/// let near = candle_average(&open, &high, &low, &close, CandleSettings::TA_LIB.near).unwrap();
+/// assert!(near[4].is_nan());
+/// assert!((near[5] - 0.6).abs() < 1e-12);
+/// ```
+pub fn candle_average(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    setting: CandleSetting,
+) -> TAResult<Vec<f64>> {
+    with_function("CANDLE_AVERAGE", || {
+        validate_not_empty(close, "close")?;
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        validate_same_length(open, close, "open", "close")?;
+        setting.validate()?;
+
+        let ranges: Vec<f64> = (0..close.len()).map(|i| setting.range(open[i], high[i], low[i], close[i])).collect();
+        let divisor = if setting.range_type == RangeType::Shadows { 2.0 } else { 1.0 };
+        let period = setting.avg_period;
+        if period == 0 {
+            return Ok(ranges.iter().map(|r| setting.factor * r / divisor).collect());
+        }
+
+        let mut averages = vec![f64::NAN; close.len()];
+        for i in period..close.len() {
+            let total: f64 = ranges[i - period..i].iter().sum();
+            averages[i] = setting.factor * total / period as f64 / divisor;
+        }
+        Ok(averages)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_candle_average_range_types() {
+        let open = [10.0, 11.0, 10.0];
+        let high = [12.0, 12.0, 11.0];
+        let low = [9.0, 10.0, 9.5];
+        let close = [11.0, 10.0, 10.5];
+
+        // Shadows of 2.0 and 1.0 over two bars: one shadow averages 0.75
+        let shadows = candle_average(&open, &high, &low, &close, CandleSetting::new(RangeType::Shadows, 2, 1.0)).unwrap();
+        assert!(shadows[1].is_nan());
+        assert_float_eq!(shadows[2], 0.75, 1e-12);
+
+        // A period of 0 measures the bar itself
+        let own = candle_average(&open, &high, &low, &close, CandleSettings::TA_LIB.shadow_very_long).unwrap();
+        assert_eq!(own, vec![2.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_candle_settings_errors() {
+        let prices = [10.0, 11.0];
+        let mut setting = CandleSettings::default().body_long;
+        assert!(candle_average(&prices, &prices, &prices, &prices, setting).is_ok());
+        setting.factor = -1.0;
+        assert!(candle_average(&prices, &prices, &prices, &prices, setting).is_err());
+        assert!(candle_average(&prices[..1], &prices, &prices, &prices, CandleSettings::TA_LIB.near).is_err());
+        assert!(candle_average(&[], &[], &[], &[], CandleSettings::TA_LIB.near).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Market Structure
+//!
+//! Price-action traders read trend from the sequence of swings: higher highs and higher
+//! lows in an uptrend, lower highs and lower lows in a downtrend. A close beyond the last
+//! swing high or low breaks the structure. A break in the direction of the trend is a
+//! break of structure (BOS), confirming it; a break against it is a change of character
+//! (CHoCH), the first sign of a reversal.
+//!
+//! Swings are the [`fractals`](crate::pattern::fractals) of the bars, and a swing is only
+//! used from the bar that confirms it, so nothing here looks ahead.
+
+use crate::common::{validate_hlc_with, HlcValidation, IntoParam, Period, TAResult};
+use crate::common::errors::with_function;
+use crate::pattern::{fractals, Pivot};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// How a swing compares with the previous swing of the same kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwingLabel {
+    /// Swing high above the previous swing high (HH)
+    HigherHigh,
+    /// Swing high below the previous swing high (LH)
+    LowerHigh,
+    /// Swing high level with the previous swing high (EQH)
+    EqualHigh,
+    /// Swing low above the previous swing low (HL)
+    HigherLow,
+    /// Swing low below the previous swing low (LL)
+    LowerLow,
+    /// Swing low level with the previous swing low (EQL)
+    EqualLow,
+}
+
+/// A swing with its label
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabeledSwing {
+    /// The swing high or low
+    pub pivot: Pivot,
+    /// Comparison with the previous swing of the same kind; `None` for the first
+    pub label: Option<SwingLabel>,
+}
+
+/// Kind of structure break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BreakKind {
+    /// Break in the direction of the trend, or the first break, which sets the trend
+    BreakOfStructure,
+    /// Break against the trend, which reverses it
+    ChangeOfCharacter,
+}
+
+/// A close beyond the last swing high or low
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructureBreak {
+    /// Index of the bar closing beyond the swing
+    pub index: usize,
+    /// Index of the broken swing
+    pub swing: usize,
+    /// Price of the broken swing
+    pub level: f64,
+    /// `true` for a close above a swing high, `false` for one below a swing low
+    pub bullish: bool,
+    /// BOS or CHoCH
+    pub kind: BreakKind,
+}
+
+/// Market structure of a series of bars
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketStructure {
+    /// Every swing in bar order with its label
+    pub swings: Vec<LabeledSwing>,
+    /// Structure breaks in bar order
+    pub breaks: Vec<StructureBreak>,
+    /// Trend after each bar: 1 up, -1 down, 0 before the first break
+    pub trend: Vec<i8>,
+}
+
+/// Labels swings and detects breaks of structure and changes of character
+///
+/// Each swing high is labeled against the previous swing high, each swing low against
+/// the previous swing low. From the bar confirming it, the latest swing high is broken by
+/// the first close above it and the latest swing low by the first close below it; each
+/// swing breaks at most once.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `width` - Bars on each side of a swing, as in [`fractals`]
+///
+/// # Returns
+/// * `Ok(MarketStructure)` - Labeled swings, breaks and the trend on each bar
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{market_structure, BreakKind, SwingLabel};
+///
+/// // Up to a high at bar 2, a pullback to bar 4, a close above the high at bar 6,
+/// // then a fall through the pullback low at bar 10
+/// let close = vec![10.0, 11.0, 12.0, 11.0, 10.5, 11.5, 12.5, 13.0, 12.0, 11.0, 10.0];
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.2).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 0.2).collect();
+/// let structure = market_structure(&high, &low, &close, 1).unwrap();
+///
+/// assert_eq!(structure.swings[2].label, Some(SwingLabel::HigherHigh));
+/// let kinds: Vec<BreakKind> = structure.breaks.iter().map(|b| b.kind).collect();
+/// assert_eq!(kinds, vec![BreakKind::BreakOfStructure, BreakKind::ChangeOfCharacter]);
+/// assert_eq!((structure.breaks[0].index, structure.breaks[1].index), (6, 10));
+/// assert_eq!((structure.trend[5], structure.trend[6], structure.trend[10]), (0, 1, -1));
+/// ```
+pub fn market_structure(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    width: impl IntoParam<Period>,
+) -> TAResult<MarketStructure> {
+    with_function("MARKET_STRUCTURE", || {
+        let width = width.into_param("width")?.get();
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let pivots = fractals(high, low, width)?.pivots;
+
+        let mut swings = Vec::with_capacity(pivots.len());
+        let (mut last_high, mut last_low): (Option<f64>, Option<f64>) = (None, None);
+        for &pivot in &pivots {
+            let previous = if pivot.is_high { &mut last_high } else { &mut last_low };
+            let label = previous.map(|before| label(pivot, before));
+            *previous = Some(pivot.price);
+            swings.push(LabeledSwing { pivot, label });
+        }
+
+        // Swings in the order they become known
+        let mut confirmed = pivots;
+        confirmed.sort_by_key(|pivot| pivot.confirmed_at);
+        let mut next = 0;
+        // Latest swing high and low not yet broken
+        let (mut active_high, mut active_low): (Option<Pivot>, Option<Pivot>) = (None, None);
+        let mut breaks = Vec::new();
+        let mut trend = vec![0i8; close.len()];
+        let mut current = 0i8;
+
+        for (i, &c) in close.iter().enumerate() {
+            while next < confirmed.len() && confirmed[next].confirmed_at <= i {
+                let pivot = confirmed[next];
+                if pivot.is_high {
+                    active_high = Some(pivot);
+                } else {
+                    active_low = Some(pivot);
+                }
+                next += 1;
+            }
+
+            for (active, bullish) in [(&mut active_high, true), (&mut active_low, false)] {
+                let Some(swing) = *active else { continue };
+                let broken = if bullish { c > swing.price } else { c < swing.price };
+                if broken {
+                    let direction = if bullish { 1 } else { -1 };
+                    let kind = if current == -direction { BreakKind::ChangeOfCharacter } else { BreakKind::BreakOfStructure };
+                    breaks.push(StructureBreak { index: i, swing: swing.index, level: swing.price, bullish, kind });
+                    current = direction;
+                    *active = None;
+                }
+            }
+            trend[i] = current;
+        }
+
+        Ok(MarketStructure { swings, breaks, trend })
+    })
+}
+
+fn label(pivot: Pivot, previous: f64) -> SwingLabel {
+    match (pivot.is_high, pivot.price.partial_cmp(&previous)) {
+        (true, Some(core::cmp::Ordering::Greater)) => SwingLabel::HigherHigh,
+        (true, Some(core::cmp::Ordering::Less)) => SwingLabel::LowerHigh,
+        (true, _) => SwingLabel::EqualHigh,
+        (false, Some(core::cmp::Ordering::Greater)) => SwingLabel::HigherLow,
+        (false, Some(core::cmp::Ordering::Less)) => SwingLabel::LowerLow,
+        (false, _) => SwingLabel::EqualLow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_in_downtrend() {
+        // Falling swings: each high and low below the last
+        let close = [20.0, 18.0, 19.0, 16.0, 17.5, 15.0, 16.5, 14.0, 13.0];
+        let structure = market_structure(&close, &close, &close, 1).unwrap();
+        let labels: Vec<Option<SwingLabel>> = structure.swings.iter().map(|s| s.label).collect();
+        assert_eq!(
+            labels,
+            vec![None, None, Some(SwingLabel::LowerLow), Some(SwingLabel::LowerHigh), Some(SwingLabel::LowerLow), Some(SwingLabel::LowerHigh)]
+        );
+        // Each new low is taken out by a later close: breaks of structure with the trend
+        assert!(structure.breaks.iter().all(|b| !b.bullish && b.kind == BreakKind::BreakOfStructure));
+        assert_eq!(structure.breaks[0].index, 3);
+        assert_eq!(*structure.trend.last().unwrap(), -1);
+    }
+
+    #[test]
+    fn test_swings_break_once_and_not_before_confirmation() {
+        // The high at bar 1 is known at bar 2 and broken at bar 3; bar 5 breaks the next
+        // high, at bar 3, not the first one again
+        let close = [10.0, 12.0, 11.0, 12.5, 11.5, 13.0];
+        let structure = market_structure(&close, &close, &close, 1).unwrap();
+        assert_eq!(structure.breaks.len(), 2);
+        assert_eq!((structure.breaks[0].swing, structure.breaks[0].index), (1, 3));
+        assert_eq!((structure.breaks[1].swing, structure.breaks[1].index), (3, 5));
+        assert!(market_structure(&close, &close, &close[1..], 1).is_err());
+        assert!(market_structure(&close, &close, &close, 0).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Trendlines
+//!
+//! A trendline joins swing lows (support) or swing highs (resistance) that line up. Lines
+//! are found by trying the line through every pair of swings of one kind, RANSAC-style but
+//! exhaustively so the result is deterministic: swings within a tolerance of it are its
+//! touches, and a line is rejected if a swing between its first and last touch crosses it.
+//! The best candidates are refitted through their touches by least squares, and each swing
+//! touches at most one line.
+
+use crate::common::{IntoParam, Percent, TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::pattern::Pivot;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// A trendline through swing points
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trendline {
+    /// `true` for a resistance line through swing highs, `false` for support through lows
+    pub is_resistance: bool,
+    /// Change of the line per bar; positive for an ascending line
+    pub slope: f64,
+    /// Value of the line at bar 0
+    pub intercept: f64,
+    /// Bar indices of the swings touching the line, in order
+    pub touches: Vec<usize>,
+    /// Bar from which every touch is confirmed and the line is known
+    pub confirmed_at: usize,
+}
+
+impl Trendline {
+    /// Value of the line at a bar
+    pub fn value_at(&self, index: usize) -> f64 {
+        self.intercept + self.slope * index as f64
+    }
+
+    /// First bar, from the line's confirmation on, closing through it: below a support
+    /// line or above a resistance line
+    pub fn first_break(&self, close: &[f64]) -> Option<usize> {
+        (self.confirmed_at..close.len()).find(|&i| {
+            let line = self.value_at(i);
+            if self.is_resistance { close[i] > line } else { close[i] < line }
+        })
+    }
+}
+
+/// Fits support and resistance lines through swing points
+///
+/// A swing touches a line when it lies within `tolerance` percent of the line's value at
+/// its bar. Lines need at least `min_touches` touches, and no swing of the same kind
+/// between the first and last touch may lie beyond the line by more than the tolerance.
+/// Lines with the most touches are taken first, then those spanning more bars.
+///
+/// # Arguments
+/// * `pivots` - Swing highs and lows, e.g. the `pivots` of [`fractals`](crate::pattern::fractals)
+/// * `min_touches` - Minimum number of touching swings, at least 2
+/// * `tolerance` - Distance from the line counted as a touch, in percent of price
+///
+/// # Returns
+/// * `Ok(Vec<Trendline>)` - Lines ordered by their first touch
+/// * `Err(TAError)` - Error if parameters are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::pattern::{fractals, trendlines};
+///
+/// // Rising lows at 10, 11 and 12, each 4 bars apart, then a close through the line
+/// let mut close: Vec<f64> = (0..16).map(|i| 10.0 + 0.25 * i as f64 + [0.0, 1.0, 1.5, 1.0][i % 4]).collect();
+/// close.push(13.0);
+/// let swings = fractals(&close, &close, 1).unwrap().pivots;
+///
+/// let lines = trendlines(&swings, 3, 0.5).unwrap();
+/// let support = lines.iter().find(|line| !line.is_resistance).unwrap();
+/// assert_eq!(support.touches, vec![4, 8, 12]);
+/// assert!((support.slope - 0.25).abs() < 1e-9);
+/// assert_eq!(support.first_break(&close), Some(16));
+/// ```
+pub fn trendlines(pivots: &[Pivot], min_touches: usize, tolerance: impl IntoParam<Percent>) -> TAResult<Vec<Trendline>> {
+    with_function("TRENDLINES", || {
+        let tolerance = tolerance.into_param("tolerance")?.fraction();
+        if min_touches < 2 {
+            return Err(TAError::invalid_parameter("min_touches", "must be at least 2"));
+        }
+
+        let mut lines = Vec::new();
+        for is_resistance in [true, false] {
+            let mut pool: Vec<Pivot> =
+                pivots.iter().filter(|p| p.is_high == is_resistance && p.price.is_finite()).copied().collect();
+            pool.sort_by_key(|p| p.index);
+
+            while let Some(touches) = best_candidate(&pool, min_touches, tolerance, is_resistance) {
+                let (slope, intercept) = least_squares(&touches);
+                lines.push(Trendline {
+                    is_resistance,
+                    slope,
+                    intercept,
+                    touches: touches.iter().map(|p| p.index).collect(),
+                    confirmed_at: touches.iter().map(|p| p.confirmed_at).max().unwrap_or(0),
+                });
+                pool.retain(|p| !touches.iter().any(|t| t.index == p.index));
+            }
+        }
+
+        lines.sort_by_key(|line| line.touches[0]);
+        Ok(lines)
+    })
+}
+
+/// Touches of the best line through two swings of `pool`, if any has enough
+fn best_candidate(pool: &[Pivot], min_touches: usize, tolerance: f64, is_resistance: bool) -> Option<Vec<Pivot>> {
+    let mut best: Option<Vec<Pivot>> = None;
+    for (a, first) in pool.iter().enumerate() {
+        for second in &pool[a + 1..] {
+            let slope = (second.price - first.price) / (second.index - first.index) as f64;
+            let line = |index: usize| first.price + slope * (index as f64 - first.index as f64);
+            let distance = |p: &Pivot| (p.price - line(p.index)) / line(p.index).abs();
+
+            let touches: Vec<Pivot> = pool.iter().filter(|p| distance(p).abs() <= tolerance).copied().collect();
+            if touches.len() < min_touches {
+                continue;
+            }
+            let (start, end) = (touches[0].index, touches[touches.len() - 1].index);
+            let crossed = pool.iter().filter(|p| p.index > start && p.index < end).any(|p| {
+                let beyond = if is_resistance { distance(p) } else { -distance(p) };
+                beyond > tolerance
+            });
+            if crossed {
+                continue;
+            }
+
+            let better = best.as_ref().is_none_or(|current| {
+                let span = |t: &[Pivot]| t[t.len() - 1].index - t[0].index;
+                (touches.len(), span(&touches)) > (current.len(), span(current))
+            });
+            if better {
+                best = Some(touches);
+            }
+        }
+    }
+    best
+}
+
+/// Slope and intercept of the least-squares line through the swings
+fn least_squares(points: &[Pivot]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.index as f64).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.price).sum::<f64>() / n;
+    let (mut sxy, mut sxx) = (0.0, 0.0);
+    for p in points {
+        let dx = p.index as f64 - mean_x;
+        sxy += dx * (p.price - mean_y);
+        sxx += dx * dx;
+    }
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    (slope, mean_y - slope * mean_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    fn pivot(index: usize, price: f64, is_high: bool) -> Pivot {
+        Pivot { index, price, is_high, confirmed_at: index + 2 }
+    }
+
+    #[test]
+    fn test_descending_resistance_with_outlier() {
+        // Highs on a line falling 0.5 a bar, one a little off, and one far off that is left out
+        let pivots = vec![
+            pivot(0, 20.0, true),
+            pivot(5, 17.5, true),
+            pivot(8, 14.0, true),
+            pivot(10, 15.02, true),
+            pivot(15, 12.5, true),
+            pivot(3, 10.0, false),
+        ];
+        let lines = trendlines(&pivots, 3, 0.5).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].is_resistance);
+        assert_eq!(lines[0].touches, vec![0, 5, 10, 15]);
+        assert_float_eq!(lines[0].slope, -0.5, 1e-3);
+        assert_eq!(lines[0].confirmed_at, 17);
+
+        let mut close = vec![10.0; 20];
+        close[16] = 13.0;
+        assert_eq!(lines[0].first_break(&close), None);
+        close[18] = 12.0;
+        assert_eq!(lines[0].first_break(&close), Some(18));
+    }
+
+    #[test]
+    fn test_crossed_lines_and_errors() {
+        // The middle low pierces the line through the outer two
+        let pivots = vec![pivot(0, 10.0, false), pivot(4, 8.0, false), pivot(8, 10.0, false)];
+        assert!(trendlines(&pivots, 2, 0.1).unwrap().iter().all(|line| line.touches != vec![0, 8]));
+        assert_eq!(trendlines(&pivots, 3, 0.1).unwrap(), vec![]);
+        assert!(trendlines(&pivots, 1, 0.1).is_err());
+        assert!(trendlines(&pivots, 2, 150.0).is_err());
+    }
+}
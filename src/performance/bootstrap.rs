@@ -0,0 +1,242 @@
+//! Block bootstrap of returns
+//!
+//! A backtest gives one path of returns; how much of its drawdown and growth is luck
+//! shows when the same returns are resampled into many alternative paths. Returns are
+//! drawn in blocks of consecutive bars, wrapping around the end of the sample (the
+//! circular block bootstrap), which keeps the short-range dependence, such as volatility
+//! clustering, that resampling single returns would destroy.
+
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::common::utils::validate_not_empty;
+use crate::performance::returns::{annualized_return_of, finite, max_drawdown_of, mean_and_sd, validate_periods_per_year};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Block bootstrap configuration for [`bootstrap_with`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootstrapParams {
+    /// Number of resampled paths, each as long as the sample
+    pub n_paths: usize,
+    /// Number of consecutive returns drawn at a time
+    pub block_len: usize,
+    /// Bars per year, for the annualized return (252 for daily data)
+    pub periods_per_year: f64,
+    /// Seed of the random number generator; the same seed gives the same paths
+    pub seed: u64,
+}
+
+impl Default for BootstrapParams {
+    fn default() -> Self {
+        Self { n_paths: 1000, block_len: 20, periods_per_year: 252.0, seed: 0 }
+    }
+}
+
+/// Distribution of a statistic over the resampled paths
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Distribution {
+    /// Value of each path, in ascending order
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub values: Vec<f64>,
+    /// Mean over the paths
+    pub mean: f64,
+    /// Sample standard deviation over the paths; NaN with a single path
+    pub std_dev: f64,
+}
+
+impl Distribution {
+    fn new(mut values: Vec<f64>) -> Self {
+        values.sort_by(f64::total_cmp);
+        let (mean, std_dev) = match values.len() {
+            1 => (values[0], f64::NAN),
+            _ => mean_and_sd(&values),
+        };
+        Self { values, mean, std_dev }
+    }
+
+    /// Value below which a fraction `q` of the paths fall, interpolating linearly
+    /// between paths; `q` is clamped to `[0, 1]`
+    pub fn quantile(&self, q: f64) -> f64 {
+        let position = q.clamp(0.0, 1.0) * (self.values.len() - 1) as f64;
+        let below = position as usize;
+        let above = (below + 1).min(self.values.len() - 1);
+        let weight = position - below as f64;
+        self.values[below] + weight * (self.values[above] - self.values[below])
+    }
+
+    /// Median over the paths
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    /// Fraction of the paths whose value is below `threshold`
+    pub fn fraction_below(&self, threshold: f64) -> f64 {
+        self.values.partition_point(|&v| v < threshold) as f64 / self.values.len() as f64
+    }
+}
+
+/// Distributions of the maximum drawdown and annualized return over the resampled paths
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootstrapSummary {
+    /// Maximum drawdown of each path, in percent
+    pub max_drawdown: Distribution,
+    /// Annualized compound return (CAGR) of each path, in percent
+    pub cagr: Distribution,
+}
+
+/// Block bootstrap of daily returns
+///
+/// Resamples `returns` into `n_paths` paths with blocks of `block_len` bars, annualizing
+/// with 252 bars a year; see [`bootstrap_with`].
+///
+/// # Example
+/// ```rust
+/// use ta_rust::performance::bootstrap;
+///
+/// let returns: Vec<f64> = (0..500).map(|i| 0.0004 + 0.01 * (i as f64 * 0.7).sin()).collect();
+/// let summary = bootstrap(&returns, 500, 10).unwrap();
+///
+/// // 90% of the paths have a drawdown between these
+/// let (low, high) = (summary.max_drawdown.quantile(0.05), summary.max_drawdown.quantile(0.95));
+/// assert!(low <= summary.max_drawdown.median() && summary.max_drawdown.median() <= high);
+/// // Chance that the strategy loses money over a sample this long
+/// let losing = summary.cagr.fraction_below(0.0);
+/// assert!((0.0..=1.0).contains(&losing));
+/// ```
+pub fn bootstrap(returns: &[f64], n_paths: usize, block_len: usize) -> TAResult<BootstrapSummary> {
+    with_function("BOOTSTRAP", || {
+        bootstrap_with(returns, &BootstrapParams { n_paths, block_len, ..BootstrapParams::default() })
+    })
+}
+
+/// Block bootstrap of returns with a chosen annualization and seed
+///
+/// Non-finite returns, such as the leading NaN of
+/// [`simple_returns`](crate::performance::simple_returns), are skipped. Each path is as
+/// long as the remaining sample and is built from blocks starting at uniformly drawn
+/// bars, continuing from the start of the sample past its end.
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `params` - Number of paths, block length, bars per year and seed
+///
+/// # Returns
+/// * `Ok(BootstrapSummary)` - Maximum drawdown and CAGR distributions, in percent
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Errors
+/// - `InvalidInput` if there is no finite return
+/// - `InvalidParameter` if `n_paths` or `block_len` is 0, `block_len` exceeds the number
+///   of finite returns, or `periods_per_year` is not positive
+///
+/// # Example
+/// ```rust
+/// use ta_rust::performance::{bootstrap_with, BootstrapParams};
+///
+/// let returns: Vec<f64> = (0..100).map(|i| if i % 3 == 0 { -0.01 } else { 0.008 }).collect();
+/// let params = BootstrapParams { n_paths: 200, block_len: 5, periods_per_year: 52.0, seed: 7 };
+/// let first = bootstrap_with(&returns, &params).unwrap();
+/// assert_eq!(first, bootstrap_with(&returns, &params).unwrap());
+/// ```
+pub fn bootstrap_with(returns: &[f64], params: &BootstrapParams) -> TAResult<BootstrapSummary> {
+    with_function("BOOTSTRAP", || {
+        validate_not_empty(returns, "returns")?;
+        validate_periods_per_year(params.periods_per_year)?;
+        let returns = finite(returns);
+        if returns.is_empty() {
+            return Err(TAError::invalid_input("returns contain no finite value"));
+        }
+        if params.n_paths == 0 {
+            return Err(TAError::invalid_parameter("n_paths", "must be at least 1"));
+        }
+        if params.block_len == 0 || params.block_len > returns.len() {
+            return Err(TAError::invalid_parameter(
+                "block_len".into(),
+                format!("must be between 1 and the number of finite returns, {}", returns.len()),
+            ));
+        }
+
+        let n = returns.len();
+        let mut rng = SplitMix64(params.seed);
+        let mut path = Vec::with_capacity(n);
+        let mut drawdowns = Vec::with_capacity(params.n_paths);
+        let mut cagrs = Vec::with_capacity(params.n_paths);
+        for _ in 0..params.n_paths {
+            path.clear();
+            while path.len() < n {
+                let start = rng.below(n);
+                let len = params.block_len.min(n - path.len());
+                path.extend((start..start + len).map(|i| returns[i % n]));
+            }
+            drawdowns.push(max_drawdown_of(&path));
+            cagrs.push(annualized_return_of(&path, params.periods_per_year));
+        }
+
+        Ok(BootstrapSummary { max_drawdown: Distribution::new(drawdowns), cagr: Distribution::new(cagrs) })
+    })
+}
+
+/// SplitMix64 generator: small, fast and the same on every platform
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform integer in `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_bootstrap_distributions() {
+        // Constant returns give the same path whatever the blocks
+        let summary = bootstrap(&[0.001; 50], 20, 5).unwrap();
+        assert_eq!(summary.max_drawdown.values, vec![0.0; 20]);
+        assert_float_eq!(summary.cagr.mean, 100.0 * (1.001f64.powf(252.0) - 1.0), 1e-9);
+        assert_float_eq!(summary.cagr.std_dev, 0.0, 1e-9);
+
+        // Blocks as long as the sample rotate it: the compound growth is unchanged
+        let returns: Vec<f64> = (0..60).map(|i| 0.02 * ((i * 7 % 11) as f64 - 5.0) / 5.0).collect();
+        let params = BootstrapParams { n_paths: 50, block_len: 60, periods_per_year: 60.0, seed: 3 };
+        let rotated = bootstrap_with(&returns, &params).unwrap();
+        let growth = 100.0 * (returns.iter().map(|r| 1.0 + r).product::<f64>() - 1.0);
+        assert!(rotated.cagr.values.iter().all(|&c| (c - growth).abs() < 1e-9));
+
+        let summary = bootstrap_with(&returns, &BootstrapParams { block_len: 4, ..params }).unwrap();
+        let drawdowns = &summary.max_drawdown;
+        assert!(drawdowns.values.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(drawdowns.quantile(0.0), drawdowns.values[0]);
+        assert_eq!(drawdowns.quantile(1.0), drawdowns.values[49]);
+        assert!(drawdowns.quantile(0.05) <= drawdowns.median());
+        assert_eq!(drawdowns.fraction_below(f64::INFINITY), 1.0);
+        assert_ne!(summary, bootstrap_with(&returns, &BootstrapParams { seed: 4, block_len: 4, ..params }).unwrap());
+    }
+
+    #[test]
+    fn test_bootstrap_invalid_input() {
+        let returns = [f64::NAN, 0.01, -0.02, 0.03];
+        assert!(bootstrap(&returns, 10, 3).is_ok());
+        assert!(bootstrap(&returns, 10, 4).is_err());
+        assert!(bootstrap(&returns, 10, 0).is_err());
+        assert!(bootstrap(&returns, 0, 2).is_err());
+        assert!(bootstrap(&[f64::NAN], 10, 1).is_err());
+        assert!(bootstrap(&[], 10, 1).is_err());
+        let params = BootstrapParams { periods_per_year: 0.0, ..BootstrapParams::default() };
+        assert!(bootstrap_with(&returns, &params).is_err());
+    }
+}
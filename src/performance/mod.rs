@@ -0,0 +1,18 @@
+//! Performance and Risk Metrics
+//!
+//! Evaluates a returns or equity series: annualized return and volatility, the Sharpe,
+//! Sortino and Calmar ratios over the full sample or a rolling window, and drawdown depth
+//! and duration. Returns are per-bar fractions; percentages follow the rest of the crate.
+//! [`bootstrap`] resamples the returns to show how much the drawdown and annualized return
+//! could have varied.
+
+pub mod ratios;
+pub mod returns;
+pub mod bootstrap;
+
+pub use ratios::*;
+pub use returns::*;
+pub use bootstrap::*;
+
+// Drawdown measures live with the volatility indicators
+pub use crate::volatility::{drawdown, drawdown_duration, max_drawdown, rolling_max_drawdown};
@@ -0,0 +1,280 @@
+//! Risk-adjusted return ratios: Sharpe, Sortino and Calmar
+//!
+//! Each ratio is available over the full sample, skipping non-finite returns, and over a
+//! rolling window, where a window containing a non-finite return is NaN.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::validate_not_empty;
+use crate::performance::returns::{annualized_return_of, finite, max_drawdown_of, mean_and_sd, validate_periods_per_year};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Sharpe ratio
+///
+/// # Formula
+/// ```text
+/// Excess = r - risk_free_rate / periods_per_year
+/// Sharpe = Mean(Excess) / StdDev(Excess) × √periods_per_year
+/// ```
+/// using the sample standard deviation.
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `risk_free_rate` - Annual risk-free rate as a fraction (e.g. 0.02)
+/// * `periods_per_year` - Bars per year (252 for daily data)
+///
+/// # Returns
+/// * `Ok(f64)` - Annualized Sharpe ratio; NaN with fewer than two finite returns or no
+///   variation
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::sharpe_ratio;
+///
+/// let returns = vec![0.01, -0.005, 0.008, 0.002, -0.003];
+/// let result = sharpe_ratio(&returns, 0.0, 252.0).unwrap();
+/// assert!(result > 0.0);
+/// ```
+pub fn sharpe_ratio(returns: &[f64], risk_free_rate: f64, periods_per_year: f64) -> TAResult<f64> {
+    with_function("SHARPE_RATIO", || {
+        validate(returns, periods_per_year)?;
+        validate_rate("risk_free_rate", risk_free_rate)?;
+        Ok(sharpe_of(&finite(returns), risk_free_rate / periods_per_year, periods_per_year))
+    })
+}
+
+/// Sortino ratio
+///
+/// Like the Sharpe ratio, but only returns below the target count as risk.
+///
+/// # Formula
+/// ```text
+/// Excess   = r - target_return / periods_per_year
+/// Downside = √(Σ min(Excess, 0)² / n)
+/// Sortino  = Mean(Excess) / Downside × √periods_per_year
+/// ```
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `target_return` - Annual target (minimum acceptable) return as a fraction, often 0
+/// * `periods_per_year` - Bars per year (252 for daily data)
+///
+/// # Returns
+/// * `Ok(f64)` - Annualized Sortino ratio; NaN with fewer than two finite returns or none
+///   below the target
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn sortino_ratio(returns: &[f64], target_return: f64, periods_per_year: f64) -> TAResult<f64> {
+    with_function("SORTINO_RATIO", || {
+        validate(returns, periods_per_year)?;
+        validate_rate("target_return", target_return)?;
+        Ok(sortino_of(&finite(returns), target_return / periods_per_year, periods_per_year))
+    })
+}
+
+/// Calmar ratio
+///
+/// # Formula
+/// ```text
+/// Calmar = AnnualizedReturn / MaxDrawdown
+/// ```
+/// where the maximum drawdown is that of the equity curve compounded from the returns,
+/// starting at 1.
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `periods_per_year` - Bars per year (252 for daily data)
+///
+/// # Returns
+/// * `Ok(f64)` - Calmar ratio; NaN without any finite return or without a drawdown
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::calmar_ratio;
+///
+/// // Up 20%, down 10% (a 10% drawdown), then up 5%: 13.4% over the year
+/// let result = calmar_ratio(&[0.2, -0.1, 0.05], 3.0).unwrap();
+/// assert!((result - 1.34).abs() < 1e-9);
+/// ```
+pub fn calmar_ratio(returns: &[f64], periods_per_year: f64) -> TAResult<f64> {
+    with_function("CALMAR_RATIO", || {
+        validate(returns, periods_per_year)?;
+        Ok(calmar_of(&finite(returns), periods_per_year))
+    })
+}
+
+/// Rolling Sharpe ratio over windows of `period` returns
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized Sharpe ratios; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn rolling_sharpe(returns: &[f64], period: impl IntoParam<Period>, risk_free_rate: f64, periods_per_year: f64) -> TAResult<Vec<f64>> {
+    with_function("ROLLING_SHARPE", || {
+        let period = period.into_param("period")?.get();
+        validate(returns, periods_per_year)?;
+        validate_rate("risk_free_rate", risk_free_rate)?;
+        let rf = risk_free_rate / periods_per_year;
+        rolling(returns, period, |window| sharpe_of(window, rf, periods_per_year))
+    })
+}
+
+/// Rolling Sortino ratio over windows of `period` returns
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized Sortino ratios; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn rolling_sortino(returns: &[f64], period: impl IntoParam<Period>, target_return: f64, periods_per_year: f64) -> TAResult<Vec<f64>> {
+    with_function("ROLLING_SORTINO", || {
+        let period = period.into_param("period")?.get();
+        validate(returns, periods_per_year)?;
+        validate_rate("target_return", target_return)?;
+        let target = target_return / periods_per_year;
+        rolling(returns, period, |window| sortino_of(window, target, periods_per_year))
+    })
+}
+
+/// Rolling Calmar ratio over windows of `period` returns
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Calmar ratios; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn rolling_calmar(returns: &[f64], period: impl IntoParam<Period>, periods_per_year: f64) -> TAResult<Vec<f64>> {
+    with_function("ROLLING_CALMAR", || {
+        let period = period.into_param("period")?.get();
+        validate(returns, periods_per_year)?;
+        rolling(returns, period, |window| calmar_of(window, periods_per_year))
+    })
+}
+
+fn validate(returns: &[f64], periods_per_year: f64) -> TAResult<()> {
+    validate_not_empty(returns, "returns")?;
+    validate_periods_per_year(periods_per_year)
+}
+
+fn validate_rate(name: &str, rate: f64) -> TAResult<()> {
+    if !rate.is_finite() {
+        return Err(TAError::invalid_parameter(name, "must be a finite number"));
+    }
+    Ok(())
+}
+
+fn rolling(returns: &[f64], period: usize, ratio: impl Fn(&[f64]) -> f64) -> TAResult<Vec<f64>> {
+    if period > returns.len() {
+        return Err(TAError::insufficient_data(period, returns.len()));
+    }
+
+    let mut result = vec![f64::NAN; returns.len()];
+    for (value, window) in result[period - 1..].iter_mut().zip(returns.windows(period)) {
+        if window.iter().all(|r| r.is_finite()) {
+            *value = ratio(window);
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn sharpe_of(returns: &[f64], risk_free: f64, periods_per_year: f64) -> f64 {
+    if returns.len() < 2 {
+        return f64::NAN;
+    }
+    let (mean, sd) = mean_and_sd(returns);
+    if sd > 0.0 {
+        (mean - risk_free) / sd * periods_per_year.sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+pub(crate) fn sortino_of(returns: &[f64], target: f64, periods_per_year: f64) -> f64 {
+    if returns.len() < 2 {
+        return f64::NAN;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().map(|r| r - target).sum::<f64>() / n;
+    let downside = returns.iter().map(|r| (r - target).min(0.0).powi(2)).sum::<f64>() / n;
+    if downside > 0.0 {
+        mean / downside.sqrt() * periods_per_year.sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+fn calmar_of(returns: &[f64], periods_per_year: f64) -> f64 {
+    let worst = max_drawdown_of(returns);
+    if worst > 0.0 {
+        annualized_return_of(returns, periods_per_year) / worst
+    } else {
+        f64::NAN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::performance::simple_returns;
+
+    #[test]
+    fn test_sharpe_and_sortino() {
+        let returns = [0.01, -0.02, 0.03, 0.0];
+        let mean = 0.005;
+        let sd = ((0.005f64.powi(2) * 2.0 + 0.025f64.powi(2) * 2.0) / 3.0).sqrt();
+        assert_float_eq!(sharpe_ratio(&returns, 0.0, 4.0).unwrap(), mean / sd * 2.0, 1e-12);
+        assert_float_eq!(sortino_ratio(&returns, 0.0, 4.0).unwrap(), mean / (0.0004f64 / 4.0).sqrt() * 2.0, 1e-12);
+
+        // A 4% annual risk-free rate is 1% a bar at 4 bars a year
+        assert_float_eq!(sharpe_ratio(&returns, 0.04, 4.0).unwrap(), (mean - 0.01) / sd * 2.0, 1e-12);
+
+        assert!(sharpe_ratio(&[0.01, 0.01], 0.0, 252.0).unwrap().is_nan());
+        assert!(sortino_ratio(&[0.01, 0.02], 0.0, 252.0).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_ratios_from_equity() {
+        let equity = vec![100.0, 101.0, 99.0, 102.0, 102.0];
+        let returns = simple_returns(&equity).unwrap();
+        // The leading NaN is skipped
+        assert_float_eq!(
+            sharpe_ratio(&returns, 0.0, 252.0).unwrap(),
+            sharpe_ratio(&returns[1..], 0.0, 252.0).unwrap(),
+            1e-12
+        );
+        assert!(calmar_ratio(&returns, 252.0).unwrap() > 0.0);
+        assert!(calmar_ratio(&[0.01, 0.02], 252.0).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_rolling_ratios_match_full_sample() {
+        let returns: Vec<f64> = (0..40).map(|i| (i as f64 * 0.7).sin() * 0.01 + 0.001).collect();
+        let period = 10;
+        let sharpe = rolling_sharpe(&returns, period, 0.02, 252.0).unwrap();
+        let sortino = rolling_sortino(&returns, period, 0.0, 252.0).unwrap();
+        let calmar = rolling_calmar(&returns, period, 252.0).unwrap();
+
+        assert!(sharpe[..period - 1].iter().all(|v| v.is_nan()));
+        for i in (period - 1)..40 {
+            let window = &returns[i + 1 - period..=i];
+            assert_float_eq!(sharpe[i], sharpe_ratio(window, 0.02, 252.0).unwrap(), 1e-12);
+            assert_float_eq!(sortino[i], sortino_ratio(window, 0.0, 252.0).unwrap(), 1e-12);
+            assert_float_eq!(calmar[i], calmar_ratio(window, 252.0).unwrap(), 1e-12);
+        }
+
+        let mut gappy = returns.clone();
+        gappy[15] = f64::NAN;
+        let sharpe = rolling_sharpe(&gappy, period, 0.0, 252.0).unwrap();
+        assert!(sharpe[15..25].iter().all(|v| v.is_nan()));
+        assert!(!sharpe[25].is_nan());
+    }
+
+    #[test]
+    fn test_ratios_invalid_input() {
+        let returns = vec![0.01, -0.01, 0.02];
+        assert!(sharpe_ratio(&[], 0.0, 252.0).is_err());
+        assert!(sharpe_ratio(&returns, f64::NAN, 252.0).is_err());
+        assert_eq!(sortino_ratio(&returns, 0.0, -1.0).unwrap_err().function(), Some("SORTINO_RATIO"));
+        assert!(calmar_ratio(&returns, 0.0).is_err());
+        assert!(rolling_sharpe(&returns, 0, 0.0, 252.0).is_err());
+        assert!(rolling_calmar(&returns, 4, 252.0).is_err());
+    }
+}
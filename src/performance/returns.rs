@@ -0,0 +1,323 @@
+//! Returns, annualized return and volatility, and drawdown duration
+//!
+//! These are the crate's return conversions; [`price_transform::returns`](crate::price_transform::returns)
+//! selects between them by [`ReturnKind`].
+
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::common::utils::validate_not_empty;
+use crate::volatility::drawdown_duration;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Simple returns of an equity (or price) series
+///
+/// # Formula
+/// ```text
+/// Return[i] = Equity[i] / Equity[i-1] - 1
+/// ```
+///
+/// # Arguments
+/// * `equity` - Equity or price series
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Returns as fractions; the first value is NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::simple_returns;
+///
+/// let result = simple_returns(&[100.0, 110.0, 99.0]).unwrap();
+/// assert!(result[0].is_nan());
+/// assert!((result[1] - 0.1).abs() < 1e-12);
+/// assert!((result[2] + 0.1).abs() < 1e-12);
+/// ```
+pub fn simple_returns(equity: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("SIMPLE_RETURNS", || {
+        validate_not_empty(equity, "equity")?;
+
+        let mut result = vec![f64::NAN; equity.len()];
+        for (value, pair) in result[1..].iter_mut().zip(equity.windows(2)) {
+            *value = pair[1] / pair[0] - 1.0;
+        }
+        Ok(result)
+    })
+}
+
+/// How a return between two prices is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReturnKind {
+    /// Percentage change as a fraction: `price / previous - 1`, see [`simple_returns`]
+    #[default]
+    Simple,
+    /// Natural log of the price ratio: `ln(price / previous)`, see [`log_returns`]
+    Log,
+}
+
+/// Log returns of an equity (or price) series
+///
+/// Log returns add up over time, where simple returns compound.
+///
+/// # Formula
+/// ```text
+/// Return[i] = ln(Equity[i] / Equity[i-1])
+/// ```
+///
+/// # Arguments
+/// * `equity` - Equity or price series
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Log returns; the first value is NaN, as is any return involving a
+///   negative value
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::log_returns;
+///
+/// let result = log_returns(&[100.0, 110.0]).unwrap();
+/// assert!(result[0].is_nan());
+/// assert!((result[1] - 1.1_f64.ln()).abs() < 1e-12);
+/// ```
+pub fn log_returns(equity: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("LOG_RETURNS", || {
+        validate_not_empty(equity, "equity")?;
+
+        let mut result = vec![f64::NAN; equity.len()];
+        for (value, pair) in result[1..].iter_mut().zip(equity.windows(2)) {
+            *value = (pair[1] / pair[0]).ln();
+        }
+        Ok(result)
+    })
+}
+
+/// Compounds per-bar returns into the cumulative return since the first bar
+///
+/// Simple returns compound multiplicatively and log returns add up; the result is of the
+/// same kind as the input. Non-finite returns, such as the leading NaN of
+/// [`simple_returns`], count as no change.
+///
+/// # Formula
+/// ```text
+/// Simple: Cumulative[i] = Π(1 + Return[0..=i]) - 1
+/// Log:    Cumulative[i] = Σ Return[0..=i]
+/// ```
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `kind` - Kind of the returns
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Cumulative returns; NaN before the first finite return
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::{cumulative_returns, simple_returns, ReturnKind};
+///
+/// let returns = simple_returns(&[100.0, 110.0, 99.0, 121.0]).unwrap();
+/// let cumulative = cumulative_returns(&returns, ReturnKind::Simple).unwrap();
+/// assert!(cumulative[0].is_nan());
+/// // 121 / 100 - 1
+/// assert!((cumulative[3] - 0.21).abs() < 1e-12);
+/// ```
+pub fn cumulative_returns(returns: &[f64], kind: ReturnKind) -> TAResult<Vec<f64>> {
+    with_function("CUMULATIVE_RETURNS", || {
+        validate_not_empty(returns, "returns")?;
+
+        let mut result = vec![f64::NAN; returns.len()];
+        let mut total: Option<f64> = None;
+        for (value, &r) in result.iter_mut().zip(returns) {
+            if r.is_finite() {
+                let before = total.unwrap_or(0.0);
+                total = Some(match kind {
+                    ReturnKind::Simple => (1.0 + before) * (1.0 + r) - 1.0,
+                    ReturnKind::Log => before + r,
+                });
+            }
+            *value = total.unwrap_or(f64::NAN);
+        }
+        Ok(result)
+    })
+}
+
+/// Annualized (compound) return
+///
+/// Non-finite returns, such as the leading NaN of [`simple_returns`], are skipped.
+///
+/// # Formula
+/// ```text
+/// AnnualizedReturn = 100 × ((Π(1 + r))^(periods_per_year / n) - 1)
+/// ```
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `periods_per_year` - Bars per year (252 for daily data)
+///
+/// # Returns
+/// * `Ok(f64)` - Annualized return in percent; NaN without any finite return
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::annualized_return;
+///
+/// // +10% a quarter compounds to 46.41% a year
+/// let result = annualized_return(&[0.1, 0.1], 4.0).unwrap();
+/// assert!((result - 46.41).abs() < 1e-9);
+/// ```
+pub fn annualized_return(returns: &[f64], periods_per_year: f64) -> TAResult<f64> {
+    with_function("ANNUALIZED_RETURN", || {
+        validate_periods_per_year(periods_per_year)?;
+        validate_not_empty(returns, "returns")?;
+        Ok(annualized_return_of(&finite(returns), periods_per_year))
+    })
+}
+
+/// Annualized volatility
+///
+/// Non-finite returns are skipped.
+///
+/// # Formula
+/// ```text
+/// AnnualizedVolatility = 100 × StdDev(r) × √periods_per_year
+/// ```
+/// using the sample standard deviation.
+///
+/// # Arguments
+/// * `returns` - Per-bar returns as fractions
+/// * `periods_per_year` - Bars per year (252 for daily data)
+///
+/// # Returns
+/// * `Ok(f64)` - Annualized volatility in percent; NaN with fewer than two finite returns
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn annualized_volatility(returns: &[f64], periods_per_year: f64) -> TAResult<f64> {
+    with_function("ANNUALIZED_VOLATILITY", || {
+        validate_periods_per_year(periods_per_year)?;
+        validate_not_empty(returns, "returns")?;
+
+        let returns = finite(returns);
+        if returns.len() < 2 {
+            return Ok(f64::NAN);
+        }
+        let (_, sd) = mean_and_sd(&returns);
+        Ok(100.0 * sd * periods_per_year.sqrt())
+    })
+}
+
+/// Longest drawdown duration
+///
+/// # Arguments
+/// * `equity` - Equity or price series
+///
+/// # Returns
+/// * `Ok(usize)` - Most consecutive bars spent below a previous peak
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::performance::max_drawdown_duration;
+///
+/// let equity = vec![100.0, 120.0, 90.0, 110.0, 125.0, 115.0];
+/// assert_eq!(max_drawdown_duration(&equity).unwrap(), 2);
+/// ```
+pub fn max_drawdown_duration(equity: &[f64]) -> TAResult<usize> {
+    with_function("MAX_DRAWDOWN_DURATION", || {
+        Ok(drawdown_duration(equity)?.into_iter().max().unwrap_or(0))
+    })
+}
+
+pub(crate) fn validate_periods_per_year(periods_per_year: f64) -> TAResult<()> {
+    if !(periods_per_year.is_finite() && periods_per_year > 0.0) {
+        return Err(TAError::invalid_parameter("periods_per_year", "must be a positive finite number"));
+    }
+    Ok(())
+}
+
+/// The finite values of `returns`
+pub(crate) fn finite(returns: &[f64]) -> Vec<f64> {
+    returns.iter().copied().filter(|r| r.is_finite()).collect()
+}
+
+/// Mean and sample standard deviation; needs at least two values
+pub(crate) fn mean_and_sd(returns: &[f64]) -> (f64, f64) {
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean) * (r - mean)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// Annualized compound return in percent of finite `returns`
+pub(crate) fn annualized_return_of(returns: &[f64], periods_per_year: f64) -> f64 {
+    if returns.is_empty() {
+        return f64::NAN;
+    }
+    let growth: f64 = returns.iter().map(|r| 1.0 + r).product();
+    100.0 * (growth.powf(periods_per_year / returns.len() as f64) - 1.0)
+}
+
+/// Maximum drawdown in percent of the equity compounded from finite `returns`
+pub(crate) fn max_drawdown_of(returns: &[f64]) -> f64 {
+    let mut equity: f64 = 1.0;
+    let mut peak: f64 = 1.0;
+    let mut worst: f64 = 0.0;
+    for r in returns {
+        equity *= 1.0 + r;
+        peak = peak.max(equity);
+        worst = worst.max(100.0 * (peak - equity) / peak);
+    }
+    worst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_annualized_return_skips_nan() {
+        let returns = simple_returns(&[100.0, 110.0, 121.0]).unwrap();
+        assert_float_eq!(annualized_return(&returns, 2.0).unwrap(), 21.0, 1e-9);
+        assert!(annualized_return(&[f64::NAN], 252.0).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_annualized_volatility() {
+        let returns = [0.01, -0.01, 0.01, -0.01];
+        let sd = (4.0 * 0.0001 / 3.0_f64).sqrt();
+        assert_float_eq!(annualized_volatility(&returns, 252.0).unwrap(), 100.0 * sd * 252.0_f64.sqrt(), 1e-9);
+        assert!(annualized_volatility(&[0.01], 252.0).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_max_drawdown_duration() {
+        assert_eq!(max_drawdown_duration(&[1.0, 2.0, 3.0]).unwrap(), 0);
+        assert_eq!(max_drawdown_duration(&[5.0, 4.0, 3.0, 6.0, 5.0]).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_log_returns_and_gaps() {
+        let log = log_returns(&[100.0, 105.0, f64::NAN, 98.0, 120.0]).unwrap();
+        assert!(log[2].is_nan() && log[3].is_nan());
+        assert!(log_returns(&[-1.0, 1.0]).unwrap()[1].is_nan());
+
+        // Summing log returns over the gap misses the move through it
+        let cumulative = cumulative_returns(&log, ReturnKind::Log).unwrap();
+        assert!(cumulative[0].is_nan());
+        assert_float_eq!(cumulative[3], (105.0_f64 / 100.0).ln(), 1e-12);
+        assert_float_eq!(cumulative[4], (105.0_f64 / 100.0).ln() + (120.0_f64 / 98.0).ln(), 1e-12);
+    }
+
+    #[test]
+    fn test_returns_invalid_input() {
+        assert!(simple_returns(&[]).is_err());
+        assert!(log_returns(&[]).is_err());
+        assert!(cumulative_returns(&[], ReturnKind::Log).is_err());
+        assert!(annualized_return(&[], 252.0).is_err());
+        assert!(annualized_return(&[0.1], 0.0).is_err());
+        assert!(annualized_volatility(&[0.1], f64::NAN).is_err());
+        assert!(max_drawdown_duration(&[1.0, f64::NAN]).is_err());
+    }
+}
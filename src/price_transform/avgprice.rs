@@ -1,7 +1,11 @@
 //! Average Price (AVGPRICE)
 
-use crate::common::{TAResult, Price};
+use crate::common::{TAResult, Price, Float};
+use crate::common::errors::with_function;
+use crate::common::types::OHLC;
 use crate::common::utils::{validate_not_empty, validate_same_length, allocate_output};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Average Price (AVGPRICE)
 ///
@@ -39,25 +43,40 @@ use crate::common::utils::{validate_not_empty, validate_same_length, allocate_ou
 /// // result[0] = (10+12+9+11)/4 = 10.5
 /// assert_eq!(result[0], 10.5);
 /// ```
-pub fn avgprice(open: &[Price], high: &[Price], low: &[Price], close: &[Price]) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(open, "open")?;
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_not_empty(close, "close")?;
-    
-    validate_same_length(open, high, "open", "high")?;
-    validate_same_length(open, low, "open", "low")?;
-    validate_same_length(open, close, "open", "close")?;
+pub fn avgprice<T: Float>(open: &[T], high: &[T], low: &[T], close: &[T]) -> TAResult<Vec<T>> {
+    with_function("AVGPRICE", || {
+        let mut output = vec![T::NAN; open.len()];
+        avgprice_into(open, high, low, close, &mut output)?;
+        Ok(output)
+    })
+}
 
-    let mut output = allocate_output(open.len());
+/// Calculates the Average Price into a caller-provided buffer
+///
+/// Allocation-free variant of [`avgprice`]. `out` must have the same length as the inputs.
+///
+/// # Returns
+/// The number of values written, which is always the input length.
+pub fn avgprice_into<T: Float>(open: &[T], high: &[T], low: &[T], close: &[T], out: &mut [T]) -> TAResult<usize> {
+    with_function("AVGPRICE", || {
+        // Input validation
+        validate_not_empty(open, "open")?;
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_not_empty(close, "close")?;
     
-    // Calculate average price for each period
-    for i in 0..open.len() {
-        output[i] = (open[i] + high[i] + low[i] + close[i]) / 4.0;
-    }
+        validate_same_length(open, high, "open", "high")?;
+        validate_same_length(open, low, "open", "low")?;
+        validate_same_length(open, close, "open", "close")?;
+        validate_same_length(open, out, "open", "out")?;
 
-    Ok(output)
+        // Calculate average price for each period
+        for (i, value) in out.iter_mut().enumerate() {
+            *value = (open[i] + high[i] + low[i] + close[i]) / T::from_f64(4.0);
+        }
+
+        Ok(open.len())
+    })
 }
 
 /// Calculates Average Price from OHLC struct data
@@ -69,16 +88,34 @@ pub fn avgprice(open: &[Price], high: &[Price], low: &[Price], close: &[Price])
 ///
 /// # Returns
 /// Vector of average price values.
-pub fn avgprice_from_ohlc(ohlc_data: &[crate::common::types::OHLC]) -> TAResult<Vec<Price>> {
-    validate_not_empty(ohlc_data, "ohlc_data")?;
-    
-    let mut output = allocate_output(ohlc_data.len());
-    
-    for (i, ohlc) in ohlc_data.iter().enumerate() {
-        output[i] = ohlc.average_price();
-    }
-    
-    Ok(output)
+pub fn avgprice_from_ohlc(ohlc_data: &[OHLC]) -> TAResult<Vec<Price>> {
+    with_function("AVGPRICE", || {
+        validate_not_empty(ohlc_data, "ohlc_data")?;
+        Ok(avgprice_iter(ohlc_data).collect())
+    })
+}
+
+/// Lazily calculates the Average Price of each bar
+///
+/// Yields one value per bar, without allocating; bars may be [`OHLC`] or
+/// [`OHLCV`](crate::common::types::OHLCV), owned or borrowed.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLC;
+/// use ta_rust::price_transform::avgprice_iter;
+///
+/// let bars = [OHLC::new(10.0, 12.0, 9.0, 11.0), OHLC::new(11.0, 13.0, 10.0, 14.0)];
+/// let mut prices = avgprice_iter(bars);
+/// assert_eq!(prices.next(), Some(10.5));
+/// assert_eq!(prices.next(), Some(12.0));
+/// ```
+pub fn avgprice_iter<I>(bars: I) -> impl Iterator<Item = Price>
+where
+    I: IntoIterator,
+    I::Item: AsRef<OHLC>,
+{
+    bars.into_iter().map(|bar| bar.as_ref().average_price())
 }
 
 /// Calculates weighted average price with custom weights
@@ -112,41 +149,42 @@ pub fn avgprice_weighted(
     close: &[Price],
     weights: (Price, Price, Price, Price)
 ) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(open, "open")?;
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_not_empty(close, "close")?;
+    with_function("AVGPRICE", || {
+        // Input validation
+        validate_not_empty(open, "open")?;
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_not_empty(close, "close")?;
     
-    validate_same_length(open, high, "open", "high")?;
-    validate_same_length(open, low, "open", "low")?;
-    validate_same_length(open, close, "open", "close")?;
+        validate_same_length(open, high, "open", "high")?;
+        validate_same_length(open, low, "open", "low")?;
+        validate_same_length(open, close, "open", "close")?;
     
-    let (w_open, w_high, w_low, w_close) = weights;
-    let weight_sum = w_open + w_high + w_low + w_close;
+        let (w_open, w_high, w_low, w_close) = weights;
+        let weight_sum = w_open + w_high + w_low + w_close;
     
-    if weight_sum <= 0.0 {
-        return Err(crate::common::TAError::invalid_parameter(
-            "weights",
-            "sum of weights must be positive"
-        ));
-    }
+        if weight_sum <= 0.0 {
+            return Err(crate::common::TAError::invalid_parameter(
+                "weights",
+                "sum of weights must be positive"
+            ));
+        }
 
-    let mut output = allocate_output(open.len());
+        let mut output = allocate_output(open.len());
     
-    // Calculate weighted average price for each period
-    for i in 0..open.len() {
-        output[i] = (open[i] * w_open + high[i] * w_high + low[i] * w_low + close[i] * w_close) / weight_sum;
-    }
+        // Calculate weighted average price for each period
+        for i in 0..open.len() {
+            output[i] = (open[i] * w_open + high[i] * w_high + low[i] * w_low + close[i] * w_close) / weight_sum;
+        }
 
-    Ok(output)
+        Ok(output)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
-    use crate::common::types::OHLC;
 
     #[test]
     fn test_avgprice_basic() {
@@ -246,7 +284,7 @@ mod tests {
 
     #[test]
     fn test_avgprice_empty_input() {
-        let open = vec![];
+        let open: Vec<Price> = vec![];
         let high = vec![];
         let low = vec![];
         let close = vec![];
@@ -1,7 +1,11 @@
 //! Median Price (MEDPRICE)
 
-use crate::common::{TAResult, Price};
-use crate::common::utils::{validate_not_empty, validate_same_length, allocate_output};
+use crate::common::{TAResult, Float, Price};
+use crate::common::errors::with_function;
+use crate::common::types::OHLC;
+use crate::common::utils::{validate_not_empty, validate_same_length};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Median Price (MEDPRICE)
 ///
@@ -35,20 +39,87 @@ use crate::common::utils::{validate_not_empty, validate_same_length, allocate_ou
 /// // result[0] = (12+9)/2 = 10.5
 /// assert_eq!(result[0], 10.5);
 /// ```
-pub fn medprice(high: &[Price], low: &[Price]) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_same_length(high, low, "high", "low")?;
+pub fn medprice<T: Float>(high: &[T], low: &[T]) -> TAResult<Vec<T>> {
+    with_function("MEDPRICE", || {
+        let mut output = vec![T::NAN; high.len()];
+        medprice_into(high, low, &mut output)?;
+        Ok(output)
+    })
+}
 
-    let mut output = allocate_output(high.len());
-    
-    // Calculate median price for each period
-    for i in 0..high.len() {
-        output[i] = (high[i] + low[i]) / 2.0;
-    }
+/// Calculates the Median Price into a caller-provided buffer
+///
+/// Allocation-free variant of [`medprice`]. `out` must have the same length as the inputs.
+///
+/// # Returns
+/// The number of values written, which is always the input length.
+pub fn medprice_into<T: Float>(high: &[T], low: &[T], out: &mut [T]) -> TAResult<usize> {
+    with_function("MEDPRICE", || {
+        // Input validation
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_same_length(high, low, "high", "low")?;
+        validate_same_length(high, out, "high", "out")?;
 
-    Ok(output)
+        // Calculate median price for each period
+        for (i, value) in out.iter_mut().enumerate() {
+            *value = (high[i] + low[i]) / T::from_f64(2.0);
+        }
+
+        Ok(high.len())
+    })
+}
+
+/// Calculates the Median Price from OHLC bars
+///
+/// Convenience function for when data is already in OHLC format.
+///
+/// # Parameters
+/// - `ohlc`: Slice of OHLC bars
+///
+/// # Returns
+/// Vector of median price values.
+///
+/// # Errors
+/// - `EmptyInput` if `ohlc` is empty
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLC;
+/// use ta_rust::price_transform::medprice_ohlc;
+///
+/// let bars = vec![OHLC::new(10.0, 12.0, 9.0, 11.0)];
+/// let result = medprice_ohlc(&bars).unwrap();
+/// // result[0] = (12+9)/2
+/// assert_eq!(result[0], (12.0 + 9.0) / 2.0);
+/// ```
+pub fn medprice_ohlc(ohlc: &[OHLC]) -> TAResult<Vec<Price>> {
+    with_function("MEDPRICE", || {
+        validate_not_empty(ohlc, "ohlc")?;
+        Ok(medprice_iter(ohlc).collect())
+    })
+}
+
+/// Lazily calculates the Median Price of each bar
+///
+/// Yields one value per bar, without allocating; bars may be [`OHLC`] or
+/// [`OHLCV`](crate::common::types::OHLCV), owned or borrowed.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLCV;
+/// use ta_rust::price_transform::medprice_iter;
+///
+/// let bars = vec![OHLCV::new(10.0, 12.0, 9.0, 11.0, 1000.0)];
+/// let highest = medprice_iter(&bars).fold(f64::MIN, f64::max);
+/// assert_eq!(highest, (12.0 + 9.0) / 2.0);
+/// ```
+pub fn medprice_iter<I>(bars: I) -> impl Iterator<Item = Price>
+where
+    I: IntoIterator,
+    I::Item: AsRef<OHLC>,
+{
+    bars.into_iter().map(|bar| bar.as_ref().median_price())
 }
 
 #[cfg(test)]
@@ -105,9 +176,22 @@ mod tests {
 
     #[test]
     fn test_medprice_empty_input() {
-        let high = vec![];
+        let high: Vec<f64> = vec![];
         let low = vec![];
         
         assert!(medprice(&high, &low).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_medprice_bars_match_columns() {
+        let bars = vec![OHLC::new(10.0, 12.0, 9.0, 11.0), OHLC::new(11.0, 13.0, 10.0, 12.5)];
+        let high: Vec<f64> = bars.iter().map(|b| b.high).collect();
+        let low: Vec<f64> = bars.iter().map(|b| b.low).collect();
+
+        let columns = medprice(&high, &low).unwrap();
+        assert_arrays_approx_equal(&medprice_ohlc(&bars).unwrap(), &columns, DEFAULT_TOLERANCE);
+        let owned: Vec<f64> = medprice_iter(bars.clone()).collect();
+        assert_arrays_approx_equal(&owned, &columns, DEFAULT_TOLERANCE);
+        assert!(medprice_ohlc(&[]).is_err());
+    }
+}
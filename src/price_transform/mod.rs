@@ -1,15 +1,25 @@
 //! Price Transform Functions
 //!
 //! This module contains functions that transform price data into different
-//! representations, such as typical price, weighted close price, etc.
+//! representations, such as typical price, weighted close price, etc., as well as
+//! the time-independent Renko and Point and Figure chart constructions and the
+//! conversion of prices to returns.
 
 pub mod avgprice;
 pub mod medprice;
+pub mod point_figure;
+pub mod price_returns;
+pub mod renko;
 pub mod typprice;
 pub mod wclprice;
 
 // Re-export all functions for convenient access
-pub use avgprice::{avgprice, avgprice_from_ohlc, avgprice_weighted};
-pub use medprice::medprice;
-pub use typprice::typprice;
-pub use wclprice::wclprice;
\ No newline at end of file
+pub use avgprice::{avgprice, avgprice_into, avgprice_from_ohlc, avgprice_iter, avgprice_weighted};
+pub use medprice::{medprice, medprice_into, medprice_iter, medprice_ohlc};
+pub use point_figure::{point_figure, PnfColumn};
+pub use price_returns::returns;
+pub use crate::performance::{cumulative_returns, ReturnKind};
+pub use crate::volatility::drawdown;
+pub use renko::{renko, renko_atr, Renko};
+pub use typprice::{typprice, typprice_into, typprice_iter, typprice_ohlc};
+pub use wclprice::{wclprice, wclprice_into, wclprice_iter, wclprice_ohlc};
\ No newline at end of file
@@ -0,0 +1,180 @@
+//! Point and Figure Columns
+//!
+//! Point and Figure charts stack boxes on a fixed price grid in alternating columns of X
+//! (rising) and O (falling). A column extends while price keeps moving in its direction and
+//! a new column starts only after a reversal of several boxes.
+
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// A single Point and Figure column
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PnfColumn {
+    /// `true` for an X (rising) column, `false` for an O (falling) column
+    pub rising: bool,
+    /// Price of the highest box
+    pub high: f64,
+    /// Price of the lowest box
+    pub low: f64,
+    /// Number of boxes in the column
+    pub boxes: usize,
+    /// Index of the source bar that started the column
+    pub start: usize,
+    /// Index of the last source bar that extended the column
+    pub end: usize,
+}
+
+/// Point and Figure columns using the high/low method
+///
+/// Boxes sit on multiples of `box_size`. The midpoint of the first finite bar, rounded down
+/// to the grid, is the starting level; the first column is drawn once price moves a full box
+/// away from it. Each bar first tries to extend the current column (with the high for X, the
+/// low for O) and only if it cannot, checks for a reversal of `reversal` boxes with the
+/// other extreme. For close-only charts pass the closes as both `high` and `low`.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `box_size` - Box height in price units (positive)
+/// * `reversal` - Boxes needed to start a new column (at least 1, typically 3)
+///
+/// # Returns
+/// * `Ok(Vec<PnfColumn>)` - Columns in chart order, possibly empty if price never moves a
+///   full box; bars with non-finite prices are skipped
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::price_transform::point_figure;
+///
+/// let high = vec![10.2, 11.5, 12.8, 12.1, 10.3];
+/// let low = vec![9.8, 10.6, 11.9, 11.2, 9.6];
+/// let columns = point_figure(&high, &low, 1.0, 2).unwrap();
+/// assert_eq!(columns.len(), 2);
+/// assert!(columns[0].rising && columns[0].high == 12.0);
+/// assert!(!columns[1].rising && columns[1].low == 10.0);
+/// ```
+pub fn point_figure(high: &[f64], low: &[f64], box_size: f64, reversal: usize) -> TAResult<Vec<PnfColumn>> {
+    with_function("POINT_FIGURE", || {
+        if high.is_empty() || low.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if high.len() != low.len() {
+            return Err(TAError::mismatched_inputs("High and low arrays must have the same length"));
+        }
+
+        if !box_size.is_finite() || box_size <= 0.0 {
+            return Err(TAError::invalid_parameter("box_size", "must be a positive finite number"));
+        }
+
+        if reversal == 0 {
+            return Err(TAError::invalid_parameter("reversal", "must be at least 1"));
+        }
+
+        // Prices are tracked as box numbers on the grid to avoid drift from repeated addition
+        let level = |price: f64| (price / box_size).floor() as i64;
+        let ceil_level = |price: f64| (price / box_size).ceil() as i64;
+        let reversal = reversal as i64;
+
+        let mut columns: Vec<(bool, i64, i64, usize, usize)> = Vec::new();
+        let mut start_level: Option<i64> = None;
+
+        for (i, (&h, &l)) in high.iter().zip(low).enumerate() {
+            if !h.is_finite() || !l.is_finite() {
+                continue;
+            }
+
+            let Some(origin) = start_level else {
+                start_level = Some(level((h + l) / 2.0));
+                continue;
+            };
+
+            let Some((rising, top, bottom, _, end)) = columns.last_mut() else {
+                // Until the first column, a full box either way starts it
+                if level(h) > origin {
+                    columns.push((true, level(h), origin + 1, i, i));
+                } else if ceil_level(l) < origin {
+                    columns.push((false, origin - 1, ceil_level(l), i, i));
+                }
+                continue;
+            };
+
+            if *rising {
+                if level(h) > *top {
+                    *top = level(h);
+                    *end = i;
+                } else if ceil_level(l) <= *top - reversal {
+                    let top = *top;
+                    columns.push((false, top - 1, ceil_level(l), i, i));
+                }
+            } else if ceil_level(l) < *bottom {
+                *bottom = ceil_level(l);
+                *end = i;
+            } else if level(h) >= *bottom + reversal {
+                let bottom = *bottom;
+                columns.push((true, level(h), bottom + 1, i, i));
+            }
+        }
+
+        Ok(columns
+            .into_iter()
+            .map(|(rising, top, bottom, start, end)| PnfColumn {
+                rising,
+                high: top as f64 * box_size,
+                low: bottom as f64 * box_size,
+                boxes: (top - bottom + 1) as usize,
+                start,
+                end,
+            })
+            .collect())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_figure_columns() {
+        // Close-only chart with 1.0 boxes and 3-box reversal, starting at 100
+        let close = vec![100.2, 101.4, 103.1, 101.5, 100.2, 99.8, 98.7, 100.9, 102.0];
+        let columns = point_figure(&close, &close, 1.0, 3).unwrap();
+
+        assert_eq!(columns.len(), 3);
+        // X from 101 to 103
+        assert_eq!(columns[0], PnfColumn { rising: true, high: 103.0, low: 101.0, boxes: 3, start: 1, end: 2 });
+        // 101.5 and 100.2 are short of the 3-box reversal, 99.8 is not
+        assert_eq!(columns[1], PnfColumn { rising: false, high: 102.0, low: 99.0, boxes: 4, start: 5, end: 6 });
+        assert_eq!(columns[2], PnfColumn { rising: true, high: 102.0, low: 100.0, boxes: 3, start: 8, end: 8 });
+    }
+
+    #[test]
+    fn test_point_figure_extension_takes_priority() {
+        // Bar 2 both extends the X column and dips far enough to reverse it
+        let high = vec![50.5, 52.3, 53.4];
+        let low = vec![49.5, 51.0, 48.0];
+        let columns = point_figure(&high, &low, 1.0, 3).unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].high, 53.0);
+        assert_eq!(columns[0].end, 2);
+    }
+
+    #[test]
+    fn test_point_figure_no_columns() {
+        let close = vec![f64::NAN, 10.1, 10.4, 10.9];
+        assert!(point_figure(&close, &close, 1.0, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_point_figure_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0];
+        assert!(point_figure(&[], &[], 1.0, 3).is_err());
+        assert!(point_figure(&data, &data[..2], 1.0, 3).is_err());
+        assert!(point_figure(&data, &data, 0.0, 3).is_err());
+        assert!(point_figure(&data, &data, 1.0, 0).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Price Returns
+//!
+//! Converts prices to per-bar returns. Statistics such as variance, correlation or beta
+//! describe returns far better than raw prices, whose level drifts.
+//!
+//! The conversions themselves live in the performance module: [`returns`] selects
+//! [`simple_returns`] or [`log_returns`] by [`ReturnKind`], and [`cumulative_returns`] and
+//! [`drawdown`](crate::volatility::drawdown) are re-exported alongside it.
+
+use crate::common::TAResult;
+use crate::common::errors::with_function;
+use crate::performance::{log_returns, simple_returns, ReturnKind};
+#[cfg(doc)]
+use crate::performance::cumulative_returns;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Calculates the return of each bar from the previous one
+///
+/// # Formula
+/// ```text
+/// Simple: Return[i] = Price[i] / Price[i-1] - 1
+/// Log:    Return[i] = ln(Price[i] / Price[i-1])
+/// ```
+///
+/// # Arguments
+/// * `prices` - Slice of prices
+/// * `kind` - Simple or log returns
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Returns as fractions; the first value is NaN, as is any return from
+///   or to a NaN price
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::price_transform::{returns, ReturnKind};
+///
+/// let simple = returns(&[100.0, 110.0, 99.0], ReturnKind::Simple).unwrap();
+/// assert!(simple[0].is_nan());
+/// assert!((simple[1] - 0.1).abs() < 1e-12);
+/// assert!((simple[2] + 0.1).abs() < 1e-12);
+///
+/// let log = returns(&[100.0, 110.0], ReturnKind::Log).unwrap();
+/// assert!((log[1] - 1.1_f64.ln()).abs() < 1e-12);
+/// ```
+pub fn returns(prices: &[f64], kind: ReturnKind) -> TAResult<Vec<f64>> {
+    with_function("RETURNS", || match kind {
+        ReturnKind::Simple => simple_returns(prices),
+        ReturnKind::Log => log_returns(prices),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ErrorKind;
+
+    #[test]
+    fn test_returns_delegate() {
+        let prices = [100.0, 105.0, f64::NAN, 98.0, 120.0];
+        let simple = returns(&prices, ReturnKind::Simple).unwrap();
+        let log = returns(&prices, ReturnKind::Log).unwrap();
+        for (a, b) in simple.iter().zip(simple_returns(&prices).unwrap()) {
+            assert!(a.to_bits() == b.to_bits());
+        }
+        for (a, b) in log.iter().zip(log_returns(&prices).unwrap()) {
+            assert!(a.to_bits() == b.to_bits());
+        }
+
+        let err = returns(&[], ReturnKind::Simple).unwrap_err();
+        assert_eq!(err.function(), Some("RETURNS"));
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
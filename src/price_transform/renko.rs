@@ -0,0 +1,228 @@
+//! Renko Bricks
+//!
+//! Renko charts discard time and draw a fixed-size brick whenever price moves a full brick
+//! beyond the last one. Continuing a trend takes one brick of movement; reversing takes two,
+//! as the new brick starts from the far side of the last one.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::volatility::atr;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Renko brick series
+///
+/// Bricks are stored as parallel vectors so the series can be fed straight into other
+/// indicators, e.g. `sma(&bricks.close, 10)`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Renko {
+    /// Price at which each brick starts
+    pub open: Vec<f64>,
+    /// Price at which each brick ends; above `open` for up bricks, below for down bricks
+    pub close: Vec<f64>,
+    /// Index of the source bar that completed each brick; a bar may complete several
+    pub index: Vec<usize>,
+}
+
+impl Renko {
+    /// Number of bricks
+    pub fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    /// Whether no brick was formed
+    pub fn is_empty(&self) -> bool {
+        self.close.is_empty()
+    }
+
+    /// Whether brick `i` is an up brick
+    pub fn is_up(&self, i: usize) -> bool {
+        self.close[i] > self.open[i]
+    }
+}
+
+/// Renko bricks with a fixed brick size
+///
+/// The first finite close is the starting level. Each bar extends the chart by as many
+/// bricks as its close has moved beyond the last brick, so gaps produce several bricks with
+/// the same source index.
+///
+/// # Formula
+/// ```text
+/// Up brick:   close ≥ top + size      → [top, top + size]
+/// Down brick: close ≤ bottom - size   → [bottom, bottom - size]
+/// ```
+/// where `top` and `bottom` are the edges of the last brick.
+///
+/// # Arguments
+/// * `close` - Close prices; non-finite values are skipped
+/// * `brick_size` - Brick height in price units (positive)
+///
+/// # Returns
+/// * `Ok(Renko)` - Brick series, possibly empty if price never moves a full brick
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::price_transform::renko;
+///
+/// let close = vec![10.0, 10.4, 11.1, 12.3, 11.6, 9.9];
+/// let bricks = renko(&close, 1.0).unwrap();
+/// assert_eq!(bricks.close, vec![11.0, 12.0, 10.0]);
+/// assert_eq!(bricks.index, vec![2, 3, 5]);
+/// ```
+pub fn renko(close: &[f64], brick_size: f64) -> TAResult<Renko> {
+    with_function("RENKO", || {
+        if close.is_empty() {
+            return Err(TAError::invalid_input("Close prices cannot be empty"));
+        }
+
+        if !brick_size.is_finite() || brick_size <= 0.0 {
+            return Err(TAError::invalid_parameter("brick_size", "must be a positive finite number"));
+        }
+
+        Ok(build(close, |_| brick_size))
+    })
+}
+
+/// Renko bricks sized by the Average True Range
+///
+/// Brick size follows `ATR × multiplier` at the bar being processed, so no future data is
+/// used; bricks formed in calmer stretches are smaller than those formed in volatile ones.
+/// Bars before the ATR is available only set the starting level.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `period` - ATR period (typically 14)
+/// * `multiplier` - Brick size in ATRs (positive, typically 1.0)
+///
+/// # Returns
+/// * `Ok(Renko)` - Brick series, possibly empty if price never moves a full brick
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::price_transform::renko_atr;
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + i as f64).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+/// let bricks = renko_atr(&high, &low, &close, 14, 1.0).unwrap();
+/// assert!(!bricks.is_empty());
+/// assert!((0..bricks.len()).all(|i| bricks.is_up(i)));
+/// ```
+pub fn renko_atr(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, multiplier: f64) -> TAResult<Renko> {
+    with_function("RENKO", || {
+        let period = period.into_param("period")?.get();
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(TAError::invalid_parameter("multiplier", "must be a positive finite number"));
+        }
+
+        let range = atr(high, low, close, period)?;
+        Ok(build(close, |i| range[i] * multiplier))
+    })
+}
+
+/// Lays bricks over `close` with the brick size of each bar given by `size`; bars whose size
+/// is not a positive number form no bricks
+fn build(close: &[f64], size: impl Fn(usize) -> f64) -> Renko {
+    let mut bricks = Renko {
+        open: Vec::new(),
+        close: Vec::new(),
+        index: Vec::new(),
+    };
+
+    // Edges of the last brick; both equal the starting level until the first brick
+    let mut edges: Option<(f64, f64)> = None;
+
+    for (i, &price) in close.iter().enumerate() {
+        if !price.is_finite() {
+            continue;
+        }
+
+        let Some((bottom, top)) = edges.as_mut() else {
+            edges = Some((price, price));
+            continue;
+        };
+
+        let size = size(i);
+        if !(size > 0.0 && size.is_finite()) {
+            continue;
+        }
+
+        while price >= *top + size {
+            bricks.open.push(*top);
+            bricks.close.push(*top + size);
+            bricks.index.push(i);
+            *bottom = *top;
+            *top += size;
+        }
+
+        while price <= *bottom - size {
+            bricks.open.push(*bottom);
+            bricks.close.push(*bottom - size);
+            bricks.index.push(i);
+            *top = *bottom;
+            *bottom -= size;
+        }
+    }
+
+    bricks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_renko_trend_and_reversal() {
+        let close = vec![100.0, 101.0, 102.5, 101.5, 100.9, 99.0, 101.2];
+        let bricks = renko(&close, 1.0).unwrap();
+
+        // Up to 102; pulling back to 100.9 is short of the two bricks needed to reverse
+        assert_eq!(bricks.open, vec![100.0, 101.0, 101.0, 100.0, 100.0]);
+        assert_eq!(bricks.close, vec![101.0, 102.0, 100.0, 99.0, 101.0]);
+        assert_eq!(bricks.index, vec![1, 2, 5, 5, 6]);
+        assert!(bricks.is_up(1) && !bricks.is_up(2) && bricks.is_up(4));
+        assert_eq!(crate::transform::renko(&close, 1.0).unwrap(), bricks);
+    }
+
+    #[test]
+    fn test_renko_gap_makes_several_bricks() {
+        let close = vec![f64::NAN, 50.0, 50.4, 53.7, f64::NAN, 49.0];
+        let bricks = renko(&close, 1.0).unwrap();
+        assert_eq!(bricks.close, vec![51.0, 52.0, 53.0, 51.0, 50.0, 49.0]);
+        assert_eq!(bricks.index, vec![3, 3, 3, 5, 5, 5]);
+
+        let flat = renko(&[5.0, 5.2, 4.9], 1.0).unwrap();
+        assert!(flat.is_empty());
+    }
+
+    #[test]
+    fn test_renko_atr_uses_current_atr() {
+        let close: Vec<f64> = (0..30).map(|i| 100.0 + i as f64 * 0.8).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let range = atr(&high, &low, &close, 5).unwrap();
+
+        let bricks = renko_atr(&high, &low, &close, 5, 1.0).unwrap();
+        assert!(bricks.index.iter().all(|&i| i >= 4));
+        for b in 0..bricks.len() {
+            assert_float_eq!(bricks.close[b] - bricks.open[b], range[bricks.index[b]], 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_renko_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0];
+        assert!(renko(&[], 1.0).is_err());
+        assert!(renko(&data, 0.0).is_err());
+        assert!(renko(&data, f64::NAN).is_err());
+        assert!(renko_atr(&data, &data, &data, 2, 0.0).is_err());
+        assert!(renko_atr(&data, &data, &data[..2], 2, 1.0).is_err());
+    }
+}
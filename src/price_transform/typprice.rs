@@ -1,7 +1,11 @@
 //! Typical Price (TYPPRICE)
 
-use crate::common::{TAResult, Price};
-use crate::common::utils::{validate_not_empty, validate_same_length, allocate_output};
+use crate::common::{TAResult, Float, Price};
+use crate::common::errors::with_function;
+use crate::common::types::OHLC;
+use crate::common::utils::{validate_not_empty, validate_same_length, validate_hlc_with, HlcValidation};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Typical Price (TYPPRICE)
 ///
@@ -30,7 +34,7 @@ use crate::common::utils::{validate_not_empty, validate_same_length, allocate_ou
 /// ```rust
 /// use ta_rust::price_transform::typprice;
 ///
-/// let high = vec![12.0, 13.0, 14.0];
+/// let high: Vec<f64> = vec![12.0, 13.0, 14.0];
 /// let low = vec![9.0, 10.0, 11.0];
 /// let close = vec![11.0, 12.0, 13.0];
 /// 
@@ -38,22 +42,88 @@ use crate::common::utils::{validate_not_empty, validate_same_length, allocate_ou
 /// // result[0] = (12+9+11)/3 = 10.667
 /// assert!((result[0] - 10.666666666666666).abs() < 1e-10);
 /// ```
-pub fn typprice(high: &[Price], low: &[Price], close: &[Price]) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_not_empty(close, "close")?;
-    validate_same_length(high, low, "high", "low")?;
-    validate_same_length(high, close, "high", "close")?;
-
-    let mut output = allocate_output(high.len());
-    
-    // Calculate typical price for each period
-    for i in 0..high.len() {
-        output[i] = (high[i] + low[i] + close[i]) / 3.0;
-    }
+pub fn typprice<T: Float>(high: &[T], low: &[T], close: &[T]) -> TAResult<Vec<T>> {
+    with_function("TYPPRICE", || {
+        let mut output = vec![T::NAN; high.len()];
+        typprice_into(high, low, close, &mut output)?;
+        Ok(output)
+    })
+}
+
+/// Calculates the Typical Price into a caller-provided buffer
+///
+/// Allocation-free variant of [`typprice`]. `out` must have the same length as the inputs.
+///
+/// # Returns
+/// The number of values written, which is always the input length.
+pub fn typprice_into<T: Float>(high: &[T], low: &[T], close: &[T], out: &mut [T]) -> TAResult<usize> {
+    with_function("TYPPRICE", || {
+        // Input validation
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_not_empty(close, "close")?;
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        validate_same_length(high, out, "high", "out")?;
 
-    Ok(output)
+        // Calculate typical price for each period
+        for (i, value) in out.iter_mut().enumerate() {
+            *value = (high[i] + low[i] + close[i]) / T::from_f64(3.0);
+        }
+
+        Ok(high.len())
+    })
+}
+
+/// Calculates the Typical Price from OHLC bars
+///
+/// Convenience function for when data is already in OHLC format.
+///
+/// # Parameters
+/// - `ohlc`: Slice of OHLC bars
+///
+/// # Returns
+/// Vector of typical price values.
+///
+/// # Errors
+/// - `EmptyInput` if `ohlc` is empty
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLC;
+/// use ta_rust::price_transform::typprice_ohlc;
+///
+/// let bars = vec![OHLC::new(10.0, 12.0, 9.0, 11.0)];
+/// let result = typprice_ohlc(&bars).unwrap();
+/// // result[0] = (12+9+11)/3
+/// assert_eq!(result[0], (12.0 + 9.0 + 11.0) / 3.0);
+/// ```
+pub fn typprice_ohlc(ohlc: &[OHLC]) -> TAResult<Vec<Price>> {
+    with_function("TYPPRICE", || {
+        validate_not_empty(ohlc, "ohlc")?;
+        Ok(typprice_iter(ohlc).collect())
+    })
+}
+
+/// Lazily calculates the Typical Price of each bar
+///
+/// Yields one value per bar, without allocating; bars may be [`OHLC`] or
+/// [`OHLCV`](crate::common::types::OHLCV), owned or borrowed.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLCV;
+/// use ta_rust::price_transform::typprice_iter;
+///
+/// let bars = vec![OHLCV::new(10.0, 12.0, 9.0, 11.0, 1000.0)];
+/// let highest = typprice_iter(&bars).fold(f64::MIN, f64::max);
+/// assert_eq!(highest, (12.0 + 9.0 + 11.0) / 3.0);
+/// ```
+pub fn typprice_iter<I>(bars: I) -> impl Iterator<Item = Price>
+where
+    I: IntoIterator,
+    I::Item: AsRef<OHLC>,
+{
+    bars.into_iter().map(|bar| bar.as_ref().typical_price())
 }
 
 #[cfg(test)]
@@ -114,10 +184,36 @@ mod tests {
 
     #[test]
     fn test_typprice_empty_input() {
-        let high = vec![];
+        let high: Vec<f64> = vec![];
         let low = vec![];
         let close = vec![];
         
         assert!(typprice(&high, &low, &close).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_typprice_into() {
+        let high = vec![12.0, 13.0, 14.0];
+        let low = vec![9.0, 10.0, 11.0];
+        let close = vec![11.0, 12.0, 13.0];
+        let mut out = [0.0; 3];
+        
+        assert_eq!(typprice_into(&high, &low, &close, &mut out).unwrap(), 3);
+        assert_arrays_approx_equal(&out, &typprice(&high, &low, &close).unwrap(), DEFAULT_TOLERANCE);
+        assert!(typprice_into(&high, &low, &close, &mut out[..2]).is_err());
+    }
+
+    #[test]
+    fn test_typprice_bars_match_columns() {
+        let bars = vec![OHLC::new(10.0, 12.0, 9.0, 11.0), OHLC::new(11.0, 13.0, 10.0, 12.5)];
+        let high: Vec<f64> = bars.iter().map(|b| b.high).collect();
+        let low: Vec<f64> = bars.iter().map(|b| b.low).collect();
+        let close: Vec<f64> = bars.iter().map(|b| b.close).collect();
+
+        let columns = typprice(&high, &low, &close).unwrap();
+        assert_arrays_approx_equal(&typprice_ohlc(&bars).unwrap(), &columns, DEFAULT_TOLERANCE);
+        let owned: Vec<f64> = typprice_iter(bars.clone()).collect();
+        assert_arrays_approx_equal(&owned, &columns, DEFAULT_TOLERANCE);
+        assert!(typprice_ohlc(&[]).is_err());
+    }
+}
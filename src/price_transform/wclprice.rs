@@ -1,7 +1,11 @@
 //! Weighted Close Price (WCLPRICE)
 
-use crate::common::{TAResult, Price};
-use crate::common::utils::{validate_not_empty, validate_same_length, allocate_output};
+use crate::common::{TAResult, Float, Price};
+use crate::common::errors::with_function;
+use crate::common::types::OHLC;
+use crate::common::utils::{validate_not_empty, validate_same_length, validate_hlc_with, HlcValidation};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates the Weighted Close Price (WCLPRICE)
 ///
@@ -38,22 +42,88 @@ use crate::common::utils::{validate_not_empty, validate_same_length, allocate_ou
 /// // result[0] = (12+9+2*11)/4 = 10.75
 /// assert_eq!(result[0], 10.75);
 /// ```
-pub fn wclprice(high: &[Price], low: &[Price], close: &[Price]) -> TAResult<Vec<Price>> {
-    // Input validation
-    validate_not_empty(high, "high")?;
-    validate_not_empty(low, "low")?;
-    validate_not_empty(close, "close")?;
-    validate_same_length(high, low, "high", "low")?;
-    validate_same_length(high, close, "high", "close")?;
-
-    let mut output = allocate_output(high.len());
-    
-    // Calculate weighted close price for each period
-    for i in 0..high.len() {
-        output[i] = (high[i] + low[i] + 2.0 * close[i]) / 4.0;
-    }
+pub fn wclprice<T: Float>(high: &[T], low: &[T], close: &[T]) -> TAResult<Vec<T>> {
+    with_function("WCLPRICE", || {
+        let mut output = vec![T::NAN; high.len()];
+        wclprice_into(high, low, close, &mut output)?;
+        Ok(output)
+    })
+}
+
+/// Calculates the Weighted Close Price into a caller-provided buffer
+///
+/// Allocation-free variant of [`wclprice`]. `out` must have the same length as the inputs.
+///
+/// # Returns
+/// The number of values written, which is always the input length.
+pub fn wclprice_into<T: Float>(high: &[T], low: &[T], close: &[T], out: &mut [T]) -> TAResult<usize> {
+    with_function("WCLPRICE", || {
+        // Input validation
+        validate_not_empty(high, "high")?;
+        validate_not_empty(low, "low")?;
+        validate_not_empty(close, "close")?;
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        validate_same_length(high, out, "high", "out")?;
+
+        // Calculate weighted close price for each period
+        for (i, value) in out.iter_mut().enumerate() {
+            *value = (high[i] + low[i] + T::from_f64(2.0) * close[i]) / T::from_f64(4.0);
+        }
+
+        Ok(high.len())
+    })
+}
+
+/// Calculates the Weighted Close Price from OHLC bars
+///
+/// Convenience function for when data is already in OHLC format.
+///
+/// # Parameters
+/// - `ohlc`: Slice of OHLC bars
+///
+/// # Returns
+/// Vector of weighted close price values.
+///
+/// # Errors
+/// - `EmptyInput` if `ohlc` is empty
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLC;
+/// use ta_rust::price_transform::wclprice_ohlc;
+///
+/// let bars = vec![OHLC::new(10.0, 12.0, 9.0, 11.0)];
+/// let result = wclprice_ohlc(&bars).unwrap();
+/// // result[0] = (12+9+2×11)/4
+/// assert_eq!(result[0], (12.0 + 9.0 + 2.0 * 11.0) / 4.0);
+/// ```
+pub fn wclprice_ohlc(ohlc: &[OHLC]) -> TAResult<Vec<Price>> {
+    with_function("WCLPRICE", || {
+        validate_not_empty(ohlc, "ohlc")?;
+        Ok(wclprice_iter(ohlc).collect())
+    })
+}
 
-    Ok(output)
+/// Lazily calculates the Weighted Close Price of each bar
+///
+/// Yields one value per bar, without allocating; bars may be [`OHLC`] or
+/// [`OHLCV`](crate::common::types::OHLCV), owned or borrowed.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::common::types::OHLCV;
+/// use ta_rust::price_transform::wclprice_iter;
+///
+/// let bars = vec![OHLCV::new(10.0, 12.0, 9.0, 11.0, 1000.0)];
+/// let highest = wclprice_iter(&bars).fold(f64::MIN, f64::max);
+/// assert_eq!(highest, (12.0 + 9.0 + 2.0 * 11.0) / 4.0);
+/// ```
+pub fn wclprice_iter<I>(bars: I) -> impl Iterator<Item = Price>
+where
+    I: IntoIterator,
+    I::Item: AsRef<OHLC>,
+{
+    bars.into_iter().map(|bar| bar.as_ref().weighted_close_price())
 }
 
 #[cfg(test)]
@@ -128,10 +198,24 @@ mod tests {
 
     #[test]
     fn test_wclprice_empty_input() {
-        let high = vec![];
+        let high: Vec<f64> = vec![];
         let low = vec![];
         let close = vec![];
         
         assert!(wclprice(&high, &low, &close).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_wclprice_bars_match_columns() {
+        let bars = vec![OHLC::new(10.0, 12.0, 9.0, 11.0), OHLC::new(11.0, 13.0, 10.0, 12.5)];
+        let high: Vec<f64> = bars.iter().map(|b| b.high).collect();
+        let low: Vec<f64> = bars.iter().map(|b| b.low).collect();
+        let close: Vec<f64> = bars.iter().map(|b| b.close).collect();
+
+        let columns = wclprice(&high, &low, &close).unwrap();
+        assert_arrays_approx_equal(&wclprice_ohlc(&bars).unwrap(), &columns, DEFAULT_TOLERANCE);
+        let owned: Vec<f64> = wclprice_iter(bars.clone()).collect();
+        assert_arrays_approx_equal(&owned, &columns, DEFAULT_TOLERANCE);
+        assert!(wclprice_ohlc(&[]).is_err());
+    }
+}
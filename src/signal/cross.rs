@@ -0,0 +1,173 @@
+//! Crossovers
+//!
+//! Detects bars where one series crosses another, or crosses a fixed level. A cross is
+//! registered on the bar where the order changes, comparing it with the bar before, so both
+//! bars must have finite values: NaN warm-up periods and gaps never produce a signal.
+
+use crate::common::{crossovers, TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::common::utils::crossings;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Bars where `a` crosses above `b`
+///
+/// # Formula
+/// ```text
+/// CrossOver[i] = a[i-1] ≤ b[i-1] and a[i] > b[i]
+/// ```
+///
+/// # Arguments
+/// * `a` - Crossing series, e.g. a fast moving average
+/// * `b` - Series being crossed, e.g. a slow moving average
+///
+/// # Returns
+/// * `Ok(Vec<bool>)` - `true` on each bar where `a` crosses above `b`; the first bar is
+///   always `false`
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::signal::cross_over;
+///
+/// let fast = vec![1.0, 2.0, 3.0, 2.0];
+/// let slow = vec![2.0, 2.0, 2.0, 2.0];
+/// assert_eq!(cross_over(&fast, &slow).unwrap(), vec![false, false, true, false]);
+/// ```
+pub fn cross_over(a: &[f64], b: &[f64]) -> TAResult<Vec<bool>> {
+    with_function("CROSS_OVER", || {
+        Ok(crosses(a, b)?.into_iter().map(|c| c == 1).collect())
+    })
+}
+
+/// Bars where `a` crosses below `b`
+///
+/// # Formula
+/// ```text
+/// CrossUnder[i] = a[i-1] ≥ b[i-1] and a[i] < b[i]
+/// ```
+///
+/// # Arguments
+/// * `a` - Crossing series
+/// * `b` - Series being crossed
+///
+/// # Returns
+/// * `Ok(Vec<bool>)` - `true` on each bar where `a` crosses below `b`; the first bar is
+///   always `false`
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::signal::cross_under;
+///
+/// let fast = vec![3.0, 2.0, 1.0, 2.0];
+/// let slow = vec![2.0, 2.0, 2.0, 2.0];
+/// assert_eq!(cross_under(&fast, &slow).unwrap(), vec![false, false, true, false]);
+/// ```
+pub fn cross_under(a: &[f64], b: &[f64]) -> TAResult<Vec<bool>> {
+    with_function("CROSS_UNDER", || {
+        Ok(crosses(a, b)?.into_iter().map(|c| c == -1).collect())
+    })
+}
+
+/// Crossings of `a` and `b` in both directions
+///
+/// Combines [`cross_over`] and [`cross_under`] in a single pass; the same as
+/// [`crossovers`](crate::common::crossovers) after checking the inputs.
+///
+/// # Arguments
+/// * `a` - Crossing series
+/// * `b` - Series being crossed
+///
+/// # Returns
+/// * `Ok(Vec<i8>)` - 1 where `a` crosses above `b`, -1 where it crosses below, 0 otherwise
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn crosses(a: &[f64], b: &[f64]) -> TAResult<Vec<i8>> {
+    with_function("CROSSES", || {
+        if a.is_empty() || b.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if a.len() != b.len() {
+            return Err(TAError::mismatched_inputs("Input arrays must have the same length"));
+        }
+
+        Ok(crossovers(a, b))
+    })
+}
+
+/// Crossings of a fixed level
+///
+/// Typical uses are an oscillator leaving overbought/oversold zones or a MACD histogram
+/// changing sign.
+///
+/// # Arguments
+/// * `series` - Input series
+/// * `level` - Level being crossed
+///
+/// # Returns
+/// * `Ok(Vec<i8>)` - 1 where `series` crosses above `level`, -1 where it crosses below, 0
+///   otherwise
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::signal::threshold_cross;
+///
+/// let rsi = vec![f64::NAN, 65.0, 72.0, 75.0, 68.0, 71.0];
+/// assert_eq!(threshold_cross(&rsi, 70.0).unwrap(), vec![0, 0, 1, 0, -1, 1]);
+/// ```
+pub fn threshold_cross(series: &[f64], level: f64) -> TAResult<Vec<i8>> {
+    with_function("THRESHOLD_CROSS", || {
+        if series.is_empty() {
+            return Err(TAError::invalid_input("Input array cannot be empty"));
+        }
+
+        if !level.is_finite() {
+            return Err(TAError::invalid_parameter("level", "must be a finite number"));
+        }
+
+        Ok(crossings(series, |_| level))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crosses_touch_counts_once() {
+        // Touching the other series and then moving through it is a single cross
+        let a = vec![1.0, 2.0, 2.0, 3.0, 2.0, 1.0];
+        let b = vec![2.0; 6];
+        assert_eq!(crosses(&a, &b).unwrap(), vec![0, 0, 0, 1, 0, -1]);
+        assert_eq!(cross_over(&a, &b).unwrap(), vec![false, false, false, true, false, false]);
+        assert_eq!(cross_under(&a, &b).unwrap(), vec![false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn test_crosses_nan_handling() {
+        let a = vec![f64::NAN, 1.0, 3.0, f64::NAN, 1.0, 3.0];
+        let b = vec![2.0, 2.0, 2.0, 2.0, 2.0, f64::NAN];
+        // Only bar 2 has finite values on both bars of the comparison
+        assert_eq!(crosses(&a, &b).unwrap(), vec![0, 0, 1, 0, 0, 0]);
+
+        let series = vec![f64::NAN, 40.0, f64::NAN, 60.0, 45.0];
+        assert_eq!(threshold_cross(&series, 50.0).unwrap(), vec![0, 0, 0, 0, -1]);
+    }
+
+    #[test]
+    fn test_threshold_cross_matches_crosses() {
+        let series: Vec<f64> = (0..50).map(|i| (i as f64 * 0.4).sin()).collect();
+        let zero = vec![0.0; 50];
+        assert_eq!(threshold_cross(&series, 0.0).unwrap(), crosses(&series, &zero).unwrap());
+    }
+
+    #[test]
+    fn test_crosses_invalid_input() {
+        assert!(cross_over(&[], &[]).is_err());
+        assert!(cross_under(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(threshold_cross(&[], 1.0).is_err());
+        assert!(threshold_cross(&[1.0], f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Divergence Detection
+//!
+//! A divergence is a disagreement between price and an oscillator across two consecutive
+//! swings. Regular divergences (price makes a new extreme the oscillator does not confirm)
+//! warn of a reversal; hidden divergences (the oscillator makes the new extreme instead)
+//! point to trend continuation. Works with any oscillator: RSI, MACD, OBV, etc.
+
+use crate::common::{IntoParam, Period, TAError, TAResult};
+use crate::common::errors::with_function;
+use crate::pattern::fractals::pivots;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Kind of divergence between price and oscillator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DivergenceKind {
+    /// Price makes a lower low, the oscillator a higher low
+    RegularBullish,
+    /// Price makes a higher high, the oscillator a lower high
+    RegularBearish,
+    /// Price makes a higher low, the oscillator a lower low
+    HiddenBullish,
+    /// Price makes a lower high, the oscillator a higher high
+    HiddenBearish,
+}
+
+impl DivergenceKind {
+    /// Whether the divergence points to rising prices
+    pub fn is_bullish(self) -> bool {
+        matches!(self, DivergenceKind::RegularBullish | DivergenceKind::HiddenBullish)
+    }
+}
+
+/// A divergence between two price swings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Divergence {
+    /// Bar index of the earlier swing
+    pub start: usize,
+    /// Bar index of the later swing; the event is known `pivot_width` bars after it
+    pub end: usize,
+    /// Kind of divergence
+    pub kind: DivergenceKind,
+}
+
+/// Detect divergences between price and an oscillator
+///
+/// Swings are pivots of `price`: a bar whose value is above (for highs) or below (for lows)
+/// the `pivot_width` bars on each side, with ties going to the earliest bar. Each pivot is
+/// compared with the previous pivot of the same type if it lies within `max_lookback` bars,
+/// using the oscillator values at the two pivot bars.
+///
+/// Since a pivot needs `pivot_width` bars after it, a divergence ending at bar `end` can
+/// only be acted on at bar `end + pivot_width`.
+///
+/// # Arguments
+/// * `price` - Price series, usually closes
+/// * `oscillator` - Oscillator series aligned with `price`
+/// * `pivot_width` - Bars on each side of a pivot (at least 1, typically 3 to 5)
+/// * `max_lookback` - Maximum distance in bars between the two swings
+///
+/// # Returns
+/// * `Ok(Vec<Divergence>)` - Divergences ordered by `end`; pivots where either series is
+///   non-finite are ignored
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::signal::{divergence, DivergenceKind};
+///
+/// // Price makes a higher high at bar 7 while the oscillator peaks lower
+/// let price = vec![10.0, 11.0, 12.0, 11.0, 10.5, 11.5, 12.5, 13.0, 12.0, 11.0];
+/// let oscillator = vec![50.0, 60.0, 70.0, 60.0, 55.0, 58.0, 62.0, 65.0, 55.0, 45.0];
+/// let events = divergence(&price, &oscillator, 2, 20).unwrap();
+///
+/// assert_eq!(events.len(), 1);
+/// assert_eq!((events[0].start, events[0].end), (2, 7));
+/// assert_eq!(events[0].kind, DivergenceKind::RegularBearish);
+/// ```
+pub fn divergence(
+    price: &[f64],
+    oscillator: &[f64],
+    pivot_width: impl IntoParam<Period>,
+    max_lookback: impl IntoParam<Period>,
+) -> TAResult<Vec<Divergence>> {
+    with_function("DIVERGENCE", || {
+        let pivot_width = pivot_width.into_param("pivot_width")?.get();
+        let max_lookback = max_lookback.into_param("max_lookback")?.get();
+        if price.is_empty() || oscillator.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if price.len() != oscillator.len() {
+            return Err(TAError::mismatched_inputs("Price and oscillator must have the same length"));
+        }
+
+        let mut events = Vec::new();
+        for is_high in [true, false] {
+            let swings = pivots(price, pivot_width, is_high)
+                .into_iter()
+                .filter(|&i| oscillator[i].is_finite());
+
+            let mut previous: Option<usize> = None;
+            for current in swings {
+                if let Some(prev) = previous.filter(|&prev| current - prev <= max_lookback) {
+                    let price_up = price[current] > price[prev];
+                    let price_down = price[current] < price[prev];
+                    let osc_up = oscillator[current] > oscillator[prev];
+                    let osc_down = oscillator[current] < oscillator[prev];
+
+                    let kind = match (is_high, price_up && osc_down, price_down && osc_up) {
+                        (true, true, _) => Some(DivergenceKind::RegularBearish),
+                        (true, _, true) => Some(DivergenceKind::HiddenBearish),
+                        (false, _, true) => Some(DivergenceKind::RegularBullish),
+                        (false, true, _) => Some(DivergenceKind::HiddenBullish),
+                        _ => None,
+                    };
+
+                    if let Some(kind) = kind {
+                        events.push(Divergence { start: prev, end: current, kind });
+                    }
+                }
+                previous = Some(current);
+            }
+        }
+
+        events.sort_by_key(|event| (event.end, event.start));
+        Ok(events)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::rsi;
+
+    #[test]
+    fn test_pivots() {
+        let series = vec![1.0, 3.0, 2.0, 2.0, 5.0, 5.0, 4.0, 1.0, 2.0];
+        assert_eq!(pivots(&series, 1, true), vec![1, 4]);
+        assert_eq!(pivots(&series, 1, false), vec![2, 7]);
+        assert_eq!(pivots(&series, 2, true), vec![4]);
+        assert!(pivots(&series[..2], 1, true).is_empty());
+    }
+
+    #[test]
+    fn test_divergence_kinds() {
+        // Troughs at bars 2, 6, 10 and peaks at bars 4, 8
+        let price = vec![10.0, 9.0, 8.0, 9.0, 11.0, 9.5, 7.5, 9.5, 12.0, 10.0, 8.5, 9.5, 10.5];
+        let oscillator = vec![50.0, 40.0, 30.0, 45.0, 70.0, 45.0, 35.0, 50.0, 65.0, 40.0, 25.0, 40.0, 50.0];
+        let events = divergence(&price, &oscillator, 1, 10).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                // Lower low in price, higher low in the oscillator
+                Divergence { start: 2, end: 6, kind: DivergenceKind::RegularBullish },
+                // Higher high in price, lower high in the oscillator
+                Divergence { start: 4, end: 8, kind: DivergenceKind::RegularBearish },
+                // Higher low in price, lower low in the oscillator
+                Divergence { start: 6, end: 10, kind: DivergenceKind::HiddenBullish },
+            ]
+        );
+        assert!(events[0].kind.is_bullish() && !events[1].kind.is_bullish());
+    }
+
+    #[test]
+    fn test_divergence_hidden_bearish_and_lookback() {
+        let price = vec![10.0, 12.0, 10.0, 9.0, 11.0, 9.0];
+        let oscillator = vec![50.0, 60.0, 50.0, 40.0, 70.0, 40.0];
+        let events = divergence(&price, &oscillator, 1, 5).unwrap();
+        assert_eq!(events, vec![Divergence { start: 1, end: 4, kind: DivergenceKind::HiddenBearish }]);
+
+        // Swings further apart than the lookback are not compared
+        assert!(divergence(&price, &oscillator, 1, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_divergence_with_rsi_warmup() {
+        let price: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.05).collect();
+        let rsi_values = rsi(&price, 14).unwrap();
+        let events = divergence(&price, &rsi_values, 3, 40).unwrap();
+
+        // Pivots inside the RSI warm-up have no oscillator value and are skipped
+        assert!(events.iter().all(|e| e.start >= 14));
+        assert!(events.windows(2).all(|w| w[0].end <= w[1].end));
+    }
+
+    #[test]
+    fn test_divergence_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0];
+        assert!(divergence(&[], &[], 1, 5).is_err());
+        assert!(divergence(&data, &data[..2], 1, 5).is_err());
+        assert!(divergence(&data, &data, 0, 5).is_err());
+        assert!(divergence(&data, &data, 1, 0).is_err());
+    }
+}
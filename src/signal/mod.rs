@@ -0,0 +1,10 @@
+//! Signal Utilities
+//!
+//! Functions that turn indicator outputs into trading events, such as crossovers and
+//! divergences between price and an oscillator.
+
+pub mod cross;
+pub mod divergence;
+
+pub use cross::*;
+pub use divergence::*;
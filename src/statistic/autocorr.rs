@@ -0,0 +1,112 @@
+//! Rolling Autocorrelation
+//!
+//! Correlation of a series with a lagged copy of itself over a rolling window. Positive
+//! values indicate persistence (trending behaviour), negative values mean reversion.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::correl;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rolling Autocorrelation
+///
+/// Pearson correlation between `x[t]` and `x[t-lag]` over the pairs that fit inside each
+/// window of `period` bars (the same definition as pandas' `Series.autocorr` applied to a
+/// rolling window). Usually applied to returns rather than prices.
+///
+/// # Formula
+/// ```text
+/// ACF(lag) = CORREL(x[t], x[t-lag]) over t in the window, (period - lag) pairs
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `period` - Window length
+/// * `lag` - Lag in bars (at least 1, and at least 2 pairs must fit in the window)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of autocorrelations in [-1, 1]; the first `period-1` values are
+///   NaN, as are windows where either leg has no variance
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::autocorr;
+///
+/// // Alternating returns are perfectly anti-correlated at lag 1
+/// let returns: Vec<f64> = (0..20).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+/// let result = autocorr(&returns, 10, 1).unwrap();
+/// assert!((result[9] + 1.0).abs() < 1e-12);
+/// ```
+pub fn autocorr(data: &[f64], period: impl IntoParam<Period>, lag: usize) -> TAResult<Vec<f64>> {
+    with_function("AUTOCORR", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Input data cannot be empty"));
+        }
+
+        if lag == 0 {
+            return Err(TAError::invalid_parameter("lag", "must be greater than 0"));
+        }
+
+        if period < lag + 2 {
+            return Err(TAError::invalid_parameter("period", "must exceed lag by at least 2"));
+        }
+
+        let len = data.len();
+        if period > len {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        // Pair k of the shifted series is (x[k + lag], x[k]); it ends the window at k + lag
+        let lagged = correl(&data[lag..], &data[..len - lag], period - lag)?;
+
+        let mut result = vec![f64::NAN; len];
+        result[lag..].copy_from_slice(&lagged);
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_autocorr_matches_direct_pearson() {
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.4).sin() + (i as f64 * 1.3).cos() * 0.5).collect();
+        let (period, lag) = (12, 3);
+        let result = autocorr(&data, period, lag).unwrap();
+
+        assert!(result[..period - 1].iter().all(|v| v.is_nan()));
+        for i in (period - 1)..data.len() {
+            let start = i + 1 - period;
+            let x = &data[start + lag..=i];
+            let y = &data[start..=i - lag];
+            let mx = x.iter().sum::<f64>() / x.len() as f64;
+            let my = y.iter().sum::<f64>() / y.len() as f64;
+            let sxy: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+            let sxx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+            let syy: f64 = y.iter().map(|b| (b - my).powi(2)).sum();
+            assert_float_eq!(result[i], sxy / (sxx * syy).sqrt(), 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_autocorr_trending_returns() {
+        // Slowly varying returns are strongly positively autocorrelated
+        let returns: Vec<f64> = (0..50).map(|i| (i as f64 * 0.1).sin()).collect();
+        let result = autocorr(&returns, 20, 1).unwrap();
+        assert!(result[19..].iter().all(|&v| v > 0.9));
+    }
+
+    #[test]
+    fn test_autocorr_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(autocorr(&[], 3, 1).is_err());
+        assert!(autocorr(&data, 3, 0).is_err());
+        assert!(autocorr(&data, 3, 2).is_err());
+        assert!(autocorr(&data, 6, 1).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Average Deviation (AVGDEV)
+//!
+//! The average absolute deviation of each value from the mean of its window. It is a
+//! dispersion measure like the standard deviation, but less sensitive to outliers.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Average Deviation
+///
+/// Calculates the mean absolute deviation over a rolling window.
+///
+/// # Formula
+/// ```text
+/// AVGDEV = Σ|x[i] - μ| / n
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `period` - Period for the calculation (typically 14)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of average deviation values; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::avgdev;
+///
+/// let data = vec![2.0, 4.0, 6.0, 8.0];
+/// let result = avgdev(&data, 4).unwrap();
+/// // Mean 5, absolute deviations 3, 1, 1, 3
+/// assert_eq!(result[3], 2.0);
+/// ```
+pub fn avgdev(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("AVGDEV", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
+
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
+
+        let mut result = vec![f64::NAN; data.len()];
+        for (out, window) in result[period - 1..].iter_mut().zip(data.windows(period)) {
+            let mean = window.iter().sum::<f64>() / period as f64;
+            *out = window.iter().map(|&x| (x - mean).abs()).sum::<f64>() / period as f64;
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_avgdev_basic() {
+        let data = vec![1.0, 2.0, 3.0, 10.0, 5.0];
+        let result = avgdev(&data, 3).unwrap();
+
+        assert!(result[0].is_nan());
+        assert!(result[1].is_nan());
+        assert_float_eq!(result[2], 2.0 / 3.0, 1e-12);
+        // Window 2, 3, 10: mean 5, deviations 3, 2, 5
+        assert_float_eq!(result[3], 10.0 / 3.0, 1e-12);
+        // Window 3, 10, 5: mean 6, deviations 3, 4, 1
+        assert_float_eq!(result[4], 8.0 / 3.0, 1e-12);
+    }
+
+    #[test]
+    fn test_avgdev_invalid_input() {
+        assert!(avgdev(&[], 3).is_err());
+        assert!(avgdev(&[1.0, 2.0], 0).is_err());
+        assert!(avgdev(&[1.0, 2.0], 3).is_err());
+    }
+}
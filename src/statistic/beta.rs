@@ -4,7 +4,12 @@
 //! compared to the market as a whole. It's calculated as the covariance between
 //! the security and market returns divided by the variance of market returns.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::comoments::rolling_comoments;
+use crate::performance::simple_returns;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Beta Coefficient
 ///
@@ -12,6 +17,9 @@ use crate::common::{TAError, TAResult};
 /// A beta of 1 indicates the security moves with the market, greater than 1
 /// indicates higher volatility, and less than 1 indicates lower volatility.
 ///
+/// Running sums are maintained across the window, so the cost per bar is
+/// constant regardless of `period`.
+///
 /// # Formula
 /// ```text
 /// β = Covariance(Security, Market) / Variance(Market)
@@ -38,62 +46,33 @@ use crate::common::{TAError, TAResult};
 /// let market = vec![1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1018.0, 1025.0, 1030.0, 1028.0, 1035.0];
 /// let result = beta(&security, &market, 5).unwrap();
 /// ```
-pub fn beta(security_prices: &[f64], market_prices: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if security_prices.is_empty() || market_prices.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
-    
-    if security_prices.len() != market_prices.len() {
-        return Err(TAError::mismatched_inputs("Security and market arrays must have the same length"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
-    
-    if period > security_prices.len() {
-        return Err(TAError::insufficient_data(period, security_prices.len()));
-    }
-    
-    let len = security_prices.len();
-    let mut result = vec![f64::NAN; len];
-    
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let security_window = &security_prices[start_idx..=i];
-        let market_window = &market_prices[start_idx..=i];
-        
-        // Calculate means
-        let security_mean = security_window.iter().sum::<f64>() / period as f64;
-        let market_mean = market_window.iter().sum::<f64>() / period as f64;
-        
-        // Calculate covariance and variance
-        let mut covariance = 0.0;
-        let mut market_variance = 0.0;
-        
-        for j in 0..period {
-            let security_diff = security_window[j] - security_mean;
-            let market_diff = market_window[j] - market_mean;
-            
-            covariance += security_diff * market_diff;
-            market_variance += market_diff * market_diff;
+pub fn beta(security_prices: &[f64], market_prices: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("BETA", || {
+        let period = period.into_param("period")?.get();
+        if security_prices.is_empty() || market_prices.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
         }
-        
-        // Use sample covariance and variance (divide by n-1)
-        if period > 1 {
-            covariance /= (period - 1) as f64;
-            market_variance /= (period - 1) as f64;
+    
+        if security_prices.len() != market_prices.len() {
+            return Err(TAError::mismatched_inputs("Security and market arrays must have the same length"));
         }
-        
-        // Calculate beta
-        if market_variance.abs() > f64::EPSILON {
-            result[i] = covariance / market_variance;
-        } else {
-            result[i] = f64::NAN; // Undefined when market has no variance
+    
+        if period > security_prices.len() {
+            return Err(TAError::insufficient_data(period, security_prices.len()));
         }
-    }
     
-    Ok(result)
+        let mut result = vec![f64::NAN; security_prices.len()];
+        rolling_comoments(security_prices, market_prices, period, |i, m| {
+            // The (n-1) divisors of the sample covariance and variance cancel out, so the
+            // co-moment and second moment can be used directly. Undefined when the market
+            // has no variance
+            if m.y_varies {
+                result[i] = m.sxy / m.syy;
+            }
+        });
+    
+        Ok(result)
+    })
 }
 
 /// Beta using returns instead of prices
@@ -109,9 +88,12 @@ pub fn beta(security_prices: &[f64], market_prices: &[f64], period: usize) -> TA
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of beta coefficients
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn beta_returns(security_returns: &[f64], market_returns: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    // This is the same calculation as beta() but using returns directly
-    beta(security_returns, market_returns, period)
+pub fn beta_returns(security_returns: &[f64], market_returns: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("BETA", || {
+        let period = period.into_param("period")?.get();
+        // This is the same calculation as beta() but using returns directly
+        beta(security_returns, market_returns, period)
+    })
 }
 
 /// Calculate rolling beta with price-to-return conversion
@@ -127,39 +109,55 @@ pub fn beta_returns(security_returns: &[f64], market_returns: &[f64], period: us
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of beta coefficients
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn beta_from_prices(security_prices: &[f64], market_prices: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if security_prices.len() < 2 || market_prices.len() < 2 {
-        return Err(TAError::invalid_input("Need at least 2 price points to calculate returns"));
-    }
-    
-    // Convert prices to returns
-    let mut security_returns = Vec::with_capacity(security_prices.len() - 1);
-    let mut market_returns = Vec::with_capacity(market_prices.len() - 1);
-    
-    for i in 1..security_prices.len() {
-        if security_prices[i - 1].abs() > f64::EPSILON {
-            security_returns.push((security_prices[i] - security_prices[i - 1]) / security_prices[i - 1]);
-        } else {
-            security_returns.push(f64::NAN);
+pub fn beta_from_prices(security_prices: &[f64], market_prices: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("BETA", || {
+        let period = period.into_param("period")?.get();
+        if security_prices.len() < 2 || market_prices.len() < 2 {
+            return Err(TAError::invalid_input("Need at least 2 price points to calculate returns"));
         }
-        
-        if market_prices[i - 1].abs() > f64::EPSILON {
-            market_returns.push((market_prices[i] - market_prices[i - 1]) / market_prices[i - 1]);
-        } else {
-            market_returns.push(f64::NAN);
-        }
-    }
     
-    // Calculate beta on returns
-    let beta_result = beta(&security_returns, &market_returns, period)?;
+        let security_returns = simple_returns(security_prices)?;
+        let market_returns = simple_returns(market_prices)?;
     
-    // Pad with NaN at the beginning to match original length
-    let mut result = vec![f64::NAN; security_prices.len()];
-    for i in 0..beta_result.len() {
-        result[i + 1] = beta_result[i];
-    }
+        // Calculate beta on returns
+        let beta_result = beta(&security_returns[1..], &market_returns[1..], period)?;
+    
+        // Pad with NaN at the beginning to match original length
+        let mut result = vec![f64::NAN; security_prices.len()];
+        result[1..=beta_result.len()].copy_from_slice(&beta_result);
     
-    Ok(result)
+        Ok(result)
+    })
+}
+
+/// Beta of the returns of `security_prices` against those of `market_prices`, as TA-Lib
+/// computes it
+///
+/// Unlike [`beta_from_prices`], a window where the market barely moves gives 0 rather than
+/// NaN: TA-Lib treats a denominator `n·Σ(x - x̄)²` below 1e-8 as zero, and a return from a
+/// zero price as 0.
+pub(crate) fn beta_talib(security_prices: &[f64], market_prices: &[f64], period: usize) -> TAResult<Vec<f64>> {
+    with_function("BETA", || {
+        if security_prices.len() != market_prices.len() {
+            return Err(TAError::mismatched_inputs("Security and market arrays must have the same length"));
+        }
+        if security_prices.len() <= period {
+            return Err(TAError::insufficient_data(period + 1, security_prices.len()));
+        }
+
+        let returns = |prices: &[f64]| -> Vec<f64> {
+            prices.windows(2).map(|w| if w[0] == 0.0 { 0.0 } else { (w[1] - w[0]) / w[0] }).collect()
+        };
+        let (security_returns, market_returns) = (returns(security_prices), returns(market_prices));
+
+        let mut result = vec![f64::NAN; security_prices.len()];
+        let n = period as f64;
+        rolling_comoments(&security_returns, &market_returns, period, |i, m| {
+            result[i + 1] = if (n * m.syy).abs() < 1e-8 { 0.0 } else { m.sxy / m.syy };
+        });
+
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
@@ -325,4 +323,40 @@ mod tests {
         let _has_variation = valid_values.iter().any(|&x| (x - first_val).abs() > 1e-10);
         // Note: might not have variation if data is very regular, so this is a soft check
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_beta_matches_windowed_calculation() {
+        let security: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 * 0.37).sin() * 5.0 + i as f64 * 0.1).collect();
+        let market: Vec<f64> = (0..200).map(|i| 1000.0 + (i as f64 * 0.21).cos() * 30.0 + i as f64 * 0.5).collect();
+        let period = 20;
+        let result = beta(&security, &market, period).unwrap();
+        
+        for i in (period - 1)..security.len() {
+            let ws = &security[i + 1 - period..=i];
+            let wm = &market[i + 1 - period..=i];
+            let ms = ws.iter().sum::<f64>() / period as f64;
+            let mm = wm.iter().sum::<f64>() / period as f64;
+            let cov: f64 = ws.iter().zip(wm).map(|(s, m)| (s - ms) * (m - mm)).sum();
+            let var: f64 = wm.iter().map(|m| (m - mm).powi(2)).sum();
+            assert_float_eq!(result[i], cov / var, 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_beta_talib_zero_market_variance() {
+        let security = vec![100.0, 102.0, 101.0, 103.0, 105.0, 104.0];
+        let market = vec![1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1018.0];
+        let result = beta_talib(&security, &market, 3).unwrap();
+        let prices = beta_from_prices(&security, &market, 3).unwrap();
+        assert!(result[..3].iter().all(|v| v.is_nan()));
+        for i in 3..6 {
+            assert_float_eq!(result[i], prices[i], 1e-12);
+        }
+
+        // A flat market has no defined beta, which TA-Lib reports as 0
+        let flat = beta_talib(&security, &[50.0; 6], 3).unwrap();
+        assert!(beta_from_prices(&security, &[50.0; 6], 3).unwrap()[3..].iter().all(|v| v.is_nan()));
+        assert_eq!(&flat[3..], &[0.0; 3]);
+        assert!(beta_talib(&security, &market, 6).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! CAPM Regression
+//!
+//! Regresses a security's returns on a benchmark's returns over a rolling window, giving
+//! the usual performance-attribution statistics together: beta (market exposure), alpha
+//! (return not explained by the benchmark), R² (share of the variance explained), tracking
+//! error and information ratio (size and reward of the active return). All five come from
+//! the same rolling co-moments as CORREL and BETA, so they are computed in one pass.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::comoments::rolling_comoments;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rolling CAPM result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capm {
+    /// Mean security return not explained by the benchmark, per bar
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub alpha: Vec<f64>,
+    /// Sensitivity of the security return to the benchmark return
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub beta: Vec<f64>,
+    /// Share of the security's return variance explained by the benchmark, in [0, 1]
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub r_squared: Vec<f64>,
+    /// Sample standard deviation of the active return (security - benchmark), per bar
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub tracking_error: Vec<f64>,
+    /// Mean active return divided by the tracking error, per bar
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub information_ratio: Vec<f64>,
+}
+
+/// Rolling CAPM regression of security returns on benchmark returns
+///
+/// Beta is the same as [`beta_returns`](crate::statistic::beta_returns). Alpha is the
+/// intercept of the regression; pass returns in excess of the risk-free rate to get
+/// Jensen's alpha. Tracking error and information ratio are per bar; multiply them by
+/// `√periods_per_year` to annualize.
+///
+/// # Formula
+/// ```text
+/// x = security, y = benchmark, a = x - y, sums over the window of n bars
+/// Sxx = Σ(x - x̄)²,  Syy = Σ(y - ȳ)²,  Sxy = Σ(x - x̄)(y - ȳ)
+/// β  = Sxy / Syy
+/// α  = x̄ - β × ȳ
+/// R² = Sxy² / (Sxx × Syy)
+/// TE = √((Sxx - 2 × Sxy + Syy) / (n - 1))
+/// IR = ā / TE
+/// ```
+///
+/// Windows containing non-finite values are NaN. Beta, alpha and R² are NaN for windows
+/// where the benchmark is flat, R² also where the security is flat, and the information
+/// ratio where the tracking error is zero.
+///
+/// # Arguments
+/// * `security_returns` - Slice of security returns
+/// * `benchmark_returns` - Slice of benchmark returns
+/// * `period` - Window length (at least 2)
+///
+/// # Returns
+/// * `Ok(Capm)` - Alpha, beta, R², tracking error and information ratio; the first
+///   `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::capm;
+///
+/// let market = vec![0.01, -0.02, 0.015, 0.003, -0.007, 0.012, 0.004, -0.01];
+/// let stock: Vec<f64> = market.iter().map(|m| 0.001 + 1.5 * m).collect();
+///
+/// let result = capm(&stock, &market, 5).unwrap();
+/// assert!((result.beta[7] - 1.5).abs() < 1e-9);
+/// assert!((result.alpha[7] - 0.001).abs() < 1e-9);
+/// assert!((result.r_squared[7] - 1.0).abs() < 1e-9);
+/// ```
+pub fn capm(security_returns: &[f64], benchmark_returns: &[f64], period: impl IntoParam<Period>) -> TAResult<Capm> {
+    with_function("CAPM", || {
+        let period = period.into_param("period")?.get();
+        if security_returns.is_empty() || benchmark_returns.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if security_returns.len() != benchmark_returns.len() {
+            return Err(TAError::mismatched_inputs("Security and benchmark arrays must have the same length"));
+        }
+
+        if period < 2 {
+            return Err(TAError::invalid_parameter("period", "must be at least 2"));
+        }
+
+        let len = security_returns.len();
+        if period > len {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let mut result = Capm {
+            alpha: vec![f64::NAN; len],
+            beta: vec![f64::NAN; len],
+            r_squared: vec![f64::NAN; len],
+            tracking_error: vec![f64::NAN; len],
+            information_ratio: vec![f64::NAN; len],
+        };
+        let n = period as f64;
+        rolling_comoments(security_returns, benchmark_returns, period, |i, m| {
+            if m.y_varies {
+                let beta = m.sxy / m.syy;
+                result.beta[i] = beta;
+                result.alpha[i] = m.mean_x - beta * m.mean_y;
+                if m.x_varies {
+                    result.r_squared[i] = (m.sxy * m.sxy / (m.sxx * m.syy)).min(1.0);
+                }
+            }
+
+            let active_variance = (m.sxx - 2.0 * m.sxy + m.syy).max(0.0) / (n - 1.0);
+            let tracking_error = active_variance.sqrt();
+            result.tracking_error[i] = tracking_error;
+            if tracking_error > 0.0 {
+                result.information_ratio[i] = (m.mean_x - m.mean_y) / tracking_error;
+            }
+        });
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::statistic::{beta_returns, correl};
+
+    fn returns() -> (Vec<f64>, Vec<f64>) {
+        let market: Vec<f64> = (0..80).map(|i| (i as f64 * 0.7).sin() * 0.01 + 0.0005).collect();
+        let stock = market
+            .iter()
+            .enumerate()
+            .map(|(i, m)| 0.0003 + 1.3 * m + (i as f64 * 1.9).cos() * 0.004)
+            .collect();
+        (stock, market)
+    }
+
+    #[test]
+    fn test_capm_matches_naive_window_statistics() {
+        let (stock, market) = returns();
+        let period = 20;
+        let result = capm(&stock, &market, period).unwrap();
+
+        assert_arrays_approx_equal(&result.beta, &beta_returns(&stock, &market, period).unwrap(), 1e-9);
+        let r = correl(&stock, &market, period).unwrap();
+        for i in (period - 1)..stock.len() {
+            assert_float_eq!(result.r_squared[i], r[i] * r[i], 1e-9);
+
+            let xs = &stock[i + 1 - period..=i];
+            let ys = &market[i + 1 - period..=i];
+            let mean = |v: &[f64]| v.iter().sum::<f64>() / period as f64;
+            assert_float_eq!(result.alpha[i], mean(xs) - result.beta[i] * mean(ys), 1e-12);
+
+            let active: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| x - y).collect();
+            let mean_active = mean(&active);
+            let te = (active.iter().map(|a| (a - mean_active).powi(2)).sum::<f64>() / (period - 1) as f64).sqrt();
+            assert_float_eq!(result.tracking_error[i], te, 1e-12);
+            assert_float_eq!(result.information_ratio[i], mean_active / te, 1e-8);
+        }
+        assert!(result.alpha[..period - 1].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_capm_degenerate_windows() {
+        let (mut stock, market) = returns();
+        stock[30] = f64::NAN;
+        let result = capm(&stock, &market, 10).unwrap();
+        assert!((30..40).all(|i| result.beta[i].is_nan() && result.tracking_error[i].is_nan()));
+        assert!(!result.beta[40].is_nan());
+
+        // Identical series: beta 1, no active return
+        let same = capm(&market, &market, 10).unwrap();
+        assert_float_eq!(same.beta[20], 1.0, 1e-12);
+        assert_float_eq!(same.tracking_error[20], 0.0, 1e-12);
+        assert!(same.information_ratio[20].is_nan());
+
+        // Flat benchmark
+        let flat = capm(&stock[..20], &[0.001; 20], 5).unwrap();
+        assert!(flat.beta[10].is_nan() && flat.r_squared[10].is_nan());
+        assert!(!flat.tracking_error[10].is_nan());
+
+        assert!(capm(&stock, &market, 1).is_err());
+        assert!(capm(&stock, &market[1..], 10).is_err());
+        assert!(capm(&stock[..5], &market[..5], 10).is_err());
+        assert!(capm(&[], &[], 10).is_err());
+    }
+}
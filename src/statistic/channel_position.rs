@@ -0,0 +1,114 @@
+//! Channel Position
+//!
+//! Where a value sits within a range, from 0 at the bottom to 1 at the top. Against the
+//! rolling minimum and maximum of a series this is a "stochastic of anything": applied to
+//! closes within the highs and lows it is the stochastic %K, and Williams' %R and
+//! Bollinger %B are the same position against other channels. [`range_position`] is the
+//! primitive those oscillators share; each chooses its own reading of a flat range.
+
+use crate::common::{TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data};
+use crate::math_operators::{max, min};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Ranges narrower than this are treated as flat
+const FLAT_RANGE: f64 = 1e-12;
+
+/// Position of `value` within `[low, high]` as a fraction
+///
+/// 0 at `low`, 1 at `high`, outside [0, 1] for values outside the range. NaN inputs give
+/// NaN.
+///
+/// # Returns
+/// * `Some(f64)` - The position
+/// * `None` - When the range is flat, narrower than 1e-12, so that no position exists
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::range_position;
+///
+/// assert_eq!(range_position(12.0, 10.0, 18.0), Some(0.25));
+/// assert_eq!(range_position(10.0, 10.0, 10.0), None);
+/// ```
+pub fn range_position(value: f64, low: f64, high: f64) -> Option<f64> {
+    let range = high - low;
+    if range.abs() < FLAT_RANGE {
+        None
+    } else {
+        Some((value - low) / range)
+    }
+}
+
+/// Rolling position of each value within the minimum-maximum range of its window
+///
+/// A window without any range, all values equal, puts the value in the middle at 0.5.
+/// Leading NaNs, such as the warm-up of an indicator given as input, are allowed; a bar
+/// whose window holds NaN gives NaN.
+///
+/// # Formula
+/// ```text
+/// Position = (x - min(x, period)) / (max(x, period) - min(x, period))
+/// ```
+///
+/// # Arguments
+/// * `series` - Input data series
+/// * `period` - Window length, including the current value
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of positions in [0, 1]; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::channel_position;
+///
+/// let data = vec![2.0, 4.0, 3.0, 6.0, 2.0, 2.0, 2.0];
+/// let result = channel_position(&data, 3).unwrap();
+/// assert_eq!(&result[2..], &[0.5, 1.0, 0.0, 0.0, 0.5]);
+/// ```
+pub fn channel_position(series: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("CHANNEL_POSITION", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(series, "series")?;
+        validate_sufficient_data(series, period, "series")?;
+
+        let highest = max(series, period)?;
+        let lowest = min(series, period)?;
+        let mut result = vec![f64::NAN; series.len()];
+        // NaN values in the current window
+        let mut nans = series[..period - 1].iter().filter(|x| x.is_nan()).count();
+        for i in period - 1..series.len() {
+            if series[i].is_nan() {
+                nans += 1;
+            }
+            if nans == 0 {
+                result[i] = range_position(series[i], lowest[i], highest[i]).unwrap_or(0.5);
+            }
+            if series[i + 1 - period].is_nan() {
+                nans -= 1;
+            }
+        }
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_position_nan_and_errors() {
+        let data = vec![f64::NAN, 1.0, 3.0, 2.0, f64::NAN, 4.0, 5.0, 4.5];
+        let result = channel_position(&data, 2).unwrap();
+        assert!(result[..2].iter().all(|v| v.is_nan()));
+        assert_eq!(&result[2..4], &[1.0, 0.0]);
+        assert!(result[4].is_nan() && result[5].is_nan());
+        assert_eq!(&result[6..], &[1.0, 0.0]);
+
+        assert!(channel_position(&data, 0).is_err());
+        assert!(channel_position(&data[..2], 3).is_err());
+        assert!(range_position(f64::NAN, 0.0, 1.0).unwrap().is_nan());
+    }
+}
@@ -0,0 +1,128 @@
+//! Rolling co-moments of two series
+//!
+//! Shared by the rolling statistics of two series (CORREL, BETA, COVAR, CAPM), which
+//! differ only in how they combine the second moments of each window.
+
+use crate::common::NeumaierSum;
+
+/// Second moments of one window of two series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CoMoments {
+    /// Mean of x over the window
+    pub mean_x: f64,
+    /// Mean of y over the window
+    pub mean_y: f64,
+    /// Co-moment Σ(x - x̄)(y - ȳ)
+    pub sxy: f64,
+    /// Second moment Σ(x - x̄)², at least 0
+    pub sxx: f64,
+    /// Second moment Σ(y - ȳ)², at least 0
+    pub syy: f64,
+    /// Whether x varies over the window, beyond the cancellation error of the sums
+    pub x_varies: bool,
+    /// Whether y varies over the window, beyond the cancellation error of the sums
+    pub y_varies: bool,
+}
+
+/// Calls `f` with the co-moments of every window of `period` bars, with the index of its
+/// last bar
+///
+/// Compensated running sums (Σx, Σy, Σxy, Σx², Σy²) are maintained across the windows, so
+/// the cost per bar is constant regardless of `period`. Windows holding a non-finite value
+/// in either series are skipped. `x` and `y` must have the same length and `period` must be
+/// at least 1.
+pub(crate) fn rolling_comoments(x: &[f64], y: &[f64], period: usize, mut f: impl FnMut(usize, CoMoments)) {
+    debug_assert!(x.len() == y.len() && period > 0);
+    let len = x.len();
+    let n = period as f64;
+
+    // Sums are of values shifted by the first valid bar, which leaves the co-moments
+    // unchanged but keeps Σx² and (Σx)²/n small enough not to cancel each other out
+    let first = (0..len).find(|&i| x[i].is_finite() && y[i].is_finite());
+    let (shift_x, shift_y) = first.map_or((0.0, 0.0), |i| (x[i], y[i]));
+
+    let mut sum_x = NeumaierSum::new();
+    let mut sum_y = NeumaierSum::new();
+    let mut sum_xy = NeumaierSum::new();
+    let mut sum_x2 = NeumaierSum::new();
+    let mut sum_y2 = NeumaierSum::new();
+    // Non-finite bars are kept out of the sums and make their windows undefined
+    let mut invalid = 0usize;
+
+    for i in 0..len {
+        let (dx, dy) = (x[i] - shift_x, y[i] - shift_y);
+        if dx.is_finite() && dy.is_finite() {
+            sum_x.add(dx);
+            sum_y.add(dy);
+            sum_xy.add(dx * dy);
+            sum_x2.add(dx * dx);
+            sum_y2.add(dy * dy);
+        } else {
+            invalid += 1;
+        }
+
+        if i + 1 < period {
+            continue;
+        }
+
+        if invalid == 0 {
+            let (sx, sy) = (sum_x.value(), sum_y.value());
+            let sxx = sum_x2.value() - sx * sx / n;
+            let syy = sum_y2.value() - sy * sy / n;
+            f(i, CoMoments {
+                mean_x: sx / n + shift_x,
+                mean_y: sy / n + shift_y,
+                sxy: sum_xy.value() - sx * sy / n,
+                sxx: sxx.max(0.0),
+                syy: syy.max(0.0),
+                // Relative to the window's magnitude to absorb cancellation in the sums
+                x_varies: sxx > f64::EPSILON * sum_x2.value(),
+                y_varies: syy > f64::EPSILON * sum_y2.value(),
+            });
+        }
+
+        // Drop the oldest bar so the sums cover the next window
+        let (old_x, old_y) = (x[i + 1 - period] - shift_x, y[i + 1 - period] - shift_y);
+        if old_x.is_finite() && old_y.is_finite() {
+            sum_x.sub(old_x);
+            sum_y.sub(old_y);
+            sum_xy.sub(old_x * old_y);
+            sum_x2.sub(old_x * old_x);
+            sum_y2.sub(old_y * old_y);
+        } else {
+            invalid -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_comoments_match_naive() {
+        let x: Vec<f64> = (0..40).map(|i| 1e6 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let mut y: Vec<f64> = (0..40).map(|i| (i as f64 * 0.9).cos() + i as f64 * 0.05).collect();
+        y[20] = f64::NAN;
+        let period = 6;
+
+        let mut ends = Vec::new();
+        rolling_comoments(&x, &y, period, |i, m| {
+            let (wx, wy) = (&x[i + 1 - period..=i], &y[i + 1 - period..=i]);
+            let mean_x = wx.iter().sum::<f64>() / period as f64;
+            let mean_y = wy.iter().sum::<f64>() / period as f64;
+            let sxy: f64 = wx.iter().zip(wy).map(|(a, b)| (a - mean_x) * (b - mean_y)).sum();
+            let sxx: f64 = wx.iter().map(|a| (a - mean_x).powi(2)).sum();
+            assert!((m.mean_x - mean_x).abs() < 1e-9 && (m.mean_y - mean_y).abs() < 1e-12);
+            assert!((m.sxy - sxy).abs() < 1e-9 && (m.sxx - sxx).abs() < 1e-9);
+            assert!(m.x_varies && m.y_varies);
+            ends.push(i);
+        });
+
+        // Windows holding the NaN at bar 20 are skipped
+        let expected: Vec<usize> = (period - 1..40).filter(|i| !(20..20 + period).contains(i)).collect();
+        assert_eq!(ends, expected);
+
+        rolling_comoments(&[5.0; 8], &y[..8], 4, |_, m| assert!(!m.x_varies && m.sxx == 0.0));
+    }
+}
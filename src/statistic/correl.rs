@@ -4,16 +4,24 @@
 //! Values range from -1 (perfect negative correlation) to +1 (perfect positive correlation),
 //! with 0 indicating no linear relationship.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::comoments::rolling_comoments;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Pearson's Correlation Coefficient
 ///
 /// Calculates the Pearson correlation coefficient between two data series over a rolling window.
 /// The correlation coefficient measures the strength and direction of the linear relationship.
 ///
+/// Running sums (Σx, Σy, Σxy, Σx², Σy²) are maintained across the window, so the cost per
+/// bar is constant regardless of `period`.
+///
 /// # Formula
 /// ```text
 /// r = Σ((x[i] - x̄)(y[i] - ȳ)) / √(Σ(x[i] - x̄)² × Σ(y[i] - ȳ)²)
+///   = (Σxy - ΣxΣy/n) / √((Σx² - (Σx)²/n) × (Σy² - (Σy)²/n))
 /// 
 /// Where x̄ and ȳ are means of x and y
 /// Range: -1 to +1
@@ -37,63 +45,53 @@ use crate::common::{TAError, TAResult};
 /// let result = correl(&series1, &series2, 5).unwrap();
 /// // Should show perfect positive correlation (1.0)
 /// ```
-pub fn correl(series1: &[f64], series2: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if series1.is_empty() || series2.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
-    
-    if series1.len() != series2.len() {
-        return Err(TAError::mismatched_inputs("Input arrays must have the same length"));
-    }
+pub fn correl(series1: &[f64], series2: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("CORREL", || {
+        let period = period.into_param("period")?.get();
+        if series1.is_empty() || series2.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+        if series1.len() != series2.len() {
+            return Err(TAError::mismatched_inputs("Input arrays must have the same length"));
+        }
     
-    if period > series1.len() {
-        return Err(TAError::insufficient_data(period, series1.len()));
-    }
+        if period > series1.len() {
+            return Err(TAError::insufficient_data(period, series1.len()));
+        }
     
-    let len = series1.len();
-    let mut result = vec![f64::NAN; len];
+        let mut result = vec![f64::NAN; series1.len()];
+        rolling_comoments(series1, series2, period, |i, m| {
+            // Undefined when one or both series have no variance
+            if m.x_varies && m.y_varies {
+                // Clamp to [-1, 1] to handle floating point precision issues
+                result[i] = (m.sxy / (m.sxx * m.syy).sqrt()).clamp(-1.0, 1.0);
+            }
+        });
     
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window1 = &series1[start_idx..=i];
-        let window2 = &series2[start_idx..=i];
-        
-        // Calculate means
-        let mean1 = window1.iter().sum::<f64>() / period as f64;
-        let mean2 = window2.iter().sum::<f64>() / period as f64;
-        
-        // Calculate correlation components
-        let mut numerator = 0.0;
-        let mut sum_sq1 = 0.0;
-        let mut sum_sq2 = 0.0;
-        
-        for j in 0..period {
-            let diff1 = window1[j] - mean1;
-            let diff2 = window2[j] - mean2;
-            
-            numerator += diff1 * diff2;
-            sum_sq1 += diff1 * diff1;
-            sum_sq2 += diff2 * diff2;
+        Ok(result)
+    })
+}
+
+/// Correlation as TA-Lib computes it: 0 rather than NaN when either series has no
+/// variance, TA-Lib treating a product of second moments below 1e-8 as zero
+pub(crate) fn correl_talib(series1: &[f64], series2: &[f64], period: usize) -> TAResult<Vec<f64>> {
+    with_function("CORREL", || {
+        if series1.len() != series2.len() {
+            return Err(TAError::mismatched_inputs("Input arrays must have the same length"));
         }
-        
-        // Calculate correlation coefficient
-        let denominator = (sum_sq1 * sum_sq2).sqrt();
-        
-        if denominator.abs() > f64::EPSILON {
-            result[i] = numerator / denominator;
-            
-            // Clamp to [-1, 1] to handle floating point precision issues
-            result[i] = result[i].max(-1.0).min(1.0);
-        } else {
-            result[i] = f64::NAN; // Undefined when one or both series have no variance
+        if period > series1.len() {
+            return Err(TAError::insufficient_data(period, series1.len()));
         }
-    }
-    
-    Ok(result)
+
+        let mut result = vec![f64::NAN; series1.len()];
+        rolling_comoments(series1, series2, period, |i, m| {
+            let spread = m.sxx * m.syy;
+            result[i] = if spread < 1e-8 { 0.0 } else { (m.sxy / spread.sqrt()).clamp(-1.0, 1.0) };
+        });
+
+        Ok(result)
+    })
 }
 
 /// Correlation with default period (20)
@@ -122,42 +120,45 @@ pub fn correl_default(series1: &[f64], series2: &[f64]) -> TAResult<Vec<f64>> {
 /// # Returns
 /// * `Ok(Vec<Vec<Vec<f64>>>)` - 3D vector: [time][series1][series2] = correlation
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn correl_matrix(series: &[Vec<f64>], period: usize) -> TAResult<Vec<Vec<Vec<f64>>>> {
-    if series.is_empty() {
-        return Err(TAError::invalid_input("Series array cannot be empty"));
-    }
+pub fn correl_matrix(series: &[Vec<f64>], period: impl IntoParam<Period>) -> TAResult<Vec<Vec<Vec<f64>>>> {
+    with_function("CORREL", || {
+        let period = period.into_param("period")?.get();
+        if series.is_empty() {
+            return Err(TAError::invalid_input("Series array cannot be empty"));
+        }
     
-    let num_series = series.len();
-    let len = series[0].len();
+        let num_series = series.len();
+        let len = series[0].len();
     
-    // Check all series have the same length
-    for s in series.iter() {
-        if s.len() != len {
-            return Err(TAError::mismatched_inputs("All series must have the same length"));
+        // Check all series have the same length
+        for s in series.iter() {
+            if s.len() != len {
+                return Err(TAError::mismatched_inputs("All series must have the same length"));
+            }
         }
-    }
     
-    let mut result = vec![vec![vec![f64::NAN; num_series]; num_series]; len];
+        let mut result = vec![vec![vec![f64::NAN; num_series]; num_series]; len];
     
-    // Calculate correlation for each pair
-    for i in 0..num_series {
-        for j in 0..num_series {
-            if i == j {
-                // Self-correlation is always 1.0 (where defined)
-                let _self_correl = vec![1.0; len];
-                for t in (period - 1)..len {
-                    result[t][i][j] = 1.0;
-                }
-            } else {
-                let correl_ij = correl(&series[i], &series[j], period)?;
-                for t in 0..len {
-                    result[t][i][j] = correl_ij[t];
+        // Calculate correlation for each pair
+        for i in 0..num_series {
+            for j in 0..num_series {
+                if i == j {
+                    // Self-correlation is always 1.0 (where defined)
+                    let _self_correl = vec![1.0; len];
+                    for row in &mut result[period - 1..] {
+                        row[i][j] = 1.0;
+                    }
+                } else {
+                    let correl_ij = correl(&series[i], &series[j], period)?;
+                    for t in 0..len {
+                        result[t][i][j] = correl_ij[t];
+                    }
                 }
             }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculate correlation strength categories
@@ -202,6 +203,7 @@ pub fn correl_strength(correlations: &[f64]) -> Vec<String> {
 mod tests {
     use super::*;
     use crate::assert_float_eq;
+    use crate::common::{fixed_point_prices, fixed_to_f64, FIXED_POINT_BITS};
     #[test]
     fn test_correl_perfect_positive() {
         let series1 = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
@@ -241,8 +243,11 @@ mod tests {
         let series2 = vec![1.0, 2.0, 3.0, 4.0, 5.0]; // Variable
         let result = correl(&series1, &series2, 5).unwrap();
         
-        // Correlation with constant series is undefined
+        // Correlation with constant series is undefined, which TA-Lib reports as 0
         assert!(result[4].is_nan());
+        assert_eq!(correl_talib(&series1, &series2, 5).unwrap()[4], 0.0);
+        let rising = correl_talib(&series2, &[2.0, 4.0, 6.0, 8.0, 10.0], 5).unwrap();
+        assert_float_eq!(rising[4], 1.0, 1e-12);
     }
 
     #[test]
@@ -393,4 +398,70 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_correl_matches_windowed_calculation() {
+        let series1: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 * 0.37).sin() * 5.0 + i as f64 * 0.1).collect();
+        let series2: Vec<f64> = (0..200).map(|i| 50.0 + (i as f64 * 0.21).cos() * 3.0 - i as f64 * 0.05).collect();
+        let period = 14;
+        let result = correl(&series1, &series2, period).unwrap();
+        
+        for i in (period - 1)..series1.len() {
+            let w1 = &series1[i + 1 - period..=i];
+            let w2 = &series2[i + 1 - period..=i];
+            let m1 = w1.iter().sum::<f64>() / period as f64;
+            let m2 = w2.iter().sum::<f64>() / period as f64;
+            let num: f64 = w1.iter().zip(w2).map(|(a, b)| (a - m1) * (b - m2)).sum();
+            let d1: f64 = w1.iter().map(|a| (a - m1).powi(2)).sum();
+            let d2: f64 = w2.iter().map(|b| (b - m2).powi(2)).sum();
+            assert_float_eq!(result[i], num / (d1 * d2).sqrt(), 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_correl_long_series_accuracy() {
+        // Prices around 10,000 make Σx² - (Σx)²/n cancel heavily and magnify any drift of the
+        // running sums (plain sums of unshifted prices are off by about 6e-4 here); compare
+        // with correlations from exact integer window sums
+        let period = 500;
+        let (fx, fy) = (fixed_point_prices(300_000, 3), fixed_point_prices(300_000, 5));
+        let x: Vec<f64> = fx.iter().map(|&p| fixed_to_f64(p, FIXED_POINT_BITS)).collect();
+        let y: Vec<f64> = fy.iter().map(|&p| fixed_to_f64(p, FIXED_POINT_BITS)).collect();
+        let result = correl(&x, &y, period).unwrap();
+
+        let n = period as i128;
+        let mut max_error: f64 = 0.0;
+        for i in (period - 1..x.len()).step_by(997) {
+            let window = i + 1 - period..=i;
+            // Values relative to the window start keep the integer products within i128
+            let (x0, y0) = (fx[i + 1 - period], fy[i + 1 - period]);
+            let (mut sx, mut sy, mut sxy, mut sxx, mut syy) = (0i128, 0i128, 0i128, 0i128, 0i128);
+            for k in window {
+                let (a, b) = (fx[k] - x0, fy[k] - y0);
+                sx += a;
+                sy += b;
+                sxy += a * b;
+                sxx += a * a;
+                syy += b * b;
+            }
+            let cov = (n * sxy - sx * sy) as f64;
+            let exact = cov / (((n * sxx - sx * sx) as f64) * ((n * syy - sy * sy) as f64)).sqrt();
+            max_error = max_error.max((result[i] - exact).abs());
+        }
+        assert!(max_error < 1e-10, "absolute error {}", max_error);
+    }
+
+    #[test]
+    fn test_correl_nan_only_affects_its_windows() {
+        let mut series1: Vec<f64> = (0..20).map(|i| (i as f64 * 0.7).sin()).collect();
+        let series2: Vec<f64> = (0..20).map(|i| (i as f64 * 0.3).cos()).collect();
+        series1[5] = f64::NAN;
+        let result = correl(&series1, &series2, 4).unwrap();
+        
+        assert!(result[5..9].iter().all(|v| v.is_nan()));
+        let expected = correl(&series1[6..], &series2[6..], 4).unwrap();
+        for i in 9..20 {
+            assert_float_eq!(result[i], expected[i - 6], 1e-10);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Rolling Covariance
+//!
+//! Sample covariance between two series over a rolling window, and the full covariance
+//! matrix of a set of series for portfolio-level risk calculations.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::statistic::comoments::rolling_comoments;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rolling Covariance
+///
+/// Running sums are maintained across the window, so the cost per bar is constant
+/// regardless of `period`.
+///
+/// # Formula
+/// ```text
+/// Cov = Σ((x[i] - x̄)(y[i] - ȳ)) / (n - 1)
+///     = (Σxy - ΣxΣy/n) / (n - 1)
+/// ```
+///
+/// # Arguments
+/// * `series1` - First data series
+/// * `series2` - Second data series
+/// * `period` - Window length (at least 2)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of sample covariances; the first `period-1` values are NaN, as
+///   are windows containing non-finite values
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::covariance;
+///
+/// let x = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+/// let y = vec![2.0, 4.0, 6.0, 8.0, 10.0];
+/// let result = covariance(&x, &y, 3).unwrap();
+/// // Var(x) = 1 over each window and y = 2x
+/// assert!((result[4] - 2.0).abs() < 1e-12);
+/// ```
+pub fn covariance(series1: &[f64], series2: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("COVAR", || {
+        let period = period.into_param("period")?.get();
+        if series1.is_empty() || series2.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if series1.len() != series2.len() {
+            return Err(TAError::mismatched_inputs("Input arrays must have the same length"));
+        }
+
+        if period < 2 {
+            return Err(TAError::invalid_parameter("period", "must be at least 2"));
+        }
+
+        if period > series1.len() {
+            return Err(TAError::insufficient_data(period, series1.len()));
+        }
+
+        let mut result = vec![f64::NAN; series1.len()];
+        let n = period as f64;
+        rolling_comoments(series1, series2, period, |i, m| result[i] = m.sxy / (n - 1.0));
+
+        Ok(result)
+    })
+}
+
+/// Calculate covariance matrix for multiple series
+///
+/// Returns the rolling covariances between all pairs of input series; the diagonal holds
+/// each series' sample variance. The matrix is symmetric, so each pair is computed once.
+///
+/// # Arguments
+/// * `series` - Vector of data series
+/// * `period` - Window length (at least 2)
+///
+/// # Returns
+/// * `Ok(Vec<Vec<Vec<f64>>>)` - 3D vector: [time][series1][series2] = covariance
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::covariance_matrix;
+///
+/// let series = vec![vec![1.0, 2.0, 3.0, 4.0], vec![4.0, 3.0, 2.0, 1.0]];
+/// let result = covariance_matrix(&series, 3).unwrap();
+/// assert!((result[3][0][0] - 1.0).abs() < 1e-12);
+/// assert!((result[3][0][1] + 1.0).abs() < 1e-12);
+/// ```
+pub fn covariance_matrix(series: &[Vec<f64>], period: impl IntoParam<Period>) -> TAResult<Vec<Vec<Vec<f64>>>> {
+    with_function("COVAR", || {
+        let period = period.into_param("period")?.get();
+        if series.is_empty() {
+            return Err(TAError::invalid_input("Series array cannot be empty"));
+        }
+
+        let num_series = series.len();
+        let len = series[0].len();
+
+        if series.iter().any(|s| s.len() != len) {
+            return Err(TAError::mismatched_inputs("All series must have the same length"));
+        }
+
+        let mut result = vec![vec![vec![f64::NAN; num_series]; num_series]; len];
+
+        for i in 0..num_series {
+            for j in i..num_series {
+                let cov_ij = covariance(&series[i], &series[j], period)?;
+                for (matrix, &value) in result.iter_mut().zip(&cov_ij) {
+                    matrix[i][j] = value;
+                    matrix[j][i] = value;
+                }
+            }
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::statistic::{beta, var};
+
+    #[test]
+    fn test_covariance_basic() {
+        let x = vec![2.0, 4.0, 6.0, 5.0];
+        let y = vec![1.0, 3.0, 2.0, 7.0];
+        let result = covariance(&x, &y, 3).unwrap();
+
+        assert!(result[1].is_nan());
+        // Window 2,4,6 / 1,3,2: means 4, 2 → (-2·-1 + 0·1 + 2·0) / 2
+        assert_float_eq!(result[2], 1.0, 1e-12);
+        // Window 4,6,5 / 3,2,7: means 5, 4 → (-1·-1 + 1·-2 + 0·3) / 2
+        assert_float_eq!(result[3], -0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_covariance_consistent_with_var_and_beta() {
+        let x: Vec<f64> = (0..30).map(|i| 50.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let y: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.3).cos() * 2.0 + i as f64 * 0.1).collect();
+        let period = 10;
+
+        let cov_xy = covariance(&x, &y, period).unwrap();
+        let cov_yy = covariance(&y, &y, period).unwrap();
+        let variance = var(&y, period).unwrap();
+        let b = beta(&x, &y, period).unwrap();
+
+        let n = period as f64;
+        for (((xy, yy), variance), b) in cov_xy.iter().zip(&cov_yy).zip(&variance).zip(&b).skip(period - 1) {
+            // VAR is the population variance
+            assert_float_eq!(*yy, variance * n / (n - 1.0), 1e-9);
+            assert_float_eq!(xy / yy, *b, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_covariance_nan_window() {
+        let x = vec![1.0, 2.0, f64::NAN, 4.0, 5.0, 6.0];
+        let y = vec![1.0, 3.0, 2.0, 4.0, 6.0, 5.0];
+        let result = covariance(&x, &y, 3).unwrap();
+        assert!(result[2..5].iter().all(|v| v.is_nan()));
+        assert!(!result[5].is_nan());
+    }
+
+    #[test]
+    fn test_covariance_matrix() {
+        let series = vec![
+            vec![1.0, 2.0, 3.0, 5.0, 4.0],
+            vec![2.0, 1.0, 4.0, 3.0, 6.0],
+            vec![5.0, 4.0, 3.0, 2.0, 1.0],
+        ];
+        let result = covariance_matrix(&series, 3).unwrap();
+        assert_eq!(result.len(), 5);
+
+        for (i, a) in series.iter().enumerate() {
+            for (j, b) in series.iter().enumerate() {
+                let expected = covariance(a, b, 3).unwrap();
+                for (t, expected) in expected.iter().enumerate().skip(2) {
+                    assert_float_eq!(result[t][i][j], *expected, 1e-12);
+                    assert_eq!(result[t][i][j], result[t][j][i]);
+                }
+            }
+        }
+        assert!(result[1][0][0].is_nan());
+    }
+
+    #[test]
+    fn test_covariance_invalid_input() {
+        let x = vec![1.0, 2.0, 3.0];
+        assert!(covariance(&[], &[], 2).is_err());
+        assert!(covariance(&x, &x[..2], 2).is_err());
+        assert!(covariance(&x, &x, 1).is_err());
+        assert!(covariance(&x, &x, 4).is_err());
+        assert!(covariance_matrix(&[], 2).is_err());
+        assert!(covariance_matrix(&[x.clone(), vec![1.0]], 2).is_err());
+    }
+}
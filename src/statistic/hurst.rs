@@ -0,0 +1,198 @@
+//! Rolling Hurst Exponent
+//!
+//! The Hurst exponent classifies the memory of a series: H ≈ 0.5 for a random walk,
+//! H > 0.5 for persistent (trending) behaviour and H < 0.5 for anti-persistent
+//! (mean-reverting) behaviour. It is estimated here with classic rescaled range (R/S)
+//! analysis of the bar-to-bar changes in each window.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::validate_prices;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Smallest chunk used in the R/S analysis; shorter chunks make the statistic too noisy
+pub const HURST_MIN_CHUNK: usize = 8;
+
+/// Rescaled range of one chunk, or `None` if the chunk has no variation
+fn rescaled_range(chunk: &[f64]) -> Option<f64> {
+    let n = chunk.len() as f64;
+    let mean = chunk.iter().sum::<f64>() / n;
+
+    let mut cumulative = 0.0;
+    let mut max = 0.0_f64;
+    let mut min = 0.0_f64;
+    let mut sum_sq = 0.0;
+    for &x in chunk {
+        let dev = x - mean;
+        cumulative += dev;
+        max = max.max(cumulative);
+        min = min.min(cumulative);
+        sum_sq += dev * dev;
+    }
+
+    let sd = (sum_sq / n).sqrt();
+    if sd > f64::EPSILON * mean.abs() {
+        Some((max - min) / sd)
+    } else {
+        None
+    }
+}
+
+/// Slope of log(R/S) against log(chunk size), using chunk sizes that double from
+/// [`HURST_MIN_CHUNK`] up to the window length
+fn rs_exponent(changes: &[f64]) -> f64 {
+    let mut points: Vec<(f64, f64)> = Vec::new();
+
+    let mut size = HURST_MIN_CHUNK;
+    while size <= changes.len() {
+        // Chunks are aligned to the end of the window so the latest bar is always used
+        let (total, count) = changes
+            .rchunks_exact(size)
+            .filter_map(rescaled_range)
+            .fold((0.0, 0usize), |(total, count), rs| (total + rs, count + 1));
+        if count > 0 {
+            points.push(((size as f64).ln(), (total / count as f64).ln()));
+        }
+        size *= 2;
+    }
+
+    if points.len() < 2 {
+        return f64::NAN;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxy: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    sxy / sxx
+}
+
+/// Rolling Hurst Exponent (rescaled range)
+///
+/// Each window holds the last `period` bar-to-bar changes. The changes are split into
+/// chunks of 8, 16, 32, ... bars, the average rescaled range is computed for each chunk
+/// size, and H is the least-squares slope of log(R/S) against log(size). Classic R/S is
+/// biased upwards on short windows, so compare readings across instruments with the same
+/// `period` rather than against 0.5 exactly.
+///
+/// # Formula
+/// ```text
+/// For each chunk x of size n:
+///   Y_t = Σ(x_k - mean(x)), k ≤ t
+///   R/S = (max(Y) - min(Y)) / stddev(x)
+/// H = slope of ln(mean R/S) versus ln(n)
+/// ```
+///
+/// # Arguments
+/// * `data` - Input price series (finite values)
+/// * `period` - Number of changes per window (at least 16, so that two chunk sizes fit)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of Hurst exponents; the first `period` values are NaN, as are
+///   windows without variation
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::hurst;
+///
+/// // A price that keeps snapping back is strongly anti-persistent
+/// let prices: Vec<f64> = (0..100).map(|i| 100.0 + if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+/// let result = hurst(&prices, 64).unwrap();
+/// assert!(result[63].is_nan());
+/// assert!(result[64] < 0.5);
+/// ```
+pub fn hurst(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("HURST", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Input data cannot be empty"));
+        }
+
+        if period < 2 * HURST_MIN_CHUNK {
+            return Err(TAError::invalid_parameter("period", "must be at least 16"));
+        }
+
+        let len = data.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+
+        validate_prices(data, "data")?;
+
+        let changes: Vec<f64> = data.windows(2).map(|w| w[1] - w[0]).collect();
+
+        let mut result = vec![f64::NAN; len];
+        for (value, window) in result[period..].iter_mut().zip(changes.windows(period)) {
+            *value = rs_exponent(window);
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_rescaled_range() {
+        assert_float_eq!(rescaled_range(&[1.0, -1.0, 1.0, -1.0]).unwrap(), 1.0, 1e-12);
+        // Deviations -1.5, -0.5, 0.5, 1.5 → cumulative range 2, stddev √1.25
+        assert_float_eq!(rescaled_range(&[1.0, 2.0, 3.0, 4.0]).unwrap(), 2.0 / 1.25_f64.sqrt(), 1e-12);
+        assert!(rescaled_range(&[3.0; 8]).is_none());
+    }
+
+    #[test]
+    fn test_hurst_single_window() {
+        // With 16 changes the estimate is the slope between the two chunk sizes
+        let prices: Vec<f64> = (0..17).map(|i| (i as f64 * 0.7).sin() * 10.0 + i as f64).collect();
+        let result = hurst(&prices, 16).unwrap();
+
+        let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+        let rs8 = (rescaled_range(&changes[..8]).unwrap() + rescaled_range(&changes[8..]).unwrap()) / 2.0;
+        let rs16 = rescaled_range(&changes).unwrap();
+        let expected = (rs16.ln() - rs8.ln()) / (16.0_f64.ln() - 8.0_f64.ln());
+
+        assert!(result[15].is_nan());
+        assert_float_eq!(result[16], expected, 1e-12);
+    }
+
+    #[test]
+    fn test_hurst_regimes() {
+        // Smoothly varying changes persist; alternating changes revert
+        let trending: Vec<f64> = (0..200)
+            .scan(100.0, |p, i| {
+                *p += (i as f64 * 0.05).sin();
+                Some(*p)
+            })
+            .collect();
+        let reverting: Vec<f64> = (0..200).map(|i| 100.0 + if i % 2 == 0 { 1.0 } else { -1.0 } + (i as f64 * 0.05).sin()).collect();
+
+        let h_trend = hurst(&trending, 128).unwrap();
+        let h_revert = hurst(&reverting, 128).unwrap();
+        for i in 128..200 {
+            assert!(h_trend[i] > 0.8, "trending H = {}", h_trend[i]);
+            assert!(h_revert[i] < 0.3, "reverting H = {}", h_revert[i]);
+        }
+    }
+
+    #[test]
+    fn test_hurst_flat_window_is_nan() {
+        let prices: Vec<f64> = (0..40).map(|i| 10.0 + i as f64 * 0.5).collect();
+        let result = hurst(&prices, 16).unwrap();
+        assert!(result.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_hurst_invalid_input() {
+        let prices = vec![1.0; 20];
+        assert!(hurst(&[], 16).is_err());
+        assert!(hurst(&prices, 15).is_err());
+        assert!(hurst(&prices, 20).is_err());
+        assert!(hurst(&[1.0, f64::NAN, 2.0, 3.0].repeat(5), 16).is_err());
+    }
+}
@@ -1,40 +1,42 @@
 //! Linear Regression Intercept
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Linear Regression Intercept
-pub fn linearreg_intercept(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
+pub fn linearreg_intercept(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("LINEARREG_INTERCEPT", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
     
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
     
-    if period > data.len() {
-        return Err(TAError::insufficient_data(period, data.len()));
-    }
+        let len = data.len();
+        let mut result = vec![f64::NAN; len];
     
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
-    
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window = &data[start_idx..=i];
+        for i in (period - 1)..len {
+            let start_idx = i + 1 - period;
+            let window = &data[start_idx..=i];
         
-        let n = period as f64;
-        let sum_x = (0..period).sum::<usize>() as f64;
-        let sum_y = window.iter().sum::<f64>();
-        let sum_xy = window.iter().enumerate().map(|(j, &y)| j as f64 * y).sum::<f64>();
-        let sum_x2 = (0..period).map(|j| (j * j) as f64).sum::<f64>();
+            let n = period as f64;
+            let sum_x = (0..period).sum::<usize>() as f64;
+            let sum_y = window.iter().sum::<f64>();
+            let sum_xy = window.iter().enumerate().map(|(j, &y)| j as f64 * y).sum::<f64>();
+            let sum_x2 = (0..period).map(|j| (j * j) as f64).sum::<f64>();
         
-        let slope = (n * sum_xy - sum_x * sum_y) / (n * sum_x2 - sum_x * sum_x);
-        let intercept = (sum_y - slope * sum_x) / n;
-        result[i] = intercept;
-    }
+            let slope = (n * sum_xy - sum_x * sum_y) / (n * sum_x2 - sum_x * sum_x);
+            let intercept = (sum_y - slope * sum_x) / n;
+            result[i] = intercept;
+        }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
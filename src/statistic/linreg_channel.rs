@@ -0,0 +1,193 @@
+//! Linear Regression Channel
+//!
+//! The regression line of the linearreg family together with a channel drawn a multiple of
+//! the standard error of the regression above and below it, and the R² of the fit. A high
+//! R² marks a clean trend; price leaving the channel marks a break from it.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Linear regression channel result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinRegChannel {
+    /// Upper channel values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub upper: Vec<f64>,
+    /// Regression line values at the last bar of each window (same as `linearreg`)
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub middle: Vec<f64>,
+    /// Lower channel values
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub lower: Vec<f64>,
+    /// Coefficient of determination of each window's fit, in [0, 1]
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub r_squared: Vec<f64>,
+}
+
+/// Linear Regression Channel
+///
+/// Fits `y = a + b·x` to each window with `x = 0..period-1` and reports the fitted value at
+/// the last bar, a channel of `deviations` standard errors around it, and R².
+///
+/// # Formula
+/// ```text
+/// Sxx    = Σ(x - x̄)² = period × (period² - 1) / 12
+/// Sxy    = Σ(x - x̄)(y - ȳ),   Syy = Σ(y - ȳ)²
+/// b      = Sxy / Sxx
+/// Middle = ȳ + b × (period - 1 - x̄)
+/// SSE    = Syy - Sxy² / Sxx
+/// StdErr = √(SSE / (period - 2))
+/// Upper  = Middle + deviations × StdErr
+/// Lower  = Middle - deviations × StdErr
+/// R²     = Sxy² / (Sxx × Syy)
+/// ```
+///
+/// R² is NaN for flat windows, where there is no variance to explain.
+///
+/// # Arguments
+/// * `prices` - Input price series
+/// * `period` - Regression window (at least 3, so the standard error is defined)
+/// * `deviations` - Channel width in standard errors (non-negative, typically 2.0)
+///
+/// # Returns
+/// * `Ok(LinRegChannel)` - Channel and R² values; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::linreg_channel;
+///
+/// let prices = vec![10.0, 11.2, 11.8, 13.1, 14.0, 14.8, 16.2, 17.0];
+/// let result = linreg_channel(&prices, 5, 2.0).unwrap();
+/// assert!(result.middle[3].is_nan());
+/// assert!(result.lower[4] < result.middle[4] && result.middle[4] < result.upper[4]);
+/// assert!(result.r_squared[7] > 0.95);
+/// ```
+pub fn linreg_channel(prices: &[f64], period: impl IntoParam<Period>, deviations: f64) -> TAResult<LinRegChannel> {
+    with_function("LINREGCHANNEL", || {
+        let period = period.into_param("period")?.get();
+        if prices.is_empty() {
+            return Err(TAError::invalid_input("Price data cannot be empty"));
+        }
+
+        if period < 3 {
+            return Err(TAError::invalid_parameter("period", "must be at least 3"));
+        }
+
+        if !deviations.is_finite() || deviations < 0.0 {
+            return Err(TAError::invalid_parameter("deviations", "must be a non-negative finite number"));
+        }
+
+        let len = prices.len();
+        if period > len {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let mut upper = vec![f64::NAN; len];
+        let mut middle = vec![f64::NAN; len];
+        let mut lower = vec![f64::NAN; len];
+        let mut r_squared = vec![f64::NAN; len];
+
+        let n = period as f64;
+        let mean_x = (n - 1.0) / 2.0;
+        let sxx = n * (n * n - 1.0) / 12.0;
+
+        for (offset, window) in prices.windows(period).enumerate() {
+            let i = offset + period - 1;
+            let mean_y = window.iter().sum::<f64>() / n;
+
+            let mut sxy = 0.0;
+            let mut syy = 0.0;
+            for (j, &y) in window.iter().enumerate() {
+                let dy = y - mean_y;
+                sxy += (j as f64 - mean_x) * dy;
+                syy += dy * dy;
+            }
+
+            let slope = sxy / sxx;
+            let line = mean_y + slope * mean_x;
+            let sse = (syy - sxy * sxy / sxx).max(0.0);
+            let stderr = (sse / (n - 2.0)).sqrt();
+
+            middle[i] = line;
+            upper[i] = line + deviations * stderr;
+            lower[i] = line - deviations * stderr;
+            if syy > f64::EPSILON * mean_y * mean_y {
+                r_squared[i] = (sxy * sxy / (sxx * syy)).min(1.0);
+            }
+        }
+
+        Ok(LinRegChannel {
+            upper,
+            middle,
+            lower,
+            r_squared,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::statistic::{correl, linearreg};
+
+    #[test]
+    fn test_linreg_channel_basic() {
+        // Fit of 1, 3, 2 on x = 0, 1, 2: slope 0.5, line at x=2 is 2.5, residuals -0.5, 1, -0.5
+        let prices = vec![1.0, 3.0, 2.0];
+        let result = linreg_channel(&prices, 3, 2.0).unwrap();
+
+        let stderr = (1.5_f64 / 1.0).sqrt();
+        assert_float_eq!(result.middle[2], 2.5, 1e-12);
+        assert_float_eq!(result.upper[2], 2.5 + 2.0 * stderr, 1e-12);
+        assert_float_eq!(result.lower[2], 2.5 - 2.0 * stderr, 1e-12);
+        // Syy = 2, Sxy = 1, Sxx = 2
+        assert_float_eq!(result.r_squared[2], 0.25, 1e-12);
+    }
+
+    #[test]
+    fn test_linreg_channel_matches_linearreg_and_correl() {
+        let prices: Vec<f64> = (0..50).map(|i| 100.0 + i as f64 * 0.3 + (i as f64 * 0.9).sin() * 2.0).collect();
+        let period = 14;
+        let result = linreg_channel(&prices, period, 2.0).unwrap();
+        let line = linearreg(&prices, period).unwrap();
+        let index: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        let r = correl(&index, &prices, period).unwrap();
+
+        assert!(result.middle[..period - 1].iter().all(|v| v.is_nan()));
+        for i in (period - 1)..prices.len() {
+            assert_float_eq!(result.middle[i], line[i], 1e-9);
+            assert_float_eq!(result.r_squared[i], r[i] * r[i], 1e-9);
+            assert_float_eq!(result.upper[i] - result.middle[i], result.middle[i] - result.lower[i], 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_linreg_channel_perfect_fit() {
+        let prices: Vec<f64> = (0..10).map(|i| 5.0 + 2.0 * i as f64).collect();
+        let result = linreg_channel(&prices, 5, 2.0).unwrap();
+        for (i, &price) in prices.iter().enumerate().skip(4) {
+            assert_float_eq!(result.upper[i], price, 1e-9);
+            assert_float_eq!(result.lower[i], price, 1e-9);
+            assert_float_eq!(result.r_squared[i], 1.0, 1e-12);
+        }
+
+        let flat = linreg_channel(&[7.0; 6], 3, 2.0).unwrap();
+        assert_float_eq!(flat.middle[5], 7.0, 1e-12);
+        assert!(flat.r_squared[5].is_nan());
+    }
+
+    #[test]
+    fn test_linreg_channel_invalid_input() {
+        let prices = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(linreg_channel(&[], 3, 2.0).is_err());
+        assert!(linreg_channel(&prices, 2, 2.0).is_err());
+        assert!(linreg_channel(&prices, 5, 2.0).is_err());
+        assert!(linreg_channel(&prices, 3, -1.0).is_err());
+        assert!(linreg_channel(&prices, 3, f64::NAN).is_err());
+    }
+}
@@ -3,22 +3,45 @@
 //! Statistical analysis functions for financial data including correlation,
 //! linear regression, standard deviation, and other statistical measures.
 
+pub mod autocorr;
+pub mod avgdev;
 pub mod beta;
+pub mod capm;
+pub(crate) mod comoments;
+pub mod channel_position;
 pub mod correl;
+pub mod covariance;
+pub mod hurst;
 pub mod linearreg;
 pub mod linearreg_angle;
 pub mod linearreg_intercept;
 pub mod linearreg_slope;
+pub mod linreg_channel;
+pub mod pairs;
+pub mod percent_rank;
+pub mod quantile;
+pub mod rolling_ols;
 pub mod stddev;
 pub mod tsf;
 pub mod var;
 
+pub use autocorr::*;
+pub use avgdev::*;
 pub use beta::*;
+pub use capm::*;
+pub use channel_position::*;
 pub use correl::*;
+pub use covariance::*;
+pub use hurst::*;
 pub use linearreg::*;
 pub use linearreg_angle::*;
 pub use linearreg_intercept::*;
 pub use linearreg_slope::*;
+pub use linreg_channel::*;
+pub use pairs::*;
+pub use percent_rank::*;
+pub use quantile::*;
+pub use rolling_ols::*;
 pub use stddev::*;
 pub use tsf::*;
 pub use var::*;
\ No newline at end of file
@@ -0,0 +1,334 @@
+//! Pairs Trading Tools
+//!
+//! Building blocks for statistical arbitrage on a pair of related instruments: a rolling
+//! hedge ratio, the hedged spread, and measures of how strongly and how quickly that
+//! spread reverts to its mean.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+use crate::statistic::covariance;
+
+/// Rolling Hedge Ratio
+///
+/// The OLS slope of `y` on `x` over each window: the number of units of `x` that offsets
+/// one unit of `y`.
+///
+/// # Formula
+/// ```text
+/// HedgeRatio = Cov(x, y) / Var(x)
+/// ```
+///
+/// # Arguments
+/// * `y` - Series being hedged
+/// * `x` - Hedging series
+/// * `period` - Window length (at least 2)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of hedge ratios; the first `period-1` values are NaN, as are
+///   windows where `x` is flat
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::hedge_ratio;
+///
+/// let x = vec![10.0, 11.0, 10.5, 12.0, 11.5];
+/// let y: Vec<f64> = x.iter().map(|v| 5.0 + 1.5 * v).collect();
+/// let result = hedge_ratio(&y, &x, 3).unwrap();
+/// assert!((result[4] - 1.5).abs() < 1e-9);
+/// ```
+pub fn hedge_ratio(y: &[f64], x: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("HEDGERATIO", || {
+        let period = period.into_param("period")?.get();
+        let cov_xy = covariance(x, y, period)?;
+        let var_x = covariance(x, x, period)?;
+
+        let mut result = vec![f64::NAN; x.len()];
+        for (offset, window) in x.windows(period).enumerate() {
+            let i = offset + period - 1;
+            // Flat windows are judged relative to their magnitude to absorb cancellation
+            let mean_sq = window.iter().map(|v| v * v).sum::<f64>() / period as f64;
+            if var_x[i] > f64::EPSILON * mean_sq {
+                result[i] = cov_xy[i] / var_x[i];
+            }
+        }
+
+        Ok(result)
+    })
+}
+
+/// Hedged Spread
+///
+/// Combines a pair into a single series using a hedge ratio per bar, typically the output
+/// of [`hedge_ratio`].
+///
+/// # Formula
+/// ```text
+/// Spread = y - HedgeRatio × x
+/// ```
+///
+/// # Arguments
+/// * `y` - Series being hedged
+/// * `x` - Hedging series
+/// * `hedge_ratio` - Hedge ratio for each bar
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of spread values; NaN wherever the hedge ratio is NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::spread;
+///
+/// let result = spread(&[10.0, 12.0], &[4.0, 5.0], &[2.0, 2.0]).unwrap();
+/// assert_eq!(result, vec![2.0, 2.0]);
+/// ```
+pub fn spread(y: &[f64], x: &[f64], hedge_ratio: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("SPREAD", || {
+        if y.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if x.len() != y.len() || hedge_ratio.len() != y.len() {
+            return Err(TAError::mismatched_inputs("Input arrays must have the same length"));
+        }
+
+        Ok(y.iter()
+            .zip(x)
+            .zip(hedge_ratio)
+            .map(|((&yi, &xi), &h)| yi - h * xi)
+            .collect())
+    })
+}
+
+/// Rolling ADF Statistic
+///
+/// Approximates the augmented Dickey-Fuller test with its lag-free form: the t-statistic of
+/// the slope when the change of a series is regressed on its previous level. Strongly
+/// negative values reject a unit root, i.e. indicate a mean-reverting spread; compare
+/// against the Dickey-Fuller critical values for a regression with constant (about -2.57,
+/// -2.86 and -3.43 at the 10%, 5% and 1% levels for large samples).
+///
+/// # Formula
+/// ```text
+/// Δs[t] = a + b × s[t-1] + e[t]
+/// ADF   = b / SE(b),   SE(b) = √(SSE / (period - 2) / Σ(s[t-1] - s̄)²)
+/// ```
+///
+/// # Arguments
+/// * `series` - Input series, typically a [`spread`]
+/// * `period` - Number of changes in each regression (at least 3)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of test statistics; the first `period` values are NaN, as are
+///   windows containing non-finite values or fitting without error
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::adf_stat;
+///
+/// let series = vec![1.0, 3.0, 2.0, 4.0];
+/// let result = adf_stat(&series, 3).unwrap();
+/// assert!((result[3] + 3.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn adf_stat(series: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("ADF", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![f64::NAN; series.len()];
+        level_regression(series, period, |i, slope, stderr| {
+            if stderr > 0.0 {
+                result[i] = slope / stderr;
+            }
+        })?;
+        Ok(result)
+    })
+}
+
+/// Rolling Half-Life of Mean Reversion
+///
+/// The number of bars over which a deviation from the mean is expected to halve, from the
+/// same regression as [`adf_stat`]. `1 + b` is the AR(1) coefficient of the series.
+///
+/// # Formula
+/// ```text
+/// Δs[t]    = a + b × s[t-1] + e[t]
+/// HalfLife = -ln(2) / ln(1 + b)
+/// ```
+///
+/// # Arguments
+/// * `series` - Input series, typically a [`spread`]
+/// * `period` - Number of changes in each regression (at least 3)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of half-lives in bars; the first `period` values are NaN, as are
+///   windows that do not revert (`b ≥ 0`) or overshoot the mean (`b ≤ -1`)
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::half_life;
+///
+/// // Deviation from 100 halves every bar
+/// let series: Vec<f64> = (0..10).map(|i| 100.0 + 64.0 * 0.5_f64.powi(i)).collect();
+/// let result = half_life(&series, 5).unwrap();
+/// assert!((result[9] - 1.0).abs() < 1e-9);
+/// ```
+pub fn half_life(series: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("HALFLIFE", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![f64::NAN; series.len()];
+        level_regression(series, period, |i, slope, _| {
+            if slope < 0.0 && slope > -1.0 {
+                result[i] = -core::f64::consts::LN_2 / (1.0 + slope).ln();
+            }
+        })?;
+        Ok(result)
+    })
+}
+
+/// Regresses `Δs[t]` on `s[t-1]` over the last `period` changes at each bar, passing the
+/// bar index, slope and slope standard error of every finite window to `emit`
+fn level_regression(series: &[f64], period: usize, mut emit: impl FnMut(usize, f64, f64)) -> TAResult<()> {
+    if series.is_empty() {
+        return Err(TAError::invalid_input("Input array cannot be empty"));
+    }
+
+    if period < 3 {
+        return Err(TAError::invalid_parameter("period", "must be at least 3"));
+    }
+
+    if period + 1 > series.len() {
+        return Err(TAError::insufficient_data(period + 1, series.len()));
+    }
+
+    let n = period as f64;
+    for (offset, window) in series.windows(period + 1).enumerate() {
+        if !window.iter().all(|v| v.is_finite()) {
+            continue;
+        }
+
+        let levels = &window[..period];
+        let mean_x = levels.iter().sum::<f64>() / n;
+        let mean_y = (window[period] - window[0]) / n;
+
+        let mut sxx = 0.0;
+        let mut sxy = 0.0;
+        let mut syy = 0.0;
+        for pair in window.windows(2) {
+            let dx = pair[0] - mean_x;
+            let dy = pair[1] - pair[0] - mean_y;
+            sxx += dx * dx;
+            sxy += dx * dy;
+            syy += dy * dy;
+        }
+
+        if sxx <= f64::EPSILON * mean_x * mean_x {
+            continue;
+        }
+
+        let slope = sxy / sxx;
+        // Residuals lost in cancellation count as an exact fit
+        let sse = syy - sxy * slope;
+        let sse = if sse > f64::EPSILON * syy { sse } else { 0.0 };
+        let stderr = (sse / (n - 2.0) / sxx).sqrt();
+        emit(offset + period, slope, stderr);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::statistic::rolling_ols;
+
+    /// Deterministic pseudo-random shocks in [-1, 1]
+    fn shocks(len: usize) -> Vec<f64> {
+        let mut state = 12345u64;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hedge_ratio_matches_rolling_ols() {
+        let x: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        let y: Vec<f64> = x.iter().zip(shocks(40)).map(|(v, e)| 10.0 + 0.7 * v + e).collect();
+        let period = 15;
+
+        let result = hedge_ratio(&y, &x, period).unwrap();
+        let ols = rolling_ols(&y, &[&x], period).unwrap();
+        assert!(result[..period - 1].iter().all(|v| v.is_nan()));
+        for (value, expected) in result.iter().zip(&ols.coefficients[0]).skip(period - 1) {
+            assert_float_eq!(*value, *expected, 1e-9);
+        }
+
+        let flat = hedge_ratio(&[1.0, 2.0, 3.0, 4.0], &[5.0; 4], 3).unwrap();
+        assert!(flat.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_spread() {
+        let y = vec![10.0, 12.0, 11.0];
+        let x = vec![4.0, 5.0, 6.0];
+        let result = spread(&y, &x, &[f64::NAN, 2.0, 1.5]).unwrap();
+        assert!(result[0].is_nan());
+        assert_float_eq!(result[1], 2.0, 1e-12);
+        assert_float_eq!(result[2], 2.0, 1e-12);
+    }
+
+    #[test]
+    fn test_adf_stat_separates_reverting_from_random_walk() {
+        let e = shocks(300);
+        let mut reverting = vec![0.0; 300];
+        let mut walk = vec![0.0; 300];
+        for t in 1..300 {
+            reverting[t] = 0.5 * reverting[t - 1] + e[t];
+            walk[t] = walk[t - 1] + e[t];
+        }
+
+        let period = 250;
+        let adf_reverting = adf_stat(&reverting, period).unwrap();
+        let adf_walk = adf_stat(&walk, period).unwrap();
+        assert!(adf_reverting[..period].iter().all(|v| v.is_nan()));
+        for (reverting, walk) in adf_reverting.iter().zip(&adf_walk).skip(period) {
+            assert!(*reverting < -3.43, "{}", reverting);
+            assert!(walk > reverting);
+        }
+    }
+
+    #[test]
+    fn test_half_life() {
+        // Deviation from 20 shrinks by 10% each bar
+        let series: Vec<f64> = (0..40).map(|i| 20.0 + 10.0 * 0.9_f64.powi(i)).collect();
+        let result = half_life(&series, 10).unwrap();
+        let expected = 2.0_f64.ln() / -(0.9_f64.ln());
+        for &value in &result[10..] {
+            assert_float_eq!(value, expected, 1e-6);
+        }
+
+        // Trending away from the mean has no half-life
+        let trend: Vec<f64> = (0..10).map(|i| 1.1_f64.powi(i)).collect();
+        assert!(half_life(&trend, 5).unwrap().iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_pairs_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(hedge_ratio(&data, &data[..3], 2).is_err());
+        assert!(hedge_ratio(&data, &data, 1).is_err());
+        assert!(spread(&[], &[], &[]).is_err());
+        assert!(spread(&data, &data, &data[..2]).is_err());
+        assert!(adf_stat(&[], 3).is_err());
+        assert!(adf_stat(&data, 2).is_err());
+        assert!(adf_stat(&data, 4).is_err());
+        assert!(half_life(&data, 4).is_err());
+    }
+}
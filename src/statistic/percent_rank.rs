@@ -0,0 +1,93 @@
+//! Percent Rank
+//!
+//! Percent Rank places the current value within the distribution of the values before it:
+//! 0 when it is below all of the previous `period` values, 100 when it is above all of them.
+//! It turns any series into a bounded oscillator and is one of the three components of
+//! ConnorsRSI, applied there to the one-bar rate of change.
+
+use crate::common::{TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_sufficient_data};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rolling Percent Rank
+///
+/// The percentage of the previous `period` values that are strictly below the current one.
+/// Leading NaNs, such as the warm-up of an indicator given as input, are allowed; a bar whose
+/// window or own value is NaN has a NaN rank.
+///
+/// # Formula
+/// ```text
+/// PercentRank = 100 × count(x[i-period..i] < x[i]) / period
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `period` - Number of previous values to compare with
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of ranks in [0, 100]; the first `period` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::percent_rank;
+///
+/// let data = vec![3.0, 1.0, 4.0, 1.0, 5.0, 2.0];
+/// let result = percent_rank(&data, 4).unwrap();
+/// // 5 is above 3, 1, 4 and 1; 2 is above the two 1s of 1, 4, 1, 5
+/// assert_eq!(&result[4..], &[100.0, 50.0]);
+/// ```
+pub fn percent_rank(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("PERCENTRANK", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period + 1, "data")?;
+
+        let mut result = vec![f64::NAN; data.len()];
+        for i in period..data.len() {
+            let window = &data[i - period..i];
+            if data[i].is_nan() || window.iter().any(|x| x.is_nan()) {
+                continue;
+            }
+            let below = window.iter().filter(|&&x| x < data[i]).count();
+            result[i] = 100.0 * below as f64 / period as f64;
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::TAError;
+
+    #[test]
+    fn test_percent_rank() {
+        let data = vec![3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let result = percent_rank(&data, 3).unwrap();
+
+        assert!(result[..3].iter().all(|v| v.is_nan()));
+        // 1 is below 3, 1, 4; 5 above 1, 4, 1; 9 above 4, 1, 5; 2 above 1 of 1, 5, 9
+        assert_eq!(result[3], 0.0);
+        assert_eq!(result[4], 100.0);
+        assert_eq!(result[5], 100.0);
+        assert!((result[6] - 100.0 / 3.0).abs() < 1e-12);
+        assert!((result[7] - 200.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_percent_rank_nan_and_errors() {
+        let data = vec![f64::NAN, f64::NAN, 1.0, 2.0, 3.0, f64::NAN, 4.0, 5.0];
+        let result = percent_rank(&data, 2).unwrap();
+        assert!(result[..4].iter().all(|v| v.is_nan()));
+        assert_eq!(result[4], 100.0);
+        assert!(result[5].is_nan() && result[6].is_nan() && result[7].is_nan());
+
+        assert!(matches!(percent_rank(&[1.0, 2.0], 2), Err(TAError::InsufficientData { .. })));
+        assert!(percent_rank(&[], 2).is_err());
+        assert!(percent_rank(&[1.0, 2.0], 0).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Rolling median, quantile and median absolute deviation
+//!
+//! Order statistics over a sliding window are maintained with two heaps (a max-heap holding
+//! the lower part of the window and a min-heap holding the rest) with lazy deletion, so each
+//! bar costs O(log n) instead of sorting every window.
+
+use alloc::collections::{BTreeMap, BinaryHeap};
+use core::cmp::{Ordering, Reverse};
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_period, validate_prices, validate_sufficient_data};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Totally ordered wrapper so finite `f64`s can live in heaps and maps
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Key(f64);
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Sliding-window order statistics for a fixed quantile
+///
+/// `lower` holds the `rank + 1` smallest live values and `upper` the rest, so the tops of the
+/// two heaps are the `rank`-th and `rank + 1`-th order statistics. Removed values are
+/// recorded in `delayed` and dropped once they surface at the top of a heap.
+struct OrderStatistics {
+    lower: BinaryHeap<Key>,
+    upper: BinaryHeap<Reverse<Key>>,
+    delayed: BTreeMap<Key, usize>,
+    lower_len: usize,
+    upper_len: usize,
+    rank: usize,
+}
+
+impl OrderStatistics {
+    fn new(rank: usize) -> Self {
+        Self {
+            lower: BinaryHeap::new(),
+            upper: BinaryHeap::new(),
+            delayed: BTreeMap::new(),
+            lower_len: 0,
+            upper_len: 0,
+            rank,
+        }
+    }
+
+    fn insert(&mut self, value: f64) {
+        let key = Key(value);
+        if self.lower.peek().is_none_or(|&top| key <= top) {
+            self.lower.push(key);
+            self.lower_len += 1;
+        } else {
+            self.upper.push(Reverse(key));
+            self.upper_len += 1;
+        }
+        self.rebalance();
+    }
+
+    fn remove(&mut self, value: f64) {
+        let key = Key(value);
+        *self.delayed.entry(key).or_insert(0) += 1;
+
+        // Live values in `lower` are never greater than its top
+        if self.lower.peek().is_some_and(|&top| key <= top) {
+            self.lower_len -= 1;
+            self.prune_lower();
+        } else {
+            self.upper_len -= 1;
+            self.prune_upper();
+        }
+        self.rebalance();
+    }
+
+    /// `rank`-th and `rank + 1`-th smallest live values (the latter if it exists)
+    fn tops(&self) -> (f64, Option<f64>) {
+        let low = self.lower.peek().map_or(f64::NAN, |k| k.0);
+        (low, self.upper.peek().map(|k| k.0 .0))
+    }
+
+    fn take_delayed(&mut self, key: Key) -> bool {
+        match self.delayed.get_mut(&key) {
+            Some(count) => {
+                *count -= 1;
+                if *count == 0 {
+                    self.delayed.remove(&key);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn prune_lower(&mut self) {
+        while let Some(&top) = self.lower.peek() {
+            if !self.take_delayed(top) {
+                break;
+            }
+            self.lower.pop();
+        }
+    }
+
+    fn prune_upper(&mut self) {
+        while let Some(&Reverse(top)) = self.upper.peek() {
+            if !self.take_delayed(top) {
+                break;
+            }
+            self.upper.pop();
+        }
+    }
+
+    fn rebalance(&mut self) {
+        let target = self.rank + 1;
+        while self.lower_len > target {
+            if let Some(top) = self.lower.pop() {
+                self.upper.push(Reverse(top));
+                self.lower_len -= 1;
+                self.upper_len += 1;
+                self.prune_lower();
+            }
+        }
+        while self.lower_len < target && self.upper_len > 0 {
+            if let Some(Reverse(top)) = self.upper.pop() {
+                self.lower.push(top);
+                self.upper_len -= 1;
+                self.lower_len += 1;
+                self.prune_upper();
+            }
+        }
+    }
+}
+
+/// Runs a sliding window of `period` over `data`, calling `value` with the heap state at
+/// each complete window
+fn rolling_order_statistic<F>(data: &[f64], period: usize, q: f64, mut value: F) -> Vec<f64>
+where
+    F: FnMut(usize, f64) -> f64,
+{
+    let position = q * (period - 1) as f64;
+    let rank = position.floor() as usize;
+    let fraction = position - rank as f64;
+
+    let mut stats = OrderStatistics::new(rank);
+    let mut result = vec![f64::NAN; data.len()];
+
+    for (i, &x) in data.iter().enumerate() {
+        stats.insert(x);
+        if i >= period {
+            stats.remove(data[i - period]);
+        }
+        if i + 1 >= period {
+            let (low, high) = stats.tops();
+            let quantile = match high {
+                Some(high) if fraction > 0.0 => low + fraction * (high - low),
+                _ => low,
+            };
+            result[i] = value(i, quantile);
+        }
+    }
+
+    result
+}
+
+fn validate_inputs(data: &[f64], period: usize) -> TAResult<()> {
+    validate_not_empty(data, "data")?;
+    validate_period(period, "period")?;
+    validate_sufficient_data(data, period, "data")?;
+    validate_prices(data, "data")
+}
+
+/// Rolling quantile
+///
+/// The `q`-quantile of each window, linearly interpolated between the two nearest order
+/// statistics (the same definition as NumPy's default and Excel's `PERCENTILE.INC`).
+///
+/// # Formula
+/// ```text
+/// h = q × (period - 1)
+/// Q = x(⌊h⌋) + (h - ⌊h⌋) × (x(⌊h⌋ + 1) - x(⌊h⌋))        x(k) = k-th smallest, from 0
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series (finite values)
+/// * `period` - Window length
+/// * `q` - Quantile in [0, 1] (0.5 is the median)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of quantiles; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::rolling_quantile;
+///
+/// let data = vec![1.0, 5.0, 2.0, 8.0, 3.0];
+/// let result = rolling_quantile(&data, 4, 0.25).unwrap();
+/// // Window 1, 2, 5, 8: h = 0.75 → 1 + 0.75 × (2 - 1)
+/// assert_eq!(result[3], 1.75);
+/// ```
+pub fn rolling_quantile(data: &[f64], period: impl IntoParam<Period>, q: f64) -> TAResult<Vec<f64>> {
+    with_function("QUANTILE", || {
+        let period = period.into_param("period")?.get();
+        validate_inputs(data, period)?;
+        if !(0.0..=1.0).contains(&q) {
+            return Err(TAError::invalid_parameter("q", "must be between 0 and 1"));
+        }
+
+        Ok(rolling_order_statistic(data, period, q, |_, quantile| quantile))
+    })
+}
+
+/// Rolling median
+///
+/// # Arguments
+/// * `data` - Input data series (finite values)
+/// * `period` - Window length
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of medians (the mean of the middle two for even periods);
+///   the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::rolling_median;
+///
+/// let data = vec![3.0, 1.0, 100.0, 2.0, 4.0];
+/// let result = rolling_median(&data, 3).unwrap();
+/// assert_eq!(&result[2..], &[3.0, 2.0, 4.0]);
+/// ```
+pub fn rolling_median(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MEDIAN", || {
+        let period = period.into_param("period")?.get();
+        rolling_quantile(data, period, 0.5)
+    })
+}
+
+/// Rolling median absolute deviation (MAD)
+///
+/// The median of the absolute deviations from the window median. The window medians come
+/// from the heaps; the median of the deviations is found by selection in O(period) per bar,
+/// since the deviations change with every new median. Multiply by 1.4826 to estimate the
+/// standard deviation of normally distributed data.
+///
+/// # Formula
+/// ```text
+/// MAD = median(|x - median(x)|)
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series (finite values)
+/// * `period` - Window length
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of MAD values; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::rolling_mad;
+///
+/// let data = vec![1.0, 2.0, 3.0, 4.0, 100.0];
+/// let result = rolling_mad(&data, 5).unwrap();
+/// // Median 3, deviations 2, 1, 0, 1, 97 → MAD 1; the outlier barely matters
+/// assert_eq!(result[4], 1.0);
+/// ```
+pub fn rolling_mad(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MAD", || {
+        let period = period.into_param("period")?.get();
+        validate_inputs(data, period)?;
+
+        let mut deviations = vec![0.0; period];
+        Ok(rolling_order_statistic(data, period, 0.5, |i, median| {
+            let window = &data[i + 1 - period..=i];
+            for (dev, &x) in deviations.iter_mut().zip(window) {
+                *dev = (x - median).abs();
+            }
+            median_in_place(&mut deviations)
+        }))
+    })
+}
+
+/// Median of a non-empty slice, reordering it
+fn median_in_place(values: &mut [f64]) -> f64 {
+    let len = values.len();
+    let (below, &mut upper, _) = values.select_nth_unstable_by(len / 2, f64::total_cmp);
+    if !len.is_multiple_of(2) {
+        upper
+    } else {
+        let lower = below.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        (lower + upper) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    /// Reference quantile by sorting each window
+    fn sorted_quantile(window: &[f64], q: f64) -> f64 {
+        let mut sorted = window.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let h = q * (sorted.len() - 1) as f64;
+        let k = h.floor() as usize;
+        if k + 1 < sorted.len() {
+            sorted[k] + (h - k as f64) * (sorted[k + 1] - sorted[k])
+        } else {
+            sorted[k]
+        }
+    }
+
+    fn sample() -> Vec<f64> {
+        // Plenty of ties and reversals to exercise lazy deletion
+        (0..200).map(|i| ((i * 37) % 23) as f64 + ((i as f64) * 0.7).sin().round()).collect()
+    }
+
+    #[test]
+    fn test_rolling_quantile_matches_sorting() {
+        let data = sample();
+        for &period in &[1, 2, 5, 10, 31] {
+            for &q in &[0.0, 0.1, 0.25, 0.5, 0.9, 1.0] {
+                let result = rolling_quantile(&data, period, q).unwrap();
+                assert!(result[..period - 1].iter().all(|v| v.is_nan()));
+                for i in (period - 1)..data.len() {
+                    let expected = sorted_quantile(&data[i + 1 - period..=i], q);
+                    assert_float_eq!(result[i], expected, 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_median_even_period() {
+        let data = vec![4.0, 1.0, 3.0, 2.0, 10.0];
+        let result = rolling_median(&data, 4).unwrap();
+        assert_eq!(result[3], 2.5);
+        assert_eq!(result[4], 2.5);
+    }
+
+    #[test]
+    fn test_rolling_mad_matches_sorting() {
+        let data = sample();
+        for &period in &[1, 4, 7] {
+            let result = rolling_mad(&data, period).unwrap();
+            for i in (period - 1)..data.len() {
+                let window = &data[i + 1 - period..=i];
+                let median = sorted_quantile(window, 0.5);
+                let deviations: Vec<f64> = window.iter().map(|x| (x - median).abs()).collect();
+                assert_float_eq!(result[i], sorted_quantile(&deviations, 0.5), 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantile_invalid_input() {
+        let data = vec![1.0, 2.0, 3.0];
+        assert!(rolling_median(&[], 2).is_err());
+        assert!(rolling_median(&data, 0).is_err());
+        assert!(rolling_median(&data, 4).is_err());
+        assert!(rolling_quantile(&data, 2, 1.5).is_err());
+        assert!(rolling_quantile(&data, 2, f64::NAN).is_err());
+        assert!(rolling_mad(&[1.0, f64::NAN, 2.0], 2).is_err());
+    }
+}
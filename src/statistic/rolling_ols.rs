@@ -0,0 +1,276 @@
+//! Rolling Ordinary Least Squares
+//!
+//! Regresses a series on several explanatory series over a rolling window, for factor-model
+//! style analysis such as a stock's exposure to its sector and to the market at once.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rolling OLS result structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingOls {
+    /// One coefficient series per regressor, in the order the regressors were given
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan::nested"))]
+    pub coefficients: Vec<Vec<f64>>,
+    /// Intercept of each window's fit
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub intercept: Vec<f64>,
+    /// Coefficient of determination of each window's fit, in [0, 1]
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub r_squared: Vec<f64>,
+}
+
+/// Rolling OLS with multiple regressors
+///
+/// Fits `y = a + b₁·x₁ + … + bₖ·xₖ` to each window by solving the normal equations on
+/// mean-centred data.
+///
+/// # Formula
+/// ```text
+/// Sxx[j][l] = Σ(xⱼ - x̄ⱼ)(xₗ - x̄ₗ),   Sxy[j] = Σ(xⱼ - x̄ⱼ)(y - ȳ),   Syy = Σ(y - ȳ)²
+/// b         = Sxx⁻¹ × Sxy
+/// a         = ȳ - Σ bⱼ × x̄ⱼ
+/// R²        = b · Sxy / Syy
+/// ```
+///
+/// Windows containing non-finite values, or whose regressors are collinear, are NaN. R² is
+/// also NaN for flat `y` windows, where there is no variance to explain.
+///
+/// # Arguments
+/// * `y` - Dependent series
+/// * `xs` - Regressor series, each the same length as `y`
+/// * `period` - Window length (greater than the number of regressors)
+///
+/// # Returns
+/// * `Ok(RollingOls)` - Coefficients, intercept and R²; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::statistic::rolling_ols;
+///
+/// let market = vec![1.0, 2.0, 1.5, 3.0, 2.5, 4.0];
+/// let sector = vec![0.5, 0.2, 0.9, 0.4, 1.1, 0.7];
+/// let stock: Vec<f64> = market.iter().zip(&sector).map(|(m, s)| 0.1 + 1.2 * m + 0.5 * s).collect();
+///
+/// let result = rolling_ols(&stock, &[&market, &sector], 4).unwrap();
+/// assert!((result.coefficients[0][5] - 1.2).abs() < 1e-9);
+/// assert!((result.coefficients[1][5] - 0.5).abs() < 1e-9);
+/// assert!((result.intercept[5] - 0.1).abs() < 1e-9);
+/// ```
+pub fn rolling_ols(y: &[f64], xs: &[&[f64]], period: impl IntoParam<Period>) -> TAResult<RollingOls> {
+    with_function("ROLLING_OLS", || {
+        let period = period.into_param("period")?.get();
+        if y.is_empty() {
+            return Err(TAError::invalid_input("Input array cannot be empty"));
+        }
+
+        if xs.is_empty() {
+            return Err(TAError::invalid_input("At least one regressor is required"));
+        }
+
+        let len = y.len();
+        if xs.iter().any(|x| x.len() != len) {
+            return Err(TAError::mismatched_inputs("Regressors must have the same length as y"));
+        }
+
+        let k = xs.len();
+        if period <= k {
+            return Err(TAError::invalid_parameter("period", "must be greater than the number of regressors"));
+        }
+
+        if period > len {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let mut coefficients = vec![vec![f64::NAN; len]; k];
+        let mut intercept = vec![f64::NAN; len];
+        let mut r_squared = vec![f64::NAN; len];
+
+        let n = period as f64;
+        let mut mean_x = vec![0.0; k];
+        // Normal equations as an augmented k × (k + 1) matrix [Sxx | Sxy]
+        let mut system = vec![vec![0.0; k + 1]; k];
+
+        for i in (period - 1)..len {
+            let start = i + 1 - period;
+            let window = start..=i;
+
+            if !y[window.clone()].iter().all(|v| v.is_finite())
+                || !xs.iter().all(|x| x[window.clone()].iter().all(|v| v.is_finite()))
+            {
+                continue;
+            }
+
+            let mean_y = y[window.clone()].iter().sum::<f64>() / n;
+            for (mean, x) in mean_x.iter_mut().zip(xs) {
+                *mean = x[window.clone()].iter().sum::<f64>() / n;
+            }
+
+            for row in system.iter_mut() {
+                row.iter_mut().for_each(|v| *v = 0.0);
+            }
+            let mut syy = 0.0;
+            for t in window {
+                let dy = y[t] - mean_y;
+                syy += dy * dy;
+                for (j, row) in system.iter_mut().enumerate() {
+                    let dxj = xs[j][t] - mean_x[j];
+                    for (l, cell) in row[..k].iter_mut().enumerate() {
+                        *cell += dxj * (xs[l][t] - mean_x[l]);
+                    }
+                    row[k] += dxj * dy;
+                }
+            }
+            let sxy: Vec<f64> = system.iter().map(|row| row[k]).collect();
+
+            let Some(b) = solve(&mut system) else {
+                continue;
+            };
+
+            let mut a = mean_y;
+            for ((series, &bj), &mean) in coefficients.iter_mut().zip(&b).zip(&mean_x) {
+                series[i] = bj;
+                a -= bj * mean;
+            }
+            intercept[i] = a;
+
+            if syy > f64::EPSILON * mean_y * mean_y {
+                let explained: f64 = b.iter().zip(&sxy).map(|(bj, sj)| bj * sj).sum();
+                r_squared[i] = (explained / syy).clamp(0.0, 1.0);
+            }
+        }
+
+        Ok(RollingOls {
+            coefficients,
+            intercept,
+            r_squared,
+        })
+    })
+}
+
+/// Solves an augmented symmetric system by Gaussian elimination with partial pivoting,
+/// returning `None` when it is (numerically) singular
+pub(crate) fn solve(system: &mut [Vec<f64>]) -> Option<Vec<f64>> {
+    let k = system.len();
+    // Pivots are judged against the largest diagonal entry to absorb the scale of the data
+    let scale = (0..k).map(|j| system[j][j]).fold(0.0, f64::max);
+    if scale <= 0.0 {
+        return None;
+    }
+
+    for col in 0..k {
+        let pivot_row = (col..k).max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))?;
+        if system[pivot_row][col].abs() <= 1e-10 * scale {
+            return None;
+        }
+        system.swap(col, pivot_row);
+
+        let (upper, lower) = system.split_at_mut(col + 1);
+        let pivot = &upper[col];
+        for row in lower {
+            let factor = row[col] / pivot[col];
+            if factor != 0.0 {
+                for (cell, &p) in row[col..].iter_mut().zip(&pivot[col..]) {
+                    *cell -= factor * p;
+                }
+            }
+        }
+    }
+
+    let mut solution = vec![0.0; k];
+    for row in (0..k).rev() {
+        let tail: f64 = ((row + 1)..k).map(|c| system[row][c] * solution[c]).sum();
+        solution[row] = (system[row][k] - tail) / system[row][row];
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::statistic::{correl, covariance, linearreg_intercept, linearreg_slope};
+
+    #[test]
+    fn test_rolling_ols_recovers_exact_model() {
+        let x1: Vec<f64> = (0..40).map(|i| (i as f64 * 0.37).sin() * 4.0).collect();
+        let x2: Vec<f64> = (0..40).map(|i| (i as f64 * 0.21).cos() * 2.0 + i as f64 * 0.05).collect();
+        let x3: Vec<f64> = (0..40).map(|i| ((i * 7) % 11) as f64).collect();
+        let y: Vec<f64> = (0..40).map(|i| 3.0 + 0.8 * x1[i] - 1.5 * x2[i] + 0.25 * x3[i]).collect();
+
+        let result = rolling_ols(&y, &[&x1, &x2, &x3], 12).unwrap();
+        assert!(result.intercept[..11].iter().all(|v| v.is_nan()));
+        for i in 11..40 {
+            assert_float_eq!(result.coefficients[0][i], 0.8, 1e-9);
+            assert_float_eq!(result.coefficients[1][i], -1.5, 1e-9);
+            assert_float_eq!(result.coefficients[2][i], 0.25, 1e-9);
+            assert_float_eq!(result.intercept[i], 3.0, 1e-9);
+            assert_float_eq!(result.r_squared[i], 1.0, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_rolling_ols_single_regressor() {
+        let x: Vec<f64> = (0..30).map(|i| 10.0 + (i as f64 * 0.5).sin() * 3.0).collect();
+        let y: Vec<f64> = (0..30).map(|i| 5.0 + (i as f64 * 0.3).cos() * 2.0 + i as f64 * 0.2).collect();
+        let period = 8;
+
+        let result = rolling_ols(&y, &[&x], period).unwrap();
+        let cov_xy = covariance(&x, &y, period).unwrap();
+        let cov_xx = covariance(&x, &x, period).unwrap();
+        let r = correl(&x, &y, period).unwrap();
+        for i in (period - 1)..30 {
+            assert_float_eq!(result.coefficients[0][i], cov_xy[i] / cov_xx[i], 1e-9);
+            assert_float_eq!(result.r_squared[i], r[i] * r[i], 1e-9);
+        }
+
+        // Regressing on the bar index reproduces the linearreg family
+        let index: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let result = rolling_ols(&y, &[&index], period).unwrap();
+        let slope = linearreg_slope(&y, period).unwrap();
+        let intercept = linearreg_intercept(&y, period).unwrap();
+        for i in (period - 1)..30 {
+            assert_float_eq!(result.coefficients[0][i], slope[i], 1e-9);
+            let start = (i + 1 - period) as f64;
+            assert_float_eq!(result.intercept[i] + result.coefficients[0][i] * start, intercept[i], 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_rolling_ols_undefined_windows() {
+        let x1 = vec![1.0, 2.0, 4.0, 3.0, 5.0, 7.0, 6.0];
+        let x2: Vec<f64> = x1.iter().map(|v| 2.0 * v + 1.0).collect();
+        let y = vec![2.0, 1.0, 3.0, 5.0, 4.0, 6.0, 8.0];
+
+        // Collinear regressors have no unique solution
+        let result = rolling_ols(&y, &[&x1, &x2], 4).unwrap();
+        assert!(result.coefficients.iter().flatten().all(|v| v.is_nan()));
+        assert!(result.intercept.iter().all(|v| v.is_nan()));
+
+        let mut gappy = y.clone();
+        gappy[2] = f64::NAN;
+        let result = rolling_ols(&gappy, &[&x1], 3).unwrap();
+        assert!(result.intercept[2..5].iter().all(|v| v.is_nan()));
+        assert!(!result.intercept[5].is_nan());
+
+        let flat = rolling_ols(&[4.0; 7], &[&x1], 3).unwrap();
+        assert_float_eq!(flat.coefficients[0][6], 0.0, 1e-12);
+        assert_float_eq!(flat.intercept[6], 4.0, 1e-12);
+        assert!(flat.r_squared[6].is_nan());
+    }
+
+    #[test]
+    fn test_rolling_ols_invalid_input() {
+        let x = vec![1.0, 2.0, 3.0, 4.0];
+        let y = vec![2.0, 3.0, 5.0, 4.0];
+        assert!(rolling_ols(&[], &[&x], 2).is_err());
+        assert!(rolling_ols(&y, &[], 2).is_err());
+        assert!(rolling_ols(&y, &[&x[..3]], 2).is_err());
+        assert!(rolling_ols(&y, &[&x, &x], 2).is_err());
+        assert!(rolling_ols(&y, &[&x], 5).is_err());
+    }
+}
@@ -3,7 +3,10 @@
 //! Standard deviation measures the amount of variation or dispersion in a dataset.
 //! It's commonly used in finance to measure volatility and risk.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, neumaier_sum, validate_same_length, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Standard Deviation
 ///
@@ -35,41 +38,56 @@ use crate::common::{TAError, TAResult};
 /// let data = vec![20.0, 21.0, 22.0, 23.0, 24.0, 23.0, 22.0, 21.0, 20.0, 19.0];
 /// let result = stddev(&data, 5, 1.0).unwrap();
 /// ```
-pub fn stddev(data: &[f64], period: usize, deviations: f64) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn stddev(data: &[f64], period: impl IntoParam<Period>, deviations: f64) -> TAResult<Vec<f64>> {
+    with_function("STDDEV", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![f64::NAN; data.len()];
+        stddev_into(data, period, deviations, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Standard Deviation into a caller-provided buffer
+///
+/// Allocation-free variant of [`stddev`]. `out` must have the same length as `data`;
+/// its first `period-1` entries are set to NaN.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of valid values written (`data.len() - period + 1`)
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn stddev_into(data: &[f64], period: impl IntoParam<Period>, deviations: f64, out: &mut [f64]) -> TAResult<usize> {
+    with_function("STDDEV", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
     
-    if period > data.len() {
-        return Err(TAError::insufficient_data(period, data.len()));
-    }
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
+        validate_same_length(data, out, "data", "out")?;
     
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
+        let len = data.len();
+        out[..period - 1].fill(f64::NAN);
     
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window = &data[start_idx..=i];
+        for i in (period - 1)..len {
+            let start_idx = i + 1 - period;
+            let window = &data[start_idx..=i];
         
-        // Calculate mean
-        let mean = window.iter().sum::<f64>() / period as f64;
+            // Calculate mean
+            let mean = neumaier_sum(window.iter().copied()) / period as f64;
         
-        // Calculate variance
-        let variance = window.iter()
-            .map(|&x| (x - mean).powi(2))
-            .sum::<f64>() / period as f64;
+            // Calculate variance
+            let variance = neumaier_sum(window.iter().map(|&x| (x - mean).powi(2))) / period as f64;
         
-        // Calculate standard deviation
-        let std_dev = variance.sqrt();
+            // Calculate standard deviation
+            let std_dev = variance.sqrt();
         
-        result[i] = std_dev * deviations;
-    }
+            out[i] = std_dev * deviations;
+        }
     
-    Ok(result)
+        Ok(len - period + 1)
+    })
 }
 
 /// Standard Deviation with default parameters (period=5, deviations=1.0)
@@ -99,41 +117,42 @@ pub fn stddev_default(data: &[f64]) -> TAResult<Vec<f64>> {
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of sample standard deviation values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn stddev_sample(data: &[f64], period: usize, deviations: f64) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
+pub fn stddev_sample(data: &[f64], period: impl IntoParam<Period>, deviations: f64) -> TAResult<Vec<f64>> {
+    with_function("STDDEV", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
     
-    if period <= 1 {
-        return Err(TAError::invalid_input("Period must be greater than 1 for sample standard deviation"));
-    }
+        if period <= 1 {
+            return Err(TAError::invalid_input("Period must be greater than 1 for sample standard deviation"));
+        }
     
-    if period > data.len() {
-        return Err(TAError::insufficient_data(period, data.len()));
-    }
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
     
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
+        let len = data.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window = &data[start_idx..=i];
+        for i in (period - 1)..len {
+            let start_idx = i + 1 - period;
+            let window = &data[start_idx..=i];
         
-        // Calculate mean
-        let mean = window.iter().sum::<f64>() / period as f64;
+            // Calculate mean
+            let mean = neumaier_sum(window.iter().copied()) / period as f64;
         
-        // Calculate sample variance (divide by n-1)
-        let variance = window.iter()
-            .map(|&x| (x - mean).powi(2))
-            .sum::<f64>() / (period - 1) as f64;
+            // Calculate sample variance (divide by n-1)
+            let variance = neumaier_sum(window.iter().map(|&x| (x - mean).powi(2))) / (period - 1) as f64;
         
-        // Calculate standard deviation
-        let std_dev = variance.sqrt();
+            // Calculate standard deviation
+            let std_dev = variance.sqrt();
         
-        result[i] = std_dev * deviations;
-    }
+            result[i] = std_dev * deviations;
+        }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Rolling Coefficient of Variation
@@ -148,44 +167,41 @@ pub fn stddev_sample(data: &[f64], period: usize, deviations: f64) -> TAResult<V
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of coefficient of variation values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn coefficient_of_variation(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn coefficient_of_variation(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("COEFFICIENT_OF_VARIATION", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
     
-    if period > data.len() {
-        return Err(TAError::insufficient_data(period, data.len()));
-    }
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
     
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
+        let len = data.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window = &data[start_idx..=i];
+        for i in (period - 1)..len {
+            let start_idx = i + 1 - period;
+            let window = &data[start_idx..=i];
         
-        // Calculate mean
-        let mean = window.iter().sum::<f64>() / period as f64;
+            // Calculate mean
+            let mean = neumaier_sum(window.iter().copied()) / period as f64;
         
-        if mean.abs() > f64::EPSILON {
-            // Calculate variance
-            let variance = window.iter()
-                .map(|&x| (x - mean).powi(2))
-                .sum::<f64>() / period as f64;
+            if mean.abs() > f64::EPSILON {
+                // Calculate variance
+                let variance = neumaier_sum(window.iter().map(|&x| (x - mean).powi(2))) / period as f64;
             
-            // Calculate coefficient of variation
-            let std_dev = variance.sqrt();
-            result[i] = std_dev / mean.abs();
-        } else {
-            result[i] = f64::NAN; // Undefined when mean is zero
+                // Calculate coefficient of variation
+                let std_dev = variance.sqrt();
+                result[i] = std_dev / mean.abs();
+            } else {
+                result[i] = f64::NAN; // Undefined when mean is zero
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Z-Score calculation
@@ -200,45 +216,42 @@ pub fn coefficient_of_variation(data: &[f64], period: usize) -> TAResult<Vec<f64
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of z-score values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn zscore(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn zscore(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("ZSCORE", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
     
-    if period > data.len() {
-        return Err(TAError::insufficient_data(period, data.len()));
-    }
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
     
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
+        let len = data.len();
+        let mut result = vec![f64::NAN; len];
     
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window = &data[start_idx..=i];
+        for i in (period - 1)..len {
+            let start_idx = i + 1 - period;
+            let window = &data[start_idx..=i];
         
-        // Calculate mean
-        let mean = window.iter().sum::<f64>() / period as f64;
+            // Calculate mean
+            let mean = neumaier_sum(window.iter().copied()) / period as f64;
         
-        // Calculate variance
-        let variance = window.iter()
-            .map(|&x| (x - mean).powi(2))
-            .sum::<f64>() / period as f64;
+            // Calculate variance
+            let variance = neumaier_sum(window.iter().map(|&x| (x - mean).powi(2))) / period as f64;
         
-        let std_dev = variance.sqrt();
+            let std_dev = variance.sqrt();
         
-        if std_dev > f64::EPSILON {
-            // Calculate z-score for the current data point
-            result[i] = (data[i] - mean) / std_dev;
-        } else {
-            result[i] = f64::NAN; // Undefined when standard deviation is zero
+            if std_dev > f64::EPSILON {
+                // Calculate z-score for the current data point
+                result[i] = (data[i] - mean) / std_dev;
+            } else {
+                result[i] = f64::NAN; // Undefined when standard deviation is zero
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 #[cfg(test)]
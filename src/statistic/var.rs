@@ -3,7 +3,10 @@
 //! Variance measures the average squared deviation from the mean.
 //! It's the square of the standard deviation.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, neumaier_sum, validate_same_length, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Variance
 ///
@@ -21,38 +24,53 @@ use crate::common::{TAError, TAResult};
 /// # Returns
 /// * `Ok(Vec<f64>)` - Vector of variance values
 /// * `Err(TAError)` - Error if inputs are invalid
-pub fn var(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
+pub fn var(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("VAR", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![f64::NAN; data.len()];
+        var_into(data, period, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Variance into a caller-provided buffer
+///
+/// Allocation-free variant of [`var`]. `out` must have the same length as `data`;
+/// its first `period-1` entries are set to NaN.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of valid values written (`data.len() - period + 1`)
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn var_into(data: &[f64], period: impl IntoParam<Period>, out: &mut [f64]) -> TAResult<usize> {
+    with_function("VAR", || {
+        let period = period.into_param("period")?.get();
+        if data.is_empty() {
+            return Err(TAError::invalid_input("Data cannot be empty"));
+        }
     
-    if period > data.len() {
-        return Err(TAError::insufficient_data(period, data.len()));
-    }
+        if period > data.len() {
+            return Err(TAError::insufficient_data(period, data.len()));
+        }
+        validate_same_length(data, out, "data", "out")?;
     
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
+        let len = data.len();
+        out[..period - 1].fill(f64::NAN);
     
-    for i in (period - 1)..len {
-        let start_idx = i + 1 - period;
-        let window = &data[start_idx..=i];
+        for i in (period - 1)..len {
+            let start_idx = i + 1 - period;
+            let window = &data[start_idx..=i];
         
-        // Calculate mean
-        let mean = window.iter().sum::<f64>() / period as f64;
+            // Calculate mean
+            let mean = neumaier_sum(window.iter().copied()) / period as f64;
         
-        // Calculate variance
-        let variance = window.iter()
-            .map(|&x| (x - mean).powi(2))
-            .sum::<f64>() / period as f64;
+            // Calculate variance
+            let variance = neumaier_sum(window.iter().map(|&x| (x - mean).powi(2))) / period as f64;
         
-        result[i] = variance;
-    }
+            out[i] = variance;
+        }
     
-    Ok(result)
+        Ok(len - period + 1)
+    })
 }
 
 #[cfg(test)]
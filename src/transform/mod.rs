@@ -0,0 +1,10 @@
+//! Transforms
+//!
+//! Feature-engineering steps applied to indicator outputs before they are fed to models,
+//! such as rolling normalization and squashing, and the Renko and Point and Figure
+//! conversions of [`price_transform`](crate::price_transform), re-exported here.
+
+pub mod normalize;
+
+pub use normalize::*;
+pub use crate::price_transform::{point_figure, renko, renko_atr, PnfColumn, Renko};
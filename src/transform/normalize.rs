@@ -0,0 +1,289 @@
+//! Normalization
+//!
+//! Indicators come on very different scales: prices, percentages, unbounded oscillators.
+//! Models trained on several of them need each feature brought to a comparable range
+//! without looking ahead, so the scalers below only use the trailing window of each bar.
+//! The squashing functions then bound unbounded values, such as z-scores, smoothly.
+//!
+//! Series with a warm-up, such as indicator outputs, can be scaled with
+//! [`with_nan_policy`](crate::common::with_nan_policy) and `NanPolicy::Skip`.
+
+use crate::common::{validate_not_empty, validate_sufficient_data, TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::{max, min};
+use crate::statistic::{rolling_mad, rolling_median, zscore};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Scale of the median absolute deviation of normally distributed data, relative to its
+/// standard deviation
+const MAD_TO_STD_DEV: f64 = 1.4826;
+
+/// Normalization method for [`normalize`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Normalization {
+    /// Rolling min-max scaling to [0, 1] over a window, see [`min_max_scale`]
+    MinMax(usize),
+    /// Rolling z-score over a window, see [`zscore`]
+    ZScore(usize),
+    /// Rolling median/MAD scaling over a window, see [`robust_scale`]
+    Robust(usize),
+    /// Hyperbolic tangent squashing to (-1, 1) with a scale, see [`tanh_squash`]
+    Tanh(f64),
+    /// Logistic squashing to (0, 1) with a scale, see [`sigmoid_squash`]
+    Sigmoid(f64),
+}
+
+/// Normalizes a series with the given method
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `method` - Normalization method and its parameter
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of normalized values
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::transform::{normalize, Normalization};
+///
+/// let data: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+/// let scaled = normalize(&data, Normalization::MinMax(20)).unwrap();
+/// assert!(scaled[19..].iter().all(|v| (0.0..=1.0).contains(v)));
+///
+/// // z-scores squashed into (-1, 1)
+/// let z = normalize(&data, Normalization::ZScore(20)).unwrap();
+/// let bounded = normalize(&z, Normalization::Tanh(2.0)).unwrap();
+/// assert!(bounded[19..].iter().all(|v| v.abs() < 1.0));
+/// ```
+pub fn normalize(data: &[f64], method: Normalization) -> TAResult<Vec<f64>> {
+    with_function("NORMALIZE", || {
+        match method {
+            Normalization::MinMax(period) => min_max_scale(data, period),
+            Normalization::ZScore(period) => zscore(data, period),
+            Normalization::Robust(period) => robust_scale(data, period),
+            Normalization::Tanh(scale) => tanh_squash(data, scale),
+            Normalization::Sigmoid(scale) => sigmoid_squash(data, scale),
+        }
+    })
+}
+
+/// Rolling min-max scaling
+///
+/// Places each value within the range of its trailing window: 0 at the window low, 1 at
+/// the window high. Like [`zscore`], a flat window has no scale and gives NaN.
+///
+/// # Formula
+/// ```text
+/// scaled = (x - min(x, period)) / (max(x, period) - min(x, period))
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `period` - Window length
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of values in [0, 1]; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::transform::min_max_scale;
+///
+/// let data = vec![2.0, 4.0, 3.0, 6.0, 2.0];
+/// let result = min_max_scale(&data, 3).unwrap();
+/// assert_eq!(&result[2..], &[0.5, 1.0, 0.0]);
+/// ```
+pub fn min_max_scale(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MIN_MAX_SCALE", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(data, "data")?;
+        validate_sufficient_data(data, period, "data")?;
+
+        let highest = max(data, period)?;
+        let lowest = min(data, period)?;
+        Ok(data
+            .iter()
+            .zip(highest.iter().zip(&lowest))
+            .map(|(&x, (&high, &low))| if high > low { (x - low) / (high - low) } else { f64::NAN })
+            .collect())
+    })
+}
+
+/// Rolling robust scaling
+///
+/// Centers each value on the median of its trailing window and divides by the median
+/// absolute deviation, scaled by 1.4826 so that normally distributed data gives values
+/// comparable to z-scores. Unlike the z-score, a few outliers in the window barely move
+/// the center or the scale. A window whose MAD is zero gives NaN.
+///
+/// # Formula
+/// ```text
+/// scaled = (x - median(x, period)) / (1.4826 × MAD(x, period))
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series (finite values)
+/// * `period` - Window length
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of scaled values; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::transform::robust_scale;
+///
+/// // The spike does not inflate the scale of its own window
+/// let data = vec![10.0, 11.0, 10.0, 11.0, 50.0];
+/// let result = robust_scale(&data, 5).unwrap();
+/// assert!((result[4] - 39.0 / 1.4826).abs() < 1e-9);
+/// ```
+pub fn robust_scale(data: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("ROBUST_SCALE", || {
+        let period = period.into_param("period")?.get();
+        let median = rolling_median(data, period)?;
+        let mad = rolling_mad(data, period)?;
+        let mut result = vec![f64::NAN; data.len()];
+        for i in (period - 1)..data.len() {
+            if mad[i] > 0.0 {
+                result[i] = (data[i] - median[i]) / (MAD_TO_STD_DEV * mad[i]);
+            }
+        }
+        Ok(result)
+    })
+}
+
+/// Hyperbolic tangent squashing
+///
+/// Maps each value into (-1, 1), nearly linearly for values well inside `scale` and
+/// saturating beyond it. NaNs are passed through.
+///
+/// # Formula
+/// ```text
+/// squashed = tanh(x / scale)
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `scale` - Positive scale of the linear region
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of values in (-1, 1)
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::transform::tanh_squash;
+///
+/// let result = tanh_squash(&[0.0, 1.0, -100.0], 2.0).unwrap();
+/// assert_eq!(result[0], 0.0);
+/// assert!((result[1] - 0.5f64.tanh()).abs() < 1e-12);
+/// assert!((result[2] + 1.0).abs() < 1e-12);
+/// ```
+pub fn tanh_squash(data: &[f64], scale: f64) -> TAResult<Vec<f64>> {
+    with_function("TANH_SQUASH", || {
+        validate_squash(data, scale)?;
+        Ok(data.iter().map(|&x| (x / scale).tanh()).collect())
+    })
+}
+
+/// Logistic (sigmoid) squashing
+///
+/// Maps each value into (0, 1), with 0 mapped to 0.5. NaNs are passed through.
+///
+/// # Formula
+/// ```text
+/// squashed = 1 / (1 + exp(-x / scale))
+/// ```
+///
+/// # Arguments
+/// * `data` - Input data series
+/// * `scale` - Positive scale of the linear region
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of values in (0, 1)
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::transform::sigmoid_squash;
+///
+/// let result = sigmoid_squash(&[0.0, 3.0, -3.0], 1.0).unwrap();
+/// assert_eq!(result[0], 0.5);
+/// assert!((result[1] + result[2] - 1.0).abs() < 1e-12);
+/// ```
+pub fn sigmoid_squash(data: &[f64], scale: f64) -> TAResult<Vec<f64>> {
+    with_function("SIGMOID_SQUASH", || {
+        validate_squash(data, scale)?;
+        Ok(data.iter().map(|&x| 1.0 / (1.0 + (-x / scale).exp())).collect())
+    })
+}
+
+fn validate_squash(data: &[f64], scale: f64) -> TAResult<()> {
+    validate_not_empty(data, "data")?;
+    if !(scale.is_finite() && scale > 0.0) {
+        return Err(TAError::invalid_parameter("scale", "must be positive and finite"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::common::assert_arrays_approx_equal;
+
+    #[test]
+    fn test_min_max_scale() {
+        let data = vec![5.0, 1.0, 3.0, 3.0, 2.0, 2.0, 2.0, 9.0];
+        let result = min_max_scale(&data, 3).unwrap();
+        assert!(result[..2].iter().all(|v| v.is_nan()));
+        assert_float_eq!(result[2], 0.5, 1e-12);
+        assert_float_eq!(result[3], 1.0, 1e-12);
+        assert_float_eq!(result[4], 0.0, 1e-12);
+        assert_float_eq!(result[5], 0.0, 1e-12);
+        // Flat window
+        assert!(result[6].is_nan());
+        assert_float_eq!(result[7], 1.0, 1e-12);
+
+        assert!(min_max_scale(&data, 9).is_err());
+        assert!(min_max_scale(&data, 0).is_err());
+    }
+
+    #[test]
+    fn test_robust_scale_matches_zscore_shape() {
+        let data: Vec<f64> = (0..60).map(|i| 50.0 + (i as f64 * 0.7).sin() * 2.0 + (i % 3) as f64).collect();
+        let robust = robust_scale(&data, 15).unwrap();
+        let median = rolling_median(&data, 15).unwrap();
+        let mad = rolling_mad(&data, 15).unwrap();
+        for i in 14..60 {
+            assert_float_eq!(robust[i], (data[i] - median[i]) / (1.4826 * mad[i]), 1e-12);
+        }
+        // Above the window median exactly when the z-score is clearly positive
+        let z = zscore(&data, 15).unwrap();
+        assert!((14..60).filter(|&i| z[i] > 1.0).all(|i| robust[i] > 0.0));
+        assert!(robust_scale(&[1.0, f64::NAN, 2.0], 2).is_err());
+    }
+
+    #[test]
+    fn test_normalize_dispatch_and_squash() {
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.4).cos() * 3.0).collect();
+        assert_arrays_approx_equal(&normalize(&data, Normalization::ZScore(10)).unwrap(), &zscore(&data, 10).unwrap(), 0.0);
+        assert_arrays_approx_equal(&normalize(&data, Normalization::MinMax(10)).unwrap(), &min_max_scale(&data, 10).unwrap(), 0.0);
+        assert_arrays_approx_equal(&normalize(&data, Normalization::Robust(10)).unwrap(), &robust_scale(&data, 10).unwrap(), 0.0);
+
+        let sigmoid = normalize(&data, Normalization::Sigmoid(2.0)).unwrap();
+        let tanh = normalize(&data, Normalization::Tanh(4.0)).unwrap();
+        for i in 0..40 {
+            // sigmoid(x / s) = (1 + tanh(x / 2s)) / 2
+            assert_float_eq!(sigmoid[i], (1.0 + tanh[i]) / 2.0, 1e-12);
+        }
+        assert!(tanh_squash(&[f64::NAN], 1.0).unwrap()[0].is_nan());
+        assert!(tanh_squash(&data, 0.0).is_err());
+        assert!(sigmoid_squash(&data, f64::INFINITY).is_err());
+        assert!(sigmoid_squash(&[], 1.0).is_err());
+    }
+}
@@ -3,8 +3,12 @@
 //! ATR is a volatility indicator that measures the average of true ranges over a specified period.
 //! It uses Wilder's smoothing method (exponential moving average with alpha = 1/period).
 
-use crate::common::{TAError, validate_period};
-use crate::volatility::trange;
+use crate::common::{TAError, Float, validate_same_length, validate_hlc_with, HlcValidation, Alpha, IntoParam, Period, TAOutput, TAResult, with_nan_policy, NanPolicy};
+use crate::common::errors::with_function;
+use crate::overlap::ewm::smoothed;
+use crate::volatility::trange::true_range;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Average True Range using Wilder's smoothing method.
 /// 
@@ -34,39 +38,135 @@ use crate::volatility::trange;
 /// let result = atr(&high, &low, &close, 3).unwrap();
 /// assert_eq!(result.len(), 7);
 /// ```
-pub fn atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    // Validate that arrays have same length and are not empty
-    if high.is_empty() || low.is_empty() || close.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
-    
-    if high.len() != low.len() || high.len() != close.len() {
-        return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length"));
-    }
-    
-    validate_period(period, "period")?;
+pub fn atr<T: Float>(high: &[T], low: &[T], close: &[T], period: impl IntoParam<Period>) -> Result<Vec<T>, TAError> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        let mut result = vec![T::NAN; high.len()];
+        atr_into(high, low, close, period, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Calculates the ATR with where its values start
+///
+/// Same as [`atr`], wrapped in a [`TAOutput`] with a lookback of `period - 1`.
+///
+/// # Errors
+/// Same as [`atr`]
+pub fn atr_ex(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<TAOutput> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(atr(high, low, close, period)?, period - 1))
+    })
+}
+
+/// Calculates the ATR of bars with missing values
+///
+/// Same as [`atr`], with NaN in any input handled according to `policy` rather than
+/// reaching the Wilder smoothing state and making every later value NaN; see
+/// [`with_nan_policy`](crate::common::with_nan_policy). Under [`NanPolicy::Skip`] a bar is
+/// skipped when any of high, low or close is NaN.
+///
+/// # Errors
+/// Same as [`atr`], plus `InvalidInput` if an input holds NaN under [`NanPolicy::Error`]
+pub fn atr_with_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: impl IntoParam<Period>,
+    policy: NanPolicy,
+) -> TAResult<Vec<f64>> {
+    with_function("ATR", || with_nan_policy(&[high, low, close], policy, |i| atr(i[0], i[1], i[2], period)))
+}
+
+/// Calculates Average True Range into a caller-provided buffer.
+/// 
+/// Allocation-free variant of [`atr`]: true ranges are computed inline instead of
+/// through an intermediate vector. `out` must have the same length as the inputs;
+/// its first `period-1` entries are set to NaN.
+/// 
+/// # Returns
+/// 
+/// Returns the number of valid values written (`high.len() - period + 1`).
+/// 
+/// # Example
+/// 
+/// ```
+/// use ta_rust::volatility::atr_into;
+/// 
+/// let high = [10.0, 11.0, 12.0, 11.5, 13.0];
+/// let low = [9.0, 10.0, 10.5, 10.0, 11.0];
+/// let close = [9.5, 10.5, 11.5, 10.5, 12.0];
+/// let mut out = [0.0; 5];
+/// 
+/// let valid = atr_into(&high, &low, &close, 3, &mut out).unwrap();
+/// assert_eq!(valid, 3);
+/// ```
+pub fn atr_into<T: Float>(high: &[T], low: &[T], close: &[T], period: impl IntoParam<Period>, out: &mut [T]) -> Result<usize, TAError> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        // Validate that arrays have same length and are not empty
+        if high.is_empty() || low.is_empty() || close.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    let len = high.len();
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
     
-    // Calculate True Range first
-    let tr_values = trange(high, low, close)?;
     
-    let mut result = vec![f64::NAN; len];
+        let len = high.len();
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
+        validate_same_length(high, out, "high", "out")?;
     
-    // Initialize ATR with SMA of first 'period' TR values
-    let initial_atr: f64 = tr_values[0..period].iter().sum::<f64>() / period as f64;
-    result[period - 1] = initial_atr;
+        Ok(atr_unchecked_into(high, low, close, period, out))
+    })
+}
+
+/// ATR of inputs already validated by the caller, with `1 <= period <= len`
+pub(crate) fn atr_unchecked_into<T: Float>(high: &[T], low: &[T], close: &[T], period: usize, out: &mut [T]) -> usize {
+    atr_custom_unchecked_into(high, low, close, period, T::ONE / T::from_usize(period), out)
+}
+
+/// ATR smoothed with `alpha` into `out`, without validating the inputs
+pub(crate) fn atr_custom_unchecked_into<T: Float>(high: &[T], low: &[T], close: &[T], period: usize, alpha: T, out: &mut [T]) -> usize {
+    let tr_at = |i: usize| {
+        if i == 0 {
+            high[0] - low[0]
+        } else {
+            true_range(high[i], low[i], close[i - 1])
+        }
+    };
+    out[..period - 1].fill(T::NAN);
     
-    // Apply Wilder's smoothing for remaining values
-    let alpha = 1.0 / period as f64;
-    for i in period..len {
-        result[i] = alpha * tr_values[i] + (1.0 - alpha) * result[i - 1];
+    // Seeded with the SMA of the first 'period' TR values
+    for (value, average) in out[period - 1..].iter_mut().zip(smoothed((0..high.len()).map(tr_at), alpha, period)) {
+        *value = average;
     }
     
-    Ok(result)
+    high.len() - period + 1
+}
+
+/// ATR as TA-Lib computes it: the first bar has no previous close and no true range, so the
+/// average is seeded with the true ranges of bars 1 to `period` and starts a bar later
+pub(crate) fn atr_talib(high: &[f64], low: &[f64], close: &[f64], period: usize) -> TAResult<Vec<f64>> {
+    with_function("ATR", || {
+        if high.is_empty() || low.is_empty() || close.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        let len = high.len();
+        if len <= period {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+
+        let mut result = vec![f64::NAN; len];
+        let true_ranges = (1..len).map(|i| true_range(high[i], low[i], close[i - 1]));
+        for (value, average) in result[period..].iter_mut().zip(smoothed(true_ranges, 1.0 / period as f64, period)) {
+            *value = average;
+        }
+        Ok(result)
+    })
 }
 
 /// Calculates ATR using OHLC data structure.
@@ -96,21 +196,26 @@ pub fn atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<Ve
 /// let result = atr_ohlc(&data, 3).unwrap();
 /// assert_eq!(result.len(), 4);
 /// ```
-pub fn atr_ohlc(ohlc: &[crate::common::types::OHLC], period: usize) -> Result<Vec<f64>, TAError> {
-    if ohlc.is_empty() {
-        return Err(TAError::invalid_input("OHLC data cannot be empty"));
-    }
+pub fn atr_ohlc(ohlc: &[crate::common::types::OHLC], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        if ohlc.is_empty() {
+            return Err(TAError::invalid_input("OHLC data cannot be empty"));
+        }
     
-    let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
-    let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
-    let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
+        let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
+        let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
+        let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
     
-    atr(&high, &low, &close, period)
+        atr(&high, &low, &close, period)
+    })
 }
 
 /// Calculates ATR with custom smoothing factor.
 /// 
 /// This allows for different smoothing methods beyond Wilder's standard approach.
+/// The first value is the SMA of the first `period` true ranges, as in [`atr`];
+/// with `alpha = 1 / period` the result equals [`atr`].
 /// 
 /// # Arguments
 /// 
@@ -137,42 +242,27 @@ pub fn atr_ohlc(ohlc: &[crate::common::types::OHLC], period: usize) -> Result<Ve
 /// let result = atr_custom(&high, &low, &close, 3, 0.2).unwrap();
 /// assert_eq!(result.len(), 5);
 /// ```
-pub fn atr_custom(high: &[f64], low: &[f64], close: &[f64], period: usize, alpha: f64) -> Result<Vec<f64>, TAError> {
-    // Validate that arrays have same length and are not empty
-    if high.is_empty() || low.is_empty() || close.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
-    
-    if high.len() != low.len() || high.len() != close.len() {
-        return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length"));
-    }
-    
-    validate_period(period, "period")?;
-    
-    if alpha <= 0.0 || alpha > 1.0 {
-        return Err(TAError::invalid_input("Alpha must be between 0 and 1"));
-    }
-    
-    let len = high.len();
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
+pub fn atr_custom(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, alpha: impl IntoParam<Alpha>) -> Result<Vec<f64>, TAError> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        let alpha = alpha.into_param("alpha")?.get();
+        // Validate that arrays have same length and are not empty
+        if high.is_empty() || low.is_empty() || close.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    // Calculate True Range first
-    let tr_values = trange(high, low, close)?;
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
     
-    let mut result = vec![f64::NAN; len];
+        let len = high.len();
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
     
-    // Initialize ATR with SMA of first 'period' TR values
-    let initial_atr: f64 = tr_values[0..period].iter().sum::<f64>() / period as f64;
-    result[period - 1] = initial_atr;
+        let mut result = vec![f64::NAN; len];
+        atr_custom_unchecked_into(high, low, close, period, alpha, &mut result);
     
-    // Apply custom smoothing for remaining values
-    for i in period..len {
-        result[i] = alpha * tr_values[i] + (1.0 - alpha) * result[i - 1];
-    }
-    
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculates ATR percentage relative to price.
@@ -203,26 +293,126 @@ pub fn atr_custom(high: &[f64], low: &[f64], close: &[f64], period: usize, alpha
 /// let result = atr_percent(&high, &low, &close, 3).unwrap();
 /// assert_eq!(result.len(), 5);
 /// ```
-pub fn atr_percent(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    let atr_values = atr(high, low, close, period)?;
+pub fn atr_percent(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("ATR", || {
+        let period = period.into_param("period")?.get();
+        let atr_values = atr(high, low, close, period)?;
     
-    let mut result = Vec::with_capacity(atr_values.len());
+        let mut result = Vec::with_capacity(atr_values.len());
     
-    for (i, &atr_val) in atr_values.iter().enumerate() {
-        if atr_val.is_nan() || close[i] == 0.0 {
-            result.push(f64::NAN);
-        } else {
-            result.push(100.0 * atr_val / close[i]);
+        for (i, &atr_val) in atr_values.iter().enumerate() {
+            if atr_val.is_nan() || close[i] == 0.0 {
+                result.push(f64::NAN);
+            } else {
+                result.push(100.0 * atr_val / close[i]);
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
+}
+
+/// Streaming Average True Range
+///
+/// Produces the same values as [`atr`] one bar at a time, in O(1) time and memory.
+///
+/// # Example
+/// ```rust
+/// use ta_rust::volatility::{atr, AtrState};
+///
+/// let high = vec![10.0, 11.0, 12.0, 11.5, 13.0, 12.5, 14.0];
+/// let low = vec![9.0, 10.0, 10.5, 10.0, 11.0, 11.5, 12.0];
+/// let close = vec![9.5, 10.5, 11.5, 10.5, 12.0, 12.0, 13.0];
+/// let batch = atr(&high, &low, &close, 3).unwrap();
+///
+/// let mut state = AtrState::new(3).unwrap();
+/// assert_eq!(state.bars_until_warm(), 3);
+/// for i in 0..close.len() {
+///     match state.update(high[i], low[i], close[i]) {
+///         Some(value) => assert!((value - batch[i]).abs() < 1e-12),
+///         None => assert!(batch[i].is_nan()),
+///     }
+/// }
+/// assert!(state.is_warm());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AtrState {
+    period: usize,
+    /// Number of bars seen
+    count: usize,
+    prev_close: Option<f64>,
+    /// Sum of the true ranges of the first `period` bars
+    seed_sum: f64,
+    value: Option<f64>,
+}
+
+impl AtrState {
+    /// Creates a new state for the given period
+    ///
+    /// # Errors
+    /// - `InvalidParameter` if period is 0
+    pub fn new(period: impl IntoParam<Period>) -> Result<Self, TAError> {
+        with_function("ATR", || {
+            let period = period.into_param("period")?.get();
+            Ok(Self { period, count: 0, prev_close: None, seed_sum: 0.0, value: None })
+        })
+    }
+
+    /// Feeds the next bar and returns the updated ATR, or `None` during warm-up
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let tr = match self.prev_close {
+            Some(prev_close) => true_range(high, low, prev_close),
+            None => high - low,
+        };
+        self.prev_close = Some(close);
+        self.count += 1;
+
+        self.value = match self.value {
+            Some(prev) => {
+                let alpha = 1.0 / self.period as f64;
+                Some(alpha * tr + (1.0 - alpha) * prev)
+            }
+            None => {
+                self.seed_sum += tr;
+                (self.count == self.period).then(|| self.seed_sum / self.period as f64)
+            }
+        };
+        self.value
+    }
+
+    /// Current ATR, or `None` during warm-up
+    pub fn current(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Whether the ATR has a value
+    pub fn is_warm(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// Number of further bars needed before the ATR has a value
+    pub fn bars_until_warm(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+
+    /// Period of the ATR
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Clears all state, as if newly created
+    pub fn reset(&mut self) {
+        *self = Self { period: self.period, count: 0, prev_close: None, seed_sum: 0.0, value: None };
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::volatility::trange;
     use crate::common::types::OHLC;
+    use crate::common::assert_arrays_approx_equal;
 
     #[test]
     fn test_atr_basic() {
@@ -248,6 +438,22 @@ mod tests {
         assert!(!result[4].is_nan());
     }
 
+    #[test]
+    fn test_atr_talib_seed() {
+        let high = vec![10.0, 11.0, 12.0, 11.5, 13.0, 12.5];
+        let low = vec![9.0, 10.0, 10.5, 10.0, 11.0, 11.5];
+        let close = vec![9.5, 10.5, 11.5, 10.5, 12.0, 12.0];
+
+        // Seeded with the true ranges of bars 1 to 3, skipping the first bar's high - low
+        let result = atr_talib(&high, &low, &close, 3).unwrap();
+        let tr_values = trange(&high, &low, &close).unwrap();
+        assert!(result[..3].iter().all(|v| v.is_nan()));
+        let seed = (tr_values[1] + tr_values[2] + tr_values[3]) / 3.0;
+        assert!((result[3] - seed).abs() < 1e-12);
+        assert!((result[4] - (seed * 2.0 + tr_values[4]) / 3.0).abs() < 1e-12);
+        assert!(atr_talib(&high[..3], &low[..3], &close[..3], 3).is_err());
+    }
+
     #[test]
     fn test_atr_wilder_smoothing() {
         let high = vec![10.0, 11.0, 12.0, 11.5, 13.0, 12.5];
@@ -279,7 +485,7 @@ mod tests {
         let result = atr(&high, &low, &close, 5);
         assert!(result.is_err());
         
-        if let Err(TAError::InsufficientData { required, provided }) = result {
+        if let Err(TAError::InsufficientData { required, provided, .. }) = result {
             assert_eq!(required, 5);
             assert_eq!(provided, 2);
         } else {
@@ -289,7 +495,7 @@ mod tests {
 
     #[test]
     fn test_atr_empty_data() {
-        let high = vec![];
+        let high: Vec<f64> = vec![];
         let low = vec![];
         let close = vec![];
         
@@ -307,6 +513,18 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_atr_inconsistent_bars() {
+        let high = vec![10.0, 11.0, 12.0];
+        let low = vec![9.0, 11.5, 10.5];
+        let close = vec![9.5, 11.2, 11.5];
+        assert!(atr(&high, &low, &close, 2).is_err());
+
+        // Gaps are not inconsistent; the NaN propagates instead
+        let close = vec![9.5, f64::NAN, 11.5];
+        assert!(atr(&high, &[9.0, 10.0, 10.5], &close, 2).is_ok());
+    }
+
     #[test]
     fn test_atr_period_1() {
         let high = vec![10.0, 11.0, 12.0];
@@ -354,6 +572,26 @@ mod tests {
         assert!(!result[2].is_nan());
     }
 
+    #[test]
+    fn test_atr_custom_alpha_changes_smoothing() {
+        let high: Vec<f64> = (0..40).map(|i| 52.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let low: Vec<f64> = high.iter().enumerate().map(|(i, h)| h - 1.0 - (i % 5) as f64 * 0.4).collect();
+        let close: Vec<f64> = high.iter().zip(&low).map(|(h, l)| (h + l) / 2.0).collect();
+        
+        let wilder = atr(&high, &low, &close, 7).unwrap();
+        let same = atr_custom(&high, &low, &close, 7, 1.0 / 7.0).unwrap();
+        crate::common::assert_arrays_approx_equal(&same, &wilder, 1e-12);
+        
+        let fast = atr_custom(&high, &low, &close, 7, 0.5).unwrap();
+        assert_eq!(fast[6], wilder[6]);
+        assert!((fast[7..].iter().zip(&wilder[7..])).all(|(f, w)| (f - w).abs() > 1e-9));
+        
+        // alpha = 1 follows the true range itself
+        let tr = trange(&high, &low, &close).unwrap();
+        let raw = atr_custom(&high, &low, &close, 7, 1.0).unwrap();
+        crate::common::assert_arrays_approx_equal(&raw[7..], &tr[7..], 1e-12);
+    }
+
     #[test]
     fn test_atr_custom_invalid_alpha() {
         let high = vec![10.0, 11.0, 12.0];
@@ -457,4 +695,57 @@ mod tests {
         // Later ATR values should be higher due to increasing volatility
         assert!(result[5] > result[2]);
     }
+
+    #[test]
+    fn test_atr_into_matches_atr() {
+        let high = vec![10.0, 11.0, 12.0, 11.5, 13.0, 12.5, 14.0];
+        let low = vec![9.0, 10.0, 10.5, 10.0, 11.0, 11.5, 12.0];
+        let close = vec![9.5, 10.5, 11.5, 10.5, 12.0, 12.0, 13.0];
+        
+        let expected = atr(&high, &low, &close, 3).unwrap();
+        let mut out = vec![0.0; high.len()];
+        let valid = atr_into(&high, &low, &close, 3, &mut out).unwrap();
+        
+        assert_eq!(valid, 5);
+        assert!(out[0].is_nan() && out[1].is_nan());
+        for i in 2..out.len() {
+            assert!((out[i] - expected[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_atr_state_matches_batch() {
+        let close: Vec<f64> = (0..80).map(|i| 50.0 + (i as f64 * 0.2).sin() * 4.0).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 0.5 + (i % 5) as f64 * 0.2).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 0.3 - (i % 3) as f64 * 0.4).collect();
+
+        for period in [1, 2, 14] {
+            let batch = atr(&high, &low, &close, period).unwrap();
+            let mut state = AtrState::new(period).unwrap();
+            for i in 0..close.len() {
+                assert_eq!(state.bars_until_warm(), period.saturating_sub(i));
+                match state.update(high[i], low[i], close[i]) {
+                    Some(value) => assert!((value - batch[i]).abs() < 1e-10, "{} at {}", period, i),
+                    None => assert!(batch[i].is_nan()),
+                }
+                assert_eq!(state.is_warm(), !batch[i].is_nan());
+            }
+            state.reset();
+            assert_eq!(state, AtrState::new(period).unwrap());
+        }
+
+        assert!(AtrState::new(0).is_err());
+    }
+
+    #[test]
+    fn test_atr_ex_warmup() {
+        let close: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.3).cos() * 2.0).collect();
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 1.0 + (i % 4) as f64 * 0.3).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+
+        let output = atr_ex(&high, &low, &close, 10).unwrap();
+        assert_eq!((output.first_valid, output.lookback), (9, 9));
+        assert_arrays_approx_equal(&output.values, &atr(&high, &low, &close, 10).unwrap(), 0.0);
+        assert!(atr_ex(&high, &low, &close, 0).is_err());
+    }
 }
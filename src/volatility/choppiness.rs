@@ -0,0 +1,115 @@
+//! Choppiness Index (CHOP)
+//!
+//! E.W. Dreiss' Choppiness Index compares the total distance travelled by price (the sum of
+//! true ranges) with the net range covered. Values near 100 indicate sideways, choppy trading;
+//! values near 0 indicate a strong trend. 61.8 and 38.2 are the usual regime thresholds.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::{max, min, sum};
+use crate::volatility::trange;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Choppiness Index
+///
+/// # Formula
+/// ```text
+/// CHOP = 100 × log10(SUM(TrueRange, n) / (Highest(High, n) - Lowest(Low, n))) / log10(n)
+/// ```
+///
+/// The true range of the first bar is its high-low range, as in [`trange`].
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - Lookback period; must be at least 2 (typically 14)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of CHOP values; the first `period-1` values are NaN, as are
+///   windows with no range
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::choppiness;
+///
+/// // A steady trend of non-overlapping bars covers its whole path: CHOP = 0
+/// let high: Vec<f64> = (0..20).map(|i| 11.0 + i as f64).collect();
+/// let low: Vec<f64> = (0..20).map(|i| 10.0 + i as f64).collect();
+/// let close = high.clone();
+/// let result = choppiness(&high, &low, &close, 14).unwrap();
+/// assert!(result[12].is_nan());
+/// assert!(result[13].abs() < 1e-10);
+/// ```
+pub fn choppiness(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("CHOP", || {
+        let period = period.into_param("period")?.get();
+        if period < 2 {
+            return Err(TAError::invalid_parameter("period", "must be at least 2"));
+        }
+
+        let true_range = trange(high, low, close)?;
+        let path = sum(&true_range, period)?;
+        let highest = max(high, period)?;
+        let lowest = min(low, period)?;
+
+        let log_period = (period as f64).log10();
+
+        Ok(path
+            .iter()
+            .zip(highest.iter().zip(&lowest))
+            .map(|(&total, (&hh, &ll))| {
+                let range = hh - ll;
+                if range > 0.0 {
+                    100.0 * (total / range).log10() / log_period
+                } else {
+                    f64::NAN
+                }
+            })
+            .collect())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_choppiness_basic() {
+        let high = vec![10.0, 11.0, 10.5, 11.5, 11.0];
+        let low = vec![9.0, 9.5, 9.5, 10.0, 10.0];
+        let close = vec![9.5, 10.5, 10.0, 11.0, 10.5];
+        let result = choppiness(&high, &low, &close, 3).unwrap();
+
+        assert!(result[1].is_nan());
+        // TR: 1.0, 1.5, 1.0 over a 9.0..11.0 range
+        let expected = 100.0 * (3.5f64 / 2.0).log10() / 3.0f64.log10();
+        assert_float_eq!(result[2], expected, 1e-12);
+    }
+
+    #[test]
+    fn test_choppiness_sideways_is_high() {
+        // Bars overlapping each other inside the same band
+        let high: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 11.0 } else { 10.6 }).collect();
+        let low: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 9.4 } else { 9.0 }).collect();
+        let close: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 10.8 } else { 9.2 }).collect();
+        let result = choppiness(&high, &low, &close, 14).unwrap();
+        assert!(result[13..].iter().all(|&v| v > 61.8));
+    }
+
+    #[test]
+    fn test_choppiness_invalid_input() {
+        let prices = vec![1.0, 2.0, 3.0];
+        assert!(choppiness(&[], &[], &[], 2).is_err());
+        assert!(choppiness(&prices, &prices[..2], &prices, 2).is_err());
+        assert!(choppiness(&prices, &prices, &prices, 1).is_err());
+        assert!(choppiness(&prices, &prices, &prices, 4).is_err());
+
+        // A window without range has no defined value
+        let flat = vec![5.0; 4];
+        assert!(choppiness(&flat, &flat, &flat, 2).unwrap()[1].is_nan());
+    }
+}
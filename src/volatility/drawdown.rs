@@ -0,0 +1,199 @@
+//! Drawdown measures
+//!
+//! Drawdown is the decline of a price (or equity) series from its previous peak. These
+//! functions report how deep the current decline is, how long it has lasted, and the worst
+//! decline seen inside a rolling window, for risk-adjusted screening alongside
+//! [`ulcer_index`](crate::volatility::ulcer_index).
+//!
+//! All depths are positive percentages below the peak; peaks at or below zero are treated
+//! as having no drawdown.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_prices, validate_sufficient_data};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Percentage decline of `price` below `peak`
+fn decline(peak: f64, price: f64) -> f64 {
+    if peak > 0.0 {
+        100.0 * (peak - price) / peak
+    } else {
+        0.0
+    }
+}
+
+/// Drawdown from the running peak
+///
+/// # Formula
+/// ```text
+/// Peak     = max(Price[0..=i])
+/// Drawdown = 100 × (Peak - Price) / Peak
+/// ```
+///
+/// # Arguments
+/// * `prices` - Slice of prices
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Drawdown in percent (0 at a new high)
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::drawdown;
+///
+/// let prices = vec![100.0, 120.0, 90.0, 130.0];
+/// let result = drawdown(&prices).unwrap();
+/// assert_eq!(result, vec![0.0, 0.0, 25.0, 0.0]);
+/// ```
+pub fn drawdown(prices: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("DRAWDOWN", || {
+        validate_not_empty(prices, "prices")?;
+        validate_prices(prices, "prices")?;
+
+        let mut peak = f64::NEG_INFINITY;
+        Ok(prices
+            .iter()
+            .map(|&price| {
+                peak = peak.max(price);
+                decline(peak, price)
+            })
+            .collect())
+    })
+}
+
+/// Drawdown duration: bars since the running peak
+///
+/// # Arguments
+/// * `prices` - Slice of prices
+///
+/// # Returns
+/// * `Ok(Vec<usize>)` - Number of bars since the last high (0 on a bar that sets or equals it)
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::drawdown_duration;
+///
+/// let prices = vec![100.0, 120.0, 90.0, 110.0, 120.0, 115.0];
+/// let result = drawdown_duration(&prices).unwrap();
+/// assert_eq!(result, vec![0, 0, 1, 2, 0, 1]);
+/// ```
+pub fn drawdown_duration(prices: &[f64]) -> TAResult<Vec<usize>> {
+    with_function("DRAWDOWN", || {
+        validate_not_empty(prices, "prices")?;
+        validate_prices(prices, "prices")?;
+
+        let mut peak = f64::NEG_INFINITY;
+        let mut bars = 0;
+        Ok(prices
+            .iter()
+            .map(|&price| {
+                if price >= peak {
+                    peak = price;
+                    bars = 0;
+                } else {
+                    bars += 1;
+                }
+                bars
+            })
+            .collect())
+    })
+}
+
+/// Rolling maximum drawdown
+///
+/// The deepest peak-to-trough decline within each window of `period` bars, where the peak
+/// must come before the trough.
+///
+/// # Arguments
+/// * `prices` - Slice of prices
+/// * `period` - Window length
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Maximum drawdown in percent; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::rolling_max_drawdown;
+///
+/// let prices = vec![100.0, 80.0, 120.0, 90.0, 95.0];
+/// let result = rolling_max_drawdown(&prices, 3).unwrap();
+/// assert!(result[1].is_nan());
+/// assert_eq!(result[2], 20.0);  // 100 → 80
+/// assert_eq!(result[3], 25.0);  // 120 → 90
+/// ```
+pub fn rolling_max_drawdown(prices: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("ROLLING_MAX_DRAWDOWN", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(prices, "prices")?;
+        validate_sufficient_data(prices, period, "prices")?;
+        validate_prices(prices, "prices")?;
+
+        let mut result = vec![f64::NAN; prices.len()];
+        for (value, window) in result[period - 1..].iter_mut().zip(prices.windows(period)) {
+            let mut peak = f64::NEG_INFINITY;
+            let mut worst: f64 = 0.0;
+            for &price in window {
+                peak = peak.max(price);
+                worst = worst.max(decline(peak, price));
+            }
+            *value = worst;
+        }
+
+        Ok(result)
+    })
+}
+
+/// Maximum drawdown over the whole series
+///
+/// # Returns
+/// * `Ok(f64)` - Deepest peak-to-trough decline in percent
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn max_drawdown(prices: &[f64]) -> TAResult<f64> {
+    with_function("MAX_DRAWDOWN", || {
+        let series = drawdown(prices)?;
+        series
+            .into_iter()
+            .reduce(f64::max)
+            .ok_or_else(|| TAError::invalid_input("Prices cannot be empty"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_drawdown_series() {
+        let prices = vec![50.0, 40.0, 45.0, 60.0, 30.0];
+        let dd = drawdown(&prices).unwrap();
+        assert_eq!(dd[..4], [0.0, 20.0, 10.0, 0.0]);
+        assert_float_eq!(dd[4], 50.0, 1e-12);
+
+        assert_eq!(drawdown_duration(&prices).unwrap(), vec![0, 1, 2, 0, 1]);
+        assert_float_eq!(max_drawdown(&prices).unwrap(), 50.0, 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_max_drawdown_matches_full_window() {
+        let prices: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.4).sin() * 10.0).collect();
+        let rolling = rolling_max_drawdown(&prices, 40).unwrap();
+        assert_float_eq!(rolling[39], max_drawdown(&prices).unwrap(), 1e-12);
+
+        // A recovery after the trough does not reduce the window's maximum drawdown
+        let result = rolling_max_drawdown(&[10.0, 5.0, 10.0, 12.0], 4).unwrap();
+        assert_eq!(result[3], 50.0);
+    }
+
+    #[test]
+    fn test_drawdown_invalid_input() {
+        assert!(drawdown(&[]).is_err());
+        assert!(drawdown_duration(&[1.0, f64::INFINITY]).is_err());
+        assert!(rolling_max_drawdown(&[1.0, 2.0], 3).is_err());
+        assert!(rolling_max_drawdown(&[1.0, 2.0], 0).is_err());
+        assert!(max_drawdown(&[]).is_err());
+    }
+}
@@ -0,0 +1,414 @@
+//! Historical (realized) volatility estimators
+//!
+//! Rolling-window estimators of annualized volatility. Close-to-close is the textbook
+//! standard deviation of log returns; the range-based estimators use the open, high and low
+//! as well and are several times more efficient on the same number of bars:
+//!
+//! * [`parkinson`] - high/low range; assumes no drift and no opening gaps
+//! * [`garman_klass`] - adds open and close; assumes no drift and no opening gaps
+//! * [`rogers_satchell`] - unbiased under drift; ignores opening gaps
+//! * [`yang_zhang`] - combines overnight, open-to-close and Rogers-Satchell variances;
+//!   handles both drift and gaps
+//!
+//! Every estimator returns volatility as a fraction (0.2 = 20%), scaled by the square root
+//! of `trading_days` (252 for equities, 365 for crypto). Prices must be positive.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::sum;
+use crate::statistic::var;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Checks that every series has the same non-zero length and only positive, finite prices
+fn validate_series(series: &[&[f64]]) -> TAResult<usize> {
+    let len = series[0].len();
+    if len == 0 {
+        return Err(TAError::invalid_input("Input arrays cannot be empty"));
+    }
+    if series.iter().any(|s| s.len() != len) {
+        return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
+    }
+    for s in series {
+        if let Some((i, &price)) = s.iter().enumerate().find(|(_, &p)| !(p.is_finite() && p > 0.0)) {
+            return Err(TAError::invalid_input(format!(
+                "Prices must be positive and finite, found {} at index {}",
+                price, i
+            )));
+        }
+    }
+    Ok(len)
+}
+
+fn validate_trading_days(trading_days: f64) -> TAResult<()> {
+    if !trading_days.is_finite() || trading_days <= 0.0 {
+        return Err(TAError::invalid_parameter("trading_days", "must be positive"));
+    }
+    Ok(())
+}
+
+/// Window checks for estimators built on one-bar returns: `period` returns need `period + 1` bars
+fn validate_window(period: usize, len: usize) -> TAResult<()> {
+    if period < 2 {
+        return Err(TAError::invalid_parameter("period", "must be at least 2"));
+    }
+    if len <= period {
+        return Err(TAError::insufficient_data(period + 1, len));
+    }
+    Ok(())
+}
+
+/// Rolling sample variance (n - 1 denominator)
+fn sample_var(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
+    let scale = period as f64 / (period - 1) as f64;
+    Ok(var(data, period)?.into_iter().map(|v| v * scale).collect())
+}
+
+/// Annualized volatility from a per-bar variance; rounding can leave tiny negative variances
+fn annualize(variance: f64, trading_days: f64) -> f64 {
+    if variance < 0.0 {
+        0.0
+    } else {
+        (variance * trading_days).sqrt()
+    }
+}
+
+/// Rolling mean of per-bar variance terms, annualized
+fn annualize_mean(terms: &[f64], period: usize, trading_days: f64) -> TAResult<Vec<f64>> {
+    Ok(sum(terms, period)?
+        .into_iter()
+        .map(|total| annualize(total / period as f64, trading_days))
+        .collect())
+}
+
+/// Close-to-close volatility
+///
+/// # Formula
+/// ```text
+/// r = ln(Close / Close[prev])
+/// σ = StdDev_sample(r, period) × sqrt(trading_days)
+/// ```
+///
+/// # Arguments
+/// * `close` - Slice of closing prices
+/// * `period` - Number of returns in the window; must be at least 2
+/// * `trading_days` - Bars per year used for annualization
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized volatility; the first `period` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::estimators::close_to_close;
+///
+/// let close: Vec<f64> = (0..30).map(|i| 100.0 * (1.0 + 0.01 * (i as f64).sin())).collect();
+/// let vol = close_to_close(&close, 20, 252.0).unwrap();
+/// assert!(vol[19].is_nan());
+/// assert!(vol[20] > 0.0);
+/// ```
+pub fn close_to_close(close: &[f64], period: impl IntoParam<Period>, trading_days: f64) -> TAResult<Vec<f64>> {
+    with_function("HV", || {
+        let period = period.into_param("period")?.get();
+        let len = validate_series(&[close])?;
+        validate_window(period, len)?;
+        validate_trading_days(trading_days)?;
+
+        let returns: Vec<f64> = close.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+
+        let mut result = vec![f64::NAN; len];
+        for (out, variance) in result[1..].iter_mut().zip(sample_var(&returns, period)?) {
+            *out = annualize(variance, trading_days);
+        }
+        Ok(result)
+    })
+}
+
+/// Parkinson volatility
+///
+/// # Formula
+/// ```text
+/// σ² = (1 / (4 ln 2)) × mean(ln(High / Low)²)
+/// ```
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `period` - Window length
+/// * `trading_days` - Bars per year used for annualization
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized volatility; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn parkinson(high: &[f64], low: &[f64], period: impl IntoParam<Period>, trading_days: f64) -> TAResult<Vec<f64>> {
+    with_function("PKVOL", || {
+        let period = period.into_param("period")?.get();
+        let len = validate_series(&[high, low])?;
+        validate_trading_days(trading_days)?;
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let factor = 1.0 / (4.0 * core::f64::consts::LN_2);
+        let terms: Vec<f64> = high
+            .iter()
+            .zip(low)
+            .map(|(&h, &l)| factor * (h / l).ln().powi(2))
+            .collect();
+
+        annualize_mean(&terms, period, trading_days)
+    })
+}
+
+/// Garman-Klass volatility
+///
+/// # Formula
+/// ```text
+/// σ² = mean(0.5 × ln(High / Low)² - (2 ln 2 - 1) × ln(Close / Open)²)
+/// ```
+///
+/// # Arguments
+/// * `open` - Slice of opening prices
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - Window length
+/// * `trading_days` - Bars per year used for annualization
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized volatility; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn garman_klass(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: impl IntoParam<Period>,
+    trading_days: f64,
+) -> TAResult<Vec<f64>> {
+    with_function("GKVOL", || {
+        let period = period.into_param("period")?.get();
+        let len = validate_series(&[open, high, low, close])?;
+        validate_trading_days(trading_days)?;
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let k = 2.0 * core::f64::consts::LN_2 - 1.0;
+        let terms: Vec<f64> = (0..len)
+            .map(|i| 0.5 * (high[i] / low[i]).ln().powi(2) - k * (close[i] / open[i]).ln().powi(2))
+            .collect();
+
+        annualize_mean(&terms, period, trading_days)
+    })
+}
+
+/// Rogers-Satchell per-bar variance term
+fn rogers_satchell_term(open: f64, high: f64, low: f64, close: f64) -> f64 {
+    (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+}
+
+/// Rogers-Satchell volatility
+///
+/// # Formula
+/// ```text
+/// σ² = mean(ln(High / Close) × ln(High / Open) + ln(Low / Close) × ln(Low / Open))
+/// ```
+///
+/// # Arguments
+/// * `open` - Slice of opening prices
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - Window length
+/// * `trading_days` - Bars per year used for annualization
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized volatility; the first `period-1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn rogers_satchell(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: impl IntoParam<Period>,
+    trading_days: f64,
+) -> TAResult<Vec<f64>> {
+    with_function("RSVOL", || {
+        let period = period.into_param("period")?.get();
+        let len = validate_series(&[open, high, low, close])?;
+        validate_trading_days(trading_days)?;
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
+
+        let terms: Vec<f64> = (0..len)
+            .map(|i| rogers_satchell_term(open[i], high[i], low[i], close[i]))
+            .collect();
+
+        annualize_mean(&terms, period, trading_days)
+    })
+}
+
+/// Yang-Zhang volatility
+///
+/// # Formula
+/// ```text
+/// σo² = Var_sample(ln(Open / Close[prev]))          overnight
+/// σc² = Var_sample(ln(Close / Open))                open to close
+/// σrs² = mean(Rogers-Satchell term)
+/// k   = 0.34 / (1.34 + (n + 1) / (n - 1))
+/// σ²  = σo² + k × σc² + (1 - k) × σrs²
+/// ```
+///
+/// # Arguments
+/// * `open` - Slice of opening prices
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `period` - Window length; must be at least 2
+/// * `trading_days` - Bars per year used for annualization
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Annualized volatility; the first `period` values are NaN, since the
+///   overnight return needs the previous close
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::estimators::{rogers_satchell, yang_zhang};
+///
+/// let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 2.0).collect();
+/// let open: Vec<f64> = close.iter().map(|c| c * 0.998).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c * 1.01).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c * 0.99).collect();
+///
+/// let yz = yang_zhang(&open, &high, &low, &close, 20, 252.0).unwrap();
+/// let rs = rogers_satchell(&open, &high, &low, &close, 20, 252.0).unwrap();
+/// assert!(yz[19].is_nan());
+/// assert!(yz[20] > 0.0 && rs[20] > 0.0);
+/// ```
+pub fn yang_zhang(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: impl IntoParam<Period>,
+    trading_days: f64,
+) -> TAResult<Vec<f64>> {
+    with_function("YZVOL", || {
+        let period = period.into_param("period")?.get();
+        let len = validate_series(&[open, high, low, close])?;
+        validate_window(period, len)?;
+        validate_trading_days(trading_days)?;
+
+        // All three components start at bar 1, where the previous close exists
+        let overnight: Vec<f64> = (1..len).map(|i| (open[i] / close[i - 1]).ln()).collect();
+        let open_close: Vec<f64> = (1..len).map(|i| (close[i] / open[i]).ln()).collect();
+        let rs_terms: Vec<f64> = (1..len)
+            .map(|i| rogers_satchell_term(open[i], high[i], low[i], close[i]))
+            .collect();
+
+        let overnight_var = sample_var(&overnight, period)?;
+        let open_close_var = sample_var(&open_close, period)?;
+        let rs_sum = sum(&rs_terms, period)?;
+
+        let n = period as f64;
+        let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+
+        let mut result = vec![f64::NAN; len];
+        for (j, out) in result[1..].iter_mut().enumerate().skip(period - 1) {
+            let variance = overnight_var[j] + k * open_close_var[j] + (1.0 - k) * rs_sum[j] / n;
+            *out = annualize(variance, trading_days);
+        }
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    fn sample(len: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+        let close: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64 * 0.35).sin() * 3.0 + i as f64 * 0.1).collect();
+        let open: Vec<f64> = (0..len)
+            .map(|i| if i == 0 { close[0] } else { close[i - 1] * (1.0 + 0.002 * (i as f64).cos()) })
+            .collect();
+        let high: Vec<f64> = (0..len).map(|i| open[i].max(close[i]) * 1.006).collect();
+        let low: Vec<f64> = (0..len).map(|i| open[i].min(close[i]) * 0.994).collect();
+        (open, high, low, close)
+    }
+
+    #[test]
+    fn test_close_to_close() {
+        let close = vec![100.0, 101.0, 99.0, 102.0, 100.0];
+        let vol = close_to_close(&close, 3, 252.0).unwrap();
+
+        assert!(vol[2].is_nan());
+        let r: Vec<f64> = close.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let mean = (r[0] + r[1] + r[2]) / 3.0;
+        let variance = r[..3].iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 2.0;
+        assert_float_eq!(vol[3], (variance * 252.0).sqrt(), 1e-12);
+    }
+
+    #[test]
+    fn test_range_estimators() {
+        let (open, high, low, close) = sample(30);
+        let pk = parkinson(&high, &low, 10, 252.0).unwrap();
+        let gk = garman_klass(&open, &high, &low, &close, 10, 252.0).unwrap();
+        let rs = rogers_satchell(&open, &high, &low, &close, 10, 252.0).unwrap();
+
+        assert!(pk[8].is_nan() && gk[8].is_nan() && rs[8].is_nan());
+
+        let mean = |f: &dyn Fn(usize) -> f64| (0..10).map(f).sum::<f64>() / 10.0;
+        let expected_pk = mean(&|i| (high[i] / low[i]).ln().powi(2)) / (4.0 * core::f64::consts::LN_2);
+        assert_float_eq!(pk[9], (expected_pk * 252.0).sqrt(), 1e-12);
+
+        let expected_rs = mean(&|i| {
+            (high[i] / close[i]).ln() * (high[i] / open[i]).ln() + (low[i] / close[i]).ln() * (low[i] / open[i]).ln()
+        });
+        assert_float_eq!(rs[9], (expected_rs * 252.0).sqrt(), 1e-12);
+        assert!(gk[9] > 0.0);
+    }
+
+    #[test]
+    fn test_yang_zhang_without_gaps() {
+        // With open equal to the previous close there is no overnight variance
+        let (_, high, low, close) = sample(40);
+        let open: Vec<f64> = (0..40).map(|i| if i == 0 { close[0] } else { close[i - 1] }).collect();
+        let high: Vec<f64> = (0..40).map(|i| high[i].max(open[i])).collect();
+        let low: Vec<f64> = (0..40).map(|i| low[i].min(open[i])).collect();
+
+        let yz = yang_zhang(&open, &high, &low, &close, 10, 252.0).unwrap();
+        let rs = rogers_satchell(&open, &high, &low, &close, 10, 252.0).unwrap();
+        let open_close: Vec<f64> = (1..40).map(|i| (close[i] / open[i]).ln()).collect();
+        let oc_var = sample_var(&open_close, 10).unwrap();
+
+        let k = 0.34 / (1.34 + 11.0 / 9.0);
+        assert!(yz[9].is_nan());
+        let expected = k * oc_var[29] + (1.0 - k) * rs[30].powi(2) / 252.0;
+        assert_float_eq!(yz[30], (expected * 252.0).sqrt(), 1e-12);
+    }
+
+    #[test]
+    fn test_estimators_invalid_input() {
+        let (open, high, low, close) = sample(10);
+        assert!(close_to_close(&[], 3, 252.0).is_err());
+        assert!(close_to_close(&close, 1, 252.0).is_err());
+        assert!(close_to_close(&close, 10, 252.0).is_err());
+        assert!(close_to_close(&close, 3, 0.0).is_err());
+        assert!(parkinson(&high, &low[..5], 3, 252.0).is_err());
+        assert!(parkinson(&[1.0, -1.0], &[1.0, 1.0], 2, 252.0).is_err());
+        assert!(garman_klass(&open, &high, &low, &close, 0, 252.0).is_err());
+        assert!(rogers_satchell(&open, &high, &low, &close, 11, 252.0).is_err());
+        assert!(yang_zhang(&open, &high, &low, &close, 10, 252.0).is_err());
+
+        // Errors name the function as the registry does
+        assert_eq!(close_to_close(&close, 1, 252.0).unwrap_err().function(), Some("HV"));
+        assert_eq!(parkinson(&high, &low[..5], 3, 252.0).unwrap_err().function(), Some("PKVOL"));
+        assert_eq!(garman_klass(&open, &high, &low, &close, 0, 252.0).unwrap_err().function(), Some("GKVOL"));
+        assert_eq!(rogers_satchell(&open, &high, &low, &close, 11, 252.0).unwrap_err().function(), Some("RSVOL"));
+        assert_eq!(yang_zhang(&open, &high, &low, &close, 10, 252.0).unwrap_err().function(), Some("YZVOL"));
+    }
+}
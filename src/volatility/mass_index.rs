@@ -0,0 +1,122 @@
+//! Mass Index
+//!
+//! Donald Dorsey's Mass Index watches the high-low range widen and narrow. A "reversal bulge"
+//! (the index rising above 27 and then falling back below 26.5) signals that the range has
+//! expanded enough for the current trend to be at risk, regardless of its direction.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::sum;
+use crate::overlap::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Mass Index
+///
+/// # Formula
+/// ```text
+/// Single EMA = EMA(High - Low, ema_period)
+/// Double EMA = EMA(Single EMA, ema_period)
+/// Mass Index = SUM(Single EMA / Double EMA, sum_period)
+/// ```
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `ema_period` - Period of both EMAs (typically 9)
+/// * `sum_period` - Number of ratios summed (typically 25)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of Mass Index values; the first
+///   `2 × (ema_period - 1) + sum_period - 1` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::mass_index;
+///
+/// // A constant range gives a ratio of 1 on every bar, so the index equals sum_period
+/// let high = vec![11.0; 60];
+/// let low = vec![9.0; 60];
+/// let result = mass_index(&high, &low, 9, 25).unwrap();
+/// assert!(result[39].is_nan());
+/// assert!((result[40] - 25.0).abs() < 1e-10);
+/// ```
+pub fn mass_index(high: &[f64], low: &[f64], ema_period: impl IntoParam<Period>, sum_period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("MASSINDEX", || {
+        let ema_period = ema_period.into_param("ema_period")?.get();
+        let sum_period = sum_period.into_param("sum_period")?.get();
+        if high.is_empty() || low.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if high.len() != low.len() {
+            return Err(TAError::mismatched_inputs("High and Low arrays must have the same length"));
+        }
+
+        let len = high.len();
+        let single_start = ema_period - 1;
+        let double_start = 2 * single_start;
+        let required = double_start + sum_period;
+        if len < required {
+            return Err(TAError::insufficient_data(required, len));
+        }
+
+        let range: Vec<f64> = high.iter().zip(low).map(|(&h, &l)| h - l).collect();
+        let single = ema(&range, ema_period)?;
+        let double = ema(&single[single_start..], ema_period)?;
+
+        // Ratio of single to double EMA, from the first bar where both are defined
+        let ratio: Vec<f64> = single[double_start..]
+            .iter()
+            .zip(&double[single_start..])
+            .map(|(&s, &d)| if d == 0.0 { 1.0 } else { s / d })
+            .collect();
+
+        let mut result = vec![f64::NAN; len];
+        result[double_start..].copy_from_slice(&sum(&ratio, sum_period)?);
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_mass_index_basic() {
+        let high: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.4).sin() * 2.0 + (i % 3) as f64).collect();
+        let low: Vec<f64> = (0..30).map(|i| 18.0 + (i as f64 * 0.4).sin() * 2.0).collect();
+        let result = mass_index(&high, &low, 3, 5).unwrap();
+
+        // EMAs valid from 2 and 4, sum of 5 ratios from 8
+        assert!(result[..8].iter().all(|v| v.is_nan()));
+
+        let range: Vec<f64> = high.iter().zip(&low).map(|(h, l)| h - l).collect();
+        let single = ema(&range, 3).unwrap();
+        let double = ema(&single[2..], 3).unwrap();
+        let expected: f64 = (4..=8).map(|i| single[i] / double[i - 2]).sum();
+        assert_float_eq!(result[8], expected, 1e-12);
+    }
+
+    #[test]
+    fn test_mass_index_expanding_range() {
+        // A widening range pushes the single EMA above the double EMA
+        let high: Vec<f64> = (0..40).map(|i| 100.0 + i as f64 * 0.2).collect();
+        let low = vec![100.0 - 1.0; 40];
+        let result = mass_index(&high, &low, 5, 10).unwrap();
+        assert!(result[39] > 10.0);
+    }
+
+    #[test]
+    fn test_mass_index_invalid_input() {
+        let prices = vec![1.0; 10];
+        assert!(mass_index(&[], &[], 3, 5).is_err());
+        assert!(mass_index(&prices, &prices[..5], 3, 5).is_err());
+        assert!(mass_index(&prices, &prices, 0, 5).is_err());
+        assert!(mass_index(&prices, &prices, 3, 0).is_err());
+        assert!(mass_index(&prices, &prices, 3, 8).is_err());
+    }
+}
@@ -7,7 +7,16 @@
 pub mod trange;
 pub mod atr;
 pub mod natr;
+pub mod mass_index;
+pub mod choppiness;
+pub mod ulcer;
+pub mod drawdown;
+pub mod estimators;
 
 pub use trange::*;
 pub use atr::*;
-pub use natr::*;
\ No newline at end of file
+pub use natr::*;
+pub use mass_index::*;
+pub use choppiness::*;
+pub use ulcer::*;
+pub use drawdown::*;
\ No newline at end of file
@@ -3,43 +3,11 @@
 //! NATR normalizes ATR by dividing it by the close price and multiplying by 100,
 //! making it easier to compare volatility across different price levels and time periods.
 
-use crate::common::{TAError, validate_period};
-use crate::volatility::atr;
-
-/// Validates HLC data consistency
-fn validate_hlc(high: &[f64], low: &[f64], close: &[f64]) -> Result<(), TAError> {
-    // Validate that high, low, close have same length
-    if high.len() != low.len() || high.len() != close.len() {
-        return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length".to_string()));
-    }
-    
-    // Validate HLC constraints for each bar
-    for i in 0..high.len() {
-        let (h, l, c) = (high[i], low[i], close[i]);
-        
-        if !h.is_finite() || !l.is_finite() || !c.is_finite() {
-            return Err(TAError::invalid_input(format!(
-                "Invalid HLC values at index {}: H={}, L={}, C={}",
-                i, h, l, c
-            )));
-        }
-        
-        if h < l {
-            return Err(TAError::invalid_input(format!(
-                "High ({}) < Low ({}) at index {}",
-                h, l, i
-            )));
-        }
-        if c < l || c > h {
-            return Err(TAError::invalid_input(format!(
-                "Close ({}) is outside High-Low range [{}, {}] at index {}",
-                c, l, h, i
-            )));
-        }
-    }
-    
-    Ok(())
-}
+use crate::common::{TAError, ValidationMode, prepare_hlc, validate_hlc, Alpha, IntoParam, Period, TAOutput, TAResult};
+use crate::common::errors::with_function;
+use crate::volatility::atr::{atr_custom_unchecked_into, atr_talib, atr_unchecked_into};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates Normalized Average True Range.
 /// 
@@ -72,26 +40,84 @@ fn validate_hlc(high: &[f64], low: &[f64], close: &[f64]) -> Result<(), TAError>
 /// let result = natr(&high, &low, &close, 3).unwrap();
 /// assert_eq!(result.len(), 7);
 /// ```
-pub fn natr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<Vec<f64>, TAError> {
-    // Validate that arrays have same length and are not empty
-    if high.is_empty() || low.is_empty() || close.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
+pub fn natr(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("NATR", || {
+        let period = period.into_param("period")?.get();
+        natr_with_mode(high, low, close, period, ValidationMode::Strict)
+    })
+}
+
+/// Calculates the NATR with where its values start
+///
+/// Same as [`natr`], wrapped in a [`TAOutput`] with a lookback of `period - 1`.
+///
+/// # Errors
+/// Same as [`natr`]
+pub fn natr_ex(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>) -> TAResult<TAOutput> {
+    with_function("NATR", || {
+        let period = period.into_param("period")?.get();
+        Ok(TAOutput::new(natr(high, low, close, period)?, period - 1))
+    })
+}
+
+/// Calculates NATR with a chosen treatment of closes outside the bar range.
+/// 
+/// [`ValidationMode::Lenient`] uses such a close as given; [`ValidationMode::Repair`] clamps it
+/// into the bar, for both the true range and the normalization.
+/// 
+/// # Arguments
+/// 
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `period` - Period for ATR calculation (typically 14)
+/// * `mode` - How a close outside the high-low range is treated
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(Vec<f64>)` containing NATR values, or `Err(TAError)` on invalid input.
+/// 
+/// # Example
+/// 
+/// ```
+/// use ta_rust::common::ValidationMode;
+/// use ta_rust::volatility::{natr, natr_with_mode};
+/// 
+/// let high = vec![100.0, 110.0, 120.0, 115.0];
+/// let low = vec![90.0, 100.0, 105.0, 100.0];
+/// let close = vec![95.0, 110.01, 115.0, 105.0]; // rounded above the high
+/// 
+/// assert!(natr(&high, &low, &close, 3).is_err());
+/// let result = natr_with_mode(&high, &low, &close, 3, ValidationMode::Lenient).unwrap();
+/// assert_eq!(result.len(), 4);
+/// ```
+pub fn natr_with_mode(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, mode: ValidationMode) -> Result<Vec<f64>, TAError> {
+    with_function("NATR", || {
+        let period = period.into_param("period")?.get();
+        // Validate that arrays have same length and are not empty
+        if high.is_empty() || low.is_empty() || close.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    validate_hlc(high, low, close)?;
-    validate_period(period, "period")?;
+        let close = prepare_hlc(high, low, close, mode)?;
     
-    let len = high.len();
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
+        let len = high.len();
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
+        }
     
-    // Calculate ATR first
-    let atr_values = atr(high, low, close, period)?;
+        // Calculate ATR first; the close was validated under `mode`, which `atr` would override
+        let mut atr_values = vec![f64::NAN; len];
+        atr_unchecked_into(high, low, &close, period, &mut atr_values);
     
-    let mut result = Vec::with_capacity(len);
+        Ok(natr_from_atr(&atr_values, &close))
+    })
+}
+
+/// NATR = 100 * ATR / Close from a precomputed ATR
+pub(crate) fn natr_from_atr(atr_values: &[f64], close: &[f64]) -> Vec<f64> {
+    let mut result = Vec::with_capacity(atr_values.len());
     
-    // Calculate NATR = 100 * ATR / Close
     for (i, &atr_val) in atr_values.iter().enumerate() {
         if atr_val.is_nan() || close[i] == 0.0 {
             result.push(f64::NAN);
@@ -100,7 +126,12 @@ pub fn natr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<V
         }
     }
     
-    Ok(result)
+    result
+}
+
+/// NATR of TA-Lib's ATR, see [`atr_talib`]
+pub(crate) fn natr_talib(high: &[f64], low: &[f64], close: &[f64], period: usize) -> TAResult<Vec<f64>> {
+    with_function("NATR", || Ok(natr_from_atr(&atr_talib(high, low, close, period)?, close)))
 }
 
 /// Calculates NATR using OHLC data structure.
@@ -130,21 +161,26 @@ pub fn natr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<V
 /// let result = natr_ohlc(&data, 3).unwrap();
 /// assert_eq!(result.len(), 4);
 /// ```
-pub fn natr_ohlc(ohlc: &[crate::common::types::OHLC], period: usize) -> Result<Vec<f64>, TAError> {
-    if ohlc.is_empty() {
-        return Err(TAError::invalid_input("OHLC data cannot be empty"));
-    }
+pub fn natr_ohlc(ohlc: &[crate::common::types::OHLC], period: impl IntoParam<Period>) -> Result<Vec<f64>, TAError> {
+    with_function("NATR", || {
+        let period = period.into_param("period")?.get();
+        if ohlc.is_empty() {
+            return Err(TAError::invalid_input("OHLC data cannot be empty"));
+        }
     
-    let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
-    let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
-    let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
+        let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
+        let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
+        let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
     
-    natr(&high, &low, &close, period)
+        natr(&high, &low, &close, period)
+    })
 }
 
 /// Calculates NATR with custom smoothing factor for ATR.
 /// 
 /// This allows for different smoothing methods beyond Wilder's standard approach.
+/// The ATR is computed by [`atr_custom`](crate::volatility::atr_custom); with
+/// `alpha = 1 / period` the result equals [`natr`].
 /// 
 /// # Arguments
 /// 
@@ -171,35 +207,24 @@ pub fn natr_ohlc(ohlc: &[crate::common::types::OHLC], period: usize) -> Result<V
 /// let result = natr_custom(&high, &low, &close, 3, 0.2).unwrap();
 /// assert_eq!(result.len(), 5);
 /// ```
-pub fn natr_custom(high: &[f64], low: &[f64], close: &[f64], period: usize, alpha: f64) -> Result<Vec<f64>, TAError> {
-    validate_hlc(high, low, close)?;
-    validate_period(period, "period")?;
-    
-    if alpha <= 0.0 || alpha > 1.0 {
-        return Err(TAError::invalid_input("Alpha must be between 0 and 1"));
-    }
-    
-    let len = high.len();
-    if len < period {
-        return Err(TAError::insufficient_data(period, len));
-    }
+pub fn natr_custom(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, alpha: impl IntoParam<Alpha>) -> Result<Vec<f64>, TAError> {
+    with_function("NATR", || {
+        let period = period.into_param("period")?.get();
+        let alpha = alpha.into_param("alpha")?.get();
+        validate_hlc(high, low, close)?;
     
-    // Calculate ATR with custom smoothing - for now use regular ATR
-    // TODO: Implement atr_custom function
-    let atr_values = atr(high, low, close, period)?;
     
-    let mut result = Vec::with_capacity(len);
-    
-    // Calculate NATR = 100 * ATR / Close
-    for (i, &atr_val) in atr_values.iter().enumerate() {
-        if atr_val.is_nan() || close[i] == 0.0 {
-            result.push(f64::NAN);
-        } else {
-            result.push(100.0 * atr_val / close[i]);
+        let len = high.len();
+        if len < period {
+            return Err(TAError::insufficient_data(period, len));
         }
-    }
     
-    Ok(result)
+        // Calculate ATR with the custom smoothing factor
+        let mut atr_values = vec![f64::NAN; len];
+        atr_custom_unchecked_into(high, low, close, period, alpha, &mut atr_values);
+    
+        Ok(natr_from_atr(&atr_values, close))
+    })
 }
 
 /// Calculates NATR bands (upper and lower bounds).
@@ -231,30 +256,35 @@ pub fn natr_custom(high: &[f64], low: &[f64], close: &[f64], period: usize, alph
 /// assert_eq!(upper.len(), 5);
 /// assert_eq!(lower.len(), 5);
 /// ```
-pub fn natr_bands(high: &[f64], low: &[f64], close: &[f64], period: usize, multiplier: f64) -> Result<(Vec<f64>, Vec<f64>), TAError> {
-    let natr_values = natr(high, low, close, period)?;
+pub fn natr_bands(high: &[f64], low: &[f64], close: &[f64], period: impl IntoParam<Period>, multiplier: f64) -> Result<(Vec<f64>, Vec<f64>), TAError> {
+    with_function("NATR", || {
+        let period = period.into_param("period")?.get();
+        let natr_values = natr(high, low, close, period)?;
     
-    let mut upper_band = Vec::with_capacity(natr_values.len());
-    let mut lower_band = Vec::with_capacity(natr_values.len());
+        let mut upper_band = Vec::with_capacity(natr_values.len());
+        let mut lower_band = Vec::with_capacity(natr_values.len());
     
-    for (i, &natr_val) in natr_values.iter().enumerate() {
-        if natr_val.is_nan() {
-            upper_band.push(f64::NAN);
-            lower_band.push(f64::NAN);
-        } else {
-            let band_width = close[i] * natr_val * multiplier / 100.0;
-            upper_band.push(close[i] + band_width);
-            lower_band.push(close[i] - band_width);
+        for (i, &natr_val) in natr_values.iter().enumerate() {
+            if natr_val.is_nan() {
+                upper_band.push(f64::NAN);
+                lower_band.push(f64::NAN);
+            } else {
+                let band_width = close[i] * natr_val * multiplier / 100.0;
+                upper_band.push(close[i] + band_width);
+                lower_band.push(close[i] - band_width);
+            }
         }
-    }
     
-    Ok((upper_band, lower_band))
+        Ok((upper_band, lower_band))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_float_eq;
     use crate::common::types::OHLC;
+    use crate::volatility::atr;
 
     #[test]
     fn test_natr_basic() {
@@ -346,6 +376,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_natr_validation_modes() {
+        let high = vec![10.0, 11.0, 12.0, 11.5];
+        let low = vec![9.0, 10.0, 10.5, 10.0];
+        let close = vec![9.5, 11.2, 11.5, 10.5];
+
+        assert!(natr(&high, &low, &close, 2).is_err());
+
+        let lenient = natr_with_mode(&high, &low, &close, 2, ValidationMode::Lenient).unwrap();
+        // True ranges 1.0, 1.5, 1.5: ATR 1.25 then 1.375, normalized by the close as given
+        assert!((lenient[1] - 125.0 / 11.2).abs() < 1e-8);
+        assert!((lenient[2] - 137.5 / 11.5).abs() < 1e-8);
+
+        let repaired = natr_with_mode(&high, &low, &close, 2, ValidationMode::Repair).unwrap();
+        let clamped = vec![9.5, 11.0, 11.5, 10.5];
+        crate::common::assert_arrays_approx_equal(&repaired, &natr(&high, &low, &clamped, 2).unwrap(), 1e-12);
+    }
+
     #[test]
     fn test_natr_period_1() {
         let high = vec![10.0, 11.0, 12.0];
@@ -394,6 +442,24 @@ mod tests {
         assert!(!result[2].is_nan());
     }
 
+    #[test]
+    fn test_natr_custom_uses_alpha() {
+        let high: Vec<f64> = (0..30).map(|i| 104.0 + (i as f64 * 0.5).sin() * 4.0).collect();
+        let low: Vec<f64> = high.iter().enumerate().map(|(i, h)| h - 2.0 - (i % 4) as f64).collect();
+        let close: Vec<f64> = high.iter().zip(&low).map(|(h, l)| l + (h - l) * 0.6).collect();
+        
+        let wilder = natr(&high, &low, &close, 5).unwrap();
+        let same = natr_custom(&high, &low, &close, 5, 0.2).unwrap();
+        crate::common::assert_arrays_approx_equal(&same, &wilder, 1e-12);
+        
+        let fast = natr_custom(&high, &low, &close, 5, 0.6).unwrap();
+        let atr_values = crate::volatility::atr_custom(&high, &low, &close, 5, 0.6).unwrap();
+        for i in 4..30 {
+            assert_float_eq!(fast[i], 100.0 * atr_values[i] / close[i], 1e-12);
+        }
+        assert!(fast[5..].iter().zip(&wilder[5..]).any(|(f, w)| (f - w).abs() > 1e-6));
+    }
+
     #[test]
     fn test_natr_custom_invalid_alpha() {
         let high = vec![100.0, 110.0, 120.0];
@@ -3,7 +3,10 @@
 //! True Range is a measure of volatility that captures the full range of price movement
 //! for a given period, including gaps. It's the foundation for calculating ATR.
 
-use crate::common::TAError;
+use crate::common::{TAError, Float, validate_same_length, validate_hlc_with, HlcValidation};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Calculates True Range for each period.
 /// 
@@ -21,7 +24,7 @@ use crate::common::TAError;
 /// # Returns
 /// 
 /// Returns `Ok(Vec<f64>)` containing True Range values, or `Err(TAError)` on invalid input.
-/// The first value will be NaN since there's no previous close (following TA-Lib convention).
+/// The first bar has no previous close, so its value is its high - low range.
 /// 
 /// # Example
 /// 
@@ -35,33 +38,60 @@ use crate::common::TAError;
 /// let result = trange(&high, &low, &close).unwrap();
 /// assert_eq!(result.len(), 5);
 /// ```
-pub fn trange(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, TAError> {
-    // Validate that arrays have same length and are not empty
-    if high.is_empty() || low.is_empty() || close.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
+pub fn trange<T: Float>(high: &[T], low: &[T], close: &[T]) -> Result<Vec<T>, TAError> {
+    with_function("TRANGE", || {
+        let mut result = vec![T::ZERO; high.len()];
+        trange_into(high, low, close, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Calculates True Range into a caller-provided buffer.
+/// 
+/// Allocation-free variant of [`trange`]. `out` must have the same length as the inputs.
+/// 
+/// # Returns
+/// 
+/// Returns the number of values written, which is always the input length.
+pub fn trange_into<T: Float>(high: &[T], low: &[T], close: &[T], out: &mut [T]) -> Result<usize, TAError> {
+    with_function("TRANGE", || {
+        // Validate that arrays have same length and are not empty
+        if high.is_empty() || low.is_empty() || close.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    if high.len() != low.len() || high.len() != close.len() {
-        return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length"));
-    }
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        validate_same_length(high, out, "high", "out")?;
     
-    let len = high.len();
-    let mut result = Vec::with_capacity(len);
+        // First value: High - Low (no previous close available)
+        out[0] = high[0] - low[0];
     
-    // First value: High - Low (no previous close available)
-    result.push(high[0] - low[0]);
+        // Calculate True Range for remaining periods
+        for i in 1..high.len() {
+            out[i] = true_range(high[i], low[i], close[i - 1]);
+        }
     
-    // Calculate True Range for remaining periods
-    for i in 1..len {
-        let hl = high[i] - low[i];
-        let hc = (high[i] - close[i - 1]).abs();
-        let lc = (low[i] - close[i - 1]).abs();
-        
-        let tr = hl.max(hc).max(lc);
-        result.push(tr);
-    }
+        Ok(high.len())
+    })
+}
+
+/// True Range as TA-Lib gives it: NaN on the first bar, which has no previous close
+pub(crate) fn trange_talib(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, TAError> {
+    with_function("TRANGE", || {
+        let mut result = trange(high, low, close)?;
+        result[0] = f64::NAN;
+        Ok(result)
+    })
+}
+
+/// True range of a single bar given the previous close
+#[inline]
+pub(crate) fn true_range<T: Float>(high: T, low: T, prev_close: T) -> T {
+    let hl = high - low;
+    let hc = (high - prev_close).abs();
+    let lc = (low - prev_close).abs();
     
-    Ok(result)
+    hl.max(hc).max(lc)
 }
 
 /// Calculates True Range using OHLC data structure.
@@ -90,15 +120,17 @@ pub fn trange(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, TAEr
 /// assert_eq!(result.len(), 3);
 /// ```
 pub fn trange_ohlc(ohlc: &[crate::common::types::OHLC]) -> Result<Vec<f64>, TAError> {
-    if ohlc.is_empty() {
-        return Err(TAError::invalid_input("OHLC data cannot be empty"));
-    }
+    with_function("TRANGE", || {
+        if ohlc.is_empty() {
+            return Err(TAError::invalid_input("OHLC data cannot be empty"));
+        }
     
-    let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
-    let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
-    let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
+        let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
+        let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
+        let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
     
-    trange(&high, &low, &close)
+        trange(&high, &low, &close)
+    })
 }
 
 #[cfg(test)]
@@ -155,7 +187,7 @@ mod tests {
 
     #[test]
     fn test_trange_empty_input() {
-        let high = vec![];
+        let high: Vec<f64> = vec![];
         let low = vec![];
         let close = vec![];
         
@@ -4,6 +4,7 @@
 //! for a given period, including gaps. It's the foundation for calculating ATR.
 
 use crate::common::TAError;
+use crate::common::errors::with_function;
 
 /// Calculates True Range for each period.
 /// 
@@ -36,57 +37,59 @@ use crate::common::TAError;
 /// assert_eq!(result.len(), 5);
 /// ```
 pub fn trange(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, TAError> {
-    // Validate that arrays have same length and are not empty
-    if high.is_empty() || low.is_empty() || close.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
-    
-    if high.len() != low.len() || high.len() != close.len() {
-        return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length"));
-    }
-    
-    // Validate OHLC constraints (we'll use close as open for first bar)
-    for i in 0..high.len() {
-        let (h, l, c) = (high[i], low[i], close[i]);
-        
-        if !h.is_finite() || !l.is_finite() || !c.is_finite() {
-            return Err(TAError::invalid_input(format!(
-                "Invalid HLC values at index {}: H={}, L={}, C={}",
-                i, h, l, c
-            )));
+    with_function("TRANGE", || {
+        // Validate that arrays have same length and are not empty
+        if high.is_empty() || low.is_empty() || close.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
         }
-        
-        if h < l {
-            return Err(TAError::invalid_input(format!(
-                "High ({}) < Low ({}) at index {}",
-                h, l, i
-            )));
+    
+        if high.len() != low.len() || high.len() != close.len() {
+            return Err(TAError::mismatched_inputs("High, Low, and Close arrays must have the same length"));
         }
-        if h < c || l > c {
-            return Err(TAError::invalid_input(format!(
-                "Close ({}) is outside High-Low range [{}, {}] at index {}",
-                c, l, h, i
-            )));
+    
+        // Validate OHLC constraints (we'll use close as open for first bar)
+        for i in 0..high.len() {
+            let (h, l, c) = (high[i], low[i], close[i]);
+        
+            if !h.is_finite() || !l.is_finite() || !c.is_finite() {
+                return Err(TAError::invalid_input(format!(
+                    "Invalid HLC values at index {}: H={}, L={}, C={}",
+                    i, h, l, c
+                )));
+            }
+        
+            if h < l {
+                return Err(TAError::invalid_input(format!(
+                    "High ({}) < Low ({}) at index {}",
+                    h, l, i
+                )));
+            }
+            if h < c || l > c {
+                return Err(TAError::invalid_input(format!(
+                    "Close ({}) is outside High-Low range [{}, {}] at index {}",
+                    c, l, h, i
+                )));
+            }
         }
-    }
     
-    let len = high.len();
-    let mut result = Vec::with_capacity(len);
+        let len = high.len();
+        let mut result = Vec::with_capacity(len);
     
-    // First value: High[0] - Low[0] (no previous close available)
-    result.push(high[0] - low[0]);
+        // First value: High[0] - Low[0] (no previous close available)
+        result.push(high[0] - low[0]);
     
-    // Calculate True Range for remaining periods
-    for i in 1..len {
-        let hl = high[i] - low[i];
-        let hc = (high[i] - close[i - 1]).abs();
-        let lc = (low[i] - close[i - 1]).abs();
-        
-        let tr = hl.max(hc).max(lc);
-        result.push(tr);
-    }
+        // Calculate True Range for remaining periods
+        for i in 1..len {
+            let hl = high[i] - low[i];
+            let hc = (high[i] - close[i - 1]).abs();
+            let lc = (low[i] - close[i - 1]).abs();
+        
+            let tr = hl.max(hc).max(lc);
+            result.push(tr);
+        }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Calculates True Range using OHLC data structure.
@@ -115,15 +118,17 @@ pub fn trange(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, TAEr
 /// assert_eq!(result.len(), 3);
 /// ```
 pub fn trange_ohlc(ohlc: &[crate::common::types::OHLC]) -> Result<Vec<f64>, TAError> {
-    if ohlc.is_empty() {
-        return Err(TAError::invalid_input("OHLC data cannot be empty"));
-    }
+    with_function("TRANGE", || {
+        if ohlc.is_empty() {
+            return Err(TAError::invalid_input("OHLC data cannot be empty"));
+        }
     
-    let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
-    let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
-    let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
+        let high: Vec<f64> = ohlc.iter().map(|x| x.high).collect();
+        let low: Vec<f64> = ohlc.iter().map(|x| x.low).collect();
+        let close: Vec<f64> = ohlc.iter().map(|x| x.close).collect();
     
-    trange(&high, &low, &close)
+        trange(&high, &low, &close)
+    })
 }
 
 #[cfg(test)]
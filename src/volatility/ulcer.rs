@@ -0,0 +1,103 @@
+//! Ulcer Index
+//!
+//! Peter Martin's Ulcer Index measures downside volatility only: the root-mean-square of the
+//! percentage drawdowns from the recent high. Deep or long-lasting declines raise it, while
+//! upside moves leave it untouched.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::common::utils::{validate_not_empty, validate_prices};
+use crate::math_operators::max;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Ulcer Index
+///
+/// # Formula
+/// ```text
+/// Drawdown    = 100 × (Close - Highest(Close, n)) / Highest(Close, n)
+/// Ulcer Index = sqrt(SUM(Drawdown², n) / n)
+/// ```
+///
+/// # Arguments
+/// * `prices` - Slice of closing prices
+/// * `period` - Lookback for both the recent high and the average (typically 14)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of Ulcer Index values (in percent); the first `2 × (period - 1)`
+///   values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volatility::ulcer_index;
+///
+/// // A market that only rises never draws down
+/// let prices: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+/// let result = ulcer_index(&prices, 14).unwrap();
+/// assert!(result[25].is_nan());
+/// assert_eq!(result[26], 0.0);
+/// ```
+pub fn ulcer_index(prices: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("ULCER", || {
+        let period = period.into_param("period")?.get();
+        validate_not_empty(prices, "prices")?;
+        validate_prices(prices, "prices")?;
+
+        let len = prices.len();
+        let start = 2 * (period - 1);
+        if len <= start {
+            return Err(TAError::insufficient_data(start + 1, len));
+        }
+
+        let highest = max(prices, period)?;
+        let squared: Vec<f64> = prices
+            .iter()
+            .zip(&highest)
+            .map(|(&price, &high)| {
+                if high > 0.0 {
+                    let pct = 100.0 * (price - high) / high;
+                    pct * pct
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        let mut result = vec![f64::NAN; len];
+        for (i, value) in result.iter_mut().enumerate().skip(start) {
+            let mean = squared[i + 1 - period..=i].iter().sum::<f64>() / period as f64;
+            *value = mean.sqrt();
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_ulcer_index_basic() {
+        let prices = vec![100.0, 90.0, 95.0, 80.0, 100.0];
+        let result = ulcer_index(&prices, 2).unwrap();
+
+        // 2-bar highs: -, 100, 95, 95, 100 → drawdowns -, -10%, 0, -15.79%, 0
+        assert!(result[1].is_nan());
+        assert_float_eq!(result[2], (100.0f64 / 2.0).sqrt(), 1e-12);
+        let dd: f64 = 100.0 * (80.0 - 95.0) / 95.0;
+        assert_float_eq!(result[3], (dd * dd / 2.0).sqrt(), 1e-12);
+        assert_float_eq!(result[4], (dd * dd / 2.0).sqrt(), 1e-12);
+    }
+
+    #[test]
+    fn test_ulcer_index_invalid_input() {
+        let prices = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(ulcer_index(&[], 2).is_err());
+        assert!(ulcer_index(&prices, 0).is_err());
+        assert!(ulcer_index(&prices, 3).is_err());
+        assert!(ulcer_index(&[1.0, f64::NAN, 2.0, 3.0], 2).is_err());
+    }
+}
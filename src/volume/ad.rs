@@ -3,7 +3,10 @@
 //! The A/D Line is a volume-based indicator designed to measure the cumulative flow of money
 //! into and out of a security. It uses the relationship between closing price and the trading range.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, validate_same_length, validate_hlc_with, HlcValidation};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Chaikin A/D Line (Accumulation/Distribution Line)
 ///
@@ -37,38 +40,56 @@ use crate::common::{TAError, TAResult};
 /// let result = ad(&high, &low, &close, &volume).unwrap();
 /// ```
 pub fn ad(high: &[f64], low: &[f64], close: &[f64], volume: &[f64]) -> TAResult<Vec<f64>> {
-    if high.is_empty() || low.is_empty() || close.is_empty() || volume.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
+    with_function("AD", || {
+        let mut result = vec![0.0; high.len()];
+        ad_into(high, low, close, volume, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// Chaikin A/D Line into a caller-provided buffer
+///
+/// Allocation-free variant of [`ad`]. `out` must have the same length as the inputs.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of values written (always the input length)
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn ad_into(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], out: &mut [f64]) -> TAResult<usize> {
+    with_function("AD", || {
+        if high.is_empty() || low.is_empty() || close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    let len = high.len();
-    if len != low.len() || len != close.len() || len != volume.len() {
-        return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
-    }
+        let len = high.len();
+        if len != low.len() || len != close.len() || len != volume.len() {
+            return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
+        }
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        validate_same_length(high, out, "high", "out")?;
     
-    let mut result = vec![0.0; len];
-    let mut ad_value = 0.0;
+        let mut ad_value = 0.0;
     
-    for i in 0..len {
-        let h = high[i];
-        let l = low[i];
-        let c = close[i];
-        let v = volume[i];
+        for i in 0..len {
+            let h = high[i];
+            let l = low[i];
+            let c = close[i];
+            let v = volume[i];
         
-        // Calculate Close Location Value (CLV)
-        let clv = if (h - l).abs() < f64::EPSILON {
-            // If high equals low (no range), CLV is 0
-            0.0
-        } else {
-            ((c - l) - (h - c)) / (h - l)
-        };
+            // Calculate Close Location Value (CLV)
+            let clv = if (h - l).abs() < f64::EPSILON {
+                // If high equals low (no range), CLV is 0
+                0.0
+            } else {
+                ((c - l) - (h - c)) / (h - l)
+            };
         
-        // Update A/D Line
-        ad_value += clv * v;
-        result[i] = ad_value;
-    }
+            // Update A/D Line
+            ad_value += clv * v;
+            out[i] = ad_value;
+        }
     
-    Ok(result)
+        Ok(len)
+    })
 }
 
 #[cfg(test)]
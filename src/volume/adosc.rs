@@ -3,42 +3,12 @@
 //! The A/D Oscillator is the difference between the fast and slow EMA of the A/D Line.
 //! It's used to identify momentum changes in the accumulation/distribution pattern.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{ema_multiplier, TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::overlap::ewm::{ewm_unchecked_into, EwmInit};
 use crate::volume::ad;
-
-/// TA-Lib compatible EMA calculation
-/// Uses SMA of first period values as initial EMA value (matches TA-Lib exactly)
-fn ema_talib_compatible(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
-    if data.is_empty() {
-        return Err(TAError::invalid_input("Data cannot be empty"));
-    }
-    
-    if period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
-    
-    let len = data.len();
-    let mut result = vec![f64::NAN; len];
-    
-    if len < period {
-        return Ok(result);
-    }
-    
-    let multiplier = 2.0 / (period as f64 + 1.0);
-    
-    // Initialize EMA with SMA of first period values (TA-Lib method)
-    let initial_sum: f64 = data[0..period].iter().sum();
-    let mut ema_value = initial_sum / period as f64;
-    result[period - 1] = ema_value;
-    
-    // Calculate EMA for remaining values
-    for i in period..len {
-        ema_value = (data[i] * multiplier) + (ema_value * (1.0 - multiplier));
-        result[i] = ema_value;
-    }
-    
-    Ok(result)
-}
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// Chaikin A/D Oscillator (ADOSC)
 ///
@@ -51,6 +21,9 @@ fn ema_talib_compatible(data: &[f64], period: usize) -> TAResult<Vec<f64>> {
 /// ADOSC = EMA(AD, fast_period) - EMA(AD, slow_period)
 /// ```
 ///
+/// As in TA-Lib, both EMAs are seeded with the first A/D value, and the oscillator starts
+/// once the slow one has run over `slow_period` values.
+///
 /// # Arguments
 /// * `high` - Slice of high prices
 /// * `low` - Slice of low prices
@@ -78,50 +51,47 @@ pub fn adosc(
     low: &[f64], 
     close: &[f64], 
     volume: &[f64], 
-    fast_period: usize, 
-    slow_period: usize
+    fast_period: impl IntoParam<Period>, 
+    slow_period: impl IntoParam<Period>
 ) -> TAResult<Vec<f64>> {
-    if high.is_empty() || low.is_empty() || close.is_empty() || volume.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
-    
-    let len = high.len();
-    if len != low.len() || len != close.len() || len != volume.len() {
-        return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
-    }
-    
-    if fast_period == 0 || slow_period == 0 {
-        return Err(TAError::invalid_parameter("period", "must be greater than 0"));
-    }
-    
-    if fast_period >= slow_period {
-        return Err(TAError::invalid_parameter("period", "fast period must be less than slow period"));
-    }
+    with_function("ADOSC", || {
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        if high.is_empty() || low.is_empty() || close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    if len < slow_period {
-        return Err(TAError::insufficient_data(slow_period, len));
-    }
+        let len = high.len();
+        if len != low.len() || len != close.len() || len != volume.len() {
+            return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
+        }
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
     
-    // Calculate A/D Line
-    let ad_line = ad(high, low, close, volume)?;
+        if fast_period >= slow_period {
+            return Err(TAError::invalid_parameter("period", "fast period must be less than slow period"));
+        }
     
-    // Calculate fast and slow EMAs of A/D Line using TA-Lib compatible method
-    let fast_ema = ema_talib_compatible(&ad_line, fast_period)?;
-    let slow_ema = ema_talib_compatible(&ad_line, slow_period)?;
+        if len < slow_period {
+            return Err(TAError::insufficient_data(slow_period, len));
+        }
     
-    // Calculate oscillator as difference
-    let mut result = vec![f64::NAN; len];
+        // Calculate A/D Line
+        let ad_line = ad(high, low, close, volume)?;
     
-    // The oscillator is valid when both EMAs are valid
-    let start_idx = slow_period - 1;  // Slow EMA determines when we have valid values
+        // Calculate fast and slow EMAs of A/D Line, seeded with its first value
+        let mut fast_ema = vec![f64::NAN; len];
+        let mut slow_ema = vec![f64::NAN; len];
+        ewm_unchecked_into(&ad_line, ema_multiplier(fast_period), EwmInit::First, &mut fast_ema);
+        ewm_unchecked_into(&ad_line, ema_multiplier(slow_period), EwmInit::First, &mut slow_ema);
     
-    for i in start_idx..len {
-        if !fast_ema[i].is_nan() && !slow_ema[i].is_nan() {
+        // Calculate oscillator as difference, from the end of the first slow window
+        let mut result = vec![f64::NAN; len];
+        for i in slow_period - 1..len {
             result[i] = fast_ema[i] - slow_ema[i];
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
 }
 
 /// Chaikin A/D Oscillator with default periods (3, 10)
@@ -0,0 +1,222 @@
+//! Volume Delta Approximations
+//!
+//! Order-flow delta is buy volume minus sell volume, normally counted trade by trade from
+//! tick data as aggressor buys and sells. Without ticks it can only be approximated from
+//! each bar's shape. [`DeltaMethod`] names the usual approximations so that a delta series
+//! means the same thing wherever it is computed.
+
+use crate::common::{validate_hlc_with, validate_not_empty, validate_same_length, HlcValidation, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Rule splitting a bar's volume into buys and sells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeltaMethod {
+    /// Buys in proportion to where the close lies in the high-low range, as in the
+    /// A/D line: `buy = volume × (close - low) / (high - low)`
+    #[default]
+    CloseLocation,
+    /// All volume to buys on an up bar (close above open), to sells on a down bar
+    BarDirection,
+    /// All volume to buys when the close is above the previous close, to sells when it
+    /// is below; an unchanged close keeps the previous bar's side, the tick rule applied
+    /// to closes
+    TickRule,
+}
+
+/// Approximate buy and sell volume of each bar with the delta between them
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaOutput {
+    /// Volume attributed to buyers
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub buy_volume: Vec<f64>,
+    /// Volume attributed to sellers
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub sell_volume: Vec<f64>,
+    /// Buy volume minus sell volume
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub delta: Vec<f64>,
+    /// Running sum of the delta from the first bar
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub cumulative_delta: Vec<f64>,
+}
+
+/// Approximates buy/sell volume and delta with the close location in the bar's range
+///
+/// Same as [`delta_approx_with`] using [`DeltaMethod::CloseLocation`].
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::delta_approx;
+///
+/// let open = vec![10.0, 11.0];
+/// let high = vec![12.0, 12.0];
+/// let low = vec![10.0, 10.0];
+/// let close = vec![11.5, 10.5];
+/// let volume = vec![1_000.0, 400.0];
+///
+/// let flow = delta_approx(&open, &high, &low, &close, &volume).unwrap();
+/// // Closing three quarters up the range: 750 bought, 250 sold
+/// assert_eq!((flow.buy_volume[0], flow.sell_volume[0]), (750.0, 250.0));
+/// assert_eq!(flow.delta, vec![500.0, -200.0]);
+/// assert_eq!(flow.cumulative_delta, vec![500.0, 300.0]);
+/// ```
+pub fn delta_approx(open: &[f64], high: &[f64], low: &[f64], close: &[f64], volume: &[f64]) -> TAResult<DeltaOutput> {
+    with_function("DELTA", || {
+        delta_approx_with(open, high, low, close, volume, DeltaMethod::CloseLocation)
+    })
+}
+
+/// Approximates buy/sell volume and delta of each bar from OHLCV
+///
+/// Splits each bar's volume into buys and sells with `method`. A bar the method cannot
+/// place, with no range, a close equal to its open, or an unchanged close with no earlier
+/// side, is split evenly and has a delta of 0. Bars with a NaN value are NaN in every
+/// output; the cumulative delta carries on past them.
+///
+/// # Formula
+/// ```text
+/// Delta           = Buy - Sell,   Buy + Sell = Volume
+/// CumulativeDelta = Σ Delta
+/// ```
+///
+/// # Arguments
+/// * `open` - Open prices
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `volume` - Volume
+/// * `method` - Rule splitting the volume
+///
+/// # Returns
+/// * `Ok(DeltaOutput)` - Buy and sell volume, delta and cumulative delta of each bar
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::{delta_approx_with, DeltaMethod};
+///
+/// let close = vec![10.0, 10.5, 10.5, 10.2];
+/// let open = vec![10.0, 10.0, 10.5, 10.5];
+/// let volume = vec![100.0, 200.0, 300.0, 400.0];
+///
+/// let flow = delta_approx_with(&open, &close, &close, &close, &volume, DeltaMethod::TickRule).unwrap();
+/// // The unchanged third close keeps the buy side of the second
+/// assert_eq!(flow.delta, vec![0.0, 200.0, 300.0, -400.0]);
+/// ```
+pub fn delta_approx_with(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    method: DeltaMethod,
+) -> TAResult<DeltaOutput> {
+    with_function("DELTA", || {
+        validate_not_empty(close, "close")?;
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+        validate_same_length(close, open, "close", "open")?;
+        validate_same_length(close, volume, "close", "volume")?;
+
+        let len = close.len();
+        let mut output = DeltaOutput {
+            buy_volume: vec![f64::NAN; len],
+            sell_volume: vec![f64::NAN; len],
+            delta: vec![f64::NAN; len],
+            cumulative_delta: vec![f64::NAN; len],
+        };
+        // Side of the last close change, for the tick rule
+        let mut side = 0.0;
+        let mut previous_close = f64::NAN;
+        let mut cumulative = 0.0;
+
+        for i in 0..len {
+            let (o, h, l, c, v) = (open[i], high[i], low[i], close[i], volume[i]);
+            if [o, h, l, c, v].iter().any(|x| x.is_nan()) {
+                continue;
+            }
+
+            // Share of the volume bought
+            let buy_share = match method {
+                DeltaMethod::CloseLocation if h > l => (c - l) / (h - l),
+                DeltaMethod::CloseLocation => 0.5,
+                DeltaMethod::BarDirection => 0.5 + 0.5 * direction(c - o),
+                DeltaMethod::TickRule => {
+                    if !previous_close.is_nan() && c != previous_close {
+                        side = direction(c - previous_close);
+                    }
+                    previous_close = c;
+                    0.5 + 0.5 * side
+                }
+            };
+
+            let buy = v * buy_share;
+            let sell = v - buy;
+            cumulative += buy - sell;
+            output.buy_volume[i] = buy;
+            output.sell_volume[i] = sell;
+            output.delta[i] = buy - sell;
+            output.cumulative_delta[i] = cumulative;
+        }
+        Ok(output)
+    })
+}
+
+/// 1 for a rise, -1 for a fall, 0 for no change
+fn direction(change: f64) -> f64 {
+    if change > 0.0 {
+        1.0
+    } else if change < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_arrays_approx_equal;
+    use crate::volume::ad;
+
+    #[test]
+    fn test_close_location_matches_ad() {
+        // The A/D line accumulates the same CLV-weighted volume as the cumulative delta
+        let high = [12.0, 13.0, 12.5, 14.0, 13.5];
+        let low = [10.0, 11.0, 10.5, 12.0, 11.5];
+        let close = [11.0, 12.5, 10.5, 14.0, 12.0];
+        let volume = [1000.0, 1500.0, 800.0, 2000.0, 1200.0];
+        let flow = delta_approx(&close, &high, &low, &close, &volume).unwrap();
+        assert_arrays_approx_equal(&flow.cumulative_delta, &ad(&high, &low, &close, &volume).unwrap(), 1e-9);
+        for ((buy, sell), total) in flow.buy_volume.iter().zip(&flow.sell_volume).zip(volume) {
+            assert_eq!(buy + sell, total);
+        }
+    }
+
+    #[test]
+    fn test_bar_direction_and_gaps() {
+        let open = [10.0, 11.0, 11.0, f64::NAN, 12.0];
+        let close = [11.0, 10.0, 11.0, 12.0, 13.0];
+        let high = [11.0, 11.0, 11.0, 12.0, 13.0];
+        let low = [10.0, 10.0, 11.0, 12.0, 12.0];
+        let volume = [100.0, 50.0, 70.0, 10.0, 30.0];
+        let flow = delta_approx_with(&open, &high, &low, &close, &volume, DeltaMethod::BarDirection).unwrap();
+        assert_eq!(&flow.delta[..3], &[100.0, -50.0, 0.0]);
+        assert_eq!(flow.sell_volume[2], 35.0);
+        // The NaN bar drops out, the running sum carries past it
+        assert!(flow.delta[3].is_nan() && flow.cumulative_delta[3].is_nan());
+        assert_eq!(flow.cumulative_delta[4], 80.0);
+    }
+
+    #[test]
+    fn test_delta_errors() {
+        let prices = [10.0, 11.0];
+        assert!(delta_approx(&[], &[], &[], &[], &[]).is_err());
+        assert!(delta_approx(&prices, &prices, &prices, &prices, &[1.0]).is_err());
+        assert!(delta_approx(&prices[..1], &prices, &prices, &prices, &prices).is_err());
+        assert!(delta_approx(&prices, &[9.0, 11.0], &prices, &prices, &prices).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Ease of Movement (EOM)
+//!
+//! Richard Arms' Ease of Movement relates the change in the bar midpoint to the volume needed
+//! to move it. Large positive values mean price rose easily on light volume; values near zero
+//! mean heavy volume was needed to move price at all.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::overlap::sma;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Volume divisor that keeps EOM values in a readable range
+pub const EOM_VOLUME_SCALE: f64 = 100_000_000.0;
+
+/// Ease of Movement
+///
+/// # Formula
+/// ```text
+/// Distance  = (High + Low) / 2 - (High[prev] + Low[prev]) / 2
+/// Box Ratio = (Volume / 100,000,000) / (High - Low)
+/// EMV       = Distance / Box Ratio
+/// EOM       = SMA(EMV, period)
+/// ```
+///
+/// Bars with no range or no volume contribute an EMV of 0.
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `volume` - Slice of volume data
+/// * `period` - SMA smoothing period (typically 14)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of EOM values; the first `period` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::eom;
+///
+/// let high = vec![12.0, 13.0, 12.5, 14.0, 13.5];
+/// let low = vec![10.0, 11.0, 10.5, 12.0, 11.5];
+/// let volume = vec![1e8, 2e8, 1e8, 4e8, 2e8];
+/// let result = eom(&high, &low, &volume, 1).unwrap();
+/// assert!(result[0].is_nan());
+/// // Midpoint rose by 1.0 over a range of 2.0 on 2 volume units
+/// assert_eq!(result[1], 1.0);
+/// ```
+pub fn eom(high: &[f64], low: &[f64], volume: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("EOM", || {
+        let period = period.into_param("period")?.get();
+        if high.is_empty() || low.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        let len = high.len();
+        if len != low.len() || len != volume.len() {
+            return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
+        }
+
+        if period >= len {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+
+        // One-period EMV is defined from the second bar onwards
+        let emv: Vec<f64> = (1..len)
+            .map(|i| {
+                let range = high[i] - low[i];
+                if range.abs() < f64::EPSILON || volume[i] == 0.0 {
+                    return 0.0;
+                }
+                let distance = (high[i] + low[i]) / 2.0 - (high[i - 1] + low[i - 1]) / 2.0;
+                distance / ((volume[i] / EOM_VOLUME_SCALE) / range)
+            })
+            .collect();
+
+        let smoothed = sma(&emv, period)?;
+
+        let mut result = vec![f64::NAN; len];
+        result[1..].copy_from_slice(&smoothed);
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
+
+    #[test]
+    fn test_eom_raw() {
+        let high = vec![12.0, 13.0, 12.5, 14.0, 14.0];
+        let low = vec![10.0, 11.0, 10.5, 12.0, 14.0];
+        let volume = vec![1e8, 2e8, 1e8, 4e8, 2e8];
+        let result = eom(&high, &low, &volume, 1).unwrap();
+
+        // Bar 2: midpoint -0.5, range 2, volume 1 → -1.0; bar 4 has no range → 0
+        let expected = vec![f64::NAN, 1.0, -1.0, 0.75, 0.0];
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_eom_smoothed() {
+        let high = vec![12.0, 13.0, 12.5, 14.0, 14.0];
+        let low = vec![10.0, 11.0, 10.5, 12.0, 14.0];
+        let volume = vec![1e8, 2e8, 1e8, 4e8, 2e8];
+        let result = eom(&high, &low, &volume, 2).unwrap();
+
+        let expected = vec![f64::NAN, f64::NAN, 0.0, -0.125, 0.375];
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_eom_invalid_input() {
+        let prices = vec![10.0, 11.0, 12.0];
+        assert!(eom(&[], &[], &[], 1).is_err());
+        assert!(eom(&prices, &prices[..2], &prices, 1).is_err());
+        assert!(eom(&prices, &prices, &prices, 0).is_err());
+        assert!(eom(&prices, &prices, &prices, 3).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! Elder Force Index
+//!
+//! The Force Index combines the direction and size of a price move with the volume behind it.
+//! Smoothing the raw one-bar force with an EMA gives a trend-confirming oscillator around zero.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::overlap::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Elder Force Index
+///
+/// # Formula
+/// ```text
+/// Force       = (Close - Close[prev]) × Volume
+/// Force Index = EMA(Force, period)
+/// ```
+///
+/// A period of 1 returns the raw one-bar force.
+///
+/// # Arguments
+/// * `close` - Slice of closing prices
+/// * `volume` - Slice of volume data
+/// * `period` - EMA period (2 for short-term, 13 for intermediate-term)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of Force Index values; the first `period` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::force_index;
+///
+/// let close = vec![10.0, 11.0, 10.5, 12.0, 11.5];
+/// let volume = vec![1000.0, 1500.0, 800.0, 2000.0, 1200.0];
+/// let result = force_index(&close, &volume, 1).unwrap();
+/// assert!(result[0].is_nan());
+/// assert_eq!(result[1], 1500.0);
+/// assert_eq!(result[2], -400.0);
+/// ```
+pub fn force_index(close: &[f64], volume: &[f64], period: impl IntoParam<Period>) -> TAResult<Vec<f64>> {
+    with_function("FORCE", || {
+        let period = period.into_param("period")?.get();
+        if close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if close.len() != volume.len() {
+            return Err(TAError::mismatched_inputs("Close and volume arrays must have the same length"));
+        }
+
+        if period >= close.len() {
+            return Err(TAError::insufficient_data(period + 1, close.len()));
+        }
+
+        // Raw force is defined from the second bar onwards
+        let force: Vec<f64> = close
+            .windows(2)
+            .zip(&volume[1..])
+            .map(|(pair, &vol)| (pair[1] - pair[0]) * vol)
+            .collect();
+
+        let smoothed = ema(&force, period)?;
+
+        let mut result = vec![f64::NAN; close.len()];
+        result[1..].copy_from_slice(&smoothed);
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
+
+    #[test]
+    fn test_force_index_raw() {
+        let close = vec![10.0, 11.0, 10.5, 12.0, 11.5];
+        let volume = vec![1000.0, 1500.0, 800.0, 2000.0, 1200.0];
+        let result = force_index(&close, &volume, 1).unwrap();
+        let expected = vec![f64::NAN, 1500.0, -400.0, 3000.0, -600.0];
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_force_index_smoothed() {
+        let close = vec![10.0, 11.0, 10.5, 12.0, 11.5, 12.5];
+        let volume = vec![1000.0, 1500.0, 800.0, 2000.0, 1200.0, 1000.0];
+        let result = force_index(&close, &volume, 3).unwrap();
+
+        // Seed is the SMA of the first three raw forces, then EMA with alpha = 0.5
+        let seed = (1500.0 - 400.0 + 3000.0) / 3.0;
+        let next = 0.5 * -600.0 + 0.5 * seed;
+        let expected = vec![f64::NAN, f64::NAN, f64::NAN, seed, next, 0.5 * 1000.0 + 0.5 * next];
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_force_index_invalid_input() {
+        let close = vec![10.0, 11.0, 12.0];
+        let volume = vec![100.0, 100.0, 100.0];
+        assert!(force_index(&[], &[], 1).is_err());
+        assert!(force_index(&close, &volume[..2], 1).is_err());
+        assert!(force_index(&close, &volume, 0).is_err());
+        assert!(force_index(&close, &volume, 3).is_err());
+    }
+}
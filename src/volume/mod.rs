@@ -6,7 +6,21 @@
 pub mod obv;
 pub mod ad;
 pub mod adosc;
+pub mod force_index;
+pub mod eom;
+pub mod pvt;
+pub mod twiggs_mf;
+pub mod vw_macd;
+pub mod profile;
+pub mod delta;
 
 pub use obv::*;
 pub use ad::*;
-pub use adosc::*;
\ No newline at end of file
+pub use adosc::*;
+pub use force_index::*;
+pub use eom::*;
+pub use pvt::*;
+pub use twiggs_mf::*;
+pub use vw_macd::*;
+pub use profile::*;
+pub use delta::*;
\ No newline at end of file
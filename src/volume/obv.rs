@@ -3,7 +3,10 @@
 //! OBV is a momentum indicator that uses volume flow to predict changes in stock price.
 //! It adds volume on up days and subtracts volume on down days.
 
-use crate::common::{TAError, TAResult};
+use crate::common::{TAError, TAResult, validate_same_length};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
 
 /// On Balance Volume (OBV)
 ///
@@ -39,34 +42,51 @@ use crate::common::{TAError, TAResult};
 /// // result[4] = 1500.0 (close down, subtract volume)
 /// ```
 pub fn obv(close: &[f64], volume: &[f64]) -> TAResult<Vec<f64>> {
-    if close.is_empty() || volume.is_empty() {
-        return Err(TAError::invalid_input("Input arrays cannot be empty"));
-    }
+    with_function("OBV", || {
+        let mut result = vec![0.0; close.len()];
+        obv_into(close, volume, &mut result)?;
+        Ok(result)
+    })
+}
+
+/// On Balance Volume into a caller-provided buffer
+///
+/// Allocation-free variant of [`obv`]. `out` must have the same length as `close`.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of values written (always the input length)
+/// * `Err(TAError)` - Error if inputs are invalid
+pub fn obv_into(close: &[f64], volume: &[f64], out: &mut [f64]) -> TAResult<usize> {
+    with_function("OBV", || {
+        if close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
     
-    if close.len() != volume.len() {
-        return Err(TAError::mismatched_inputs("Close and volume arrays must have the same length"));
-    }
+        if close.len() != volume.len() {
+            return Err(TAError::mismatched_inputs("Close and volume arrays must have the same length"));
+        }
+        validate_same_length(close, out, "close", "out")?;
     
-    let len = close.len();
-    let mut result = vec![0.0; len];
+        let len = close.len();
     
-    // First value is the first volume value (TA-Lib compatible)
-    result[0] = volume[0];
+        // First value is the first volume value (TA-Lib compatible)
+        out[0] = volume[0];
     
-    for i in 1..len {
-        if close[i] > close[i - 1] {
-            // Price up: add volume
-            result[i] = result[i - 1] + volume[i];
-        } else if close[i] < close[i - 1] {
-            // Price down: subtract volume
-            result[i] = result[i - 1] - volume[i];
-        } else {
-            // Price unchanged: keep same OBV
-            result[i] = result[i - 1];
+        for i in 1..len {
+            if close[i] > close[i - 1] {
+                // Price up: add volume
+                out[i] = out[i - 1] + volume[i];
+            } else if close[i] < close[i - 1] {
+                // Price down: subtract volume
+                out[i] = out[i - 1] - volume[i];
+            } else {
+                // Price unchanged: keep same OBV
+                out[i] = out[i - 1];
+            }
         }
-    }
     
-    Ok(result)
+        Ok(len)
+    })
 }
 
 #[cfg(test)]
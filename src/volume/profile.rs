@@ -0,0 +1,445 @@
+//! Volume Profile
+//!
+//! A volume profile, or market profile histogram, shows how much volume traded at each
+//! price rather than in each bar. Each bar's volume is spread evenly over its high-low
+//! range and added to fixed price levels. The level with the most volume is the point of
+//! control (POC), and the value area is the band around it holding 70% of the volume;
+//! both act as support and resistance.
+//!
+//! [`profile`] builds the histogram of a span of bars. [`profile_rolling`] and
+//! [`profile_session`] track the POC and value area bar by bar, over a moving window or
+//! over each trading session so far.
+
+use core::ops::Range;
+
+use crate::common::{
+    sessions, validate_hlc_with, validate_not_empty, validate_same_length, HlcValidation, IntoParam,
+    OhlcvSeries, Percent, Period, SessionBoundary, TAError, TAResult,
+};
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+use crate::common::errors::with_function;
+
+/// Share of the volume in the value area of [`VolumeProfile`] and [`ProfileLevels`]
+pub const VALUE_AREA_PERCENT: f64 = 70.0;
+
+/// Most price levels a tick size may give, to keep a tiny tick from allocating without bound
+const MAX_LEVELS: usize = 1_000_000;
+
+/// Price levels of a volume profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProfileBins {
+    /// This many levels of equal width, spanning the lowest low to the highest high
+    Count(usize),
+    /// Levels one tick wide, aligned to multiples of the tick, e.g. 0.25 for index futures
+    TickSize(f64),
+}
+
+impl ProfileBins {
+    fn validate(self) -> TAResult<()> {
+        match self {
+            ProfileBins::Count(0) => Err(TAError::invalid_parameter("bins", "must be greater than 0")),
+            ProfileBins::TickSize(tick) if !(tick.is_finite() && tick > 0.0) => {
+                Err(TAError::invalid_parameter("tick_size", "must be positive and finite"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Lower edge, width and number of the levels covering `low..=high`
+    fn grid(self, low: f64, high: f64) -> TAResult<(f64, f64, usize)> {
+        match self {
+            ProfileBins::Count(count) => Ok((low, (high - low) / count as f64, count)),
+            ProfileBins::TickSize(tick) => {
+                let start = (low / tick).floor() * tick;
+                let count = ((high - start) / tick).ceil().max(1.0);
+                if count > MAX_LEVELS as f64 {
+                    return Err(TAError::invalid_parameter("tick_size", "gives too many price levels"));
+                }
+                Ok((start, tick, count as usize))
+            }
+        }
+    }
+}
+
+/// Volume traded at each price level of a span of bars
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeProfile {
+    /// Lower edge of the lowest level
+    pub start: f64,
+    /// Width of each level, 0 when every bar traded at a single price
+    pub bin_width: f64,
+    /// Volume of each level, lowest price first
+    pub volumes: Vec<f64>,
+    /// Point of control, the middle of the level with the most volume
+    pub poc: f64,
+    /// Upper edge of the value area
+    pub value_area_high: f64,
+    /// Lower edge of the value area
+    pub value_area_low: f64,
+}
+
+impl VolumeProfile {
+    /// Middle price of a level
+    pub fn price(&self, level: usize) -> f64 {
+        self.start + (level as f64 + 0.5) * self.bin_width
+    }
+
+    /// Volume of all levels
+    pub fn total(&self) -> f64 {
+        self.volumes.iter().sum()
+    }
+
+    /// Lower and upper edge of the band around the POC holding `percent` of the volume
+    ///
+    /// The band grows from the POC one level at a time, toward whichever neighbouring
+    /// level has more volume. Without any volume both edges are NaN.
+    pub fn value_area(&self, percent: impl IntoParam<Percent>) -> TAResult<(f64, f64)> {
+        let fraction = percent.into_param("percent")?.fraction();
+        Ok(match self.value_area_levels(fraction) {
+            Some((low, high)) => (self.edge(low), self.edge(high + 1)),
+            None => (f64::NAN, f64::NAN),
+        })
+    }
+
+    fn edge(&self, level: usize) -> f64 {
+        self.start + level as f64 * self.bin_width
+    }
+
+    /// Lowest level with the most volume, `None` without any volume
+    fn poc_level(&self) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        for (level, &volume) in self.volumes.iter().enumerate() {
+            if volume > 0.0 && best.is_none_or(|b| volume > self.volumes[b]) {
+                best = Some(level);
+            }
+        }
+        best
+    }
+
+    fn value_area_levels(&self, fraction: f64) -> Option<(usize, usize)> {
+        let poc = self.poc_level()?;
+        let target = fraction * self.total() * (1.0 - 1e-12);
+        let (mut low, mut high, mut sum) = (poc, poc, self.volumes[poc]);
+        while sum < target {
+            let below = low.checked_sub(1).map(|level| self.volumes[level]);
+            let above = self.volumes.get(high + 1).copied();
+            match (below, above) {
+                (Some(b), Some(a)) if b > a => {
+                    low -= 1;
+                    sum += b;
+                }
+                (_, Some(a)) => {
+                    high += 1;
+                    sum += a;
+                }
+                (Some(b), None) => {
+                    low -= 1;
+                    sum += b;
+                }
+                (None, None) => break,
+            }
+        }
+        Some((low, high))
+    }
+}
+
+/// POC and value area of a volume profile on every bar
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileLevels {
+    /// Point of control
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub poc: Vec<f64>,
+    /// Upper edge of the value area
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub value_area_high: Vec<f64>,
+    /// Lower edge of the value area
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::serde_nan"))]
+    pub value_area_low: Vec<f64>,
+}
+
+impl ProfileLevels {
+    fn with_len(len: usize) -> Self {
+        Self { poc: vec![f64::NAN; len], value_area_high: vec![f64::NAN; len], value_area_low: vec![f64::NAN; len] }
+    }
+
+    fn set(&mut self, bar: usize, profile: &VolumeProfile) {
+        self.poc[bar] = profile.poc;
+        self.value_area_high[bar] = profile.value_area_high;
+        self.value_area_low[bar] = profile.value_area_low;
+    }
+}
+
+/// Builds the volume profile of a span of bars
+///
+/// Each bar's volume is spread evenly over its high-low range; a bar without range puts
+/// all of it on the level of its close. Bars with a NaN price or volume are skipped. The
+/// value area holds [`VALUE_AREA_PERCENT`] of the volume; use
+/// [`VolumeProfile::value_area`] for another share.
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `volume` - Volume
+/// * `bins` - Number of levels or tick size
+///
+/// # Returns
+/// * `Ok(VolumeProfile)` - The histogram with its POC and value area; NaN levels if no bar
+///   traded any volume
+/// * `Err(TAError)` - Error if inputs are invalid or no bar has valid prices
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::{profile, ProfileBins};
+///
+/// let high = vec![101.0, 102.0, 102.0, 104.0];
+/// let low = vec![100.0, 101.0, 101.0, 103.0];
+/// let close = vec![101.0, 101.5, 101.5, 103.5];
+/// let volume = vec![100.0, 500.0, 400.0, 100.0];
+///
+/// let histogram = profile(&high, &low, &close, &volume, ProfileBins::TickSize(1.0)).unwrap();
+/// assert_eq!(histogram.volumes, vec![100.0, 900.0, 0.0, 100.0]);
+/// assert_eq!(histogram.poc, 101.5);
+/// // 101-102 alone holds 82% of the volume
+/// assert_eq!((histogram.value_area_low, histogram.value_area_high), (101.0, 102.0));
+/// ```
+pub fn profile(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], bins: ProfileBins) -> TAResult<VolumeProfile> {
+    with_function("VPROFILE", || {
+        validate_inputs(high, low, close, volume, bins)?;
+        build(high, low, close, volume, 0..close.len(), bins)?
+            .ok_or_else(|| TAError::invalid_input("No bar has valid prices and volume"))
+    })
+}
+
+/// Calculates the POC and value area of a rolling volume profile
+///
+/// Each bar's levels come from the profile of the last `period` bars, as built by
+/// [`profile`].
+///
+/// # Arguments
+/// * `high` - High prices
+/// * `low` - Low prices
+/// * `close` - Close prices
+/// * `volume` - Volume
+/// * `period` - Number of bars in each profile
+/// * `bins` - Number of levels or tick size
+///
+/// # Returns
+/// * `Ok(ProfileLevels)` - POC and value area of each bar; the first `period-1` are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::{profile_rolling, ProfileBins};
+///
+/// let close: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 * 0.3).sin() * 2.0).collect();
+/// let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+/// let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+/// let volume = vec![1_000.0; 50];
+///
+/// let levels = profile_rolling(&high, &low, &close, &volume, 20, ProfileBins::Count(24)).unwrap();
+/// assert!(levels.poc[18].is_nan());
+/// assert!(levels.value_area_low[49] <= levels.poc[49] && levels.poc[49] <= levels.value_area_high[49]);
+/// ```
+pub fn profile_rolling(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    period: impl IntoParam<Period>,
+    bins: ProfileBins,
+) -> TAResult<ProfileLevels> {
+    with_function("VPROFILE", || {
+        let period = period.into_param("period")?.get();
+        validate_inputs(high, low, close, volume, bins)?;
+        if close.len() < period {
+            return Err(TAError::insufficient_data(period, close.len()));
+        }
+
+        let mut levels = ProfileLevels::with_len(close.len());
+        for bar in period - 1..close.len() {
+            if let Some(profile) = build(high, low, close, volume, bar + 1 - period..bar + 1, bins)? {
+                levels.set(bar, &profile);
+            }
+        }
+        Ok(levels)
+    })
+}
+
+/// Calculates the developing POC and value area of each trading session
+///
+/// Each bar's levels come from the profile of its session from the first bar up to the
+/// bar, so they restart with every session.
+///
+/// # Arguments
+/// * `series` - Bars with timestamps
+/// * `boundary` - Rule starting a new session
+/// * `bins` - Number of levels or tick size
+///
+/// # Returns
+/// * `Ok(ProfileLevels)` - POC and value area of each bar's session so far
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::common::{OhlcvSeries, SessionBoundary};
+/// use ta_rust::volume::{profile_session, ProfileBins};
+///
+/// let series = OhlcvSeries::new(
+///     vec![0, 60, 120, 90_000],
+///     vec![10.0, 10.0, 11.0, 20.0],
+///     vec![10.5, 10.5, 11.5, 20.5],
+///     vec![10.0, 10.0, 11.0, 20.0],
+///     vec![10.5, 10.5, 11.5, 20.5],
+///     vec![100.0, 100.0, 500.0, 10.0],
+/// ).unwrap();
+///
+/// let levels = profile_session(&series, SessionBoundary::Gap(3_600), ProfileBins::TickSize(0.5)).unwrap();
+/// assert_eq!(levels.poc, vec![10.25, 10.25, 11.25, 20.25]);
+/// ```
+pub fn profile_session(series: &OhlcvSeries, boundary: SessionBoundary, bins: ProfileBins) -> TAResult<ProfileLevels> {
+    with_function("VPROFILE", || {
+        series.validate()?;
+        validate_inputs(&series.high, &series.low, &series.close, &series.volume, bins)?;
+
+        let mut levels = ProfileLevels::with_len(series.len());
+        for session in sessions(&series.timestamp, boundary)? {
+            for bar in session.clone() {
+                let span = session.start..bar + 1;
+                if let Some(profile) = build(&series.high, &series.low, &series.close, &series.volume, span, bins)? {
+                    levels.set(bar, &profile);
+                }
+            }
+        }
+        Ok(levels)
+    })
+}
+
+fn validate_inputs(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], bins: ProfileBins) -> TAResult<()> {
+    validate_not_empty(close, "close")?;
+    validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+    validate_same_length(close, volume, "close", "volume")?;
+    bins.validate()
+}
+
+/// Profile of the bars in `span`, `None` if none of them has valid prices and volume
+fn build(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    span: Range<usize>,
+    bins: ProfileBins,
+) -> TAResult<Option<VolumeProfile>> {
+    let valid = |&i: &usize| !(high[i].is_nan() || low[i].is_nan() || close[i].is_nan() || volume[i].is_nan());
+    let bars: Vec<usize> = span.filter(valid).collect();
+    if bars.is_empty() {
+        return Ok(None);
+    }
+    let lowest = bars.iter().map(|&i| low[i]).fold(f64::INFINITY, f64::min);
+    let highest = bars.iter().map(|&i| high[i]).fold(f64::NEG_INFINITY, f64::max);
+    let (start, width, count) = bins.grid(lowest, highest)?;
+
+    let level_of = |price: f64| {
+        if width > 0.0 {
+            (((price - start) / width).floor().max(0.0) as usize).min(count - 1)
+        } else {
+            0
+        }
+    };
+    let mut volumes = vec![0.0; count];
+    for i in bars {
+        let range = high[i] - low[i];
+        if range > 0.0 && width > 0.0 {
+            for (level, bin) in volumes.iter_mut().enumerate().take(level_of(high[i]) + 1).skip(level_of(low[i])) {
+                let bottom = start + level as f64 * width;
+                let overlap = high[i].min(bottom + width) - low[i].max(bottom);
+                if overlap > 0.0 {
+                    *bin += volume[i] * overlap / range;
+                }
+            }
+        } else {
+            volumes[level_of(close[i])] += volume[i];
+        }
+    }
+
+    let mut profile = VolumeProfile {
+        start,
+        bin_width: width,
+        volumes,
+        poc: f64::NAN,
+        value_area_high: f64::NAN,
+        value_area_low: f64::NAN,
+    };
+    if let Some(poc) = profile.poc_level() {
+        profile.poc = profile.price(poc);
+        (profile.value_area_low, profile.value_area_high) = profile.value_area(VALUE_AREA_PERCENT)?;
+    }
+    Ok(Some(profile))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_profile_spreads_volume_over_range() {
+        // One bar over 100-104 in four levels, one at a single price
+        let profile = profile(&[104.0, 101.2], &[100.0, 101.2], &[103.0, 101.2], &[400.0, 50.0], ProfileBins::Count(4))
+            .unwrap();
+        assert_eq!(profile.volumes, vec![100.0, 150.0, 100.0, 100.0]);
+        assert_eq!((profile.start, profile.bin_width), (100.0, 1.0));
+        assert_eq!(profile.poc, 101.5);
+        assert_float_eq!(profile.total(), 450.0, 1e-12);
+
+        // 70% of 450 is 315: the POC, then the two levels above, which tie those below
+        assert_eq!(profile.value_area(70.0).unwrap(), (101.0, 104.0));
+        assert_eq!(profile.value_area(100.0).unwrap(), (100.0, 104.0));
+        assert!(profile.value_area(120.0).is_err());
+
+        // Ticks align to multiples of the tick, not to the lowest low
+        let ticks = super::profile(&[10.3], &[10.1], &[10.2], &[30.0], ProfileBins::TickSize(0.25)).unwrap();
+        assert_eq!((ticks.start, ticks.volumes.len()), (10.0, 2));
+        assert_float_eq!(ticks.volumes[0], 22.5, 1e-9);
+    }
+
+    #[test]
+    fn test_rolling_and_session_levels() {
+        let high = [11.0, 11.0, 13.0, 13.0, 13.0];
+        let low = [10.0, 10.0, 12.0, 12.0, 12.0];
+        let close = [10.5, 10.5, 12.5, 12.5, 12.5];
+        let volume = [100.0, 100.0, 10.0, 10.0, f64::NAN];
+        let levels = profile_rolling(&high, &low, &close, &volume, 2, ProfileBins::TickSize(1.0)).unwrap();
+        assert!(levels.poc[0].is_nan());
+        assert_eq!(&levels.poc[1..], &[10.5, 10.5, 12.5, 12.5]);
+        assert_eq!((levels.value_area_low[2], levels.value_area_high[2]), (10.0, 11.0));
+
+        let series = OhlcvSeries::new(vec![0, 60, 120, 86_400, 86_460], close.to_vec(), high.to_vec(), low.to_vec(),
+            close.to_vec(), vec![100.0, 100.0, 300.0, 5.0, 10.0]).unwrap();
+        let session = profile_session(&series, SessionBoundary::Gap(3_600), ProfileBins::TickSize(1.0)).unwrap();
+        assert_eq!(session.poc, vec![10.5, 10.5, 12.5, 12.5, 12.5]);
+        // Below the POC the empty level is crossed to reach the first two bars
+        assert_eq!(session.value_area_low[2], 10.0);
+    }
+
+    #[test]
+    fn test_profile_errors() {
+        let prices = [10.0, 11.0];
+        assert!(profile(&prices, &prices, &prices, &[1.0, 1.0], ProfileBins::Count(0)).is_err());
+        assert!(profile(&prices, &prices, &prices, &[1.0, 1.0], ProfileBins::TickSize(-1.0)).is_err());
+        assert!(profile(&prices, &prices, &prices, &[1.0, 1.0], ProfileBins::TickSize(1e-9)).is_err());
+        assert!(profile(&prices, &prices, &prices, &[1.0], ProfileBins::Count(4)).is_err());
+        assert!(profile(&[], &[], &[], &[], ProfileBins::Count(4)).is_err());
+        assert!(profile(&[f64::NAN], &[f64::NAN], &[f64::NAN], &[1.0], ProfileBins::Count(4)).is_err());
+        assert!(profile_rolling(&prices, &prices, &prices, &[1.0, 1.0], 3, ProfileBins::Count(4)).is_err());
+
+        // No volume at all leaves the levels undefined
+        let idle = profile(&prices, &prices, &prices, &[0.0, 0.0], ProfileBins::Count(4)).unwrap();
+        assert!(idle.poc.is_nan() && idle.value_area_high.is_nan());
+    }
+}
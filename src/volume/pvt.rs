@@ -0,0 +1,84 @@
+//! Volume Price Trend (PVT)
+//!
+//! PVT is a cumulative volume line like OBV, but each bar adds volume in proportion to the
+//! percentage change of the close rather than all-or-nothing.
+
+use crate::common::{TAError, TAResult};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Volume Price Trend (PVT)
+///
+/// # Formula
+/// ```text
+/// PVT[0] = 0
+/// PVT    = PVT[prev] + Volume × (Close - Close[prev]) / Close[prev]
+/// ```
+///
+/// # Arguments
+/// * `close` - Slice of closing prices
+/// * `volume` - Slice of volume data
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of PVT values
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::pvt;
+///
+/// let close = vec![10.0, 11.0, 9.9];
+/// let volume = vec![1000.0, 1500.0, 800.0];
+/// let result = pvt(&close, &volume).unwrap();
+/// assert_eq!(result[0], 0.0);
+/// assert!((result[1] - 150.0).abs() < 1e-9); // +10% of 1500
+/// assert!((result[2] - 70.0).abs() < 1e-9);  // -10% of 800
+/// ```
+pub fn pvt(close: &[f64], volume: &[f64]) -> TAResult<Vec<f64>> {
+    with_function("PVT", || {
+        if close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if close.len() != volume.len() {
+            return Err(TAError::mismatched_inputs("Close and volume arrays must have the same length"));
+        }
+
+        let mut result = vec![0.0; close.len()];
+        let mut total = 0.0;
+
+        for i in 1..close.len() {
+            // A zero previous close has no defined percentage change
+            if close[i - 1] != 0.0 {
+                total += volume[i] * (close[i] - close[i - 1]) / close[i - 1];
+            }
+            result[i] = total;
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_arrays_approx_equal, DEFAULT_TOLERANCE};
+
+    #[test]
+    fn test_pvt_basic() {
+        let close = vec![20.0, 21.0, 21.0, 18.9, 0.0, 5.0];
+        let volume = vec![500.0, 1000.0, 700.0, 2000.0, 300.0, 400.0];
+        let result = pvt(&close, &volume).unwrap();
+
+        // +5% of 1000, unchanged, -10% of 2000, -100% of 300, then skipped after a zero close
+        let expected = vec![0.0, 50.0, 50.0, -150.0, -450.0, -450.0];
+        assert_arrays_approx_equal(&result, &expected, DEFAULT_TOLERANCE);
+    }
+
+    #[test]
+    fn test_pvt_invalid_input() {
+        assert!(pvt(&[], &[]).is_err());
+        assert!(pvt(&[1.0, 2.0], &[1.0]).is_err());
+    }
+}
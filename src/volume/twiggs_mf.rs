@@ -0,0 +1,146 @@
+//! Twiggs Money Flow (TMF)
+//!
+//! Colin Twiggs' refinement of Chaikin Money Flow. It measures the close within the *true*
+//! range (so gaps count) and uses Wilder smoothing instead of a plain sum, which removes
+//! the jumps CMF shows when a large bar leaves the window.
+
+use crate::common::{TAError, TAResult, validate_hlc_with, HlcValidation, IntoParam, Period};
+use crate::common::errors::with_function;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Twiggs Money Flow
+///
+/// # Formula
+/// ```text
+/// TRH = max(High, Close[prev])
+/// TRL = min(Low, Close[prev])
+/// AD  = Volume × ((Close - TRL) - (TRH - Close)) / (TRH - TRL)
+/// TMF = Wilder(AD, period) / Wilder(Volume, period)
+/// ```
+///
+/// Wilder smoothing is seeded with the average of the first `period` values. Bars with no
+/// true range contribute an AD of 0.
+///
+/// # Arguments
+/// * `high` - Slice of high prices
+/// * `low` - Slice of low prices
+/// * `close` - Slice of closing prices
+/// * `volume` - Slice of volume data
+/// * `period` - Smoothing period (typically 21)
+///
+/// # Returns
+/// * `Ok(Vec<f64>)` - Vector of TMF values in [-1, 1]; the first `period` values are NaN
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::twiggs_mf;
+///
+/// let high = vec![12.0, 13.0, 12.5, 14.0, 13.5];
+/// let low = vec![10.0, 11.0, 10.5, 12.0, 11.5];
+/// let close = vec![11.0, 13.0, 12.5, 14.0, 12.0];
+/// let volume = vec![1000.0, 1500.0, 800.0, 2000.0, 1200.0];
+/// let result = twiggs_mf(&high, &low, &close, &volume, 3).unwrap();
+/// assert!(result[2].is_nan());
+/// assert!(result[3] > 0.0);
+/// ```
+pub fn twiggs_mf(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    period: impl IntoParam<Period>,
+) -> TAResult<Vec<f64>> {
+    with_function("TWIGGSMF", || {
+        let period = period.into_param("period")?.get();
+        if high.is_empty() || low.is_empty() || close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        let len = high.len();
+        if len != low.len() || len != close.len() || len != volume.len() {
+            return Err(TAError::mismatched_inputs("All input arrays must have the same length"));
+        }
+        validate_hlc_with(high, low, close, HlcValidation::CONSISTENT)?;
+
+        if period >= len {
+            return Err(TAError::insufficient_data(period + 1, len));
+        }
+
+        // Money flow volume over the true range, defined from the second bar onwards
+        let money_flow = |i: usize| {
+            let true_high = high[i].max(close[i - 1]);
+            let true_low = low[i].min(close[i - 1]);
+            let range = true_high - true_low;
+            if range.abs() < f64::EPSILON {
+                0.0
+            } else {
+                volume[i] * ((close[i] - true_low) - (true_high - close[i])) / range
+            }
+        };
+
+        let mut result = vec![f64::NAN; len];
+        let n = period as f64;
+
+        // Seed both averages over bars 1..=period
+        let mut ad_avg = (1..=period).map(money_flow).sum::<f64>() / n;
+        let mut vol_avg = volume[1..=period].iter().sum::<f64>() / n;
+        result[period] = if vol_avg == 0.0 { 0.0 } else { ad_avg / vol_avg };
+
+        for i in (period + 1)..len {
+            ad_avg += (money_flow(i) - ad_avg) / n;
+            vol_avg += (volume[i] - vol_avg) / n;
+            result[i] = if vol_avg == 0.0 { 0.0 } else { ad_avg / vol_avg };
+        }
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+
+    #[test]
+    fn test_twiggs_mf_basic() {
+        let high = vec![12.0, 13.0, 12.5, 14.0, 13.5];
+        let low = vec![10.0, 11.0, 10.5, 12.0, 11.5];
+        let close = vec![11.0, 13.0, 12.5, 14.0, 12.0];
+        let volume = vec![1000.0, 1500.0, 800.0, 2000.0, 1200.0];
+        let result = twiggs_mf(&high, &low, &close, &volume, 2).unwrap();
+
+        // Bar 1 closes at the true high: +1500; bar 2: TR 10.5..13, close 12.5 → 0.6 × 800
+        let ad1 = 1500.0;
+        let ad2 = 800.0 * ((12.5 - 10.5) - (13.0 - 12.5)) / 2.5;
+        assert!(result[1].is_nan());
+        assert_float_eq!(result[2], (ad1 + ad2) / (1500.0 + 800.0), 1e-12);
+
+        // Bar 3: TR 12..14 closes at the high; Wilder update with n = 2
+        let ad = ((ad1 + ad2) / 2.0 + 2000.0) / 2.0;
+        let vol = ((1500.0 + 800.0) / 2.0 + 2000.0) / 2.0;
+        assert_float_eq!(result[3], ad / vol, 1e-12);
+    }
+
+    #[test]
+    fn test_twiggs_mf_bounded() {
+        let close: Vec<f64> = (0..60).map(|i| 40.0 + (i as f64 * 0.25).sin() * 4.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let volume: Vec<f64> = (0..60).map(|i| 1000.0 + (i % 7) as f64 * 100.0).collect();
+        let result = twiggs_mf(&high, &low, &close, &volume, 21).unwrap();
+
+        assert!(result[..21].iter().all(|v| v.is_nan()));
+        assert!(result[21..].iter().all(|&v| (-1.0..=1.0).contains(&v)));
+    }
+
+    #[test]
+    fn test_twiggs_mf_invalid_input() {
+        let prices = vec![10.0, 11.0, 12.0];
+        assert!(twiggs_mf(&[], &[], &[], &[], 2).is_err());
+        assert!(twiggs_mf(&prices, &prices, &prices, &prices[..2], 2).is_err());
+        assert!(twiggs_mf(&prices, &prices, &prices, &prices, 0).is_err());
+        assert!(twiggs_mf(&prices, &prices, &prices, &prices, 3).is_err());
+    }
+}
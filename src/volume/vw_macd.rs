@@ -0,0 +1,159 @@
+//! Volume-Weighted MACD (VW-MACD)
+//!
+//! Buff Dormeier's VW-MACD replaces the two EMAs of the classic MACD with volume-weighted
+//! moving averages, so moves on heavy volume carry more weight than moves on light volume.
+
+use crate::common::{TAError, TAResult, IntoParam, Period};
+use crate::common::errors::with_function;
+use crate::math_operators::sum;
+use crate::momentum::MacdOutput;
+use crate::overlap::ema;
+#[cfg(not(feature = "std"))]
+use crate::common::compat::*;
+
+/// Volume-weighted moving average: Σ(Close × Volume) / Σ(Volume) over `period` bars
+fn vwma(close: &[f64], volume: &[f64], period: usize) -> TAResult<Vec<f64>> {
+    let weighted: Vec<f64> = close.iter().zip(volume).map(|(&c, &v)| c * v).collect();
+    let weighted_sum = sum(&weighted, period)?;
+    let volume_sum = sum(volume, period)?;
+
+    Ok(weighted_sum
+        .iter()
+        .zip(&volume_sum)
+        .map(|(&w, &v)| if v == 0.0 { f64::NAN } else { w / v })
+        .collect())
+}
+
+/// Volume-Weighted MACD
+///
+/// # Formula
+/// ```text
+/// VWMA(n)   = Σ(Close × Volume, n) / Σ(Volume, n)
+/// MACD      = VWMA(fast) - VWMA(slow)
+/// Signal    = EMA(MACD, signal)
+/// Histogram = MACD - Signal
+/// ```
+///
+/// Windows with no volume have no defined average and yield NaN.
+///
+/// # Arguments
+/// * `close` - Slice of closing prices
+/// * `volume` - Slice of volume data
+/// * `fast_period` - Fast VWMA period (typically 12)
+/// * `slow_period` - Slow VWMA period (typically 26)
+/// * `signal_period` - Signal EMA period (typically 9)
+///
+/// # Returns
+/// * `Ok(MacdOutput)` - MACD line valid from `slow_period-1`, signal and histogram from
+///   `slow_period + signal_period - 2`
+/// * `Err(TAError)` - Error if inputs are invalid
+///
+/// # Examples
+/// ```
+/// use ta_rust::volume::vw_macd;
+///
+/// let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+/// let volume: Vec<f64> = (0..60).map(|i| 1000.0 + (i % 5) as f64 * 200.0).collect();
+/// let result = vw_macd(&close, &volume, 12, 26, 9).unwrap();
+/// assert!(result.macd[24].is_nan());
+/// assert!(!result.macd[25].is_nan());
+/// assert!(!result.histogram[33].is_nan());
+/// ```
+pub fn vw_macd(
+    close: &[f64],
+    volume: &[f64],
+    fast_period: impl IntoParam<Period>,
+    slow_period: impl IntoParam<Period>,
+    signal_period: impl IntoParam<Period>,
+) -> TAResult<MacdOutput> {
+    with_function("VWMACD", || {
+        let fast_period = fast_period.into_param("fast_period")?.get();
+        let slow_period = slow_period.into_param("slow_period")?.get();
+        let signal_period = signal_period.into_param("signal_period")?.get();
+        if close.is_empty() || volume.is_empty() {
+            return Err(TAError::invalid_input("Input arrays cannot be empty"));
+        }
+
+        if close.len() != volume.len() {
+            return Err(TAError::mismatched_inputs("Close and volume arrays must have the same length"));
+        }
+
+        if fast_period >= slow_period {
+            return Err(TAError::invalid_parameter("period", "fast period must be less than slow period"));
+        }
+
+        let len = close.len();
+        let start = slow_period - 1;
+        if len < start + signal_period {
+            return Err(TAError::insufficient_data(start + signal_period, len));
+        }
+
+        let fast = vwma(close, volume, fast_period)?;
+        let slow = vwma(close, volume, slow_period)?;
+        let macd: Vec<f64> = fast.iter().zip(&slow).map(|(&f, &s)| f - s).collect();
+
+        let mut signal = vec![f64::NAN; len];
+        signal[start..].copy_from_slice(&ema(&macd[start..], signal_period)?);
+
+        let histogram = macd.iter().zip(&signal).map(|(&m, &s)| m - s).collect();
+
+        Ok(MacdOutput {
+            macd,
+            signal,
+            histogram,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_float_eq;
+    use crate::overlap::sma;
+
+    #[test]
+    fn test_vw_macd_basic() {
+        let close: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.3).sin() * 3.0).collect();
+        let volume: Vec<f64> = (0..40).map(|i| 100.0 + i as f64 * 10.0).collect();
+        let result = vw_macd(&close, &volume, 3, 6, 4).unwrap();
+
+        assert!(result.macd[..5].iter().all(|v| v.is_nan()));
+        assert!(result.signal[..8].iter().all(|v| v.is_nan()));
+        assert!(!result.signal[8].is_nan());
+
+        let vwma = |end: usize, n: usize| {
+            let window = end + 1 - n..=end;
+            let pv: f64 = window.clone().map(|i| close[i] * volume[i]).sum();
+            let v: f64 = window.map(|i| volume[i]).sum();
+            pv / v
+        };
+        assert_float_eq!(result.macd[20], vwma(20, 3) - vwma(20, 6), 1e-9);
+        assert_float_eq!(result.histogram[20], result.macd[20] - result.signal[20], 1e-12);
+    }
+
+    #[test]
+    fn test_vw_macd_constant_volume_is_sma_macd() {
+        let close: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.3).sin() * 3.0).collect();
+        let volume = vec![500.0; 40];
+        let result = vw_macd(&close, &volume, 3, 6, 4).unwrap();
+
+        let fast = sma(&close, 3).unwrap();
+        let slow = sma(&close, 6).unwrap();
+        for i in 5..40 {
+            assert_float_eq!(result.macd[i], fast[i] - slow[i], 1e-9);
+        }
+
+        // The crossover helpers come along with MacdOutput
+        assert_eq!(result.crossovers().len(), 40);
+    }
+
+    #[test]
+    fn test_vw_macd_invalid_input() {
+        let close = vec![1.0; 20];
+        assert!(vw_macd(&[], &[], 3, 6, 4).is_err());
+        assert!(vw_macd(&close, &close[..10], 3, 6, 4).is_err());
+        assert!(vw_macd(&close, &close, 6, 3, 4).is_err());
+        assert!(vw_macd(&close, &close, 3, 6, 0).is_err());
+        assert!(vw_macd(&close, &close, 3, 18, 4).is_err());
+    }
+}
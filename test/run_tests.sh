@@ -115,7 +115,7 @@ fi
 
 # Step 6: Run Rust comparison test
 print_status "Running Rust vs TA-Lib comparison..."
-if cargo run --bin rust_comparison_test; then
+if cargo run --features comparison --bin rust_comparison_test; then
     print_success "All compatibility tests passed! 🎉"
     TESTS_PASSED=true
 else
@@ -0,0 +1,329 @@
+#!/usr/bin/env python3
+"""
+Generate TA-Lib golden vectors for tests/accuracy_tests
+
+Writes one input file per dataset to data/ and, for every TA-Lib function and
+parameter set, one output file to golden/<dataset>/ in the format read by main.rs.
+Functions ta-rust does not implement yet are written too; the test runner skips them.
+
+TA-Lib is called through the abstract interface of its C library, the same one the
+Python wrapper uses, so only the shared library is needed, no Python packages:
+
+Usage:
+    python tests/accuracy_tests/generate_golden.py --library /usr/lib/libta_lib.so \\
+        [--size 300] [--function RSI ...]
+
+Without --library, $TA_LIBRARY or the system's ta_lib / ta-lib library is used.
+Re-run after changing datasets or parameter sets and commit data/ and golden/.
+The reference_100 dataset comes from test/talib_reference_data.json and is left
+untouched.
+"""
+
+import argparse
+import ctypes
+import ctypes.util
+import math
+import os
+import random
+import sys
+
+ROOT = os.path.dirname(os.path.abspath(__file__))
+
+# TA-Lib's abstract interface maps price inputs to these columns
+COLUMNS = ["open", "high", "low", "close", "volume", "periods"]
+
+# Price input flags, in the argument order of TA_SetInputParamPricePtr
+PRICE_FLAGS = [("open", 0x01), ("high", 0x02), ("low", 0x04), ("close", 0x08), ("volume", 0x10), ("openinterest", 0x20)]
+
+# Columns of real inputs, as chosen by the Python wrapper
+REAL_INPUTS = {"inReal": "close", "inReal0": "high", "inReal1": "low", "inPeriods": "periods"}
+
+TA_INPUT_PRICE, TA_INPUT_REAL = 0, 1
+TA_OPT_INPUT_REAL_RANGE, TA_OPT_INPUT_REAL_LIST = 0, 1
+TA_OUTPUT_REAL = 0
+
+
+class FuncInfo(ctypes.Structure):
+    _fields_ = [
+        ("name", ctypes.c_char_p),
+        ("group", ctypes.c_char_p),
+        ("hint", ctypes.c_char_p),
+        ("camelCaseName", ctypes.c_char_p),
+        ("flags", ctypes.c_int),
+        ("nbInput", ctypes.c_uint),
+        ("nbOptInput", ctypes.c_uint),
+        ("nbOutput", ctypes.c_uint),
+        ("handle", ctypes.c_void_p),
+    ]
+
+
+class InputInfo(ctypes.Structure):
+    _fields_ = [("type", ctypes.c_int), ("paramName", ctypes.c_char_p), ("flags", ctypes.c_int)]
+
+
+class OptInputInfo(ctypes.Structure):
+    _fields_ = [
+        ("type", ctypes.c_int),
+        ("paramName", ctypes.c_char_p),
+        ("flags", ctypes.c_int),
+        ("displayName", ctypes.c_char_p),
+        ("dataSet", ctypes.c_void_p),
+        ("defaultValue", ctypes.c_double),
+        ("hint", ctypes.c_char_p),
+        ("helpFile", ctypes.c_char_p),
+    ]
+
+
+class OutputInfo(ctypes.Structure):
+    _fields_ = [("type", ctypes.c_int), ("paramName", ctypes.c_char_p), ("flags", ctypes.c_int)]
+
+
+class StringTable(ctypes.Structure):
+    _fields_ = [("size", ctypes.c_uint), ("string", ctypes.POINTER(ctypes.c_char_p)), ("hiddenData", ctypes.c_void_p)]
+
+
+class TaLib:
+    """The abstract interface of the TA-Lib C library"""
+
+    def __init__(self, path):
+        self.lib = ctypes.CDLL(path)
+        self.check(self.lib.TA_Initialize(), "TA_Initialize")
+
+    @staticmethod
+    def check(code, call):
+        if code != 0:
+            raise RuntimeError("%s failed with TA_RetCode %d" % (call, code))
+
+    def functions(self):
+        names = []
+        groups = ctypes.POINTER(StringTable)()
+        self.check(self.lib.TA_GroupTableAlloc(ctypes.byref(groups)), "TA_GroupTableAlloc")
+        for g in range(groups.contents.size):
+            table = ctypes.POINTER(StringTable)()
+            self.check(self.lib.TA_FuncTableAlloc(groups.contents.string[g], ctypes.byref(table)), "TA_FuncTableAlloc")
+            names.extend(table.contents.string[i].decode() for i in range(table.contents.size))
+            self.lib.TA_FuncTableFree(table)
+        self.lib.TA_GroupTableFree(groups)
+        return sorted(names)
+
+    def function(self, name):
+        return Function(self, name)
+
+
+class Function:
+    """One TA-Lib function with the names the Python wrapper gives its parameters"""
+
+    def __init__(self, talib, name):
+        self.lib = talib.lib
+        self.name = name
+        self.handle = ctypes.c_void_p()
+        TaLib.check(self.lib.TA_GetFuncHandle(name.encode(), ctypes.byref(self.handle)), "TA_GetFuncHandle " + name)
+        info = ctypes.POINTER(FuncInfo)()
+        TaLib.check(self.lib.TA_GetFuncInfo(self.handle, ctypes.byref(info)), "TA_GetFuncInfo " + name)
+        info = info.contents
+
+        self.inputs = [self.info(self.lib.TA_GetInputParameterInfo, InputInfo, i) for i in range(info.nbInput)]
+        self.opt_inputs = [self.info(self.lib.TA_GetOptInputParameterInfo, OptInputInfo, i) for i in range(info.nbOptInput)]
+        self.outputs = [self.info(self.lib.TA_GetOutputParameterInfo, OutputInfo, i) for i in range(info.nbOutput)]
+
+        self.parameters = {}
+        for opt in self.opt_inputs:
+            is_real = opt.type in (TA_OPT_INPUT_REAL_RANGE, TA_OPT_INPUT_REAL_LIST)
+            self.parameters[param_name(opt)] = opt.defaultValue if is_real else int(opt.defaultValue)
+        self.output_names = [output_name(o.paramName.decode()) for o in self.outputs]
+
+    def info(self, getter, struct, index):
+        pointer = ctypes.POINTER(struct)()
+        TaLib.check(getter(self.handle, index, ctypes.byref(pointer)), "parameter info of " + self.name)
+        return pointer.contents
+
+    def __call__(self, data, params):
+        """Outputs for the whole dataset, NaN over the lookback, or None if TA-Lib rejects the call"""
+        size = len(data["close"])
+        holder = ctypes.c_void_p()
+        TaLib.check(self.lib.TA_ParamHolderAlloc(self.handle, ctypes.byref(holder)), "TA_ParamHolderAlloc")
+        arrays = []
+
+        def array(values):
+            arrays.append((ctypes.c_double * size)(*values))
+            return arrays[-1]
+
+        try:
+            for index, info in enumerate(self.inputs):
+                if info.type == TA_INPUT_PRICE:
+                    prices = [array(data[c]) if info.flags & flag else None for c, flag in PRICE_FLAGS]
+                    code = self.lib.TA_SetInputParamPricePtr(holder, index, *prices)
+                elif info.type == TA_INPUT_REAL:
+                    code = self.lib.TA_SetInputParamRealPtr(holder, index, array(data[REAL_INPUTS[info.paramName.decode()]]))
+                else:
+                    raise RuntimeError("%s: integer inputs are not supported" % self.name)
+                TaLib.check(code, "input %d of %s" % (index, self.name))
+
+            for index, opt in enumerate(self.opt_inputs):
+                value = params[param_name(opt)]
+                if opt.type in (TA_OPT_INPUT_REAL_RANGE, TA_OPT_INPUT_REAL_LIST):
+                    code = self.lib.TA_SetOptInputParamReal(holder, index, ctypes.c_double(value))
+                else:
+                    code = self.lib.TA_SetOptInputParamInteger(holder, index, ctypes.c_int(value))
+                if code != 0:
+                    return None
+
+            buffers = []
+            for index, info in enumerate(self.outputs):
+                if info.type == TA_OUTPUT_REAL:
+                    buffers.append((ctypes.c_double * size)())
+                    code = self.lib.TA_SetOutputParamRealPtr(holder, index, buffers[-1])
+                else:
+                    buffers.append((ctypes.c_int * size)())
+                    code = self.lib.TA_SetOutputParamIntegerPtr(holder, index, buffers[-1])
+                TaLib.check(code, "output %d of %s" % (index, self.name))
+
+            begin, count = ctypes.c_int(), ctypes.c_int()
+            if self.lib.TA_CallFunc(holder, 0, size - 1, ctypes.byref(begin), ctypes.byref(count)) != 0:
+                return None
+        finally:
+            self.lib.TA_ParamHolderFree(holder)
+
+        outputs = []
+        for info, buffer in zip(self.outputs, buffers):
+            # Like the Python wrapper: NaN before the first value, 0 for integer outputs
+            fill = math.nan if info.type == TA_OUTPUT_REAL else 0.0
+            values = [fill] * size
+            values[begin.value:begin.value + count.value] = [float(v) for v in buffer[:count.value]]
+            outputs.append(values)
+        return outputs
+
+
+def param_name(opt):
+    """optInTimePeriod -> timeperiod"""
+    return opt.paramName.decode()[len("optIn"):].lower()
+
+
+def output_name(name):
+    """outReal -> real, outRealUpperBand -> upperband, outMACDSignal -> macdsignal"""
+    name = name[len("out"):]
+    if name.startswith("Real") and name != "Real":
+        name = name[len("Real"):]
+    return name.lower()
+
+
+def ohlcv(close, rng, spread):
+    """Builds open/high/low/volume around a close series"""
+    open_ = [prev * (1 + rng.gauss(0, spread / 4)) for prev in [close[0]] + close[:-1]]
+    high = [max(o, c) * (1 + abs(rng.gauss(0, spread))) for o, c in zip(open_, close)]
+    low = [min(o, c) * (1 - abs(rng.gauss(0, spread))) for o, c in zip(open_, close)]
+    volume = [float(round(rng.uniform(1e4, 1e5))) for _ in close]
+    periods = [float(rng.randint(2, 29)) for _ in close]
+    return {"open": open_, "high": high, "low": low, "close": close, "volume": volume, "periods": periods}
+
+
+def student_t(rng, dof):
+    return rng.gauss(0, 1) / math.sqrt(sum(rng.gauss(0, 1) ** 2 for _ in range(dof)) / dof)
+
+
+def cumulative(start, returns):
+    values, level = [], start
+    for r in returns:
+        level *= 1 + r
+        values.append(level)
+    return values
+
+
+def datasets(size):
+    """Deterministic datasets covering trending, ranging and degenerate markets"""
+    rng = random.Random(42)
+
+    random_walk = cumulative(100, [rng.gauss(0.0005, 0.02) for _ in range(size)])
+    trending = [50 + 0.2 * t + rng.gauss(0, 0.5) for t in range(size)]
+    ranging = [100 + 10 * math.sin(t / 15) + rng.gauss(0, 1) for t in range(size)]
+    # Flat stretches exercise zero-range and zero-variance branches
+    levels = [round(100 + rng.gauss(0, 2), 1) for _ in range(size // 25 + 1)]
+    flat = [levels[t // 25] for t in range(size)]
+    volatile = cumulative(20, [student_t(rng, 3) * 0.04 for _ in range(size)])
+
+    return {
+        "random_walk": ohlcv(random_walk, rng, 0.01),
+        "trending": ohlcv(trending, rng, 0.005),
+        "ranging": ohlcv(ranging, rng, 0.01),
+        "flat": ohlcv(flat, rng, 0.0),
+        "volatile": ohlcv(volatile, rng, 0.03),
+    }
+
+
+def parameter_sets(function):
+    """Defaults plus shorter and longer periods, and every MA type where one is taken"""
+    defaults = dict(function.parameters)
+    sets = [defaults]
+
+    periods = [name for name, value in defaults.items() if "period" in name and isinstance(value, int)]
+    if periods:
+        for scale in (0.5, 2.0):
+            params = dict(defaults)
+            for name in periods:
+                params[name] = max(2, int(defaults[name] * scale))
+            if params != defaults:
+                sets.append(params)
+
+    if "matype" in defaults:
+        for matype in range(1, 9):
+            sets.append(dict(defaults, matype=matype))
+    return sets
+
+
+def fmt(value):
+    return "" if math.isnan(value) else repr(float(value))
+
+
+def write_dataset(name, data):
+    with open(os.path.join(ROOT, "data", name + ".csv"), "w") as f:
+        f.write(",".join(COLUMNS) + "\n")
+        for row in zip(*(data[c] for c in COLUMNS)):
+            f.write(",".join(fmt(v) for v in row) + "\n")
+
+
+def write_case(path, name, params, output_names, outputs):
+    with open(path, "w") as f:
+        f.write("# function=" + name + "".join(" %s=%s" % (k, v) for k, v in params.items()) + "\n")
+        f.write(",".join(output_names) + "\n")
+        for row in zip(*outputs):
+            f.write(",".join(fmt(v) for v in row) + "\n")
+
+
+def default_library():
+    return os.environ.get("TA_LIBRARY") or ctypes.util.find_library("ta_lib") or ctypes.util.find_library("ta-lib")
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[1])
+    parser.add_argument("--library", default=default_library(), help="path to the TA-Lib shared library")
+    parser.add_argument("--size", type=int, default=300, help="bars per dataset")
+    parser.add_argument("--function", nargs="*", help="only these functions (default: all)")
+    args = parser.parse_args()
+    if not args.library:
+        print("TA-Lib not found: pass --library or set TA_LIBRARY")
+        sys.exit(1)
+
+    talib = TaLib(args.library)
+    functions = [talib.function(name) for name in args.function or talib.functions()]
+    os.makedirs(os.path.join(ROOT, "data"), exist_ok=True)
+
+    for dataset, data in datasets(args.size).items():
+        write_dataset(dataset, data)
+        directory = os.path.join(ROOT, "golden", dataset)
+        os.makedirs(directory, exist_ok=True)
+
+        cases = 0
+        for function in functions:
+            for index, params in enumerate(parameter_sets(function)):
+                outputs = function(data, params)
+                if outputs is None:
+                    print("%s: TA-Lib rejects %s %s" % (dataset, function.name, params))
+                    continue
+                path = os.path.join(directory, "%s_%d.csv" % (function.name, index))
+                write_case(path, function.name, params, function.output_names, outputs)
+                cases += 1
+        print("%s: %d functions, %d cases" % (dataset, len(functions), cases))
+
+
+if __name__ == "__main__":
+    main()
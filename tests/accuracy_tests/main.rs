@@ -0,0 +1,267 @@
+//! Accuracy tests against TA-Lib reference vectors
+//!
+//! `data/<dataset>.csv` holds the input series of a dataset (`open`, `high`, `low`,
+//! `close`, `volume`, `periods`; datasets may omit columns). Each file in
+//! `golden/<dataset>/` holds the output of one TA-Lib call on that dataset:
+//!
+//! ```text
+//! # function=MACD fastperiod=12 slowperiod=26 signalperiod=9
+//! macd,macdsignal,macdhist
+//! ,,
+//! -3.9159306857359724,-4.567112902253367,0.6511822165173946
+//! ```
+//!
+//! Empty fields are NaN. Every case is run through the function registry, and must
+//! reproduce TA-Lib's warm-up period exactly and its values within [`TOLERANCE`]. Cases
+//! whose function is not registered, or whose inputs the dataset lacks, are reported and
+//! skipped. Cases covered by [`KNOWN_DIFFERENCES`] must match within the allowance of their
+//! entry, and an entry that no case needs any more fails the test until it is removed.
+//!
+//! The vectors are written by `generate_golden.py`; see the README next to this file.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ta_rust::common::registry::Registry;
+
+/// Relative tolerance of every value, applied as `|actual - expected| <= tol * max(1, |expected|)`
+const TOLERANCE: f64 = 1e-9;
+
+/// How the cases of a [`KnownDifference`] may deviate from TA-Lib
+enum Allowance {
+    /// Each output starts at its own lookback in the registry, where TA-Lib starts every
+    /// output at the latest of them; from there on, values match within [`TOLERANCE`]
+    OwnLookbacks,
+    /// Values match within this relative tolerance instead of [`TOLERANCE`]
+    Tolerance(f64),
+}
+
+/// A documented difference from TA-Lib
+struct KnownDifference {
+    /// A function name for all its cases, a file name for that case in every dataset, or
+    /// `dataset/file` for one case
+    cases: &'static [&'static str],
+    /// Why the results differ
+    cause: &'static str,
+    allowance: Allowance,
+}
+
+/// Cases that differ from TA-Lib, each within a stated allowance. A case that fails even
+/// with the allowance fails the test, and so does an entry that no failing case needs.
+const KNOWN_DIFFERENCES: &[KnownDifference] = &[
+    KnownDifference {
+        cases: &["MACD", "MACDEXT", "MACDFIX", "STOCH", "STOCHF", "STOCHRSI"],
+        cause: "the MACD and %K lines are given before their signal and %D averages have warmed up",
+        allowance: Allowance::OwnLookbacks,
+    },
+    KnownDifference {
+        cases: &["ranging/STDDEV_1.csv", "trending/STDDEV_1.csv"],
+        cause: "TA-Lib's running sum of squares loses precision on windows of tiny variance",
+        allowance: Allowance::Tolerance(1e-8),
+    },
+];
+
+/// Entry of [`KNOWN_DIFFERENCES`] covering a case, with its index
+fn known_difference(label: &str, function: &str) -> Option<(usize, &'static KnownDifference)> {
+    let file = label.split_once('/').map_or(label, |(_, file)| file);
+    KNOWN_DIFFERENCES
+        .iter()
+        .enumerate()
+        .find(|(_, entry)| entry.cases.iter().any(|&case| case == function || case == file || case == label))
+}
+
+/// Input column used for a registry input name, following TA-Lib's abstract interface
+fn column_for(input: &str) -> &str {
+    match input {
+        "real" => "close",
+        "real0" => "high",
+        "real1" => "low",
+        other => other,
+    }
+}
+
+struct Case {
+    file: PathBuf,
+    function: String,
+    params: Vec<(String, f64)>,
+    outputs: Vec<Vec<f64>>,
+}
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/accuracy_tests")
+}
+
+fn parse_value(field: &str, file: &Path) -> f64 {
+    let field = field.trim();
+    if field.is_empty() {
+        return f64::NAN;
+    }
+    field.parse().unwrap_or_else(|_| panic!("{}: cannot parse '{}'", file.display(), field))
+}
+
+fn read_dataset(file: &Path) -> HashMap<String, Vec<f64>> {
+    let text = fs::read_to_string(file).unwrap();
+    let mut lines = text.lines();
+    let header: Vec<&str> = lines.next().expect("empty dataset").split(',').collect();
+    let mut columns = vec![Vec::new(); header.len()];
+    for line in lines {
+        for (column, field) in columns.iter_mut().zip(line.split(',')) {
+            column.push(parse_value(field, file));
+        }
+    }
+    header.into_iter().map(str::to_string).zip(columns).collect()
+}
+
+fn read_case(file: &Path) -> Case {
+    let text = fs::read_to_string(file).unwrap();
+    let mut lines = text.lines();
+    let spec = lines
+        .next()
+        .and_then(|l| l.strip_prefix("# "))
+        .unwrap_or_else(|| panic!("{}: missing '# function=...' line", file.display()));
+
+    let mut function = String::new();
+    let mut params = Vec::new();
+    for pair in spec.split_whitespace() {
+        let (key, value) = pair.split_once('=').unwrap();
+        if key == "function" {
+            function = value.to_string();
+        } else {
+            params.push((key.to_string(), parse_value(value, file)));
+        }
+    }
+
+    let width = lines.next().expect("missing header").split(',').count();
+    let mut outputs = vec![Vec::new(); width];
+    for line in lines {
+        for (output, field) in outputs.iter_mut().zip(line.split(',')) {
+            output.push(parse_value(field, file));
+        }
+    }
+    Case { file: file.to_path_buf(), function, params, outputs }
+}
+
+/// Runs one case under an allowance, returning a description of the first mismatch
+fn check(case: &Case, dataset: &HashMap<String, Vec<f64>>, allowance: Option<&Allowance>) -> Result<(), String> {
+    let info = Registry::get(&case.function).expect("checked by caller");
+    let inputs: Vec<&[f64]> = info.inputs.iter().map(|&name| dataset[column_for(name)].as_slice()).collect();
+    let params: Vec<(&str, f64)> = case.params.iter().map(|(name, value)| (name.as_str(), *value)).collect();
+
+    let actual = info.call_named(&inputs, &params).map_err(|e| format!("returned an error: {}", e))?;
+    if actual.len() != case.outputs.len() {
+        return Err(format!("{} outputs, TA-Lib has {}", actual.len(), case.outputs.len()));
+    }
+
+    let tol = match allowance {
+        Some(Allowance::Tolerance(tol)) => *tol,
+        _ => TOLERANCE,
+    };
+    // Bars before TA-Lib's start where an output may already have values
+    let early_starts = match allowance {
+        Some(Allowance::OwnLookbacks) => {
+            let positional: Vec<f64> = info
+                .params
+                .iter()
+                .map(|p| params.iter().find(|(name, _)| name.eq_ignore_ascii_case(p.name)).map_or(p.default, |&(_, v)| v))
+                .collect();
+            info.output_lookbacks(&positional).map_err(|e| format!("lookbacks: {}", e))?
+        }
+        _ => vec![usize::MAX; actual.len()],
+    };
+
+    for (k, (actual, expected)) in actual.iter().zip(&case.outputs).enumerate() {
+        let name = info.outputs.get(k).copied().unwrap_or("?");
+        if actual.len() != expected.len() {
+            return Err(format!("{}: length {}, TA-Lib has {}", name, actual.len(), expected.len()));
+        }
+        let talib_start = expected.iter().position(|v| !v.is_nan()).unwrap_or(expected.len());
+        for (i, (&a, &e)) in actual.iter().zip(expected).enumerate() {
+            let matches = if e.is_nan() {
+                a.is_nan() || (i >= early_starts[k] && i < talib_start)
+            } else {
+                (a - e).abs() <= tol * e.abs().max(1.0)
+            };
+            if !matches {
+                return Err(format!("{}[{}] = {}, TA-Lib has {} (tolerance {:e})", name, i, a, e, tol));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn golden_vectors_match_talib() {
+    let mut datasets: Vec<PathBuf> = fs::read_dir(root().join("data"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "csv"))
+        .collect();
+    datasets.sort();
+
+    let (mut passed, mut skipped, mut failures, mut known) = (0, Vec::new(), Vec::new(), Vec::new());
+    let mut needed = HashSet::new();
+    for dataset_file in &datasets {
+        let name = dataset_file.file_stem().unwrap().to_string_lossy().to_string();
+        let dataset = read_dataset(dataset_file);
+        let Ok(entries) = fs::read_dir(root().join("golden").join(&name)) else { continue };
+        let mut files: Vec<PathBuf> = entries.map(|entry| entry.unwrap().path()).collect();
+        files.sort();
+
+        for file in files {
+            let case = read_case(&file);
+            let label = format!("{}/{}", name, file.file_name().unwrap().to_string_lossy());
+            match Registry::get(&case.function) {
+                None => skipped.push(format!("{}: {} is not implemented", label, case.function)),
+                Some(info) if info.inputs.iter().any(|&i| !dataset.contains_key(column_for(i))) => {
+                    skipped.push(format!("{}: dataset lacks inputs {:?}", label, info.inputs))
+                }
+                Some(_) => match (check(&case, &dataset, None), known_difference(&label, &case.function)) {
+                    (Ok(()), _) => passed += 1,
+                    (Err(message), Some((index, entry))) => {
+                        needed.insert(index);
+                        match check(&case, &dataset, Some(&entry.allowance)) {
+                            Ok(()) => known.push(format!("{}: {} ({})", label, message, entry.cause)),
+                            Err(message) => failures.push(format!("{} ({}), beyond its allowance: {}", label, case.file.display(), message)),
+                        }
+                    }
+                    (Err(message), None) => failures.push(format!("{} ({}): {}", label, case.file.display(), message)),
+                },
+            }
+        }
+    }
+    for (index, entry) in KNOWN_DIFFERENCES.iter().enumerate() {
+        if !needed.contains(&index) {
+            failures.push(format!("KNOWN_DIFFERENCES entry {:?} is no longer needed", entry.cases));
+        }
+    }
+
+    println!("{} golden cases passed, {} known differences, {} skipped", passed, known.len(), skipped.len());
+    for message in &known {
+        println!("  known difference {}", message);
+    }
+    for message in &skipped {
+        println!("  skipped {}", message);
+    }
+    assert!(passed > 0, "no golden vectors found under {}", root().display());
+    assert!(failures.is_empty(), "{} golden cases failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+#[test]
+fn golden_files_are_well_formed() {
+    for dir in fs::read_dir(root().join("golden")).unwrap() {
+        let dir = dir.unwrap().path();
+        let dataset = read_dataset(&root().join("data").join(dir.file_name().unwrap()).with_extension("csv"));
+        let len = dataset.values().next().map_or(0, Vec::len);
+        for file in fs::read_dir(&dir).unwrap() {
+            let case = read_case(&file.unwrap().path());
+            assert!(
+                case.function.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+                "{}: unexpected function name '{}'",
+                case.file.display(),
+                case.function
+            );
+            assert!(case.outputs.iter().all(|o| o.len() == len), "{}: expected {} rows", case.file.display(), len);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Property-based tests over random market data
+//!
+//! Random OHLCV bars are generated as a positive random walk with consistent highs and
+//! lows, and every registered function is checked for shape invariants while the
+//! bounded indicators are checked for their ranges.
+
+use proptest::prelude::*;
+use ta_rust::common::registry::Registry;
+use ta_rust::momentum::{rsi, willr};
+use ta_rust::overlap::bbands;
+
+const TOLERANCE: f64 = 1e-9;
+
+/// Functions whose first value depends on the data by design
+const DATA_DEPENDENT_WARMUP: &[&str] = &[
+    // NaN while a window shows no mean reversion
+    "HALFLIFE",
+    // The line starts at the first confirmed pivot
+    "ZIGZAG",
+    // Short periods in the period input start the average early
+    "MAVP",
+    // Windows sized by the measured dominant cycle
+    "RSICYCLE",
+    "STOCHCYCLE",
+];
+
+/// Functions with index outputs, which hold 0 rather than NaN during the warm-up
+const INDEX_OUTPUTS: &[&str] = &["MAXINDEX", "MININDEX", "MINMAXINDEX"];
+
+#[derive(Debug, Clone)]
+struct Bars {
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    periods: Vec<f64>,
+}
+
+impl Bars {
+    fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    /// Input series for a registry input name
+    fn input(&self, name: &str) -> &[f64] {
+        match name {
+            "open" => &self.open,
+            "high" | "real0" => &self.high,
+            "low" | "real1" => &self.low,
+            "volume" => &self.volume,
+            "periods" => &self.periods,
+            _ => &self.close,
+        }
+    }
+}
+
+/// Bars from per-bar (return, open gap, upper wick, lower wick, volume) tuples
+fn bars(min_len: usize, max_len: usize) -> impl Strategy<Value = Bars> {
+    prop::collection::vec((-0.05f64..0.05, -0.01f64..0.01, 0.0f64..0.02, 0.0f64..0.02, 1.0f64..1e6), min_len..max_len)
+        .prop_map(|steps| {
+            let mut price = 100.0;
+            let mut bars = Bars {
+                open: Vec::new(),
+                high: Vec::new(),
+                low: Vec::new(),
+                close: Vec::new(),
+                volume: Vec::new(),
+                periods: Vec::new(),
+            };
+            for (i, (ret, gap, up, down, volume)) in steps.into_iter().enumerate() {
+                let open = price * (1.0 + gap);
+                price *= 1.0 + ret;
+                bars.open.push(open);
+                bars.high.push(open.max(price) * (1.0 + up));
+                bars.low.push(open.min(price) * (1.0 - down));
+                bars.close.push(price);
+                bars.volume.push(volume.round());
+                bars.periods.push((2 + i % 20) as f64);
+            }
+            bars
+        })
+}
+
+fn leading_nans(values: &[f64]) -> usize {
+    values.iter().take_while(|v| v.is_nan()).count()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn rsi_is_bounded(bars in bars(20, 300), period in 2usize..30) {
+        prop_assume!(period < bars.len());
+        for value in rsi(&bars.close, period).unwrap().into_iter().filter(|v| !v.is_nan()) {
+            prop_assert!((-TOLERANCE..=100.0 + TOLERANCE).contains(&value), "RSI {}", value);
+        }
+    }
+
+    #[test]
+    fn willr_is_bounded(bars in bars(20, 300), period in 2usize..30) {
+        prop_assume!(period < bars.len());
+        for value in willr(&bars.high, &bars.low, &bars.close, period).unwrap().into_iter().filter(|v| !v.is_nan()) {
+            prop_assert!((-100.0 - TOLERANCE..=TOLERANCE).contains(&value), "WILLR {}", value);
+        }
+    }
+
+    #[test]
+    fn bbands_are_ordered(bars in bars(20, 300), period in 2usize..30, dev in 0.0f64..4.0) {
+        prop_assume!(period < bars.len());
+        let bands = bbands(&bars.close, period, dev).unwrap();
+        for i in 0..bars.len() {
+            if bands.middle[i].is_nan() {
+                prop_assert!(bands.upper[i].is_nan() && bands.lower[i].is_nan());
+                continue;
+            }
+            prop_assert!(bands.upper[i] >= bands.middle[i] - TOLERANCE);
+            prop_assert!(bands.middle[i] >= bands.lower[i] - TOLERANCE);
+        }
+    }
+
+    /// Every output has one value per bar, and the warm-up period depends on the
+    /// parameters only, not on the values of the data, and matches the lookback
+    #[test]
+    fn registry_outputs_are_bar_aligned(a in bars(300, 301), b in bars(300, 301)) {
+        for info in Registry::functions() {
+            let lookbacks = info.output_lookbacks(&[]).unwrap();
+            let run = |bars: &Bars| {
+                let inputs: Vec<&[f64]> = info.inputs.iter().map(|&name| bars.input(name)).collect();
+                info.call_default(&inputs)
+            };
+            // Inputs outside a function's domain, such as prices for ACOS, give an error or
+            // NaN throughout; both are left to the unit tests
+            let (Ok(first), Ok(second)) = (run(&a), run(&b)) else { continue };
+            if first.iter().chain(&second).any(|output| output.iter().all(|v| v.is_nan())) {
+                continue;
+            }
+
+            prop_assert_eq!(first.len(), info.outputs.len(), "{}", info.name);
+            for (k, (x, y)) in first.iter().zip(&second).enumerate() {
+                prop_assert_eq!(x.len(), a.len(), "{} output {}", info.name, k);
+                if DATA_DEPENDENT_WARMUP.contains(&info.name) || INDEX_OUTPUTS.contains(&info.name) {
+                    continue;
+                }
+                prop_assert_eq!(
+                    leading_nans(x), leading_nans(y),
+                    "{} output {} warm-up depends on the data", info.name, k
+                );
+                prop_assert_eq!(leading_nans(x), lookbacks[k], "{} output {} lookback", info.name, k);
+            }
+        }
+    }
+
+    /// Errors, not panics, on arbitrary lengths and parameters
+    #[test]
+    fn registry_never_panics(len in 0usize..40, params in prop::collection::vec(-5.0f64..50.0, 0..4)) {
+        let data: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64).sin()).collect();
+        for info in Registry::functions() {
+            let inputs: Vec<&[f64]> = info.inputs.iter().map(|_| data.as_slice()).collect();
+            let params = &params[..params.len().min(info.params.len())];
+            let _ = info.call(&inputs, params);
+        }
+    }
+}